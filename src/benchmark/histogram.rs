@@ -0,0 +1,182 @@
+//! Histogram of latencies which takes the same memory for any number of
+//! requests, and can be merged between the clients.
+
+use std::time::Duration;
+
+/// Values under this one have a bucket each. The bigger ones are split in 32
+/// buckets per power of two, so a percentile is off by less than 3.2%.
+const EXACT_LIMIT: u64 = 64;
+const SUB_BUCKETS: u64 = 32;
+const BUCKETS: usize = 1920;
+
+/// Latencies in microseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    counts: Vec<u64>,
+    total: u64,
+    sum: u128,
+    min: u64,
+    max: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Histogram {
+            counts: vec![0; BUCKETS],
+            total: 0,
+            sum: 0,
+            min: u64::MAX,
+            max: 0,
+        }
+    }
+
+    pub fn record(&mut self, latency: Duration) {
+        let micros = latency.as_micros().min(u64::MAX as u128) as u64;
+        self.counts[index(micros)] += 1;
+        self.total += 1;
+        self.sum += micros as u128;
+        self.min = self.min.min(micros);
+        self.max = self.max.max(micros);
+    }
+
+    pub fn merge(&mut self, other: &Histogram) {
+        for (count, other_count) in self.counts.iter_mut().zip(other.counts.iter()) {
+            *count += other_count;
+        }
+        self.total += other.total;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.total
+    }
+
+    pub fn mean(&self) -> u64 {
+        match self.total {
+            0 => 0,
+            total => (self.sum / total as u128) as u64,
+        }
+    }
+
+    pub fn min(&self) -> u64 {
+        match self.total {
+            0 => 0,
+            _ => self.min,
+        }
+    }
+
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Returns the latency under which are the `percentile` percent of the
+    /// requests: the upper limit of its bucket.
+    pub fn percentile(&self, percentile: f64) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+        let rank = ((percentile / 100.0 * self.total as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return upper_limit(index).clamp(self.min, self.max);
+            }
+        }
+        self.max
+    }
+
+    /// Percentage of the requests with a latency up to `limit`.
+    pub fn percent_up_to(&self, limit: u64) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let seen: u64 = self
+            .counts
+            .iter()
+            .enumerate()
+            .take_while(|(index, _)| upper_limit(*index) <= limit)
+            .map(|(_, count)| count)
+            .sum();
+        seen as f64 * 100.0 / self.total as f64
+    }
+}
+
+fn index(value: u64) -> usize {
+    if value < EXACT_LIMIT {
+        return value as usize;
+    }
+    let bits = 64 - value.leading_zeros() as u64;
+    let shift = bits - 6;
+    (EXACT_LIMIT + (shift - 1) * SUB_BUCKETS + ((value >> shift) - SUB_BUCKETS)) as usize
+}
+
+/// Biggest value counted in the bucket.
+fn upper_limit(index: usize) -> u64 {
+    let index = index as u64;
+    if index < EXACT_LIMIT {
+        return index;
+    }
+    let offset = index - EXACT_LIMIT;
+    let shift = offset / SUB_BUCKETS + 1;
+    let lower = (SUB_BUCKETS + offset % SUB_BUCKETS) << shift;
+    lower.saturating_add((1 << shift) - 1)
+}
+
+#[cfg(test)]
+mod test_histogram {
+    use super::*;
+
+    #[test]
+    fn test_01_buckets_are_contiguous() {
+        assert_eq!(index(63), 63);
+        assert_eq!(index(64), 64);
+        assert_eq!(index(127), 95);
+        assert_eq!(index(128), 96);
+        assert_eq!(index(u64::MAX), BUCKETS - 1);
+        for value in [0, 63, 64, 65, 1000, 123_456, 1 << 40] {
+            assert!(upper_limit(index(value)) >= value);
+            assert!(index(value) == 0 || upper_limit(index(value) - 1) < value);
+        }
+    }
+
+    #[test]
+    fn test_02_percentiles_are_close_to_the_values() {
+        let mut histogram = Histogram::new();
+        for micros in 1..=1000 {
+            histogram.record(Duration::from_micros(micros));
+        }
+        assert_eq!(histogram.count(), 1000);
+        assert_eq!(histogram.mean(), 500);
+        assert_eq!(histogram.min(), 1);
+        assert_eq!(histogram.max(), 1000);
+        for (percentile, expected) in [(50.0, 500.0), (95.0, 950.0), (99.0, 990.0)] {
+            let value = histogram.percentile(percentile) as f64;
+            assert!(value >= expected && value <= expected * 1.032);
+        }
+        assert_eq!(histogram.percentile(100.0), 1000);
+        assert_eq!(histogram.percent_up_to(63), 6.3);
+    }
+
+    #[test]
+    fn test_03_merged_histograms_count_both() {
+        let mut first = Histogram::new();
+        first.record(Duration::from_micros(10));
+        let mut second = Histogram::new();
+        second.record(Duration::from_millis(2));
+        first.merge(&second);
+        assert_eq!(first.count(), 2);
+        assert_eq!(first.min(), 10);
+        assert_eq!(first.max(), 2000);
+        assert_eq!(first.percentile(50.0), 10);
+        assert_eq!(Histogram::new().percentile(99.0), 0);
+    }
+}
@@ -0,0 +1,542 @@
+//! Load test of a server running in this machine, as redis-benchmark. See
+//! the `benchmark` binary.
+//!
+//! Each test sends its command from many clients in parallel, each one
+//! writing a pipeline of commands and then reading their replies, and
+//! reports the requests per second and the percentiles of the latency.
+
+pub mod histogram;
+
+use crate::client::{reply, Connection, Reply};
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use histogram::Histogram;
+use rand::Rng;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Only servers of this machine are tested.
+const HOST: &str = "127.0.0.1";
+
+/// Commands tested, in the order they run.
+pub const TESTS: [&str; 9] = [
+    "set", "get", "incr", "lpush", "lpop", "sadd", "spop", "publish", "mset",
+];
+
+/// Keys written by each MSET.
+const MSET_KEYS: usize = 10;
+
+/// Limits of the latency distribution, in microseconds.
+const DISTRIBUTION_LIMITS: [u64; 12] = [
+    100, 250, 500, 1000, 2000, 4000, 8000, 16000, 32000, 64000, 128000, 256000,
+];
+
+pub const USAGE: &str = "\
+benchmark [options]                   tests the server listening in 127.0.0.1
+  -p <port>       server port (default: 6379)
+  -c <clients>    parallel connections (default: 50)
+  -n <requests>   requests of each test (default: 100000)
+  -P <pipeline>   commands sent by a client before reading their replies (default: 1)
+  -r <keyspace>   random keys, from 0 to keyspace - 1 (default: the same key)
+  -d <size>       bytes of the values (default: 3)
+  -t <tests>      comma separated list of set,get,incr,lpush,lpop,sadd,spop,publish,mset
+  --csv <file>    also writes the results in CSV
+  -q              prints a line for each test
+  --help          shows this message";
+
+pub const CSV_HEADER: &str = "\"test\",\"rps\",\"avg_latency_ms\",\"min_latency_ms\",\
+\"p50_latency_ms\",\"p95_latency_ms\",\"p99_latency_ms\",\"max_latency_ms\",\"errors\"";
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub port: u16,
+    pub clients: usize,
+    pub requests: usize,
+    pub pipeline: usize,
+    /// Number of random keys, or [None] to use always the same one.
+    pub keyspace: Option<u64>,
+    pub data_size: usize,
+    pub tests: Vec<&'static str>,
+    pub csv: Option<String>,
+    pub quiet: bool,
+    pub help: bool,
+}
+
+impl Options {
+    /// Parses the arguments, without the name of the binary.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * An option is unknown or misses its value.
+    /// * A number is not valid, or is zero where it can not be.
+    /// * A test is unknown.
+    pub fn parse(args: &[String]) -> Result<Self, ErrorStruct> {
+        let mut options = Options {
+            port: 6379,
+            clients: 50,
+            requests: 100_000,
+            pipeline: 1,
+            keyspace: None,
+            data_size: 3,
+            tests: TESTS.to_vec(),
+            csv: None,
+            quiet: false,
+            help: false,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(usage_error);
+            match arg.as_str() {
+                "-p" => options.port = number(value()?)?,
+                "-c" => options.clients = positive(value()?)?,
+                "-n" => options.requests = positive(value()?)?,
+                "-P" => options.pipeline = positive(value()?)?,
+                "-r" => options.keyspace = Some(positive(value()?)? as u64),
+                "-d" => options.data_size = number(value()?)?,
+                "-t" => {
+                    options.tests = value()?
+                        .split(',')
+                        .map(|test| {
+                            TESTS
+                                .iter()
+                                .find(|known| known.eq_ignore_ascii_case(test.trim()))
+                                .copied()
+                                .ok_or_else(usage_error)
+                        })
+                        .collect::<Result<_, _>>()?
+                }
+                "--csv" => options.csv = Some(value()?.to_string()),
+                "-q" => options.quiet = true,
+                "--help" => options.help = true,
+                _ => return Err(usage_error()),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Results of a test.
+pub struct Report {
+    pub test: &'static str,
+    pub requests: usize,
+    pub errors: usize,
+    pub elapsed: Duration,
+    pub clients: usize,
+    pub data_size: usize,
+    pub pipeline: usize,
+    pub latencies: Histogram,
+}
+
+impl Report {
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Returns the line of the test with the requests per second and the
+    /// median latency, as printed with `-q`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {:.2} requests per second, p50={} msec",
+            self.test.to_uppercase(),
+            self.requests_per_second(),
+            millis(self.latencies.percentile(50.0))
+        )
+    }
+
+    /// Returns the line of CSV of the test. See [CSV_HEADER].
+    pub fn csv(&self) -> String {
+        let latencies = &self.latencies;
+        [
+            self.test.to_uppercase(),
+            format!("{:.2}", self.requests_per_second()),
+            millis(latencies.mean()),
+            millis(latencies.min()),
+            millis(latencies.percentile(50.0)),
+            millis(latencies.percentile(95.0)),
+            millis(latencies.percentile(99.0)),
+            millis(latencies.max()),
+            self.errors.to_string(),
+        ]
+        .iter()
+        .map(|field| format!("\"{}\"", field))
+        .collect::<Vec<String>>()
+        .join(",")
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let latencies = &self.latencies;
+        writeln!(f, "====== {} ======", self.test.to_uppercase())?;
+        writeln!(
+            f,
+            "  {} requests completed in {:.2} seconds",
+            self.requests,
+            self.elapsed.as_secs_f64()
+        )?;
+        writeln!(f, "  {} parallel clients", self.clients)?;
+        writeln!(f, "  {} bytes payload", self.data_size)?;
+        writeln!(f, "  pipeline {}", self.pipeline)?;
+        if self.errors > 0 {
+            writeln!(f, "  {} errors", self.errors)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "  {:.2} requests per second", self.requests_per_second())?;
+        writeln!(
+            f,
+            "  latency (msec): avg {} min {} p50 {} p95 {} p99 {} max {}",
+            millis(latencies.mean()),
+            millis(latencies.min()),
+            millis(latencies.percentile(50.0)),
+            millis(latencies.percentile(95.0)),
+            millis(latencies.percentile(99.0)),
+            millis(latencies.max())
+        )?;
+        writeln!(f, "  latency distribution (msec):")?;
+        for limit in DISTRIBUTION_LIMITS.iter() {
+            writeln!(
+                f,
+                "    <= {:>7}: {:6.2}%",
+                millis(*limit),
+                latencies.percent_up_to(*limit)
+            )?;
+            if *limit >= latencies.max() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs the tests of the arguments, without the name of the binary, writing
+/// their reports to `out`.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The arguments are wrong.
+/// * The server can not be reached, or closes a connection.
+/// * The CSV file can not be written.
+pub fn run<W: Write>(args: &[String], out: &mut W) -> Result<(), ErrorStruct> {
+    let options = Options::parse(args)?;
+    if options.help {
+        return print(out, USAGE);
+    }
+    let mut reports = Vec::new();
+    for test in options.tests.iter() {
+        let report = run_test(&options, test)?;
+        if options.quiet {
+            print(out, &report.summary())?;
+        } else {
+            print(out, &report.to_string())?;
+        }
+        reports.push(report);
+    }
+    if let Some(path) = options.csv.as_ref() {
+        write_csv(path, &reports)?;
+    }
+    Ok(())
+}
+
+/// Runs a test: the requests are shared between the clients, which start
+/// together once all of them are connected.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The server can not be reached, or closes a connection.
+pub fn run_test(options: &Options, test: &'static str) -> Result<Report, ErrorStruct> {
+    let clients = options.clients.min(options.requests);
+    let connections = (0..clients)
+        .map(|_| Connection::connect(HOST, options.port))
+        .collect::<Result<Vec<Connection>, ErrorStruct>>()?;
+    let start = Arc::new(Barrier::new(clients + 1));
+    let mut handles = Vec::with_capacity(clients);
+    for (client, mut connection) in connections.into_iter().enumerate() {
+        let requests =
+            options.requests / clients + usize::from(client < options.requests % clients);
+        let pipeline = options.pipeline;
+        let keyspace = options.keyspace;
+        let value = "x".repeat(options.data_size);
+        let start = Arc::clone(&start);
+        handles.push(thread::spawn(move || {
+            start.wait();
+            let mut rng = rand::thread_rng();
+            let mut latencies = Histogram::new();
+            let mut errors = 0;
+            let mut remaining = requests;
+            while remaining > 0 {
+                let batch = remaining.min(pipeline);
+                let mut bytes = Vec::new();
+                for _ in 0..batch {
+                    let command = command(test, keyspace, &value, &mut rng);
+                    bytes.extend(reply::encode_command(&command));
+                }
+                let sent = Instant::now();
+                connection.send_encoded(&bytes)?;
+                for _ in 0..batch {
+                    if let Reply::Error(_) = connection.read_reply()? {
+                        errors += 1;
+                    }
+                    latencies.record(sent.elapsed());
+                }
+                remaining -= batch;
+            }
+            Ok((latencies, errors))
+        }));
+    }
+    start.wait();
+    let started = Instant::now();
+    let mut latencies = Histogram::new();
+    let mut errors = 0;
+    let mut failure = None;
+    for handle in handles {
+        match handle.join() {
+            Ok(Ok((client_latencies, client_errors))) => {
+                latencies.merge(&client_latencies);
+                errors += client_errors;
+            }
+            Ok(Err(error)) => failure = Some(error),
+            Err(_) => {
+                failure = Some(ErrorStruct::from(redis_messages::thread_panic(
+                    "benchmark client",
+                )))
+            }
+        }
+    }
+    if let Some(error) = failure {
+        return Err(error);
+    }
+    Ok(Report {
+        test,
+        requests: options.requests,
+        errors,
+        elapsed: started.elapsed(),
+        clients,
+        data_size: options.data_size,
+        pipeline: options.pipeline,
+        latencies,
+    })
+}
+
+/// Returns the command of the test. The `__rand_int__` of the keys is
+/// replaced by a random number below the keyspace, if there is one.
+pub fn command<R: Rng>(test: &str, keyspace: Option<u64>, value: &str, rng: &mut R) -> Vec<String> {
+    let mut key = |prefix: &str| match keyspace {
+        Some(keyspace) => format!("{}{:012}", prefix, rng.gen_range(0..keyspace)),
+        None => format!("{}__rand_int__", prefix),
+    };
+    match test {
+        "set" => vec!["SET".into(), key("key:"), value.into()],
+        "get" => vec!["GET".into(), key("key:")],
+        "incr" => vec!["INCR".into(), key("counter:")],
+        "lpush" => vec!["LPUSH".into(), "mylist".into(), value.into()],
+        "lpop" => vec!["LPOP".into(), "mylist".into()],
+        "sadd" => vec!["SADD".into(), "myset".into(), key("element:")],
+        "spop" => vec!["SPOP".into(), "myset".into()],
+        "publish" => vec!["PUBLISH".into(), "channel".into(), value.into()],
+        _ => {
+            let mut args = vec!["MSET".to_string()];
+            for _ in 0..MSET_KEYS {
+                args.push(key("key:"));
+                args.push(value.into());
+            }
+            args
+        }
+    }
+}
+
+fn write_csv(path: &str, reports: &[Report]) -> Result<(), ErrorStruct> {
+    let write_error = || ErrorStruct::from(redis_messages::file_error(path, "can not be written"));
+    let mut file = BufWriter::new(File::create(path).map_err(|_| write_error())?);
+    writeln!(file, "{}", CSV_HEADER).map_err(|_| write_error())?;
+    for report in reports.iter() {
+        writeln!(file, "{}", report.csv()).map_err(|_| write_error())?;
+    }
+    file.flush().map_err(|_| write_error())
+}
+
+/// Formats microseconds as milliseconds.
+fn millis(micros: u64) -> String {
+    format!("{:.3}", micros as f64 / 1000.0)
+}
+
+fn number<T: std::str::FromStr>(value: &str) -> Result<T, ErrorStruct> {
+    value.parse().map_err(|_| usage_error())
+}
+
+fn positive(value: &str) -> Result<usize, ErrorStruct> {
+    match number(value)? {
+        0 => Err(usage_error()),
+        number => Ok(number),
+    }
+}
+
+fn print<W: Write>(out: &mut W, text: &str) -> Result<(), ErrorStruct> {
+    writeln!(out, "{}", text)
+        .and_then(|_| out.flush())
+        .map_err(|_| {
+            ErrorStruct::from(redis_messages::file_error(
+                "standard output",
+                "can not be written",
+            ))
+        })
+}
+
+fn usage_error() -> ErrorStruct {
+    ErrorStruct::from(redis_messages::wrong_usage(USAGE))
+}
+
+#[cfg(test)]
+mod test_benchmark {
+    use super::*;
+    use crate::vec_strings;
+    use std::fs;
+    use std::io::BufReader;
+    use std::net::TcpListener;
+
+    /// Answers every command of every client with OK, or with an error to
+    /// SPOP.
+    fn fake_server() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.unwrap();
+                thread::spawn(move || {
+                    let mut writer = stream.try_clone().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    while let Ok(Reply::Array(command)) = reply::read(&mut reader) {
+                        let answer: &[u8] = match command.first() {
+                            Some(Reply::Bulk(name)) if name == b"SPOP" => b"-ERR empty\r\n\n",
+                            _ => b"+OK\r\n\n",
+                        };
+                        if writer.write_all(answer).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_01_options_are_parsed() {
+        let options = Options::parse(&vec_strings![
+            "-p", "7000", "-c", "4", "-n", "10", "-P", "16", "-r", "100", "-d", "8", "-t",
+            "SET,lpop", "-q"
+        ])
+        .unwrap();
+        assert_eq!(options.port, 7000);
+        assert_eq!(
+            (options.clients, options.requests, options.pipeline),
+            (4, 10, 16)
+        );
+        assert_eq!((options.keyspace, options.data_size), (Some(100), 8));
+        assert_eq!(options.tests, vec!["set", "lpop"]);
+        assert!(options.quiet);
+        assert_eq!(Options::parse(&[]).unwrap().tests, TESTS.to_vec());
+        for args in [
+            vec_strings!["-c", "0"],
+            vec_strings!["-t", "set,hset"],
+            vec_strings!["-n"],
+            vec_strings!["-h", "example.com"],
+        ] {
+            assert!(Options::parse(&args)
+                .unwrap_err()
+                .print_it()
+                .contains("benchmark [options]"));
+        }
+    }
+
+    #[test]
+    fn test_02_keys_are_random_in_the_keyspace() {
+        let mut rng = rand::thread_rng();
+        assert_eq!(
+            command("set", None, "xxx", &mut rng),
+            vec_strings!["SET", "key:__rand_int__", "xxx"]
+        );
+        for _ in 0..20 {
+            let command = command("get", Some(3), "", &mut rng);
+            assert!(["key:000000000000", "key:000000000001", "key:000000000002"]
+                .contains(&command[1].as_str()));
+        }
+        let mset = command("mset", Some(10), "v", &mut rng);
+        assert_eq!(mset.len(), 1 + 2 * MSET_KEYS);
+        assert_eq!(mset[0], "MSET");
+    }
+
+    #[test]
+    fn test_03_tests_report_their_results() {
+        let port = fake_server();
+        let path = "benchmark_03.csv";
+        let mut out = Vec::new();
+        run(
+            &vec_strings![
+                "-p",
+                port.to_string(),
+                "-c",
+                "3",
+                "-n",
+                "20",
+                "-P",
+                "4",
+                "-t",
+                "set,spop",
+                "-q",
+                "--csv",
+                path
+            ],
+            &mut out,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("SET: ") && lines[0].ends_with(" msec"));
+        assert!(lines[1].starts_with("SPOP: "));
+
+        let csv = fs::read_to_string(path).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], CSV_HEADER);
+        assert!(rows[1].starts_with("\"SET\",") && rows[1].ends_with(",\"0\""));
+        assert!(rows[2].starts_with("\"SPOP\",") && rows[2].ends_with(",\"20\""));
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_04_report_shows_the_latencies() {
+        let mut latencies = Histogram::new();
+        for micros in [90, 300, 1200] {
+            latencies.record(Duration::from_micros(micros));
+        }
+        let report = Report {
+            test: "get",
+            requests: 3,
+            errors: 1,
+            elapsed: Duration::from_millis(500),
+            clients: 1,
+            data_size: 3,
+            pipeline: 1,
+            latencies,
+        };
+        assert_eq!(
+            report.to_string(),
+            "====== GET ======\n  3 requests completed in 0.50 seconds\n  1 parallel clients\n  \
+             3 bytes payload\n  pipeline 1\n  1 errors\n\n  6.00 requests per second\n  \
+             latency (msec): avg 0.530 min 0.090 p50 0.303 p95 1.200 p99 1.200 max 1.200\n  \
+             latency distribution (msec):\n    <=   0.100:  33.33%\n    <=   0.250:  33.33%\n    \
+             <=   0.500:  66.67%\n    <=   1.000:  66.67%\n    <=   2.000: 100.00%\n"
+        );
+        assert_eq!(
+            report.csv(),
+            "\"GET\",\"6.00\",\"0.530\",\"0.090\",\"0.303\",\"1.200\",\"1.200\",\"1.200\",\"1\""
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use std::{env, io, process};
+
+use redis_rust::benchmark;
+
+/// ## Commands to Load Test the Server, in console:
+///
+/// * *cargo run --release --bin benchmark -q* runs every test.
+/// * *cargo run --release --bin benchmark -t set,get -c 100 -P 16 -r 100000*
+/// * *cargo run --release --bin benchmark --csv results.csv*
+///
+/// See [benchmark::USAGE] for all the options.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let stdout = io::stdout();
+    if let Err(error) = benchmark::run(&args, &mut stdout.lock()) {
+        eprintln!("{}", error.print_it());
+        process::exit(1);
+    }
+}
@@ -0,0 +1,155 @@
+//! Printing of the replies as redis-cli does: quoted and annotated with
+//! their type for a terminal, and as plain text otherwise.
+
+use crate::client::Reply;
+
+/// Formats the reply for a terminal. Nested arrays are numbered and
+/// indented under the index of their parent:
+///
+/// ```text
+/// 1) 1) "a"
+///    2) (integer) 1
+/// 2) (nil)
+/// ```
+pub fn tty(reply: &Reply) -> String {
+    let mut out = String::new();
+    write_tty(reply, "", &mut out);
+    out
+}
+
+fn write_tty(reply: &Reply, prefix: &str, out: &mut String) {
+    match reply {
+        Reply::Status(text) => out.push_str(text),
+        Reply::Error(text) => {
+            out.push_str("(error) ");
+            out.push_str(text);
+        }
+        Reply::Integer(number) => out.push_str(&format!("(integer) {}", number)),
+        Reply::Bulk(bytes) => out.push_str(&quoted(bytes)),
+        Reply::Nil => out.push_str("(nil)"),
+        Reply::Array(elements) if elements.is_empty() => out.push_str("(empty array)"),
+        Reply::Array(elements) => {
+            let width = elements.len().to_string().len();
+            let nested_prefix = format!("{}{}", prefix, " ".repeat(width + 2));
+            for (index, element) in elements.iter().enumerate() {
+                // The first element follows the index written by the parent
+                if index > 0 {
+                    out.push_str(prefix);
+                }
+                out.push_str(&format!("{:>width$}) ", index + 1, width = width));
+                write_tty(element, &nested_prefix, out);
+            }
+            return;
+        }
+    }
+    out.push('\n');
+}
+
+/// Formats the reply as plain text, as when the output is not a terminal:
+/// strings without quotes, and the elements of the arrays in separate lines.
+pub fn raw(reply: &Reply) -> String {
+    let mut out = String::new();
+    write_raw(reply, &mut out);
+    out.push('\n');
+    out
+}
+
+fn write_raw(reply: &Reply, out: &mut String) {
+    match reply {
+        Reply::Status(text) | Reply::Error(text) => out.push_str(text),
+        Reply::Integer(number) => out.push_str(&number.to_string()),
+        Reply::Bulk(bytes) => out.push_str(&String::from_utf8_lossy(bytes)),
+        Reply::Nil => {}
+        Reply::Array(elements) => {
+            for (index, element) in elements.iter().enumerate() {
+                if index > 0 {
+                    out.push('\n');
+                }
+                write_raw(element, out);
+            }
+        }
+    }
+}
+
+/// Quotes the string, escaping the quotes, backslashes and the bytes which
+/// are not printable.
+pub fn quoted(bytes: &[u8]) -> String {
+    let mut out = String::from("\"");
+    for byte in bytes.iter() {
+        match byte {
+            b'\\' => out.push_str("\\\\"),
+            b'"' => out.push_str("\\\""),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            7 => out.push_str("\\a"),
+            8 => out.push_str("\\b"),
+            b' '..=b'~' => out.push(*byte as char),
+            _ => out.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod test_format {
+    use super::*;
+
+    fn bulk(text: &str) -> Reply {
+        Reply::Bulk(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_01_simple_replies_are_annotated_with_their_type() {
+        assert_eq!(tty(&Reply::Status("OK".to_string())), "OK\n");
+        assert_eq!(
+            tty(&Reply::Error("ERR wrong".to_string())),
+            "(error) ERR wrong\n"
+        );
+        assert_eq!(tty(&Reply::Integer(-3)), "(integer) -3\n");
+        assert_eq!(tty(&Reply::Nil), "(nil)\n");
+        assert_eq!(tty(&Reply::Array(vec![])), "(empty array)\n");
+        assert_eq!(
+            tty(&Reply::Bulk(b"a \"b\"\\\n\x07\x00\xc3\xa9".to_vec())),
+            "\"a \\\"b\\\"\\\\\\n\\a\\x00\\xc3\\xa9\"\n"
+        );
+    }
+
+    #[test]
+    fn test_02_nested_arrays_are_indented_under_their_index() {
+        let mut elements: Vec<Reply> = (0..9).map(|_| Reply::Integer(0)).collect();
+        elements.push(Reply::Array(vec![
+            bulk("a"),
+            Reply::Array(vec![bulk("b"), Reply::Nil]),
+            Reply::Error("ERR inner".to_string()),
+        ]));
+        elements.push(Reply::Array(vec![]));
+        let out = tty(&Reply::Array(elements));
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], " 1) (integer) 0");
+        assert_eq!(
+            lines[9..],
+            [
+                "10) 1) \"a\"",
+                "    2) 1) \"b\"",
+                "       2) (nil)",
+                "    3) (error) ERR inner",
+                "11) (empty array)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_03_raw_output_is_plain_text() {
+        let reply = Reply::Array(vec![
+            bulk("a b"),
+            Reply::Integer(1),
+            Reply::Nil,
+            Reply::Array(vec![bulk("x"), bulk("y")]),
+        ]);
+        assert_eq!(raw(&reply), "a b\n1\n\nx\ny\n");
+        assert_eq!(raw(&Reply::Error("ERR e".to_string())), "ERR e\n");
+        assert_eq!(raw(&Reply::Nil), "\n");
+    }
+}
@@ -0,0 +1,442 @@
+//! Reading of the lines typed in the client, with the usual editing keys and
+//! a history kept in a file between sessions.
+//!
+//! | Keys                     | Action                              |
+//! |--------------------------|-------------------------------------|
+//! | Left, Right, Ctrl-B/F    | Move the cursor                     |
+//! | Home, End, Ctrl-A/E      | Go to the start or end of the line  |
+//! | Up, Down, Ctrl-P/N       | Previous or next line of history    |
+//! | Backspace, Delete        | Delete a character                  |
+//! | Ctrl-U, Ctrl-K, Ctrl-W   | Delete to the start, end, or a word |
+//! | Ctrl-L                   | Clear the screen                    |
+//! | Ctrl-C, Ctrl-D           | Quit (Ctrl-D deletes if not empty)  |
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Read, Write};
+use std::path::PathBuf;
+
+/// Lines of history loaded from the file.
+const HISTORY_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    /// A letter pressed with Ctrl, in lowercase.
+    Ctrl(char),
+    /// Any other sequence, which is ignored.
+    Unknown,
+}
+
+/// Reads the next key pressed, decoding the escape sequences of the
+/// terminal. Returns [None] at the end of the input.
+pub fn read_key<R: Read>(input: &mut R) -> io::Result<Option<Key>> {
+    let first = match read_byte(input)? {
+        Some(byte) => byte,
+        None => return Ok(None),
+    };
+    let key = match first {
+        b'\r' | b'\n' => Key::Enter,
+        127 | 8 => Key::Backspace,
+        27 => read_escape(input)?,
+        1..=26 => Key::Ctrl((b'a' + first - 1) as char),
+        0..=31 => Key::Unknown,
+        _ => {
+            let len = match first {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => 1,
+            };
+            let mut bytes = vec![first];
+            for _ in 1..len {
+                match read_byte(input)? {
+                    Some(byte) => bytes.push(byte),
+                    None => break,
+                }
+            }
+            match std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|text| text.chars().next())
+            {
+                Some(char) => Key::Char(char),
+                None => Key::Unknown,
+            }
+        }
+    };
+    Ok(Some(key))
+}
+
+/// Decodes the sequence after an escape, as `ESC [ A` or `ESC [ 3 ~`.
+fn read_escape<R: Read>(input: &mut R) -> io::Result<Key> {
+    let kind = read_byte(input)?;
+    if kind != Some(b'[') && kind != Some(b'O') {
+        return Ok(Key::Unknown);
+    }
+    Ok(match read_byte(input)? {
+        Some(b'A') => Key::Up,
+        Some(b'B') => Key::Down,
+        Some(b'C') => Key::Right,
+        Some(b'D') => Key::Left,
+        Some(b'H') => Key::Home,
+        Some(b'F') => Key::End,
+        Some(digit @ b'0'..=b'9') => {
+            if read_byte(input)? != Some(b'~') {
+                return Ok(Key::Unknown);
+            }
+            match digit {
+                b'1' | b'7' => Key::Home,
+                b'4' | b'8' => Key::End,
+                b'3' => Key::Delete,
+                _ => Key::Unknown,
+            }
+        }
+        _ => Key::Unknown,
+    })
+}
+
+fn read_byte<R: Read>(input: &mut R) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    loop {
+        match input.read(&mut byte) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(byte[0])),
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// What the editor does after a key.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// The line changed or the cursor moved, so it is shown again.
+    Refresh,
+    ClearScreen,
+    /// The line is complete.
+    Submit,
+    /// The user wants to quit.
+    Quit,
+}
+
+/// The line being edited, with the position in the history.
+pub struct LineState {
+    line: Vec<char>,
+    cursor: usize,
+    /// Line of the history shown, or its length for the new line.
+    history_index: usize,
+    /// The new line, kept while going through the history.
+    draft: Vec<char>,
+}
+
+impl LineState {
+    pub fn new(history: &[String]) -> Self {
+        LineState {
+            line: Vec::new(),
+            cursor: 0,
+            history_index: history.len(),
+            draft: Vec::new(),
+        }
+    }
+
+    pub fn line(&self) -> String {
+        self.line.iter().collect()
+    }
+
+    /// Position of the cursor, in characters.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn apply(&mut self, key: Key, history: &[String]) -> Outcome {
+        match key {
+            Key::Char(char) => {
+                self.line.insert(self.cursor, char);
+                self.cursor += 1;
+            }
+            Key::Enter => return Outcome::Submit,
+            Key::Backspace | Key::Ctrl('h') if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.line.remove(self.cursor);
+            }
+            Key::Ctrl('d') if self.line.is_empty() => return Outcome::Quit,
+            Key::Delete | Key::Ctrl('d') if self.cursor < self.line.len() => {
+                self.line.remove(self.cursor);
+            }
+            Key::Left | Key::Ctrl('b') if self.cursor > 0 => self.cursor -= 1,
+            Key::Right | Key::Ctrl('f') if self.cursor < self.line.len() => self.cursor += 1,
+            Key::Home | Key::Ctrl('a') => self.cursor = 0,
+            Key::End | Key::Ctrl('e') => self.cursor = self.line.len(),
+            Key::Up | Key::Ctrl('p') if self.history_index > 0 => {
+                self.show_history(self.history_index - 1, history)
+            }
+            Key::Down | Key::Ctrl('n') if self.history_index < history.len() => {
+                self.show_history(self.history_index + 1, history)
+            }
+            Key::Ctrl('u') => {
+                self.line.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            Key::Ctrl('k') => self.line.truncate(self.cursor),
+            Key::Ctrl('w') => {
+                let mut start = self.cursor;
+                while start > 0 && self.line[start - 1] == ' ' {
+                    start -= 1;
+                }
+                while start > 0 && self.line[start - 1] != ' ' {
+                    start -= 1;
+                }
+                self.line.drain(start..self.cursor);
+                self.cursor = start;
+            }
+            Key::Ctrl('l') => return Outcome::ClearScreen,
+            Key::Ctrl('c') => return Outcome::Quit,
+            _ => {}
+        }
+        Outcome::Refresh
+    }
+
+    fn show_history(&mut self, index: usize, history: &[String]) {
+        if self.history_index == history.len() {
+            self.draft = self.line.clone();
+        }
+        self.history_index = index;
+        self.line = match history.get(index) {
+            Some(line) => line.chars().collect(),
+            None => self.draft.clone(),
+        };
+        self.cursor = self.line.len();
+    }
+}
+
+/// Reads lines from the standard input: edited in raw mode if it is a
+/// terminal, or as they come otherwise.
+pub struct LineEditor {
+    history: Vec<String>,
+    history_path: Option<PathBuf>,
+}
+
+impl LineEditor {
+    /// Creates an editor with the history of the file, which is created
+    /// when the first line is added.
+    pub fn new(history_path: Option<PathBuf>) -> Self {
+        let mut history: Vec<String> = history_path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| text.lines().map(String::from).collect())
+            .unwrap_or_default();
+        if history.len() > HISTORY_LIMIT {
+            history.drain(..history.len() - HISTORY_LIMIT);
+        }
+        LineEditor {
+            history,
+            history_path,
+        }
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Adds the line to the history, unless it is empty or repeats the last
+    /// one.
+    pub fn add_history(&mut self, line: &str) {
+        if line.trim().is_empty() || self.history.last().map(String::as_str) == Some(line) {
+            return;
+        }
+        self.history.push(line.to_string());
+        if let Some(path) = self.history_path.as_ref() {
+            if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+    }
+
+    /// Shows the prompt and reads a line. Returns [None] at the end of the
+    /// input or when the user quits.
+    pub fn read_line(&mut self, prompt: &str) -> io::Result<Option<String>> {
+        if !terminal::is_terminal(0) {
+            let mut line = String::new();
+            return match io::stdin().lock().read_line(&mut line)? {
+                0 => Ok(None),
+                _ => Ok(Some(line.trim_end_matches(&['\r', '\n'][..]).to_string())),
+            };
+        }
+        let _raw_mode = terminal::RawMode::enable()?;
+        let stdin = io::stdin();
+        let mut input = stdin.lock();
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+        let mut state = LineState::new(&self.history);
+        refresh(&mut out, prompt, &state)?;
+        loop {
+            let key = match read_key(&mut input)? {
+                Some(key) => key,
+                None => return Ok(None),
+            };
+            match state.apply(key, &self.history) {
+                Outcome::Refresh => refresh(&mut out, prompt, &state)?,
+                Outcome::ClearScreen => {
+                    write!(out, "\x1b[H\x1b[2J")?;
+                    refresh(&mut out, prompt, &state)?;
+                }
+                Outcome::Submit => {
+                    write!(out, "\r\n")?;
+                    out.flush()?;
+                    return Ok(Some(state.line()));
+                }
+                Outcome::Quit => {
+                    write!(out, "\r\n")?;
+                    out.flush()?;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+/// Writes the prompt and the line again, and places the cursor.
+fn refresh<W: Write>(out: &mut W, prompt: &str, state: &LineState) -> io::Result<()> {
+    write!(
+        out,
+        "\r{}{}\x1b[0K\r\x1b[{}C",
+        prompt,
+        state.line(),
+        prompt.chars().count() + state.cursor()
+    )?;
+    out.flush()
+}
+
+/// Access to the terminal through the C library.
+pub mod terminal {
+    use std::io;
+
+    /// Returns true if the file descriptor is a terminal.
+    pub fn is_terminal(fd: i32) -> bool {
+        unsafe { libc::isatty(fd) == 1 }
+    }
+
+    /// Puts the standard input in raw mode, so each key is read as it is
+    /// pressed and not echoed, until it is dropped.
+    pub struct RawMode {
+        original: libc::termios,
+    }
+
+    impl RawMode {
+        pub fn enable() -> io::Result<Self> {
+            // SAFETY: termios is a plain C struct, filled by tcgetattr
+            // before it is used.
+            let mut original: libc::termios = unsafe { std::mem::zeroed() };
+            if unsafe { libc::tcgetattr(0, &mut original) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut raw = original;
+            raw.c_iflag &= !(libc::BRKINT | libc::ICRNL | libc::INPCK | libc::ISTRIP | libc::IXON);
+            raw.c_cflag |= libc::CS8;
+            raw.c_lflag &= !(libc::ECHO | libc::ICANON | libc::IEXTEN | libc::ISIG);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if unsafe { libc::tcsetattr(0, libc::TCSAFLUSH, &raw) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(RawMode { original })
+        }
+    }
+
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            unsafe {
+                libc::tcsetattr(0, libc::TCSAFLUSH, &self.original);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_line_editor {
+    use super::*;
+
+    fn keys(bytes: &[u8]) -> Vec<Key> {
+        let mut input = bytes;
+        let mut keys = Vec::new();
+        while let Some(key) = read_key(&mut input).unwrap() {
+            keys.push(key);
+        }
+        keys
+    }
+
+    fn edit(bytes: &[u8], history: &[String]) -> (String, Outcome) {
+        let mut state = LineState::new(history);
+        for key in keys(bytes) {
+            let outcome = state.apply(key, history);
+            if outcome != Outcome::Refresh {
+                return (state.line(), outcome);
+            }
+        }
+        (state.line(), Outcome::Refresh)
+    }
+
+    #[test]
+    fn test_01_escape_sequences_and_utf8_are_decoded() {
+        assert_eq!(
+            keys("a\x1b[A\x1b[3~\x1bOH\x01ñ\x7f\r".as_bytes()),
+            vec![
+                Key::Char('a'),
+                Key::Up,
+                Key::Delete,
+                Key::Home,
+                Key::Ctrl('a'),
+                Key::Char('ñ'),
+                Key::Backspace,
+                Key::Enter,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_02_keys_edit_the_line() {
+        assert_eq!(
+            edit(b"get ky\x1b[De\r", &[]),
+            ("get key".to_string(), Outcome::Submit)
+        );
+        assert_eq!(
+            edit(b"set a b\x01\x1b[3~S\x05\x7fc\r", &[]),
+            ("Set a c".to_string(), Outcome::Submit)
+        );
+        assert_eq!(edit(b"one two  \x17\r", &[]).0, "one ");
+        assert_eq!(edit(b"abc\x02\x02\x0b\r", &[]).0, "a");
+        assert_eq!(edit(b"abc\x02\x15\r", &[]).0, "c");
+        assert_eq!(edit(b"\x04", &[]).1, Outcome::Quit);
+        assert_eq!(edit(b"ab\x03", &[]).1, Outcome::Quit);
+    }
+
+    #[test]
+    fn test_03_up_and_down_go_through_the_history() {
+        let history = vec!["get a".to_string(), "get b".to_string()];
+        assert_eq!(edit(b"\x1b[A\x1b[A\r", &history).0, "get a");
+        assert_eq!(edit(b"x\x1b[A\x1b[A\x1b[A\x1b[B\r", &history).0, "get b");
+        assert_eq!(edit(b"x\x1b[A\x1b[B\r", &history).0, "x");
+    }
+
+    #[test]
+    fn test_04_history_is_kept_in_the_file() {
+        let path = PathBuf::from("line_editor_04_history.txt");
+        let _ = fs::remove_file(&path);
+        let mut editor = LineEditor::new(Some(path.clone()));
+        editor.add_history("get a");
+        editor.add_history("get a");
+        editor.add_history(" ");
+        editor.add_history("set a 1");
+        let editor = LineEditor::new(Some(path.clone()));
+        assert_eq!(editor.history(), ["get a", "set a 1"]);
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,552 @@
+//! Interactive client of the server, as redis-cli. See the `redis_cli`
+//! binary.
+//!
+//! Without a command it opens a prompt, with the line editing and history of
+//! [line_editor]. With a command it runs it and exits, and with `--pipe` it
+//! sends the commands read from the standard input. The commands which
+//! subscribe to channels or monitor the server print what they receive
+//! until Ctrl-C.
+
+pub mod format;
+pub mod line_editor;
+pub mod quoting;
+
+use crate::client::{reply, Connection, Reply};
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use line_editor::{terminal, LineEditor};
+use std::env;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::thread;
+
+pub const USAGE: &str = "\
+redis_cli [options] [<command> [<arg> ...]]
+  -h <host>     server host (default: 127.0.0.1)
+  -p <port>     server port (default: 6379)
+  -a <password> password sent with AUTH when connecting
+  --pipe        sends the commands of the standard input, as protocol or one per line
+  --raw         prints the replies as plain text
+  --no-raw      prints the replies formatted, even if the output is not a terminal
+  --help        shows this message";
+
+/// Commands after which the server keeps sending messages.
+const STREAMING_COMMANDS: [&str; 4] = ["subscribe", "psubscribe", "ssubscribe", "monitor"];
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub host: String,
+    pub port: u16,
+    pub password: Option<String>,
+    pub pipe: bool,
+    /// Forced plain text output, or [None] to format it only for terminals.
+    pub raw: Option<bool>,
+    pub help: bool,
+    /// Command to run instead of opening the prompt.
+    pub command: Vec<String>,
+}
+
+impl Options {
+    /// Parses the arguments, without the name of the binary. The first
+    /// argument which is not an option starts the command.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * An option is unknown or misses its value.
+    /// * The port is not a number.
+    pub fn parse(args: &[String]) -> Result<Self, ErrorStruct> {
+        let mut options = Options {
+            host: "127.0.0.1".to_string(),
+            port: 6379,
+            password: None,
+            pipe: false,
+            raw: None,
+            help: false,
+            command: Vec::new(),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" => options.host = args.next().ok_or_else(usage_error)?.to_string(),
+                "-p" => {
+                    options.port = args
+                        .next()
+                        .and_then(|port| port.parse().ok())
+                        .ok_or_else(usage_error)?
+                }
+                "-a" => options.password = Some(args.next().ok_or_else(usage_error)?.to_string()),
+                "--pipe" => options.pipe = true,
+                "--raw" => options.raw = Some(true),
+                "--no-raw" => options.raw = Some(false),
+                "--help" => options.help = true,
+                option if option.starts_with('-') => return Err(usage_error()),
+                _ => {
+                    options.command.push(arg.to_string());
+                    options.command.extend(args.cloned());
+                    break;
+                }
+            }
+        }
+        Ok(options)
+    }
+
+    /// Connects to the server, authenticating if there is a password. A
+    /// failed authentication is reported, but the connection is kept.
+    fn connect<W: Write>(&self, out: &mut W) -> Result<Connection, ErrorStruct> {
+        let mut connection = Connection::connect(&self.host, self.port)?;
+        if let Some(password) = self.password.as_ref() {
+            if let Reply::Error(error) = connection.request(&["AUTH", password])? {
+                print(out, &format!("AUTH failed: {}", error))?;
+            }
+        }
+        Ok(connection)
+    }
+}
+
+/// Runs the client with the arguments, without the name of the binary.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The arguments are wrong.
+/// * The server can not be reached, or closes the connection while running
+///   a single command or a pipe.
+/// * Some command of the pipe replied with an error.
+pub fn run(args: &[String]) -> Result<(), ErrorStruct> {
+    let options = Options::parse(args)?;
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    if options.help {
+        return print(&mut out, USAGE);
+    }
+    let formatted = !options.raw.unwrap_or(!terminal::is_terminal(1));
+    if options.pipe {
+        pipe(&options, &mut io::stdin(), &mut out)
+    } else if !options.command.is_empty() {
+        one_shot(&options, formatted, &mut out)
+    } else {
+        drop(out);
+        repl(&options, formatted)
+    }
+}
+
+/// Runs the command of the options and prints its reply.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The server can not be reached or closes the connection.
+pub fn one_shot<W: Write>(
+    options: &Options,
+    formatted: bool,
+    out: &mut W,
+) -> Result<(), ErrorStruct> {
+    let mut connection = options.connect(out)?;
+    execute(&mut connection, &options.command, formatted, out)
+}
+
+/// Sends the command and prints its reply. After the commands which subscribe
+/// or monitor, prints the messages until the connection is closed.
+fn execute<A: AsRef<[u8]>, W: Write>(
+    connection: &mut Connection,
+    command: &[A],
+    formatted: bool,
+    out: &mut W,
+) -> Result<(), ErrorStruct> {
+    let reply = connection.request(command)?;
+    write_reply(out, &reply, formatted)?;
+    let name = String::from_utf8_lossy(command[0].as_ref()).to_lowercase();
+    if !STREAMING_COMMANDS.contains(&name.as_str()) || matches!(reply, Reply::Error(_)) {
+        return Ok(());
+    }
+    if formatted && name != "monitor" {
+        print(out, "Reading messages... (press Ctrl-C to quit)")?;
+    }
+    loop {
+        match connection.read_reply() {
+            Ok(reply) => write_reply(out, &reply, formatted)?,
+            Err(error) if error.message() == closed_message() => return Ok(()),
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Sends the commands of the input, without waiting for the replies, and
+/// counts the replies and errors. The commands are read as in the protocol,
+/// or one per line with the quoting rules of the prompt.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The input has a command which is not valid.
+/// * The server can not be reached or closes the connection.
+/// * Some command replied with an error.
+pub fn pipe<R: Read, W: Write>(
+    options: &Options,
+    input: &mut R,
+    out: &mut W,
+) -> Result<(), ErrorStruct> {
+    let mut bytes = Vec::new();
+    input.read_to_end(&mut bytes).map_err(|_| {
+        ErrorStruct::from(redis_messages::file_error(
+            "standard input",
+            "can not be read",
+        ))
+    })?;
+    let commands = parse_commands(&bytes)?;
+    let mut connection = options.connect(out)?;
+    let mut writer = connection.writer()?;
+    let total = commands.len();
+    // The commands are written while the replies are read, so neither side
+    // waits for the other with a full buffer
+    let sender = thread::spawn(move || {
+        commands
+            .iter()
+            .try_for_each(|command| writer.write_all(&reply::encode_command(command)))
+    });
+    let mut errors = 0;
+    for _ in 1..total {
+        errors += check_reply(connection.read_reply()?, out)?;
+    }
+    // The last reply arrives after every command was written
+    if !sender.join().map(|sent| sent.is_ok()).unwrap_or(false) {
+        return Err(ErrorStruct::from(redis_messages::connection_closed()));
+    }
+    print(out, "All data transferred. Waiting for the last reply...")?;
+    if total > 0 {
+        errors += check_reply(connection.read_reply()?, out)?;
+    }
+    print(out, "Last reply received from server.")?;
+    print(out, &format!("errors: {}, replies: {}", errors, total))?;
+    if errors > 0 {
+        return Err(ErrorStruct::from(redis_messages::failed_commands(errors)));
+    }
+    Ok(())
+}
+
+/// Prints the reply of a command of the pipe if it is an error. Returns the
+/// number of errors.
+fn check_reply<W: Write>(reply: Reply, out: &mut W) -> Result<usize, ErrorStruct> {
+    match reply {
+        Reply::Error(error) => {
+            print(out, &error)?;
+            Ok(1)
+        }
+        _ => Ok(0),
+    }
+}
+
+/// Splits the input of the pipe into commands: arrays of bulk strings, or
+/// lines with the quoting rules of the prompt. The empty lines are skipped.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * An array is incomplete or has elements which are not bulk strings.
+/// * A line has unbalanced quotes.
+pub fn parse_commands(bytes: &[u8]) -> Result<Vec<Vec<Vec<u8>>>, ErrorStruct> {
+    let mut rest = bytes;
+    let mut commands = Vec::new();
+    loop {
+        while let Some(b'\r' | b'\n' | b' ' | b'\t') = rest.first() {
+            rest = &rest[1..];
+        }
+        if rest.is_empty() {
+            return Ok(commands);
+        }
+        if rest[0] == b'*' {
+            let command = match reply::read(&mut rest)? {
+                Reply::Array(args) if !args.is_empty() => args
+                    .into_iter()
+                    .map(|arg| match arg {
+                        Reply::Bulk(arg) => Ok(arg),
+                        _ => Err(ErrorStruct::from(redis_messages::protocol_error(
+                            "expected a bulk string",
+                        ))),
+                    })
+                    .collect::<Result<_, _>>()?,
+                _ => {
+                    return Err(ErrorStruct::from(redis_messages::protocol_error(
+                        "expected an array of bulk strings",
+                    )))
+                }
+            };
+            commands.push(command);
+        } else {
+            let end = rest
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .unwrap_or(rest.len());
+            commands.push(quoting::split_args(&String::from_utf8_lossy(&rest[..end]))?);
+            rest = &rest[end..];
+        }
+    }
+}
+
+/// Opens the prompt, which reconnects when a command is typed after the
+/// connection was lost.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The standard output can not be written.
+fn repl(options: &Options, formatted: bool) -> Result<(), ErrorStruct> {
+    let stdout = io::stdout();
+    let mut editor = LineEditor::new(history_path());
+    let mut connection = match options.connect(&mut stdout.lock()) {
+        Ok(connection) => Some(connection),
+        Err(error) => {
+            print(&mut stdout.lock(), error.message())?;
+            None
+        }
+    };
+    loop {
+        let prompt = match connection.as_ref() {
+            Some(connection) => format!("{}> ", connection.address()),
+            None => "not connected> ".to_string(),
+        };
+        let line = match editor.read_line(&prompt) {
+            Ok(Some(line)) => line,
+            _ => return Ok(()),
+        };
+        editor.add_history(&line);
+        let mut out = stdout.lock();
+        let command = match quoting::split_args(&line) {
+            Ok(command) if command.is_empty() => continue,
+            Ok(command) => command,
+            Err(error) => {
+                print(&mut out, error.message())?;
+                continue;
+            }
+        };
+        match String::from_utf8_lossy(&command[0]).to_lowercase().as_str() {
+            "quit" | "exit" => return Ok(()),
+            "clear" => {
+                write!(out, "\x1b[H\x1b[2J").map_err(|_| write_error())?;
+                continue;
+            }
+            _ => {}
+        }
+        if connection.is_none() {
+            match options.connect(&mut out) {
+                Ok(new_connection) => connection = Some(new_connection),
+                Err(error) => {
+                    print(&mut out, error.message())?;
+                    continue;
+                }
+            }
+        }
+        if let Some(current) = connection.as_mut() {
+            if let Err(error) = execute(current, &command, formatted, &mut out) {
+                print(&mut out, &format!("Error: {}", error.message()))?;
+                connection = None;
+            }
+        }
+    }
+}
+
+/// File of the history: `REDISCLI_HISTFILE`, or `.rediscli_history` in the
+/// home directory.
+fn history_path() -> Option<PathBuf> {
+    match env::var("REDISCLI_HISTFILE") {
+        Ok(path) => Some(PathBuf::from(path)),
+        Err(_) => env::var("HOME")
+            .ok()
+            .map(|home| PathBuf::from(home).join(".rediscli_history")),
+    }
+}
+
+fn write_reply<W: Write>(out: &mut W, reply: &Reply, formatted: bool) -> Result<(), ErrorStruct> {
+    let text = if formatted {
+        format::tty(reply)
+    } else {
+        format::raw(reply)
+    };
+    out.write_all(text.as_bytes())
+        .and_then(|_| out.flush())
+        .map_err(|_| write_error())
+}
+
+fn print<W: Write>(out: &mut W, line: &str) -> Result<(), ErrorStruct> {
+    writeln!(out, "{}", line)
+        .and_then(|_| out.flush())
+        .map_err(|_| write_error())
+}
+
+fn closed_message() -> String {
+    redis_messages::connection_closed().get_message()
+}
+
+fn usage_error() -> ErrorStruct {
+    ErrorStruct::from(redis_messages::wrong_usage(USAGE))
+}
+
+fn write_error() -> ErrorStruct {
+    ErrorStruct::from(redis_messages::file_error(
+        "standard output",
+        "can not be written",
+    ))
+}
+
+#[cfg(test)]
+mod test_cli {
+    use super::*;
+    use crate::vec_strings;
+    use std::io::BufReader;
+    use std::net::TcpListener;
+
+    fn args(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    /// Accepts one client and answers the commands with the replies given,
+    /// in order, closing the connection after the last one. Returns the
+    /// commands received.
+    fn fake_server(replies: Vec<&'static str>) -> (u16, thread::JoinHandle<Vec<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut commands = Vec::new();
+            for reply in replies {
+                if let Ok(Reply::Array(args)) = reply::read(&mut reader) {
+                    commands.push(
+                        args.into_iter()
+                            .map(|arg| match arg {
+                                Reply::Bulk(arg) => String::from_utf8(arg).unwrap(),
+                                _ => String::new(),
+                            })
+                            .collect(),
+                    );
+                }
+                writer.write_all(reply.as_bytes()).unwrap();
+            }
+            commands
+        });
+        (port, handle)
+    }
+
+    fn options(port: u16, command: Vec<String>) -> Options {
+        let mut args = vec_strings!["-p", port.to_string()];
+        args.extend(command);
+        Options::parse(&args).unwrap()
+    }
+
+    #[test]
+    fn test_01_options_are_parsed_until_the_command() {
+        let options = Options::parse(&args(&[
+            "-h",
+            "localhost",
+            "-a",
+            "pass",
+            "--raw",
+            "set",
+            "k",
+            "-1",
+        ]))
+        .unwrap();
+        assert_eq!(options.host, "localhost");
+        assert_eq!(options.port, 6379);
+        assert_eq!(options.password, Some("pass".to_string()));
+        assert_eq!(options.raw, Some(true));
+        assert_eq!(options.command, vec_strings!["set", "k", "-1"]);
+        assert!(Options::parse(&args(&["-p", "port"])).is_err());
+        assert!(Options::parse(&args(&["-h"])).is_err());
+        assert!(Options::parse(&args(&["--verbose"]))
+            .unwrap_err()
+            .print_it()
+            .contains("redis_cli [options]"));
+    }
+
+    #[test]
+    fn test_02_one_shot_prints_the_reply() {
+        let (port, server) =
+            fake_server(vec!["-ERR no password\r\n\n", "*2\r\n$1\r\na\r\n:1\r\n\n"]);
+        let mut options = options(port, vec_strings!["lrange", "my list", "0", "-1"]);
+        options.password = Some("secret".to_string());
+        let mut out = Vec::new();
+        one_shot(&options, true, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "AUTH failed: ERR no password\n1) \"a\"\n2) (integer) 1\n"
+        );
+        assert_eq!(
+            server.join().unwrap(),
+            vec![
+                vec_strings!["AUTH", "secret"],
+                vec_strings!["lrange", "my list", "0", "-1"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_03_subscribe_prints_the_messages_until_the_connection_is_closed() {
+        let (port, server) = fake_server(vec![
+            "*3\r\n$9\r\nsubscribe\r\n$2\r\nch\r\n:1\r\n\n\
+             *3\r\n$7\r\nmessage\r\n$2\r\nch\r\n$2\r\nhi\r\n\n",
+        ]);
+        let mut out = Vec::new();
+        one_shot(
+            &options(port, vec_strings!["subscribe", "ch"]),
+            false,
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "subscribe\nch\n1\nmessage\nch\nhi\n"
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_04_pipe_sends_protocol_and_lines() {
+        let (port, server) = fake_server(vec!["+OK\r\n\n", "-ERR wrong\r\n\n", "+OK\r\n\n"]);
+        let mut input =
+            &b"*3\r\n$3\r\nSET\r\n$1\r\na\r\n$1\r\n1\r\n\nincr a\n\nset b \"x y\"\n"[..];
+        let mut out = Vec::new();
+        let error = pipe(&options(port, vec![]), &mut input, &mut out).unwrap_err();
+        assert_eq!(error.print_it(), "ERR 1 commands replied with an error");
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "ERR wrong\nAll data transferred. Waiting for the last reply...\n\
+             Last reply received from server.\nerrors: 1, replies: 3\n"
+        );
+        assert_eq!(
+            server.join().unwrap(),
+            vec![
+                vec_strings!["SET", "a", "1"],
+                vec_strings!["incr", "a"],
+                vec_strings!["set", "b", "x y"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_05_invalid_pipe_input_is_rejected() {
+        assert!(parse_commands(b"*2\r\n$3\r\nGET\r\n").is_err());
+        assert!(parse_commands(b"*1\r\n:1\r\n").is_err());
+        assert_eq!(
+            parse_commands(b"get \"a\n").unwrap_err().print_it(),
+            "ERR Invalid argument(s)"
+        );
+        assert!(parse_commands(b" \r\n\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_06_unreachable_server() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let error =
+            one_shot(&options(port, vec_strings!["ping"]), true, &mut Vec::new()).unwrap_err();
+        assert!(error
+            .message()
+            .starts_with(&format!("Could not connect to Redis at 127.0.0.1:{}", port)));
+    }
+}
@@ -0,0 +1,152 @@
+//! Splitting of the lines typed in the client into arguments, with the
+//! quoting rules of redis-cli:
+//!
+//! * Arguments are separated by spaces, tabs or line breaks.
+//! * Between double quotes, `\"`, `\\`, `\n`, `\r`, `\t`, `\b`, `\a` and
+//!   `\xHH` are escapes.
+//! * Between single quotes, only `\'` is an escape.
+//! * A closing quote must be followed by a space or the end of the line.
+
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+
+/// Splits the line into its arguments.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * Some quote is not closed, or is followed by other characters.
+pub fn split_args(line: &str) -> Result<Vec<Vec<u8>>, ErrorStruct> {
+    let bytes = line.as_bytes();
+    let mut position = 0;
+    let mut args = Vec::new();
+    loop {
+        while position < bytes.len() && is_space(bytes[position]) {
+            position += 1;
+        }
+        if position == bytes.len() {
+            return Ok(args);
+        }
+        let mut arg = Vec::new();
+        let (mut double_quoted, mut single_quoted) = (false, false);
+        loop {
+            let current = bytes.get(position).copied();
+            let next = bytes.get(position + 1).copied();
+            if double_quoted {
+                match (current, next, hex_byte(bytes, position + 2)) {
+                    (Some(b'\\'), Some(b'x'), Some(byte)) => {
+                        arg.push(byte);
+                        position += 3;
+                    }
+                    (Some(b'\\'), Some(escaped), _) => {
+                        arg.push(match escaped {
+                            b'n' => b'\n',
+                            b'r' => b'\r',
+                            b't' => b'\t',
+                            b'b' => 8,
+                            b'a' => 7,
+                            other => other,
+                        });
+                        position += 1;
+                    }
+                    (Some(b'"'), _, _) => {
+                        closing_quote(next)?;
+                        position += 1;
+                        break;
+                    }
+                    (None, _, _) => return Err(invalid()),
+                    (Some(byte), _, _) => arg.push(byte),
+                }
+            } else if single_quoted {
+                match (current, next) {
+                    (Some(b'\\'), Some(b'\'')) => {
+                        arg.push(b'\'');
+                        position += 1;
+                    }
+                    (Some(b'\''), _) => {
+                        closing_quote(next)?;
+                        position += 1;
+                        break;
+                    }
+                    (None, _) => return Err(invalid()),
+                    (Some(byte), _) => arg.push(byte),
+                }
+            } else {
+                match current {
+                    None => break,
+                    Some(byte) if is_space(byte) => break,
+                    Some(b'"') => double_quoted = true,
+                    Some(b'\'') => single_quoted = true,
+                    Some(byte) => arg.push(byte),
+                }
+            }
+            position += 1;
+        }
+        args.push(arg);
+    }
+}
+
+fn is_space(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\r' | 0)
+}
+
+/// Returns the byte of the two hexadecimal digits at `position`.
+fn hex_byte(bytes: &[u8], position: usize) -> Option<u8> {
+    let digits = bytes.get(position..position + 2)?;
+    u8::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()
+}
+
+fn closing_quote(next: Option<u8>) -> Result<(), ErrorStruct> {
+    match next {
+        Some(byte) if !is_space(byte) => Err(invalid()),
+        _ => Ok(()),
+    }
+}
+
+fn invalid() -> ErrorStruct {
+    ErrorStruct::from(redis_messages::invalid_arguments())
+}
+
+#[cfg(test)]
+mod test_quoting {
+    use super::*;
+
+    fn split(line: &str) -> Vec<String> {
+        split_args(line)
+            .unwrap()
+            .into_iter()
+            .map(|arg| String::from_utf8(arg).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_01_arguments_are_separated_by_whitespace() {
+        assert_eq!(split("  set  key\tvalue \n"), ["set", "key", "value"]);
+        assert!(split("   ").is_empty());
+    }
+
+    #[test]
+    fn test_02_double_quotes_have_escapes() {
+        assert_eq!(
+            split(r#"set "my key" "a\"b\\c\n\x41\xzz" """#),
+            ["set", "my key", "a\"b\\c\nAxzz", ""]
+        );
+        assert_eq!(split_args(r#""\xff""#).unwrap(), vec![vec![255]]);
+        assert_eq!(split(r#"a"b c""#), ["ab c"]);
+    }
+
+    #[test]
+    fn test_03_single_quotes_only_escape_the_quote() {
+        assert_eq!(split(r#"set 'it\'s' 'a\nb'"#), ["set", "it's", "a\\nb"]);
+    }
+
+    #[test]
+    fn test_04_unbalanced_quotes_are_invalid() {
+        for line in [r#"get "key"#, "get 'key", r#"get "a"b"#, "get 'a'b"] {
+            assert_eq!(
+                split_args(line).unwrap_err().print_it(),
+                "ERR Invalid argument(s)"
+            );
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::{env, process};
+
+use redis_rust::cli;
+
+/// ## Commands to Talk to the Server, in console:
+///
+/// * *cargo run --bin redis_cli* opens the prompt.
+/// * *cargo run --bin redis_cli -p 6380 get foo* runs a single command.
+/// * *cargo run --bin redis_cli --pipe < commands.txt* sends many commands.
+///
+/// See [cli::USAGE] for all the options.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(error) = cli::run(&args) {
+        eprintln!("{}", error.print_it());
+        process::exit(1);
+    }
+}
@@ -0,0 +1,381 @@
+//! Commands built argument by argument, pipelines of them and transactions.
+//!
+//! The transactions need WATCH, MULTI and EXEC, so they only work against a
+//! real Redis, not against this server.
+
+use super::types::{server_error, FromReply, ToArgs};
+use super::{reply, Connection, Reply};
+use crate::native_types::ErrorStruct;
+use std::collections::HashSet;
+
+/// A command and its arguments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cmd {
+    args: Vec<Vec<u8>>,
+}
+
+/// Starts a command, as `cmd("SET").arg("key").arg(42).execute(&mut connection)`.
+pub fn cmd(name: &str) -> Cmd {
+    let mut command = Cmd::default();
+    command.arg(name);
+    command
+}
+
+/// Starts an empty pipeline.
+pub fn pipe() -> Pipeline {
+    Pipeline::default()
+}
+
+impl Cmd {
+    /// Appends an argument, or one per element of a collection.
+    pub fn arg<A: ToArgs>(&mut self, arg: A) -> &mut Self {
+        arg.write_args(&mut self.args);
+        self
+    }
+
+    pub fn args(&self) -> &[Vec<u8>] {
+        &self.args
+    }
+
+    /// Sends the command and converts its reply.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    /// * The server replied with an error.
+    /// * The reply can not be represented in the requested type.
+    pub fn query<T: FromReply>(&self, connection: &mut Connection) -> Result<T, ErrorStruct> {
+        match connection.request(&self.args)? {
+            Reply::Error(text) => Err(server_error(&text)),
+            reply => T::from_reply(reply),
+        }
+    }
+
+    /// Sends the command, discarding its reply.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    /// * The server replied with an error.
+    pub fn execute(&self, connection: &mut Connection) -> Result<(), ErrorStruct> {
+        self.query(connection)
+    }
+}
+
+/// Commands sent in a single write, whose replies are read afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct Pipeline {
+    commands: Vec<Cmd>,
+    ignored: HashSet<usize>,
+    atomic: bool,
+}
+
+impl Pipeline {
+    /// Starts a new command in the pipeline.
+    pub fn cmd(&mut self, name: &str) -> &mut Self {
+        self.commands.push(cmd(name));
+        self
+    }
+
+    /// Appends an argument to the last command, or starts one with it.
+    pub fn arg<A: ToArgs>(&mut self, arg: A) -> &mut Self {
+        match self.commands.last_mut() {
+            Some(command) => {
+                command.arg(arg);
+            }
+            None => {
+                let mut command = Cmd::default();
+                command.arg(arg);
+                self.commands.push(command);
+            }
+        }
+        self
+    }
+
+    pub fn add(&mut self, command: Cmd) -> &mut Self {
+        self.commands.push(command);
+        self
+    }
+
+    /// Leaves the reply of the last command out of the result.
+    pub fn ignore(&mut self) -> &mut Self {
+        if !self.commands.is_empty() {
+            self.ignored.insert(self.commands.len() - 1);
+        }
+        self
+    }
+
+    /// Sends the commands between MULTI and EXEC, so the server runs them
+    /// as a transaction.
+    /// Only a real Redis runs MULTI and EXEC: this server rejects MULTI, and
+    /// the pipeline then fails without sending the commands.
+    pub fn atomic(&mut self) -> &mut Self {
+        self.atomic = true;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Sends the commands and converts the array of their replies, without
+    /// the ignored ones. An atomic pipeline aborted by a watched key gets
+    /// nil, so it is converted to [None] when queried as an [Option].
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    /// * Any command got an error, or the server does not run transactions.
+    /// * The replies can not be represented in the requested type.
+    pub fn query<T: FromReply>(&self, connection: &mut Connection) -> Result<T, ErrorStruct> {
+        let replies = if self.atomic {
+            self.send_transaction(connection)?
+        } else {
+            Some(self.send_commands(connection)?)
+        };
+        match replies {
+            Some(replies) => T::from_reply(Reply::Array(self.without_ignored(replies)?)),
+            None => T::from_reply(Reply::Nil),
+        }
+    }
+
+    /// Sends the commands, discarding their replies.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    /// * Any command got an error, or the server does not run transactions.
+    pub fn execute(&self, connection: &mut Connection) -> Result<(), ErrorStruct> {
+        self.query::<Reply>(connection).map(|_| ())
+    }
+
+    fn encoded(&self) -> Vec<u8> {
+        self.commands
+            .iter()
+            .flat_map(|command| reply::encode_command(command.args()))
+            .collect()
+    }
+
+    /// All the replies are read before looking for errors, so the
+    /// connection can keep being used.
+    fn send_commands(&self, connection: &mut Connection) -> Result<Vec<Reply>, ErrorStruct> {
+        connection.send_encoded(&self.encoded())?;
+        (0..self.commands.len())
+            .map(|_| connection.read_reply())
+            .collect()
+    }
+
+    /// MULTI is confirmed before sending the commands, so they are not run
+    /// one by one by a server which does not know it. Returns [None] when
+    /// EXEC was aborted by a watched key.
+    fn send_transaction(
+        &self,
+        connection: &mut Connection,
+    ) -> Result<Option<Vec<Reply>>, ErrorStruct> {
+        cmd("MULTI").execute(connection)?;
+        let mut encoded = self.encoded();
+        encoded.extend(reply::encode_command(&["EXEC"]));
+        connection.send_encoded(&encoded)?;
+        let queued = (0..self.commands.len())
+            .map(|_| connection.read_reply())
+            .collect::<Result<Vec<Reply>, ErrorStruct>>()?;
+        match connection.read_reply()? {
+            Reply::Array(replies) => Ok(Some(replies)),
+            Reply::Nil => Ok(None),
+            Reply::Error(text) => Err(first_error(&queued).unwrap_or_else(|| server_error(&text))),
+            other => Ok(Some(vec![other])),
+        }
+    }
+
+    fn without_ignored(&self, replies: Vec<Reply>) -> Result<Vec<Reply>, ErrorStruct> {
+        if let Some(error) = first_error(&replies) {
+            return Err(error);
+        }
+        Ok(replies
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !self.ignored.contains(index))
+            .map(|(_, reply)| reply)
+            .collect())
+    }
+}
+
+fn first_error(replies: &[Reply]) -> Option<ErrorStruct> {
+    replies.iter().find_map(|reply| match reply {
+        Reply::Error(text) => Some(server_error(text)),
+        _ => None,
+    })
+}
+
+impl Connection {
+    /// Runs `transaction` until the atomic pipeline it queries is not aborted:
+    /// the `keys` are watched, and the pipeline it receives is executed
+    /// between MULTI and EXEC. It returns [None] to be run again, as the
+    /// pipeline queried as an [Option] does when a watched key changed.
+    /// Needs a server which runs WATCH, MULTI and EXEC, as a real Redis:
+    /// this server does not.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    /// * The server does not run transactions.
+    /// * `transaction` returns an error.
+    pub fn transaction<K, T, F>(&mut self, keys: K, mut transaction: F) -> Result<T, ErrorStruct>
+    where
+        K: ToArgs,
+        F: FnMut(&mut Connection, &mut Pipeline) -> Result<Option<T>, ErrorStruct>,
+    {
+        let mut watch = cmd("WATCH");
+        watch.arg(keys);
+        loop {
+            if watch.args().len() > 1 {
+                watch.execute(self)?;
+            }
+            let mut pipeline = pipe();
+            pipeline.atomic();
+            match transaction(self, &mut pipeline) {
+                Ok(Some(result)) => return Ok(result),
+                Ok(None) => continue,
+                Err(error) => {
+                    let _ = cmd("UNWATCH").execute(self);
+                    return Err(error);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_cmd {
+    use super::*;
+    use crate::client::test_server::{args, fake_server};
+
+    #[test]
+    fn test_01_commands_are_queried_in_the_requested_type() {
+        let (port, requests) = fake_server(vec![b":7\r\n\n", b"-ERR wrong\r\n\n"]);
+        let mut connection = Connection::connect("127.0.0.1", port).unwrap();
+
+        let count: i64 = cmd("INCRBY")
+            .arg("key")
+            .arg(7)
+            .query(&mut connection)
+            .unwrap();
+        let error = cmd("GET").arg("key").execute(&mut connection).unwrap_err();
+
+        assert_eq!(count, 7);
+        assert_eq!(error.print_it(), "ERR wrong");
+        assert_eq!(requests.recv().unwrap(), args(&["INCRBY", "key", "7"]));
+    }
+
+    #[test]
+    fn test_02_pipelines_skip_the_ignored_replies() {
+        let (port, requests) = fake_server(vec![b"+OK\r\n\n", b"$1\r\n1\r\n\n", b":2\r\n\n"]);
+        let mut connection = Connection::connect("127.0.0.1", port).unwrap();
+
+        let (value, count): (String, i64) = pipe()
+            .cmd("SET")
+            .arg("key")
+            .arg(1)
+            .ignore()
+            .cmd("GET")
+            .arg("key")
+            .cmd("INCR")
+            .arg("key")
+            .query(&mut connection)
+            .unwrap();
+
+        assert_eq!((value.as_str(), count), ("1", 2));
+        let requests: Vec<Vec<Vec<u8>>> = requests.iter().collect();
+        assert_eq!(
+            requests,
+            vec![
+                args(&["SET", "key", "1"]),
+                args(&["GET", "key"]),
+                args(&["INCR", "key"])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_03_a_pipeline_with_an_error_keeps_the_connection_usable() {
+        let (port, _requests) = fake_server(vec![
+            b"-WRONGTYPE Operation\r\n\n",
+            b":1\r\n\n",
+            b"+PONG\r\n\n",
+        ]);
+        let mut connection = Connection::connect("127.0.0.1", port).unwrap();
+
+        let error = pipe()
+            .cmd("LPUSH")
+            .arg("text")
+            .arg("a")
+            .cmd("INCR")
+            .arg("count")
+            .execute(&mut connection)
+            .unwrap_err();
+
+        assert_eq!(error.prefix(), Some("WRONGTYPE"));
+        let pong: String = cmd("PING").query(&mut connection).unwrap();
+        assert_eq!(pong, "PONG");
+    }
+
+    #[test]
+    fn test_04_transactions_are_run_again_when_aborted() {
+        let (port, requests) = fake_server(vec![
+            b"+OK\r\n",
+            b":5\r\n",
+            b"+OK\r\n",
+            b"+QUEUED\r\n",
+            b"*-1\r\n",
+            b"+OK\r\n",
+            b":5\r\n",
+            b"+OK\r\n",
+            b"+QUEUED\r\n",
+            b"*1\r\n+OK\r\n",
+        ]);
+        let mut connection = Connection::connect("127.0.0.1", port).unwrap();
+        let mut runs = 0;
+
+        let () = connection
+            .transaction("key", |connection, pipeline| {
+                runs += 1;
+                let value: i64 = cmd("GET").arg("key").query(connection)?;
+                pipeline.cmd("SET").arg("key").arg(value + 1).ignore();
+                pipeline.query(connection)
+            })
+            .unwrap();
+
+        assert_eq!(runs, 2);
+        let requests: Vec<Vec<Vec<u8>>> = requests.iter().collect();
+        assert_eq!(requests.len(), 10);
+        assert_eq!(requests[0], args(&["WATCH", "key"]));
+        assert_eq!(requests[2], args(&["MULTI"]));
+        assert_eq!(requests[3], args(&["SET", "key", "6"]));
+        assert_eq!(requests[4], args(&["EXEC"]));
+    }
+
+    #[test]
+    fn test_05_transactions_fail_in_servers_without_multi() {
+        let (port, requests) = fake_server(vec![b"-ERR unknown command 'multi'\r\n\n"]);
+        let mut connection = Connection::connect("127.0.0.1", port).unwrap();
+
+        let error = pipe()
+            .atomic()
+            .cmd("INCR")
+            .arg("key")
+            .execute(&mut connection)
+            .unwrap_err();
+
+        assert_eq!(error.print_it(), "ERR unknown command 'multi'");
+        assert_eq!(requests.iter().count(), 1);
+    }
+}
@@ -0,0 +1,283 @@
+//! Typed methods for the commands most used. The ones which return stored
+//! values are generic over the type to convert them to, as
+//! `let value: Option<String> = connection.get("key")?`. Any other command
+//! can be sent with [cmd](super::cmd).
+//!
+//! # Error
+//! Every method returns an [ErrorStruct] if:
+//!
+//! * The connection was closed.
+//! * The server replied with an error, as WRONGTYPE.
+//! * The reply can not be represented in the requested type.
+
+use super::cmd::cmd;
+use super::types::{FromReply, ToArgs};
+use super::Connection;
+use crate::native_types::ErrorStruct;
+
+impl Connection {
+    pub fn ping(&mut self) -> Result<String, ErrorStruct> {
+        cmd("PING").query(self)
+    }
+
+    pub fn flushdb(&mut self) -> Result<(), ErrorStruct> {
+        cmd("FLUSHDB").execute(self)
+    }
+
+    pub fn dbsize(&mut self) -> Result<i64, ErrorStruct> {
+        cmd("DBSIZE").query(self)
+    }
+
+    /// Returns the number of keys removed.
+    pub fn del<K: ToArgs>(&mut self, keys: K) -> Result<i64, ErrorStruct> {
+        cmd("DEL").arg(keys).query(self)
+    }
+
+    /// Returns how many of the keys exist.
+    pub fn exists<K: ToArgs>(&mut self, keys: K) -> Result<i64, ErrorStruct> {
+        cmd("EXISTS").arg(keys).query(self)
+    }
+
+    /// Returns false if the key does not exist.
+    pub fn expire<K: ToArgs>(&mut self, key: K, seconds: i64) -> Result<bool, ErrorStruct> {
+        cmd("EXPIRE").arg(key).arg(seconds).query(self)
+    }
+
+    /// Returns false if the key does not exist or has no timeout.
+    pub fn persist<K: ToArgs>(&mut self, key: K) -> Result<bool, ErrorStruct> {
+        cmd("PERSIST").arg(key).query(self)
+    }
+
+    /// Returns the seconds to live, -1 without a timeout and -2 if the key
+    /// does not exist.
+    pub fn ttl<K: ToArgs>(&mut self, key: K) -> Result<i64, ErrorStruct> {
+        cmd("TTL").arg(key).query(self)
+    }
+
+    pub fn rename<K: ToArgs, N: ToArgs>(&mut self, key: K, new_key: N) -> Result<(), ErrorStruct> {
+        cmd("RENAME").arg(key).arg(new_key).execute(self)
+    }
+
+    /// Returns the type stored at the key, or "none".
+    pub fn key_type<K: ToArgs>(&mut self, key: K) -> Result<String, ErrorStruct> {
+        cmd("TYPE").arg(key).query(self)
+    }
+
+    pub fn keys<K: ToArgs>(&mut self, pattern: K) -> Result<Vec<String>, ErrorStruct> {
+        cmd("KEYS").arg(pattern).query(self)
+    }
+
+    pub fn get<K: ToArgs, V: FromReply>(&mut self, key: K) -> Result<V, ErrorStruct> {
+        cmd("GET").arg(key).query(self)
+    }
+
+    pub fn set<K: ToArgs, V: ToArgs>(&mut self, key: K, value: V) -> Result<(), ErrorStruct> {
+        cmd("SET").arg(key).arg(value).execute(self)
+    }
+
+    pub fn set_ex<K: ToArgs, V: ToArgs>(
+        &mut self,
+        key: K,
+        value: V,
+        seconds: i64,
+    ) -> Result<(), ErrorStruct> {
+        cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("EX")
+            .arg(seconds)
+            .execute(self)
+    }
+
+    /// Returns the value stored before.
+    pub fn getset<K: ToArgs, V: ToArgs, R: FromReply>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<R, ErrorStruct> {
+        cmd("GETSET").arg(key).arg(value).query(self)
+    }
+
+    pub fn getdel<K: ToArgs, V: FromReply>(&mut self, key: K) -> Result<V, ErrorStruct> {
+        cmd("GETDEL").arg(key).query(self)
+    }
+
+    /// Sets every key to its value, as in `mset(&[("k1", "v1"), ("k2", "v2")])`.
+    pub fn mset<K: ToArgs, V: ToArgs>(&mut self, pairs: &[(K, V)]) -> Result<(), ErrorStruct> {
+        cmd("MSET").arg(pairs).execute(self)
+    }
+
+    pub fn mget<K: ToArgs, V: FromReply>(&mut self, keys: K) -> Result<V, ErrorStruct> {
+        cmd("MGET").arg(keys).query(self)
+    }
+
+    /// Returns the length of the string after appending the value.
+    pub fn append<K: ToArgs, V: ToArgs>(&mut self, key: K, value: V) -> Result<i64, ErrorStruct> {
+        cmd("APPEND").arg(key).arg(value).query(self)
+    }
+
+    pub fn strlen<K: ToArgs>(&mut self, key: K) -> Result<i64, ErrorStruct> {
+        cmd("STRLEN").arg(key).query(self)
+    }
+
+    pub fn incr<K: ToArgs>(&mut self, key: K) -> Result<i64, ErrorStruct> {
+        cmd("INCR").arg(key).query(self)
+    }
+
+    pub fn incr_by<K: ToArgs>(&mut self, key: K, increment: i64) -> Result<i64, ErrorStruct> {
+        cmd("INCRBY").arg(key).arg(increment).query(self)
+    }
+
+    pub fn decr<K: ToArgs>(&mut self, key: K) -> Result<i64, ErrorStruct> {
+        cmd("DECR").arg(key).query(self)
+    }
+
+    pub fn decr_by<K: ToArgs>(&mut self, key: K, decrement: i64) -> Result<i64, ErrorStruct> {
+        cmd("DECRBY").arg(key).arg(decrement).query(self)
+    }
+
+    /// Returns the length of the list after the push.
+    pub fn lpush<K: ToArgs, V: ToArgs>(&mut self, key: K, values: V) -> Result<i64, ErrorStruct> {
+        cmd("LPUSH").arg(key).arg(values).query(self)
+    }
+
+    /// Returns the length of the list after the push.
+    pub fn rpush<K: ToArgs, V: ToArgs>(&mut self, key: K, values: V) -> Result<i64, ErrorStruct> {
+        cmd("RPUSH").arg(key).arg(values).query(self)
+    }
+
+    pub fn lpop<K: ToArgs, V: FromReply>(&mut self, key: K) -> Result<V, ErrorStruct> {
+        cmd("LPOP").arg(key).query(self)
+    }
+
+    pub fn rpop<K: ToArgs, V: FromReply>(&mut self, key: K) -> Result<V, ErrorStruct> {
+        cmd("RPOP").arg(key).query(self)
+    }
+
+    pub fn llen<K: ToArgs>(&mut self, key: K) -> Result<i64, ErrorStruct> {
+        cmd("LLEN").arg(key).query(self)
+    }
+
+    pub fn lindex<K: ToArgs, V: FromReply>(
+        &mut self,
+        key: K,
+        index: isize,
+    ) -> Result<V, ErrorStruct> {
+        cmd("LINDEX").arg(key).arg(index).query(self)
+    }
+
+    pub fn lrange<K: ToArgs, V: FromReply>(
+        &mut self,
+        key: K,
+        start: isize,
+        stop: isize,
+    ) -> Result<V, ErrorStruct> {
+        cmd("LRANGE").arg(key).arg(start).arg(stop).query(self)
+    }
+
+    pub fn lset<K: ToArgs, V: ToArgs>(
+        &mut self,
+        key: K,
+        index: isize,
+        value: V,
+    ) -> Result<(), ErrorStruct> {
+        cmd("LSET").arg(key).arg(index).arg(value).execute(self)
+    }
+
+    /// Returns the number of elements removed.
+    pub fn lrem<K: ToArgs, V: ToArgs>(
+        &mut self,
+        key: K,
+        count: isize,
+        value: V,
+    ) -> Result<i64, ErrorStruct> {
+        cmd("LREM").arg(key).arg(count).arg(value).query(self)
+    }
+
+    /// Returns the number of members added.
+    pub fn sadd<K: ToArgs, M: ToArgs>(&mut self, key: K, members: M) -> Result<i64, ErrorStruct> {
+        cmd("SADD").arg(key).arg(members).query(self)
+    }
+
+    /// Returns the number of members removed.
+    pub fn srem<K: ToArgs, M: ToArgs>(&mut self, key: K, members: M) -> Result<i64, ErrorStruct> {
+        cmd("SREM").arg(key).arg(members).query(self)
+    }
+
+    pub fn scard<K: ToArgs>(&mut self, key: K) -> Result<i64, ErrorStruct> {
+        cmd("SCARD").arg(key).query(self)
+    }
+
+    pub fn sismember<K: ToArgs, M: ToArgs>(
+        &mut self,
+        key: K,
+        member: M,
+    ) -> Result<bool, ErrorStruct> {
+        cmd("SISMEMBER").arg(key).arg(member).query(self)
+    }
+
+    pub fn smembers<K: ToArgs, V: FromReply>(&mut self, key: K) -> Result<V, ErrorStruct> {
+        cmd("SMEMBERS").arg(key).query(self)
+    }
+
+    /// Returns the number of clients which received the message.
+    pub fn publish<C: ToArgs, M: ToArgs>(
+        &mut self,
+        channel: C,
+        message: M,
+    ) -> Result<i64, ErrorStruct> {
+        cmd("PUBLISH").arg(channel).arg(message).query(self)
+    }
+}
+
+#[cfg(test)]
+mod test_commands {
+    use super::*;
+    use crate::client::test_server::{args, fake_server};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_01_typed_methods_send_their_command() {
+        let (port, requests) = fake_server(vec![
+            b"+OK\r\n\n",
+            b"$5\r\nvalue\r\n\n",
+            b"$-1\r\n\n",
+            b":2\r\n\n",
+            b"*2\r\n$1\r\na\r\n$1\r\nb\r\n\n",
+            b":0\r\n\n",
+        ]);
+        let mut connection = Connection::connect("127.0.0.1", port).unwrap();
+
+        connection.set("key", "value").unwrap();
+        let value: String = connection.get("key").unwrap();
+        let missing: Option<String> = connection.get("missing").unwrap();
+        let added = connection.sadd("set", ["a", "b"]).unwrap();
+        let members: HashSet<String> = connection.smembers("set").unwrap();
+        let receivers = connection.publish("channel", "message").unwrap();
+
+        assert_eq!(value, "value");
+        assert_eq!(missing, None);
+        assert_eq!(added, 2);
+        assert_eq!(
+            members,
+            vec!["a".to_string(), "b".to_string()].into_iter().collect()
+        );
+        assert_eq!(receivers, 0);
+        let requests: Vec<Vec<Vec<u8>>> = requests.iter().collect();
+        assert_eq!(requests[3], args(&["SADD", "set", "a", "b"]));
+        assert_eq!(requests[5], args(&["PUBLISH", "channel", "message"]));
+    }
+
+    #[test]
+    fn test_02_mset_sends_the_pairs_in_order() {
+        let (port, requests) = fake_server(vec![b"+OK\r\n\n"]);
+        let mut connection = Connection::connect("127.0.0.1", port).unwrap();
+
+        connection.mset(&[("k1", 1), ("k2", 2)]).unwrap();
+
+        assert_eq!(
+            requests.recv().unwrap(),
+            args(&["MSET", "k1", "1", "k2", "2"])
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Client side of the protocol, used by the `redis_cli` and `benchmark`
+//! binaries, the HTTP server and the integration tests.
+//!
+//! The transactions, [Connection::transaction] and [Pipeline::atomic], need
+//! WATCH, MULTI and EXEC, which this server does not run: they only work
+//! against a real Redis, and fail with the error of the server here.
+//!
+//! ```no_run
+//! use redis_rust::client::{cmd, Connection};
+//!
+//! let mut connection = Connection::connect("127.0.0.1", 6379)?;
+//! connection.set("key", 41)?;
+//! let value: i64 = cmd("INCR").arg("key").query(&mut connection)?;
+//! # Ok::<(), redis_rust::native_types::ErrorStruct>(())
+//! ```
+
+pub mod cmd;
+pub mod commands;
+pub mod pool;
+pub mod pubsub;
+pub mod reply;
+pub mod types;
+
+pub use cmd::{cmd, pipe, Cmd, Pipeline};
+pub use pool::{Pool, PooledConnection};
+pub use pubsub::{Message, Subscription};
+pub use reply::Reply;
+pub use types::{FromReply, ToArgs};
+
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use std::io::{BufReader, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// Connection with a server.
+pub struct Connection {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+    address: String,
+    /// A write or read failed, so the replies may be out of step.
+    broken: bool,
+}
+
+impl Connection {
+    /// Connects to the server listening in `host` and `port`.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The server can not be reached.
+    pub fn connect(host: &str, port: u16) -> Result<Self, ErrorStruct> {
+        let address = format!("{}:{}", host, port);
+        let failed =
+            |error: std::io::Error| redis_messages::connection_failed(&address, &error.to_string());
+        let stream =
+            TcpStream::connect((host, port)).map_err(|error| ErrorStruct::from(failed(error)))?;
+        let reader = BufReader::new(
+            stream
+                .try_clone()
+                .map_err(|_| ErrorStruct::from(redis_messages::clone_socket()))?,
+        );
+        Ok(Connection {
+            stream,
+            reader,
+            address,
+            broken: false,
+        })
+    }
+
+    /// Host and port of the server, as `host:port`.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Sends a command without waiting for its reply.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    pub fn send<A: AsRef<[u8]>>(&mut self, command: &[A]) -> Result<(), ErrorStruct> {
+        self.send_encoded(&reply::encode_command(command))
+    }
+
+    /// Sends commands already encoded, as a pipeline is sent in a single
+    /// write.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    pub fn send_encoded(&mut self, bytes: &[u8]) -> Result<(), ErrorStruct> {
+        let written = self.stream.write_all(bytes);
+        self.broken |= written.is_err();
+        written.map_err(|_| ErrorStruct::from(redis_messages::connection_closed()))
+    }
+
+    /// Reads the next reply of the server.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    /// * The reply does not follow the protocol.
+    pub fn read_reply(&mut self) -> Result<Reply, ErrorStruct> {
+        let reply = reply::read(&mut self.reader);
+        self.broken |= reply.is_err();
+        reply
+    }
+
+    /// Sends a command and waits for its reply.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    /// * The reply does not follow the protocol.
+    pub fn request<A: AsRef<[u8]>>(&mut self, command: &[A]) -> Result<Reply, ErrorStruct> {
+        self.send(command)?;
+        self.read_reply()
+    }
+
+    /// Sets how long a read waits for the server, or [None] to wait forever.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The socket does not accept the timeout.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), ErrorStruct> {
+        self.stream.set_read_timeout(timeout).map_err(|error| {
+            ErrorStruct::from(redis_messages::connection_failed(
+                &self.address,
+                &error.to_string(),
+            ))
+        })
+    }
+
+    /// Returns true after a write or read failed, as the connection can not
+    /// be used anymore.
+    pub fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    /// Returns another handle to write to the server while the replies are
+    /// read, as the mass insertion does.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The socket can not be cloned.
+    pub fn writer(&self) -> Result<TcpStream, ErrorStruct> {
+        self.stream
+            .try_clone()
+            .map_err(|_| ErrorStruct::from(redis_messages::clone_socket()))
+    }
+}
+
+/// Server which answers the commands of one client with the replies given.
+#[cfg(test)]
+pub(crate) mod test_server {
+    use super::{reply, Reply};
+    use std::io::{BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    pub fn args(args: &[&str]) -> Vec<Vec<u8>> {
+        args.iter().map(|arg| arg.as_bytes().to_vec()).collect()
+    }
+
+    /// Reads a command before writing each reply, and sends the commands
+    /// read. The connection is closed after the last reply.
+    pub fn fake_server(replies: Vec<&'static [u8]>) -> (u16, Receiver<Vec<Vec<u8>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            for reply in replies {
+                let command = match reply::read(&mut reader) {
+                    Ok(Reply::Array(args)) => args
+                        .into_iter()
+                        .map(|arg| match arg {
+                            Reply::Bulk(arg) => arg,
+                            _ => Vec::new(),
+                        })
+                        .collect(),
+                    _ => return,
+                };
+                let _ = sender.send(command);
+                writer.write_all(reply).unwrap();
+            }
+        });
+        (port, receiver)
+    }
+}
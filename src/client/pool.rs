@@ -0,0 +1,211 @@
+//! Pool of connections shared between threads.
+
+use super::Connection;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::ErrorStruct;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+/// Opens up to `size` connections to a server, when they are needed, and
+/// lends them. A connection goes back to the pool when it is dropped,
+/// unless it was closed or left in the middle of a reply.
+#[derive(Clone)]
+pub struct Pool {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    host: String,
+    port: u16,
+    size: usize,
+    state: Mutex<State>,
+    released: Condvar,
+}
+
+struct State {
+    idle: Vec<Connection>,
+    open: usize,
+}
+
+/// Connection lent by a [Pool].
+pub struct PooledConnection {
+    connection: Option<Connection>,
+    shared: Arc<Shared>,
+}
+
+impl Pool {
+    pub fn new(host: &str, port: u16, size: usize) -> Self {
+        Pool {
+            shared: Arc::new(Shared {
+                host: host.to_string(),
+                port,
+                size: size.max(1),
+                state: Mutex::new(State {
+                    idle: Vec::new(),
+                    open: 0,
+                }),
+                released: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Lends an idle connection, opens a new one, or waits until one is
+    /// returned when all of them are in use.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The server can not be reached.
+    /// * The pool is poisoned.
+    pub fn get(&self) -> Result<PooledConnection, ErrorStruct> {
+        let mut state = self.shared.lock()?;
+        while state.idle.is_empty() && state.open >= self.shared.size {
+            state = self.shared.released.wait(state).map_err(|_| poisoned())?;
+        }
+        self.lend(state)
+    }
+
+    /// Lends a connection as [get](Pool::get) does, without waiting.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * All the connections are in use.
+    /// * The server can not be reached.
+    /// * The pool is poisoned.
+    pub fn try_get(&self) -> Result<PooledConnection, ErrorStruct> {
+        let state = self.shared.lock()?;
+        if state.idle.is_empty() && state.open >= self.shared.size {
+            return Err(ErrorStruct::from(redis_messages::pool_exhausted(
+                self.shared.size,
+            )));
+        }
+        self.lend(state)
+    }
+
+    pub fn size(&self) -> usize {
+        self.shared.size
+    }
+
+    /// Number of connections open, idle or lent.
+    pub fn open(&self) -> usize {
+        self.shared.lock().map(|state| state.open).unwrap_or(0)
+    }
+
+    fn lend(&self, mut state: MutexGuard<'_, State>) -> Result<PooledConnection, ErrorStruct> {
+        let connection = match state.idle.pop() {
+            Some(connection) => connection,
+            None => {
+                state.open += 1;
+                drop(state);
+                Connection::connect(&self.shared.host, self.shared.port)
+                    .inspect_err(|_| self.shared.forget())?
+            }
+        };
+        Ok(PooledConnection {
+            connection: Some(connection),
+            shared: Arc::clone(&self.shared),
+        })
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> Result<MutexGuard<'_, State>, ErrorStruct> {
+        self.state.lock().map_err(|_| poisoned())
+    }
+
+    /// Frees the place of a connection which was closed.
+    fn forget(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.open -= 1;
+        }
+        self.released.notify_one();
+    }
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.connection.as_ref().expect("connection lent")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.connection.as_mut().expect("connection lent")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        match self.connection.take() {
+            Some(connection) if !connection.is_broken() => {
+                if let Ok(mut state) = self.shared.state.lock() {
+                    state.idle.push(connection);
+                }
+                self.shared.released.notify_one();
+            }
+            _ => self.shared.forget(),
+        }
+    }
+}
+
+fn poisoned() -> ErrorStruct {
+    ErrorStruct::from(redis_messages::poisoned_lock(
+        "connection pool",
+        ErrorSeverity::Comunicate,
+    ))
+}
+
+#[cfg(test)]
+mod test_pool {
+    use super::*;
+    use crate::client::test_server::fake_server;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_01_connections_are_reused() {
+        let (port, requests) = fake_server(vec![b"+PONG\r\n\n", b"+PONG\r\n\n"]);
+        let pool = Pool::new("127.0.0.1", port, 2);
+
+        assert_eq!(pool.get().unwrap().ping().unwrap(), "PONG");
+        assert_eq!(pool.get().unwrap().ping().unwrap(), "PONG");
+
+        assert_eq!(pool.open(), 1);
+        assert_eq!(requests.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_02_get_waits_for_a_returned_connection() {
+        let (port, _requests) = fake_server(vec![]);
+        let pool = Pool::new("127.0.0.1", port, 1);
+        let lent = pool.get().unwrap();
+
+        assert_eq!(
+            pool.try_get().err().unwrap().print_it(),
+            "ERR The 1 connections of the pool are in use"
+        );
+        let waiting = {
+            let pool = pool.clone();
+            thread::spawn(move || pool.get().map(|_| ()))
+        };
+        thread::sleep(Duration::from_millis(50));
+        drop(lent);
+
+        assert!(waiting.join().unwrap().is_ok());
+        assert_eq!(pool.open(), 1);
+    }
+
+    #[test]
+    fn test_03_closed_connections_leave_the_pool() {
+        let (port, _requests) = fake_server(vec![]);
+        let pool = Pool::new("127.0.0.1", port, 1);
+
+        assert!(pool.get().unwrap().ping().is_err());
+
+        assert_eq!(pool.open(), 0);
+    }
+}
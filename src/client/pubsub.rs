@@ -0,0 +1,280 @@
+//! Subscriptions to channels, read as an iterator of messages.
+
+use super::cmd::{cmd, Cmd};
+use super::types::{server_error, FromReply, ToArgs};
+use super::{Connection, Reply};
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Message published in a channel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    channel: Option<String>,
+    payload: Vec<u8>,
+}
+
+impl Message {
+    /// The channel of the message. This server only sends the payload, so
+    /// it is known when the connection is subscribed to a single channel.
+    pub fn channel(&self) -> Option<&str> {
+        self.channel.as_deref()
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Converts the payload, as a bulk string reply.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The payload can not be represented in the requested type.
+    pub fn get_payload<T: FromReply>(&self) -> Result<T, ErrorStruct> {
+        T::from_reply(Reply::Bulk(self.payload.clone()))
+    }
+}
+
+/// Connection subscribed to channels, which only receives their messages.
+pub struct Subscription {
+    connection: Connection,
+    channels: Vec<String>,
+    pending: VecDeque<Message>,
+}
+
+impl Connection {
+    /// Subscribes the connection to the channels.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    /// * The server replied with an error.
+    pub fn subscribe<C: ToArgs>(self, channels: C) -> Result<Subscription, ErrorStruct> {
+        let mut subscription = Subscription {
+            connection: self,
+            channels: Vec::new(),
+            pending: VecDeque::new(),
+        };
+        subscription.subscribe(channels)?;
+        Ok(subscription)
+    }
+}
+
+impl Subscription {
+    /// Subscribes to more channels.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    /// * The server replied with an error.
+    pub fn subscribe<C: ToArgs>(&mut self, channels: C) -> Result<(), ErrorStruct> {
+        let mut command = cmd("SUBSCRIBE");
+        command.arg(channels);
+        let names = names(&command);
+        self.connection.send(command.args())?;
+        self.confirm(names.len())?;
+        for name in names {
+            if !self.channels.contains(&name) {
+                self.channels.push(name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Unsubscribes from the channels, or from all of them if none is
+    /// given.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    /// * The server replied with an error.
+    pub fn unsubscribe<C: ToArgs>(&mut self, channels: C) -> Result<(), ErrorStruct> {
+        let mut command = cmd("UNSUBSCRIBE");
+        command.arg(channels);
+        let mut names = names(&command);
+        self.connection.send(command.args())?;
+        if names.is_empty() {
+            names = self.channels.clone();
+        }
+        self.confirm(names.len().max(1))?;
+        self.channels.retain(|channel| !names.contains(channel));
+        Ok(())
+    }
+
+    pub fn channels(&self) -> &[String] {
+        &self.channels
+    }
+
+    /// Sets how long [next_message](Subscription::next_message) waits, or
+    /// [None] to wait forever.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The socket does not accept the timeout.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), ErrorStruct> {
+        self.connection.set_read_timeout(timeout)
+    }
+
+    /// Waits for the next message.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed, or the read timed out.
+    /// * The server sent something which is not a message.
+    pub fn next_message(&mut self) -> Result<Message, ErrorStruct> {
+        if let Some(message) = self.pending.pop_front() {
+            return Ok(message);
+        }
+        let reply = self.connection.read_reply()?;
+        self.message(reply)
+            .ok_or_else(|| ErrorStruct::from(redis_messages::protocol_error("expected a message")))
+    }
+
+    /// Unsubscribes from every channel and returns the connection.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The connection was closed.
+    pub fn into_connection(mut self) -> Result<Connection, ErrorStruct> {
+        self.unsubscribe(Vec::<String>::new())?;
+        Ok(self.connection)
+    }
+
+    /// Reads the confirmations of a SUBSCRIBE or UNSUBSCRIBE, keeping the
+    /// messages received meanwhile. Redis confirms each channel with an
+    /// array, while this server replies the count followed by an empty bulk
+    /// string.
+    fn confirm(&mut self, mut expected: usize) -> Result<(), ErrorStruct> {
+        while expected > 0 {
+            match self.connection.read_reply()? {
+                Reply::Array(elements) if is_confirmation(&elements) => expected -= 1,
+                Reply::Integer(_) => {
+                    self.connection.read_reply()?;
+                    expected = 0;
+                }
+                Reply::Error(text) => return Err(server_error(&text)),
+                reply => match self.message(reply) {
+                    Some(message) => self.pending.push_back(message),
+                    None => {
+                        return Err(ErrorStruct::from(redis_messages::protocol_error(
+                            "expected a subscription confirmation",
+                        )))
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn message(&self, reply: Reply) -> Option<Message> {
+        match reply {
+            Reply::Array(elements) => {
+                let mut elements = elements.into_iter();
+                match (elements.next(), elements.next(), elements.next()) {
+                    (Some(Reply::Bulk(kind)), Some(channel), Some(Reply::Bulk(payload)))
+                        if kind == b"message" =>
+                    {
+                        Some(Message {
+                            channel: String::from_reply(channel).ok(),
+                            payload,
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            Reply::Bulk(payload) => Some(Message {
+                channel: match self.channels.as_slice() {
+                    [channel] => Some(channel.clone()),
+                    _ => None,
+                },
+                payload,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Iterates over the messages until the connection is closed or fails.
+impl Iterator for Subscription {
+    type Item = Message;
+
+    fn next(&mut self) -> Option<Message> {
+        self.next_message().ok()
+    }
+}
+
+fn names(command: &Cmd) -> Vec<String> {
+    command.args()[1..]
+        .iter()
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect()
+}
+
+fn is_confirmation(elements: &[Reply]) -> bool {
+    matches!(
+        elements.first(),
+        Some(Reply::Bulk(kind)) if kind == b"subscribe" || kind == b"unsubscribe"
+    )
+}
+
+#[cfg(test)]
+mod test_pubsub {
+    use super::*;
+    use crate::client::test_server::{args, fake_server};
+
+    #[test]
+    fn test_01_messages_of_this_server_are_bulk_strings() {
+        let (port, requests) = fake_server(vec![
+            b":1\r\n\n$0\r\n\r\n\n$6\r\nhello \r\n\n$3\r\nbye\r\n\n",
+            b":0\r\n\n$0\r\n\r\n\n",
+        ]);
+        let connection = Connection::connect("127.0.0.1", port).unwrap();
+
+        let mut subscription = connection.subscribe("news").unwrap();
+        let first = subscription.next().unwrap();
+        let second = subscription.next_message().unwrap();
+        subscription.into_connection().unwrap();
+
+        assert_eq!(first.channel(), Some("news"));
+        assert_eq!(first.get_payload::<String>().unwrap(), "hello ");
+        assert_eq!(second.payload(), b"bye");
+        let requests: Vec<Vec<Vec<u8>>> = requests.iter().collect();
+        assert_eq!(
+            requests,
+            vec![args(&["SUBSCRIBE", "news"]), args(&["UNSUBSCRIBE"])]
+        );
+    }
+
+    #[test]
+    fn test_02_messages_of_redis_are_arrays() {
+        let (port, _requests) = fake_server(vec![
+            b"*3\r\n$9\r\nsubscribe\r\n$1\r\na\r\n:1\r\n\
+              *3\r\n$7\r\nmessage\r\n$1\r\na\r\n$2\r\nhi\r\n\
+              *3\r\n$9\r\nsubscribe\r\n$1\r\nb\r\n:2\r\n\
+              *3\r\n$7\r\nmessage\r\n$1\r\nb\r\n$3\r\nyou\r\n",
+            b"*3\r\n$11\r\nunsubscribe\r\n$1\r\na\r\n:1\r\n",
+        ]);
+        let connection = Connection::connect("127.0.0.1", port).unwrap();
+
+        let mut subscription = connection.subscribe(["a", "b"]).unwrap();
+        let first = subscription.next().unwrap();
+        let second = subscription.next().unwrap();
+        subscription.unsubscribe("a").unwrap();
+
+        assert_eq!((first.channel(), first.payload()), (Some("a"), &b"hi"[..]));
+        assert_eq!(
+            (second.channel(), second.payload()),
+            (Some("b"), &b"you"[..])
+        );
+        assert_eq!(subscription.channels(), ["b".to_string()]);
+        assert!(subscription.next().is_none());
+    }
+}
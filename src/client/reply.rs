@@ -0,0 +1,214 @@
+//! Replies of the server, as the clients read them, and the encoding of the
+//! commands they send.
+
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use std::io::{BufRead, ErrorKind};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    Status(String),
+    Error(String),
+    Integer(i64),
+    Bulk(Vec<u8>),
+    /// The null bulk string and the null array.
+    Nil,
+    Array(Vec<Reply>),
+}
+
+impl Reply {
+    /// Name of the type of the reply, for the error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Reply::Status(_) => "a status",
+            Reply::Error(_) => "an error",
+            Reply::Integer(_) => "an integer",
+            Reply::Bulk(_) => "a bulk string",
+            Reply::Nil => "nil",
+            Reply::Array(_) => "an array",
+        }
+    }
+}
+
+/// Encodes a command as an array of bulk strings.
+pub fn encode_command<A: AsRef<[u8]>>(args: &[A]) -> Vec<u8> {
+    let mut bytes = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args.iter() {
+        let arg = arg.as_ref();
+        bytes.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        bytes.extend_from_slice(arg);
+        bytes.extend_from_slice(b"\r\n");
+    }
+    bytes
+}
+
+/// Reads the next reply. The empty lines before it are skipped, as this
+/// server follows each reply with one.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The connection was closed before the whole reply was read.
+/// * The reply does not follow the protocol.
+pub fn read<R: BufRead>(reader: &mut R) -> Result<Reply, ErrorStruct> {
+    let mut line = read_line(reader)?;
+    while line.is_empty() {
+        line = read_line(reader)?;
+    }
+    read_after_header(reader, line)
+}
+
+fn read_after_header<R: BufRead>(reader: &mut R, line: Vec<u8>) -> Result<Reply, ErrorStruct> {
+    let text = String::from_utf8_lossy(&line[1..]).into_owned();
+    match line[0] {
+        b'+' => Ok(Reply::Status(text)),
+        b'-' => Ok(Reply::Error(text)),
+        b':' => Ok(Reply::Integer(parse_number(&text)?)),
+        b'$' => match parse_number(&text)? {
+            -1 => Ok(Reply::Nil),
+            len if len >= 0 => {
+                let mut bytes = vec![0; len as usize + 2];
+                reader.read_exact(&mut bytes).map_err(|error| {
+                    if error.kind() == ErrorKind::UnexpectedEof {
+                        closed()
+                    } else {
+                        invalid("can not read the bulk string")
+                    }
+                })?;
+                if !bytes.ends_with(b"\r\n") {
+                    return Err(invalid("bulk string not followed by CRLF"));
+                }
+                bytes.truncate(len as usize);
+                Ok(Reply::Bulk(bytes))
+            }
+            _ => Err(invalid("invalid bulk length")),
+        },
+        b'*' => match parse_number(&text)? {
+            -1 => Ok(Reply::Nil),
+            len if len >= 0 => {
+                let mut elements = Vec::with_capacity(len as usize);
+                for _ in 0..len {
+                    let line = read_line(reader)?;
+                    if line.is_empty() {
+                        return Err(invalid("empty line inside an array"));
+                    }
+                    elements.push(read_after_header(reader, line)?);
+                }
+                Ok(Reply::Array(elements))
+            }
+            _ => Err(invalid("invalid multibulk length")),
+        },
+        other => Err(invalid(&format!(
+            "expected '+', '-', ':', '$' or '*', got '{}'",
+            other as char
+        ))),
+    }
+}
+
+/// Reads a line without its line break.
+fn read_line<R: BufRead>(reader: &mut R) -> Result<Vec<u8>, ErrorStruct> {
+    let mut line = Vec::new();
+    match reader.read_until(b'\n', &mut line) {
+        Ok(0) => return Err(closed()),
+        Ok(_) if !line.ends_with(b"\n") => return Err(closed()),
+        Ok(_) => {}
+        Err(_) => return Err(closed()),
+    }
+    line.pop();
+    if line.ends_with(b"\r") {
+        line.pop();
+    }
+    Ok(line)
+}
+
+fn parse_number(text: &str) -> Result<i64, ErrorStruct> {
+    text.parse()
+        .map_err(|_| invalid(&format!("invalid number '{}'", text)))
+}
+
+fn closed() -> ErrorStruct {
+    ErrorStruct::from(redis_messages::connection_closed())
+}
+
+fn invalid(detail: &str) -> ErrorStruct {
+    ErrorStruct::from(redis_messages::protocol_error(detail))
+}
+
+#[cfg(test)]
+mod test_reply {
+    use super::*;
+
+    fn read_all(bytes: &[u8]) -> Vec<Result<Reply, ErrorStruct>> {
+        let mut reader = bytes;
+        let mut replies = Vec::new();
+        while !reader.is_empty() {
+            let reply = read(&mut reader);
+            let failed = reply.is_err();
+            replies.push(reply);
+            if failed {
+                break;
+            }
+        }
+        replies
+    }
+
+    #[test]
+    fn test_01_every_type_is_read() {
+        let replies = read_all(
+            b"+OK\r\n\n-ERR wrong\r\n\n:-5\r\n\n$3\r\na\r\n\r\n\n$-1\r\n\n*-1\r\n\n*0\r\n\n\
+              *2\r\n*2\r\n$1\r\nx\r\n:1\r\n$0\r\n\r\n",
+        );
+        let replies: Vec<Reply> = replies.into_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            replies,
+            vec![
+                Reply::Status("OK".to_string()),
+                Reply::Error("ERR wrong".to_string()),
+                Reply::Integer(-5),
+                Reply::Bulk(b"a\r\n".to_vec()),
+                Reply::Nil,
+                Reply::Nil,
+                Reply::Array(vec![]),
+                Reply::Array(vec![
+                    Reply::Array(vec![Reply::Bulk(b"x".to_vec()), Reply::Integer(1)]),
+                    Reply::Bulk(vec![]),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_02_commands_are_encoded_as_bulk_strings() {
+        assert_eq!(
+            encode_command(&["SET", "key", ""]),
+            b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$0\r\n\r\n".to_vec()
+        );
+        let command = encode_command(&[b"GET".to_vec(), vec![0, 255]]);
+        assert_eq!(
+            read(&mut &command[..]).unwrap(),
+            Reply::Array(vec![
+                Reply::Bulk(b"GET".to_vec()),
+                Reply::Bulk(vec![0, 255])
+            ])
+        );
+    }
+
+    #[test]
+    fn test_03_invalid_or_incomplete_replies_fail() {
+        assert_eq!(
+            read(&mut &b"?x\r\n"[..]).unwrap_err().print_it(),
+            "ERR Protocol error: expected '+', '-', ':', '$' or '*', got '?'"
+        );
+        assert_eq!(
+            read(&mut &b":one\r\n"[..]).unwrap_err().print_it(),
+            "ERR Protocol error: invalid number 'one'"
+        );
+        assert_eq!(
+            read(&mut &b"$5\r\nab"[..]).unwrap_err().print_it(),
+            "ERR Server closed the connection"
+        );
+        assert!(read(&mut &b"*2\r\n:1\r\n"[..]).is_err());
+        assert!(read(&mut &b"+OK"[..]).is_err());
+        assert!(read(&mut &b""[..]).is_err());
+    }
+}
@@ -0,0 +1,289 @@
+//! Conversions between the Rust types and the arguments of the commands or
+//! the replies of the server.
+
+use super::Reply;
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Types which can be sent as arguments of a command. A collection is sent
+/// as one argument per element.
+pub trait ToArgs {
+    fn write_args(&self, args: &mut Vec<Vec<u8>>);
+}
+
+/// Types in which a reply of the server can be converted.
+pub trait FromReply: Sized {
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The reply is an error of the server.
+    /// * The reply can not be represented in this type.
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct>;
+}
+
+/// Converts an error reply, as `WRONGTYPE Operation against...`, keeping
+/// its prefix.
+pub fn server_error(text: &str) -> ErrorStruct {
+    match text.split_once(' ') {
+        Some((prefix, message)) => ErrorStruct::new(prefix.to_string(), message.to_string()),
+        None => ErrorStruct::new(text.to_string(), String::new()),
+    }
+}
+
+fn unexpected(expected: &str, reply: &Reply) -> ErrorStruct {
+    match reply {
+        Reply::Error(text) => server_error(text),
+        _ => ErrorStruct::from(redis_messages::unexpected_reply(expected, reply.kind())),
+    }
+}
+
+impl ToArgs for str {
+    fn write_args(&self, args: &mut Vec<Vec<u8>>) {
+        args.push(self.as_bytes().to_vec());
+    }
+}
+
+impl ToArgs for String {
+    fn write_args(&self, args: &mut Vec<Vec<u8>>) {
+        args.push(self.as_bytes().to_vec());
+    }
+}
+
+impl ToArgs for [u8] {
+    fn write_args(&self, args: &mut Vec<Vec<u8>>) {
+        args.push(self.to_vec());
+    }
+}
+
+impl ToArgs for Vec<u8> {
+    fn write_args(&self, args: &mut Vec<Vec<u8>>) {
+        args.push(self.clone());
+    }
+}
+
+macro_rules! numbers_to_args {
+    ($($number:ty),*) => {
+        $(impl ToArgs for $number {
+            fn write_args(&self, args: &mut Vec<Vec<u8>>) {
+                args.push(self.to_string().into_bytes());
+            }
+        })*
+    };
+}
+
+numbers_to_args!(i32, i64, isize, u32, u64, usize, f64);
+
+impl<T: ToArgs + ?Sized> ToArgs for &T {
+    fn write_args(&self, args: &mut Vec<Vec<u8>>) {
+        (*self).write_args(args)
+    }
+}
+
+impl<T: ToArgs> ToArgs for [T] {
+    fn write_args(&self, args: &mut Vec<Vec<u8>>) {
+        for element in self.iter() {
+            element.write_args(args);
+        }
+    }
+}
+
+impl<T: ToArgs, const N: usize> ToArgs for [T; N] {
+    fn write_args(&self, args: &mut Vec<Vec<u8>>) {
+        self[..].write_args(args)
+    }
+}
+
+impl<T: ToArgs> ToArgs for Vec<T> {
+    fn write_args(&self, args: &mut Vec<Vec<u8>>) {
+        self.as_slice().write_args(args)
+    }
+}
+
+impl<A: ToArgs, B: ToArgs> ToArgs for (A, B) {
+    fn write_args(&self, args: &mut Vec<Vec<u8>>) {
+        self.0.write_args(args);
+        self.1.write_args(args);
+    }
+}
+
+impl FromReply for Reply {
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+        Ok(reply)
+    }
+}
+
+impl FromReply for () {
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+        match reply {
+            Reply::Error(text) => Err(server_error(&text)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FromReply for String {
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+        match reply {
+            Reply::Status(text) => Ok(text),
+            Reply::Integer(number) => Ok(number.to_string()),
+            Reply::Bulk(bytes) => String::from_utf8(bytes)
+                .map_err(|error| unexpected("a string", &Reply::Bulk(error.into_bytes()))),
+            other => Err(unexpected("a string", &other)),
+        }
+    }
+}
+
+impl FromReply for Vec<u8> {
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+        match reply {
+            Reply::Bulk(bytes) => Ok(bytes),
+            Reply::Status(text) => Ok(text.into_bytes()),
+            Reply::Integer(number) => Ok(number.to_string().into_bytes()),
+            other => Err(unexpected("a bulk string", &other)),
+        }
+    }
+}
+
+macro_rules! numbers_from_reply {
+    ($($number:ty),*) => {
+        $(impl FromReply for $number {
+            fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+                let parsed = match &reply {
+                    Reply::Integer(number) => number.to_string().parse().ok(),
+                    Reply::Status(text) => text.parse().ok(),
+                    Reply::Bulk(bytes) => String::from_utf8_lossy(bytes).parse().ok(),
+                    _ => None,
+                };
+                parsed.ok_or_else(|| unexpected("a number", &reply))
+            }
+        })*
+    };
+}
+
+numbers_from_reply!(i32, i64, isize, u32, u64, usize, f64);
+
+impl FromReply for bool {
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+        match reply {
+            Reply::Integer(number) => Ok(number != 0),
+            Reply::Status(text) => Ok(text == "OK"),
+            Reply::Nil => Ok(false),
+            other => Err(unexpected("a boolean", &other)),
+        }
+    }
+}
+
+impl<T: FromReply> FromReply for Option<T> {
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+        match reply {
+            Reply::Nil => Ok(None),
+            other => T::from_reply(other).map(Some),
+        }
+    }
+}
+
+impl<T: FromReply> FromReply for Vec<T> {
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+        match reply {
+            Reply::Array(elements) => elements.into_iter().map(T::from_reply).collect(),
+            Reply::Nil => Ok(Vec::new()),
+            other => Err(unexpected("an array", &other)),
+        }
+    }
+}
+
+impl<T: FromReply + Eq + Hash> FromReply for HashSet<T> {
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+        Ok(Vec::<T>::from_reply(reply)?.into_iter().collect())
+    }
+}
+
+/// From an array of fields followed by their values.
+impl<K: FromReply + Eq + Hash, V: FromReply> FromReply for HashMap<K, V> {
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+        let mut elements = Vec::<Reply>::from_reply(reply)?.into_iter();
+        let mut map = HashMap::new();
+        while let Some(key) = elements.next() {
+            let value = elements
+                .next()
+                .ok_or_else(|| unexpected("an array of pairs", &Reply::Nil))?;
+            map.insert(K::from_reply(key)?, V::from_reply(value)?);
+        }
+        Ok(map)
+    }
+}
+
+impl<A: FromReply, B: FromReply> FromReply for (A, B) {
+    fn from_reply(reply: Reply) -> Result<Self, ErrorStruct> {
+        match reply {
+            Reply::Array(elements) if elements.len() == 2 => {
+                let mut elements = elements.into_iter();
+                let first = A::from_reply(elements.next().unwrap_or(Reply::Nil))?;
+                let second = B::from_reply(elements.next().unwrap_or(Reply::Nil))?;
+                Ok((first, second))
+            }
+            other => Err(unexpected("an array of two elements", &other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_types {
+    use super::*;
+
+    fn args<T: ToArgs + ?Sized>(value: &T) -> Vec<Vec<u8>> {
+        let mut args = Vec::new();
+        value.write_args(&mut args);
+        args
+    }
+
+    #[test]
+    fn test_01_collections_are_sent_as_one_argument_per_element() {
+        assert_eq!(args("key"), vec![b"key".to_vec()]);
+        assert_eq!(args(&b"\x00\xff"[..]), vec![vec![0, 255]]);
+        assert_eq!(args(&-5), vec![b"-5".to_vec()]);
+        assert_eq!(args(&vec!["a", "b"]), vec![b"a".to_vec(), b"b".to_vec()]);
+        assert_eq!(
+            args(&[("k1", 1), ("k2", 2)][..]),
+            vec![b"k1".to_vec(), b"1".to_vec(), b"k2".to_vec(), b"2".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_02_replies_are_converted_to_the_requested_type() {
+        assert_eq!(i64::from_reply(Reply::Integer(3)).unwrap(), 3);
+        assert_eq!(i64::from_reply(Reply::Bulk(b"12".to_vec())).unwrap(), 12);
+        assert_eq!(
+            String::from_reply(Reply::Status("OK".into())).unwrap(),
+            "OK"
+        );
+        assert_eq!(Option::<String>::from_reply(Reply::Nil).unwrap(), None);
+        assert!(bool::from_reply(Reply::Integer(1)).unwrap());
+        let array = Reply::Array(vec![Reply::Bulk(b"a".to_vec()), Reply::Bulk(b"1".to_vec())]);
+        assert_eq!(
+            HashMap::<String, i64>::from_reply(array.clone()).unwrap(),
+            vec![("a".to_string(), 1)].into_iter().collect()
+        );
+        assert_eq!(
+            <(String, String)>::from_reply(array).unwrap(),
+            ("a".to_string(), "1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_03_errors_keep_the_prefix_of_the_server() {
+        let error = String::from_reply(Reply::Error(
+            "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+        ))
+        .unwrap_err();
+        assert_eq!(error.prefix(), Some("WRONGTYPE"));
+        assert_eq!(
+            i64::from_reply(Reply::Bulk(b"one".to_vec()))
+                .unwrap_err()
+                .print_it(),
+            "ERR Unexpected reply: expected a number, got a bulk string"
+        );
+    }
+}
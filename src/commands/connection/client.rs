@@ -0,0 +1,271 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{
+        connection::{
+            client_caching::ClientCaching, client_getname::ClientGetname,
+            client_getredir::ClientGetredir, client_id::ClientId, client_info::ClientInfo,
+            client_kill::ClientKill, client_list::ClientList, client_no_evict::ClientNoEvict,
+            client_pause::ClientPause, client_setname::ClientSetname,
+            client_tracking::ClientTracking, client_unpause::ClientUnpause,
+        },
+        Runnable,
+    },
+    messages::redis_messages,
+    native_types::ErrorStruct,
+};
+
+pub struct Client;
+
+impl Runnable<ServerRedisAttributes> for Client {
+    /// Inspects and manages the connections of the server.
+    ///
+    /// # Sub Commands
+    ///
+    /// * LIST: Shows the information of the connected clients.
+    /// * INFO: Shows the information of the current client.
+    /// * KILL: Closes the connection of some clients.
+    /// * SETNAME / GETNAME: Sets and gets the name of the current client.
+    /// * ID: Returns the id of the current client.
+    /// * PAUSE / UNPAUSE: Suspends and resumes the commands of the clients.
+    /// * NO-EVICT: Excludes the current client from the eviction.
+    /// * TRACKING / CACHING / GETREDIR: Controls the client-side caching.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * User does not give a supported subcommand.
+    /// * [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes) has poisoned methods.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        let mut subcommand = buffer.remove(0);
+        subcommand.make_ascii_lowercase();
+        match subcommand.as_str() {
+            "list" => ClientList.run(buffer, server),
+            "info" => ClientInfo.run(buffer, server),
+            "kill" => ClientKill.run(buffer, server),
+            "setname" => ClientSetname.run(buffer, server),
+            "getname" => ClientGetname.run(buffer, server),
+            "id" => ClientId.run(buffer, server),
+            "pause" => ClientPause.run(buffer, server),
+            "unpause" => ClientUnpause.run(buffer, server),
+            "no-evict" => ClientNoEvict.run(buffer, server),
+            "tracking" => ClientTracking.run(buffer, server),
+            "caching" => ClientCaching.run(buffer, server),
+            "getredir" => ClientGetredir.run(buffer, server),
+            _ => Err(ErrorStruct::from(redis_messages::unknown_subcommand(
+                &("client|".to_owned() + &subcommand),
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_client {
+    use super::*;
+    use crate::vec_strings;
+    use crate::{
+        commands,
+        tcp_protocol::{client_atributes::client_fields::ClientFields, client_aware::ClientAware},
+        time_expiration::clock::SystemClock,
+    };
+    use std::net::{Ipv4Addr, SocketAddrV4};
+    use std::sync::{Arc, Mutex};
+
+    fn create_server() -> (ServerRedisAttributes, Arc<Mutex<ClientFields>>) {
+        let mut server = commands::create_server("client");
+        let client = Arc::new(Mutex::new(ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        )));
+        server.set_current_client(Arc::clone(&client));
+        (server, client)
+    }
+
+    #[test]
+    fn test_01_client_id() {
+        let (mut server, client) = create_server();
+        let id = client.lock().unwrap().id();
+        let result = Client.run(vec_strings!["ID"], &mut server).unwrap();
+        assert_eq!(result, format!(":{}\r\n", id));
+    }
+
+    #[test]
+    fn test_02_client_getname_without_name() {
+        let (mut server, _client) = create_server();
+        let result = Client.run(vec_strings!["getname"], &mut server).unwrap();
+        assert_eq!(result, "$-1\r\n");
+    }
+
+    #[test]
+    fn test_03_client_setname_and_getname() {
+        let (mut server, _client) = create_server();
+        let result = Client
+            .run(vec_strings!["setname", "martina"], &mut server)
+            .unwrap();
+        assert_eq!(result, "+OK\r\n");
+        let result = Client.run(vec_strings!["getname"], &mut server).unwrap();
+        assert_eq!(result, "$7\r\nmartina\r\n");
+    }
+
+    #[test]
+    fn test_04_client_setname_with_spaces() {
+        let (mut server, _client) = create_server();
+        let error = Client
+            .run(vec_strings!["setname", "mar tina"], &mut server)
+            .unwrap_err();
+        assert_eq!(
+            error.print_it(),
+            "ERR Client names cannot contain spaces, newlines or special characters."
+        );
+    }
+
+    #[test]
+    fn test_05_client_info() {
+        let (mut server, client) = create_server();
+        let id = client.lock().unwrap().id();
+        let result = Client.run(vec_strings!["info"], &mut server).unwrap();
+        assert!(result.contains(&format!("id={} addr=127.0.0.1:8080", id)));
+        assert!(result.ends_with("\n\r\n"));
+    }
+
+    #[test]
+    fn test_06_client_list_without_connections() {
+        let (mut server, _client) = create_server();
+        let result = Client.run(vec_strings!["list"], &mut server).unwrap();
+        assert_eq!(result, "$0\r\n\r\n");
+    }
+
+    #[test]
+    fn test_07_client_list_with_unknown_type() {
+        let (mut server, _client) = create_server();
+        let error = Client
+            .run(vec_strings!["list", "type", "tv"], &mut server)
+            .unwrap_err();
+        assert_eq!(error.print_it(), "ERR Unknown client type 'tv'");
+    }
+
+    #[test]
+    fn test_08_client_kill_unknown_address() {
+        let (mut server, _client) = create_server();
+        let error = Client
+            .run(vec_strings!["kill", "127.0.0.1:1"], &mut server)
+            .unwrap_err();
+        assert_eq!(error.print_it(), "ERR No such client");
+    }
+
+    #[test]
+    fn test_09_client_kill_with_filters() {
+        let (mut server, _client) = create_server();
+        let result = Client
+            .run(
+                vec_strings!["kill", "type", "normal", "skipme", "no"],
+                &mut server,
+            )
+            .unwrap();
+        assert_eq!(result, ":0\r\n");
+    }
+
+    #[test]
+    fn test_10_client_pause_and_unpause() {
+        let (mut server, _client) = create_server();
+        let result = Client
+            .run(vec_strings!["pause", "10000", "write"], &mut server)
+            .unwrap();
+        assert_eq!(result, "+OK\r\n");
+        assert!(server.get_client_pause().is_paused("set").unwrap());
+        assert!(!server.get_client_pause().is_paused("get").unwrap());
+
+        let result = Client.run(vec_strings!["unpause"], &mut server).unwrap();
+        assert_eq!(result, "+OK\r\n");
+        assert!(!server.get_client_pause().is_paused("set").unwrap());
+    }
+
+    #[test]
+    fn test_11_client_pause_with_invalid_timeout() {
+        let (mut server, _client) = create_server();
+        let error = Client
+            .run(vec_strings!["pause", "soon"], &mut server)
+            .unwrap_err();
+        assert_eq!(
+            error.print_it(),
+            "ERR timeout is not an integer or out of range"
+        );
+    }
+
+    #[test]
+    fn test_12_client_no_evict() {
+        let (mut server, client) = create_server();
+        let result = Client
+            .run(vec_strings!["no-evict", "on"], &mut server)
+            .unwrap();
+        assert_eq!(result, "+OK\r\n");
+        assert_eq!(client.lock().unwrap().flags(), "e");
+    }
+
+    #[test]
+    fn test_13_unknown_subcommand() {
+        let (mut server, _client) = create_server();
+        let error = Client.run(vec_strings!["dance"], &mut server).unwrap_err();
+        assert_eq!(
+            error.print_it(),
+            "ERR Unknown subcommand or wrong number of arguments for 'client|dance'"
+        );
+    }
+
+    #[test]
+    fn test_14_client_tracking_and_getredir() {
+        let (mut server, _client) = create_server();
+        let result = Client.run(vec_strings!["getredir"], &mut server).unwrap();
+        assert_eq!(result, ":-1\r\n");
+
+        let result = Client
+            .run(vec_strings!["tracking", "on", "noloop"], &mut server)
+            .unwrap();
+        assert_eq!(result, "+OK\r\n");
+        let result = Client.run(vec_strings!["getredir"], &mut server).unwrap();
+        assert_eq!(result, ":0\r\n");
+
+        Client
+            .run(vec_strings!["tracking", "off"], &mut server)
+            .unwrap();
+        let result = Client.run(vec_strings!["getredir"], &mut server).unwrap();
+        assert_eq!(result, ":-1\r\n");
+    }
+
+    #[test]
+    fn test_15_client_tracking_prefix_without_bcast() {
+        let (mut server, _client) = create_server();
+        let error = Client
+            .run(
+                vec_strings!["tracking", "on", "prefix", "user:"],
+                &mut server,
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.print_it(),
+            "ERR PREFIX option requires BCAST mode to be enabled"
+        );
+    }
+
+    #[test]
+    fn test_16_client_caching_requires_optin_or_optout() {
+        let (mut server, _client) = create_server();
+        Client
+            .run(vec_strings!["tracking", "on"], &mut server)
+            .unwrap();
+        assert!(Client
+            .run(vec_strings!["caching", "yes"], &mut server)
+            .is_err());
+
+        Client
+            .run(vec_strings!["tracking", "on", "optin"], &mut server)
+            .unwrap();
+        let result = Client
+            .run(vec_strings!["caching", "yes"], &mut server)
+            .unwrap();
+        assert_eq!(result, "+OK\r\n");
+    }
+}
@@ -0,0 +1,40 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{connection::with_current_client, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+};
+
+pub struct ClientCaching;
+
+impl Runnable<ServerRedisAttributes> for ClientCaching {
+    /// Controls whether the keys read by the next command of the client are
+    /// remembered, when the tracking is in OPTIN or OPTOUT mode.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: OK.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The argument is not YES or NO.
+    /// * The tracking of the client is not in OPTIN or OPTOUT mode.
+    /// * [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes) has poisoned methods.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        if buffer.len() != 1 {
+            return Err(ErrorStruct::from(redis_messages::syntax_error()));
+        }
+        let caching = match buffer[0].to_lowercase().as_str() {
+            "yes" => true,
+            "no" => false,
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        };
+        let id = with_current_client(server, |fields| fields.id())?;
+        server.get_client_tracking().set_caching(id, caching)?;
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
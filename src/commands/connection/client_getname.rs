@@ -0,0 +1,32 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{check_not_empty, connection::with_current_client, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RBulkString, RedisType},
+};
+
+pub struct ClientGetname;
+
+impl Runnable<ServerRedisAttributes> for ClientGetname {
+    /// Returns the name of the current connection, as set by CLIENT SETNAME.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the name of the connection, or nil if it has none.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Any argument is received.
+    /// * [ClientFields](crate::tcp_protocol::client_atributes::client_fields::ClientFields) is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_not_empty(&buffer)?;
+        let name = with_current_client(server, |fields| fields.name().cloned())?;
+        Ok(RBulkString::encode(
+            name.unwrap_or_else(redis_messages::nil),
+        ))
+    }
+}
@@ -0,0 +1,34 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{check_not_empty, connection::with_current_client, Runnable},
+    native_types::{ErrorStruct, RInteger, RedisType},
+};
+
+pub struct ClientGetredir;
+
+impl Runnable<ServerRedisAttributes> for ClientGetredir {
+    /// Returns the id of the client that receives the invalidations of the current one.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the id of the REDIRECT client,
+    /// 0 if the tracking is on without redirection, or -1 if it is off.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Any argument is received.
+    /// * [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes) has poisoned methods.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_not_empty(&buffer)?;
+        let id = with_current_client(server, |fields| fields.id())?;
+        let redirect = match server.get_client_tracking().options(id)? {
+            Some(options) => options.redirect.map_or(0, |redirect| redirect as isize),
+            None => -1,
+        };
+        Ok(RInteger::encode(redirect))
+    }
+}
@@ -0,0 +1,29 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{check_not_empty, connection::with_current_client, Runnable},
+    native_types::{ErrorStruct, RInteger, RedisType},
+};
+
+pub struct ClientId;
+
+impl Runnable<ServerRedisAttributes> for ClientId {
+    /// Returns the id of the current connection. Ids are never repeated.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the id of the client.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Any argument is received.
+    /// * [ClientFields](crate::tcp_protocol::client_atributes::client_fields::ClientFields) is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_not_empty(&buffer)?;
+        let id = with_current_client(server, |fields| fields.id())?;
+        Ok(RInteger::encode(id as isize))
+    }
+}
@@ -0,0 +1,33 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{
+        check_not_empty,
+        connection::{join_info_lines, with_current_client},
+        Runnable,
+    },
+    native_types::{ErrorStruct, RBulkString, RedisType},
+};
+
+pub struct ClientInfo;
+
+impl Runnable<ServerRedisAttributes> for ClientInfo {
+    /// Returns information about the current connection, in the same format as CLIENT LIST.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the line of the client.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Any argument is received.
+    /// * [ClientFields](crate::tcp_protocol::client_atributes::client_fields::ClientFields) is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_not_empty(&buffer)?;
+        let info = with_current_client(server, |fields| fields.get_info())?;
+        Ok(RBulkString::encode(join_info_lines(vec![info])))
+    }
+}
@@ -0,0 +1,118 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{
+        check_empty,
+        connection::{parse_client_id, parse_client_type, with_client_list, with_current_client},
+        Runnable,
+    },
+    messages::redis_messages,
+    native_types::{ErrorStruct, RInteger, RSimpleString, RedisType},
+    tcp_protocol::client_atributes::client_fields::ClientFields,
+};
+
+pub struct ClientKill;
+
+/// Filters accepted by the new form of CLIENT KILL.
+#[derive(Default)]
+struct KillFilter {
+    id: Option<u64>,
+    addr: Option<String>,
+    user: Option<String>,
+    client_type: Option<String>,
+    skip_me: Option<u64>,
+}
+
+impl KillFilter {
+    fn matches(&self, fields: &ClientFields) -> bool {
+        self.id.is_none_or(|id| fields.id() == id)
+            && self
+                .addr
+                .as_ref()
+                .is_none_or(|addr| fields.get_addr() == *addr)
+            && self.user.as_ref().is_none_or(|user| fields.user() == *user)
+            && self
+                .client_type
+                .as_ref()
+                .is_none_or(|client_type| fields.client_type() == *client_type)
+            && self.skip_me.is_none_or(|own_id| fields.id() != own_id)
+    }
+}
+
+impl Runnable<ServerRedisAttributes> for ClientKill {
+    /// Closes the connection of the clients.
+    ///
+    /// * CLIENT KILL ip:port: closes the connection with the given address.
+    /// * CLIENT KILL \[ID id\] \[ADDR ip:port\] \[USER user\] \[TYPE type\] \[SKIPME yes/no\]:
+    ///   closes every connection that satisfies all the filters. By default,
+    ///   the client that sends the command is not closed.
+    ///
+    /// # Return value
+    /// * [String] _encoded_ in [RSimpleString]: OK, for the ip:port form.
+    /// * [String] _encoded_ in [RInteger]: the number of clients killed, for the filters form.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * There is no client with the given ip:port.
+    /// * A filter is unknown or it has no value.
+    /// * [ClientList](crate::tcp_protocol::client_list::ClientList) is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_empty(&buffer, "client|kill")?;
+
+        if buffer.len() == 1 {
+            let addr = buffer[0].to_string();
+            let killed = with_client_list(server, |clients| {
+                clients.kill_clients(|fields| fields.get_addr() == addr)
+            })?;
+            return if killed > 0 {
+                Ok(RSimpleString::encode(redis_messages::ok()))
+            } else {
+                Err(ErrorStruct::from(redis_messages::no_such_client()))
+            };
+        }
+
+        let filter = parse_filters(&buffer, server)?;
+        let killed = with_client_list(server, |clients| {
+            clients.kill_clients(|fields| filter.matches(fields))
+        })?;
+        Ok(RInteger::encode(killed as isize))
+    }
+}
+
+/// Builds the [KillFilter] from the pairs of filter and value received.
+fn parse_filters(
+    buffer: &[String],
+    server: &ServerRedisAttributes,
+) -> Result<KillFilter, ErrorStruct> {
+    if buffer.len() % 2 == 1 {
+        return Err(ErrorStruct::from(redis_messages::syntax_error()));
+    }
+
+    let mut filter = KillFilter::default();
+    let mut skip_me = true;
+    for pair in buffer.chunks(2) {
+        let value = &pair[1];
+        match pair[0].to_lowercase().as_str() {
+            "id" => filter.id = Some(parse_client_id(value)?),
+            "addr" => filter.addr = Some(value.to_string()),
+            "user" => filter.user = Some(value.to_string()),
+            "type" => filter.client_type = Some(parse_client_type(value)?),
+            "skipme" => {
+                skip_me = match value.to_lowercase().as_str() {
+                    "yes" => true,
+                    "no" => false,
+                    _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+                }
+            }
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        }
+    }
+    if skip_me {
+        filter.skip_me = with_current_client(server, |fields| fields.id()).ok();
+    }
+    Ok(filter)
+}
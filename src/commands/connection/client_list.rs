@@ -0,0 +1,71 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{
+        connection::{join_info_lines, parse_client_id, parse_client_type, with_client_list},
+        Runnable,
+    },
+    messages::redis_messages,
+    native_types::{ErrorStruct, RBulkString, RedisType},
+};
+
+pub struct ClientList;
+
+impl Runnable<ServerRedisAttributes> for ClientList {
+    /// Returns information and statistics about the client connections server.
+    /// With TYPE only the clients of the given type are listed, and with ID
+    /// only the clients with the given ids.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: one line per client, with the fields
+    /// id, addr, name, age, idle, flags, db, sub, psub, cmd and user.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The filter is not TYPE or ID, or it has no value.
+    /// * The client type or any of the ids are not valid.
+    /// * [ClientList](crate::tcp_protocol::client_list::ClientList) is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        let mut client_type: Option<String> = None;
+        let mut ids: Option<Vec<u64>> = None;
+
+        let mut args = buffer.iter();
+        while let Some(filter) = args.next() {
+            match filter.to_lowercase().as_str() {
+                "type" => {
+                    let value = args.next().ok_or_else(syntax_error)?;
+                    client_type = Some(parse_client_type(value)?);
+                }
+                "id" => {
+                    let values = args
+                        .by_ref()
+                        .map(|id| parse_client_id(id))
+                        .collect::<Result<Vec<u64>, ErrorStruct>>()?;
+                    if values.is_empty() {
+                        return Err(syntax_error());
+                    }
+                    ids = Some(values);
+                }
+                _ => return Err(syntax_error()),
+            }
+        }
+
+        let lines = with_client_list(server, |clients| {
+            clients.clients_info(|fields| {
+                client_type
+                    .as_ref()
+                    .is_none_or(|client_type| fields.client_type() == *client_type)
+                    && ids.as_ref().is_none_or(|ids| ids.contains(&fields.id()))
+            })
+        })?;
+        Ok(RBulkString::encode(join_info_lines(lines)))
+    }
+}
+
+fn syntax_error() -> ErrorStruct {
+    ErrorStruct::from(redis_messages::syntax_error())
+}
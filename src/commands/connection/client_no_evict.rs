@@ -0,0 +1,37 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{connection::with_current_client, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+};
+
+pub struct ClientNoEvict;
+
+impl Runnable<ServerRedisAttributes> for ClientNoEvict {
+    /// Turns on or off the protection of the current connection against the eviction.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: OK.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The argument is not ON or OFF.
+    /// * [ClientFields](crate::tcp_protocol::client_atributes::client_fields::ClientFields) is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        if buffer.len() != 1 {
+            return Err(ErrorStruct::from(redis_messages::syntax_error()));
+        }
+        let no_evict = match buffer[0].to_lowercase().as_str() {
+            "on" => true,
+            "off" => false,
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        };
+        with_current_client(server, |fields| fields.set_no_evict(no_evict))?;
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
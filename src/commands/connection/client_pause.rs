@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{check_empty, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+    tcp_protocol::client_pause::PauseMode,
+};
+
+pub struct ClientPause;
+
+impl Runnable<ServerRedisAttributes> for ClientPause {
+    /// Suspends the commands of all the clients for the given milliseconds.
+    ///
+    /// * WRITE: only the commands that can modify the dataset are suspended.
+    /// * ALL (default): every command is suspended.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: OK if the clients were paused.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The timeout is not a positive integer.
+    /// * The mode is not WRITE or ALL.
+    /// * The state of the pause is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_empty(&buffer, "client|pause")?;
+        if buffer.len() > 2 {
+            return Err(ErrorStruct::from(redis_messages::syntax_error()));
+        }
+
+        let timeout = buffer[0]
+            .parse::<u64>()
+            .map_err(|_| ErrorStruct::from(redis_messages::timeout_not_integer()))?;
+        let mode = match buffer.get(1).map(|mode| mode.to_lowercase()).as_deref() {
+            None | Some("all") => PauseMode::All,
+            Some("write") => PauseMode::Write,
+            Some(_) => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        };
+
+        server
+            .get_client_pause()
+            .pause(mode, Duration::from_millis(timeout))?;
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
@@ -0,0 +1,39 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{connection::with_current_client, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+};
+
+pub struct ClientSetname;
+
+impl Runnable<ServerRedisAttributes> for ClientSetname {
+    /// Assigns a name to the current connection. An empty name removes it.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: OK if the name was set.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The number of arguments is not one.
+    /// * The name contains spaces, newlines or special characters.
+    /// * [ClientFields](crate::tcp_protocol::client_atributes::client_fields::ClientFields) is poisoned.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        if buffer.len() != 1 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "client|setname",
+            )));
+        }
+        let name = buffer.remove(0);
+        if name.chars().any(|c| !('!'..='~').contains(&c)) {
+            return Err(ErrorStruct::from(redis_messages::invalid_client_name()));
+        }
+        with_current_client(server, |fields| fields.set_name(name))?;
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
@@ -0,0 +1,92 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{
+        check_empty,
+        connection::{parse_client_id, with_current_client},
+        Runnable,
+    },
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+    tcp_protocol::client_tracking::TrackingOptions,
+};
+
+pub struct ClientTracking;
+
+impl Runnable<ServerRedisAttributes> for ClientTracking {
+    /// Turns on or off the tracking of the keys for the client-side caching.
+    ///
+    /// # Options
+    ///
+    /// * REDIRECT id: the invalidations are sent to the given client, as
+    ///   messages of the \_\_redis\_\_:invalidate channel.
+    /// * BCAST: every modified key is notified, without remembering the keys read.
+    /// * PREFIX prefix: in BCAST mode, only the keys with the prefix are notified.
+    /// * OPTIN: the keys are only remembered after CLIENT CACHING yes.
+    /// * OPTOUT: the keys are not remembered after CLIENT CACHING no.
+    /// * NOLOOP: the keys modified by the client itself are not notified.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: OK.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The first argument is not ON or OFF.
+    /// * The options are unknown or incompatible.
+    /// * The client of the REDIRECT option is not connected.
+    /// * [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes) has poisoned methods.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_empty(&buffer, "client|tracking")?;
+        let id = with_current_client(server, |fields| fields.id())?;
+        let tracking = server.get_client_tracking();
+
+        match buffer[0].to_lowercase().as_str() {
+            "on" => tracking.enable(id, parse_options(&buffer[1..])?)?,
+            "off" => tracking.disable(id)?,
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        }
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
+
+/// Builds the [TrackingOptions] from the arguments that follow ON.
+fn parse_options(buffer: &[String]) -> Result<TrackingOptions, ErrorStruct> {
+    let mut options = TrackingOptions::default();
+    let mut args = buffer.iter();
+    while let Some(option) = args.next() {
+        match option.to_lowercase().as_str() {
+            "redirect" => {
+                let id = args
+                    .next()
+                    .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))?;
+                options.redirect = Some(parse_client_id(id)?);
+            }
+            "prefix" => {
+                let prefix = args
+                    .next()
+                    .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))?;
+                options.prefixes.push(prefix.to_string());
+            }
+            "bcast" => options.bcast = true,
+            "optin" => options.optin = true,
+            "optout" => options.optout = true,
+            "noloop" => options.noloop = true,
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        }
+    }
+
+    if !options.bcast && !options.prefixes.is_empty() {
+        return Err(ErrorStruct::from(redis_messages::prefix_requires_bcast()));
+    }
+    if options.optin && options.optout {
+        return Err(ErrorStruct::from(redis_messages::optin_and_optout()));
+    }
+    if options.bcast && (options.optin || options.optout) {
+        return Err(ErrorStruct::from(redis_messages::bcast_with_optin_optout()));
+    }
+    Ok(options)
+}
@@ -0,0 +1,30 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{check_not_empty, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+};
+
+pub struct ClientUnpause;
+
+impl Runnable<ServerRedisAttributes> for ClientUnpause {
+    /// Resumes the commands suspended by CLIENT PAUSE.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: OK.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Any argument is received.
+    /// * The state of the pause is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_not_empty(&buffer)?;
+        server.get_client_pause().unpause()?;
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
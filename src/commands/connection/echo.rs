@@ -0,0 +1,56 @@
+use crate::messages::redis_messages;
+use crate::tcp_protocol::client_atributes::client_fields::ClientFields;
+use crate::{
+    commands::Runnable,
+    native_types::{ErrorStruct, RBulkString, RedisType},
+};
+use std::sync::{Arc, Mutex};
+
+pub struct Echo;
+
+impl Runnable<Arc<Mutex<ClientFields>>> for Echo {
+    /// Returns the given message.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the message.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Buffer [Vec]<[String]> is not received with exactly one message.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        _status: &mut Arc<Mutex<ClientFields>>,
+    ) -> Result<String, ErrorStruct> {
+        if buffer.len() != 1 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "echo",
+            )));
+        }
+        Ok(RBulkString::encode(buffer.remove(0)))
+    }
+}
+
+#[cfg(test)]
+mod test_echo {
+    use super::*;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_echo_a_message() {
+        let mut status = Arc::new(Mutex::new(ClientFields::default()));
+        let result = Echo.run(vec_strings!["hello world"], &mut status).unwrap();
+        assert_eq!(result, "$11\r\nhello world\r\n");
+    }
+
+    #[test]
+    fn test_02_echo_without_message() {
+        let mut status = Arc::new(Mutex::new(ClientFields::default()));
+        let error = Echo.run(vec![], &mut status).unwrap_err();
+        assert_eq!(
+            error.print_it(),
+            "ERR wrong number of arguments for 'echo' command"
+        );
+    }
+}
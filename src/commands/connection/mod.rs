@@ -0,0 +1,102 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{
+    messages::redis_messages,
+    native_types::{error_severity::ErrorSeverity, ErrorStruct},
+    tcp_protocol::{
+        client_atributes::client_fields::ClientFields, client_list::ClientList,
+        server_redis_attributes::ServerRedisAttributes,
+    },
+};
+
+pub mod client;
+pub mod client_caching;
+pub mod client_getname;
+pub mod client_getredir;
+pub mod client_id;
+pub mod client_info;
+pub mod client_kill;
+pub mod client_list;
+pub mod client_no_evict;
+pub mod client_pause;
+pub mod client_setname;
+pub mod client_tracking;
+pub mod client_unpause;
+pub mod echo;
+pub mod ping;
+pub mod quit;
+pub mod reset;
+pub mod time;
+
+/// Runs the given function on the fields of the client
+/// whose command is being executed.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * There is no client attached to the command.
+/// * The fields of the client are poisoned.
+pub fn with_current_client<F, R>(
+    server: &ServerRedisAttributes,
+    function: F,
+) -> Result<R, ErrorStruct>
+where
+    F: FnOnce(&mut ClientFields) -> R,
+{
+    let client: Arc<Mutex<ClientFields>> = server.get_current_client()?;
+    let mut fields = client.lock().map_err(|_| {
+        ErrorStruct::from(redis_messages::poisoned_lock(
+            "client fields",
+            ErrorSeverity::CloseClient,
+        ))
+    })?;
+    Ok(function(&mut fields))
+}
+
+/// Runs the given function on the [ClientList] of the server.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The [ClientList] is poisoned.
+pub fn with_client_list<F, R>(server: &ServerRedisAttributes, function: F) -> Result<R, ErrorStruct>
+where
+    F: FnOnce(&mut MutexGuard<ClientList>) -> R,
+{
+    let clients = server.get_client_list();
+    let mut clients_guard = clients.lock().map_err(|_| {
+        ErrorStruct::from(redis_messages::poisoned_lock(
+            "client list",
+            ErrorSeverity::ShutdownServer,
+        ))
+    })?;
+    Ok(function(&mut clients_guard))
+}
+
+/// Checks that the received client type is one known by CLIENT LIST and CLIENT KILL,
+/// and returns it as it is stored in [ClientFields].
+fn parse_client_type(client_type: &str) -> Result<String, ErrorStruct> {
+    match client_type.to_lowercase().as_str() {
+        "normal" => Ok(String::from("normal")),
+        "pubsub" => Ok(String::from("pubsub")),
+        "master" => Ok(String::from("master")),
+        "replica" | "slave" => Ok(String::from("replica")),
+        _ => Err(ErrorStruct::from(redis_messages::unknown_client_type(
+            client_type,
+        ))),
+    }
+}
+
+/// Parses a client id, as received by CLIENT LIST and CLIENT KILL.
+fn parse_client_id(id: &str) -> Result<u64, ErrorStruct> {
+    id.parse::<u64>()
+        .map_err(|_| ErrorStruct::new(String::from("ERR"), String::from("Invalid client ID")))
+}
+
+/// Encodes the received CLIENT LIST lines as the bulk string answered to the client.
+fn join_info_lines(lines: Vec<String>) -> String {
+    lines
+        .into_iter()
+        .map(|line| line + "\n")
+        .collect::<String>()
+}
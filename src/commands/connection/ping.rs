@@ -0,0 +1,104 @@
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::tcp_protocol::client_atributes::client_fields::ClientFields;
+use crate::tcp_protocol::client_atributes::status::Status;
+use crate::{
+    commands::Runnable,
+    native_types::{ErrorStruct, RArray, RBulkString, RSimpleString, RedisType},
+};
+use std::sync::{Arc, Mutex};
+
+pub struct Ping;
+
+impl Runnable<Arc<Mutex<ClientFields>>> for Ping {
+    /// Checks that the connection is alive.
+    ///
+    /// # Return value
+    /// * [String] _encoded_ in [RSimpleString]: PONG, if no message is given.
+    /// * [String] _encoded_ in [RBulkString]: the given message.
+    /// * [String] _encoded_ in [RArray]: *pong* and the given message (or an
+    ///   empty one), if the client is in [Status::Subscriber].
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * More than one message is received.
+    /// * [ClientFields] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        status: &mut Arc<Mutex<ClientFields>>,
+    ) -> Result<String, ErrorStruct> {
+        if buffer.len() > 1 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "ping",
+            )));
+        }
+        let subscriber = status
+            .lock()
+            .map_err(|_| {
+                ErrorStruct::from(redis_messages::poisoned_lock(
+                    "client fields",
+                    ErrorSeverity::CloseClient,
+                ))
+            })?
+            .status()
+            .eq(&Some(&Status::Subscriber));
+
+        let message = buffer.pop();
+        if subscriber {
+            Ok(RArray::encode(vec![
+                "pong".to_string(),
+                message.unwrap_or_default(),
+            ]))
+        } else if let Some(message) = message {
+            Ok(RBulkString::encode(message))
+        } else {
+            Ok(RSimpleString::encode("PONG".to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_ping {
+    use super::*;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_ping_without_message() {
+        let mut status = Arc::new(Mutex::new(ClientFields::default()));
+        let result = Ping.run(vec![], &mut status).unwrap();
+        assert_eq!(result, "+PONG\r\n");
+    }
+
+    #[test]
+    fn test_02_ping_with_message() {
+        let mut status = Arc::new(Mutex::new(ClientFields::default()));
+        let result = Ping.run(vec_strings!["hello"], &mut status).unwrap();
+        assert_eq!(result, "$5\r\nhello\r\n");
+    }
+
+    #[test]
+    fn test_03_ping_in_subscriber_mode() {
+        let mut status = Arc::new(Mutex::new(ClientFields::default()));
+        status
+            .lock()
+            .unwrap()
+            .add_subscriptions(vec_strings!["telefe"])
+            .unwrap();
+        let result = Ping.run(vec![], &mut status).unwrap();
+        assert_eq!(result, "*2\r\n$4\r\npong\r\n$0\r\n\r\n");
+    }
+
+    #[test]
+    fn test_04_ping_with_many_messages() {
+        let mut status = Arc::new(Mutex::new(ClientFields::default()));
+        let error = Ping
+            .run(vec_strings!["hello", "world"], &mut status)
+            .unwrap_err();
+        assert_eq!(
+            error.print_it(),
+            "ERR wrong number of arguments for 'ping' command"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use crate::tcp_protocol::client_atributes::status::Status;
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{connection::with_current_client, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+};
+
+pub struct Quit;
+
+impl Runnable<ServerRedisAttributes> for Quit {
+    /// Asks the server to close the connection. The client is marked with
+    /// [Status::Dead], so its handler stops reading and closes the socket
+    /// once the reply has been written.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: OK.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * There is no client attached to the command.
+    /// * The fields of the client are poisoned.
+    fn run(
+        &self,
+        _buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        with_current_client(server, |fields| {
+            fields.replace_status(Status::Dead);
+        })?;
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
+
+#[cfg(test)]
+mod test_quit {
+    use super::*;
+    use crate::{
+        commands,
+        tcp_protocol::{client_atributes::client_fields::ClientFields, client_aware::ClientAware},
+    };
+    use std::sync::{Arc, Mutex};
+
+    fn create_server() -> (ServerRedisAttributes, Arc<Mutex<ClientFields>>) {
+        let mut server = commands::create_server("quit");
+        let client = Arc::new(Mutex::new(ClientFields::default()));
+        server.set_current_client(Arc::clone(&client));
+        (server, client)
+    }
+
+    #[test]
+    fn test_01_quit_marks_the_client_as_dead() {
+        let (mut server, client) = create_server();
+        let result = Quit.run(vec![], &mut server).unwrap();
+        assert_eq!(result, "+OK\r\n");
+        assert!(client.lock().unwrap().is_dead());
+    }
+}
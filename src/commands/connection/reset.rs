@@ -0,0 +1,89 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{
+        connection::{with_client_list, with_current_client},
+        Runnable,
+    },
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+};
+
+pub struct Reset;
+
+impl Runnable<ServerRedisAttributes> for Reset {
+    /// Leaves the connection as it was when the client connected: the client
+    /// leaves the subscriber or monitor mode, its subscriptions, name,
+    /// no-evict mode and CLIENT TRACKING are removed.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: RESET.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * There is no client attached to the command.
+    /// * The fields of the client, the [ClientList](crate::tcp_protocol::client_list::ClientList)
+    ///   or the [ClientTracking](crate::tcp_protocol::client_tracking::ClientTracking) are poisoned.
+    fn run(
+        &self,
+        _buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        let (id, channels) = with_current_client(server, |fields| (fields.id(), fields.reset()))?;
+        with_client_list(server, |clients| clients.decrease_channels(channels))?;
+        server.get_client_tracking().disable(id)?;
+        Ok(RSimpleString::encode("RESET".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test_reset {
+    use super::*;
+    use crate::{
+        commands,
+        tcp_protocol::{
+            client_atributes::{client_fields::ClientFields, status::Status},
+            client_aware::ClientAware,
+        },
+    };
+    use std::sync::{Arc, Mutex};
+
+    fn create_server() -> (ServerRedisAttributes, Arc<Mutex<ClientFields>>) {
+        let mut server = commands::create_server("reset");
+        let client = Arc::new(Mutex::new(ClientFields::default()));
+        server.set_current_client(Arc::clone(&client));
+        (server, client)
+    }
+
+    #[test]
+    fn test_01_reset_leaves_the_subscriber_mode() {
+        let (mut server, client) = create_server();
+        client
+            .lock()
+            .unwrap()
+            .add_subscriptions(vec!["telefe".to_string()])
+            .unwrap();
+        server
+            .get_client_list()
+            .lock()
+            .unwrap()
+            .increase_channels(vec!["telefe".to_string()]);
+
+        let result = Reset.run(vec![], &mut server).unwrap();
+        assert_eq!(result, "+RESET\r\n");
+        assert_eq!(client.lock().unwrap().status(), Some(&Status::Executor));
+        assert!(server
+            .get_client_list()
+            .lock()
+            .unwrap()
+            .get_register()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_02_reset_leaves_the_monitor_mode() {
+        let (mut server, client) = create_server();
+        client.lock().unwrap().replace_status(Status::Monitor);
+        Reset.run(vec![], &mut server).unwrap();
+        assert_eq!(client.lock().unwrap().status(), Some(&Status::Executor));
+    }
+}
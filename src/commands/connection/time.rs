@@ -0,0 +1,45 @@
+use crate::tcp_protocol::client_atributes::client_fields::ClientFields;
+use crate::{
+    commands::Runnable,
+    native_types::{ErrorStruct, RArray, RedisType},
+};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+pub struct Time;
+
+impl Runnable<Arc<Mutex<ClientFields>>> for Time {
+    /// Returns the current server time.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray]: the unix time in seconds and
+    /// the microseconds already elapsed in the current second.
+    fn run(
+        &self,
+        _buffer: Vec<String>,
+        _status: &mut Arc<Mutex<ClientFields>>,
+    ) -> Result<String, ErrorStruct> {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        Ok(RArray::encode(vec![
+            now.as_secs().to_string(),
+            now.subsec_micros().to_string(),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod test_time {
+    use super::*;
+
+    #[test]
+    fn test_01_time_returns_seconds_and_microseconds() {
+        let mut status = Arc::new(Mutex::new(ClientFields::default()));
+        let result = Time.run(vec![], &mut status).unwrap();
+        let parts: Vec<&str> = result.split("\r\n").collect();
+        assert_eq!(parts[0], "*2");
+        assert!(parts[2].parse::<u64>().unwrap() > 1600000000);
+        assert!(parts[4].parse::<u32>().unwrap() < 1000000);
+    }
+}
@@ -0,0 +1,194 @@
+use super::{lock, parse_coordinates};
+use crate::commands::Runnable;
+use crate::database::{Database, TypeSaved};
+use crate::err_wrongtype;
+use crate::messages::redis_messages;
+use crate::native_types::{ErrorStruct, RInteger, RedisType};
+use crate::sorted_set::SortedSet;
+use std::sync::{Arc, Mutex};
+
+pub struct GeoAdd;
+
+impl Runnable<Arc<Mutex<Database>>> for GeoAdd {
+    /// Adds the members with their coordinates to the geospatial index stored at
+    /// **key**, which is created if it does not exist. The members are stored in
+    /// a sorted set, with the 52 bits geohash of their coordinates as score.
+    ///
+    /// GEOADD key [NX | XX] [CH] longitude latitude member [longitude latitude member ...]
+    ///
+    /// * NX: only adds new members.
+    /// * XX: only updates the members which already exist.
+    /// * CH: counts the members updated too.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the number of members added, plus the
+    /// ones updated if CH is given.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a value which is not a sorted set.
+    /// * NX and XX are given at the same time.
+    /// * The coordinates are not valid.
+    /// * The buffer [Vec]<[String]> does not contain triples of longitude, latitude and member.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        let key = buffer
+            .first()
+            .ok_or_else(|| ErrorStruct::from(redis_messages::wrong_number_args_for("geoadd")))?;
+        let (mut nx, mut xx, mut ch) = (false, false, false);
+        let mut index = 1;
+        while let Some(option) = buffer.get(index) {
+            match option.to_uppercase().as_str() {
+                "NX" => nx = true,
+                "XX" => xx = true,
+                "CH" => ch = true,
+                _ => break,
+            }
+            index += 1;
+        }
+        if nx && xx {
+            return Err(ErrorStruct::from(redis_messages::xx_and_nx_incompatible()));
+        }
+        let triples = &buffer[index..];
+        if triples.is_empty() || !triples.len().is_multiple_of(3) {
+            return Err(ErrorStruct::from(redis_messages::syntax_error()));
+        }
+        let members = triples
+            .chunks(3)
+            .map(|triple| Ok((parse_coordinates(&triple[0], &triple[1])?, &triple[2])))
+            .collect::<Result<Vec<_>, ErrorStruct>>()?;
+
+        if !database.contains_key(key) {
+            if xx {
+                return Ok(RInteger::encode(0));
+            }
+            database.insert(key.to_string(), TypeSaved::SortedSet(SortedSet::new()));
+        }
+        let sorted_set = match database.get_mut(key) {
+            Some(TypeSaved::SortedSet(sorted_set)) => sorted_set,
+            _ => return err_wrongtype!(),
+        };
+        let mut changed = 0;
+        for (coordinates, member) in members {
+            let score = coordinates.encode() as f64;
+            match sorted_set.score(member) {
+                Some(_) if nx => continue,
+                None if xx => continue,
+                Some(previous) => {
+                    if previous != score {
+                        sorted_set.insert(member, score);
+                        changed += ch as isize;
+                    }
+                }
+                None => {
+                    sorted_set.insert(member, score);
+                    changed += 1;
+                }
+            }
+        }
+        Ok(RInteger::encode(changed))
+    }
+}
+
+#[cfg(test)]
+mod test_geoadd {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::vec_strings;
+
+    fn sicily() -> Vec<String> {
+        vec_strings![
+            "Sicily",
+            "13.361389",
+            "38.115556",
+            "Palermo",
+            "15.087269",
+            "37.502669",
+            "Catania"
+        ]
+    }
+
+    #[test]
+    fn test_01_geoadd_stores_geohashes_as_scores() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let result = GeoAdd.run(sicily(), &mut database);
+        assert_eq!(result.unwrap(), ":2\r\n");
+        let result = GeoAdd.run(sicily(), &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+        match database.lock().unwrap().get("Sicily") {
+            Some(TypeSaved::SortedSet(sorted_set)) => {
+                assert_eq!(sorted_set.score("Palermo"), Some(3479099956230698.0));
+                assert_eq!(sorted_set.score("Catania"), Some(3479447370796909.0));
+            }
+            _ => panic!("Sicily is not a sorted set"),
+        };
+    }
+
+    #[test]
+    fn test_02_geoadd_options() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        GeoAdd.run(sicily(), &mut database).unwrap();
+        let result = GeoAdd.run(
+            vec_strings![
+                "Sicily",
+                "XX",
+                "CH",
+                "13",
+                "38",
+                "Palermo",
+                "14",
+                "37",
+                "Agrigento"
+            ],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), ":1\r\n");
+        let result = GeoAdd.run(
+            vec_strings![
+                "Sicily",
+                "NX",
+                "15",
+                "37",
+                "Palermo",
+                "14",
+                "37",
+                "Agrigento"
+            ],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), ":1\r\n");
+        let result = GeoAdd.run(
+            vec_strings!["Sicily", "NX", "XX", "15", "37", "Palermo"],
+            &mut database,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_03_geoadd_invalid_arguments() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let result = GeoAdd.run(vec_strings!["key", "200", "10", "a"], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR invalid longitude,latitude pair 200.000000,10.000000"
+        );
+        let result = GeoAdd.run(vec_strings!["key", "10", "10"], &mut database);
+        assert!(result.is_err());
+        assert!(database.lock().unwrap().get("key").is_none());
+        database
+            .lock()
+            .unwrap()
+            .insert("string".to_string(), TypeSaved::String("value".into()));
+        let result = GeoAdd.run(vec_strings!["string", "10", "10", "a"], &mut database);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use super::{format_distance, load, lock, parse_unit, position};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::{ErrorStruct, RBulkString, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct GeoDist;
+
+impl Runnable<Arc<Mutex<Database>>> for GeoDist {
+    /// Returns the distance between two members of the geospatial index stored
+    /// at **key**, in meters or in the unit given: m, km, ft or mi.
+    ///
+    /// GEODIST key member1 member2 [unit]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the distance with 4 decimals, or nil
+    /// if some member does not exist.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a value which is not a sorted set.
+    /// * The unit is not supported.
+    /// * The buffer [Vec]<[String]> does not contain 3 or 4 elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        if !(3..=4).contains(&buffer.len()) {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "geodist",
+            )));
+        }
+        let unit = match buffer.get(3) {
+            Some(unit) => parse_unit(unit)?,
+            None => 1.0,
+        };
+        let sorted_set = match load(&mut database, &buffer[0])? {
+            Some(sorted_set) => sorted_set,
+            None => return Ok(RBulkString::encode(redis_messages::nil())),
+        };
+        match (
+            position(sorted_set, &buffer[1]),
+            position(sorted_set, &buffer[2]),
+        ) {
+            (Some(first), Some(second)) => Ok(RBulkString::encode(format_distance(
+                first.distance(&second),
+                unit,
+            ))),
+            _ => Ok(RBulkString::encode(redis_messages::nil())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_geodist {
+    use super::*;
+    use crate::commands::{create_notifier, geo::geoadd::GeoAdd};
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_geodist_in_different_units() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        GeoAdd
+            .run(
+                vec_strings![
+                    "Sicily",
+                    "13.361389",
+                    "38.115556",
+                    "Palermo",
+                    "15.087269",
+                    "37.502669",
+                    "Catania"
+                ],
+                &mut database,
+            )
+            .unwrap();
+        let result = GeoDist.run(vec_strings!["Sicily", "Palermo", "Catania"], &mut database);
+        assert_eq!(result.unwrap(), "$11\r\n166274.1516\r\n");
+        let result = GeoDist.run(
+            vec_strings!["Sicily", "Palermo", "Catania", "km"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "$8\r\n166.2742\r\n");
+        let result = GeoDist.run(
+            vec_strings!["Sicily", "Palermo", "Catania", "mi"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "$8\r\n103.3182\r\n");
+        let result = GeoDist.run(vec_strings!["Sicily", "Foo", "Bar"], &mut database);
+        assert_eq!(result.unwrap(), "$-1\r\n");
+        let result = GeoDist.run(
+            vec_strings!["Sicily", "Palermo", "Catania", "yd"],
+            &mut database,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use super::{load, lock, position};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::{ErrorStruct, RArray, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct GeoHash;
+
+impl Runnable<Arc<Mutex<Database>>> for GeoHash {
+    /// Returns the standard geohash of the members of the geospatial index stored at **key**.
+    ///
+    /// GEOHASH key [member [member ...]]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray]: for each member, its geohash of 11
+    /// characters, or nil if it does not exist.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a value which is not a sorted set.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        let key = &buffer[0];
+        let sorted_set = load(&mut database, key)?;
+        let hashes = buffer[1..]
+            .iter()
+            .map(|member| {
+                sorted_set
+                    .and_then(|sorted_set| position(sorted_set, member))
+                    .map(|coordinates| coordinates.geohash())
+                    .unwrap_or_else(redis_messages::nil)
+            })
+            .collect();
+        Ok(RArray::encode(hashes))
+    }
+}
+
+#[cfg(test)]
+mod test_geohash {
+    use super::*;
+    use crate::commands::{create_notifier, geo::geoadd::GeoAdd};
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_geohash_of_members() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        GeoAdd
+            .run(
+                vec_strings![
+                    "Sicily",
+                    "13.361389",
+                    "38.115556",
+                    "Palermo",
+                    "15.087269",
+                    "37.502669",
+                    "Catania"
+                ],
+                &mut database,
+            )
+            .unwrap();
+        let result = GeoHash.run(
+            vec_strings!["Sicily", "Palermo", "Catania", "Foo"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*3\r\n$11\r\nsqc8b49rny0\r\n$11\r\nsqdtr74hyu0\r\n$-1\r\n"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use super::{encode_coordinates, load, lock, position};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::native_types::ErrorStruct;
+use std::sync::{Arc, Mutex};
+
+pub struct GeoPos;
+
+impl Runnable<Arc<Mutex<Database>>> for GeoPos {
+    /// Returns the coordinates of the members of the geospatial index stored at **key**.
+    ///
+    /// GEOPOS key [member [member ...]]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray](crate::native_types::RArray): for each member,
+    /// an array with its longitude and latitude, or a nil array if it does not exist.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a value which is not a sorted set.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        let key = &buffer[0];
+        let sorted_set = load(&mut database, key)?;
+        let mut encoded = format!("*{}\r\n", buffer.len() - 1);
+        for member in buffer[1..].iter() {
+            match sorted_set.and_then(|sorted_set| position(sorted_set, member)) {
+                Some(coordinates) => encoded.push_str(&encode_coordinates(&coordinates)),
+                None => encoded.push_str("*-1\r\n"),
+            }
+        }
+        Ok(encoded)
+    }
+}
+
+#[cfg(test)]
+mod test_geopos {
+    use super::*;
+    use crate::commands::{create_notifier, geo::geoadd::GeoAdd};
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_geopos_returns_the_center_of_the_geohash() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        GeoAdd
+            .run(
+                vec_strings!["Sicily", "13.361389", "38.115556", "Palermo"],
+                &mut database,
+            )
+            .unwrap();
+        let result = GeoPos.run(
+            vec_strings!["Sicily", "Palermo", "NonExisting"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*2\r\n*2\r\n$18\r\n13.361389338970184\r\n$16\r\n38.1155563954963\r\n*-1\r\n"
+        );
+        let result = GeoPos.run(vec_strings!["missing", "Palermo"], &mut database);
+        assert_eq!(result.unwrap(), "*1\r\n*-1\r\n");
+    }
+}
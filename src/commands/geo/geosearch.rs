@@ -0,0 +1,162 @@
+use super::{load, lock, Search};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use std::sync::{Arc, Mutex};
+
+pub struct GeoSearch;
+
+impl Runnable<Arc<Mutex<Database>>> for GeoSearch {
+    /// Returns the members of the geospatial index stored at **key** which are
+    /// inside the circle or the box centered at a member or at the coordinates given.
+    ///
+    /// GEOSEARCH key FROMMEMBER member | FROMLONLAT longitude latitude
+    /// BYRADIUS radius unit | BYBOX width height unit
+    /// [ASC | DESC] [COUNT count [ANY]] [WITHCOORD] [WITHDIST] [WITHHASH]
+    ///
+    /// * ASC, DESC: sorts the members by their distance to the center.
+    /// * COUNT: returns the nearest members only, or any of them if ANY is given.
+    /// * WITHDIST, WITHHASH, WITHCOORD: add the distance in the unit of the shape,
+    ///   the geohash and the coordinates of each member.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray](crate::native_types::RArray): the members
+    /// found, or an array with each member and the information requested.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a value which is not a sorted set.
+    /// * The center is a member which does not exist.
+    /// * Some argument is not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        let key = buffer
+            .first()
+            .ok_or_else(|| ErrorStruct::from(redis_messages::wrong_number_args_for("geosearch")))?;
+        let search = Search::parse(&buffer[1..], false)?;
+        let found = search.run(load(&mut database, key)?)?;
+        Ok(search.encode(found))
+    }
+}
+
+#[cfg(test)]
+mod test_geosearch {
+    use super::*;
+    use crate::commands::{create_notifier, geo::geoadd::GeoAdd};
+    use crate::vec_strings;
+
+    fn sicily() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        GeoAdd
+            .run(
+                vec_strings![
+                    "Sicily",
+                    "13.361389",
+                    "38.115556",
+                    "Palermo",
+                    "15.087269",
+                    "37.502669",
+                    "Catania",
+                    "12.758489",
+                    "38.788135",
+                    "edge1",
+                    "17.241510",
+                    "38.788135",
+                    "edge2"
+                ],
+                &mut database,
+            )
+            .unwrap();
+        database
+    }
+
+    #[test]
+    fn test_01_geosearch_by_radius() {
+        let mut database = sicily();
+        let result = GeoSearch.run(
+            vec_strings![
+                "Sicily",
+                "FROMLONLAT",
+                "15",
+                "37",
+                "BYRADIUS",
+                "200",
+                "km",
+                "ASC"
+            ],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "*2\r\n$7\r\nCatania\r\n$7\r\nPalermo\r\n");
+        let result = GeoSearch.run(
+            vec_strings!["Sicily", "FROMMEMBER", "Palermo", "BYRADIUS", "100", "km"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "*2\r\n$7\r\nPalermo\r\n$5\r\nedge1\r\n");
+    }
+
+    #[test]
+    fn test_02_geosearch_by_box_with_distances() {
+        let mut database = sicily();
+        let result = GeoSearch.run(
+            vec_strings![
+                "Sicily",
+                "FROMLONLAT",
+                "15",
+                "37",
+                "BYBOX",
+                "400",
+                "400",
+                "km",
+                "DESC",
+                "WITHDIST"
+            ],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*4\r\n*2\r\n$5\r\nedge1\r\n$8\r\n279.7405\r\n*2\r\n$5\r\nedge2\r\n$8\r\n279.7403\r\n\
+             *2\r\n$7\r\nPalermo\r\n$8\r\n190.4424\r\n*2\r\n$7\r\nCatania\r\n$7\r\n56.4413\r\n"
+        );
+    }
+
+    #[test]
+    fn test_03_geosearch_count_and_hash() {
+        let mut database = sicily();
+        let result = GeoSearch.run(
+            vec_strings![
+                "Sicily",
+                "FROMMEMBER",
+                "Catania",
+                "BYRADIUS",
+                "1000",
+                "km",
+                "COUNT",
+                "1",
+                "WITHHASH"
+            ],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*1\r\n*2\r\n$7\r\nCatania\r\n:3479447370796909\r\n"
+        );
+        let result = GeoSearch.run(
+            vec_strings!["Sicily", "FROMMEMBER", "Rome", "BYRADIUS", "1", "km"],
+            &mut database,
+        );
+        assert!(result.is_err());
+        let result = GeoSearch.run(
+            vec_strings!["missing", "FROMLONLAT", "15", "37", "BYRADIUS", "1", "km"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "*0\r\n");
+    }
+}
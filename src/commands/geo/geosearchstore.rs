@@ -0,0 +1,146 @@
+use super::{load, lock, Search};
+use crate::commands::Runnable;
+use crate::database::{Database, TypeSaved};
+use crate::messages::redis_messages;
+use crate::native_types::{ErrorStruct, RInteger, RedisType};
+use crate::sorted_set::SortedSet;
+use std::sync::{Arc, Mutex};
+
+pub struct GeoSearchStore;
+
+impl Runnable<Arc<Mutex<Database>>> for GeoSearchStore {
+    /// Like GEOSEARCH, but stores the members found at **destination** as a
+    /// geospatial index, replacing its value. With STOREDIST, their distances
+    /// to the center are stored as scores instead of their geohashes.
+    ///
+    /// GEOSEARCHSTORE destination source FROMMEMBER member | FROMLONLAT longitude latitude
+    /// BYRADIUS radius unit | BYBOX width height unit
+    /// [ASC | DESC] [COUNT count [ANY]] [STOREDIST]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the number of members stored.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Source holds a value which is not a sorted set.
+    /// * The center is a member which does not exist.
+    /// * Some argument is not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        if buffer.len() < 2 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "geosearchstore",
+            )));
+        }
+        let search = Search::parse(&buffer[2..], true)?;
+        let found = search.run(load(&mut database, &buffer[1])?)?;
+
+        let mut sorted_set = SortedSet::new();
+        for found in found {
+            let score = if search.store_distance {
+                found.distance / search.unit
+            } else {
+                found.hash as f64
+            };
+            sorted_set.insert(&found.member, score);
+        }
+        let stored = sorted_set.len();
+        if sorted_set.is_empty() {
+            database.remove(&buffer[0]);
+        } else {
+            database.insert(buffer[0].to_string(), TypeSaved::SortedSet(sorted_set));
+        }
+        Ok(RInteger::encode(stored as isize))
+    }
+}
+
+#[cfg(test)]
+mod test_geosearchstore {
+    use super::*;
+    use crate::commands::{create_notifier, geo::geoadd::GeoAdd, geo::geopos::GeoPos};
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_geosearchstore_stores_geohashes_or_distances() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        GeoAdd
+            .run(
+                vec_strings![
+                    "Sicily",
+                    "13.361389",
+                    "38.115556",
+                    "Palermo",
+                    "15.087269",
+                    "37.502669",
+                    "Catania"
+                ],
+                &mut database,
+            )
+            .unwrap();
+        let result = GeoSearchStore.run(
+            vec_strings![
+                "near",
+                "Sicily",
+                "FROMLONLAT",
+                "15",
+                "37",
+                "BYRADIUS",
+                "100",
+                "km"
+            ],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), ":1\r\n");
+        let result = GeoPos.run(vec_strings!["near", "Catania", "Palermo"], &mut database);
+        assert_eq!(
+            result.unwrap(),
+            "*2\r\n*2\r\n$18\r\n15.087267458438873\r\n$17\r\n37.50266842333162\r\n*-1\r\n"
+        );
+
+        let result = GeoSearchStore.run(
+            vec_strings![
+                "distances",
+                "Sicily",
+                "FROMLONLAT",
+                "15",
+                "37",
+                "BYRADIUS",
+                "200",
+                "km",
+                "STOREDIST"
+            ],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), ":2\r\n");
+        match database.lock().unwrap().get("distances") {
+            Some(TypeSaved::SortedSet(sorted_set)) => {
+                let distance = sorted_set.score("Catania").unwrap();
+                assert!((distance - 56.4413).abs() < 0.0001);
+            }
+            _ => panic!("distances is not a sorted set"),
+        };
+
+        let result = GeoSearchStore.run(
+            vec_strings![
+                "near",
+                "Sicily",
+                "FROMLONLAT",
+                "0",
+                "0",
+                "BYRADIUS",
+                "1",
+                "km"
+            ],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), ":0\r\n");
+        assert!(database.lock().unwrap().get("near").is_none());
+    }
+}
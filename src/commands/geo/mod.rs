@@ -0,0 +1,414 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{
+    commands::get_as_float,
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    geohash::Coordinates,
+    messages::redis_messages,
+    native_types::RInteger,
+    native_types::{error_severity::ErrorSeverity, ErrorStruct, RArray, RBulkString, RedisType},
+    sorted_set::SortedSet,
+};
+
+pub mod geoadd;
+pub mod geodist;
+pub mod geohash;
+pub mod geopos;
+pub mod geosearch;
+pub mod geosearchstore;
+
+fn lock(database: &Arc<Mutex<Database>>) -> Result<MutexGuard<'_, Database>, ErrorStruct> {
+    database.lock().map_err(|_| {
+        ErrorStruct::from(redis_messages::poisoned_lock(
+            "database",
+            ErrorSeverity::ShutdownServer,
+        ))
+    })
+}
+
+/// Returns the sorted set stored at the key, or [None] if it does not exist.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The value stored at **key** is not a sorted set.
+fn load<'a>(database: &'a mut Database, key: &str) -> Result<Option<&'a SortedSet>, ErrorStruct> {
+    match database.get(key) {
+        Some(TypeSaved::SortedSet(sorted_set)) => Ok(Some(sorted_set)),
+        Some(_) => err_wrongtype!(),
+        None => Ok(None),
+    }
+}
+
+/// Coordinates of the member of the sorted set, or [None] if it does not belong to it.
+fn position(sorted_set: &SortedSet, member: &str) -> Option<Coordinates> {
+    sorted_set
+        .score(member)
+        .map(|score| Coordinates::decode(score as u64))
+}
+
+/// Parses a longitude and a latitude.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * Some coordinate is not a number.
+/// * The coordinates are out of the range which can be indexed.
+fn parse_coordinates(longitude: &str, latitude: &str) -> Result<Coordinates, ErrorStruct> {
+    let (longitude, latitude) = (get_as_float(longitude)?, get_as_float(latitude)?);
+    Coordinates::new(longitude, latitude).ok_or_else(|| {
+        ErrorStruct::from(redis_messages::invalid_longitude_latitude(
+            longitude, latitude,
+        ))
+    })
+}
+
+/// Parses a unit of distance: m, km, ft or mi.
+///
+/// # Return value
+/// [f64]: the meters of the unit.
+fn parse_unit(unit: &str) -> Result<f64, ErrorStruct> {
+    match unit.to_lowercase().as_str() {
+        "m" => Ok(1.0),
+        "km" => Ok(1000.0),
+        "ft" => Ok(0.3048),
+        "mi" => Ok(1609.34),
+        _ => Err(ErrorStruct::from(redis_messages::unsupported_unit())),
+    }
+}
+
+/// Formats a distance in meters in the given unit, with 4 decimals.
+fn format_distance(meters: f64, unit: f64) -> String {
+    format!("{:.4}", meters / unit)
+}
+
+/// Encodes the coordinates as an array with the longitude and the latitude.
+fn encode_coordinates(coordinates: &Coordinates) -> String {
+    RArray::encode(vec![
+        coordinates.longitude.to_string(),
+        coordinates.latitude.to_string(),
+    ])
+}
+
+/// Point from which GEOSEARCH searches.
+#[derive(Debug, PartialEq)]
+enum Origin {
+    Member(String),
+    Coordinates(Coordinates),
+}
+
+/// Area in which GEOSEARCH searches, in meters.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Shape {
+    Radius(f64),
+    Box { width: f64, height: f64 },
+}
+
+/// Member found by GEOSEARCH, with its distance to the origin in meters.
+#[derive(Debug, PartialEq)]
+struct Found {
+    member: String,
+    hash: u64,
+    coordinates: Coordinates,
+    distance: f64,
+}
+
+/// Arguments of GEOSEARCH and GEOSEARCHSTORE.
+#[derive(Debug, PartialEq)]
+struct Search {
+    origin: Origin,
+    shape: Shape,
+    /// Meters of the unit of the shape, which is also the unit of the distances replied.
+    unit: f64,
+    ascending: Option<bool>,
+    count: Option<usize>,
+    any: bool,
+    with_coordinates: bool,
+    with_distance: bool,
+    with_hash: bool,
+    store_distance: bool,
+}
+
+impl Search {
+    /// Parses the arguments after the key of GEOSEARCH, or after the keys of
+    /// GEOSEARCHSTORE if `store` is true:
+    ///
+    /// FROMMEMBER member | FROMLONLAT longitude latitude
+    /// BYRADIUS radius unit | BYBOX width height unit
+    /// [ASC | DESC] [COUNT count [ANY]] [WITHCOORD] [WITHDIST] [WITHHASH] [STOREDIST]
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Some argument is unknown or not valid.
+    /// * Not exactly one origin or one shape are given.
+    /// * ANY is used without COUNT.
+    /// * WITHCOORD, WITHDIST or WITHHASH are used by GEOSEARCHSTORE, or STOREDIST by GEOSEARCH.
+    fn parse(buffer: &[String], store: bool) -> Result<Self, ErrorStruct> {
+        let command = if store { "GEOSEARCHSTORE" } else { "GEOSEARCH" };
+        let (mut origin, mut shape, mut unit) = (None, None, 1.0);
+        let (mut ascending, mut count, mut any) = (None, None, false);
+        let (mut with_coordinates, mut with_distance, mut with_hash) = (false, false, false);
+        let mut store_distance = false;
+        let mut index = 0;
+        while index < buffer.len() {
+            let option = buffer[index].to_uppercase();
+            let arguments = match option.as_str() {
+                "FROMMEMBER" => 1,
+                "FROMLONLAT" | "BYRADIUS" => 2,
+                "BYBOX" => 3,
+                "COUNT" => 1,
+                _ => 0,
+            };
+            let arguments = buffer
+                .get(index + 1..index + 1 + arguments)
+                .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))?;
+            match option.as_str() {
+                "FROMMEMBER" | "FROMLONLAT" if origin.is_some() => {
+                    return Err(ErrorStruct::from(redis_messages::exactly_one_of(
+                        "FROMMEMBER",
+                        "FROMLONLAT",
+                        command,
+                    )))
+                }
+                "FROMMEMBER" => origin = Some(Origin::Member(arguments[0].to_string())),
+                "FROMLONLAT" => {
+                    origin = Some(Origin::Coordinates(parse_coordinates(
+                        &arguments[0],
+                        &arguments[1],
+                    )?))
+                }
+                "BYRADIUS" | "BYBOX" if shape.is_some() => {
+                    return Err(ErrorStruct::from(redis_messages::exactly_one_of(
+                        "BYRADIUS", "BYBOX", command,
+                    )))
+                }
+                "BYRADIUS" => {
+                    unit = parse_unit(&arguments[1])?;
+                    shape = Some(Shape::Radius(parse_distance(&arguments[0], unit)?));
+                }
+                "BYBOX" => {
+                    unit = parse_unit(&arguments[2])?;
+                    shape = Some(Shape::Box {
+                        width: parse_distance(&arguments[0], unit)?,
+                        height: parse_distance(&arguments[1], unit)?,
+                    });
+                }
+                "ASC" => ascending = Some(true),
+                "DESC" => ascending = Some(false),
+                "COUNT" => {
+                    let value = arguments[0].parse::<i64>().map_err(|_| {
+                        ErrorStruct::new(
+                            "ERR".to_string(),
+                            "value is not an integer or out of range".to_string(),
+                        )
+                    })?;
+                    if value <= 0 {
+                        return Err(ErrorStruct::from(redis_messages::count_must_be_positive()));
+                    }
+                    count = Some(value as usize);
+                }
+                "ANY" => any = true,
+                "WITHCOORD" if !store => with_coordinates = true,
+                "WITHDIST" if !store => with_distance = true,
+                "WITHHASH" if !store => with_hash = true,
+                "WITHCOORD" | "WITHDIST" | "WITHHASH" => {
+                    return Err(ErrorStruct::from(
+                        redis_messages::geosearchstore_with_options(),
+                    ))
+                }
+                "STOREDIST" if store => store_distance = true,
+                _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+            }
+            index += 1 + arguments.len();
+        }
+        let origin = origin.ok_or_else(|| {
+            ErrorStruct::from(redis_messages::exactly_one_of(
+                "FROMMEMBER",
+                "FROMLONLAT",
+                command,
+            ))
+        })?;
+        let shape = shape.ok_or_else(|| {
+            ErrorStruct::from(redis_messages::exactly_one_of("BYRADIUS", "BYBOX", command))
+        })?;
+        if any && count.is_none() {
+            return Err(ErrorStruct::from(redis_messages::any_requires_count()));
+        }
+        // Redis sorts by distance when the results are limited, unless ANY is used
+        if count.is_some() && !any && ascending.is_none() {
+            ascending = Some(true);
+        }
+        Ok(Search {
+            origin,
+            shape,
+            unit,
+            ascending,
+            count,
+            any,
+            with_coordinates,
+            with_distance,
+            with_hash,
+            store_distance,
+        })
+    }
+
+    /// Finds the members of the sorted set inside the shape.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The origin is a member which does not belong to the sorted set.
+    fn run(&self, sorted_set: Option<&SortedSet>) -> Result<Vec<Found>, ErrorStruct> {
+        let origin = match (&self.origin, sorted_set) {
+            (Origin::Coordinates(coordinates), _) => *coordinates,
+            (Origin::Member(member), Some(sorted_set)) => position(sorted_set, member)
+                .ok_or_else(|| ErrorStruct::from(redis_messages::zset_member_not_found()))?,
+            (Origin::Member(_), None) => {
+                return Err(ErrorStruct::from(redis_messages::zset_member_not_found()))
+            }
+        };
+        let sorted_set = match sorted_set {
+            Some(sorted_set) => sorted_set,
+            None => return Ok(Vec::new()),
+        };
+        let mut found = Vec::new();
+        for (member, score) in sorted_set.iter() {
+            if self.any && Some(found.len()) == self.count {
+                break;
+            }
+            let coordinates = Coordinates::decode(score as u64);
+            let distance = origin.distance(&coordinates);
+            let inside = match self.shape {
+                Shape::Radius(radius) => distance <= radius,
+                Shape::Box { width, height } => origin.box_contains(width, height, &coordinates),
+            };
+            if inside {
+                found.push(Found {
+                    member: member.to_string(),
+                    hash: score as u64,
+                    coordinates,
+                    distance,
+                });
+            }
+        }
+        match self.ascending {
+            Some(true) => found.sort_by(|a, b| a.distance.total_cmp(&b.distance)),
+            Some(false) => found.sort_by(|a, b| b.distance.total_cmp(&a.distance)),
+            None => {}
+        }
+        if let Some(count) = self.count {
+            found.truncate(count);
+        }
+        Ok(found)
+    }
+
+    /// Encodes the members found, each one with the options requested.
+    fn encode(&self, found: Vec<Found>) -> String {
+        let with_options = self.with_coordinates || self.with_distance || self.with_hash;
+        if !with_options {
+            return RArray::encode(found.into_iter().map(|found| found.member).collect());
+        }
+        let fields = 1
+            + self.with_coordinates as usize
+            + self.with_distance as usize
+            + self.with_hash as usize;
+        let mut encoded = format!("*{}\r\n", found.len());
+        for found in found {
+            encoded.push_str(&format!("*{}\r\n", fields));
+            encoded.push_str(&RBulkString::encode(found.member));
+            if self.with_distance {
+                encoded.push_str(&RBulkString::encode(format_distance(
+                    found.distance,
+                    self.unit,
+                )));
+            }
+            if self.with_hash {
+                encoded.push_str(&RInteger::encode(found.hash as isize));
+            }
+            if self.with_coordinates {
+                encoded.push_str(&encode_coordinates(&found.coordinates));
+            }
+        }
+        encoded
+    }
+}
+
+/// Parses a non-negative distance in the given unit, returning it in meters.
+fn parse_distance(distance: &str, unit: f64) -> Result<f64, ErrorStruct> {
+    let distance = get_as_float(distance)?;
+    if distance < 0.0 {
+        return Err(ErrorStruct::from(redis_messages::negative_search_shape()));
+    }
+    Ok(distance * unit)
+}
+
+#[cfg(test)]
+mod test_geo {
+    use super::*;
+    use crate::vec_strings;
+
+    fn args(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn test_01_parse_search() {
+        let search = Search::parse(
+            &args(&[
+                "FROMLONLAT",
+                "15",
+                "37",
+                "BYRADIUS",
+                "200",
+                "km",
+                "COUNT",
+                "2",
+            ]),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            search.origin,
+            Origin::Coordinates(Coordinates::new(15.0, 37.0).unwrap())
+        );
+        assert_eq!(search.shape, Shape::Radius(200000.0));
+        assert_eq!(search.ascending, Some(true));
+        assert_eq!(search.count, Some(2));
+    }
+
+    #[test]
+    fn test_02_parse_search_errors() {
+        let parse = |buffer: Vec<String>, store: bool| Search::parse(&buffer, store).is_err();
+        assert!(parse(vec_strings!["BYRADIUS", "1", "m"], false));
+        assert!(parse(vec_strings!["FROMMEMBER", "a"], false));
+        assert!(parse(
+            vec_strings!["FROMMEMBER", "a", "BYRADIUS", "1", "yd"],
+            false
+        ));
+        assert!(parse(
+            vec_strings!["FROMMEMBER", "a", "BYRADIUS", "1", "m", "ANY"],
+            false
+        ));
+        assert!(parse(
+            vec_strings!["FROMMEMBER", "a", "BYBOX", "1", "1", "m", "WITHDIST"],
+            true
+        ));
+        assert!(parse(
+            vec_strings!["FROMMEMBER", "a", "BYBOX", "1", "1", "m", "STOREDIST"],
+            false
+        ));
+        assert!(parse(
+            vec_strings!["FROMMEMBER", "a", "BYRADIUS", "-1", "m"],
+            false
+        ));
+    }
+
+    #[test]
+    fn test_03_parse_units() {
+        assert_eq!(parse_unit("KM").unwrap(), 1000.0);
+        assert_eq!(format_distance(166274.15156960033, 1000.0), "166.2742");
+        assert!(parse_unit("yd").is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    hyperloglog::HyperLogLog,
+    messages::redis_messages,
+    native_types::ErrorStruct,
+};
+
+pub mod pfadd;
+pub mod pfcount;
+pub mod pfmerge;
+
+/// Returns the HyperLogLog stored at the key, or [None] if it does not exist.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The value stored at **key** is not a string.
+/// * The string stored at **key** is not a valid HyperLogLog.
+fn load(database: &mut Database, key: &str) -> Result<Option<HyperLogLog>, ErrorStruct> {
+    match database.get(key) {
+        Some(TypeSaved::String(value)) => Ok(Some(HyperLogLog::from_bytes(value)?)),
+        Some(_) => err_wrongtype!(),
+        None => Ok(None),
+    }
+}
+
+/// Stores the HyperLogLog at the key, keeping its expiration time if it already exists.
+fn store(database: &mut Database, key: &str, hyperloglog: &HyperLogLog) {
+    match database.get_mut(key) {
+        Some(value) => *value = TypeSaved::String(hyperloglog.to_bytes()),
+        None => {
+            database.insert(key.to_string(), TypeSaved::String(hyperloglog.to_bytes()));
+        }
+    }
+}
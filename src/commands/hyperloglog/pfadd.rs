@@ -0,0 +1,92 @@
+use super::{load, store};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::hyperloglog::HyperLogLog;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::{ErrorStruct, RInteger, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct Pfadd;
+
+impl Runnable<Arc<Mutex<Database>>> for Pfadd {
+    /// Adds the elements to the HyperLogLog stored at **key**, which is created
+    /// if it does not exist.
+    ///
+    /// PFADD key [element [element ...]]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: 1 if the estimated cardinality changed
+    /// or the key was created, 0 otherwise.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a value which is not a HyperLogLog.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let (mut hyperloglog, mut updated) = match load(&mut database, &buffer[0])? {
+            Some(hyperloglog) => (hyperloglog, false),
+            None => (HyperLogLog::new(), true),
+        };
+        for element in buffer[1..].iter() {
+            updated |= hyperloglog.add(element.as_bytes());
+        }
+        if updated {
+            store(&mut database, &buffer[0], &hyperloglog);
+        }
+        Ok(RInteger::encode(updated as isize))
+    }
+}
+
+#[cfg(test)]
+mod test_pfadd {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_pfadd_returns_whether_the_hyperloglog_changed() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let result = Pfadd.run(vec_strings!["key"], &mut database);
+        assert_eq!(result.unwrap(), ":1\r\n");
+        let result = Pfadd.run(vec_strings!["key"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+        let result = Pfadd.run(vec_strings!["key", "a", "b", "c"], &mut database);
+        assert_eq!(result.unwrap(), ":1\r\n");
+        let result = Pfadd.run(vec_strings!["key", "a", "b"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+    }
+
+    #[test]
+    fn test_02_pfadd_on_values_which_are_not_hyperloglogs() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("string".to_string(), TypeSaved::String("value".into()));
+        let result = Pfadd.run(vec_strings!["string", "a"], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "WRONGTYPE Key is not a valid HyperLogLog string value."
+        );
+        database
+            .lock()
+            .unwrap()
+            .insert("list".to_string(), TypeSaved::List(Default::default()));
+        assert!(Pfadd.run(vec_strings!["list", "a"], &mut database).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use super::{load, store};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::hyperloglog::HyperLogLog;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::{ErrorStruct, RInteger, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct Pfcount;
+
+impl Runnable<Arc<Mutex<Database>>> for Pfcount {
+    /// Returns the estimated cardinality of the HyperLogLog stored at **key**, caching it
+    /// in the value. With several keys, returns the estimated cardinality of their union,
+    /// without caching it. The missing keys are empty HyperLogLogs.
+    ///
+    /// PFCOUNT key [key ...]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the estimated number of unique elements.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Some key holds a value which is not a HyperLogLog.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        if buffer.len() == 1 {
+            return match load(&mut database, &buffer[0])? {
+                Some(mut hyperloglog) => {
+                    let cached = hyperloglog.cached_cardinality().is_some();
+                    let cardinality = hyperloglog.count();
+                    if !cached {
+                        store(&mut database, &buffer[0], &hyperloglog);
+                    }
+                    Ok(RInteger::encode(cardinality as isize))
+                }
+                None => Ok(RInteger::encode(0)),
+            };
+        }
+        let mut union = HyperLogLog::new();
+        for key in buffer.iter() {
+            if let Some(hyperloglog) = load(&mut database, key)? {
+                union.merge(&hyperloglog);
+            }
+        }
+        Ok(RInteger::encode(union.count() as isize))
+    }
+}
+
+#[cfg(test)]
+mod test_pfcount {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::commands::hyperloglog::pfadd::Pfadd;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_pfcount_of_one_key_caches_the_cardinality() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        Pfadd
+            .run(
+                vec_strings!["key", "a", "b", "c", "d", "e", "f", "g"],
+                &mut database,
+            )
+            .unwrap();
+        let result = Pfcount.run(vec_strings!["key"], &mut database);
+        assert_eq!(result.unwrap(), ":7\r\n");
+        match database.lock().unwrap().get("key") {
+            Some(TypeSaved::String(value)) => {
+                assert_eq!(&value[8..16], &7u64.to_le_bytes());
+            }
+            _ => panic!("the key does not hold a string"),
+        }
+        let result = Pfcount.run(vec_strings!["missing"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+    }
+
+    #[test]
+    fn test_02_pfcount_of_several_keys_counts_their_union() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        Pfadd
+            .run(vec_strings!["first", "a", "b", "c"], &mut database)
+            .unwrap();
+        Pfadd
+            .run(vec_strings!["second", "c", "d"], &mut database)
+            .unwrap();
+        let result = Pfcount.run(vec_strings!["first", "second", "missing"], &mut database);
+        assert_eq!(result.unwrap(), ":4\r\n");
+    }
+}
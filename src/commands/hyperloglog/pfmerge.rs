@@ -0,0 +1,78 @@
+use super::{load, store};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::hyperloglog::HyperLogLog;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::{ErrorStruct, RSimpleString, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct Pfmerge;
+
+impl Runnable<Arc<Mutex<Database>>> for Pfmerge {
+    /// Merges the HyperLogLogs stored at the source keys into the one stored at
+    /// **destkey**, which is created if it does not exist. The missing source keys
+    /// are empty HyperLogLogs.
+    ///
+    /// PFMERGE destkey [sourcekey [sourcekey ...]]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: OK.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Some key holds a value which is not a HyperLogLog.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let mut merged = load(&mut database, &buffer[0])?.unwrap_or_else(HyperLogLog::new);
+        for key in buffer[1..].iter() {
+            if let Some(hyperloglog) = load(&mut database, key)? {
+                merged.merge(&hyperloglog);
+            }
+        }
+        store(&mut database, &buffer[0], &merged);
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
+
+#[cfg(test)]
+mod test_pfmerge {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::commands::hyperloglog::{pfadd::Pfadd, pfcount::Pfcount};
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_pfmerge_stores_the_union() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        Pfadd
+            .run(vec_strings!["first", "a", "b", "c"], &mut database)
+            .unwrap();
+        Pfadd
+            .run(vec_strings!["second", "c", "d", "e"], &mut database)
+            .unwrap();
+        let result = Pfmerge.run(
+            vec_strings!["dest", "first", "second", "missing"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "+OK\r\n");
+        let result = Pfcount.run(vec_strings!["dest"], &mut database);
+        assert_eq!(result.unwrap(), ":5\r\n");
+        let result = Pfmerge.run(vec_strings!["first", "second"], &mut database);
+        assert_eq!(result.unwrap(), "+OK\r\n");
+        let result = Pfcount.run(vec_strings!["first"], &mut database);
+        assert_eq!(result.unwrap(), ":5\r\n");
+    }
+}
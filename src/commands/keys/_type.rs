@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, check_not_empty, Runnable},
+    commands::Runnable,
     database::{Database, TypeSaved},
     native_types::{ErrorStruct, RSimpleString, RedisType},
 };
@@ -18,8 +18,6 @@ impl Runnable<Arc<Mutex<Database>>> for Type {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with a number of elements
-    /// different than 1.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -32,14 +30,14 @@ impl Runnable<Arc<Mutex<Database>>> for Type {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_empty(&buffer, "type")?;
         let key = buffer.pop().unwrap();
-        check_not_empty(&buffer)?;
         if let Some(typesaved) = database.get(&key) {
             match typesaved {
                 TypeSaved::String(_) => Ok(RSimpleString::encode("string".to_string())),
                 TypeSaved::Set(_) => Ok(RSimpleString::encode("set".to_string())),
                 TypeSaved::List(_) => Ok(RSimpleString::encode("list".to_string())),
+                TypeSaved::Stream(_) => Ok(RSimpleString::encode("stream".to_string())),
+                TypeSaved::SortedSet(_) => Ok(RSimpleString::encode("zset".to_string())),
             }
         } else {
             Ok(RSimpleString::encode("none".to_string()))
@@ -67,7 +65,7 @@ mod test_type {
         database
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer = vec_strings!["key"];
         let result = Type.run(buffer, &mut database);
         assert_eq!(RSimpleString::encode("string".to_string()), result.unwrap());
@@ -112,7 +110,7 @@ mod test_type {
         database
             .lock()
             .unwrap()
-            .insert("key1".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key1".to_string(), TypeSaved::String("value".into()));
         let buffer = vec_strings!["key2"];
         let result = Type.run(buffer, &mut database);
         assert_eq!(RSimpleString::encode("none".to_string()), result.unwrap());
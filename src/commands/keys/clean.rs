@@ -1,5 +1,5 @@
 use crate::{
-    commands::keys::{no_more_values, parse_integer, pop_value},
+    commands::keys::parse_integer,
     commands::Runnable,
     database::Database,
     messages::redis_messages,
@@ -20,8 +20,6 @@ impl Runnable<Arc<Mutex<Database>>> for Clean {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with a number of elements
-    /// different than 1.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -34,9 +32,7 @@ impl Runnable<Arc<Mutex<Database>>> for Clean {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        let argument = pop_value(&mut buffer, "clean")?;
-
-        no_more_values(&buffer, "clean")?;
+        let argument = buffer.pop().unwrap();
         let iterations = parse_integer(argument)?;
 
         let mut continue_cleaning = true;
@@ -74,24 +70,14 @@ mod test_clean {
 
     use super::*;
     use crate::database::TypeSaved;
+    use crate::time_expiration::clock::ManualClock;
     use crate::vec_strings;
     use std::collections::VecDeque;
-    use std::thread::sleep;
-    use std::time::Duration;
 
     fn load_database(database: &mut Database) {
-        database.insert(
-            "Agustin".to_string(),
-            TypeSaved::String("Firmapaz".to_string()),
-        );
-        database.insert(
-            "Martina".to_string(),
-            TypeSaved::String("Panetta".to_string()),
-        );
-        database.insert(
-            "Federico".to_string(),
-            TypeSaved::String("Pacheco".to_string()),
-        );
+        database.insert("Agustin".to_string(), TypeSaved::String("Firmapaz".into()));
+        database.insert("Martina".to_string(), TypeSaved::String("Panetta".into()));
+        database.insert("Federico".to_string(), TypeSaved::String("Pacheco".into()));
 
         let mut profes: VecDeque<String> = VecDeque::new();
         profes.push_back("Pablo".to_string());
@@ -104,14 +90,16 @@ mod test_clean {
     //#[ignore = "Long test"]
     fn test_01_cleaning_some_keys() {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let clock = Arc::new(ManualClock::default());
         let mut database = Database::new(notifier);
+        database.set_clock(clock.clone());
 
         load_database(&mut database);
 
         database.set_ttl("Agustin", 2).unwrap();
         database.set_ttl("Federico", 5).unwrap();
 
-        sleep(Duration::new(5, 0));
+        clock.advance_secs(5);
 
         let command = vec_strings!["3"];
         let mut c_database = Arc::new(Mutex::new(database));
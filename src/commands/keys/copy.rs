@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, Runnable},
+    commands::{get_as_integer, Runnable},
     database::Database,
     messages::redis_messages,
     native_types::{ErrorStruct, RInteger, RedisType},
@@ -10,11 +10,17 @@ use std::sync::{Arc, Mutex};
 pub struct Copy;
 
 impl Runnable<Arc<Mutex<Database>>> for Copy {
-    /// This command copies the value stored at the source key to the destination key.
+    /// This command copies the value stored at the source key to the destination key,
+    /// together with its timeout.
     /// The command returns an error when the destination key already exists.
     /// Time complexity: O(N) worst case for collections, where N is the number of
     /// nested items. O(1) for string values.
     ///
+    /// COPY source destination [DB destination-db] [REPLACE]
+    ///
+    /// * DB: the database of the destination key. There is only the database 0.
+    /// * REPLACE: removes the destination key before copying the value to it.
+    ///
     /// # Return value
     /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): 1 if source was copied.
     /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): 0 if source was not copied.
@@ -22,8 +28,8 @@ impl Runnable<Arc<Mutex<Database>>> for Copy {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with a number of elements
-    /// different than 2.
+    /// * Some option is unknown, or the destination database is not 0.
+    /// * Source and destination are the same key.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -36,34 +42,54 @@ impl Runnable<Arc<Mutex<Database>>> for Copy {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_error_cases(&buffer)?;
+        let replace = check_error_cases(&buffer)?;
 
         let key_source = &buffer[0];
         let key_destinty = &buffer[1];
 
+        if replace && database.contains_key(key_source) {
+            database.remove(key_destinty);
+        }
         if !database.contains_key(key_source) | database.contains_key(key_destinty) {
             Ok(RInteger::encode(0))
         } else {
             let value = database.get(key_source).unwrap().clone(); // Unwrap Reason: Value associated for Key Source exists!
+            let timeout = database.ttl_millis(key_source);
             database.insert(key_destinty.to_string(), value);
+            if let Some(timeout) = timeout {
+                database.set_ttl_millis(key_destinty, timeout)?;
+            }
             Ok(RInteger::encode(1))
         }
     }
 }
 
-fn check_error_cases(buffer: &[String]) -> Result<(), ErrorStruct> {
-    check_empty(buffer, "copy")?;
+/// Checks the arguments and options, returning true if REPLACE was given.
+fn check_error_cases(buffer: &[String]) -> Result<bool, ErrorStruct> {
+    let mut replace = false;
+    let mut options = buffer[2..].iter();
+    while let Some(option) = options.next() {
+        match option.to_uppercase().as_str() {
+            "REPLACE" => replace = true,
+            "DB" => {
+                let db = options
+                    .next()
+                    .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))?;
+                if get_as_integer(db)? != 0 {
+                    return Err(ErrorStruct::from(redis_messages::db_index_out_of_range()));
+                }
+            }
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        }
+    }
 
-    if buffer.len() != 2 {
-        // never "copy" or "copy arg1"
-        let error_message = redis_messages::arguments_invalid_to("copy");
-        return Err(ErrorStruct::new(
-            error_message.get_prefix(),
-            error_message.get_message(),
+    if buffer[0] == buffer[1] {
+        return Err(ErrorStruct::from(
+            redis_messages::same_source_and_destination(),
         ));
     }
 
-    Ok(())
+    Ok(replace)
 }
 
 #[cfg(test)]
@@ -84,7 +110,7 @@ mod test_copy_function {
         database_mock
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock_get = vec_strings!["key", "key_new"];
 
         let result_received = Copy.run(buffer_mock_get, &mut database_mock);
@@ -94,11 +120,11 @@ mod test_copy_function {
 
         let mut mutex_db = database_mock.lock().unwrap();
         if let TypeSaved::String(set_post_copy) = mutex_db.get("key").unwrap() {
-            assert!(set_post_copy.contains("value"));
+            assert_eq!(set_post_copy, b"value");
         }
 
         if let TypeSaved::String(set_post_copy) = mutex_db.get("key_new").unwrap() {
-            assert!(set_post_copy.contains("value"));
+            assert_eq!(set_post_copy, b"value");
         }
     }
 
@@ -110,11 +136,11 @@ mod test_copy_function {
         database_mock
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
-        database_mock.lock().unwrap().insert(
-            "key_new".to_string(),
-            TypeSaved::String("value".to_string()),
-        );
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        database_mock
+            .lock()
+            .unwrap()
+            .insert("key_new".to_string(), TypeSaved::String("value".into()));
         let buffer_mock_get = vec_strings!["key", "key_new"];
 
         let result_received = Copy.run(buffer_mock_get, &mut database_mock);
@@ -131,7 +157,7 @@ mod test_copy_function {
         database_mock
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock_get = vec_strings!["key_random", "key_new"];
 
         let result_received = Copy.run(buffer_mock_get, &mut database_mock);
@@ -207,4 +233,56 @@ mod test_copy_function {
             assert!(set_post_copy.len().eq(&2))
         }
     }
+
+    #[test]
+    fn test_07_copy_with_replace_overwrites_the_destination_and_keeps_the_timeout() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database_mock = Arc::new(Mutex::new(Database::new(notifier)));
+        database_mock
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        database_mock.lock().unwrap().set_ttl("key", 100).unwrap();
+        database_mock
+            .lock()
+            .unwrap()
+            .insert("key_new".to_string(), TypeSaved::String("old".into()));
+
+        let result_received = Copy.run(
+            vec_strings!["key", "key_new", "DB", "0", "REPLACE"],
+            &mut database_mock,
+        );
+
+        assert_eq!(RInteger::encode(1), result_received.unwrap());
+        let mut mutex_db = database_mock.lock().unwrap();
+        assert_eq!(
+            mutex_db.get("key_new"),
+            Some(&TypeSaved::String("value".into()))
+        );
+        assert!(mutex_db.ttl("key_new").is_some());
+    }
+
+    #[test]
+    fn test_08_copy_to_other_database_or_same_key_return_error() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database_mock = Arc::new(Mutex::new(Database::new(notifier)));
+        database_mock
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+
+        let result_received = Copy.run(
+            vec_strings!["key", "key_new", "DB", "1"],
+            &mut database_mock,
+        );
+        assert_eq!(
+            result_received.unwrap_err().print_it(),
+            "ERR DB index is out of range"
+        );
+        let result_received = Copy.run(vec_strings!["key", "key"], &mut database_mock);
+        assert_eq!(
+            result_received.unwrap_err().print_it(),
+            "ERR source and destination objects are the same"
+        );
+    }
 }
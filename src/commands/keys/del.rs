@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, check_not_empty, Runnable},
+    commands::Runnable,
     database::Database,
     messages::redis_messages,
     native_types::{ErrorStruct, RInteger, RedisType},
@@ -23,12 +23,10 @@ impl Runnable<Arc<Mutex<Database>>> for Del {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with a number of elements
-    /// different than 1.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
-        mut buffer: Vec<String>,
+        buffer: Vec<String>,
         database: &mut Arc<Mutex<Database>>,
     ) -> Result<String, ErrorStruct> {
         let mut database = database.lock().map_err(|_| {
@@ -37,14 +35,11 @@ impl Runnable<Arc<Mutex<Database>>> for Del {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_empty(&buffer, "del")?;
-        let key = buffer.pop().unwrap();
-        check_not_empty(&buffer)?;
-        if database.remove(&key).is_some() {
-            Ok(RInteger::encode(1))
-        } else {
-            Ok(RInteger::encode(0))
-        }
+        let removed = buffer
+            .iter()
+            .filter(|key| database.remove(key).is_some())
+            .count();
+        Ok(RInteger::encode(removed as isize))
     }
 }
 
@@ -62,7 +57,7 @@ mod test_del {
         database
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock_del = vec_strings!["key"];
         let result_received = Del.run(buffer_mock_del, &mut database);
         assert_eq!(RInteger::encode(1), result_received.unwrap());
@@ -75,7 +70,7 @@ mod test_del {
         database
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock_del = vec_strings!["key1"];
         let result_received = Del.run(buffer_mock_del, &mut database);
         assert_eq!(RInteger::encode(0), result_received.unwrap());
@@ -88,7 +83,7 @@ mod test_del {
         database
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock_del_1 = vec_strings!["key"];
         let result1 = Del.run(buffer_mock_del_1, &mut database);
         assert_eq!(RInteger::encode(1), result1.unwrap());
@@ -96,4 +91,19 @@ mod test_del {
         let result2 = Del.run(buffer_mock_del_2, &mut database);
         assert_eq!(RInteger::encode(0), result2.unwrap());
     }
+
+    #[test]
+    fn test_04_del_several_keys_counts_the_removed_ones() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        for key in ["a", "b"] {
+            database
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), TypeSaved::String("value".into()));
+        }
+        let result_received = Del.run(vec_strings!["a", "b", "c", "a"], &mut database);
+        assert_eq!(RInteger::encode(2), result_received.unwrap());
+        assert!(!database.lock().unwrap().contains_key("b"));
+    }
 }
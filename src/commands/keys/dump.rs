@@ -0,0 +1,69 @@
+use crate::database::Database;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::payload;
+use crate::{
+    commands::Runnable,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RBulkString, RedisType},
+};
+use std::sync::{Arc, Mutex};
+
+pub struct Dump;
+
+impl Runnable<Arc<Mutex<Database>>> for Dump {
+    /// Serialize the value stored at key in an opaque format, which includes a version
+    /// and a CRC64 checksum, and return it to the user. The returned value can be
+    /// turned back into a key using the RESTORE command. The timeout is not included.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the serialized value, or nil if key does not exist.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let key = buffer.pop().unwrap();
+
+        match database.get(&key) {
+            Some(value) => Ok(RBulkString::encode(payload::serialize(value))),
+            None => Ok(RBulkString::encode(redis_messages::nil())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_dump {
+    use crate::commands::create_notifier;
+
+    use super::*;
+    use crate::{database::TypeSaved, vec_strings};
+
+    #[test]
+    fn test_01_dump_existing_and_non_existing_keys() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let value = TypeSaved::String("value".into());
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), value.clone());
+        let result = Dump.run(vec_strings!["key"], &mut database);
+        assert_eq!(
+            result.unwrap(),
+            RBulkString::encode(payload::serialize(&value))
+        );
+        let result = Dump.run(vec_strings!["missing"], &mut database);
+        assert_eq!(result.unwrap(), "$-1\r\n");
+    }
+}
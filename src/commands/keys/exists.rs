@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, check_not_empty, Runnable},
+    commands::Runnable,
     database::Database,
     messages::redis_messages,
     native_types::{ErrorStruct, RInteger, RedisType},
@@ -10,21 +10,19 @@ use std::sync::{Arc, Mutex};
 pub struct Exists;
 
 impl Runnable<Arc<Mutex<Database>>> for Exists {
-    /// Returns if key exists.
+    /// Returns how many of the keys exist. A key received several times is
+    /// counted as many times.
     ///
     /// # Return value
-    /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): 1 if the timeout was set.
-    /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): 0 if key does not exist.
+    /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): the number of keys that exist.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with a number of elements
-    /// different than 1.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
-        mut buffer: Vec<String>,
+        buffer: Vec<String>,
         database: &mut Arc<Mutex<Database>>,
     ) -> Result<String, ErrorStruct> {
         let mut database = database.lock().map_err(|_| {
@@ -33,14 +31,11 @@ impl Runnable<Arc<Mutex<Database>>> for Exists {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_empty(&buffer, "exists")?;
-        let key = buffer.pop().unwrap();
-        check_not_empty(&buffer)?;
-        if database.contains_key(&key) {
-            Ok(RInteger::encode(1))
-        } else {
-            Ok(RInteger::encode(0))
-        }
+        let existing = buffer
+            .iter()
+            .filter(|key| database.contains_key(key))
+            .count();
+        Ok(RInteger::encode(existing as isize))
     }
 }
 
@@ -58,7 +53,7 @@ mod test_exists {
         database
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock = vec!["key".to_string()];
         let result_received = Exists.run(buffer_mock, &mut database);
         assert_eq!(RInteger::encode(1), result_received.unwrap());
@@ -71,9 +66,22 @@ mod test_exists {
         database
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock = vec!["key1".to_string()];
         let result_received = Exists.run(buffer_mock, &mut database);
         assert_eq!(RInteger::encode(0), result_received.unwrap());
     }
+
+    #[test]
+    fn test_03_exists_several_keys_counts_the_repeated_ones() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        let buffer_mock = vec!["key".to_string(), "other".to_string(), "key".to_string()];
+        let result_received = Exists.run(buffer_mock, &mut database);
+        assert_eq!(RInteger::encode(2), result_received.unwrap());
+    }
 }
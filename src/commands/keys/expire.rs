@@ -1,4 +1,4 @@
-use super::{no_more_values, parse_integer, pop_value};
+use super::{parse_integer, ExpireConditions};
 use crate::database::Database;
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
@@ -18,19 +18,29 @@ impl Runnable<Arc<Mutex<Database>>> for Expire {
     /// key without replacing it with a new one will leave the timeout untouched.
     /// The timeout can also be cleared, turning the key back into a persistent key.
     ///
+    /// EXPIRE key seconds [NX | XX | GT | LT]
+    ///
+    /// * NX: sets the timeout only when the key has none.
+    /// * XX: sets the timeout only when the key already has one.
+    /// * GT: sets the timeout only when it is greater than the current one.
+    /// * LT: sets the timeout only when it is less than the current one.
+    ///
+    /// A key without timeout counts as having an infinite one for GT and LT.
+    ///
     /// # Return value
     /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): 1 if the timeout was set.
-    /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): 0 if key does not exist.
+    /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): 0 if key does not exist
+    ///   or the conditions given are not met.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with a number of elements
-    /// different than 2.
+    /// * Buffer [Vec]<[String]> is received with less than 2 elements.
+    /// * Some option is unknown, or NX, GT and LT are combined in a way that is not compatible.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
-        mut buffer: Vec<String>,
+        buffer: Vec<String>,
         database: &mut Arc<Mutex<Database>>,
     ) -> Result<String, ErrorStruct> {
         let mut database = database.lock().map_err(|_| {
@@ -39,15 +49,25 @@ impl Runnable<Arc<Mutex<Database>>> for Expire {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        let timeout = pop_value(&mut buffer, "Expire")?;
+        if buffer.len() < 2 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "expire",
+            )));
+        }
+        let conditions = ExpireConditions::parse(&buffer[2..])?;
+        let timeout = buffer[1].to_string();
         if timeout.starts_with('-') {
             return Err(ErrorStruct::from(redis_messages::negative_number()));
         }
         let timeout = parse_integer(timeout)? as u64;
-        let key = pop_value(&mut buffer, "Expire")?;
-        no_more_values(&buffer, "Expire")?;
+        let key = &buffer[0];
+        if !database.contains_key(key)
+            || !conditions.allow(database.ttl_millis(key), timeout as i128 * 1000)
+        {
+            return Ok(RInteger::encode(0));
+        }
 
-        check_errors(database.set_ttl(&key, timeout))
+        check_errors(database.set_ttl(key, timeout))
     }
 }
 
@@ -1,4 +1,4 @@
-use super::{no_more_values, parse_integer, pop_value};
+use super::{parse_integer, ExpireConditions};
 use crate::database::Database;
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
@@ -16,18 +16,24 @@ impl Runnable<Arc<Mutex<Database>>> for ExpireAt {
     /// the number of seconds representing the TTL (time to live), it takes an absolute
     /// Unix timestamp. A timestamp in the past will delete the key immediately.
     ///
+    /// EXPIREAT key unix-time-seconds [NX | XX | GT | LT]
+    ///
+    /// The options have the same meaning as in [Expire](super::expire::Expire).
+    ///
     /// # Return value
     /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): 1 if the timeout was set.
-    /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): 0 if key does not exist.
+    /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): 0 if key does not exist
+    ///   or the conditions given are not met.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with more than 1 element.
+    /// * Buffer [Vec]<[String]> is received with less than 2 elements.
+    /// * Some option is unknown, or NX, GT and LT are combined in a way that is not compatible.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
-        mut buffer: Vec<String>,
+        buffer: Vec<String>,
         database: &mut Arc<Mutex<Database>>,
     ) -> Result<String, ErrorStruct> {
         let mut database = database.lock().map_err(|_| {
@@ -36,15 +42,28 @@ impl Runnable<Arc<Mutex<Database>>> for ExpireAt {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        let timeout = pop_value(&mut buffer, "Expire")?;
+        if buffer.len() < 2 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "expireat",
+            )));
+        }
+        let conditions = ExpireConditions::parse(&buffer[2..])?;
+        let timeout = buffer[1].to_string();
         if timeout.starts_with('-') {
             return Err(ErrorStruct::from(redis_messages::negative_number()));
         }
         let timeout = parse_integer(timeout)? as u64;
-        let key = pop_value(&mut buffer, "Expire")?;
-        no_more_values(&buffer, "Expire")?;
+        let key = &buffer[0];
+        if !database.contains_key(key)
+            || !conditions.allow(
+                database.ttl_millis(key),
+                timeout as i128 * 1000 - database.clock().unix_millis() as i128,
+            )
+        {
+            return Ok(RInteger::encode(0));
+        }
 
-        check_errors(database.set_ttl_unix_timestamp(&key, timeout))
+        check_errors(database.set_ttl_unix_timestamp(key, timeout))
     }
 }
 
@@ -0,0 +1,82 @@
+use crate::database::Database;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable,
+    messages::redis_messages,
+    native_types::ErrorStruct,
+    native_types::{RInteger, RedisType},
+};
+
+use std::sync::{Arc, Mutex};
+pub struct ExpireTime;
+
+impl Runnable<Arc<Mutex<Database>>> for ExpireTime {
+    /// Returns the absolute Unix timestamp, in seconds, at which the given key will expire.
+    /// The command returns -2 if the key does not exist.
+    /// The command returns -1 if the key exists but has no associated expire.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): expiration Unix timestamp
+    /// in seconds, or a negative value in order to signal an error.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let key = buffer.pop().unwrap();
+
+        Ok(RInteger::encode(
+            match expire_time_millis(&mut database, &key) {
+                Some(millis) => millis / 1000,
+                None if database.contains_key(&key) => -1,
+                None => -2,
+            },
+        ))
+    }
+}
+
+/// Returns the Unix timestamp in milliseconds at which the key expires,
+/// or None if it does not exist or has no timeout.
+pub fn expire_time_millis(database: &mut Database, key: &str) -> Option<isize> {
+    let now = database.clock().unix_millis();
+    database.ttl_millis(key).map(|ttl| (now + ttl) as isize)
+}
+
+#[cfg(test)]
+mod test_expiretime {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::time_expiration::clock::ManualClock;
+    use crate::{database::TypeSaved, vec_strings};
+
+    #[test]
+    fn test_01_expiretime_of_keys_with_and_without_timeout() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Database::new(notifier);
+        database.set_clock(Arc::new(ManualClock::from_unix_millis(1_000_000)));
+        let mut database = Arc::new(Mutex::new(database));
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        let result = ExpireTime.run(vec_strings!["key"], &mut database);
+        assert_eq!(result.unwrap(), ":-1\r\n");
+        let result = ExpireTime.run(vec_strings!["missing"], &mut database);
+        assert_eq!(result.unwrap(), ":-2\r\n");
+
+        database.lock().unwrap().set_ttl("key", 100).unwrap();
+        let result = ExpireTime.run(vec_strings!["key"], &mut database);
+        assert_eq!(result.unwrap(), ":1100\r\n");
+    }
+}
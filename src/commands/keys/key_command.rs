@@ -1,4 +1,3 @@
-use crate::commands::keys::{no_more_values, pop_value};
 use crate::commands::Runnable;
 use crate::database::Database;
 use crate::messages::redis_messages::{self, wrong_regex_pattern};
@@ -23,7 +22,6 @@ impl Runnable<Arc<Mutex<Database>>> for Keys {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with more than 1 element.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -36,8 +34,7 @@ impl Runnable<Arc<Mutex<Database>>> for Keys {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        let regex = pop_value(&mut buffer, "keys")?;
-        no_more_values(&buffer, "keys")?;
+        let regex = buffer.pop().unwrap();
 
         match database.match_pattern(&regex) {
             Ok(vec) => Ok(RArray::encode(vec)),
@@ -56,25 +53,16 @@ mod test_keys {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut db = Database::new(notifier);
 
-        db.insert(String::from("Camo"), TypeSaved::String(String::from("a")));
-        db.insert(String::from("Cemo"), TypeSaved::String(String::from("a")));
-        db.insert(String::from("Cimo"), TypeSaved::String(String::from("a")));
-        db.insert(String::from("Como"), TypeSaved::String(String::from("a")));
-        db.insert(String::from("David"), TypeSaved::String(String::from("a")));
-        db.insert(String::from("Hello"), TypeSaved::String(String::from("a")));
-        db.insert(
-            String::from("Hassallo"),
-            TypeSaved::String(String::from("a")),
-        );
-        db.insert(
-            String::from("dsaHello"),
-            TypeSaved::String(String::from("a")),
-        );
-        db.insert(String::from("Hollo"), TypeSaved::String(String::from("a")));
-        db.insert(
-            String::from("Hiaillo"),
-            TypeSaved::String(String::from("a")),
-        );
+        db.insert(String::from("Camo"), TypeSaved::String("a".into()));
+        db.insert(String::from("Cemo"), TypeSaved::String("a".into()));
+        db.insert(String::from("Cimo"), TypeSaved::String("a".into()));
+        db.insert(String::from("Como"), TypeSaved::String("a".into()));
+        db.insert(String::from("David"), TypeSaved::String("a".into()));
+        db.insert(String::from("Hello"), TypeSaved::String("a".into()));
+        db.insert(String::from("Hassallo"), TypeSaved::String("a".into()));
+        db.insert(String::from("dsaHello"), TypeSaved::String("a".into()));
+        db.insert(String::from("Hollo"), TypeSaved::String("a".into()));
+        db.insert(String::from("Hiaillo"), TypeSaved::String("a".into()));
 
         db
     }
@@ -0,0 +1,360 @@
+use crate::database::Database;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::payload;
+use crate::{
+    commands::{get_as_integer, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RArray, RSimpleString, RedisType},
+};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Milliseconds used when the timeout given is not positive, as Redis.
+const DEFAULT_TIMEOUT: u64 = 1000;
+
+pub struct Migrate;
+
+/// Options of MIGRATE.
+#[derive(Default)]
+struct MigrateOptions {
+    copy: bool,
+    replace: bool,
+    auth: Option<Vec<String>>,
+    keys: Vec<String>,
+}
+
+impl Runnable<Arc<Mutex<Database>>> for Migrate {
+    /// Atomically transfer keys from this instance to a destination instance. The keys
+    /// are serialized with DUMP and sent to the destination with RESTORE, together with
+    /// their timeouts. On success, the keys are deleted from this instance.
+    ///
+    /// MIGRATE host port key|"" destination-db timeout [COPY] [REPLACE] [AUTH password | AUTH2 username password] [KEYS key [key ...]]
+    ///
+    /// * destination-db: there is only the database 0.
+    /// * timeout: milliseconds to connect, write and read from the destination.
+    /// * COPY: does not remove the keys from this instance.
+    /// * REPLACE: replaces the keys existing in the destination.
+    /// * AUTH, AUTH2: authenticates with the destination before transferring the keys.
+    /// * KEYS: the keys to transfer, when key is the empty string.
+    ///
+    /// # Return value
+    /// * [String] _encoded_ in [RSimpleString]: "OK" if the keys were transferred.
+    /// * [String] _encoded_ in [RSimpleString]: "NOKEY" if no key exists in this instance.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The destination can not be reached, or replied with an error.
+    /// * The destination database is not 0, or some option is not valid.
+    /// * Buffer [Vec]<[String]> is received with less than 5 elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        if buffer.len() < 5 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "migrate",
+            )));
+        }
+        let options = parse_options(&buffer[2], &buffer[5..])?;
+        if get_as_integer(&buffer[3])? != 0 {
+            return Err(ErrorStruct::from(redis_messages::db_index_out_of_range()));
+        }
+        let timeout = match get_as_integer(&buffer[4])? {
+            timeout if timeout <= 0 => DEFAULT_TIMEOUT,
+            timeout => timeout as u64,
+        };
+
+        // The lock is released while talking with the destination, which
+        // could be this same instance
+        let dumped = dump_keys(database, &options.keys)?;
+        if dumped.is_empty() {
+            return Ok(RSimpleString::encode("NOKEY".to_string()));
+        }
+        let mut target = Target::connect(&buffer[0], &buffer[1], timeout)?;
+        if let Some(auth) = options.auth {
+            target.send(auth)?;
+        }
+        let mut migrated = Vec::new();
+        let mut result = Ok(RSimpleString::encode(redis_messages::ok()));
+        for (key, ttl, serialized) in dumped {
+            let mut command = vec![
+                "RESTORE".to_string(),
+                key.clone(),
+                ttl.to_string(),
+                serialized,
+            ];
+            if options.replace {
+                command.push("REPLACE".to_string());
+            }
+            if let Err(error) = target.send(command) {
+                result = Err(error);
+                break;
+            }
+            migrated.push(key);
+        }
+
+        if !options.copy {
+            let mut database = lock(database)?;
+            for key in migrated {
+                database.remove(&key);
+            }
+        }
+        result
+    }
+}
+
+fn parse_options(key: &str, buffer: &[String]) -> Result<MigrateOptions, ErrorStruct> {
+    let mut options = MigrateOptions::default();
+    let mut arguments = buffer.iter();
+    while let Some(option) = arguments.next() {
+        match option.to_uppercase().as_str() {
+            "COPY" => options.copy = true,
+            "REPLACE" => options.replace = true,
+            "AUTH" => options.auth = Some(vec!["AUTH".to_string(), next(&mut arguments)?]),
+            "AUTH2" => {
+                let username = next(&mut arguments)?;
+                options.auth = Some(vec!["AUTH".to_string(), username, next(&mut arguments)?]);
+            }
+            "KEYS" if key.is_empty() => {
+                options.keys = arguments.by_ref().cloned().collect();
+                if options.keys.is_empty() {
+                    return Err(ErrorStruct::from(redis_messages::syntax_error()));
+                }
+            }
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        }
+    }
+    if options.keys.is_empty() {
+        if key.is_empty() {
+            return Err(ErrorStruct::from(redis_messages::syntax_error()));
+        }
+        options.keys.push(key.to_string());
+    }
+    Ok(options)
+}
+
+fn next<'a>(arguments: &mut impl Iterator<Item = &'a String>) -> Result<String, ErrorStruct> {
+    arguments
+        .next()
+        .cloned()
+        .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))
+}
+
+fn lock(
+    database: &Arc<Mutex<Database>>,
+) -> Result<std::sync::MutexGuard<'_, Database>, ErrorStruct> {
+    database.lock().map_err(|_| {
+        ErrorStruct::from(redis_messages::poisoned_lock(
+            "database",
+            ErrorSeverity::ShutdownServer,
+        ))
+    })
+}
+
+/// Serializes the keys that exist, with their timeouts in milliseconds (0 if they have none).
+fn dump_keys(
+    database: &Arc<Mutex<Database>>,
+    keys: &[String],
+) -> Result<Vec<(String, u64, String)>, ErrorStruct> {
+    let mut database = lock(database)?;
+    Ok(keys
+        .iter()
+        .filter_map(|key| {
+            let serialized = payload::serialize(database.get(key)?);
+            let ttl = database.ttl_millis(key).map_or(0, |ttl| ttl.max(1));
+            Some((key.to_string(), ttl, serialized))
+        })
+        .collect())
+}
+
+/// Connection with the destination instance.
+struct Target {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl Target {
+    fn connect(host: &str, port: &str, timeout: u64) -> Result<Self, ErrorStruct> {
+        let connecting = || ErrorStruct::from(redis_messages::migrate_io_error("connecting to"));
+        let timeout = Duration::from_millis(timeout);
+        let address = (host, port.parse::<u16>().map_err(|_| connecting())?)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .ok_or_else(connecting)?;
+        let stream = TcpStream::connect_timeout(&address, timeout).map_err(|_| connecting())?;
+        stream
+            .set_read_timeout(Some(timeout))
+            .and_then(|_| stream.set_write_timeout(Some(timeout)))
+            .map_err(|_| connecting())?;
+        let reader = BufReader::new(stream.try_clone().map_err(|_| connecting())?);
+        Ok(Target { stream, reader })
+    }
+
+    /// Sends a command and waits for its reply, which must not be an error.
+    fn send(&mut self, command: Vec<String>) -> Result<(), ErrorStruct> {
+        let io_error = || ErrorStruct::from(redis_messages::migrate_io_error("writing to"));
+        self.stream
+            .write_all(RArray::encode(command).as_bytes())
+            .map_err(|_| io_error())?;
+        let mut reply = String::new();
+        // This server follows each reply with an empty line
+        while reply.trim_end().is_empty() {
+            reply.clear();
+            match self.reader.read_line(&mut reply) {
+                Ok(0) | Err(_) => {
+                    return Err(ErrorStruct::from(redis_messages::migrate_io_error(
+                        "reading from",
+                    )))
+                }
+                Ok(_) => {}
+            }
+        }
+        match reply.trim_end().strip_prefix('-') {
+            Some(error) => Err(ErrorStruct::from(redis_messages::target_replied_error(
+                error,
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_migrate {
+    use crate::commands::create_notifier;
+
+    use super::*;
+    use crate::{database::TypeSaved, vec_strings};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Accepts one client and answers each command with the reply given,
+    /// returning the commands received.
+    fn fake_target(reply: &'static str) -> (String, thread::JoinHandle<Vec<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut lines = BufReader::new(stream).lines();
+            let mut commands = Vec::new();
+            while let Some(Ok(header)) = lines.next() {
+                let len: usize = header[1..].parse().unwrap();
+                let command: Vec<String> = (0..len)
+                    .map(|_| {
+                        lines.next();
+                        lines.next().unwrap().unwrap()
+                    })
+                    .collect();
+                commands.push(command);
+                writer.write_all(reply.as_bytes()).unwrap();
+            }
+            commands
+        });
+        (port, handle)
+    }
+
+    fn database() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        database
+    }
+
+    #[test]
+    fn test_01_migrate_sends_restore_and_removes_the_key() {
+        let mut database = database();
+        let (port, target) = fake_target("+OK\r\n\n");
+        let result = Migrate.run(
+            vec_strings![
+                "127.0.0.1",
+                port,
+                "key",
+                "0",
+                "1000",
+                "REPLACE",
+                "AUTH",
+                "pass"
+            ],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "+OK\r\n");
+        assert!(database.lock().unwrap().get("key").is_none());
+        let commands = target.join().unwrap();
+        assert_eq!(commands[0], vec_strings!["AUTH", "pass"]);
+        assert_eq!(
+            commands[1],
+            vec_strings![
+                "RESTORE",
+                "key",
+                "0",
+                payload::serialize(&TypeSaved::String("value".into())),
+                "REPLACE"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_02_migrate_with_copy_and_target_errors_keeps_the_key() {
+        let mut database = database();
+        let (port, _target) = fake_target("+OK\r\n");
+        let result = Migrate.run(
+            vec_strings![
+                "127.0.0.1",
+                port,
+                "",
+                "0",
+                "1000",
+                "COPY",
+                "KEYS",
+                "key",
+                "missing"
+            ],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "+OK\r\n");
+        assert!(database.lock().unwrap().get("key").is_some());
+
+        let (port, _target) = fake_target("-BUSYKEY Target key name already exists.\r\n");
+        let result = Migrate.run(
+            vec_strings!["127.0.0.1", port, "key", "0", "1000"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR Target instance replied with error: BUSYKEY Target key name already exists."
+        );
+        assert!(database.lock().unwrap().get("key").is_some());
+    }
+
+    #[test]
+    fn test_03_migrate_without_keys_or_with_wrong_arguments() {
+        let mut database = database();
+        let result = Migrate.run(
+            vec_strings!["127.0.0.1", "1", "missing", "0", "1000"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "+NOKEY\r\n");
+        let result = Migrate.run(
+            vec_strings!["127.0.0.1", "1", "key", "0", "1000", "KEYS", "key"],
+            &mut database,
+        );
+        assert!(result.is_err());
+        let result = Migrate.run(
+            vec_strings!["127.0.0.1", "1", "key", "1", "1000"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR DB index is out of range"
+        );
+    }
+}
@@ -1,18 +1,79 @@
-use crate::native_types::ErrorStruct;
+use crate::{messages::redis_messages, native_types::ErrorStruct};
 
 pub mod _type;
 pub mod clean;
 pub mod copy;
 pub mod del;
+pub mod dump;
 pub mod exists;
 pub mod expire;
 pub mod expireat;
+pub mod expiretime;
 pub mod key_command;
+pub mod migrate;
+pub mod object;
 pub mod persist;
+pub mod pexpiretime;
+pub mod randomkey;
 pub mod rename;
+pub mod renamenx;
+pub mod restore;
 pub mod sort;
 pub mod touch;
 pub mod ttl;
+pub mod unlink;
+
+/// Conditions of EXPIRE and EXPIREAT to change the timeout of a key.
+#[derive(Default)]
+pub struct ExpireConditions {
+    nx: bool,
+    xx: bool,
+    gt: bool,
+    lt: bool,
+}
+
+impl ExpireConditions {
+    /// Parses the NX, XX, GT and LT options.
+    pub fn parse(options: &[String]) -> Result<Self, ErrorStruct> {
+        let mut conditions = ExpireConditions::default();
+        for option in options {
+            match option.to_uppercase().as_str() {
+                "NX" => conditions.nx = true,
+                "XX" => conditions.xx = true,
+                "GT" => conditions.gt = true,
+                "LT" => conditions.lt = true,
+                _ => {
+                    return Err(ErrorStruct::from(redis_messages::unsupported_option(
+                        option,
+                    )))
+                }
+            }
+        }
+        if conditions.nx && (conditions.xx || conditions.gt || conditions.lt) {
+            return Err(ErrorStruct::from(
+                redis_messages::nx_and_xx_gt_lt_incompatible(),
+            ));
+        }
+        if conditions.gt && conditions.lt {
+            return Err(ErrorStruct::from(redis_messages::gt_and_lt_incompatible()));
+        }
+        Ok(conditions)
+    }
+
+    /// Returns true if the timeout of a key can change from the current one to
+    /// the new one, both in milliseconds. Keys without timeout have an infinite one.
+    pub fn allow(&self, current: Option<u64>, new: i128) -> bool {
+        match current {
+            None => !self.xx && !self.gt,
+            Some(current) => {
+                !self.nx
+                    && (!self.gt || new > current as i128)
+                    && (!self.lt || new < current as i128)
+            }
+        }
+    }
+}
+
 pub fn pop_value(buffer: &mut Vec<String>, name: &str) -> Result<String, ErrorStruct> {
     if let Some(value) = buffer.pop() {
         Ok(value)
@@ -0,0 +1,204 @@
+use crate::database::{Database, TypeSaved};
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::{strings::text, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RArray, RBulkString, RInteger, RedisType},
+};
+use std::sync::{Arc, Mutex};
+
+/// Greatest length of a string stored together with its header by Redis.
+const EMBSTR_SIZE_LIMIT: usize = 44;
+/// Greatest number of entries of a listpack encoded set or sorted set.
+const LISTPACK_MAX_ENTRIES: usize = 128;
+/// Greatest length of each entry of a listpack encoded set or sorted set.
+const LISTPACK_MAX_VALUE: usize = 64;
+/// Greatest size in bytes of a listpack encoded list.
+const LIST_LISTPACK_MAX_SIZE: usize = 8192;
+/// Greatest number of members of an intset encoded set.
+const INTSET_MAX_ENTRIES: usize = 512;
+
+pub struct Object;
+
+impl Runnable<Arc<Mutex<Database>>> for Object {
+    /// Inspects the internals of the value stored at **key**, without registering
+    /// an access to it:
+    ///
+    /// * OBJECT ENCODING key: the internal representation Redis would use for the value.
+    /// * OBJECT IDLETIME key: the seconds since the key was last read or written.
+    /// * OBJECT FREQ key: the logarithmic access frequency counter of the key.
+    /// * OBJECT REFCOUNT key: the number of references of the value, which is never shared.
+    /// * OBJECT HELP: the subcommands.
+    ///
+    /// # Return value
+    /// * [String] _encoded_ in [RBulkString]: the encoding, or nil if **key** does not exist.
+    /// * [String] _encoded_ in [RInteger]: the idle time, frequency or references.
+    /// * [String] _encoded_ in [RArray]: the help.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The subcommand is unknown or has a wrong number of arguments.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let subcommand = buffer
+            .first()
+            .map(|subcommand| subcommand.to_lowercase())
+            .unwrap_or_default();
+        let key = match (subcommand.as_str(), buffer.len()) {
+            ("help", 1) => return Ok(help()),
+            ("encoding", 2) | ("idletime", 2) | ("freq", 2) | ("refcount", 2) => &buffer[1],
+            _ => {
+                return Err(ErrorStruct::from(redis_messages::unknown_subcommand(
+                    buffer.first().map(|name| name.as_str()).unwrap_or("object"),
+                )))
+            }
+        };
+        let now = database.clock().now();
+        let (info, value) = match database.object(key) {
+            Some(object) => object,
+            None => return Ok(RBulkString::encode(redis_messages::nil())),
+        };
+        Ok(match subcommand.as_str() {
+            "encoding" => RBulkString::encode(encoding(value).to_string()),
+            "idletime" => RInteger::encode(info.idle_time(now) as isize),
+            "freq" => RInteger::encode(info.frequency(now) as isize),
+            _ => RInteger::encode(1),
+        })
+    }
+}
+
+/// Returns the encoding Redis would use for the value, with the default limits.
+fn encoding(value: &TypeSaved) -> &'static str {
+    match value {
+        TypeSaved::String(bytes) => {
+            let string = text(bytes);
+            if bytes.len() <= 20 && is_integer(&string) {
+                "int"
+            } else if bytes.len() <= EMBSTR_SIZE_LIMIT {
+                "embstr"
+            } else {
+                "raw"
+            }
+        }
+        TypeSaved::List(list) => {
+            if list.iter().map(|element| element.len()).sum::<usize>() <= LIST_LISTPACK_MAX_SIZE {
+                "listpack"
+            } else {
+                "quicklist"
+            }
+        }
+        TypeSaved::Set(set) => {
+            if set.len() <= INTSET_MAX_ENTRIES && set.iter().all(|member| is_integer(member)) {
+                "intset"
+            } else if fits_listpack(set.len(), set.iter().map(String::as_str)) {
+                "listpack"
+            } else {
+                "hashtable"
+            }
+        }
+        TypeSaved::SortedSet(sorted_set) => {
+            if fits_listpack(
+                sorted_set.len(),
+                sorted_set.iter().map(|(member, _)| member),
+            ) {
+                "listpack"
+            } else {
+                "skiplist"
+            }
+        }
+        TypeSaved::Stream(_) => "stream",
+    }
+}
+
+/// Returns true if a set or sorted set is small enough to be listpack encoded.
+fn fits_listpack<'a>(len: usize, mut members: impl Iterator<Item = &'a str>) -> bool {
+    len <= LISTPACK_MAX_ENTRIES && members.all(|member| member.len() <= LISTPACK_MAX_VALUE)
+}
+
+/// Returns true if the string is the canonical representation of a 64 bit integer.
+fn is_integer(string: &str) -> bool {
+    string
+        .parse::<i64>()
+        .is_ok_and(|number| number.to_string() == string)
+}
+
+fn help() -> String {
+    RArray::encode(vec![
+        "OBJECT <subcommand> [<arg> [value] [opt] ...]. Subcommands are:".to_string(),
+        "ENCODING <key>".to_string(),
+        "    Return the kind of internal representation used in order to store the value"
+            .to_string(),
+        "    associated with a <key>.".to_string(),
+        "FREQ <key>".to_string(),
+        "    Return the access frequency index of the <key>. The returned integer is".to_string(),
+        "    proportional to the logarithm of the recent access frequency of the key.".to_string(),
+        "IDLETIME <key>".to_string(),
+        "    Return the idle time of the <key>, that is the approximated number of".to_string(),
+        "    seconds elapsed since the last access to the key.".to_string(),
+        "REFCOUNT <key>".to_string(),
+        "    Return the number of references of the value associated with the specified"
+            .to_string(),
+        "    <key>.".to_string(),
+        "HELP".to_string(),
+        "    Print this help.".to_string(),
+    ])
+}
+
+#[cfg(test)]
+mod test_object {
+    use crate::commands::create_notifier;
+
+    use super::*;
+    use crate::vec_strings;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_01_object_encoding_of_strings_and_sets() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let mut guard = database.lock().unwrap();
+        guard.insert("int".to_string(), TypeSaved::String("12345".into()));
+        guard.insert("embstr".to_string(), TypeSaved::String("value".into()));
+        guard.insert("raw".to_string(), TypeSaved::String(vec![b'a'; 45]));
+        let integers: HashSet<String> = (0..10).map(|n| n.to_string()).collect();
+        guard.insert("intset".to_string(), TypeSaved::Set(integers));
+        let members: HashSet<String> = (0..200).map(|n| format!("m{}", n)).collect();
+        guard.insert("hashtable".to_string(), TypeSaved::Set(members));
+        drop(guard);
+        for key in ["int", "embstr", "raw", "intset", "hashtable"] {
+            let result = Object.run(vec_strings!["ENCODING", key], &mut database);
+            assert_eq!(result.unwrap(), RBulkString::encode(key.to_string()));
+        }
+        let result = Object.run(vec_strings!["ENCODING", "missing"], &mut database);
+        assert_eq!(result.unwrap(), "$-1\r\n");
+    }
+
+    #[test]
+    fn test_02_object_idletime_freq_and_refcount() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        let result = Object.run(vec_strings!["IDLETIME", "key"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+        let result = Object.run(vec_strings!["FREQ", "key"], &mut database);
+        assert_eq!(result.unwrap(), ":5\r\n");
+        let result = Object.run(vec_strings!["REFCOUNT", "key"], &mut database);
+        assert_eq!(result.unwrap(), ":1\r\n");
+        let result = Object.run(vec_strings!["ENCODING"], &mut database);
+        assert!(result.is_err());
+    }
+}
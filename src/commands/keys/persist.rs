@@ -1,4 +1,3 @@
-use super::{no_more_values, pop_value};
 use crate::database::Database;
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
@@ -24,7 +23,6 @@ impl Runnable<Arc<Mutex<Database>>> for Persist {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with more than 1 element
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -37,8 +35,7 @@ impl Runnable<Arc<Mutex<Database>>> for Persist {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        let key = pop_value(&mut buffer, "Persist")?;
-        no_more_values(&buffer, "Persist")?;
+        let key = buffer.pop().unwrap();
 
         if database.persist(&key).is_some() {
             Ok(RInteger::encode(1))
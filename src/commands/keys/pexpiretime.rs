@@ -0,0 +1,47 @@
+use super::expiretime::expire_time_millis;
+use crate::database::Database;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable,
+    messages::redis_messages,
+    native_types::ErrorStruct,
+    native_types::{RInteger, RedisType},
+};
+
+use std::sync::{Arc, Mutex};
+pub struct PexpireTime;
+
+impl Runnable<Arc<Mutex<Database>>> for PexpireTime {
+    /// PEXPIRETIME has the same semantic as EXPIRETIME, but returns the absolute
+    /// Unix expiration timestamp in milliseconds instead of seconds.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): expiration Unix timestamp
+    /// in milliseconds, or a negative value in order to signal an error.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let key = buffer.pop().unwrap();
+
+        Ok(RInteger::encode(
+            match expire_time_millis(&mut database, &key) {
+                Some(millis) => millis,
+                None if database.contains_key(&key) => -1,
+                None => -2,
+            },
+        ))
+    }
+}
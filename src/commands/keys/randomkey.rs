@@ -0,0 +1,63 @@
+use crate::database::Database;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RBulkString, RedisType},
+};
+use std::sync::{Arc, Mutex};
+
+pub struct RandomKey;
+
+impl Runnable<Arc<Mutex<Database>>> for RandomKey {
+    /// Return a random key from the currently selected database.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the random key, or nil when the database is empty.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        _buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        // Expired keys are removed when checked, so the loop ends
+        while let Some(key) = database.random_key() {
+            if database.contains_key(&key) {
+                return Ok(RBulkString::encode(key));
+            }
+        }
+        Ok(RBulkString::encode(redis_messages::nil()))
+    }
+}
+
+#[cfg(test)]
+mod test_randomkey {
+    use crate::commands::create_notifier;
+
+    use super::*;
+    use crate::database::TypeSaved;
+
+    #[test]
+    fn test_01_randomkey_of_empty_and_non_empty_database() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let result = RandomKey.run(vec![], &mut database);
+        assert_eq!(result.unwrap(), "$-1\r\n");
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        let result = RandomKey.run(vec![], &mut database);
+        assert_eq!(result.unwrap(), RBulkString::encode("key".to_string()));
+    }
+}
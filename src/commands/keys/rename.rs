@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, check_not_empty, Runnable},
+    commands::Runnable,
     database::Database,
     messages::redis_messages,
     native_types::{ErrorStruct, RSimpleString, RedisType},
@@ -13,7 +13,7 @@ impl Runnable<Arc<Mutex<Database>>> for Rename {
     /// If newkey already exists it is overwritten, when this happens RENAME
     /// executes an implicit DEL operation, so if the deleted key contains a
     /// very big value it may cause high latency even if RENAME itself is
-    /// usually a constant-time operation.
+    /// usually a constant-time operation. The timeout of key is kept.
     ///
     /// # Return value
     /// * [String] _encoded_ in [RSimpleString]: "OK"
@@ -21,8 +21,6 @@ impl Runnable<Arc<Mutex<Database>>> for Rename {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with an amount
-    /// of elements different than 2.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -35,15 +33,9 @@ impl Runnable<Arc<Mutex<Database>>> for Rename {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_empty(&buffer, "rename")?;
         let new_key = buffer.pop().unwrap();
-        check_empty(&buffer, "rename")?;
         let old_key = buffer.pop().unwrap();
-        check_not_empty(&buffer)?;
-        if let Some(string_list) = database.get(&old_key) {
-            let value = string_list.clone();
-            database.remove(&old_key);
-            database.insert(new_key, value);
+        if database.rename(&old_key, &new_key) {
             Ok(RSimpleString::encode("OK".to_string()))
         } else {
             Err(ErrorStruct::new(
@@ -70,7 +62,7 @@ mod test_rename {
         database
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock_1 = vec_strings!["key", "new_key"];
         let result1 = Rename.run(buffer_mock_1, &mut database);
         assert_eq!(result1.unwrap(), "+OK\r\n".to_string());
@@ -86,7 +78,7 @@ mod test_rename {
         database
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock = vec_strings!["random_key", "new_key"];
         let error = Rename.run(buffer_mock, &mut database);
         assert_eq!(error.unwrap_err().print_it(), "ERR no such key".to_string());
@@ -99,7 +91,7 @@ mod test_rename {
         database
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock_1 = vec_strings!["key", "key"];
         let result1 = Rename.run(buffer_mock_1, &mut database);
         assert_eq!(result1.unwrap(), "+OK\r\n".to_string());
@@ -0,0 +1,93 @@
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable,
+    database::Database,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RInteger, RedisType},
+};
+use std::sync::{Arc, Mutex};
+pub struct RenameNx;
+
+impl Runnable<Arc<Mutex<Database>>> for RenameNx {
+    /// Renames key to newkey if newkey does not yet exist. It returns an error
+    /// when key does not exist. The timeout of key is kept.
+    ///
+    /// # Return value
+    /// * [String] _encoded_ in [RInteger]: 1 if key was renamed to newkey.
+    /// * [String] _encoded_ in [RInteger]: 0 if newkey already exists.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key does not exist.
+    ///   of elements different than 2.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let new_key = buffer.pop().unwrap();
+        let old_key = buffer.pop().unwrap();
+        if !database.contains_key(&old_key) {
+            return Err(ErrorStruct::from(redis_messages::no_such_key()));
+        }
+        if database.contains_key(&new_key) {
+            return Ok(RInteger::encode(0));
+        }
+        database.rename(&old_key, &new_key);
+        Ok(RInteger::encode(1))
+    }
+}
+
+#[cfg(test)]
+mod test_renamenx {
+    use crate::commands::create_notifier;
+
+    use super::*;
+    use crate::{database::TypeSaved, vec_strings};
+
+    #[test]
+    fn test_01_renamenx_to_non_existing_key_keeps_the_timeout() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        database.lock().unwrap().set_ttl("key", 100).unwrap();
+        let result = RenameNx.run(vec_strings!["key", "new_key"], &mut database);
+        assert_eq!(result.unwrap(), ":1\r\n");
+        let mut database = database.lock().unwrap();
+        assert!(database.get("key").is_none());
+        assert_eq!(
+            database.get("new_key"),
+            Some(&TypeSaved::String("value".into()))
+        );
+        assert!(database.ttl("new_key").is_some());
+    }
+
+    #[test]
+    fn test_02_renamenx_to_existing_key_and_from_non_existing_key() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        database
+            .lock()
+            .unwrap()
+            .insert("other".to_string(), TypeSaved::String("other".into()));
+        let result = RenameNx.run(vec_strings!["key", "other"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+        let result = RenameNx.run(vec_strings!["random_key", "new_key"], &mut database);
+        assert_eq!(result.unwrap_err().print_it(), "ERR no such key");
+    }
+}
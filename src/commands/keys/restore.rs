@@ -0,0 +1,217 @@
+use crate::database::Database;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::payload;
+use crate::{
+    commands::{get_as_integer, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+};
+use std::sync::{Arc, Mutex};
+
+pub struct Restore;
+
+/// Options of RESTORE.
+#[derive(Default)]
+struct RestoreOptions {
+    replace: bool,
+    absolute_ttl: bool,
+    idle_time: Option<u64>,
+    frequency: Option<u8>,
+}
+
+impl Runnable<Arc<Mutex<Database>>> for Restore {
+    /// Create a key associated with a value that is obtained by deserializing the
+    /// provided serialized value (obtained via DUMP).
+    ///
+    /// RESTORE key ttl serialized-value [REPLACE] [ABSTTL] [IDLETIME seconds] [FREQ frequency]
+    ///
+    /// * ttl: milliseconds until the key expires, or 0 to create it without timeout.
+    /// * REPLACE: overwrites key if it already exists.
+    /// * ABSTTL: ttl is an absolute Unix timestamp in milliseconds. Keys with a
+    ///   timestamp in the past are not created.
+    /// * IDLETIME: seconds since the key was last accessed, for OBJECT IDLETIME.
+    /// * FREQ: access frequency counter of the key, for OBJECT FREQ.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: "OK".
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key already exists and REPLACE is not given.
+    /// * The version or the checksum of the serialized value are wrong.
+    /// * The ttl, IDLETIME or FREQ values are not valid, or some option is unknown.
+    /// * Buffer [Vec]<[String]> is received with less than 3 elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        if buffer.len() < 3 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "restore",
+            )));
+        }
+        let key = &buffer[0];
+        let options = parse_options(&buffer[3..])?;
+        let ttl = get_as_integer(&buffer[1])?;
+        if ttl < 0 {
+            return Err(ErrorStruct::from(redis_messages::invalid_ttl()));
+        }
+        if !options.replace && database.contains_key(key) {
+            return Err(ErrorStruct::from(redis_messages::busy_key()));
+        }
+        let value = payload::deserialize(&buffer[2])?;
+
+        let now = database.clock().unix_millis();
+        let ttl = match (ttl as u64, options.absolute_ttl) {
+            (0, _) => None,
+            (timestamp, true) if timestamp <= now => {
+                database.remove(key);
+                return Ok(RSimpleString::encode(redis_messages::ok()));
+            }
+            (timestamp, true) => Some(timestamp - now),
+            (ttl, false) => Some(ttl),
+        };
+        database.insert(key.to_string(), value);
+        if let Some(ttl) = ttl {
+            database.set_ttl_millis(key, ttl)?;
+        }
+        let now = database.clock().now();
+        if let Some(info) = database.object_mut(key) {
+            if let Some(idle_time) = options.idle_time {
+                info.set_idle_time(now, idle_time);
+            }
+            if let Some(frequency) = options.frequency {
+                info.set_frequency(now, frequency);
+            }
+        }
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
+
+fn parse_options(buffer: &[String]) -> Result<RestoreOptions, ErrorStruct> {
+    let mut options = RestoreOptions::default();
+    let mut arguments = buffer.iter();
+    while let Some(option) = arguments.next() {
+        match option.to_uppercase().as_str() {
+            "REPLACE" => options.replace = true,
+            "ABSTTL" => options.absolute_ttl = true,
+            "IDLETIME" if options.frequency.is_none() => {
+                let seconds = get_as_integer(next(&mut arguments)?)?;
+                if seconds < 0 {
+                    return Err(ErrorStruct::from(redis_messages::invalid_idletime()));
+                }
+                options.idle_time = Some(seconds as u64);
+            }
+            "FREQ" if options.idle_time.is_none() => {
+                let frequency = get_as_integer(next(&mut arguments)?)?;
+                if !(0..=255).contains(&frequency) {
+                    return Err(ErrorStruct::from(redis_messages::invalid_freq()));
+                }
+                options.frequency = Some(frequency as u8);
+            }
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        }
+    }
+    Ok(options)
+}
+
+fn next<'a>(arguments: &mut impl Iterator<Item = &'a String>) -> Result<&'a String, ErrorStruct> {
+    arguments
+        .next()
+        .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))
+}
+
+#[cfg(test)]
+mod test_restore {
+    use crate::commands::create_notifier;
+
+    use super::*;
+    use crate::{database::TypeSaved, vec_strings};
+    use std::collections::VecDeque;
+
+    fn dumped() -> String {
+        payload::serialize(&TypeSaved::List(VecDeque::from(vec![
+            "a".to_string(),
+            "b".to_string(),
+        ])))
+    }
+
+    #[test]
+    fn test_01_restore_with_ttl_and_idletime() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let result = Restore.run(
+            vec_strings!["key", "100000", dumped(), "IDLETIME", "1000"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "+OK\r\n");
+        let mut database = database.lock().unwrap();
+        let now = database.clock().now();
+        assert_eq!(database.object("key").unwrap().0.idle_time(now), 1000);
+        assert!(database.ttl("key").unwrap() > 90);
+        assert_eq!(
+            database.get("key"),
+            Some(&TypeSaved::List(VecDeque::from(vec![
+                "a".to_string(),
+                "b".to_string()
+            ])))
+        );
+    }
+
+    #[test]
+    fn test_02_restore_existing_key_requires_replace() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        let result = Restore.run(vec_strings!["key", "0", dumped()], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "BUSYKEY Target key name already exists."
+        );
+        let result = Restore.run(
+            vec_strings!["key", "0", dumped(), "REPLACE", "FREQ", "100"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "+OK\r\n");
+        let mut database = database.lock().unwrap();
+        let now = database.clock().now();
+        assert_eq!(database.object("key").unwrap().0.frequency(now), 100);
+        assert_eq!(database.ttl("key"), None);
+    }
+
+    #[test]
+    fn test_03_restore_errors_and_past_absolute_ttl() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let result = Restore.run(vec_strings!["key", "-1", dumped()], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR Invalid TTL value, must be >= 0"
+        );
+        let result = Restore.run(vec_strings!["key", "0", "0000"], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR DUMP payload version or checksum are wrong"
+        );
+        let result = Restore.run(
+            vec_strings!["key", "0", dumped(), "IDLETIME", "1", "FREQ", "1"],
+            &mut database,
+        );
+        assert!(result.is_err());
+        let result = Restore.run(vec_strings!["key", "1", dumped(), "ABSTTL"], &mut database);
+        assert_eq!(result.unwrap(), "+OK\r\n");
+        assert!(database.lock().unwrap().get("key").is_none());
+    }
+}
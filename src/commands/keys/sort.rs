@@ -4,12 +4,14 @@ use super::{no_more_values, pop_value};
 use crate::database::Database;
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::Runnable,
+    commands::{strings::text, Runnable},
     database::TypeSaved,
+    err_wrongtype,
     messages::redis_messages,
     native_types::ErrorStruct,
     native_types::RArray,
     native_types::{RBulkString, RedisType},
+    sorted_set::SortedSet,
 };
 
 use std::sync::{Arc, Mutex};
@@ -45,9 +47,11 @@ impl Runnable<Arc<Mutex<Database>>> for Sort {
 
         if let Some(type_saved) = database.get(&key) {
             let sorted: Vec<String> = match type_saved {
-                TypeSaved::String(string) => vec![String::from(string)],
+                TypeSaved::String(string) => vec![text(string)],
                 TypeSaved::List(list) => sort_list(list),
                 TypeSaved::Set(set) => sort_set(set),
+                TypeSaved::SortedSet(sorted_set) => sort_sorted_set(sorted_set),
+                TypeSaved::Stream(_) => return err_wrongtype!(),
             };
             Ok(RArray::encode(sorted))
         } else {
@@ -70,6 +74,16 @@ fn sort_set(set: &HashSet<String>) -> Vec<String> {
     sorted
 }
 
+// Sorts the members of a SortedSet, ignoring their scores, to use Rust sort() function.
+fn sort_sorted_set(sorted_set: &SortedSet) -> Vec<String> {
+    let mut sorted = sorted_set
+        .iter()
+        .map(|(member, _)| member.to_string())
+        .collect::<Vec<String>>();
+    sorted.sort();
+    sorted
+}
+
 #[cfg(test)]
 pub mod test_llen {
     use crate::commands::create_notifier;
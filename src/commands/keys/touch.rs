@@ -52,9 +52,9 @@ pub mod test_touch {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut db = Database::new(notifier);
 
-        db.insert("key1".to_string(), TypeSaved::String("a".to_string()));
-        db.insert("key2".to_string(), TypeSaved::String("b".to_string()));
-        db.insert("key3".to_string(), TypeSaved::String("c".to_string()));
+        db.insert("key1".to_string(), TypeSaved::String("a".into()));
+        db.insert("key2".to_string(), TypeSaved::String("b".into()));
+        db.insert("key3".to_string(), TypeSaved::String("c".into()));
         let mut c_db = Arc::new(Mutex::new(db));
 
         let sum = Touch.run(
@@ -70,9 +70,9 @@ pub mod test_touch {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut db = Database::new(notifier);
 
-        db.insert("key1".to_string(), TypeSaved::String("a".to_string()));
-        db.insert("key2".to_string(), TypeSaved::String("b".to_string()));
-        db.insert("key3".to_string(), TypeSaved::String("c".to_string()));
+        db.insert("key1".to_string(), TypeSaved::String("a".into()));
+        db.insert("key2".to_string(), TypeSaved::String("b".into()));
+        db.insert("key3".to_string(), TypeSaved::String("c".into()));
 
         let mut c_db = Arc::new(Mutex::new(db));
 
@@ -86,9 +86,9 @@ pub mod test_touch {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut db = Database::new(notifier);
 
-        db.insert("key1".to_string(), TypeSaved::String("a".to_string()));
-        db.insert("key2".to_string(), TypeSaved::String("b".to_string()));
-        db.insert("key3".to_string(), TypeSaved::String("c".to_string()));
+        db.insert("key1".to_string(), TypeSaved::String("a".into()));
+        db.insert("key2".to_string(), TypeSaved::String("b".into()));
+        db.insert("key3".to_string(), TypeSaved::String("c".into()));
 
         let mut c_db = Arc::new(Mutex::new(db));
         let sum = Touch.run(
@@ -109,9 +109,9 @@ pub mod test_touch {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut db = Database::new(notifier);
 
-        db.insert("key1".to_string(), TypeSaved::String("a".to_string()));
-        db.insert("key2".to_string(), TypeSaved::String("b".to_string()));
-        db.insert("key3".to_string(), TypeSaved::String("c".to_string()));
+        db.insert("key1".to_string(), TypeSaved::String("a".into()));
+        db.insert("key2".to_string(), TypeSaved::String("b".into()));
+        db.insert("key3".to_string(), TypeSaved::String("c".into()));
         let mut c_db = Arc::new(Mutex::new(db));
         let sum = Touch.run(vec![], &mut c_db);
 
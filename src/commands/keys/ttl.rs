@@ -1,4 +1,3 @@
-use super::{no_more_values, pop_value};
 use crate::database::Database;
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
@@ -23,7 +22,6 @@ impl Runnable<Arc<Mutex<Database>>> for Ttl {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or received with more than 1 element.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -36,8 +34,7 @@ impl Runnable<Arc<Mutex<Database>>> for Ttl {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        let key = pop_value(&mut buffer, "Ttl")?;
-        no_more_values(&buffer, "Ttl")?;
+        let key = buffer.pop().unwrap();
 
         if database.contains_key(&key) {
             if let Some(ttl) = database.ttl(&key) {
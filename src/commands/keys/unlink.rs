@@ -0,0 +1,72 @@
+use crate::database::Database;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable,
+    messages::redis_messages,
+    native_types::ErrorStruct,
+    native_types::{RInteger, RedisType},
+};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub struct Unlink;
+
+impl Runnable<Arc<Mutex<Database>>> for Unlink {
+    /// Removes the specified keys, like DEL, but the memory of their values is
+    /// reclaimed in another thread, so the command returns without waiting for
+    /// big values to be dropped. A key is ignored if it does not exist.
+    ///
+    /// # Return value
+    /// * [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): The number of keys that were unlinked.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let unlinked: Vec<_> = buffer
+            .iter()
+            .filter_map(|key| database.remove(key))
+            .collect();
+        let removed = unlinked.len();
+        if removed > 0 {
+            thread::spawn(move || drop(unlinked));
+        }
+        Ok(RInteger::encode(removed as isize))
+    }
+}
+
+#[cfg(test)]
+mod test_unlink {
+    use crate::commands::create_notifier;
+
+    use super::*;
+    use crate::{database::TypeSaved, vec_strings};
+
+    #[test]
+    fn test_01_unlink_existing_and_non_existing_keys() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key1".to_string(), TypeSaved::String("value".into()));
+        database
+            .lock()
+            .unwrap()
+            .insert("key2".to_string(), TypeSaved::List(Default::default()));
+        let result = Unlink.run(vec_strings!["key1", "key2", "key3"], &mut database);
+        assert_eq!(RInteger::encode(2), result.unwrap());
+        assert_eq!(database.lock().unwrap().size(), 0);
+    }
+}
@@ -1,14 +1,13 @@
 use crate::messages::redis_messages;
 use crate::native_types::error_severity::ErrorSeverity;
-use crate::{
-    commands::lists::{check_empty, check_not_empty},
-    database::{Database, TypeSaved},
-    native_types::bulk_string::RBulkString,
-};
 use crate::{
     commands::Runnable,
     native_types::{error::ErrorStruct, redis_type::RedisType},
 };
+use crate::{
+    database::{Database, TypeSaved},
+    native_types::bulk_string::RBulkString,
+};
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
@@ -28,7 +27,6 @@ impl Runnable<Arc<Mutex<Database>>> for LIndex {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a list.
-    /// * Buffer [Vec]<[String]> is received empty, or received with less or more than 2 elements.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -41,11 +39,8 @@ impl Runnable<Arc<Mutex<Database>>> for LIndex {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_empty(&buffer, "lindex")?;
         let key = buffer.remove(0);
-        check_empty(&buffer, "lindex")?;
         let index = parse_index(&mut buffer)?;
-        check_not_empty(&buffer)?;
 
         if let Some(typesaved) = database.get(&key) {
             match typesaved {
@@ -1,6 +1,5 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::lists::{check_empty, check_not_empty},
     commands::Runnable,
     database::{Database, TypeSaved},
     messages::redis_messages,
@@ -20,7 +19,6 @@ impl Runnable<Arc<Mutex<Database>>> for Llen {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a list.
-    /// * Buffer [Vec]<[String]> is received empty, or received with 2 or more elements.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -33,10 +31,8 @@ impl Runnable<Arc<Mutex<Database>>> for Llen {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_empty(&buffer, "llen")?;
         let key = buffer.remove(0);
-        check_not_empty(&buffer)?;
-        if let Some(typesaved) = database.get_mut(&key) {
+        if let Some(typesaved) = database.get(&key) {
             match typesaved {
                 TypeSaved::List(list_of_values) => {
                     Ok(RInteger::encode(list_of_values.len() as isize))
@@ -106,7 +102,7 @@ pub mod test_llen {
         // redis> SET mykey 10
         data.lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
 
         let buffer = vec_strings!["key"];
         let error = Llen.run(buffer, &mut data);
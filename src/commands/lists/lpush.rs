@@ -26,7 +26,6 @@ impl Runnable<Arc<Mutex<Database>>> for LPush {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a list.
-    /// * Buffer [Vec]<[String]> is received empty, or received less than 2 elements.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
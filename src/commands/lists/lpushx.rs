@@ -21,7 +21,6 @@ impl Runnable<Arc<Mutex<Database>>> for LPushx {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a list.
-    /// * Buffer [Vec]<[String]> is received empty, or received with more than 2 elements.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
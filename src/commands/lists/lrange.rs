@@ -1,4 +1,3 @@
-use crate::commands::lists::{check_empty, check_not_empty};
 use crate::commands::{get_as_integer, Runnable};
 use crate::database::Database;
 use crate::database::TypeSaved;
@@ -32,7 +31,6 @@ impl Runnable<Arc<Mutex<Database>>> for Lrange {
     ///
     /// * The value stored at **key** is not a list.
     /// * **Key** does not exist.
-    /// * Buffer [Vec]<[String]> is received empty, or received with an amount of elements different than 3.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -45,9 +43,8 @@ impl Runnable<Arc<Mutex<Database>>> for Lrange {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_empty(&buffer, "lrange")?;
         let key = buffer.remove(0);
-        if let Some(typesaved) = database.get_mut(&key) {
+        if let Some(typesaved) = database.get(&key) {
             match typesaved {
                 TypeSaved::List(values_list) => find_elements_in_range(values_list, buffer),
                 _ => Err(ErrorStruct::new(
@@ -66,14 +63,11 @@ impl Runnable<Arc<Mutex<Database>>> for Lrange {
 // indexes are not valid, returns "(empty list or set)", any other case, returns
 // a decoded RArray containing all elements at interval [start, stop].
 pub fn find_elements_in_range(
-    values_list: &mut VecDeque<String>,
+    values_list: &VecDeque<String>,
     mut buffer: Vec<String>,
 ) -> Result<String, ErrorStruct> {
-    check_empty(&buffer, "lrange")?;
     let mut stop = get_as_integer(&buffer.pop().unwrap()).unwrap();
-    check_empty(&buffer, "lrange")?;
     let mut start = get_as_integer(&buffer.pop().unwrap()).unwrap();
-    check_not_empty(&buffer)?;
     let len = values_list.len() as isize;
     if start < 0 {
         start += len; // start = 2
@@ -99,7 +93,7 @@ pub fn find_elements_in_range(
 pub fn get_list_elements_in_range(
     start: isize,
     stop: isize,
-    values_list: &mut VecDeque<String>,
+    values_list: &VecDeque<String>,
 ) -> Result<String, ErrorStruct> {
     let mut iter = values_list.iter();
     let mut iter_elem = None;
@@ -188,7 +182,7 @@ pub mod test_lrange {
         // redis> SET mykey 10
         data.lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
 
         let buffer = vec_strings!["key"];
         let error = Lrange.run(buffer, &mut data);
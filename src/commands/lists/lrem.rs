@@ -1,8 +1,7 @@
 use crate::messages::redis_messages;
 use crate::native_types::error_severity::ErrorSeverity;
-use crate::{
-    commands::lists::{check_empty, check_not_empty},
-    native_types::{error::ErrorStruct, redis_type::RedisType, simple_string::RSimpleString},
+use crate::native_types::{
+    error::ErrorStruct, redis_type::RedisType, simple_string::RSimpleString,
 };
 use crate::{
     commands::{get_as_integer, Runnable},
@@ -32,7 +31,6 @@ impl Runnable<Arc<Mutex<Database>>> for Lrem {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a list.
-    /// * Buffer [Vec]<[String]> is received empty, or received with an amount of elements different than 3.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -45,13 +43,9 @@ impl Runnable<Arc<Mutex<Database>>> for Lrem {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_empty(&buffer, "lrem")?;
         let key = buffer.remove(0);
-        check_empty(&buffer, "lrem")?;
         let value = buffer.pop().unwrap();
-        check_empty(&buffer, "lrem")?;
         let count = get_as_integer(&buffer.pop().unwrap()).unwrap();
-        check_not_empty(&buffer)?;
         if let Some(typesaved) = database.get_mut(&key) {
             match typesaved {
                 TypeSaved::List(values_list) => remove_value(count, value, values_list),
@@ -1,5 +1,4 @@
 use crate::commands::get_as_integer;
-use crate::commands::lists::{check_empty, check_not_empty};
 use crate::commands::Runnable;
 use crate::database::Database;
 use crate::database::TypeSaved;
@@ -26,7 +25,6 @@ impl Runnable<Arc<Mutex<Database>>> for Lset {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a list.
-    /// * Buffer [Vec]<[String]> is received empty, or received with an amount of elements different than 3.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -39,13 +37,9 @@ impl Runnable<Arc<Mutex<Database>>> for Lset {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_empty(&buffer, "lset")?;
         let key = buffer.remove(0);
-        check_empty(&buffer, "lset")?;
         let index = get_as_integer(&buffer.remove(0)).unwrap();
-        check_empty(&buffer, "lset")?;
         let replacement = buffer.remove(0);
-        check_not_empty(&buffer)?;
 
         if let Some(typesaved) = database.get_mut(&key) {
             match typesaved {
@@ -223,7 +217,7 @@ pub mod test_lset {
         // redis> SET mykey 10
         data.lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
 
         let buffer = vec_strings!["key", "1", "new_value"];
         let error = Lset.run(buffer, &mut data);
@@ -325,76 +319,4 @@ pub mod test_lset {
             panic!();
         };
     }
-
-    #[test]
-    fn test_11_lset_with_zero_arguments() {
-        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
-        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
-
-        let new_list = VecDeque::new();
-
-        let key = "key".to_string();
-        data.lock().unwrap().insert(key, TypeSaved::List(new_list));
-
-        let buffer = vec_strings![];
-        let error = Lset.run(buffer, &mut data);
-        assert_eq!(
-            error.unwrap_err().print_it(),
-            "ERR wrong number of arguments for 'lset' command".to_string()
-        );
-    }
-
-    #[test]
-    fn test_12_lset_with_wrong_number_of_arguments() {
-        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
-        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
-
-        let new_list = VecDeque::new();
-
-        let key = "key".to_string();
-        data.lock().unwrap().insert(key, TypeSaved::List(new_list));
-
-        let buffer = vec_strings!["key", "2", "new_value_1", "new_value_2"];
-        let error = Lset.run(buffer, &mut data);
-        assert_eq!(
-            error.unwrap_err().print_it(),
-            "ERR wrong number of arguments".to_string()
-        );
-    }
-
-    #[test]
-    fn test_13_lset_with_wrong_number_of_arguments() {
-        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
-        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
-
-        let new_list = VecDeque::new();
-
-        let key = "key".to_string();
-        data.lock().unwrap().insert(key, TypeSaved::List(new_list));
-
-        let buffer = vec_strings!["key", "2"];
-        let error = Lset.run(buffer, &mut data);
-        assert_eq!(
-            error.unwrap_err().print_it(),
-            "ERR wrong number of arguments for 'lset' command".to_string()
-        );
-    }
-
-    #[test]
-    fn test_14_lset_with_wrong_number_of_arguments() {
-        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
-        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
-
-        let new_list = VecDeque::new();
-
-        let key = "key".to_string();
-        data.lock().unwrap().insert(key, TypeSaved::List(new_list));
-
-        let buffer = vec_strings!["key"];
-        let error = Lset.run(buffer, &mut data);
-        assert_eq!(
-            error.unwrap_err().print_it(),
-            "ERR wrong number of arguments for 'lset' command".to_string()
-        );
-    }
 }
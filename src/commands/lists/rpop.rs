@@ -24,7 +24,7 @@ impl Runnable<Arc<Mutex<Database>>> for RPop {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a list.
-    /// * Buffer [Vec]<[String]> is received empty, or received with more than 2 elements.
+    /// * Buffer [Vec]<[String]> is received with more than 2 elements.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -118,18 +118,4 @@ pub mod test_rpop {
         assert_eq!(encode.unwrap(), "$-1\r\n".to_string());
         assert_eq!(data.lock().unwrap().get("key"), None);
     }
-
-    #[test]
-    fn test_04_lpop_with_no_key() {
-        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
-        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
-        let buffer = vec_strings![];
-        match RPop.run(buffer, &mut data) {
-            Ok(_encode) => {}
-            Err(error) => assert_eq!(
-                error.print_it(),
-                "ERR wrong number of arguments for 'lpop or rpop' command".to_string()
-            ),
-        }
-    }
 }
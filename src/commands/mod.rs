@@ -3,6 +3,7 @@ use std::sync::{
     mpsc::{self, Receiver},
     Arc,
 };
+use std::time::Duration;
 
 use crate::{
     communication::log_messages::LogMessage,
@@ -10,12 +11,24 @@ use crate::{
     native_types::ErrorStruct,
     tcp_protocol::{notifier::Notifier, RawCommand},
 };
+#[cfg(test)]
+use crate::{
+    redis_config::RedisConfig,
+    tcp_protocol::{client_list::ClientList, server_redis_attributes::ServerRedisAttributes},
+    time_expiration::clock::SystemClock,
+};
+#[cfg(test)]
+use std::sync::Mutex;
 
+pub mod connection;
+pub mod geo;
+pub mod hyperloglog;
 pub mod keys;
 pub mod lists;
 pub mod pubsub;
 pub mod server;
 pub mod sets;
+pub mod streams;
 pub mod strings;
 
 #[macro_export]
@@ -65,12 +78,32 @@ pub trait Runnable<T> {
     /// assert_eq!(expected_result, result_received.unwrap());
     /// ```
     fn run(&self, buffer: Vec<String>, item: &mut T) -> Result<String, ErrorStruct>;
+
+    /// Blocking commands, such as XREAD with BLOCK, return how long the client
+    /// waits for data before [run](Runnable::run) is called anyway. Arguments
+    /// that depend on the data at the time of the call, such as the `$` id,
+    /// are replaced in the buffer.
+    ///
+    /// # Return value
+    /// * [None]: the command can run now. This is the default.
+    /// * [Some]<[Duration]>: the command must wait, forever if it is zero.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The arguments of the command are not valid.
+    fn blocking_time(
+        &self,
+        _buffer: &mut Vec<String>,
+        _item: &mut T,
+    ) -> Result<Option<Duration>, ErrorStruct> {
+        Ok(None)
+    }
 }
 
 // Fun aux
 
 pub fn get_as_integer(value: &str) -> Result<isize, ErrorStruct> {
-    // value es mut porque TypeSaved::String() devuelve &mut String
     match value.parse::<isize>() {
         Ok(value_int) => Ok(value_int), // if value is parsable as pointer size integer
         Err(_) => Err(ErrorStruct::new(
@@ -80,6 +113,13 @@ pub fn get_as_integer(value: &str) -> Result<isize, ErrorStruct> {
     }
 }
 
+pub fn get_as_float(value: &str) -> Result<f64, ErrorStruct> {
+    match value.parse::<f64>() {
+        Ok(value_float) if !value_float.is_nan() => Ok(value_float),
+        _ => Err(ErrorStruct::from(redis_messages::not_valid_float())),
+    }
+}
+
 // Check number of arguments
 
 fn check_empty(buffer: &[String], name: &str) -> Result<(), ErrorStruct> {
@@ -125,20 +165,39 @@ pub fn create_notifier() -> (
     )
 }
 
-fn check_error_cases_without_elements(
-    buffer: &[String],
-    name_command: &str,
-    elements: usize,
-) -> Result<(), ErrorStruct> {
-    check_empty(buffer, name_command)?;
+/// Path of a file of the server created by [create_server], in the temporary directory.
+#[cfg(test)]
+fn server_file(name: &str, extension: &str) -> String {
+    let file = std::env::temp_dir().join(format!("server_{}.{}", name, extension));
+    file.to_string_lossy().into_owned()
+}
 
-    if buffer.len() != elements {
-        let error_message = redis_messages::arguments_invalid_to(name_command);
-        return Err(ErrorStruct::new(
-            error_message.get_prefix(),
-            error_message.get_message(),
-        ));
-    }
+/// Creates the [ServerRedisAttributes] of a server which is not listening,
+/// with a new [RedisConfig] whose files are named after `name` and an empty
+/// [ClientList], for the tests of the commands run by the server.
+#[cfg(test)]
+pub fn create_server(name: &str) -> ServerRedisAttributes {
+    let config = RedisConfig::new(
+        "127.0.0.1".to_string(),
+        "6379".to_string(),
+        server_file(name, "txt"),
+        server_file(name, "rdb"),
+        0,
+    )
+    .unwrap();
+    let (log_sender, _) = mpsc::channel();
+    ServerRedisAttributes::new(
+        Arc::new(Mutex::new(config)),
+        Arc::new(AtomicBool::new(false)),
+        Arc::new(Mutex::new(ClientList::new(log_sender))),
+        Arc::new(SystemClock),
+    )
+}
 
-    Ok(())
+/// Removes the files of a server created by [create_server].
+#[cfg(test)]
+pub fn remove_server_files(name: &str) {
+    for extension in ["txt", "rdb"] {
+        let _ = std::fs::remove_file(server_file(name, extension));
+    }
 }
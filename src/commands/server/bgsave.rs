@@ -0,0 +1,165 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    commands::Runnable,
+    database::Database,
+    messages::redis_messages,
+    native_types::{error_severity::ErrorSeverity, ErrorStruct, RSimpleString, RedisType},
+};
+
+pub struct BgSave;
+
+impl Runnable<Arc<Mutex<Database>>> for BgSave {
+    /// Saves the [Database] to the dump file in the background: a snapshot is
+    /// taken and written in a separate thread, while the clients keep being
+    /// served.
+    ///
+    /// BGSAVE [SCHEDULE]
+    ///
+    /// * SCHEDULE: if another background save is in progress, starts a new one
+    ///   when it finishes instead of failing.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: "Background saving started", or
+    /// "Background saving scheduled" with SCHEDULE.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Another background save is in progress, without SCHEDULE.
+    /// * The option is not SCHEDULE.
+    /// * There is no dump file available.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let schedule = match buffer.first() {
+            None => false,
+            Some(option) if buffer.len() == 1 && option.eq_ignore_ascii_case("schedule") => true,
+            Some(_) => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        };
+        if schedule && database.background_save_in_progress() {
+            database.schedule_background_save();
+            return Ok(RSimpleString::encode(
+                "Background saving scheduled".to_string(),
+            ));
+        }
+        database.background_save()?;
+        Ok(RSimpleString::encode(
+            "Background saving started".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test_bgsave {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::commands::server::lastsave::LastSave;
+    use crate::database::TypeSaved;
+    use crate::dump;
+    use crate::redis_config::{DumpFormat, RedisConfig};
+    use crate::tcp_protocol::notifier::Notifier;
+    use crate::vec_strings;
+    use std::fs;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    fn database(filename: &str, notifier: Notifier) -> Arc<Mutex<Database>> {
+        let config = RedisConfig::new(
+            String::new(),
+            String::new(),
+            String::from("log.txt"),
+            String::from(filename),
+            0,
+        )
+        .unwrap();
+        let mut database = Database::new(notifier);
+        database.set_redis_config(Arc::new(Mutex::new(config)));
+        Arc::new(Mutex::new(database))
+    }
+
+    fn wait_background_save(database: &Arc<Mutex<Database>>) {
+        while database.lock().unwrap().background_save_in_progress() {
+            sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_01_bgsave_writes_the_snapshot_and_resets_the_changes() {
+        let filename = "bgsave_01.rdb";
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = database(filename, notifier);
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        assert_eq!(database.lock().unwrap().dirty(), 1);
+
+        let result = BgSave.run(vec![], &mut database);
+        assert_eq!(result.unwrap(), "+Background saving started\r\n");
+        wait_background_save(&database);
+
+        assert_eq!(database.lock().unwrap().dirty(), 0);
+        let entries = [("key".to_string(), None, TypeSaved::String("value".into()))];
+        assert_eq!(
+            fs::read(filename).unwrap(),
+            dump::encode(&entries, DumpFormat::Legacy, 0)
+        );
+        let _ = fs::remove_file(filename);
+    }
+
+    #[test]
+    fn test_02_successive_saves_replace_the_file() {
+        let filename = "bgsave_02.rdb";
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = database(filename, notifier);
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("value".into()));
+        BgSave.run(vec![], &mut database).unwrap();
+        wait_background_save(&database);
+        database.lock().unwrap().remove("key");
+        BgSave.run(vec![], &mut database).unwrap();
+        wait_background_save(&database);
+
+        assert_eq!(
+            fs::read(filename).unwrap(),
+            dump::encode(&[], DumpFormat::Legacy, 0)
+        );
+        let _ = fs::remove_file(filename);
+    }
+
+    #[test]
+    fn test_03_lastsave_returns_the_time_of_the_last_save() {
+        let filename = "bgsave_03.rdb";
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = database(filename, notifier);
+        let started = database.lock().unwrap().last_save();
+        let result = LastSave.run(vec![], &mut database);
+        assert_eq!(result.unwrap(), format!(":{}\r\n", started));
+
+        BgSave.run(vec![], &mut database).unwrap();
+        wait_background_save(&database);
+        assert!(database.lock().unwrap().last_save() >= started);
+        let _ = fs::remove_file(filename);
+    }
+
+    #[test]
+    fn test_04_bgsave_with_unknown_option() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = database("bgsave_04.rdb", notifier);
+        let result = BgSave.run(vec_strings!["now"], &mut database);
+        assert_eq!(result.unwrap_err().print_it(), "ERR syntax error");
+        let _ = fs::remove_file("bgsave_04.rdb");
+    }
+}
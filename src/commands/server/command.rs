@@ -0,0 +1,130 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{
+        check_not_empty,
+        server::{
+            command_docs::CommandDocs, command_getkeys::CommandGetKeys, command_info::CommandInfo,
+        },
+        Runnable,
+    },
+    messages::redis_messages,
+    native_types::{ErrorStruct, RInteger, RedisType},
+};
+
+pub struct Command;
+
+impl Runnable<ServerRedisAttributes> for Command {
+    /// Returns the metadata of the commands of the server, as registered
+    /// in the [CommandTable](crate::tcp_protocol::command_table::CommandTable).
+    /// Without subcommand, returns the information of every command.
+    ///
+    /// # Sub Commands
+    ///
+    /// * COUNT: Returns the number of commands.
+    /// * INFO \[command-name ...\]: Returns the arity, flags, key positions and ACL categories of the commands.
+    /// * DOCS \[command-name ...\]: Returns the summary and group of the commands.
+    /// * GETKEYS command \[arg ...\]: Returns the keys of a full command.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * User does not give a supported subcommand.
+    /// * The subcommand fails.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        if buffer.is_empty() {
+            return CommandInfo.run(buffer, server);
+        }
+
+        let mut subcommand = buffer.remove(0);
+        subcommand.make_ascii_lowercase();
+        match subcommand.as_str() {
+            "count" => {
+                check_not_empty(&buffer)?;
+                Ok(RInteger::encode(server.get_command_table().len() as isize))
+            }
+            "info" => CommandInfo.run(buffer, server),
+            "docs" => CommandDocs.run(buffer, server),
+            "getkeys" => CommandGetKeys.run(buffer, server),
+            _ => Err(ErrorStruct::from(redis_messages::unknown_subcommand(
+                &("command|".to_owned() + &subcommand),
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_command {
+    use super::*;
+    use crate::{commands::create_server, vec_strings};
+
+    #[test]
+    fn test_01_count() {
+        let mut server = create_server("command");
+        let count = server.get_command_table().len();
+        let result = Command.run(vec_strings!["COUNT"], &mut server).unwrap();
+        assert_eq!(result, format!(":{}\r\n", count));
+    }
+
+    #[test]
+    fn test_02_without_subcommand_returns_every_command() {
+        let mut server = create_server("command");
+        let count = server.get_command_table().len();
+        let result = Command.run(vec![], &mut server).unwrap();
+        assert!(result.starts_with(&format!("*{}\r\n*10\r\n$6\r\nappend\r\n", count)));
+    }
+
+    #[test]
+    fn test_03_info_of_a_command() {
+        let mut server = create_server("command");
+        let result = Command
+            .run(vec_strings!["info", "get", "unknown"], &mut server)
+            .unwrap();
+        assert_eq!(
+            result,
+            "*2\r\n*10\r\n$3\r\nget\r\n:2\r\n*2\r\n+readonly\r\n+fast\r\n:1\r\n:1\r\n:1\r\n*3\r\n+@read\r\n+@string\r\n+@fast\r\n*0\r\n*0\r\n*0\r\n$-1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_04_docs_of_a_command() {
+        let mut server = create_server("command");
+        let result = Command
+            .run(vec_strings!["docs", "strlen"], &mut server)
+            .unwrap();
+        assert_eq!(
+            result,
+            "*2\r\n$6\r\nstrlen\r\n*4\r\n$7\r\nsummary\r\n$37\r\nReturns the length of a string value.\r\n$5\r\ngroup\r\n$6\r\nstring\r\n"
+        );
+    }
+
+    #[test]
+    fn test_05_getkeys() {
+        let mut server = create_server("command");
+        let result = Command
+            .run(
+                vec_strings!["getkeys", "mset", "a", "1", "b", "2"],
+                &mut server,
+            )
+            .unwrap();
+        assert_eq!(result, "*2\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+        let error = Command
+            .run(vec_strings!["getkeys", "publish", "a", "b"], &mut server)
+            .unwrap_err();
+        assert_eq!(error.print_it(), "ERR The command has no key arguments");
+    }
+
+    #[test]
+    fn test_06_unknown_subcommand() {
+        let mut server = create_server("command");
+        let error = Command.run(vec_strings!["dance"], &mut server).unwrap_err();
+        assert_eq!(
+            error.print_it(),
+            "ERR Unknown subcommand or wrong number of arguments for 'command|dance'"
+        );
+    }
+}
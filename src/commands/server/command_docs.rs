@@ -0,0 +1,43 @@
+use crate::tcp_protocol::command_table::CommandSpec;
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::Runnable,
+    native_types::{ErrorStruct, RArray, RBulkString, RedisType},
+};
+
+pub struct CommandDocs;
+
+impl Runnable<ServerRedisAttributes> for CommandDocs {
+    /// Returns the documentation of the given commands, or of every command if none is given.
+    ///
+    /// # Return value
+    /// [String] _encoded_ as an array alternating the name of each command and
+    /// an array with its *summary* and *group*. Unknown commands are skipped.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        let table = server.get_command_table();
+        let specs: Vec<&CommandSpec> = if buffer.is_empty() {
+            table.all()
+        } else {
+            buffer
+                .iter()
+                .filter_map(|name| table.get(&name.to_lowercase()))
+                .collect()
+        };
+
+        let mut encoded = format!("*{}\r\n", specs.len() * 2);
+        for spec in specs {
+            encoded.push_str(&RBulkString::encode(spec.name.to_string()));
+            encoded.push_str(&RArray::encode(vec![
+                "summary".to_string(),
+                spec.summary.to_string(),
+                "group".to_string(),
+                spec.group.to_string(),
+            ]));
+        }
+        Ok(encoded)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{check_empty, Runnable},
+    native_types::{ErrorStruct, RArray, RedisType},
+};
+
+pub struct CommandGetKeys;
+
+impl Runnable<ServerRedisAttributes> for CommandGetKeys {
+    /// Returns the keys of a full command, with its name and arguments.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray]: the keys of the command.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Buffer [Vec]<[String]> is received empty.
+    /// * The command is unknown or has no keys.
+    /// * The command does not satisfy its arity.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_empty(&buffer, "command|getkeys")?;
+        Ok(RArray::encode(
+            server.get_command_table().get_keys(&buffer)?,
+        ))
+    }
+}
@@ -0,0 +1,68 @@
+use crate::tcp_protocol::command_table::CommandSpec;
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::Runnable,
+    native_types::{ErrorStruct, RBulkString, RInteger, RSimpleString, RedisType},
+};
+
+pub struct CommandInfo;
+
+impl Runnable<ServerRedisAttributes> for CommandInfo {
+    /// Returns the metadata of the given commands, or of every command if none is given.
+    ///
+    /// # Return value
+    /// [String] _encoded_ as an array with, for each command, an array with:
+    /// * name.
+    /// * arity: negative if it is the minimum number of arguments.
+    /// * flags.
+    /// * first key, last key and step between keys.
+    /// * ACL categories.
+    /// * tips, key specifications and subcommands, always empty.
+    ///
+    /// Unknown commands are answered with nil.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        let table = server.get_command_table();
+        let specs: Vec<Option<&CommandSpec>> = if buffer.is_empty() {
+            table.all().into_iter().map(Some).collect()
+        } else {
+            buffer
+                .iter()
+                .map(|name| table.get(&name.to_lowercase()))
+                .collect()
+        };
+
+        let mut encoded = format!("*{}\r\n", specs.len());
+        for spec in specs {
+            match spec {
+                Some(spec) => encoded.push_str(&encode_spec(spec)),
+                None => encoded.push_str(&RBulkString::encode("(nil)".to_string())),
+            }
+        }
+        Ok(encoded)
+    }
+}
+
+fn encode_spec(spec: &CommandSpec) -> String {
+    format!(
+        "*10\r\n{}{}{}{}{}{}{}*0\r\n*0\r\n*0\r\n",
+        RBulkString::encode(spec.name.to_string()),
+        RInteger::encode(spec.arity),
+        encode_status_list(spec.flags),
+        RInteger::encode(spec.first_key),
+        RInteger::encode(spec.last_key),
+        RInteger::encode(spec.step),
+        encode_status_list(spec.acl_categories),
+    )
+}
+
+fn encode_status_list(items: &[&str]) -> String {
+    let mut encoded = format!("*{}\r\n", items.len());
+    for item in items {
+        encoded.push_str(&RSimpleString::encode(item.to_string()));
+    }
+    encoded
+}
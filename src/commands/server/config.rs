@@ -1,8 +1,10 @@
 use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
 use crate::{
     commands::{
-        check_empty,
-        server::{config_get::ConfigGet, config_set::ConfigSet},
+        server::{
+            config_get::ConfigGet, config_resetstat::ConfigResetStat,
+            config_rewrite::ConfigRewrite, config_set::ConfigSet,
+        },
         Runnable,
     },
     native_types::ErrorStruct,
@@ -11,27 +13,27 @@ use crate::{
 pub struct Config;
 
 impl Runnable<ServerRedisAttributes> for Config {
-    /// Return the number of keys in the currently-selected database.
+    /// A container for the server configuration subcommands: GET, SET, REWRITE and RESETSTAT.
     ///
     /// # Return value
-    /// [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): a number of keys in the currently-selected database.
+    /// [String] _encoded_ with the reply of the subcommand.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty.
+    /// * The subcommand does not exist or failed.
     /// * [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes) has poisoned methods.
     fn run(
         &self,
         mut buffer: Vec<String>,
         server: &mut ServerRedisAttributes,
     ) -> Result<String, ErrorStruct> {
-        check_empty(&buffer, "config")?;
-
         let item = buffer.remove(0);
-        match item.as_str() {
+        match item.to_lowercase().as_str() {
             "set" => ConfigSet.run(buffer, server),
             "get" => ConfigGet.run(buffer, server),
+            "rewrite" => ConfigRewrite.run(buffer, server),
+            "resetstat" => ConfigResetStat.run(buffer, server),
             _ => Err(ErrorStruct::new(
                 String::from("ERR"),
                 String::from("Unknown subcommand or wrong number of arguments for 'config'"),
@@ -0,0 +1,32 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{check_not_empty, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+};
+
+pub struct ConfigResetStat;
+
+impl Runnable<ServerRedisAttributes> for ConfigResetStat {
+    /// Resets the statistics reported by INFO: the commands processed, the commandstats and
+    /// the keyspace hits, misses and expirations.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: OK.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Buffer [Vec]<[String]> is not received empty.
+    /// * The [ServerStats](crate::logs::server_stats::ServerStats) are poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_not_empty(&buffer)?;
+
+        server.get_stats().reset()?;
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
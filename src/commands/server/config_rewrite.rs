@@ -0,0 +1,34 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{check_not_empty, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
+};
+
+pub struct ConfigRewrite;
+
+impl Runnable<ServerRedisAttributes> for ConfigRewrite {
+    /// The CONFIG REWRITE command writes the configuration of the running server in the
+    /// _redis.conf_ file it was started with. The lines of the parameters are replaced in
+    /// place, keeping the comments, and the changed parameters missing from the file are appended.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RSimpleString]: OK when the file was rewritten.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Buffer [Vec]<[String]> is not received empty.
+    /// * The server was started without a configuration file, or it could not be written.
+    /// * [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes) has poisoned methods.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        check_not_empty(&buffer)?;
+
+        server.rewrite_config()?;
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
@@ -1,14 +1,8 @@
 use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
 use crate::{
-    commands::{
-        check_empty,
-        server::{
-            config_set_db_file_name::ConfigSetDbFileName, config_set_log_fle::ConfigSetLogFile,
-            config_set_verbose::ConfigSetVerbose,
-        },
-        Runnable,
-    },
-    native_types::ErrorStruct,
+    commands::{check_empty, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RSimpleString, RedisType},
 };
 
 pub struct ConfigSet;
@@ -16,36 +10,116 @@ pub struct ConfigSet;
 impl Runnable<ServerRedisAttributes> for ConfigSet {
     /// The CONFIG SET command is used in order to reconfigure the server at run time without the need to restart Redis.
     ///
-    /// Enabled reconfigurations:
-    /// * logfile
-    /// * dbfilename
-    /// * verbose
+    /// It receives one or more pairs of parameter and value, as `CONFIG SET timeout 30 gc-period 0`,
+    /// and sets all of them or none: if a value is not valid or can not be applied, the parameters
+    /// already changed go back to their previous values. Every parameter of the
+    /// [registry](crate::redis_config::parameters::PARAMETERS) can be set, except the immutable ones.
     ///
     /// # Return value
-    /// [String] _encoded_ in [RArray](crate::native_types::RArray): OK when the configuration was set properly.
+    /// [String] _encoded_ in [RSimpleString]: OK when the configuration was set properly.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty.
+    /// * Buffer [Vec]<[String]> is received empty, or a parameter has no value.
+    /// * A parameter does not exist, is immutable or is repeated.
+    /// * A value is not valid for its parameter.
     /// * [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes) has poisoned methods.
-    /// * Unknown subcommand for CONFIG SET.    
     fn run(
         &self,
-        mut buffer: Vec<String>,
+        buffer: Vec<String>,
         server: &mut ServerRedisAttributes,
     ) -> Result<String, ErrorStruct> {
         check_empty(&buffer, "config set")?;
-
-        let item = buffer.remove(0);
-        match item.as_str() {
-            "logfile" => ConfigSetLogFile.run(buffer, server),
-            "dbfilename" => ConfigSetDbFileName.run(buffer, server),
-            "verbose" => ConfigSetVerbose.run(buffer, server),
-            _ => Err(ErrorStruct::new(
-                String::from("ERR"),
-                String::from("Unknown subcommand or wrong number of arguments for 'set'."),
-            )),
+        if !buffer.len().is_multiple_of(2) {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "config|set",
+            )));
         }
+
+        let pairs: Vec<(String, String)> = buffer
+            .chunks(2)
+            .map(|pair| (pair[0].clone(), pair[1].clone()))
+            .collect();
+        server.set_config_parameters(&pairs)?;
+        Ok(RSimpleString::encode(redis_messages::ok()))
+    }
+}
+
+#[cfg(test)]
+mod test_config_set {
+    use super::*;
+    use crate::commands::server::config_get::ConfigGet;
+    use crate::commands::{create_server, remove_server_files};
+    use crate::native_types::RArray;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_several_parameters_are_set_and_read() {
+        let mut server = create_server("config_set_01");
+
+        ConfigSet
+            .run(
+                vec_strings!["gc-period", "0", "gc-sample-size", "50", "verbose", "3"],
+                &mut server,
+            )
+            .unwrap();
+        let gc = ConfigGet.run(vec_strings!["gc-*"], &mut server);
+        let verbose = ConfigGet.run(vec_strings!["verbose", "save-interval"], &mut server);
+
+        assert_eq!(
+            gc.unwrap(),
+            RArray::encode(vec_strings!["gc-period", "0", "gc-sample-size", "50"])
+        );
+        assert_eq!(
+            verbose.unwrap(),
+            RArray::encode(vec_strings!["verbose", "3", "save-interval", "1"])
+        );
+        remove_server_files("config_set_01");
+    }
+
+    #[test]
+    fn test_02_invalid_values_change_nothing() {
+        let mut server = create_server("config_set_02");
+
+        let negative = ConfigSet.run(
+            vec_strings!["gc-period", "5", "save-interval", "-1"],
+            &mut server,
+        );
+        let empty_sample = ConfigSet.run(vec_strings!["gc-sample-size", "0"], &mut server);
+        let odd = ConfigSet.run(vec_strings!["gc-period", "5", "verbose"], &mut server);
+
+        assert_eq!(
+            negative.unwrap_err().print_it(),
+            "ERR CONFIG SET failed (possibly related to argument 'save-interval') - argument must be between 0 and 9223372036854775807 inclusive"
+        );
+        assert!(empty_sample.is_err());
+        assert!(odd.is_err());
+        assert_eq!(
+            server.get_config_parameters(&["gc-*".to_string()]).unwrap(),
+            vec_strings!["gc-period", "10", "gc-sample-size", "20"]
+        );
+        remove_server_files("config_set_02");
+    }
+
+    #[test]
+    fn test_03_slowlog_parameters_are_applied_to_the_slowlog() {
+        let mut server = create_server("config_set_03");
+
+        ConfigSet
+            .run(vec_strings!["slowlog-log-slower-than", "0"], &mut server)
+            .unwrap();
+        server
+            .get_stats()
+            .register_execution(
+                &["get".to_string(), "key".to_string()],
+                std::time::Duration::from_micros(1),
+                String::new(),
+                String::new(),
+            )
+            .unwrap();
+
+        assert_eq!(server.get_slowlog().len().unwrap(), 1);
+        remove_server_files("config_set_03");
     }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    commands::{check_error_cases_without_elements, Runnable},
+    commands::Runnable,
     database::Database,
     messages::redis_messages,
     native_types::RedisType,
@@ -17,11 +17,10 @@ impl Runnable<Arc<Mutex<Database>>> for Dbsize {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or not received with only one element.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.    
     fn run(
         &self,
-        buffer: Vec<String>,
+        _buffer: Vec<String>,
         database: &mut Arc<Mutex<Database>>,
     ) -> Result<String, ErrorStruct> {
         let database = database.lock().map_err(|_| {
@@ -30,8 +29,6 @@ impl Runnable<Arc<Mutex<Database>>> for Dbsize {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_error_cases_without_elements(&buffer, "dbisze", 1)?;
-
         Ok(RInteger::encode(database.size() as isize))
     }
 }
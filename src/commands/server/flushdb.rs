@@ -17,7 +17,6 @@ impl Runnable<Arc<Mutex<Database>>> for FlushDb {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * Buffer [Vec]<[String]> is received empty, or not received with only one element.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.       
     fn run(
         &self,
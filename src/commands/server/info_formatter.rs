@@ -1,22 +1,87 @@
-///Methods with the format in String for the [InfoSv](crate::commands::server::info_sv::InfoSv) command about a database.
-pub mod info_db_formatter {
-    pub fn title() -> String {
-        "DATABASE:".to_string()
+///Sections requested to the [InfoSv](crate::commands::server::info_sv::InfoSv) command.
+pub mod info_sections {
+    pub const SERVER: &str = "server";
+    pub const CLIENTS: &str = "clients";
+    pub const MEMORY: &str = "memory";
+    pub const PERSISTENCE: &str = "persistence";
+    pub const STATS: &str = "stats";
+    pub const REPLICATION: &str = "replication";
+    pub const COMMANDSTATS: &str = "commandstats";
+    pub const KEYSPACE: &str = "keyspace";
+
+    /// Sections shown by INFO without arguments, or with *default*.
+    const DEFAULT_SECTIONS: [&str; 7] = [
+        SERVER,
+        CLIENTS,
+        MEMORY,
+        PERSISTENCE,
+        STATS,
+        REPLICATION,
+        KEYSPACE,
+    ];
+
+    /// Sections selected by the arguments of INFO [section ...].
+    ///
+    /// * No arguments, or *default*, select the default sections.
+    /// * *all* and *everything* select every section, including *commandstats*.
+    /// * Unknown sections are ignored.
+    pub struct InfoSections {
+        sections: Vec<String>,
+    }
+
+    impl InfoSections {
+        /// Creates the selection from the arguments of the command, skipping its name.
+        pub fn from_buffer(buffer: &[String]) -> Self {
+            let mut sections: Vec<String> = Vec::new();
+            if buffer.is_empty() {
+                sections.extend(DEFAULT_SECTIONS.iter().map(|section| section.to_string()));
+            }
+            for argument in buffer.iter().map(|argument| argument.to_lowercase()) {
+                match argument.as_str() {
+                    "default" => {
+                        sections.extend(DEFAULT_SECTIONS.iter().map(|section| section.to_string()))
+                    }
+                    "all" | "everything" => {
+                        sections.extend(DEFAULT_SECTIONS.iter().map(|section| section.to_string()));
+                        sections.push(COMMANDSTATS.to_string());
+                    }
+                    _ => sections.push(argument),
+                }
+            }
+            InfoSections { sections }
+        }
+
+        /// Returns true if the section has been requested.
+        pub fn contains(&self, section: &str) -> bool {
+            self.sections.iter().any(|requested| requested == section)
+        }
     }
 
-    pub fn number_of_keys(n: usize) -> String {
-        format!("Number of keys: {}", n)
+    /// Title of the section, as *# Server*.
+    pub fn title(section: &str) -> String {
+        let mut chars = section.chars();
+        match chars.next() {
+            Some(first) => format!("# {}{}", first.to_uppercase(), chars.as_str()),
+            None => "#".to_string(),
+        }
+    }
+}
+
+///Methods with the format in String for the keyspace section of the [InfoSv](crate::commands::server::info_sv::InfoSv) command.
+pub mod info_db_formatter {
+    pub fn keyspace(keys: usize, expires: usize) -> String {
+        format!("db0:keys={},expires={},avg_ttl=0", keys, expires)
     }
 }
 
 ///Methods with the format in String for the [InfoSv](crate::commands::server::info_sv::InfoSv) command about a clients and channels.
 pub mod info_client_formatter {
     pub fn clients_connected(n: usize) -> String {
-        format!("clients connected: {}", n)
+        format!("connected_clients:{}", n)
     }
 
     pub fn active_channels(n: usize) -> String {
-        format!("active channels: {}", n)
+        format!("pubsub_channels:{}", n)
     }
 }
 
@@ -24,31 +89,178 @@ pub mod info_client_formatter {
 pub mod info_server_formatter {
     use std::env::current_exe;
 
-    pub fn title() -> String {
-        "SERVER:".to_string()
+    pub fn version() -> String {
+        format!("redis_version:{}", env!("CARGO_PKG_VERSION"))
+    }
+
+    pub fn process_id() -> String {
+        format!("process_id:{}", std::process::id())
     }
 
     pub fn ip(ip: &str) -> String {
-        format!("ip: {}", ip)
+        format!("ip:{}", ip)
     }
 
     pub fn port(port: &str) -> String {
-        format!("tcp port: {}", port)
+        format!("tcp_port:{}", port)
     }
 
     pub fn current_exe_dir() -> String {
-        format!("executable: {:?}", current_exe())
+        let executable = current_exe()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        format!("executable:{}", executable)
     }
 
     pub fn verbose_level(v: usize) -> String {
-        format!("verbose level: {}", v)
+        format!("verbose:{}", v)
     }
 
     pub fn client_timeout(t: u64) -> String {
-        format!("connection timeout: {}", t)
+        format!("timeout:{}", t)
     }
 
     pub fn logfile_name(name: &str) -> String {
-        format!("log file name: {}", name)
+        format!("logfile:{}", name)
+    }
+}
+
+///Methods with the format in String for the [InfoSv](crate::commands::server::info_sv::InfoSv) command about the activity of the server.
+pub mod info_stats_formatter {
+    use crate::logs::server_stats::{CommandStat, StatsSnapshot};
+
+    pub fn uptime(snapshot: &StatsSnapshot) -> Vec<String> {
+        vec![
+            format!("uptime_in_seconds:{}", snapshot.uptime_in_seconds),
+            format!("uptime_in_days:{}", snapshot.uptime_in_seconds / 86400),
+        ]
+    }
+
+    pub fn memory(used_memory: u64) -> Vec<String> {
+        vec![
+            format!("used_memory:{}", used_memory),
+            format!("used_memory_human:{}", human_bytes(used_memory)),
+        ]
+    }
+
+    pub fn persistence(snapshot: &StatsSnapshot) -> Vec<String> {
+        vec![
+            format!(
+                "rdb_changes_since_last_save:{}",
+                snapshot.rdb_changes_since_last_save
+            ),
+            format!(
+                "rdb_bgsave_in_progress:{}",
+                snapshot.rdb_bgsave_in_progress as u8
+            ),
+            format!("rdb_last_save_time:{}", snapshot.rdb_last_save_time),
+            format!(
+                "rdb_last_bgsave_status:{}",
+                if snapshot.rdb_last_bgsave_ok {
+                    "ok"
+                } else {
+                    "err"
+                }
+            ),
+            format!("rdb_saves:{}", snapshot.rdb_saves),
+        ]
+    }
+
+    pub fn stats(snapshot: &StatsSnapshot) -> Vec<String> {
+        vec![
+            format!(
+                "total_commands_processed:{}",
+                snapshot.total_commands_processed
+            ),
+            format!(
+                "instantaneous_ops_per_sec:{}",
+                snapshot.instantaneous_ops_per_sec
+            ),
+            format!("expired_keys:{}", snapshot.expired_keys),
+            format!("evicted_keys:{}", snapshot.evicted_keys),
+            format!("keyspace_hits:{}", snapshot.keyspace_hits),
+            format!("keyspace_misses:{}", snapshot.keyspace_misses),
+        ]
+    }
+
+    pub fn replication() -> Vec<String> {
+        vec!["role:master".to_string(), "connected_slaves:0".to_string()]
+    }
+
+    pub fn command_stat(name: &str, stat: &CommandStat) -> String {
+        format!(
+            "cmdstat_{}:calls={},usec={},usec_per_call={:.2}",
+            name,
+            stat.calls,
+            stat.usec,
+            stat.usec_per_call()
+        )
+    }
+
+    /// Formats a number of bytes as *1.50K*, *2.00M*...
+    fn human_bytes(bytes: u64) -> String {
+        let units = ["B", "K", "M", "G", "T"];
+        let mut value = bytes as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < units.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            format!("{}B", bytes)
+        } else {
+            format!("{:.2}{}", value, units[unit])
+        }
+    }
+
+    #[cfg(test)]
+    mod test_info_stats_formatter {
+        use super::*;
+
+        #[test]
+        fn test_01_human_bytes() {
+            assert_eq!(human_bytes(100), "100B");
+            assert_eq!(human_bytes(1536), "1.50K");
+            assert_eq!(human_bytes(3 * 1024 * 1024), "3.00M");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_info_sections {
+    use super::info_sections::*;
+
+    fn args(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn test_01_no_arguments_select_the_default_sections() {
+        let sections = InfoSections::from_buffer(&[]);
+        assert!(sections.contains(SERVER));
+        assert!(sections.contains(KEYSPACE));
+        assert!(!sections.contains(COMMANDSTATS));
+    }
+
+    #[test]
+    fn test_02_all_selects_commandstats() {
+        let sections = InfoSections::from_buffer(&args(&["everything"]));
+        assert!(sections.contains(STATS));
+        assert!(sections.contains(COMMANDSTATS));
+    }
+
+    #[test]
+    fn test_03_only_named_sections_are_selected() {
+        let sections = InfoSections::from_buffer(&args(&["Stats", "commandstats"]));
+        assert!(sections.contains(STATS));
+        assert!(sections.contains(COMMANDSTATS));
+        assert!(!sections.contains(SERVER));
+        assert!(!sections.contains(KEYSPACE));
+    }
+
+    #[test]
+    fn test_04_section_title() {
+        assert_eq!(title(SERVER), "# Server");
+        assert_eq!(title(COMMANDSTATS), "# Commandstats");
     }
 }
@@ -1,17 +1,18 @@
+use crate::commands::server::info_formatter::info_sections::InfoSections;
 use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
 use crate::{
     commands::Runnable,
     native_types::ErrorStruct,
-    native_types::{RArray, RedisType},
+    native_types::{RBulkString, RedisType},
 };
 
 pub struct InfoSv;
 
 impl Runnable<ServerRedisAttributes> for InfoSv {
-    /// Required for the INFO command. Returns information and statistics about the [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes) in a format that is simple to parse by computers and easy to read by humans.
+    /// Required for the INFO command. Returns information and statistics about the [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes) and its [Database](crate::database::Database) in a format that is simple to parse by computers and easy to read by humans.
     ///
     /// # Return value
-    /// [String] _encoded_ in [RArray]: as a collection of text lines.
+    /// [String] _encoded_ in [RBulkString]: the text lines of every requested section, separated by CRLF.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
@@ -19,9 +20,43 @@ impl Runnable<ServerRedisAttributes> for InfoSv {
     /// * [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes) has poisoned methods.
     fn run(
         &self,
-        _buffer: Vec<String>,
+        buffer: Vec<String>,
         server: &mut ServerRedisAttributes,
     ) -> Result<String, ErrorStruct> {
-        Ok(RArray::encode(server.info()?))
+        Ok(RBulkString::encode(
+            server
+                .info(&InfoSections::from_buffer(&buffer))?
+                .join("\r\n"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test_info_sv {
+    use super::*;
+    use crate::commands::{create_notifier, create_server, remove_server_files, strings::set::Set};
+    use crate::database::Database;
+    use crate::vec_strings;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_01_server_and_keyspace_sections_are_sent_in_one_bulk_string() {
+        let mut server = create_server("info_sv_01");
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        Set.run(vec_strings!["key", "value"], &mut database)
+            .unwrap();
+        server.set_database(database);
+
+        let reply = InfoSv
+            .run(vec_strings!["server", "keyspace"], &mut server)
+            .unwrap();
+
+        let (size, text) = reply[1..].split_once("\r\n").unwrap();
+        assert_eq!(size.parse::<usize>().unwrap() + 2, text.len());
+        assert!(text.starts_with("# Server\r\n"));
+        assert!(text.contains("\r\n\r\n# Keyspace\r\ndb0:keys=1,"));
+        assert!(!text.contains("# Clients"));
+        remove_server_files("info_sv_01");
     }
 }
@@ -0,0 +1,36 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    commands::Runnable,
+    database::Database,
+    messages::redis_messages,
+    native_types::{error_severity::ErrorSeverity, ErrorStruct, RInteger, RedisType},
+};
+
+pub struct LastSave;
+
+impl Runnable<Arc<Mutex<Database>>> for LastSave {
+    /// Returns the unix time of the last successful save of the [Database],
+    /// or of the start of the server if there was none.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the unix time, in seconds.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        _buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        Ok(RInteger::encode(database.last_save() as isize))
+    }
+}
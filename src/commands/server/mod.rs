@@ -1,17 +1,23 @@
+pub mod bgsave;
+pub mod command;
+pub mod command_docs;
+pub mod command_getkeys;
+pub mod command_info;
 pub mod config;
 pub mod config_get;
+pub mod config_resetstat;
+pub mod config_rewrite;
 pub mod config_set;
-pub mod config_set_db_file_name;
-pub mod config_set_log_fle;
-pub mod config_set_verbose;
 pub mod flushdb;
-pub mod info_db;
 pub mod info_formatter;
 pub mod info_sv;
+pub mod lastsave;
 pub mod monitor;
 pub mod notify_monitors;
 pub mod save;
 pub mod shutdown;
+pub mod slowlog;
+pub mod slowlog_get;
 pub use monitor::Monitor;
 pub use notify_monitors::NotifyMonitors;
 pub use shutdown::Shutdown;
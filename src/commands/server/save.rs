@@ -1,7 +1,7 @@
 use std::sync::{Arc, Mutex};
 
 use crate::{
-    commands::{check_not_empty, Runnable},
+    commands::Runnable,
     database::Database,
     messages::redis_messages,
     native_types::{error_severity::ErrorSeverity, ErrorStruct, RSimpleString, RedisType},
@@ -18,26 +18,26 @@ impl Runnable<Arc<Mutex<Database>>> for Save {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
+    /// * A background save is in progress.
     /// * The take snapshot on the [Database] failed.
-    /// * The buffer [Vec]<[String]> is received not empty.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.   
     fn run(
         &self,
-        buffer: Vec<String>,
+        _buffer: Vec<String>,
         database: &mut Arc<Mutex<Database>>,
     ) -> Result<String, ErrorStruct> {
-        check_not_empty(&buffer)?;
-
-        match database
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "database",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .take_snapshot()
-        {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        if database.background_save_in_progress() {
+            return Err(ErrorStruct::from(
+                redis_messages::background_save_in_progress(),
+            ));
+        }
+        match database.take_snapshot() {
             Ok(_) => Ok(RSimpleString::encode(redis_messages::ok())),
             Err(_) => Err(ErrorStruct::new(
                 String::from("ERR"),
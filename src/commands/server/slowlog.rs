@@ -0,0 +1,130 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::{check_not_empty, server::slowlog_get::SlowlogGet, Runnable},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RInteger, RSimpleString, RedisType},
+};
+
+pub struct Slowlog;
+
+impl Runnable<ServerRedisAttributes> for Slowlog {
+    /// Reads or resets the register of the commands that exceeded the
+    /// *slowlog-log-slower-than* threshold.
+    ///
+    /// # Sub Commands
+    ///
+    /// * GET \[count\]: Returns the newest entries, 10 by default or all of them with -1.
+    /// * LEN: Returns the number of entries.
+    /// * RESET: Removes every entry.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * User does not give a supported subcommand.
+    /// * The [Slowlog](crate::logs::slowlog::Slowlog) is poisoned.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        let mut subcommand = buffer.remove(0);
+        subcommand.make_ascii_lowercase();
+        match subcommand.as_str() {
+            "get" => SlowlogGet.run(buffer, server),
+            "len" => {
+                check_not_empty(&buffer)?;
+                Ok(RInteger::encode(server.get_slowlog().len()? as isize))
+            }
+            "reset" => {
+                check_not_empty(&buffer)?;
+                server.get_slowlog().reset()?;
+                Ok(RSimpleString::encode(redis_messages::ok()))
+            }
+            _ => Err(ErrorStruct::from(redis_messages::unknown_subcommand(
+                &("slowlog|".to_owned() + &subcommand),
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_slowlog {
+    use super::*;
+    use crate::{
+        commands::create_server, tcp_protocol::server_redis_attributes::ServerRedisAttributes,
+        vec_strings,
+    };
+    use std::time::Duration;
+
+    fn record(server: &ServerRedisAttributes, command: Vec<String>) {
+        server
+            .get_slowlog()
+            .record(
+                &command,
+                Duration::from_secs(1),
+                "127.0.0.1:8080".to_string(),
+                "martina".to_string(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_01_len_and_reset() {
+        let mut server = create_server("slowlog");
+        record(&server, vec_strings!["get", "key"]);
+        record(&server, vec_strings!["set", "key", "value"]);
+        let result = Slowlog.run(vec_strings!["len"], &mut server).unwrap();
+        assert_eq!(result, ":2\r\n");
+
+        let result = Slowlog.run(vec_strings!["RESET"], &mut server).unwrap();
+        assert_eq!(result, "+OK\r\n");
+        let result = Slowlog.run(vec_strings!["len"], &mut server).unwrap();
+        assert_eq!(result, ":0\r\n");
+    }
+
+    #[test]
+    fn test_02_get_an_entry() {
+        let mut server = create_server("slowlog");
+        record(&server, vec_strings!["get", "key"]);
+        let result = Slowlog.run(vec_strings!["get"], &mut server).unwrap();
+        assert!(result.starts_with("*1\r\n*6\r\n:0\r\n:"));
+        assert!(result.ends_with(
+            ":1000000\r\n*2\r\n$3\r\nget\r\n$3\r\nkey\r\n$14\r\n127.0.0.1:8080\r\n$7\r\nmartina\r\n"
+        ));
+    }
+
+    #[test]
+    fn test_03_get_with_count() {
+        let mut server = create_server("slowlog");
+        record(&server, vec_strings!["get", "key"]);
+        record(&server, vec_strings!["get", "key"]);
+        let result = Slowlog.run(vec_strings!["get", "1"], &mut server).unwrap();
+        assert!(result.starts_with("*1\r\n*6\r\n:1\r\n"));
+        let result = Slowlog.run(vec_strings!["get", "-1"], &mut server).unwrap();
+        assert!(result.starts_with("*2\r\n"));
+        let result = Slowlog.run(vec_strings!["get", "0"], &mut server).unwrap();
+        assert_eq!(result, "*0\r\n");
+    }
+
+    #[test]
+    fn test_04_get_with_invalid_count() {
+        let mut server = create_server("slowlog");
+        let error = Slowlog
+            .run(vec_strings!["get", "many"], &mut server)
+            .unwrap_err();
+        assert_eq!(
+            error.print_it(),
+            "ERR count should be greater than or equal to -1"
+        );
+    }
+
+    #[test]
+    fn test_05_unknown_subcommand() {
+        let mut server = create_server("slowlog");
+        let error = Slowlog.run(vec_strings!["dance"], &mut server).unwrap_err();
+        assert_eq!(
+            error.print_it(),
+            "ERR Unknown subcommand or wrong number of arguments for 'slowlog|dance'"
+        );
+    }
+}
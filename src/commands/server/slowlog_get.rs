@@ -0,0 +1,72 @@
+use crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes;
+use crate::{
+    commands::Runnable,
+    logs::slowlog::SlowlogEntry,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RArray, RBulkString, RInteger, RedisType},
+};
+
+/// Number of entries returned by SLOWLOG GET without count.
+const DEFAULT_COUNT: usize = 10;
+
+pub struct SlowlogGet;
+
+impl Runnable<ServerRedisAttributes> for SlowlogGet {
+    /// Returns the newest entries of the slowlog.
+    ///
+    /// # Return value
+    /// [String] _encoded_ as an array of entries, each one an array with:
+    /// * id: unique identifier of the entry.
+    /// * timestamp: unix time when the command was executed.
+    /// * duration: microseconds of the execution.
+    /// * arguments: the command and its arguments, truncated.
+    /// * client address.
+    /// * client name.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The count is not an integer greater than or equal to -1.
+    /// * More than one argument is received.
+    /// * The [Slowlog](crate::logs::slowlog::Slowlog) is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        server: &mut ServerRedisAttributes,
+    ) -> Result<String, ErrorStruct> {
+        let count = match buffer.as_slice() {
+            [] => Some(DEFAULT_COUNT),
+            [count] => match count.parse::<isize>() {
+                Ok(-1) => None,
+                Ok(count) if count >= 0 => Some(count as usize),
+                _ => {
+                    return Err(ErrorStruct::from(redis_messages::invalid_slowlog_count()));
+                }
+            },
+            _ => {
+                return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                    "slowlog|get",
+                )))
+            }
+        };
+
+        let entries = server.get_slowlog().get(count)?;
+        let mut encoded = format!("*{}\r\n", entries.len());
+        for entry in entries {
+            encoded.push_str(&encode_entry(entry));
+        }
+        Ok(encoded)
+    }
+}
+
+fn encode_entry(entry: SlowlogEntry) -> String {
+    format!(
+        "*6\r\n{}{}{}{}{}{}",
+        RInteger::encode(entry.id as isize),
+        RInteger::encode(entry.timestamp as isize),
+        RInteger::encode(entry.duration as isize),
+        RArray::encode(entry.args),
+        RBulkString::encode(entry.client_addr),
+        RBulkString::encode(entry.client_name)
+    )
+}
@@ -2,4 +2,5 @@ pub mod sadd;
 pub mod scard;
 pub mod sismember;
 pub mod smembers;
+pub mod spop;
 pub mod srem;
@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, Runnable},
+    commands::Runnable,
     database::{Database, TypeSaved},
     err_wrongtype,
     messages::redis_messages,
@@ -22,7 +22,6 @@ impl Runnable<Arc<Mutex<Database>>> for Sadd {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a set.
-    /// * Buffer [Vec]<[String]> is received empty, or received with only one element.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -35,7 +34,6 @@ impl Runnable<Arc<Mutex<Database>>> for Sadd {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_error_cases(&buffer)?;
 
         let key = &buffer[0];
 
@@ -69,20 +67,6 @@ fn insert_in_set(buffer: &[String], item: &mut HashSet<String>) -> usize {
         .count()
 }
 
-fn check_error_cases(buffer: &[String]) -> Result<(), ErrorStruct> {
-    check_empty(buffer, "sadd")?;
-
-    if buffer.len() < 2 {
-        let error_message = redis_messages::arguments_invalid_to("sadd");
-        return Err(ErrorStruct::new(
-            error_message.get_prefix(),
-            error_message.get_message(),
-        ));
-    }
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod test_sadd_function {
     use crate::commands::create_notifier;
@@ -128,7 +112,7 @@ mod test_sadd_function {
         database_mock
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let buffer_mock = vec_strings![
             "key", "member2", "member1", "member1", "member3", "member2", "member1", "member1",
             "member3"
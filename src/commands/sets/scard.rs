@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, Runnable},
+    commands::Runnable,
     database::{Database, TypeSaved},
     err_wrongtype,
     messages::redis_messages,
@@ -19,11 +19,10 @@ impl Runnable<Arc<Mutex<Database>>> for Scard {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a set.
-    /// * Buffer [Vec]<[String]> is received empty, or not received with only one element.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.    
     fn run(
         &self,
-        mut buffer: Vec<String>,
+        buffer: Vec<String>,
         database: &mut Arc<Mutex<Database>>,
     ) -> Result<String, ErrorStruct> {
         let mut database = database.lock().map_err(|_| {
@@ -32,11 +31,10 @@ impl Runnable<Arc<Mutex<Database>>> for Scard {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_error_cases(&mut buffer)?;
 
         let key = &buffer[0];
 
-        match database.get_mut(key) {
+        match database.get(key) {
             Some(item) => match item {
                 TypeSaved::Set(item) => Ok(RInteger::encode(item.len() as isize)),
                 _ => {
@@ -48,19 +46,6 @@ impl Runnable<Arc<Mutex<Database>>> for Scard {
     }
 }
 
-fn check_error_cases(buffer: &mut Vec<String>) -> Result<(), ErrorStruct> {
-    check_empty(buffer, "scard")?;
-
-    if buffer.len() != 1 {
-        let error_message = redis_messages::arguments_invalid_to("scard");
-        return Err(ErrorStruct::new(
-            error_message.get_prefix(),
-            error_message.get_message(),
-        ));
-    }
-    Ok(())
-}
-
 #[cfg(test)]
 mod test_scard_function {
     use crate::commands::create_notifier;
@@ -157,10 +142,10 @@ mod test_scard_function {
     fn test_05_scard_return_error_wrongtype_if_execute_with_key_of_string() {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut database_mock = Arc::new(Mutex::new(Database::new(notifier)));
-        database_mock.lock().unwrap().insert(
-            "keyOfString".to_string(),
-            TypeSaved::String("value".to_string()),
-        );
+        database_mock
+            .lock()
+            .unwrap()
+            .insert("keyOfString".to_string(), TypeSaved::String("value".into()));
         let buffer_mock = vec_strings!["keyOfString"];
 
         let result_received = Scard.run(buffer_mock, &mut database_mock);
@@ -193,19 +178,4 @@ mod test_scard_function {
             ("-".to_owned() + &expected_message_redis.get_message_complete() + "\r\n").to_string();
         assert_eq!(expected_result, result_received_encoded);
     }
-
-    #[test]
-    fn test_07_scard_return_error_arguments_invalid_ifbuffer_has_many_one_key() {
-        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
-        let mut database_mock = Arc::new(Mutex::new(Database::new(notifier)));
-        let buffer_mock = vec_strings!["key1", "key2", "key3"];
-
-        let result_received = Scard.run(buffer_mock, &mut database_mock);
-        let result_received_encoded = result_received.unwrap_err().get_encoded_message_complete();
-
-        let expected_message_redis = redis_messages::arguments_invalid_to("scard");
-        let expected_result =
-            ("-".to_owned() + &expected_message_redis.get_message_complete() + "\r\n").to_string();
-        assert_eq!(expected_result, result_received_encoded);
-    }
 }
@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, Runnable},
+    commands::Runnable,
     database::{Database, TypeSaved},
     err_wrongtype,
     messages::redis_messages,
@@ -21,7 +21,6 @@ impl Runnable<Arc<Mutex<Database>>> for Sismember {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a set.
-    /// * Buffer [Vec]<[String]> is received empty, or not received with only two element.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.       
     fn run(
         &self,
@@ -34,11 +33,10 @@ impl Runnable<Arc<Mutex<Database>>> for Sismember {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_error_cases(&buffer)?;
 
         let key = &buffer[0];
 
-        match database.get_mut(key) {
+        match database.get(key) {
             Some(item) => match item {
                 TypeSaved::Set(item) => {
                     let member = &buffer[1];
@@ -57,19 +55,6 @@ impl Runnable<Arc<Mutex<Database>>> for Sismember {
     }
 }
 
-fn check_error_cases(buffer: &[String]) -> Result<(), ErrorStruct> {
-    check_empty(buffer, "sismember")?;
-
-    if buffer.len() != 2 {
-        let error_message = redis_messages::arguments_invalid_to("sismember");
-        return Err(ErrorStruct::new(
-            error_message.get_prefix(),
-            error_message.get_message(),
-        ));
-    }
-    Ok(())
-}
-
 #[cfg(test)]
 mod test_sismember_function {
     use crate::commands::create_notifier;
@@ -142,10 +127,10 @@ mod test_sismember_function {
     fn test_04_sismember_return_error_wrongtype_if_execute_with_key_of_string() {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut database_mock = Arc::new(Mutex::new(Database::new(notifier)));
-        database_mock.lock().unwrap().insert(
-            "keyOfString".to_string(),
-            TypeSaved::String("value".to_string()),
-        );
+        database_mock
+            .lock()
+            .unwrap()
+            .insert("keyOfString".to_string(), TypeSaved::String("value".into()));
         let buffer_mock = vec_strings!["keyOfString", "value"];
 
         let result_received = Sismember.run(buffer_mock, &mut database_mock);
@@ -179,19 +164,4 @@ mod test_sismember_function {
             ("-".to_owned() + &expected_message_redis.get_message_complete() + "\r\n").to_string();
         assert_eq!(expected_result, result_received_encoded);
     }
-
-    #[test]
-    fn test_06_sismember_return_error_arguments_invalid_ifbuffer_has_more_than_3_arguments() {
-        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
-        let mut database_mock = Arc::new(Mutex::new(Database::new(notifier)));
-        let buffer_mock = vec_strings!["arg1", "arg2", "arg3"];
-
-        let result_received = Sismember.run(buffer_mock, &mut database_mock);
-        let result_received_encoded = result_received.unwrap_err().get_encoded_message_complete();
-
-        let expected_message_redis = redis_messages::arguments_invalid_to("sismember");
-        let expected_result =
-            ("-".to_owned() + &expected_message_redis.get_message_complete() + "\r\n").to_string();
-        assert_eq!(expected_result, result_received_encoded);
-    }
 }
@@ -0,0 +1,174 @@
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::{get_as_integer, Runnable},
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RArray, RBulkString, RedisType},
+};
+use rand::seq::IteratorRandom;
+use std::sync::{Arc, Mutex};
+pub struct Spop;
+
+impl Runnable<Arc<Mutex<Database>>> for Spop {
+    /// Removes and returns one or more random members from the set stored at **key**.
+    /// The key is deleted when its last member is removed.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the removed member, or nil when **key**
+    /// does not exist. With the count argument, [String] _encoded_ in [RArray]: the
+    /// removed members, up to count of them.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a set.
+    /// * The count is not a positive integer or zero.
+    /// * Buffer [Vec]<[String]> is received with more than two elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let count = check_error_cases(&buffer)?;
+
+        let key = &buffer[0];
+
+        let (popped, emptied) = match database.get_mut(key) {
+            Some(TypeSaved::Set(set)) => {
+                let chosen = set
+                    .iter()
+                    .cloned()
+                    .choose_multiple(&mut rand::thread_rng(), count.unwrap_or(1));
+                for member in chosen.iter() {
+                    set.remove(member);
+                }
+                (chosen, set.is_empty())
+            }
+            Some(_) => return err_wrongtype!(),
+            None => (Vec::new(), false),
+        };
+        if emptied {
+            database.remove(key);
+        }
+
+        match count {
+            Some(_) => Ok(RArray::encode(popped)),
+            None => Ok(RBulkString::encode(
+                popped
+                    .into_iter()
+                    .next()
+                    .unwrap_or_else(|| "(nil)".to_string()),
+            )),
+        }
+    }
+}
+
+/// Returns the count, if it was given.
+fn check_error_cases(buffer: &[String]) -> Result<Option<usize>, ErrorStruct> {
+    match buffer.len() {
+        1 => Ok(None),
+        2 => match get_as_integer(&buffer[1])? {
+            count if count >= 0 => Ok(Some(count as usize)),
+            _ => Err(ErrorStruct::from(redis_messages::value_must_be_positive())),
+        },
+        _ => {
+            let error_message = redis_messages::arguments_invalid_to("spop");
+            Err(ErrorStruct::new(
+                error_message.get_prefix(),
+                error_message.get_message(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_spop_function {
+    use crate::commands::create_notifier;
+    use std::collections::HashSet;
+
+    use crate::vec_strings;
+
+    use super::*;
+
+    fn database_with_set(members: Vec<String>) -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier)));
+        database.lock().unwrap().insert(
+            "key".to_string(),
+            TypeSaved::Set(members.into_iter().collect()),
+        );
+        database
+    }
+
+    #[test]
+    fn test_01_spop_removes_and_returns_a_random_member() {
+        let mut database = database_with_set(vec_strings!["m1", "m2"]);
+
+        let popped = Spop.run(vec_strings!["key"], &mut database).unwrap();
+
+        let remaining = match database.lock().unwrap().get("key") {
+            Some(TypeSaved::Set(set)) => set.clone(),
+            _ => panic!("the set was removed"),
+        };
+        assert_eq!(remaining.len(), 1);
+        let member = if remaining.contains("m1") { "m2" } else { "m1" };
+        assert_eq!(popped, RBulkString::encode(member.to_string()));
+    }
+
+    #[test]
+    fn test_02_spop_with_count_removes_the_key_when_it_is_emptied() {
+        let mut database = database_with_set(vec_strings!["m1", "m2", "m3"]);
+
+        let popped = Spop.run(vec_strings!["key", "5"], &mut database).unwrap();
+
+        assert!(popped.starts_with("*3\r\n"));
+        let members: HashSet<&str> = popped
+            .split("\r\n")
+            .filter(|m| m.starts_with('m'))
+            .collect();
+        assert_eq!(members, ["m1", "m2", "m3"].iter().copied().collect());
+        assert!(database.lock().unwrap().get("key").is_none());
+        assert_eq!(
+            Spop.run(vec_strings!["key"], &mut database).unwrap(),
+            "$-1\r\n"
+        );
+        assert_eq!(
+            Spop.run(vec_strings!["key", "0"], &mut database).unwrap(),
+            "*0\r\n"
+        );
+    }
+
+    #[test]
+    fn test_03_spop_with_invalid_arguments() {
+        let mut database = database_with_set(vec_strings!["m1"]);
+
+        assert_eq!(
+            Spop.run(vec_strings!["key", "-1"], &mut database)
+                .unwrap_err()
+                .print_it(),
+            "ERR value is out of range, must be positive"
+        );
+        assert!(Spop.run(vec_strings!["key", "one"], &mut database).is_err());
+        assert!(Spop
+            .run(vec_strings!["key", "1", "2"], &mut database)
+            .is_err());
+
+        database
+            .lock()
+            .unwrap()
+            .insert("text".to_string(), TypeSaved::String(b"a".to_vec()));
+        assert!(Spop
+            .run(vec_strings!["text"], &mut database)
+            .unwrap_err()
+            .print_it()
+            .starts_with("WRONGTYPE"));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, Runnable},
+    commands::Runnable,
     database::{Database, TypeSaved},
     err_wrongtype,
     messages::redis_messages,
@@ -20,7 +20,6 @@ impl Runnable<Arc<Mutex<Database>>> for Srem {
     /// Return an [ErrorStruct] if:
     ///
     /// * The value stored at **key** is not a set.
-    /// * Buffer [Vec]<[String]> is received empty, or received with only one element.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.    
     fn run(
         &self,
@@ -33,7 +32,6 @@ impl Runnable<Arc<Mutex<Database>>> for Srem {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_error_cases(&buffer)?;
 
         let key = &buffer[0];
 
@@ -58,20 +56,6 @@ impl Runnable<Arc<Mutex<Database>>> for Srem {
     }
 }
 
-fn check_error_cases(buffer: &[String]) -> Result<(), ErrorStruct> {
-    check_empty(buffer, "srem")?;
-
-    if buffer.len() < 2 {
-        let error_message = redis_messages::arguments_invalid_to("srem");
-        return Err(ErrorStruct::new(
-            error_message.get_prefix(),
-            error_message.get_message(),
-        ));
-    }
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod test_srem_function {
     use crate::commands::create_notifier;
@@ -190,10 +174,10 @@ mod test_srem_function {
     fn test_05_srem_return_error_wrongtype_if_execute_with_key_of_string() {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut database_mock = Arc::new(Mutex::new(Database::new(notifier)));
-        database_mock.lock().unwrap().insert(
-            "keyOfString".to_string(),
-            TypeSaved::String("value".to_string()),
-        );
+        database_mock
+            .lock()
+            .unwrap()
+            .insert("keyOfString".to_string(), TypeSaved::String("value".into()));
         let buffer_mock = vec_strings!["keyOfString", "value"];
 
         let result_received = Srem.run(buffer_mock, &mut database_mock);
@@ -0,0 +1,328 @@
+use std::ops::Bound;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::{
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::{error_severity::ErrorSeverity, ErrorStruct, RArray, RBulkString, RedisType},
+    stream::{HistoryEntry, Stream, StreamEntry, StreamId},
+};
+
+pub mod xack;
+pub mod xadd;
+pub mod xautoclaim;
+pub mod xclaim;
+pub mod xdel;
+pub mod xgroup;
+pub mod xinfo;
+pub mod xlen;
+pub mod xpending;
+pub mod xrange;
+pub mod xread;
+pub mod xreadgroup;
+pub mod xrevrange;
+pub mod xtrim;
+
+/// Strategy of XADD and XTRIM to remove the oldest entries of a stream.
+#[derive(Debug, PartialEq)]
+pub enum TrimStrategy {
+    /// Keeps at most this number of entries.
+    MaxLen(usize),
+    /// Removes the entries with an id lower than this one.
+    MinId(StreamId),
+}
+
+impl TrimStrategy {
+    /// Parses *MAXLEN|MINID [=|~] threshold [LIMIT count]* starting at
+    /// `buffer[*index]`, leaving the index after the last argument parsed.
+    /// Approximate trimming (`~`) trims exactly, as it is allowed to.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The threshold is missing or not valid.
+    /// * LIMIT is used without `~`, or its count is not valid.
+    pub fn parse(buffer: &[String], index: &mut usize) -> Result<Self, ErrorStruct> {
+        let strategy = argument(buffer, *index)?.to_uppercase();
+        *index += 1;
+        let mut approximate = false;
+        match argument(buffer, *index)? {
+            "~" => {
+                approximate = true;
+                *index += 1;
+            }
+            "=" => *index += 1,
+            _ => {}
+        }
+        let threshold = argument(buffer, *index)?;
+        *index += 1;
+        let trim_strategy = match strategy.as_str() {
+            "MAXLEN" => TrimStrategy::MaxLen(parse_count(threshold)?),
+            "MINID" => TrimStrategy::MinId(StreamId::parse(threshold, 0)?),
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        };
+        if let Some(limit) = buffer.get(*index) {
+            if limit.eq_ignore_ascii_case("limit") {
+                if !approximate {
+                    return Err(ErrorStruct::from(redis_messages::syntax_error()));
+                }
+                parse_count(argument(buffer, *index + 1)?)?;
+                *index += 2;
+            }
+        }
+        Ok(trim_strategy)
+    }
+
+    /// Removes the entries of the stream that exceed the threshold.
+    ///
+    /// # Return value
+    /// [usize]: the number of entries removed.
+    pub fn trim(&self, stream: &mut Stream) -> usize {
+        match self {
+            TrimStrategy::MaxLen(max_len) => stream.trim_max_len(*max_len),
+            TrimStrategy::MinId(min_id) => stream.trim_min_id(*min_id),
+        }
+    }
+}
+
+/// Returns the argument at the given position, or a syntax error if it is missing.
+fn argument(buffer: &[String], index: usize) -> Result<&str, ErrorStruct> {
+    buffer
+        .get(index)
+        .map(|argument| argument.as_str())
+        .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))
+}
+
+/// Parses a non-negative integer, such as COUNT or MAXLEN.
+fn parse_count(value: &str) -> Result<usize, ErrorStruct> {
+    value.parse::<usize>().map_err(|_| {
+        ErrorStruct::new(
+            "ERR".to_string(),
+            "value is not an integer or out of range".to_string(),
+        )
+    })
+}
+
+/// Parses the start or the end of XRANGE and XREVRANGE:
+///
+/// * `-` and `+`: the lowest and the greatest ids.
+/// * `(id`: the id, excluded.
+/// * `id`: the id, included. Without sequence, it starts at 0 for the
+///   start and ends at the greatest sequence for the end.
+fn parse_range_bound(value: &str, is_start: bool) -> Result<Bound<StreamId>, ErrorStruct> {
+    let default_seq = if is_start { 0 } else { u64::MAX };
+    match value {
+        "-" if is_start => Ok(Bound::Unbounded),
+        "+" if !is_start => Ok(Bound::Unbounded),
+        "-" => Ok(Bound::Included(StreamId::default())),
+        "+" => Ok(Bound::Included(StreamId::new(u64::MAX, u64::MAX))),
+        _ => match value.strip_prefix('(') {
+            Some(id) => Ok(Bound::Excluded(StreamId::parse(id, default_seq)?)),
+            None => Ok(Bound::Included(StreamId::parse(value, default_seq)?)),
+        },
+    }
+}
+
+/// Parses the optional *COUNT count* at the end of XRANGE and XREVRANGE.
+fn parse_range_count(buffer: &[String]) -> Result<Option<usize>, ErrorStruct> {
+    match buffer {
+        [] => Ok(None),
+        [option, count] if option.eq_ignore_ascii_case("count") => Ok(Some(parse_count(count)?)),
+        _ => Err(ErrorStruct::from(redis_messages::syntax_error())),
+    }
+}
+
+// Xrange and xrevrange aux
+
+/// Returns the entries of the stream stored at the first element of the buffer,
+/// between both bounds, in reverse order if `rev` is true. A missing key is an
+/// empty stream.
+pub fn range_at(
+    mut buffer: Vec<String>,
+    database: &mut Database,
+    name: &str,
+    rev: bool,
+) -> Result<String, ErrorStruct> {
+    if buffer.len() < 3 {
+        return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+            name,
+        )));
+    }
+    let count = parse_range_count(&buffer[3..])?;
+    let (start, end) = if rev {
+        (
+            parse_range_bound(&buffer[2], true)?,
+            parse_range_bound(&buffer[1], false)?,
+        )
+    } else {
+        (
+            parse_range_bound(&buffer[1], true)?,
+            parse_range_bound(&buffer[2], false)?,
+        )
+    };
+    match database.get(&buffer.remove(0)) {
+        Some(TypeSaved::Stream(stream)) => Ok(encode_entries(stream.range(start, end, count, rev))),
+        Some(_) => err_wrongtype!(),
+        None => Ok(encode_entries(Vec::new())),
+    }
+}
+
+/// Encodes the entries as an array of arrays with the id and the fields and values.
+fn encode_entries(entries: Vec<StreamEntry>) -> String {
+    let mut encoded = format!("*{}\r\n", entries.len());
+    for (id, fields) in entries {
+        encoded.push_str("*2\r\n");
+        encoded.push_str(&RBulkString::encode(id.to_string()));
+        encoded.push_str(&RArray::encode(fields));
+    }
+    encoded
+}
+
+/// Encodes the entries read from the history of a consumer, with a nil array
+/// instead of the fields and values of the entries deleted from the stream.
+fn encode_history(entries: Vec<HistoryEntry>) -> String {
+    let mut encoded = format!("*{}\r\n", entries.len());
+    for (id, fields) in entries {
+        encoded.push_str("*2\r\n");
+        encoded.push_str(&RBulkString::encode(id.to_string()));
+        match fields {
+            Some(fields) => encoded.push_str(&RArray::encode(fields)),
+            None => encoded.push_str("*-1\r\n"),
+        }
+    }
+    encoded
+}
+
+/// Encodes the name and the entries read of each stream for XREAD and
+/// XREADGROUP, or a nil array if no stream has been read.
+fn encode_streams(streams: Vec<(String, String)>) -> String {
+    if streams.is_empty() {
+        return "*-1\r\n".to_string();
+    }
+    let mut encoded = format!("*{}\r\n", streams.len());
+    for (key, entries) in streams {
+        encoded.push_str("*2\r\n");
+        encoded.push_str(&RBulkString::encode(key));
+        encoded.push_str(&entries);
+    }
+    encoded
+}
+
+// Consumer groups aux
+
+/// Returns the stream stored at the key, or [None] if it does not exist.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The value stored at **key** is not a stream.
+fn stream_mut<'a>(
+    database: &'a mut Database,
+    key: &str,
+) -> Result<Option<&'a mut Stream>, ErrorStruct> {
+    match database.get_mut(key) {
+        Some(TypeSaved::Stream(stream)) => Ok(Some(stream)),
+        Some(_) => err_wrongtype!(),
+        None => Ok(None),
+    }
+}
+
+/// Returns the stream stored at the key, which has the consumer group.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The value stored at **key** is not a stream.
+/// * The key or the group do not exist.
+fn group_stream_mut<'a>(
+    database: &'a mut Database,
+    key: &str,
+    group: &str,
+) -> Result<&'a mut Stream, ErrorStruct> {
+    stream_mut(database, key)?
+        .filter(|stream| stream.group(group).is_some())
+        .ok_or_else(|| ErrorStruct::from(redis_messages::no_such_group(key, group)))
+}
+
+fn lock(database: &Arc<Mutex<Database>>) -> Result<MutexGuard<'_, Database>, ErrorStruct> {
+    database.lock().map_err(|_| {
+        ErrorStruct::from(redis_messages::poisoned_lock(
+            "database",
+            ErrorSeverity::ShutdownServer,
+        ))
+    })
+}
+
+// Xread and xreadgroup aux
+
+/// Arguments of XREAD and XREADGROUP:
+///
+/// [GROUP group consumer] [COUNT count] [BLOCK milliseconds] [NOACK] STREAMS key [key ...] id [id ...]
+///
+/// GROUP and NOACK are only accepted by XREADGROUP.
+struct ReadArguments {
+    group: Option<(String, String)>,
+    count: Option<usize>,
+    block: Option<Duration>,
+    no_ack: bool,
+    keys: Vec<String>,
+    ids: Vec<String>,
+    /// Position of the first id in the buffer.
+    ids_position: usize,
+}
+
+impl ReadArguments {
+    fn parse(buffer: &[String], name: &str) -> Result<Self, ErrorStruct> {
+        let with_group = name == "xreadgroup";
+        let mut arguments = ReadArguments {
+            group: None,
+            count: None,
+            block: None,
+            no_ack: false,
+            keys: Vec::new(),
+            ids: Vec::new(),
+            ids_position: 0,
+        };
+        let mut index = 0;
+        loop {
+            match argument(buffer, index)?.to_uppercase().as_str() {
+                "COUNT" => {
+                    arguments.count = Some(parse_count(argument(buffer, index + 1)?)?);
+                    index += 2;
+                }
+                "BLOCK" => {
+                    let millis = parse_count(argument(buffer, index + 1)?)?;
+                    arguments.block = Some(Duration::from_millis(millis as u64));
+                    index += 2;
+                }
+                "GROUP" if with_group => {
+                    arguments.group = Some((
+                        argument(buffer, index + 1)?.to_string(),
+                        argument(buffer, index + 2)?.to_string(),
+                    ));
+                    index += 3;
+                }
+                "NOACK" if with_group => {
+                    arguments.no_ack = true;
+                    index += 1;
+                }
+                "STREAMS" => break,
+                _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+            }
+        }
+        if with_group && arguments.group.is_none() {
+            return Err(ErrorStruct::from(redis_messages::syntax_error()));
+        }
+        let streams = &buffer[index + 1..];
+        if streams.is_empty() || !streams.len().is_multiple_of(2) {
+            return Err(ErrorStruct::from(redis_messages::unbalanced_streams(name)));
+        }
+        let half = streams.len() / 2;
+        arguments.keys = streams[..half].to_vec();
+        arguments.ids = streams[half..].to_vec();
+        arguments.ids_position = index + 1 + half;
+        Ok(arguments)
+    }
+}
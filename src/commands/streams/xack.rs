@@ -0,0 +1,82 @@
+use crate::{
+    commands::Runnable,
+    database::Database,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RInteger, RedisType},
+    stream::StreamId,
+};
+use std::sync::{Arc, Mutex};
+
+use super::{lock, stream_mut};
+
+pub struct Xack;
+
+impl Runnable<Arc<Mutex<Database>>> for Xack {
+    /// Acknowledges the entries with the specified ids, removing them from the
+    /// pending entries of the group. A missing **key** or group acknowledges nothing.
+    ///
+    /// XACK key group id [id ...]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the number of entries acknowledged.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a stream.
+    /// * Some id is not valid.
+    /// * Buffer [Vec]<[String]> is received with less than three elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        if buffer.len() < 3 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "xack",
+            )));
+        }
+        let ids = buffer[2..]
+            .iter()
+            .map(|id| StreamId::parse(id, 0))
+            .collect::<Result<Vec<StreamId>, ErrorStruct>>()?;
+        let acknowledged = stream_mut(&mut database, &buffer[0])?
+            .and_then(|stream| stream.group_mut(&buffer[1]))
+            .map(|group| group.ack(&ids))
+            .unwrap_or(0);
+        Ok(RInteger::encode(acknowledged as isize))
+    }
+}
+
+#[cfg(test)]
+mod test_xack {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::commands::streams::{xadd::Xadd, xgroup::Xgroup, xreadgroup::Xreadgroup};
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_xack_removes_the_pending_entries() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        Xadd.run(vec_strings!["key", "1-1", "a", "1"], &mut database)
+            .unwrap();
+        Xgroup
+            .run(vec_strings!["CREATE", "key", "group", "0"], &mut database)
+            .unwrap();
+        Xreadgroup
+            .run(
+                vec_strings!["GROUP", "group", "alice", "STREAMS", "key", ">"],
+                &mut database,
+            )
+            .unwrap();
+
+        let ack = vec_strings!["key", "group", "1-1"];
+        assert_eq!(Xack.run(ack.clone(), &mut database).unwrap(), ":1\r\n");
+        assert_eq!(Xack.run(ack, &mut database).unwrap(), ":0\r\n");
+        let result = Xack.run(vec_strings!["other", "group", "1-1"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+    }
+}
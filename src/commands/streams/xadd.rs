@@ -0,0 +1,194 @@
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable,
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RBulkString, RedisType},
+    stream::Stream,
+};
+use std::sync::{Arc, Mutex};
+
+use super::TrimStrategy;
+
+pub struct Xadd;
+
+impl Runnable<Arc<Mutex<Database>>> for Xadd {
+    /// Appends an entry with the specified fields and values to the stream stored at **key**.
+    /// If **key** does not exist, a new stream is created, unless NOMKSTREAM is given.
+    /// The id can be `*` to be generated from the current time, *ms-\** to generate only
+    /// its sequence number, or an explicit id greater than the last one of the stream.
+    /// With MAXLEN or MINID, the stream is trimmed after adding the entry.
+    ///
+    /// XADD key [NOMKSTREAM] [MAXLEN|MINID [=|~] threshold [LIMIT count]] *|id field value [field value ...]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the id of the added entry,
+    /// or nil if the stream does not exist and NOMKSTREAM was given.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a stream.
+    /// * The id is not valid, or not greater than the last id of the stream.
+    /// * The fields and values are missing or unbalanced.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let arguments = XaddArguments::parse(buffer)?;
+
+        let stream = match database.get_mut(&arguments.key) {
+            Some(TypeSaved::Stream(stream)) => stream,
+            Some(_) => return err_wrongtype!(),
+            None if arguments.no_mkstream => return Ok(RBulkString::encode("(nil)".to_string())),
+            None => {
+                let mut stream = Stream::new();
+                let id = add_and_trim(&mut stream, &arguments)?;
+                database.insert(arguments.key, TypeSaved::Stream(stream));
+                return Ok(RBulkString::encode(id));
+            }
+        };
+        let id = add_and_trim(stream, &arguments)?;
+        Ok(RBulkString::encode(id))
+    }
+}
+
+struct XaddArguments {
+    key: String,
+    no_mkstream: bool,
+    trim_strategy: Option<TrimStrategy>,
+    id: String,
+    fields: Vec<String>,
+}
+
+impl XaddArguments {
+    fn parse(mut buffer: Vec<String>) -> Result<Self, ErrorStruct> {
+        if buffer.len() < 4 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "xadd",
+            )));
+        }
+        let mut no_mkstream = false;
+        let mut trim_strategy = None;
+        let mut index = 1;
+        while index < buffer.len() {
+            match buffer[index].to_uppercase().as_str() {
+                "NOMKSTREAM" => {
+                    no_mkstream = true;
+                    index += 1;
+                }
+                "MAXLEN" | "MINID" => {
+                    trim_strategy = Some(TrimStrategy::parse(&buffer, &mut index)?);
+                }
+                _ => break,
+            }
+        }
+        let fields = buffer.split_off((index + 1).min(buffer.len()));
+        if fields.is_empty() || !fields.len().is_multiple_of(2) {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "xadd",
+            )));
+        }
+        let id = buffer.pop().unwrap_or_default();
+        Ok(XaddArguments {
+            key: buffer.remove(0),
+            no_mkstream,
+            trim_strategy,
+            id,
+            fields,
+        })
+    }
+}
+
+/// Adds the entry to the stream and trims it, returning the id of the entry.
+fn add_and_trim(stream: &mut Stream, arguments: &XaddArguments) -> Result<String, ErrorStruct> {
+    let id = stream.add(&arguments.id, arguments.fields.clone())?;
+    if let Some(trim_strategy) = arguments.trim_strategy.as_ref() {
+        trim_strategy.trim(stream);
+    }
+    Ok(id.to_string())
+}
+
+#[cfg(test)]
+mod test_xadd {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::vec_strings;
+
+    fn database() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        Arc::new(Mutex::new(Database::new(notifier)))
+    }
+
+    #[test]
+    fn test_01_xadd_creates_the_stream_with_explicit_ids() {
+        let mut database = database();
+        let result = Xadd.run(vec_strings!["key", "1-1", "a", "1"], &mut database);
+        assert_eq!(result.unwrap(), "$3\r\n1-1\r\n");
+        let result = Xadd.run(vec_strings!["key", "1-*", "b", "2"], &mut database);
+        assert_eq!(result.unwrap(), "$3\r\n1-2\r\n");
+        let result = Xadd.run(vec_strings!["key", "1-2", "c", "3"], &mut database);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_02_xadd_generates_ids() {
+        let mut database = database();
+        let result = Xadd
+            .run(vec_strings!["key", "*", "a", "1"], &mut database)
+            .unwrap();
+        assert!(result.ends_with("-0\r\n"));
+    }
+
+    #[test]
+    fn test_03_xadd_with_maxlen_trims_the_stream() {
+        let mut database = database();
+        for seq in 1..=5 {
+            Xadd.run(
+                vec_strings!["key", "MAXLEN", "~", "3", format!("1-{}", seq), "n", seq],
+                &mut database,
+            )
+            .unwrap();
+        }
+        let mut database = database.lock().unwrap();
+        match database.get("key") {
+            Some(TypeSaved::Stream(stream)) => assert_eq!(stream.len(), 3),
+            _ => panic!("the key does not hold a stream"),
+        }
+    }
+
+    #[test]
+    fn test_04_xadd_with_nomkstream_does_not_create_the_stream() {
+        let mut database = database();
+        let result = Xadd.run(
+            vec_strings!["key", "NOMKSTREAM", "*", "a", "1"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "$-1\r\n");
+        assert!(!database.lock().unwrap().contains_key("key"));
+    }
+
+    #[test]
+    fn test_05_xadd_with_unbalanced_fields_or_wrong_type() {
+        let mut database = database();
+        assert!(Xadd
+            .run(vec_strings!["key", "*", "a", "1", "b"], &mut database)
+            .is_err());
+        database
+            .lock()
+            .unwrap()
+            .insert("string".to_string(), TypeSaved::String("value".into()));
+        assert!(Xadd
+            .run(vec_strings!["string", "*", "a", "1"], &mut database)
+            .is_err());
+    }
+}
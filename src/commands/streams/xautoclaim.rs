@@ -0,0 +1,151 @@
+use crate::{
+    commands::Runnable,
+    database::Database,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RArray, RBulkString, RedisType},
+    stream::{ClaimOptions, StreamId},
+};
+use std::sync::{Arc, Mutex};
+
+use super::{encode_entries, group_stream_mut, lock, parse_count};
+
+pub struct Xautoclaim;
+
+impl Runnable<Arc<Mutex<Database>>> for Xautoclaim {
+    /// Changes the owner of up to *count* (100 by default) pending entries with an id
+    /// equal or greater than *start* to the consumer, if they have been idle for at
+    /// least *min-idle-time* milliseconds, like XCLAIM. The pending entries deleted
+    /// from the stream are removed.
+    ///
+    /// XAUTOCLAIM key group consumer min-idle-time start [COUNT count] [JUSTID]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray]: the id to use as *start* in the next call, or
+    /// 0-0 if every pending entry has been scanned; the entries claimed, or their
+    /// ids with JUSTID; and the ids of the pending entries deleted from the stream.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a stream.
+    /// * **key** or the group do not exist.
+    /// * The start, the min idle time or the count are not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        if buffer.len() < 5 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "xautoclaim",
+            )));
+        }
+        let (key, group, consumer) = (&buffer[0], &buffer[1], &buffer[2]);
+        let min_idle = parse_count(&buffer[3])? as u64;
+        let start = match buffer[4].as_str() {
+            "-" => StreamId::default(),
+            start => StreamId::parse(start, 0)?,
+        };
+        let (count, just_id) = parse_options(&buffer[5..])?;
+
+        let now = database.clock().unix_millis();
+        let options = ClaimOptions {
+            just_id,
+            ..ClaimOptions::default()
+        };
+        let stream = group_stream_mut(&mut database, key, group)?;
+        let (next, ids) = stream
+            .idle_pending(group, min_idle, start, count, now)
+            .unwrap_or_default();
+        let claimed = stream
+            .claim(group, consumer, min_idle, &ids, &options, now)
+            .unwrap_or_default();
+        let deleted: Vec<String> = ids
+            .iter()
+            .filter(|id| claimed.iter().all(|(claimed, _)| claimed != *id))
+            .map(|id| id.to_string())
+            .collect();
+
+        let claimed = if just_id {
+            RArray::encode(claimed.iter().map(|(id, _)| id.to_string()).collect())
+        } else {
+            encode_entries(claimed)
+        };
+        Ok(format!(
+            "*3\r\n{}{}{}",
+            RBulkString::encode(next.to_string()),
+            claimed,
+            RArray::encode(deleted)
+        ))
+    }
+}
+
+/// Parses [COUNT count] [JUSTID].
+fn parse_options(options: &[String]) -> Result<(usize, bool), ErrorStruct> {
+    let (mut count, mut just_id) = (100, false);
+    let mut index = 0;
+    while index < options.len() {
+        match options[index].to_uppercase().as_str() {
+            "COUNT" if index + 1 < options.len() => {
+                count = parse_count(&options[index + 1])?;
+                index += 1;
+            }
+            "JUSTID" => just_id = true,
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        }
+        index += 1;
+    }
+    Ok((count, just_id))
+}
+
+#[cfg(test)]
+mod test_xautoclaim {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::commands::streams::{
+        xadd::Xadd, xdel::Xdel, xgroup::Xgroup, xreadgroup::Xreadgroup,
+    };
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_xautoclaim_claims_in_pages_and_reports_deleted_entries() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        for seq in 1..=3 {
+            Xadd.run(
+                vec_strings!["key", format!("1-{}", seq), "n", seq],
+                &mut database,
+            )
+            .unwrap();
+        }
+        Xgroup
+            .run(vec_strings!["CREATE", "key", "group", "0"], &mut database)
+            .unwrap();
+        Xreadgroup
+            .run(
+                vec_strings!["GROUP", "group", "alice", "STREAMS", "key", ">"],
+                &mut database,
+            )
+            .unwrap();
+        Xdel.run(vec_strings!["key", "1-2"], &mut database).unwrap();
+
+        let result = Xautoclaim.run(
+            vec_strings!["key", "group", "bob", "0", "-", "COUNT", "2", "JUSTID"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*3\r\n$3\r\n1-3\r\n*1\r\n$3\r\n1-1\r\n*1\r\n$3\r\n1-2\r\n"
+        );
+        let result = Xautoclaim.run(
+            vec_strings!["key", "group", "bob", "0", "1-3", "JUSTID"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*3\r\n$3\r\n0-0\r\n*1\r\n$3\r\n1-3\r\n*0\r\n"
+        );
+    }
+}
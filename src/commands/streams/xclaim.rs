@@ -0,0 +1,198 @@
+use crate::{
+    commands::Runnable,
+    database::Database,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RArray, RedisType},
+    stream::{ClaimOptions, StreamId},
+};
+use std::sync::{Arc, Mutex};
+
+use super::{argument, encode_entries, group_stream_mut, lock, parse_count};
+
+pub struct Xclaim;
+
+impl Runnable<Arc<Mutex<Database>>> for Xclaim {
+    /// Changes the owner of the pending entries with the specified ids to the consumer,
+    /// if they have been idle for at least *min-idle-time* milliseconds. The entries
+    /// deleted from the stream are removed from the pending entries.
+    ///
+    /// XCLAIM key group consumer min-idle-time id [id ...] [IDLE ms] [TIME unix-time-milliseconds]
+    /// [RETRYCOUNT count] [FORCE] [JUSTID] [LASTID lastid]
+    ///
+    /// * IDLE and TIME: set the last delivery, instead of now.
+    /// * RETRYCOUNT: sets the delivery count, instead of incrementing it.
+    /// * FORCE: claims the ids that are not pending too.
+    /// * JUSTID: returns only the ids, without incrementing the delivery count.
+    /// * LASTID: sets the last id delivered to the group, if it is greater.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray]: the entries claimed, or their ids with JUSTID.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a stream.
+    /// * **key** or the group do not exist.
+    /// * Some id, time or count is not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        if buffer.len() < 5 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "xclaim",
+            )));
+        }
+        let (key, group, consumer) = (&buffer[0], &buffer[1], &buffer[2]);
+        let min_idle = parse_count(&buffer[3])? as u64;
+        let mut ids = Vec::new();
+        let mut index = 4;
+        while let Some(Ok(id)) = buffer.get(index).map(|id| StreamId::parse(id, 0)) {
+            ids.push(id);
+            index += 1;
+        }
+        if ids.is_empty() {
+            return Err(ErrorStruct::from(redis_messages::invalid_stream_id()));
+        }
+        let (options, last_id) = parse_options(&buffer, index)?;
+
+        let now = database.clock().unix_millis();
+        let stream = group_stream_mut(&mut database, key, group)?;
+        let claimed = stream
+            .claim(group, consumer, min_idle, &ids, &options, now)
+            .unwrap_or_default();
+        if let (Some(last_id), Some(group)) = (last_id, stream.group_mut(group)) {
+            if last_id > group.last_delivered_id() {
+                group.set_last_delivered_id(last_id);
+            }
+        }
+
+        if options.just_id {
+            Ok(RArray::encode(
+                claimed.iter().map(|(id, _)| id.to_string()).collect(),
+            ))
+        } else {
+            Ok(encode_entries(claimed))
+        }
+    }
+}
+
+/// Parses the options after the ids, starting at `buffer[index]`.
+fn parse_options(
+    buffer: &[String],
+    mut index: usize,
+) -> Result<(ClaimOptions, Option<StreamId>), ErrorStruct> {
+    let mut options = ClaimOptions::default();
+    let mut last_id = None;
+    while index < buffer.len() {
+        match buffer[index].to_uppercase().as_str() {
+            "FORCE" => options.force = true,
+            "JUSTID" => options.just_id = true,
+            "IDLE" => {
+                index += 1;
+                options.idle = Some(parse_count(argument(buffer, index)?)? as u64);
+            }
+            "TIME" => {
+                index += 1;
+                options.time = Some(parse_count(argument(buffer, index)?)? as u64);
+            }
+            "RETRYCOUNT" => {
+                index += 1;
+                options.retry_count = Some(parse_count(argument(buffer, index)?)? as u64);
+            }
+            "LASTID" => {
+                index += 1;
+                last_id = Some(StreamId::parse(argument(buffer, index)?, 0)?);
+            }
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        }
+        index += 1;
+    }
+    Ok((options, last_id))
+}
+
+#[cfg(test)]
+mod test_xclaim {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::commands::streams::{
+        xadd::Xadd, xdel::Xdel, xgroup::Xgroup, xpending::Xpending, xreadgroup::Xreadgroup,
+    };
+    use crate::time_expiration::clock::ManualClock;
+    use crate::vec_strings;
+
+    fn database_with_pending() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Database::new(notifier);
+        // A stopped clock, so the idle times do not depend on the speed of the test
+        database.set_clock(Arc::new(ManualClock::from_unix_millis(1_000_000)));
+        let mut database = Arc::new(Mutex::new(database));
+        for seq in 1..=2 {
+            Xadd.run(
+                vec_strings!["key", format!("1-{}", seq), "n", seq],
+                &mut database,
+            )
+            .unwrap();
+        }
+        Xgroup
+            .run(vec_strings!["CREATE", "key", "group", "0"], &mut database)
+            .unwrap();
+        Xreadgroup
+            .run(
+                vec_strings!["GROUP", "group", "alice", "STREAMS", "key", ">"],
+                &mut database,
+            )
+            .unwrap();
+        database
+    }
+
+    #[test]
+    fn test_01_xclaim_changes_the_owner() {
+        let mut database = database_with_pending();
+        let result = Xclaim.run(
+            vec_strings!["key", "group", "bob", "0", "1-1", "JUSTID"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "*1\r\n$3\r\n1-1\r\n");
+        let result = Xpending.run(
+            vec_strings!["key", "group", "-", "+", "10", "bob"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*1\r\n*4\r\n$3\r\n1-1\r\n$3\r\nbob\r\n:0\r\n:1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_02_xclaim_respects_the_min_idle_time() {
+        let mut database = database_with_pending();
+        let result = Xclaim.run(
+            vec_strings!["key", "group", "bob", "100000", "1-1"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "*0\r\n");
+    }
+
+    #[test]
+    fn test_03_xclaim_increments_the_delivery_count_and_drops_deleted_entries() {
+        let mut database = database_with_pending();
+        Xdel.run(vec_strings!["key", "1-2"], &mut database).unwrap();
+        let result = Xclaim.run(
+            vec_strings!["key", "group", "bob", "0", "1-1", "1-2"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*1\r\n*2\r\n$3\r\n1-1\r\n*2\r\n$1\r\nn\r\n$1\r\n1\r\n"
+        );
+        let result = Xpending.run(vec_strings!["key", "group", "-", "+", "10"], &mut database);
+        assert_eq!(
+            result.unwrap(),
+            "*1\r\n*4\r\n$3\r\n1-1\r\n$3\r\nbob\r\n:0\r\n:2\r\n"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable,
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RInteger, RedisType},
+    stream::StreamId,
+};
+use std::sync::{Arc, Mutex};
+
+pub struct Xdel;
+
+impl Runnable<Arc<Mutex<Database>>> for Xdel {
+    /// Removes the entries with the specified ids from the stream stored at **key**.
+    /// The ids that do not exist are ignored.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the number of entries removed.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a stream.
+    /// * Some id is not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let key = buffer.remove(0);
+        let ids = buffer
+            .iter()
+            .map(|id| StreamId::parse(id, 0))
+            .collect::<Result<Vec<StreamId>, ErrorStruct>>()?;
+        match database.get_mut(&key) {
+            Some(TypeSaved::Stream(stream)) => Ok(RInteger::encode(stream.delete(&ids) as isize)),
+            Some(_) => err_wrongtype!(),
+            None => Ok(RInteger::encode(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_xdel {
+    use super::*;
+    use crate::commands::{create_notifier, streams::xadd::Xadd};
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_xdel_removes_the_existing_ids() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        Xadd.run(vec_strings!["key", "1-1", "a", "1"], &mut database)
+            .unwrap();
+        Xadd.run(vec_strings!["key", "1-2", "b", "2"], &mut database)
+            .unwrap();
+        let result = Xdel.run(vec_strings!["key", "1-1", "5-5"], &mut database);
+        assert_eq!(result.unwrap(), ":1\r\n");
+        assert!(Xdel.run(vec_strings!["key", "id"], &mut database).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    commands::Runnable,
+    database::{Database, TypeSaved},
+    messages::redis_messages,
+    native_types::{ErrorStruct, RInteger, RSimpleString, RedisType},
+    stream::{Stream, StreamId},
+};
+use std::sync::{Arc, Mutex};
+
+use super::{group_stream_mut, lock, parse_count, stream_mut};
+
+pub struct Xgroup;
+
+impl Runnable<Arc<Mutex<Database>>> for Xgroup {
+    /// Manages the consumer groups of a stream:
+    ///
+    /// * XGROUP CREATE key group id|$ [MKSTREAM] [ENTRIESREAD entries-read]: creates a
+    ///   group that delivers the entries after the id. MKSTREAM creates an empty stream
+    ///   if **key** does not exist.
+    /// * XGROUP DESTROY key group: destroys the group.
+    /// * XGROUP CREATECONSUMER key group consumer: creates a consumer in the group.
+    /// * XGROUP DELCONSUMER key group consumer: deletes the consumer and its pending entries.
+    /// * XGROUP SETID key group id|$ [ENTRIESREAD entries-read]: sets the last id
+    ///   delivered to the group.
+    ///
+    /// ENTRIESREAD is accepted and ignored.
+    ///
+    /// # Return value
+    /// * CREATE and SETID: [String] _encoded_ in [RSimpleString]: OK.
+    /// * DESTROY and CREATECONSUMER: [String] _encoded_ in [RInteger]: 1 if the group
+    ///   or the consumer were destroyed or created, 0 otherwise.
+    /// * DELCONSUMER: [String] _encoded_ in [RInteger]: the number of pending
+    ///   entries the consumer had.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The subcommand is unknown or has a wrong number of arguments.
+    /// * The value stored at **key** is not a stream.
+    /// * **key** does not exist, without MKSTREAM.
+    /// * The group already exists, for CREATE, or does not exist, for the others.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        let subcommand = buffer
+            .first()
+            .ok_or_else(|| ErrorStruct::from(redis_messages::wrong_number_args_for("xgroup")))?;
+        let arguments = &buffer[1..];
+        let check_len = |min: usize, max: usize| {
+            if arguments.len() < min || arguments.len() > max {
+                Err(ErrorStruct::from(redis_messages::unknown_subcommand(
+                    subcommand,
+                )))
+            } else {
+                Ok(())
+            }
+        };
+        match subcommand.to_lowercase().as_str() {
+            "create" => {
+                check_len(3, 6)?;
+                create(arguments, &mut database)
+            }
+            "destroy" => {
+                check_len(2, 2)?;
+                let stream = existing_stream(&mut database, &arguments[0])?;
+                Ok(RInteger::encode(
+                    stream.destroy_group(&arguments[1]) as isize
+                ))
+            }
+            "createconsumer" => {
+                check_len(3, 3)?;
+                let now = database.clock().unix_millis();
+                let group = group_stream_mut(&mut database, &arguments[0], &arguments[1])?
+                    .group_mut(&arguments[1])
+                    .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))?;
+                let created = group.create_consumer(&arguments[2], now);
+                Ok(RInteger::encode(created as isize))
+            }
+            "delconsumer" => {
+                check_len(3, 3)?;
+                let group = group_stream_mut(&mut database, &arguments[0], &arguments[1])?
+                    .group_mut(&arguments[1])
+                    .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))?;
+                Ok(RInteger::encode(
+                    group.delete_consumer(&arguments[2]) as isize
+                ))
+            }
+            "setid" => {
+                check_len(3, 5)?;
+                parse_entries_read(&arguments[3..])?;
+                let stream = group_stream_mut(&mut database, &arguments[0], &arguments[1])?;
+                let id = start_id(stream, &arguments[2])?;
+                if let Some(group) = stream.group_mut(&arguments[1]) {
+                    group.set_last_delivered_id(id);
+                }
+                Ok(RSimpleString::encode(redis_messages::ok()))
+            }
+            _ => Err(ErrorStruct::from(redis_messages::unknown_subcommand(
+                subcommand,
+            ))),
+        }
+    }
+}
+
+/// XGROUP CREATE key group id|$ [MKSTREAM] [ENTRIESREAD entries-read]
+fn create(arguments: &[String], database: &mut Database) -> Result<String, ErrorStruct> {
+    let mut options = &arguments[3..];
+    let mut mkstream = false;
+    if let Some(option) = options.first() {
+        if option.eq_ignore_ascii_case("mkstream") {
+            mkstream = true;
+            options = &options[1..];
+        }
+    }
+    parse_entries_read(options)?;
+
+    let key = &arguments[0];
+    if mkstream && stream_mut(database, key)?.is_none() {
+        database.insert(key.to_string(), TypeSaved::Stream(Stream::new()));
+    }
+    let stream = existing_stream(database, key)?;
+    let id = start_id(stream, &arguments[2])?;
+    stream.create_group(&arguments[1], id)?;
+    Ok(RSimpleString::encode(redis_messages::ok()))
+}
+
+/// Returns the stream stored at the key.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The value stored at **key** is not a stream, or does not exist.
+fn existing_stream<'a>(
+    database: &'a mut Database,
+    key: &str,
+) -> Result<&'a mut Stream, ErrorStruct> {
+    stream_mut(database, key)?
+        .ok_or_else(|| ErrorStruct::from(redis_messages::xgroup_requires_key()))
+}
+
+/// Parses the id from which a group delivers the entries, where `$` is the last id of the stream.
+fn start_id(stream: &Stream, id: &str) -> Result<StreamId, ErrorStruct> {
+    if id == "$" {
+        Ok(stream.last_id())
+    } else {
+        StreamId::parse(id, 0)
+    }
+}
+
+/// Checks the optional *ENTRIESREAD entries-read*, which is ignored.
+fn parse_entries_read(options: &[String]) -> Result<(), ErrorStruct> {
+    match options {
+        [] => Ok(()),
+        [option, entries_read] if option.eq_ignore_ascii_case("entriesread") => {
+            parse_count(entries_read).map(|_| ())
+        }
+        _ => Err(ErrorStruct::from(redis_messages::syntax_error())),
+    }
+}
+
+#[cfg(test)]
+mod test_xgroup {
+    use super::*;
+    use crate::commands::{create_notifier, streams::xadd::Xadd};
+    use crate::vec_strings;
+
+    fn database() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        Arc::new(Mutex::new(Database::new(notifier)))
+    }
+
+    #[test]
+    fn test_01_create_requires_the_key_or_mkstream() {
+        let mut database = database();
+        assert!(Xgroup
+            .run(vec_strings!["CREATE", "key", "group", "$"], &mut database)
+            .is_err());
+        let result = Xgroup.run(
+            vec_strings!["CREATE", "key", "group", "$", "MKSTREAM"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "+OK\r\n");
+        let result = Xgroup.run(vec_strings!["CREATE", "key", "group", "0"], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "BUSYGROUP Consumer Group name already exists"
+        );
+    }
+
+    #[test]
+    fn test_02_create_and_delete_consumers() {
+        let mut database = database();
+        Xadd.run(vec_strings!["key", "1-1", "a", "1"], &mut database)
+            .unwrap();
+        Xgroup
+            .run(vec_strings!["CREATE", "key", "group", "0"], &mut database)
+            .unwrap();
+        let create = vec_strings!["CREATECONSUMER", "key", "group", "alice"];
+        assert_eq!(Xgroup.run(create.clone(), &mut database).unwrap(), ":1\r\n");
+        assert_eq!(Xgroup.run(create, &mut database).unwrap(), ":0\r\n");
+        let result = Xgroup.run(
+            vec_strings!["DELCONSUMER", "key", "group", "alice"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), ":0\r\n");
+        assert!(Xgroup
+            .run(
+                vec_strings!["CREATECONSUMER", "key", "other", "alice"],
+                &mut database
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_03_setid_and_destroy() {
+        let mut database = database();
+        Xadd.run(vec_strings!["key", "1-1", "a", "1"], &mut database)
+            .unwrap();
+        Xgroup
+            .run(vec_strings!["CREATE", "key", "group", "0"], &mut database)
+            .unwrap();
+        let result = Xgroup.run(vec_strings!["SETID", "key", "group", "$"], &mut database);
+        assert_eq!(result.unwrap(), "+OK\r\n");
+        {
+            let mut database = database.lock().unwrap();
+            let stream = stream_mut(&mut database, "key").unwrap().unwrap();
+            assert_eq!(
+                stream.group("group").unwrap().last_delivered_id(),
+                StreamId::new(1, 1)
+            );
+        }
+        let destroy = vec_strings!["DESTROY", "key", "group"];
+        assert_eq!(
+            Xgroup.run(destroy.clone(), &mut database).unwrap(),
+            ":1\r\n"
+        );
+        assert_eq!(Xgroup.run(destroy, &mut database).unwrap(), ":0\r\n");
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    commands::Runnable,
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RArray, RBulkString, RInteger, RedisType},
+    stream::{Stream, StreamEntry},
+};
+use std::sync::{Arc, Mutex};
+
+use super::lock;
+
+pub struct Xinfo;
+
+impl Runnable<Arc<Mutex<Database>>> for Xinfo {
+    /// Returns information about a stream and its consumer groups:
+    ///
+    /// * XINFO STREAM key: its length, last generated id, number of groups and
+    ///   first and last entries.
+    /// * XINFO GROUPS key: the name, number of consumers, number of pending entries
+    ///   and last delivered id of each group.
+    /// * XINFO CONSUMERS key group: the name, number of pending entries and
+    ///   milliseconds since it was last seen of each consumer.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray]: the fields and values of the stream, or of
+    /// each group or consumer.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The subcommand is unknown or has a wrong number of arguments.
+    /// * The value stored at **key** is not a stream.
+    /// * **key** or the group do not exist.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        let subcommand = buffer
+            .first()
+            .map(|subcommand| subcommand.to_lowercase())
+            .unwrap_or_default();
+        let arguments = match (subcommand.as_str(), buffer.len()) {
+            ("stream", 2) | ("groups", 2) | ("consumers", 3) => &buffer[1..],
+            _ => {
+                return Err(ErrorStruct::from(redis_messages::unknown_subcommand(
+                    buffer.first().map(|name| name.as_str()).unwrap_or("xinfo"),
+                )))
+            }
+        };
+        let now = database.clock().unix_millis();
+        let stream = match database.get(&arguments[0]) {
+            Some(TypeSaved::Stream(stream)) => stream,
+            Some(_) => return err_wrongtype!(),
+            None => return Err(ErrorStruct::from(redis_messages::no_such_key())),
+        };
+        match subcommand.as_str() {
+            "stream" => Ok(stream_info(stream)),
+            "groups" => Ok(groups_info(stream)),
+            _ => consumers_info(stream, &arguments[0], &arguments[1], now),
+        }
+    }
+}
+
+fn stream_info(stream: &Stream) -> String {
+    format!(
+        "*10\r\n{}{}{}{}{}{}{}{}{}{}",
+        RBulkString::encode("length".to_string()),
+        RInteger::encode(stream.len() as isize),
+        RBulkString::encode("last-generated-id".to_string()),
+        RBulkString::encode(stream.last_id().to_string()),
+        RBulkString::encode("groups".to_string()),
+        RInteger::encode(stream.groups().len() as isize),
+        RBulkString::encode("first-entry".to_string()),
+        encode_entry(stream.first_entry()),
+        RBulkString::encode("last-entry".to_string()),
+        encode_entry(stream.last_entry()),
+    )
+}
+
+fn groups_info(stream: &Stream) -> String {
+    let mut encoded = format!("*{}\r\n", stream.groups().len());
+    for (name, group) in stream.groups() {
+        encoded.push_str(&format!(
+            "*8\r\n{}{}{}{}{}{}{}{}",
+            RBulkString::encode("name".to_string()),
+            RBulkString::encode(name.to_string()),
+            RBulkString::encode("consumers".to_string()),
+            RInteger::encode(group.consumers().len() as isize),
+            RBulkString::encode("pending".to_string()),
+            RInteger::encode(group.pending().len() as isize),
+            RBulkString::encode("last-delivered-id".to_string()),
+            RBulkString::encode(group.last_delivered_id().to_string()),
+        ));
+    }
+    encoded
+}
+
+fn consumers_info(
+    stream: &Stream,
+    key: &str,
+    group: &str,
+    now: u64,
+) -> Result<String, ErrorStruct> {
+    let group = stream
+        .group(group)
+        .ok_or_else(|| ErrorStruct::from(redis_messages::no_such_group(key, group)))?;
+    let mut encoded = format!("*{}\r\n", group.consumers().len());
+    for (name, seen_time) in group.consumers() {
+        encoded.push_str(&format!(
+            "*6\r\n{}{}{}{}{}{}",
+            RBulkString::encode("name".to_string()),
+            RBulkString::encode(name.to_string()),
+            RBulkString::encode("pending".to_string()),
+            RInteger::encode(group.consumer_pending(name) as isize),
+            RBulkString::encode("idle".to_string()),
+            RInteger::encode(now.saturating_sub(*seen_time) as isize),
+        ));
+    }
+    Ok(encoded)
+}
+
+/// Encodes the entry as an array with its id and its fields and values, or nil.
+fn encode_entry(entry: Option<StreamEntry>) -> String {
+    match entry {
+        Some((id, fields)) => format!(
+            "*2\r\n{}{}",
+            RBulkString::encode(id.to_string()),
+            RArray::encode(fields)
+        ),
+        None => RBulkString::encode(redis_messages::nil()),
+    }
+}
+
+#[cfg(test)]
+mod test_xinfo {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::commands::streams::{xadd::Xadd, xgroup::Xgroup, xreadgroup::Xreadgroup};
+    use crate::vec_strings;
+
+    fn database_with_group() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        Xadd.run(vec_strings!["key", "1-1", "a", "1"], &mut database)
+            .unwrap();
+        Xgroup
+            .run(vec_strings!["CREATE", "key", "group", "0"], &mut database)
+            .unwrap();
+        Xreadgroup
+            .run(
+                vec_strings!["GROUP", "group", "alice", "STREAMS", "key", ">"],
+                &mut database,
+            )
+            .unwrap();
+        database
+    }
+
+    #[test]
+    fn test_01_xinfo_stream() {
+        let mut database = database_with_group();
+        let result = Xinfo.run(vec_strings!["STREAM", "key"], &mut database);
+        assert_eq!(
+            result.unwrap(),
+            "*10\r\n$6\r\nlength\r\n:1\r\n$17\r\nlast-generated-id\r\n$3\r\n1-1\r\n\
+            $6\r\ngroups\r\n:1\r\n\
+            $11\r\nfirst-entry\r\n*2\r\n$3\r\n1-1\r\n*2\r\n$1\r\na\r\n$1\r\n1\r\n\
+            $10\r\nlast-entry\r\n*2\r\n$3\r\n1-1\r\n*2\r\n$1\r\na\r\n$1\r\n1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_02_xinfo_groups() {
+        let mut database = database_with_group();
+        let result = Xinfo.run(vec_strings!["GROUPS", "key"], &mut database);
+        assert_eq!(
+            result.unwrap(),
+            "*1\r\n*8\r\n$4\r\nname\r\n$5\r\ngroup\r\n$9\r\nconsumers\r\n:1\r\n\
+            $7\r\npending\r\n:1\r\n$17\r\nlast-delivered-id\r\n$3\r\n1-1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_03_xinfo_consumers() {
+        let mut database = database_with_group();
+        let result = Xinfo
+            .run(vec_strings!["CONSUMERS", "key", "group"], &mut database)
+            .unwrap();
+        assert!(result.starts_with(
+            "*1\r\n*6\r\n$4\r\nname\r\n$5\r\nalice\r\n$7\r\npending\r\n:1\r\n$4\r\nidle\r\n"
+        ));
+        assert!(Xinfo
+            .run(vec_strings!["CONSUMERS", "key", "other"], &mut database)
+            .is_err());
+        assert!(Xinfo
+            .run(vec_strings!["STREAM", "missing"], &mut database)
+            .is_err());
+    }
+}
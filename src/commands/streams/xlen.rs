@@ -0,0 +1,73 @@
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable,
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RInteger, RedisType},
+};
+use std::sync::{Arc, Mutex};
+
+pub struct Xlen;
+
+impl Runnable<Arc<Mutex<Database>>> for Xlen {
+    /// Returns the number of entries of the stream stored at **key**.
+    /// If **key** does not exist, 0 is returned.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the number of entries of the stream.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a stream.
+    /// * Buffer [Vec]<[String]> is not received with exactly one element.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        if buffer.len() != 1 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "xlen",
+            )));
+        }
+        match database.get(&buffer[0]) {
+            Some(TypeSaved::Stream(stream)) => Ok(RInteger::encode(stream.len() as isize)),
+            Some(_) => err_wrongtype!(),
+            None => Ok(RInteger::encode(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_xlen {
+    use super::*;
+    use crate::commands::{create_notifier, streams::xadd::Xadd};
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_xlen_counts_the_entries() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        assert_eq!(
+            Xlen.run(vec_strings!["key"], &mut database).unwrap(),
+            ":0\r\n"
+        );
+        Xadd.run(vec_strings!["key", "*", "a", "1"], &mut database)
+            .unwrap();
+        Xadd.run(vec_strings!["key", "*", "b", "2"], &mut database)
+            .unwrap();
+        assert_eq!(
+            Xlen.run(vec_strings!["key"], &mut database).unwrap(),
+            ":2\r\n"
+        );
+    }
+}
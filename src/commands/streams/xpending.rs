@@ -0,0 +1,197 @@
+use crate::{
+    commands::Runnable,
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RBulkString, RInteger, RedisType},
+    stream::{idle_time, ConsumerGroup},
+};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use super::{lock, parse_count, parse_range_bound};
+
+pub struct Xpending;
+
+impl Runnable<Arc<Mutex<Database>>> for Xpending {
+    /// Returns the entries delivered to the consumers of a group and not acknowledged yet.
+    ///
+    /// * XPENDING key group: a summary with the number of pending entries, the lowest
+    ///   and the greatest ids, and the number of pending entries of each consumer.
+    /// * XPENDING key group [IDLE min-idle-time] start end count [consumer]: the id,
+    ///   consumer, milliseconds since the last delivery and delivery count of the
+    ///   pending entries between *start* and *end*.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray](crate::native_types::array::RArray): the
+    /// summary or the pending entries.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a stream.
+    /// * **key** or the group do not exist.
+    /// * The idle time, the start, the end or the count are not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        if buffer.len() < 2 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "xpending",
+            )));
+        }
+        let (key, group) = (&buffer[0], &buffer[1]);
+        let now = database.clock().unix_millis();
+        let group = match database.get(key) {
+            Some(TypeSaved::Stream(stream)) => stream.group(group),
+            Some(_) => return err_wrongtype!(),
+            None => None,
+        }
+        .ok_or_else(|| ErrorStruct::from(redis_messages::no_such_group(key, group)))?;
+
+        if buffer.len() == 2 {
+            Ok(summary(group))
+        } else {
+            extended(group, &buffer[2..], now)
+        }
+    }
+}
+
+fn summary(group: &ConsumerGroup) -> String {
+    let pending = group.pending();
+    let (first, last) = match (pending.keys().next(), pending.keys().next_back()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return "*4\r\n:0\r\n$-1\r\n$-1\r\n*-1\r\n".to_string(),
+    };
+    let mut consumers: BTreeMap<&str, usize> = BTreeMap::new();
+    for entry in pending.values() {
+        *consumers.entry(&entry.consumer).or_default() += 1;
+    }
+    let mut encoded = format!(
+        "*4\r\n{}{}{}*{}\r\n",
+        RInteger::encode(pending.len() as isize),
+        RBulkString::encode(first.to_string()),
+        RBulkString::encode(last.to_string()),
+        consumers.len()
+    );
+    for (consumer, count) in consumers {
+        encoded.push_str("*2\r\n");
+        encoded.push_str(&RBulkString::encode(consumer.to_string()));
+        encoded.push_str(&RBulkString::encode(count.to_string()));
+    }
+    encoded
+}
+
+/// [IDLE min-idle-time] start end count [consumer]
+fn extended(
+    group: &ConsumerGroup,
+    mut arguments: &[String],
+    now: u64,
+) -> Result<String, ErrorStruct> {
+    let mut min_idle = 0;
+    if arguments[0].eq_ignore_ascii_case("idle") {
+        min_idle = parse_count(arguments.get(1).map(|idle| idle.as_str()).unwrap_or(""))? as u64;
+        arguments = &arguments[2..];
+    }
+    if arguments.len() < 3 || arguments.len() > 4 {
+        return Err(ErrorStruct::from(redis_messages::syntax_error()));
+    }
+    let start = parse_range_bound(&arguments[0], true)?;
+    let end = parse_range_bound(&arguments[1], false)?;
+    let count = parse_count(&arguments[2])?;
+    let consumer = arguments.get(3);
+
+    let entries: Vec<String> = group
+        .pending_range(start, end)
+        .into_iter()
+        .filter(|(_, entry)| consumer.is_none_or(|consumer| *consumer == entry.consumer))
+        .filter(|(_, entry)| idle_time(entry, now) >= min_idle)
+        .take(count)
+        .map(|(id, entry)| {
+            format!(
+                "*4\r\n{}{}{}{}",
+                RBulkString::encode(id.to_string()),
+                RBulkString::encode(entry.consumer.to_string()),
+                RInteger::encode(idle_time(&entry, now) as isize),
+                RInteger::encode(entry.delivery_count as isize)
+            )
+        })
+        .collect();
+    Ok(format!("*{}\r\n{}", entries.len(), entries.concat()))
+}
+
+#[cfg(test)]
+mod test_xpending {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::commands::streams::{xadd::Xadd, xgroup::Xgroup, xreadgroup::Xreadgroup};
+    use crate::time_expiration::clock::ManualClock;
+    use crate::vec_strings;
+
+    fn database_with_pending() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Database::new(notifier);
+        // A stopped clock, so the idle times do not depend on the speed of the test
+        database.set_clock(Arc::new(ManualClock::from_unix_millis(1_000_000)));
+        let mut database = Arc::new(Mutex::new(database));
+        for seq in 1..=3 {
+            Xadd.run(
+                vec_strings!["key", format!("1-{}", seq), "n", seq],
+                &mut database,
+            )
+            .unwrap();
+        }
+        Xgroup
+            .run(vec_strings!["CREATE", "key", "group", "0"], &mut database)
+            .unwrap();
+        Xreadgroup
+            .run(
+                vec_strings!["GROUP", "group", "alice", "COUNT", "2", "STREAMS", "key", ">"],
+                &mut database,
+            )
+            .unwrap();
+        Xreadgroup
+            .run(
+                vec_strings!["GROUP", "group", "bob", "STREAMS", "key", ">"],
+                &mut database,
+            )
+            .unwrap();
+        database
+    }
+
+    #[test]
+    fn test_01_summary() {
+        let mut database = database_with_pending();
+        let result = Xpending.run(vec_strings!["key", "group"], &mut database);
+        assert_eq!(
+            result.unwrap(),
+            "*4\r\n:3\r\n$3\r\n1-1\r\n$3\r\n1-3\r\n*2\r\n\
+            *2\r\n$5\r\nalice\r\n$1\r\n2\r\n*2\r\n$3\r\nbob\r\n$1\r\n1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_02_extended_by_consumer() {
+        let mut database = database_with_pending();
+        let result = Xpending.run(
+            vec_strings!["key", "group", "-", "+", "10", "bob"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*1\r\n*4\r\n$3\r\n1-3\r\n$3\r\nbob\r\n:0\r\n:1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_03_missing_group() {
+        let mut database = database_with_pending();
+        assert!(Xpending
+            .run(vec_strings!["key", "other"], &mut database)
+            .is_err());
+    }
+}
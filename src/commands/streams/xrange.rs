@@ -0,0 +1,94 @@
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable, database::Database, messages::redis_messages, native_types::ErrorStruct,
+};
+use std::sync::{Arc, Mutex};
+
+use super::range_at;
+
+pub struct Xrange;
+
+impl Runnable<Arc<Mutex<Database>>> for Xrange {
+    /// Returns the entries of the stream stored at **key** with an id between
+    /// *start* and *end*, in order. `-` and `+` are the lowest and the greatest
+    /// ids, and an id preceded by `(` is excluded from the range.
+    ///
+    /// XRANGE key start end [COUNT count]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray](crate::native_types::array::RArray): the entries,
+    /// each one as an array with its id and an array with its fields and values.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a stream.
+    /// * The start, the end or the count are not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        range_at(buffer, &mut database, "xrange", false)
+    }
+}
+
+#[cfg(test)]
+mod test_xrange {
+    use super::*;
+    use crate::commands::{create_notifier, streams::xadd::Xadd};
+    use crate::vec_strings;
+
+    fn database_with_entries() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        for seq in 1..=3 {
+            Xadd.run(
+                vec_strings!["key", format!("1-{}", seq), "n", seq],
+                &mut database,
+            )
+            .unwrap();
+        }
+        database
+    }
+
+    #[test]
+    fn test_01_xrange_returns_every_entry() {
+        let mut database = database_with_entries();
+        let result = Xrange.run(vec_strings!["key", "-", "+"], &mut database);
+        assert_eq!(
+            result.unwrap(),
+            "*3\r\n\
+            *2\r\n$3\r\n1-1\r\n*2\r\n$1\r\nn\r\n$1\r\n1\r\n\
+            *2\r\n$3\r\n1-2\r\n*2\r\n$1\r\nn\r\n$1\r\n2\r\n\
+            *2\r\n$3\r\n1-3\r\n*2\r\n$1\r\nn\r\n$1\r\n3\r\n"
+        );
+    }
+
+    #[test]
+    fn test_02_xrange_with_exclusive_start_and_count() {
+        let mut database = database_with_entries();
+        let result = Xrange.run(
+            vec_strings!["key", "(1-1", "+", "COUNT", "1"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*1\r\n*2\r\n$3\r\n1-2\r\n*2\r\n$1\r\nn\r\n$1\r\n2\r\n"
+        );
+    }
+
+    #[test]
+    fn test_03_xrange_of_missing_key_is_empty() {
+        let mut database = database_with_entries();
+        let result = Xrange.run(vec_strings!["other", "-", "+"], &mut database);
+        assert_eq!(result.unwrap(), "*0\r\n");
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{
+    commands::Runnable,
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::ErrorStruct,
+    stream::StreamId,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::{encode_entries, encode_streams, lock, ReadArguments};
+
+pub struct Xread;
+
+impl Runnable<Arc<Mutex<Database>>> for Xread {
+    /// Returns the entries with an id greater than the specified one of each stream,
+    /// up to *count* entries per stream. The id `$` is the last id of the stream.
+    ///
+    /// XREAD [COUNT count] [BLOCK milliseconds] STREAMS key [key ...] id [id ...]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray](crate::native_types::array::RArray): an array
+    /// with the name and the entries of each stream with new entries, or a nil array
+    /// if there are none, also after BLOCK times out.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at some **key** is not a stream.
+    /// * Some id, the count or the timeout are not valid.
+    /// * The keys and the ids are unbalanced.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let arguments = ReadArguments::parse(&buffer, "xread")?;
+        let mut database = lock(database)?;
+
+        let mut streams = Vec::new();
+        for (key, id) in arguments.keys.iter().zip(arguments.ids.iter()) {
+            let entries = match database.get(key) {
+                Some(TypeSaved::Stream(stream)) => {
+                    let id = match id.as_str() {
+                        "$" => stream.last_id(),
+                        id => StreamId::parse(id, 0)?,
+                    };
+                    stream.after(id, arguments.count)
+                }
+                Some(_) => return err_wrongtype!(),
+                None => continue,
+            };
+            if !entries.is_empty() {
+                streams.push((key.to_string(), encode_entries(entries)));
+            }
+        }
+        Ok(encode_streams(streams))
+    }
+
+    /// With BLOCK, waits while none of the streams has entries with an id greater
+    /// than the specified one. Each `$` is replaced by the last id of its stream,
+    /// so only the entries added while waiting are returned.
+    /// The streams are looked up without registering a read, since this check
+    /// is repeated while waiting: the read is counted when the command runs.
+    fn blocking_time(
+        &self,
+        buffer: &mut Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<Option<Duration>, ErrorStruct> {
+        let arguments = ReadArguments::parse(buffer, "xread")?;
+        let timeout = match arguments.block {
+            Some(timeout) => timeout,
+            None => return Ok(None),
+        };
+        let mut database = lock(database)?;
+
+        for (position, (key, id)) in arguments.keys.iter().zip(arguments.ids.iter()).enumerate() {
+            let stream = match database.object(key).map(|(_, value)| value) {
+                Some(TypeSaved::Stream(stream)) => Some(stream),
+                Some(_) => return err_wrongtype!(),
+                None => None,
+            };
+            if id == "$" {
+                let last_id = stream.map(|stream| stream.last_id()).unwrap_or_default();
+                buffer[arguments.ids_position + position] = last_id.to_string();
+            } else if let Some(stream) = stream {
+                if !stream.after(StreamId::parse(id, 0)?, Some(1)).is_empty() {
+                    return Ok(None);
+                }
+            }
+        }
+        Ok(Some(timeout))
+    }
+}
+
+#[cfg(test)]
+mod test_xread {
+    use super::*;
+    use crate::commands::{create_notifier, streams::xadd::Xadd};
+    use crate::logs::server_stats::ServerStats;
+    use crate::vec_strings;
+
+    fn database_with_entries() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        for seq in 1..=3 {
+            Xadd.run(
+                vec_strings!["key", format!("1-{}", seq), "n", seq],
+                &mut database,
+            )
+            .unwrap();
+        }
+        database
+    }
+
+    #[test]
+    fn test_01_xread_returns_the_entries_after_the_id() {
+        let mut database = database_with_entries();
+        let result = Xread.run(
+            vec_strings!["COUNT", "1", "STREAMS", "key", "other", "1-1", "0"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*1\r\n*2\r\n$3\r\nkey\r\n*1\r\n*2\r\n$3\r\n1-2\r\n*2\r\n$1\r\nn\r\n$1\r\n2\r\n"
+        );
+    }
+
+    #[test]
+    fn test_02_xread_without_new_entries_returns_nil() {
+        let mut database = database_with_entries();
+        let result = Xread.run(vec_strings!["STREAMS", "key", "$"], &mut database);
+        assert_eq!(result.unwrap(), "*-1\r\n");
+    }
+
+    #[test]
+    fn test_03_xread_unbalanced_streams() {
+        let mut database = database_with_entries();
+        let result = Xread.run(vec_strings!["STREAMS", "key", "other", "0"], &mut database);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_04_xread_block_replaces_the_last_id() {
+        let mut database = database_with_entries();
+        let mut buffer = vec_strings!["BLOCK", "0", "STREAMS", "key", "$"];
+        let timeout = Xread.blocking_time(&mut buffer, &mut database).unwrap();
+        assert_eq!(timeout, Some(Duration::ZERO));
+        assert_eq!(buffer, vec_strings!["BLOCK", "0", "STREAMS", "key", "1-3"]);
+
+        Xadd.run(vec_strings!["key", "1-4", "n", "4"], &mut database)
+            .unwrap();
+        let timeout = Xread.blocking_time(&mut buffer, &mut database).unwrap();
+        assert_eq!(timeout, None);
+    }
+
+    #[test]
+    fn test_05_xread_without_block_does_not_wait() {
+        let mut database = database_with_entries();
+        let mut buffer = vec_strings!["STREAMS", "key", "$"];
+        assert_eq!(
+            Xread.blocking_time(&mut buffer, &mut database).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_06_waiting_does_not_count_as_a_read() {
+        let mut database = database_with_entries();
+        let stats = ServerStats::default();
+        database.lock().unwrap().set_server_stats(stats.clone());
+        let mut buffer = vec_strings!["BLOCK", "0", "STREAMS", "key", "other", "$", "$"];
+        for _ in 0..3 {
+            Xread.blocking_time(&mut buffer, &mut database).unwrap();
+        }
+        let snapshot = stats.snapshot().unwrap();
+        assert_eq!((snapshot.keyspace_hits, snapshot.keyspace_misses), (0, 0));
+
+        Xread.run(buffer, &mut database).unwrap();
+        let snapshot = stats.snapshot().unwrap();
+        assert_eq!((snapshot.keyspace_hits, snapshot.keyspace_misses), (1, 1));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{
+    commands::Runnable,
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::ErrorStruct,
+    stream::StreamId,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::{encode_history, encode_streams, group_stream_mut, lock, ReadArguments};
+
+pub struct Xreadgroup;
+
+impl Runnable<Arc<Mutex<Database>>> for Xreadgroup {
+    /// Reads the streams as a consumer of a group. The consumer is created if needed.
+    ///
+    /// * With the id `>`, returns the entries never delivered to the group, which
+    ///   become pending for the consumer until they are acknowledged, unless NOACK is given.
+    /// * With any other id, returns the pending entries of the consumer with a greater id,
+    ///   incrementing their delivery count. The entries deleted from the stream are
+    ///   returned with a nil array.
+    ///
+    /// XREADGROUP GROUP group consumer [COUNT count] [BLOCK milliseconds] [NOACK] STREAMS key [key ...] id [id ...]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray](crate::native_types::array::RArray): an array
+    /// with the name and the entries of each stream read, or a nil array if there
+    /// are none, also after BLOCK times out.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at some **key** is not a stream.
+    /// * Some **key** or the group do not exist.
+    /// * Some id, the count or the timeout are not valid.
+    /// * The keys and the ids are unbalanced.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let arguments = ReadArguments::parse(&buffer, "xreadgroup")?;
+        let (group, consumer) = arguments.group.clone().unwrap_or_default();
+        let mut database = lock(database)?;
+        let now = database.clock().unix_millis();
+
+        let mut streams = Vec::new();
+        for (key, id) in arguments.keys.iter().zip(arguments.ids.iter()) {
+            let start = match id.as_str() {
+                ">" => None,
+                id => Some(StreamId::parse(id, 0)?),
+            };
+            let entries = group_stream_mut(&mut database, key, &group)?
+                .read_group(
+                    &group,
+                    &consumer,
+                    start,
+                    arguments.count,
+                    arguments.no_ack,
+                    now,
+                )
+                .unwrap_or_default();
+            if start.is_some() || !entries.is_empty() {
+                streams.push((key.to_string(), encode_history(entries)));
+            }
+        }
+        Ok(encode_streams(streams))
+    }
+
+    /// With BLOCK, waits while none of the streams read with `>` has entries
+    /// not delivered to the group. Reading the pending entries never waits.
+    /// The streams are looked up without registering a read, as in XREAD.
+    fn blocking_time(
+        &self,
+        buffer: &mut Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<Option<Duration>, ErrorStruct> {
+        let arguments = ReadArguments::parse(buffer, "xreadgroup")?;
+        let timeout = match arguments.block {
+            Some(timeout) => timeout,
+            None => return Ok(None),
+        };
+        let (group, _) = arguments.group.unwrap_or_default();
+        let mut database = lock(database)?;
+
+        for (key, id) in arguments.keys.iter().zip(arguments.ids.iter()) {
+            if id != ">" {
+                return Ok(None);
+            }
+            match database.object(key).map(|(_, value)| value) {
+                Some(TypeSaved::Stream(stream)) if stream.group(&group).is_some() => {
+                    if stream.has_undelivered(&group) {
+                        return Ok(None);
+                    }
+                }
+                Some(TypeSaved::Stream(_)) | None => {
+                    return Err(ErrorStruct::from(redis_messages::no_such_group(
+                        key, &group,
+                    )))
+                }
+                Some(_) => return err_wrongtype!(),
+            }
+        }
+        Ok(Some(timeout))
+    }
+}
+
+#[cfg(test)]
+mod test_xreadgroup {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::commands::streams::{xadd::Xadd, xgroup::Xgroup};
+    use crate::vec_strings;
+
+    fn database_with_group() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        for seq in 1..=2 {
+            Xadd.run(
+                vec_strings!["key", format!("1-{}", seq), "n", seq],
+                &mut database,
+            )
+            .unwrap();
+        }
+        Xgroup
+            .run(vec_strings!["CREATE", "key", "group", "0"], &mut database)
+            .unwrap();
+        database
+    }
+
+    #[test]
+    fn test_01_new_entries_are_delivered_once() {
+        let mut database = database_with_group();
+        let read = vec_strings!["GROUP", "group", "alice", "COUNT", "1", "STREAMS", "key", ">"];
+        assert_eq!(
+            Xreadgroup.run(read.clone(), &mut database).unwrap(),
+            "*1\r\n*2\r\n$3\r\nkey\r\n*1\r\n*2\r\n$3\r\n1-1\r\n*2\r\n$1\r\nn\r\n$1\r\n1\r\n"
+        );
+        assert_eq!(
+            Xreadgroup.run(read.clone(), &mut database).unwrap(),
+            "*1\r\n*2\r\n$3\r\nkey\r\n*1\r\n*2\r\n$3\r\n1-2\r\n*2\r\n$1\r\nn\r\n$1\r\n2\r\n"
+        );
+        assert_eq!(Xreadgroup.run(read, &mut database).unwrap(), "*-1\r\n");
+    }
+
+    #[test]
+    fn test_02_history_returns_the_pending_entries_of_the_consumer() {
+        let mut database = database_with_group();
+        Xreadgroup
+            .run(
+                vec_strings!["GROUP", "group", "alice", "STREAMS", "key", ">"],
+                &mut database,
+            )
+            .unwrap();
+        let result = Xreadgroup.run(
+            vec_strings!["GROUP", "group", "bob", "STREAMS", "key", "0"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "*1\r\n*2\r\n$3\r\nkey\r\n*0\r\n");
+        let result = Xreadgroup.run(
+            vec_strings!["GROUP", "group", "alice", "STREAMS", "key", "1-1"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*1\r\n*2\r\n$3\r\nkey\r\n*1\r\n*2\r\n$3\r\n1-2\r\n*2\r\n$1\r\nn\r\n$1\r\n2\r\n"
+        );
+    }
+
+    #[test]
+    fn test_03_missing_group() {
+        let mut database = database_with_group();
+        let result = Xreadgroup.run(
+            vec_strings!["GROUP", "other", "alice", "STREAMS", "key", ">"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "NOGROUP No such key 'key' or consumer group 'other'"
+        );
+    }
+
+    #[test]
+    fn test_04_block_waits_for_undelivered_entries() {
+        let mut database = database_with_group();
+        let mut buffer =
+            vec_strings!["GROUP", "group", "alice", "BLOCK", "0", "STREAMS", "key", ">"];
+        assert_eq!(
+            Xreadgroup
+                .blocking_time(&mut buffer, &mut database)
+                .unwrap(),
+            None
+        );
+        Xreadgroup.run(buffer.clone(), &mut database).unwrap();
+        assert_eq!(
+            Xreadgroup
+                .blocking_time(&mut buffer, &mut database)
+                .unwrap(),
+            Some(Duration::ZERO)
+        );
+    }
+}
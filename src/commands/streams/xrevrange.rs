@@ -0,0 +1,67 @@
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable, database::Database, messages::redis_messages, native_types::ErrorStruct,
+};
+use std::sync::{Arc, Mutex};
+
+use super::range_at;
+
+pub struct Xrevrange;
+
+impl Runnable<Arc<Mutex<Database>>> for Xrevrange {
+    /// Like [Xrange](crate::commands::streams::xrange::Xrange), but returns the
+    /// entries in reverse order, so the *end* is given before the *start*.
+    ///
+    /// XREVRANGE key end start [COUNT count]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray](crate::native_types::array::RArray): the entries,
+    /// each one as an array with its id and an array with its fields and values.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a stream.
+    /// * The start, the end or the count are not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        range_at(buffer, &mut database, "xrevrange", true)
+    }
+}
+
+#[cfg(test)]
+mod test_xrevrange {
+    use super::*;
+    use crate::commands::{create_notifier, streams::xadd::Xadd};
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_xrevrange_returns_the_last_entries_first() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        for seq in 1..=3 {
+            Xadd.run(
+                vec_strings!["key", format!("1-{}", seq), "n", seq],
+                &mut database,
+            )
+            .unwrap();
+        }
+        let result = Xrevrange.run(vec_strings!["key", "+", "-", "COUNT", "2"], &mut database);
+        assert_eq!(
+            result.unwrap(),
+            "*2\r\n\
+            *2\r\n$3\r\n1-3\r\n*2\r\n$1\r\nn\r\n$1\r\n3\r\n\
+            *2\r\n$3\r\n1-2\r\n*2\r\n$1\r\nn\r\n$1\r\n2\r\n"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable,
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RInteger, RedisType},
+};
+use std::sync::{Arc, Mutex};
+
+use super::TrimStrategy;
+
+pub struct Xtrim;
+
+impl Runnable<Arc<Mutex<Database>>> for Xtrim {
+    /// Trims the stream stored at **key**, removing its oldest entries:
+    ///
+    /// * MAXLEN: until the stream has at most *threshold* entries.
+    /// * MINID: the entries with an id lower than *threshold*.
+    ///
+    /// XTRIM key MAXLEN|MINID [=|~] threshold [LIMIT count]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the number of entries removed.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value stored at **key** is not a stream.
+    /// * The strategy or the threshold are not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        if buffer.len() < 3 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "xtrim",
+            )));
+        }
+        let mut index = 1;
+        let trim_strategy = TrimStrategy::parse(&buffer, &mut index)?;
+        if index != buffer.len() {
+            return Err(ErrorStruct::from(redis_messages::syntax_error()));
+        }
+        match database.get_mut(&buffer[0]) {
+            Some(TypeSaved::Stream(stream)) => {
+                Ok(RInteger::encode(trim_strategy.trim(stream) as isize))
+            }
+            Some(_) => err_wrongtype!(),
+            None => Ok(RInteger::encode(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_xtrim {
+    use super::*;
+    use crate::commands::{create_notifier, streams::xadd::Xadd};
+    use crate::vec_strings;
+
+    fn database_with_entries() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        for seq in 1..=5 {
+            Xadd.run(
+                vec_strings!["key", format!("1-{}", seq), "n", seq],
+                &mut database,
+            )
+            .unwrap();
+        }
+        database
+    }
+
+    #[test]
+    fn test_01_xtrim_maxlen() {
+        let mut database = database_with_entries();
+        let result = Xtrim.run(vec_strings!["key", "MAXLEN", "2"], &mut database);
+        assert_eq!(result.unwrap(), ":3\r\n");
+    }
+
+    #[test]
+    fn test_02_xtrim_minid() {
+        let mut database = database_with_entries();
+        let result = Xtrim.run(vec_strings!["key", "MINID", "=", "1-3"], &mut database);
+        assert_eq!(result.unwrap(), ":2\r\n");
+    }
+
+    #[test]
+    fn test_03_xtrim_limit_requires_approximate_trimming() {
+        let mut database = database_with_entries();
+        let result = Xtrim.run(
+            vec_strings!["key", "MAXLEN", "2", "LIMIT", "10"],
+            &mut database,
+        );
+        assert!(result.is_err());
+        let result = Xtrim.run(
+            vec_strings!["key", "MAXLEN", "~", "2", "LIMIT", "10"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), ":3\r\n");
+    }
+}
@@ -1,5 +1,5 @@
 use super::{no_more_values, pop_value};
-use crate::commands::{check_empty, Runnable};
+use crate::commands::Runnable;
 use crate::database::{Database, TypeSaved};
 use crate::messages::redis_messages;
 use crate::native_types::error::ErrorStruct;
@@ -33,8 +33,6 @@ impl Runnable<Arc<Mutex<Database>>> for Append {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_empty(&buffer, "append")?;
-
         let new_value = pop_value(&mut buffer)?;
         let key = pop_value(&mut buffer)?;
         no_more_values(&buffer, "append")?;
@@ -43,7 +41,7 @@ impl Runnable<Arc<Mutex<Database>>> for Append {
         if let Some(typesaved) = database.get_mut(&key) {
             match typesaved {
                 TypeSaved::String(old_value) => {
-                    old_value.push_str(&new_value);
+                    old_value.extend_from_slice(new_value.as_bytes());
                     size = old_value.len();
                     Ok(RInteger::encode(size as isize))
                 }
@@ -55,7 +53,7 @@ impl Runnable<Arc<Mutex<Database>>> for Append {
             }
         } else {
             size = new_value.len();
-            database.insert(key, TypeSaved::String(new_value));
+            database.insert(key, TypeSaved::String(new_value.into_bytes()));
             Ok(RInteger::encode(size as isize))
         }
     }
@@ -78,7 +76,7 @@ pub mod test_append {
 
         data.lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
 
         let buffer = vec_strings!["key", "Appended"];
         let encoded = Append.run(buffer, &mut data);
@@ -86,7 +84,7 @@ pub mod test_append {
         assert_eq!(encoded.unwrap(), ":13\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("key"),
-            Some(&TypeSaved::String("valueAppended".to_string()))
+            Some(&TypeSaved::String("valueAppended".into()))
         );
     }
 
@@ -100,7 +98,7 @@ pub mod test_append {
         assert_eq!(encoded.unwrap(), ":8\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("key"),
-            Some(&TypeSaved::String("newValue".to_string()))
+            Some(&TypeSaved::String("newValue".into()))
         );
     }
 
@@ -111,7 +109,7 @@ pub mod test_append {
 
         data.lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
 
         let buffer = vec_strings!["key"];
         let encoded = Append.run(buffer, &mut data);
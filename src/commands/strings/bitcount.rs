@@ -0,0 +1,105 @@
+use super::bitmap::{bytes, count_bits, RangeUnit};
+use crate::commands::{get_as_integer, Runnable};
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::{ErrorStruct, RInteger, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct Bitcount;
+
+impl Runnable<Arc<Mutex<Database>>> for Bitcount {
+    /// Counts the bits set to 1 in the string value stored at **key**, optionally
+    /// between **start** and **end**, both included. They are byte indexes by default,
+    /// or bit indexes with BIT, and can be negative to count from the end of the string.
+    ///
+    /// BITCOUNT key [start end [BYTE|BIT]]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the number of bits set to 1, or 0 when key does not exist.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a non-string value.
+    /// * The start or the end are not integers, or only one of them is received.
+    /// * The unit is not BYTE or BIT.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let (start, end, unit) = match buffer.len() {
+            0 => {
+                return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                    "bitcount",
+                )))
+            }
+            1 => (0, -1, RangeUnit::Byte),
+            3 | 4 => (
+                get_as_integer(&buffer[1])? as i64,
+                get_as_integer(&buffer[2])? as i64,
+                RangeUnit::parse(buffer.get(3))?,
+            ),
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        };
+        let count = bytes(&mut database, &buffer[0])?
+            .and_then(|bytes| {
+                unit.bit_range(bytes.len(), start, end)
+                    .map(|(first, last)| count_bits(bytes, first, last))
+            })
+            .unwrap_or(0);
+        Ok(RInteger::encode(count as isize))
+    }
+}
+
+#[cfg(test)]
+mod test_bitcount {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    fn database() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("foobar".into()));
+        database
+    }
+
+    #[test]
+    fn test_01_bitcount_of_the_whole_string_and_byte_ranges() {
+        let mut database = database();
+        let result = Bitcount.run(vec_strings!["key"], &mut database);
+        assert_eq!(result.unwrap(), ":26\r\n");
+        let result = Bitcount.run(vec_strings!["key", "0", "0"], &mut database);
+        assert_eq!(result.unwrap(), ":4\r\n");
+        let result = Bitcount.run(vec_strings!["key", "1", "1"], &mut database);
+        assert_eq!(result.unwrap(), ":6\r\n");
+        let result = Bitcount.run(vec_strings!["key", "-2", "-1"], &mut database);
+        assert_eq!(result.unwrap(), ":7\r\n");
+        let result = Bitcount.run(vec_strings!["missing"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+    }
+
+    #[test]
+    fn test_02_bitcount_of_bit_ranges() {
+        let mut database = database();
+        let result = Bitcount.run(vec_strings!["key", "5", "30", "BIT"], &mut database);
+        assert_eq!(result.unwrap(), ":17\r\n");
+        let result = Bitcount.run(vec_strings!["key", "5", "30", "NIBBLE"], &mut database);
+        assert!(result.is_err());
+        let result = Bitcount.run(vec_strings!["key", "5"], &mut database);
+        assert!(result.is_err());
+    }
+}
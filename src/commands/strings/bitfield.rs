@@ -0,0 +1,262 @@
+use super::bitmap::{bytes, bytes_mut, BitfieldType, Overflow};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::{ErrorStruct, RBulkString, RInteger, RedisType};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub struct Bitfield;
+
+impl Runnable<Arc<Mutex<Database>>> for Bitfield {
+    /// Treats the string value stored at **key** as an array of integers of arbitrary
+    /// bit width and offset, performing the subcommands in order:
+    ///
+    /// * GET type offset: returns the integer.
+    /// * SET type offset value: sets the integer, returning its previous value.
+    /// * INCRBY type offset increment: increments the integer, returning its new value.
+    /// * OVERFLOW WRAP|SAT|FAIL: changes how the following SET and INCRBY overflow,
+    ///   wrapping around by default, saturating or failing without changes.
+    ///
+    /// The type is i1 to i64 for signed integers, or u1 to u63 for unsigned ones. The offset
+    /// is a bit offset, or the number of integers of the type before it if prefixed with `#`.
+    /// The string is grown with zeros to hold the integers written, and created if **key**
+    /// does not exist.
+    ///
+    /// BITFIELD key [GET type offset] [SET type offset value] [INCRBY type offset increment]
+    /// [OVERFLOW WRAP|SAT|FAIL] ...
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray](crate::native_types::array::RArray): the
+    /// integer of each subcommand, or nil for those that fail with OVERFLOW FAIL.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a non-string value.
+    /// * Some subcommand, type, offset, value or overflow is not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        let operations = BitfieldOperation::parse_all(&buffer, "bitfield")?;
+        execute(&mut database, &buffer[0], operations)
+    }
+}
+
+pub(super) fn lock(
+    database: &Arc<Mutex<Database>>,
+) -> Result<MutexGuard<'_, Database>, ErrorStruct> {
+    database.lock().map_err(|_| {
+        ErrorStruct::from(redis_messages::poisoned_lock(
+            "database",
+            ErrorSeverity::ShutdownServer,
+        ))
+    })
+}
+
+/// Subcommand of BITFIELD, with the overflow behavior of SET and INCRBY.
+pub(super) enum BitfieldOperation {
+    Get(BitfieldType, u64),
+    Set(BitfieldType, u64, i64, Overflow),
+    Incrby(BitfieldType, u64, i64, Overflow),
+}
+
+impl BitfieldOperation {
+    /// Parses the subcommands after the key. BITFIELD_RO only accepts GET.
+    pub(super) fn parse_all(buffer: &[String], name: &str) -> Result<Vec<Self>, ErrorStruct> {
+        if buffer.is_empty() {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                name,
+            )));
+        }
+        let read_only = name == "bitfield_ro";
+        let mut operations = Vec::new();
+        let mut overflow = Overflow::Wrap;
+        let mut index = 1;
+        while index < buffer.len() {
+            let subcommand = buffer[index].to_uppercase();
+            if read_only && subcommand != "GET" {
+                return Err(ErrorStruct::from(redis_messages::bitfield_ro_only_get()));
+            }
+            let arguments = match subcommand.as_str() {
+                "OVERFLOW" => 1,
+                "GET" => 2,
+                "SET" | "INCRBY" => 3,
+                _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+            };
+            let arguments = buffer
+                .get(index + 1..=index + arguments)
+                .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))?;
+            index += arguments.len() + 1;
+            if subcommand == "OVERFLOW" {
+                overflow = Overflow::parse(&arguments[0])?;
+                continue;
+            }
+            let bitfield_type = BitfieldType::parse(&arguments[0])?;
+            let offset = bitfield_type.parse_offset(&arguments[1])?;
+            operations.push(match subcommand.as_str() {
+                "GET" => BitfieldOperation::Get(bitfield_type, offset),
+                "SET" => BitfieldOperation::Set(
+                    bitfield_type,
+                    offset,
+                    parse_value(&arguments[2])?,
+                    overflow,
+                ),
+                _ => BitfieldOperation::Incrby(
+                    bitfield_type,
+                    offset,
+                    parse_value(&arguments[2])?,
+                    overflow,
+                ),
+            });
+        }
+        Ok(operations)
+    }
+
+    /// Returns the number of bytes the string needs to write this integer,
+    /// or 0 if it is not written.
+    fn written_len(&self) -> usize {
+        match self {
+            BitfieldOperation::Get(..) => 0,
+            BitfieldOperation::Set(bitfield_type, offset, ..)
+            | BitfieldOperation::Incrby(bitfield_type, offset, ..) => {
+                (offset + bitfield_type.bits as u64).div_ceil(8) as usize
+            }
+        }
+    }
+
+    /// Performs the subcommand on the string.
+    ///
+    /// # Return value
+    /// [Option]<[i64]>: the integer returned, or [None] if it overflows with FAIL.
+    fn apply(&self, bytes: &mut [u8]) -> Option<i64> {
+        match *self {
+            BitfieldOperation::Get(bitfield_type, offset) => Some(bitfield_type.get(bytes, offset)),
+            BitfieldOperation::Set(bitfield_type, offset, value, overflow) => {
+                let value = bitfield_type.fit(value as i128, overflow)?;
+                let previous = bitfield_type.get(bytes, offset);
+                bitfield_type.set(bytes, offset, value);
+                Some(previous)
+            }
+            BitfieldOperation::Incrby(bitfield_type, offset, increment, overflow) => {
+                let current = bitfield_type.get(bytes, offset) as i128;
+                let value = bitfield_type.fit(current + increment as i128, overflow)?;
+                bitfield_type.set(bytes, offset, value);
+                Some(value)
+            }
+        }
+    }
+}
+
+fn parse_value(value: &str) -> Result<i64, ErrorStruct> {
+    value.parse::<i64>().map_err(|_| {
+        ErrorStruct::new(
+            "ERR".to_string(),
+            "value is not an integer or out of range".to_string(),
+        )
+    })
+}
+
+/// Performs the subcommands on the string stored at the key. It is only created
+/// or grown if some subcommand writes on it.
+pub(super) fn execute(
+    database: &mut Database,
+    key: &str,
+    operations: Vec<BitfieldOperation>,
+) -> Result<String, ErrorStruct> {
+    let len = operations
+        .iter()
+        .map(|operation| operation.written_len())
+        .max()
+        .unwrap_or(0);
+    let results: Vec<Option<i64>> = if len == 0 {
+        let mut bytes = bytes(database, key)?.unwrap_or_default().to_vec();
+        operations
+            .iter()
+            .map(|operation| operation.apply(&mut bytes))
+            .collect()
+    } else {
+        let bytes = bytes_mut(database, key, len)?;
+        operations
+            .iter()
+            .map(|operation| operation.apply(bytes))
+            .collect()
+    };
+
+    let mut encoded = format!("*{}\r\n", results.len());
+    for result in results {
+        match result {
+            Some(value) => encoded.push_str(&RInteger::encode(value as isize)),
+            None => encoded.push_str(&RBulkString::encode(redis_messages::nil())),
+        }
+    }
+    Ok(encoded)
+}
+
+#[cfg(test)]
+mod test_bitfield {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    fn database() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        Arc::new(Mutex::new(Database::new(notifier)))
+    }
+
+    #[test]
+    fn test_01_bitfield_set_get_and_incrby() {
+        let mut database = database();
+        let result = Bitfield.run(
+            vec_strings![
+                "key", "SET", "i8", "#1", "-100", "GET", "u4", "0", "INCRBY", "i8", "8", "10"
+            ],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "*3\r\n:0\r\n:0\r\n:-90\r\n");
+        assert_eq!(
+            database.lock().unwrap().get("key"),
+            Some(&TypeSaved::String(vec![0x00, 0xa6]))
+        );
+    }
+
+    #[test]
+    fn test_02_bitfield_overflows() {
+        let mut database = database();
+        let result = Bitfield.run(
+            vec_strings![
+                "key", "INCRBY", "u2", "100", "5", "OVERFLOW", "SAT", "INCRBY", "u2", "102", "5",
+                "OVERFLOW", "FAIL", "INCRBY", "u2", "104", "5", "SET", "i4", "0", "100"
+            ],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "*4\r\n:1\r\n:3\r\n$-1\r\n$-1\r\n");
+    }
+
+    #[test]
+    fn test_03_bitfield_only_gets_do_not_create_the_key() {
+        let mut database = database();
+        let result = Bitfield.run(vec_strings!["key", "GET", "i64", "0"], &mut database);
+        assert_eq!(result.unwrap(), "*1\r\n:0\r\n");
+        assert!(!database.lock().unwrap().contains_key("key"));
+    }
+
+    #[test]
+    fn test_04_bitfield_with_invalid_arguments() {
+        let mut database = database();
+        for buffer in [
+            vec_strings!["key", "GET", "u64", "0"],
+            vec_strings!["key", "GET", "i8", "-1"],
+            vec_strings!["key", "SET", "i8", "0"],
+            vec_strings!["key", "OVERFLOW", "NEVER"],
+            vec_strings!["key", "DECRBY", "i8", "0", "1"],
+        ] {
+            assert!(Bitfield.run(buffer, &mut database).is_err());
+        }
+    }
+}
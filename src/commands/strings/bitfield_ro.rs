@@ -0,0 +1,61 @@
+use super::bitfield::{execute, lock, BitfieldOperation};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::native_types::ErrorStruct;
+use std::sync::{Arc, Mutex};
+
+pub struct BitfieldRo;
+
+impl Runnable<Arc<Mutex<Database>>> for BitfieldRo {
+    /// Read-only variant of BITFIELD, which only accepts the GET subcommand.
+    ///
+    /// BITFIELD_RO key [GET type offset ...]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RArray](crate::native_types::array::RArray): the
+    /// integer of each GET.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a non-string value.
+    /// * Some subcommand is not GET, or its type or offset are not valid.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        let operations = BitfieldOperation::parse_all(&buffer, "bitfield_ro")?;
+        execute(&mut database, &buffer[0], operations)
+    }
+}
+
+#[cfg(test)]
+mod test_bitfield_ro {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_bitfield_ro_only_accepts_get() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String(vec![0x01, 0x02]));
+        let result = BitfieldRo.run(
+            vec_strings!["key", "GET", "u8", "0", "GET", "u8", "#1"],
+            &mut database,
+        );
+        assert_eq!(result.unwrap(), "*2\r\n:1\r\n:2\r\n");
+        let result = BitfieldRo.run(vec_strings!["key", "SET", "u8", "0", "1"], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR BITFIELD_RO only supports the GET subcommand"
+        );
+    }
+}
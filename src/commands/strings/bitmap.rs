@@ -0,0 +1,321 @@
+use crate::{
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::ErrorStruct,
+};
+
+/// Greatest bit offset accepted, as strings are limited to 512MB.
+const MAX_BIT_OFFSET: u64 = (512 * 1024 * 1024 * 8) - 1;
+
+/// Returns the bytes of the string stored at the key, or [None] if it does not exist.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The value stored at **key** is not a string.
+pub fn bytes<'a>(database: &'a mut Database, key: &str) -> Result<Option<&'a [u8]>, ErrorStruct> {
+    match database.get(key) {
+        Some(TypeSaved::String(value)) => Ok(Some(value)),
+        Some(_) => err_wrongtype!(),
+        None => Ok(None),
+    }
+}
+
+/// Returns the bytes of the string stored at the key, padded with zeros to have
+/// at least `len` bytes. If the key does not exist, an empty string is created.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The value stored at **key** is not a string.
+pub fn bytes_mut<'a>(
+    database: &'a mut Database,
+    key: &str,
+    len: usize,
+) -> Result<&'a mut Vec<u8>, ErrorStruct> {
+    if !database.contains_key(key) {
+        database.insert(key.to_string(), TypeSaved::String(Vec::new()));
+    }
+    match database.get_mut(key) {
+        Some(TypeSaved::String(value)) => {
+            if value.len() < len {
+                value.resize(len, 0);
+            }
+            Ok(value)
+        }
+        _ => err_wrongtype!(),
+    }
+}
+
+/// Parses a bit offset, which must be lower than 2^32.
+pub fn parse_bit_offset(offset: &str) -> Result<u64, ErrorStruct> {
+    offset
+        .parse::<u64>()
+        .ok()
+        .filter(|offset| *offset <= MAX_BIT_OFFSET)
+        .ok_or_else(|| ErrorStruct::from(redis_messages::bit_offset_out_of_range()))
+}
+
+/// Returns the bit at the offset, where the offset 0 is the most significant
+/// bit of the first byte. The bits beyond the end of the string are 0.
+pub fn get_bit(bytes: &[u8], offset: u64) -> u8 {
+    bytes
+        .get((offset / 8) as usize)
+        .map(|byte| (byte >> (7 - offset % 8)) & 1)
+        .unwrap_or(0)
+}
+
+/// Sets the bit at the offset, which must be inside the string, returning its
+/// previous value.
+pub fn set_bit(bytes: &mut [u8], offset: u64, bit: u8) -> u8 {
+    let byte = &mut bytes[(offset / 8) as usize];
+    let mask = 1 << (7 - offset % 8);
+    let previous = (*byte & mask != 0) as u8;
+    if bit == 0 {
+        *byte &= !mask;
+    } else {
+        *byte |= mask;
+    }
+    previous
+}
+
+/// Unit of the start and the end of BITCOUNT and BITPOS.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RangeUnit {
+    Byte,
+    Bit,
+}
+
+impl RangeUnit {
+    /// Parses the optional BYTE or BIT argument, which is BYTE by default.
+    pub fn parse(unit: Option<&String>) -> Result<Self, ErrorStruct> {
+        match unit.map(|unit| unit.to_uppercase()).as_deref() {
+            None | Some("BYTE") => Ok(RangeUnit::Byte),
+            Some("BIT") => Ok(RangeUnit::Bit),
+            _ => Err(ErrorStruct::from(redis_messages::syntax_error())),
+        }
+    }
+
+    /// Returns the range of bits between the start and the end, both included,
+    /// in units of this type. Negative values count from the end of the string.
+    ///
+    /// # Return value
+    /// [Option]<([u64], [u64])>: the first and the last bit, or [None] if the range is empty.
+    pub fn bit_range(&self, len: usize, start: i64, end: i64) -> Option<(u64, u64)> {
+        let len = match self {
+            RangeUnit::Byte => len as i64,
+            RangeUnit::Bit => len as i64 * 8,
+        };
+        let normalize = |index: i64| {
+            if index < 0 {
+                (len + index).max(0)
+            } else {
+                index
+            }
+        };
+        let (start, end) = (normalize(start), normalize(end).min(len - 1));
+        if start > end {
+            return None;
+        }
+        match self {
+            RangeUnit::Byte => Some((start as u64 * 8, end as u64 * 8 + 7)),
+            RangeUnit::Bit => Some((start as u64, end as u64)),
+        }
+    }
+}
+
+/// Counts the bits set to 1 between the first and the last bit, both included.
+pub fn count_bits(bytes: &[u8], first: u64, last: u64) -> u64 {
+    let (first_byte, last_byte) = ((first / 8) as usize, (last / 8) as usize);
+    let mut count: u64 = bytes[first_byte..=last_byte]
+        .iter()
+        .map(|byte| byte.count_ones() as u64)
+        .sum();
+    // Bits of the first and the last bytes out of the range
+    count -= (bytes[first_byte] as u32 >> (8 - first % 8)).count_ones() as u64;
+    count -= (bytes[last_byte] as u32 & (0xff >> (last % 8 + 1))).count_ones() as u64;
+    count
+}
+
+/// Returns the position of the first bit with the value between the first and
+/// the last bit, both included.
+pub fn first_bit(bytes: &[u8], bit: u8, first: u64, last: u64) -> Option<u64> {
+    let skipped = if bit == 0 { 0xff } else { 0 };
+    let mut offset = first;
+    while offset <= last {
+        if offset.is_multiple_of(8) && offset + 7 <= last && bytes[(offset / 8) as usize] == skipped
+        {
+            offset += 8;
+        } else if get_bit(bytes, offset) == bit {
+            return Some(offset);
+        } else {
+            offset += 1;
+        }
+    }
+    None
+}
+
+/// Type of the integers of BITFIELD: signed of up to 64 bits or unsigned of up to 63 bits.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BitfieldType {
+    pub signed: bool,
+    pub bits: u32,
+}
+
+impl BitfieldType {
+    /// Parses types such as i8 or u16.
+    pub fn parse(encoding: &str) -> Result<Self, ErrorStruct> {
+        let (signed, bits) = match encoding.split_at_checked(1) {
+            Some(("i", bits)) | Some(("I", bits)) => (true, bits),
+            Some(("u", bits)) | Some(("U", bits)) => (false, bits),
+            _ => return Err(ErrorStruct::from(redis_messages::invalid_bitfield_type())),
+        };
+        let max_bits = if signed { 64 } else { 63 };
+        match bits.parse::<u32>() {
+            Ok(bits) if (1..=max_bits).contains(&bits) => Ok(BitfieldType { signed, bits }),
+            _ => Err(ErrorStruct::from(redis_messages::invalid_bitfield_type())),
+        }
+    }
+
+    /// Parses the offset of an integer of this type: a bit offset, or the number
+    /// of integers of this type before it if prefixed with `#`.
+    pub fn parse_offset(&self, offset: &str) -> Result<u64, ErrorStruct> {
+        let offset = match offset.strip_prefix('#') {
+            Some(index) => index
+                .parse::<u64>()
+                .ok()
+                .and_then(|index| index.checked_mul(self.bits as u64))
+                .ok_or_else(|| ErrorStruct::from(redis_messages::bit_offset_out_of_range()))?,
+            None => parse_bit_offset(offset)?,
+        };
+        if offset.saturating_add(self.bits as u64 - 1) > MAX_BIT_OFFSET {
+            return Err(ErrorStruct::from(redis_messages::bit_offset_out_of_range()));
+        }
+        Ok(offset)
+    }
+
+    fn min(&self) -> i128 {
+        if self.signed {
+            -(1 << (self.bits - 1))
+        } else {
+            0
+        }
+    }
+
+    fn max(&self) -> i128 {
+        if self.signed {
+            (1 << (self.bits - 1)) - 1
+        } else {
+            (1 << self.bits) - 1
+        }
+    }
+
+    /// Reads the integer of this type at the bit offset.
+    pub fn get(&self, bytes: &[u8], offset: u64) -> i64 {
+        let mut value: u64 = 0;
+        for index in 0..self.bits as u64 {
+            value = (value << 1) | get_bit(bytes, offset + index) as u64;
+        }
+        if self.signed && self.bits < 64 && value >> (self.bits - 1) == 1 {
+            value |= u64::MAX << self.bits;
+        }
+        value as i64
+    }
+
+    /// Writes the integer of this type at the bit offset, which must be inside the string.
+    pub fn set(&self, bytes: &mut [u8], offset: u64, value: i64) {
+        let value = value as u64;
+        for index in 0..self.bits as u64 {
+            let bit = (value >> (self.bits as u64 - 1 - index)) & 1;
+            set_bit(bytes, offset + index, bit as u8);
+        }
+    }
+
+    /// Fits the value in this type according to the overflow behavior.
+    ///
+    /// # Return value
+    /// [Option]<[i64]>: the value fitted, or [None] if it overflows with FAIL.
+    pub fn fit(&self, value: i128, overflow: Overflow) -> Option<i64> {
+        let (min, max) = (self.min(), self.max());
+        if (min..=max).contains(&value) {
+            return Some(value as i64);
+        }
+        match overflow {
+            Overflow::Wrap => Some(((value - min).rem_euclid(max - min + 1) + min) as i64),
+            Overflow::Sat => Some(value.clamp(min, max) as i64),
+            Overflow::Fail => None,
+        }
+    }
+}
+
+/// Behavior of BITFIELD when SET or INCRBY overflow the type.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Overflow {
+    Wrap,
+    Sat,
+    Fail,
+}
+
+impl Overflow {
+    pub fn parse(overflow: &str) -> Result<Self, ErrorStruct> {
+        match overflow.to_uppercase().as_str() {
+            "WRAP" => Ok(Overflow::Wrap),
+            "SAT" => Ok(Overflow::Sat),
+            "FAIL" => Ok(Overflow::Fail),
+            _ => Err(ErrorStruct::from(redis_messages::invalid_overflow_type())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_bitmap {
+    use super::*;
+
+    #[test]
+    fn test_01_get_and_set_bits() {
+        let mut bytes = vec![0u8; 2];
+        assert_eq!(set_bit(&mut bytes, 0, 1), 0);
+        assert_eq!(set_bit(&mut bytes, 9, 1), 0);
+        assert_eq!(set_bit(&mut bytes, 9, 0), 1);
+        assert_eq!(bytes, vec![0x80, 0]);
+        assert_eq!(get_bit(&bytes, 0), 1);
+        assert_eq!(get_bit(&bytes, 100), 0);
+    }
+
+    #[test]
+    fn test_02_ranges_and_counts() {
+        let bytes = b"foobar";
+        assert_eq!(RangeUnit::Byte.bit_range(6, 1, 1), Some((8, 15)));
+        assert_eq!(RangeUnit::Byte.bit_range(6, -2, 100), Some((32, 47)));
+        assert_eq!(RangeUnit::Bit.bit_range(6, 5, 30), Some((5, 30)));
+        assert_eq!(RangeUnit::Byte.bit_range(6, 3, 1), None);
+        assert_eq!(count_bits(bytes, 0, 47), 26);
+        assert_eq!(count_bits(bytes, 8, 15), 6);
+        assert_eq!(count_bits(bytes, 5, 30), 17);
+    }
+
+    #[test]
+    fn test_03_bitfield_types() {
+        assert!(BitfieldType::parse("u64").is_err());
+        assert!(BitfieldType::parse("x8").is_err());
+        let signed = BitfieldType::parse("i8").unwrap();
+        let mut bytes = vec![0u8; 2];
+        signed.set(&mut bytes, 4, -2);
+        assert_eq!(bytes, vec![0x0f, 0xe0]);
+        assert_eq!(signed.get(&bytes, 4), -2);
+        assert_eq!(BitfieldType::parse("u8").unwrap().get(&bytes, 4), 254);
+        assert_eq!(signed.parse_offset("#2").unwrap(), 16);
+    }
+
+    #[test]
+    fn test_04_overflows() {
+        let unsigned = BitfieldType::parse("u2").unwrap();
+        assert_eq!(unsigned.fit(5, Overflow::Wrap), Some(1));
+        assert_eq!(unsigned.fit(5, Overflow::Sat), Some(3));
+        assert_eq!(unsigned.fit(5, Overflow::Fail), None);
+        let signed = BitfieldType::parse("i8").unwrap();
+        assert_eq!(signed.fit(128, Overflow::Wrap), Some(-128));
+        assert_eq!(signed.fit(-200, Overflow::Sat), Some(-128));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::commands::Runnable;
+use crate::database::{Database, TypeSaved};
+use crate::err_wrongtype;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::{ErrorStruct, RInteger, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct Bitop;
+
+impl Runnable<Arc<Mutex<Database>>> for Bitop {
+    /// Performs a bitwise operation between the strings stored at the source keys and
+    /// stores the result in **destkey**. The shorter strings and the missing keys are
+    /// padded with zeros up to the length of the longest string. If the result is
+    /// empty, **destkey** is deleted.
+    ///
+    /// BITOP AND|OR|XOR|NOT destkey key [key ...]
+    ///
+    /// NOT inverts the bits of a single key.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the length of the string stored in **destkey**.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Some source key holds a non-string value.
+    /// * The operation is unknown, or NOT receives more than one key.
+    /// * The buffer [Vec]<[String]> has less than three elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        if buffer.len() < 3 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "bitop",
+            )));
+        }
+        let operation = buffer[0].to_uppercase();
+        let operator: Option<fn(u8, u8) -> u8> = match operation.as_str() {
+            "AND" => Some(|a, b| a & b),
+            "OR" => Some(|a, b| a | b),
+            "XOR" => Some(|a, b| a ^ b),
+            "NOT" if buffer.len() == 3 => None,
+            "NOT" => return Err(ErrorStruct::from(redis_messages::bitop_not_single_key())),
+            _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+        };
+
+        let mut sources = Vec::new();
+        for key in buffer[2..].iter() {
+            match database.get(key) {
+                Some(TypeSaved::String(value)) => sources.push(value.clone()),
+                Some(_) => return err_wrongtype!(),
+                None => sources.push(Vec::new()),
+            }
+        }
+        let len = sources.iter().map(|source| source.len()).max().unwrap_or(0);
+        let byte_at = |source: &Vec<u8>, index: usize| source.get(index).copied().unwrap_or(0);
+        let result: Vec<u8> = match operator {
+            Some(operator) => (0..len)
+                .map(|index| {
+                    sources[1..]
+                        .iter()
+                        .fold(byte_at(&sources[0], index), |byte, source| {
+                            operator(byte, byte_at(source, index))
+                        })
+                })
+                .collect(),
+            None => sources[0].iter().map(|byte| !byte).collect(),
+        };
+
+        if result.is_empty() {
+            database.remove(&buffer[1]);
+        } else {
+            database.insert(buffer[1].to_string(), TypeSaved::String(result));
+        }
+        Ok(RInteger::encode(len as isize))
+    }
+}
+
+#[cfg(test)]
+mod test_bitop {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::vec_strings;
+
+    fn database() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier)));
+        let mut guard = database.lock().unwrap();
+        guard.insert("a".to_string(), TypeSaved::String(vec![0b1100, 0xff]));
+        guard.insert("b".to_string(), TypeSaved::String(vec![0b1010]));
+        drop(guard);
+        database
+    }
+
+    fn value_of(database: &Arc<Mutex<Database>>, key: &str) -> Option<TypeSaved> {
+        database.lock().unwrap().get(key).cloned()
+    }
+
+    #[test]
+    fn test_01_bitop_and_or_xor_pad_shorter_strings() {
+        let mut database = database();
+        let result = Bitop.run(vec_strings!["AND", "dest", "a", "b"], &mut database);
+        assert_eq!(result.unwrap(), ":2\r\n");
+        assert_eq!(
+            value_of(&database, "dest"),
+            Some(TypeSaved::String(vec![0b1000, 0]))
+        );
+        Bitop
+            .run(
+                vec_strings!["or", "dest", "a", "b", "missing"],
+                &mut database,
+            )
+            .unwrap();
+        assert_eq!(
+            value_of(&database, "dest"),
+            Some(TypeSaved::String(vec![0b1110, 0xff]))
+        );
+        Bitop
+            .run(vec_strings!["XOR", "dest", "a", "b"], &mut database)
+            .unwrap();
+        assert_eq!(
+            value_of(&database, "dest"),
+            Some(TypeSaved::String(vec![0b0110, 0xff]))
+        );
+    }
+
+    #[test]
+    fn test_02_bitop_not_and_empty_results() {
+        let mut database = database();
+        Bitop
+            .run(vec_strings!["NOT", "dest", "b"], &mut database)
+            .unwrap();
+        assert_eq!(
+            value_of(&database, "dest"),
+            Some(TypeSaved::String(vec![0b11110101]))
+        );
+        assert!(Bitop
+            .run(vec_strings!["NOT", "dest", "a", "b"], &mut database)
+            .is_err());
+        Bitop
+            .run(vec_strings!["AND", "dest", "a"], &mut database)
+            .unwrap();
+        assert_eq!(value_of(&database, "dest"), value_of(&database, "a"));
+        let result = Bitop.run(vec_strings!["AND", "dest", "missing"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+        assert_eq!(value_of(&database, "dest"), None);
+    }
+}
@@ -0,0 +1,131 @@
+use super::bitmap::{bytes, first_bit, RangeUnit};
+use crate::commands::{get_as_integer, Runnable};
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::{ErrorStruct, RInteger, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct Bitpos;
+
+impl Runnable<Arc<Mutex<Database>>> for Bitpos {
+    /// Returns the position of the first bit set to **bit** in the string value stored at
+    /// **key**, optionally between **start** and **end**, both included. They are byte
+    /// indexes by default, or bit indexes with BIT, and can be negative to count from
+    /// the end of the string.
+    ///
+    /// BITPOS key bit [start [end [BYTE|BIT]]]
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the position of the first bit, counting from the
+    /// beginning of the string, or -1 if it is not found. When looking for a 0 without
+    /// **end**, the bits after the string are considered 0. A missing key is an empty string.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a non-string value.
+    /// * The bit is not 0 or 1.
+    /// * The start or the end are not integers.
+    /// * The unit is not BYTE or BIT.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        if buffer.len() < 2 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "bitpos",
+            )));
+        }
+        if buffer.len() > 5 {
+            return Err(ErrorStruct::from(redis_messages::syntax_error()));
+        }
+        let bit = match buffer[1].as_str() {
+            "0" => 0,
+            "1" => 1,
+            _ => return Err(ErrorStruct::from(redis_messages::bit_argument())),
+        };
+        let start = match buffer.get(2) {
+            Some(start) => get_as_integer(start)? as i64,
+            None => 0,
+        };
+        let end = match buffer.get(3) {
+            Some(end) => Some(get_as_integer(end)? as i64),
+            None => None,
+        };
+        let unit = RangeUnit::parse(buffer.get(4))?;
+
+        let bytes = match bytes(&mut database, &buffer[0])? {
+            Some(bytes) => bytes,
+            None => return Ok(RInteger::encode(if bit == 0 { 0 } else { -1 })),
+        };
+        let position = match unit.bit_range(bytes.len(), start, end.unwrap_or(-1)) {
+            Some((first, last)) => match first_bit(bytes, bit, first, last) {
+                Some(position) => position as isize,
+                // The bits after the string are 0 if the range is open
+                None if bit == 0 && end.is_none() => last as isize + 1,
+                None => -1,
+            },
+            None => -1,
+        };
+        Ok(RInteger::encode(position))
+    }
+}
+
+#[cfg(test)]
+mod test_bitpos {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    fn database() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier)));
+        let mut guard = database.lock().unwrap();
+        guard.insert(
+            "ones".to_string(),
+            TypeSaved::String(vec![0xff, 0xf0, 0x00]),
+        );
+        guard.insert("full".to_string(), TypeSaved::String(vec![0xff, 0xff]));
+        drop(guard);
+        database
+    }
+
+    #[test]
+    fn test_01_bitpos_of_set_and_clear_bits() {
+        let mut database = database();
+        let result = Bitpos.run(vec_strings!["ones", "0"], &mut database);
+        assert_eq!(result.unwrap(), ":12\r\n");
+        let result = Bitpos.run(vec_strings!["ones", "1", "2"], &mut database);
+        assert_eq!(result.unwrap(), ":-1\r\n");
+        let result = Bitpos.run(vec_strings!["ones", "1", "7", "15", "BIT"], &mut database);
+        assert_eq!(result.unwrap(), ":7\r\n");
+        let result = Bitpos.run(vec_strings!["ones", "1", "-2"], &mut database);
+        assert_eq!(result.unwrap(), ":8\r\n");
+    }
+
+    #[test]
+    fn test_02_bitpos_of_clear_bits_after_the_string() {
+        let mut database = database();
+        let result = Bitpos.run(vec_strings!["full", "0"], &mut database);
+        assert_eq!(result.unwrap(), ":16\r\n");
+        let result = Bitpos.run(vec_strings!["full", "0", "0", "-1"], &mut database);
+        assert_eq!(result.unwrap(), ":-1\r\n");
+        let result = Bitpos.run(vec_strings!["missing", "0"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+        let result = Bitpos.run(vec_strings!["missing", "1"], &mut database);
+        assert_eq!(result.unwrap(), ":-1\r\n");
+        assert!(Bitpos
+            .run(vec_strings!["full", "2"], &mut database)
+            .is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use super::{execute_value_modification, no_more_values, pop_value};
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::Runnable, database::Database, messages::redis_messages,
+    native_types::error::ErrorStruct,
+};
+use std::sync::{Arc, Mutex};
+
+pub struct Decr;
+
+impl Runnable<Arc<Mutex<Database>>> for Decr {
+    /// Decrements the number stored at **key** by one. If the **key** does not exist, it is set
+    /// to 0 before performing the operation.
+    ///
+    /// This operation is limited to 64 bit signed integers.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger](crate::native_types::integer::RInteger): the value of **key** after the decrement.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The key contains a value of the wrong type or contains a string that can not be represented as integer.
+    /// * The result does not fit in a 64 bit signed integer.
+    /// * The buffer [Vec]<[String]> more than one element is received or empty.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let key = pop_value(&mut buffer)?;
+        no_more_values(&buffer, "decr")?;
+        execute_value_modification(
+            &mut database,
+            vec![key, "1".to_string()],
+            isize::checked_sub,
+        )
+    }
+}
+
+#[cfg(test)]
+pub mod test_decr {
+    use crate::commands::create_notifier;
+
+    use crate::{database::TypeSaved, vec_strings};
+
+    use super::*;
+
+    #[test]
+    fn test_01_decr_existing_key() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
+        // redis> SET mykey 10
+        data.lock()
+            .unwrap()
+            .insert("mykey".to_string(), TypeSaved::String("10".into()));
+        // redis> DECR mykey
+        let encoded = Decr.run(vec_strings!["mykey"], &mut data);
+
+        assert_eq!(encoded.unwrap(), ":9\r\n".to_string());
+        assert_eq!(
+            data.lock().unwrap().get("mykey"),
+            Some(&TypeSaved::String("9".into()))
+        );
+    }
+
+    #[test]
+    fn test_02_decr_non_existing_key() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
+        let encoded = Decr.run(vec_strings!["mykey"], &mut data);
+
+        assert_eq!(encoded.unwrap(), ":-1\r\n".to_string());
+    }
+
+    #[test]
+    fn test_03_decr_keeps_the_expiration_time() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
+        data.lock()
+            .unwrap()
+            .insert("mykey".to_string(), TypeSaved::String("10".into()));
+        data.lock().unwrap().set_ttl("mykey", 100).unwrap();
+        Decr.run(vec_strings!["mykey"], &mut data).unwrap();
+
+        assert!(data.lock().unwrap().ttl("mykey").is_some());
+    }
+
+    #[test]
+    fn test_04_decr_overflow() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
+        data.lock().unwrap().insert(
+            "mykey".to_string(),
+            TypeSaved::String("-9223372036854775808".into()),
+        );
+        let error = Decr.run(vec_strings!["mykey"], &mut data);
+
+        assert_eq!(
+            error.unwrap_err().print_it(),
+            "ERR increment or decrement would overflow".to_string()
+        );
+    }
+}
@@ -21,6 +21,7 @@ impl Runnable<Arc<Mutex<Database>>> for Decrby {
     /// Return an [ErrorStruct] if:
     ///
     /// * The key contains a value of the wrong type or contains a string that can not be represented as integer.
+    /// * The result does not fit in a 64 bit signed integer.
     /// * The buffer [Vec]<[String]> more than two elements is received or empty.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.    
     fn run(
@@ -34,14 +35,10 @@ impl Runnable<Arc<Mutex<Database>>> for Decrby {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        execute_value_modification(&mut database, buffer, decr)
+        execute_value_modification(&mut database, buffer, isize::checked_sub)
     }
 }
 
-fn decr(minuend: isize, subtrahend: isize) -> isize {
-    minuend - subtrahend
-}
-
 #[cfg(test)]
 pub mod test_decrby {
     use crate::commands::create_notifier;
@@ -57,7 +54,7 @@ pub mod test_decrby {
         // redis> SET mykey 10
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("10".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("10".into()));
         // redis> DECRBY mykey 3 ---> (integer) 7
         let buffer = vec_strings!["mykey", "3"];
         let encoded = Decrby.run(buffer, &mut data);
@@ -65,7 +62,7 @@ pub mod test_decrby {
         assert_eq!(encoded.unwrap(), ":7\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("mykey"),
-            Some(&TypeSaved::String("7".to_string()))
+            Some(&TypeSaved::String("7".into()))
         );
     }
 
@@ -76,7 +73,7 @@ pub mod test_decrby {
         // redis> SET mykey 10
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("10".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("10".into()));
         // redis> DECRBY mykey -3
         let buffer = vec_strings!["mykey", "-3"];
         let encoded = Decrby.run(buffer, &mut data);
@@ -84,7 +81,7 @@ pub mod test_decrby {
         assert_eq!(encoded.unwrap(), ":13\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("mykey"),
-            Some(&TypeSaved::String("13".to_string()))
+            Some(&TypeSaved::String("13".into()))
         );
     }
 
@@ -95,7 +92,7 @@ pub mod test_decrby {
         // redis> SET mykey -10
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("-10".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("-10".into()));
         // redis> DECRBY mykey 3
         let buffer = vec_strings!["mykey", "3"];
         let encoded = Decrby.run(buffer, &mut data);
@@ -103,7 +100,7 @@ pub mod test_decrby {
         assert_eq!(encoded.unwrap(), ":-13\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("mykey"),
-            Some(&TypeSaved::String("-13".to_string()))
+            Some(&TypeSaved::String("-13".into()))
         );
     }
 
@@ -114,7 +111,7 @@ pub mod test_decrby {
         // redis> SET mykey -10
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("-10".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("-10".into()));
         // redis> DECRBY mykey -3
         let buffer = vec_strings!["mykey", "-3"];
         let encoded = Decrby.run(buffer, &mut data);
@@ -122,7 +119,7 @@ pub mod test_decrby {
         assert_eq!(encoded.unwrap(), ":-7\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("mykey"),
-            Some(&TypeSaved::String("-7".to_string()))
+            Some(&TypeSaved::String("-7".into()))
         );
     }
 
@@ -136,7 +133,7 @@ pub mod test_decrby {
         assert_eq!(encoded.unwrap(), ":-3\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("mykey"),
-            Some(&TypeSaved::String("-3".to_string()))
+            Some(&TypeSaved::String("-3".into()))
         );
     }
 
@@ -147,7 +144,7 @@ pub mod test_decrby {
         // redis> SET mykey value
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("value".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("value".into()));
         // redis> DECRBY mykey 1
         let buffer = vec_strings!["mykey", "value"];
         let error = Decrby.run(buffer, &mut data);
@@ -165,7 +162,7 @@ pub mod test_decrby {
         // redis> SET mykey 10
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("10".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("10".into()));
         // redis> DECRBY mykey a
         let buffer = vec_strings!["mykey", "a"];
         let error = Decrby.run(buffer, &mut data);
@@ -175,4 +172,26 @@ pub mod test_decrby {
             "ERR value is not an integer or out of range".to_string()
         );
     }
+    #[test]
+    fn test_08_decrby_overflow_keeps_the_value() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
+        // redis> SET mykey -9223372036854775808
+        data.lock().unwrap().insert(
+            "mykey".to_string(),
+            TypeSaved::String("-9223372036854775808".into()),
+        );
+        // redis> DECRBY mykey 1
+        let buffer = vec_strings!["mykey", "1"];
+        let error = Decrby.run(buffer, &mut data);
+
+        assert_eq!(
+            error.unwrap_err().print_it(),
+            "ERR increment or decrement would overflow".to_string()
+        );
+        assert_eq!(
+            data.lock().unwrap().get("mykey"),
+            Some(&TypeSaved::String("-9223372036854775808".into()))
+        );
+    }
 }
@@ -38,7 +38,7 @@ impl Runnable<Arc<Mutex<Database>>> for Get {
 
         match database.get(&key) {
             Some(item) => match item {
-                TypeSaved::String(item) => Ok(RBulkString::encode(item.to_string())),
+                TypeSaved::String(item) => Ok(RBulkString::encode_bytes(item)),
                 _ => {
                     err_wrongtype!()
                 }
@@ -78,7 +78,7 @@ mod test_get {
         database_mock
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let result_received = Get.run(buffer_mock_get, &mut database_mock);
 
         let expected_result = RBulkString::encode("value".to_string());
@@ -94,11 +94,26 @@ mod test_get {
         database_mock
             .lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
         let result_received = Get.run(buffer_mock_get, &mut database_mock);
         let received = result_received.unwrap();
 
         let expected_result = "$-1\r\n".to_string();
         assert_eq!(expected_result, received)
     }
+
+    #[test]
+    fn test_03_get_value_set_by_bits_returns_its_bytes() {
+        use crate::commands::strings::setbit::Setbit;
+        use crate::native_types::bulk_string::wire_bytes;
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database_mock = Arc::new(Mutex::new(Database::new(notifier)));
+
+        Setbit
+            .run(vec_strings!["key", "0", "1"], &mut database_mock)
+            .unwrap();
+        let received = Get.run(vec_strings!["key"], &mut database_mock).unwrap();
+
+        assert_eq!(wire_bytes(&received), b"$1\r\n\x80\r\n".to_vec());
+    }
 }
@@ -0,0 +1,83 @@
+use super::bitmap::{bytes, get_bit, parse_bit_offset};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::{ErrorStruct, RInteger, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct Getbit;
+
+impl Runnable<Arc<Mutex<Database>>> for Getbit {
+    /// Returns the bit value at **offset** in the string value stored at **key**.
+    /// The bits beyond the end of the string, or of a missing key, are 0.
+    ///
+    /// GETBIT key offset
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the bit value stored at **offset**.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a non-string value.
+    /// * The offset is not an integer between 0 and 2^32 - 1.
+    /// * The buffer [Vec]<[String]> has not two elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        if buffer.len() != 2 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "getbit",
+            )));
+        }
+        let offset = parse_bit_offset(&buffer[1])?;
+        let bit = bytes(&mut database, &buffer[0])?
+            .map(|bytes| get_bit(bytes, offset))
+            .unwrap_or(0);
+        Ok(RInteger::encode(bit as isize))
+    }
+}
+
+#[cfg(test)]
+mod test_getbit {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_getbit_of_existing_and_missing_bits() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key".to_string(), TypeSaved::String("a".into()));
+        // "a" is 0b01100001
+        let bits: Vec<String> = (0..8)
+            .map(|offset| {
+                Getbit
+                    .run(vec_strings!["key", offset], &mut database)
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(
+            bits.concat(),
+            ":0\r\n:1\r\n:1\r\n:0\r\n:0\r\n:0\r\n:0\r\n:1\r\n"
+        );
+        let result = Getbit.run(vec_strings!["key", "100"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+        let result = Getbit.run(vec_strings!["missing", "0"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+    }
+}
@@ -53,7 +53,7 @@ impl Runnable<Arc<Mutex<Database>>> for Getdel {
 
         if let Some(value) = database.remove(&key) {
             match value {
-                TypeSaved::String(value) => Ok(RBulkString::encode(value)),
+                TypeSaved::String(value) => Ok(RBulkString::encode_bytes(&value)),
                 _ => Err(ErrorStruct::new(
                     String::from("ERR"),
                     String::from("key provided is not from string"),
@@ -82,7 +82,7 @@ pub mod test_getdel {
 
         data.lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
 
         let buffer = vec_strings!["key"];
         let encoded = Getdel.run(buffer, &mut data);
@@ -109,7 +109,7 @@ pub mod test_getdel {
 
         data.lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
 
         let buffer = vec_strings!["key", "ahre", "mas", "argumentos"];
         let encoded = Getdel.run(buffer, &mut data);
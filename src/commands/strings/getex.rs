@@ -0,0 +1,154 @@
+use super::bitfield::lock;
+use crate::commands::{get_as_integer, Runnable};
+use crate::database::{Database, TypeSaved};
+use crate::err_wrongtype;
+use crate::messages::redis_messages;
+use crate::native_types::{ErrorStruct, RBulkString, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct Getex;
+
+/// Change of the expiration time requested to GETEX.
+#[derive(Debug, PartialEq)]
+enum Expiration {
+    /// Milliseconds from now until the key expires, which can be negative if
+    /// the unix time given has already passed.
+    In(i128),
+    Persist,
+}
+
+impl Runnable<Arc<Mutex<Database>>> for Getex {
+    /// Get the **value** of **key** and optionally set its expiration time.
+    ///
+    /// GETEX key [EX seconds | PX milliseconds | EXAT unix-time-seconds | PXAT unix-time-milliseconds | PERSIST]
+    ///
+    /// * EX, PX: the key expires after the seconds or milliseconds given.
+    /// * EXAT, PXAT: the key expires at the unix time given. Past times delete the key.
+    /// * PERSIST: removes the expiration time of the key.
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the **value** of **key**, **nil** when **key** does not exist.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a non-string value.
+    /// * More than one option is given, or the expiration time is not a positive integer.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        let key = &buffer[0];
+        let expiration = parse_expiration(&buffer[1..], database.clock().unix_millis())?;
+        let value = match database.get(key) {
+            Some(TypeSaved::String(value)) => value.clone(),
+            Some(_) => return err_wrongtype!(),
+            None => return Ok(RBulkString::encode(redis_messages::nil())),
+        };
+        match expiration {
+            Some(Expiration::In(millis)) if millis > 0 => {
+                database.set_ttl_millis(key, millis.min(u64::MAX as i128) as u64)?
+            }
+            Some(Expiration::In(_)) => {
+                database.remove(key);
+            }
+            Some(Expiration::Persist) => {
+                database.persist(key);
+            }
+            None => {}
+        }
+        Ok(RBulkString::encode_bytes(&value))
+    }
+}
+
+/// Parses the optional EX, PX, EXAT, PXAT or PERSIST argument. The unix
+/// times are compared against `now`, in milliseconds.
+fn parse_expiration(options: &[String], now: u64) -> Result<Option<Expiration>, ErrorStruct> {
+    let (option, time) = match options {
+        [] => return Ok(None),
+        [option] if option.to_uppercase() == "PERSIST" => return Ok(Some(Expiration::Persist)),
+        [option, time] => (option.to_uppercase(), time),
+        _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+    };
+    let (unit, absolute) = match option.as_str() {
+        "EX" => (1000, false),
+        "PX" => (1, false),
+        "EXAT" => (1000, true),
+        "PXAT" => (1, true),
+        _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+    };
+    let time = get_as_integer(time)?;
+    if time <= 0 {
+        return Err(ErrorStruct::from(redis_messages::invalid_expire_time(
+            "getex",
+        )));
+    }
+    let mut millis = time as i128 * unit;
+    if absolute {
+        millis -= now as i128;
+    }
+    Ok(Some(Expiration::In(millis)))
+}
+
+#[cfg(test)]
+mod test_getex {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::vec_strings;
+
+    fn database_with_key() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("mykey".to_string(), TypeSaved::String("Hello".into()));
+        database
+    }
+
+    #[test]
+    fn test_01_getex_sets_and_removes_the_expiration_time() {
+        let mut database = database_with_key();
+        let result = Getex.run(vec_strings!["mykey"], &mut database);
+        assert_eq!(result.unwrap(), "$5\r\nHello\r\n");
+        assert_eq!(database.lock().unwrap().ttl("mykey"), None);
+        let result = Getex.run(vec_strings!["mykey", "EX", "60"], &mut database);
+        assert_eq!(result.unwrap(), "$5\r\nHello\r\n");
+        assert!(database.lock().unwrap().ttl("mykey").unwrap() > 50);
+        let result = Getex.run(vec_strings!["mykey", "PERSIST"], &mut database);
+        assert_eq!(result.unwrap(), "$5\r\nHello\r\n");
+        assert_eq!(database.lock().unwrap().ttl("mykey"), None);
+        let at = (database.lock().unwrap().clock().unix_millis() + 60000).to_string();
+        Getex
+            .run(vec_strings!["mykey", "PXAT", at], &mut database)
+            .unwrap();
+        assert!(database.lock().unwrap().ttl("mykey").unwrap() > 50);
+    }
+
+    #[test]
+    fn test_02_getex_with_past_unix_time_deletes_the_key() {
+        let mut database = database_with_key();
+        let result = Getex.run(vec_strings!["mykey", "EXAT", "1"], &mut database);
+        assert_eq!(result.unwrap(), "$5\r\nHello\r\n");
+        assert!(database.lock().unwrap().get("mykey").is_none());
+    }
+
+    #[test]
+    fn test_03_getex_errors() {
+        let mut database = database_with_key();
+        let result = Getex.run(vec_strings!["mykey", "EX", "0"], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR invalid expire time in 'getex' command"
+        );
+        let result = Getex.run(vec_strings!["mykey", "EX", "1", "PERSIST"], &mut database);
+        assert!(result.is_err());
+        let result = Getex.run(vec_strings!["mykey", "KEEPTTL"], &mut database);
+        assert!(result.is_err());
+        let result = Getex.run(vec_strings!["missing", "EX", "10"], &mut database);
+        assert_eq!(result.unwrap(), "$-1\r\n");
+    }
+}
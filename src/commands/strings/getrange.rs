@@ -0,0 +1,124 @@
+use super::bitfield::lock;
+use super::bitmap::bytes;
+use crate::commands::{get_as_integer, Runnable};
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::{ErrorStruct, RBulkString};
+use std::sync::{Arc, Mutex};
+
+pub struct Getrange;
+
+impl Runnable<Arc<Mutex<Database>>> for Getrange {
+    /// Returns the substring of the string stored at **key** between the start
+    /// and the end offsets, both included. Negative offsets count from the end
+    /// of the string, so -1 is the last byte.
+    ///
+    /// GETRANGE key start end
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the substring, which is empty if the
+    /// key does not exist or the range is empty.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a non-string value.
+    /// * The offsets are not integers.
+    /// * The buffer [Vec]<[String]> does not contain three elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        if buffer.len() != 3 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "getrange",
+            )));
+        }
+        let (start, end) = (get_as_integer(&buffer[1])?, get_as_integer(&buffer[2])?);
+        let substring = match bytes(&mut database, &buffer[0])? {
+            Some(bytes) => range(bytes, start, end),
+            None => &[],
+        };
+        Ok(RBulkString::encode_bytes(substring))
+    }
+}
+
+/// Returns the bytes between the start and the end, both included, where
+/// negative indexes count from the end.
+fn range(bytes: &[u8], start: isize, end: isize) -> &[u8] {
+    let len = bytes.len() as isize;
+    let normalize = |index: isize| {
+        if index < 0 {
+            (len + index).max(0)
+        } else {
+            index
+        }
+    };
+    let (start, end) = (normalize(start), normalize(end).min(len - 1));
+    if start > end {
+        return &[];
+    }
+    &bytes[start as usize..=end as usize]
+}
+
+#[cfg(test)]
+mod test_getrange {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_getrange_with_positive_and_negative_offsets() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database.lock().unwrap().insert(
+            "mykey".to_string(),
+            TypeSaved::String("This is a string".into()),
+        );
+        let result = Getrange.run(vec_strings!["mykey", "0", "3"], &mut database);
+        assert_eq!(result.unwrap(), "$4\r\nThis\r\n");
+        let result = Getrange.run(vec_strings!["mykey", "-3", "-1"], &mut database);
+        assert_eq!(result.unwrap(), "$3\r\ning\r\n");
+        let result = Getrange.run(vec_strings!["mykey", "0", "-1"], &mut database);
+        assert_eq!(result.unwrap(), "$16\r\nThis is a string\r\n");
+        let result = Getrange.run(vec_strings!["mykey", "10", "100"], &mut database);
+        assert_eq!(result.unwrap(), "$6\r\nstring\r\n");
+    }
+
+    #[test]
+    fn test_02_getrange_empty_ranges() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("mykey".to_string(), TypeSaved::String("value".into()));
+        let result = Getrange.run(vec_strings!["mykey", "3", "1"], &mut database);
+        assert_eq!(result.unwrap(), "$0\r\n\r\n");
+        let result = Getrange.run(vec_strings!["mykey", "-1", "-100"], &mut database);
+        assert_eq!(result.unwrap(), "$0\r\n\r\n");
+        let result = Getrange.run(vec_strings!["missing", "0", "-1"], &mut database);
+        assert_eq!(result.unwrap(), "$0\r\n\r\n");
+        let result = Getrange.run(vec_strings!["mykey", "a", "1"], &mut database);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_03_getrange_keeps_the_bytes_which_are_not_utf8() {
+        use crate::native_types::bulk_string::wire_bytes;
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database.lock().unwrap().insert(
+            "mykey".to_string(),
+            TypeSaved::String(vec![b'a', 0xff, 0xfe]),
+        );
+
+        let result = Getrange.run(vec_strings!["mykey", "1", "-1"], &mut database);
+
+        assert_eq!(wire_bytes(&result.unwrap()), b"$2\r\n\xff\xfe\r\n".to_vec());
+    }
+}
@@ -46,7 +46,7 @@ impl Runnable<Arc<Mutex<Database>>> for Getset {
                 )),
             }
         } else {
-            database.insert(key, TypeSaved::String(new_value));
+            database.insert(key, TypeSaved::String(new_value.into_bytes()));
             Ok(RBulkString::encode("(nil)".to_string()))
         }
     }
@@ -67,7 +67,7 @@ pub mod test_getset {
         let mut data = Arc::new(Mutex::new(Database::new(notifier)));
         data.lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
 
         let buffer = vec_strings!["key", "other"];
         let encoded = Getset.run(buffer, &mut data);
@@ -75,7 +75,7 @@ pub mod test_getset {
         assert_eq!(encoded.unwrap(), "$5\r\nvalue\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("key"),
-            Some(&TypeSaved::String("other".to_string()))
+            Some(&TypeSaved::String("other".into()))
         );
     }
 
@@ -89,7 +89,7 @@ pub mod test_getset {
         assert_eq!(encoded.unwrap(), "$-1\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("key"),
-            Some(&TypeSaved::String("newValue".to_string()))
+            Some(&TypeSaved::String("newValue".into()))
         );
     }
 
@@ -100,7 +100,7 @@ pub mod test_getset {
 
         data.lock()
             .unwrap()
-            .insert("key".to_string(), TypeSaved::String("value".to_string()));
+            .insert("key".to_string(), TypeSaved::String("value".into()));
 
         let buffer = vec_strings![];
         let encoded = Getset.run(buffer, &mut data);
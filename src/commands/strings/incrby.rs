@@ -19,6 +19,7 @@ impl Runnable<Arc<Mutex<Database>>> for Incrby {
     /// Return an [ErrorStruct] if:
     ///
     /// * The key contains a value of the wrong type or contains a string that can not be represented as integer.
+    /// * The result does not fit in a 64 bit signed integer.
     /// * The buffer [Vec]<[String]> more than two elements is received or empty.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
@@ -32,14 +33,10 @@ impl Runnable<Arc<Mutex<Database>>> for Incrby {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        execute_value_modification(&mut database, buffer, incr)
+        execute_value_modification(&mut database, buffer, isize::checked_add)
     }
 }
 
-fn incr(addend1: isize, addend2: isize) -> isize {
-    addend1 + addend2
-}
-
 #[cfg(test)]
 pub mod test_incrby {
     use crate::commands::create_notifier;
@@ -58,7 +55,7 @@ pub mod test_incrby {
         // redis> SET mykey 10
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("10".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("10".into()));
         // redis> INCRBY mykey 3 ---> (integer) 13
         let buffer = vec_strings!["mykey", "3"];
         let encoded = Incrby.run(buffer, &mut data);
@@ -66,7 +63,7 @@ pub mod test_incrby {
         assert_eq!(encoded.unwrap(), ":13\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("mykey"),
-            Some(&TypeSaved::String("13".to_string()))
+            Some(&TypeSaved::String("13".into()))
         );
     }
 
@@ -77,7 +74,7 @@ pub mod test_incrby {
         // redis> SET mykey 10
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("10".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("10".into()));
         // redis> INCRBY mykey -3
         let buffer = vec_strings!["mykey", "-3"];
         let encoded = Incrby.run(buffer, &mut data);
@@ -85,7 +82,7 @@ pub mod test_incrby {
         assert_eq!(encoded.unwrap(), ":7\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("mykey"),
-            Some(&TypeSaved::String("7".to_string()))
+            Some(&TypeSaved::String("7".into()))
         );
     }
 
@@ -96,7 +93,7 @@ pub mod test_incrby {
         // redis> SET mykey -10
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("-10".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("-10".into()));
         // redis> INCRBY mykey 3
         let buffer = vec_strings!["mykey", "3"];
         let encoded = Incrby.run(buffer, &mut data);
@@ -104,7 +101,7 @@ pub mod test_incrby {
         assert_eq!(encoded.unwrap(), ":-7\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("mykey"),
-            Some(&TypeSaved::String("-7".to_string()))
+            Some(&TypeSaved::String("-7".into()))
         );
     }
 
@@ -115,7 +112,7 @@ pub mod test_incrby {
         // redis> SET mykey -10
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("-10".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("-10".into()));
         // redis> INCRBY mykey -3
         let buffer = vec_strings!["mykey", "-3"];
         let encoded = Incrby.run(buffer, &mut data);
@@ -123,7 +120,7 @@ pub mod test_incrby {
         assert_eq!(encoded.unwrap(), ":-13\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("mykey"),
-            Some(&TypeSaved::String("-13".to_string()))
+            Some(&TypeSaved::String("-13".into()))
         );
     }
 
@@ -137,7 +134,7 @@ pub mod test_incrby {
         assert_eq!(encoded.unwrap(), ":3\r\n".to_string());
         assert_eq!(
             data.lock().unwrap().get("mykey"),
-            Some(&TypeSaved::String("3".to_string()))
+            Some(&TypeSaved::String("3".into()))
         );
     }
 
@@ -148,7 +145,7 @@ pub mod test_incrby {
         // redis> SET mykey value
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("value".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("value".into()));
         // redis> INCRBY mykey 1
         let buffer = vec_strings!["mykey", "value"];
         let error = Incrby.run(buffer, &mut data);
@@ -166,7 +163,7 @@ pub mod test_incrby {
         // redis> SET mykey 10
         data.lock()
             .unwrap()
-            .insert("mykey".to_string(), TypeSaved::String("10".to_string()));
+            .insert("mykey".to_string(), TypeSaved::String("10".into()));
         // redis> INCRBY mykey a
         let buffer = vec_strings!["mykey", "a"];
         let error = Incrby.run(buffer, &mut data);
@@ -176,4 +173,26 @@ pub mod test_incrby {
             "ERR value is not an integer or out of range".to_string()
         );
     }
+    #[test]
+    fn test_08_incrby_overflow_keeps_the_value() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
+        // redis> SET mykey 9223372036854775807
+        data.lock().unwrap().insert(
+            "mykey".to_string(),
+            TypeSaved::String("9223372036854775807".into()),
+        );
+        // redis> INCRBY mykey 1
+        let buffer = vec_strings!["mykey", "1"];
+        let error = Incrby.run(buffer, &mut data);
+
+        assert_eq!(
+            error.unwrap_err().print_it(),
+            "ERR increment or decrement would overflow".to_string()
+        );
+        assert_eq!(
+            data.lock().unwrap().get("mykey"),
+            Some(&TypeSaved::String("9223372036854775807".into()))
+        );
+    }
 }
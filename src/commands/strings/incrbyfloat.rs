@@ -0,0 +1,130 @@
+use super::{no_more_values, pop_value, store_value, text};
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::{
+    commands::{get_as_float, Runnable},
+    database::{Database, TypeSaved},
+    err_wrongtype,
+    messages::redis_messages,
+    native_types::{ErrorStruct, RBulkString, RedisType},
+};
+use std::sync::{Arc, Mutex};
+
+pub struct Incrbyfloat;
+
+impl Runnable<Arc<Mutex<Database>>> for Incrbyfloat {
+    /// Increments the floating point number stored at **key** by increment, which
+    /// can be negative. If the **key** does not exist, it is set to 0 before
+    /// performing the operation. The result is stored without exponent nor
+    /// trailing zeros, keeping the expiration time of the **key**.
+    ///
+    /// INCRBYFLOAT key increment
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString]: the value of **key** after the increment.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The key contains a value of the wrong type or contains a string that can not be represented as float.
+    /// * The result is NaN or Infinity.
+    /// * The buffer [Vec]<[String]> does not contain two elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        mut buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        let increment = pop_value(&mut buffer)?;
+        let key = pop_value(&mut buffer)?;
+        no_more_values(&buffer, "incrbyfloat")?;
+
+        let increment = get_as_float(&increment)?;
+        let current = match database.get(&key) {
+            Some(TypeSaved::String(value)) => get_as_float(&text(value))?,
+            Some(_) => return err_wrongtype!(),
+            None => 0.0,
+        };
+        let new_value = current + increment;
+        if !new_value.is_finite() {
+            return Err(ErrorStruct::from(redis_messages::nan_or_infinity()));
+        }
+        // Rust prints the shortest representation which parses back to the same
+        // number, never in scientific notation
+        let new_value = new_value.to_string();
+        store_value(&mut database, &key, new_value.clone().into_bytes());
+        Ok(RBulkString::encode(new_value))
+    }
+}
+
+#[cfg(test)]
+pub mod test_incrbyfloat {
+    use crate::commands::create_notifier;
+
+    use crate::vec_strings;
+
+    use super::*;
+
+    #[test]
+    fn test_01_incrbyfloat_existing_key() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
+        // redis> SET mykey 10.50
+        data.lock()
+            .unwrap()
+            .insert("mykey".to_string(), TypeSaved::String("10.50".into()));
+        // redis> INCRBYFLOAT mykey 0.1
+        let encoded = Incrbyfloat.run(vec_strings!["mykey", "0.1"], &mut data);
+        assert_eq!(encoded.unwrap(), "$4\r\n10.6\r\n".to_string());
+        // redis> INCRBYFLOAT mykey -5
+        let encoded = Incrbyfloat.run(vec_strings!["mykey", "-5"], &mut data);
+        assert_eq!(encoded.unwrap(), "$3\r\n5.6\r\n".to_string());
+        assert_eq!(
+            data.lock().unwrap().get("mykey"),
+            Some(&TypeSaved::String("5.6".into()))
+        );
+    }
+
+    #[test]
+    fn test_02_incrbyfloat_with_exponents() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
+        // redis> SET mykey 5.0e3
+        data.lock()
+            .unwrap()
+            .insert("mykey".to_string(), TypeSaved::String("5.0e3".into()));
+        // redis> INCRBYFLOAT mykey 2.0e2
+        let encoded = Incrbyfloat.run(vec_strings!["mykey", "2.0e2"], &mut data);
+        assert_eq!(encoded.unwrap(), "$4\r\n5200\r\n".to_string());
+        let encoded = Incrbyfloat.run(vec_strings!["other", "1e20"], &mut data);
+        assert_eq!(
+            encoded.unwrap(),
+            "$21\r\n100000000000000000000\r\n".to_string()
+        );
+    }
+
+    #[test]
+    fn test_03_incrbyfloat_errors() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut data = Arc::new(Mutex::new(Database::new(notifier)));
+        data.lock()
+            .unwrap()
+            .insert("mykey".to_string(), TypeSaved::String("value".into()));
+        let error = Incrbyfloat.run(vec_strings!["mykey", "1"], &mut data);
+        assert_eq!(
+            error.unwrap_err().print_it(),
+            "ERR value is not a valid float".to_string()
+        );
+        let error = Incrbyfloat.run(vec_strings!["other", "inf"], &mut data);
+        assert_eq!(
+            error.unwrap_err().print_it(),
+            "ERR increment would produce NaN or Infinity".to_string()
+        );
+        assert!(data.lock().unwrap().get("other").is_none());
+    }
+}
@@ -0,0 +1,257 @@
+use super::bitfield::lock;
+use super::bitmap::bytes;
+use crate::commands::{get_as_integer, Runnable};
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::{ErrorStruct, RBulkString, RInteger, RedisType};
+use std::sync::{Arc, Mutex};
+
+/// Greatest number of cells of the table used to compute the LCS, which uses
+/// at most 512MB as Redis.
+const MAX_TABLE_CELLS: usize = 512 * 1024 * 1024 / 4;
+
+pub struct Lcs;
+
+/// Part of the LCS found in both strings: the range of each one, both included.
+#[derive(Debug, PartialEq)]
+struct Match {
+    first: (usize, usize),
+    second: (usize, usize),
+}
+
+impl Match {
+    fn len(&self) -> usize {
+        self.first.1 - self.first.0 + 1
+    }
+}
+
+impl Runnable<Arc<Mutex<Database>>> for Lcs {
+    /// Returns the longest common subsequence of the strings stored at **key1**
+    /// and **key2**. Missing keys are empty strings.
+    ///
+    /// LCS key1 key2 [LEN] [IDX] [MINMATCHLEN min-match-len] [WITHMATCHLEN]
+    ///
+    /// * LEN: returns the length of the subsequence.
+    /// * IDX: returns the ranges of both strings of each match, from the last to
+    ///   the first, and the length of the subsequence.
+    /// * MINMATCHLEN: with IDX, ignores the matches shorter than this length.
+    /// * WITHMATCHLEN: with IDX, adds the length of each match.
+    ///
+    /// # Return value
+    /// * [String] _encoded_ in [RBulkString]: the subsequence.
+    /// * [String] _encoded_ in [RInteger]: its length, with LEN.
+    /// * [String] _encoded_ in [RArray](crate::native_types::RArray): "matches",
+    ///   the matches, "len" and the length, with IDX.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Some key holds a non-string value.
+    /// * Some option is unknown, or LEN and IDX are used together.
+    /// * The strings are too long.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        if buffer.len() < 2 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "lcs",
+            )));
+        }
+        let (mut len, mut idx, mut min_match_len, mut with_match_len) = (false, false, 0, false);
+        let mut options = buffer[2..].iter();
+        while let Some(option) = options.next() {
+            match option.to_uppercase().as_str() {
+                "LEN" => len = true,
+                "IDX" => idx = true,
+                "WITHMATCHLEN" => with_match_len = true,
+                "MINMATCHLEN" => {
+                    let value = options
+                        .next()
+                        .ok_or_else(|| ErrorStruct::from(redis_messages::syntax_error()))?;
+                    min_match_len = get_as_integer(value)?.max(0) as usize;
+                }
+                _ => return Err(ErrorStruct::from(redis_messages::syntax_error())),
+            }
+        }
+        if len && idx {
+            return Err(ErrorStruct::from(redis_messages::lcs_len_and_idx()));
+        }
+        let first = bytes(&mut database, &buffer[0])?
+            .unwrap_or_default()
+            .to_vec();
+        let second = bytes(&mut database, &buffer[1])?.unwrap_or_default();
+
+        let table = lcs_table(&first, second)?;
+        let lcs_len = table[table.len() - 1];
+        if len {
+            return Ok(RInteger::encode(lcs_len as isize));
+        }
+        let (subsequence, matches) = backtrack(&table, &first, second);
+        if !idx {
+            return Ok(RBulkString::encode_bytes(&subsequence));
+        }
+        let matches: Vec<Match> = matches
+            .into_iter()
+            .filter(|found| found.len() >= min_match_len)
+            .collect();
+        let mut encoded = format!("*4\r\n{}", RBulkString::encode("matches".to_string()));
+        encoded.push_str(&format!("*{}\r\n", matches.len()));
+        for found in matches {
+            encoded.push_str(&format!("*{}\r\n", 2 + with_match_len as usize));
+            for (start, end) in [found.first, found.second] {
+                encoded.push_str("*2\r\n");
+                encoded.push_str(&RInteger::encode(start as isize));
+                encoded.push_str(&RInteger::encode(end as isize));
+            }
+            if with_match_len {
+                encoded.push_str(&RInteger::encode(found.len() as isize));
+            }
+        }
+        encoded.push_str(&RBulkString::encode("len".to_string()));
+        encoded.push_str(&RInteger::encode(lcs_len as isize));
+        Ok(encoded)
+    }
+}
+
+/// Computes the table where the cell (i, j) is the length of the LCS of the
+/// first i bytes of the first string and the first j bytes of the second one.
+fn lcs_table(first: &[u8], second: &[u8]) -> Result<Vec<u32>, ErrorStruct> {
+    let columns = second.len() + 1;
+    let cells = (first.len() + 1)
+        .checked_mul(columns)
+        .filter(|cells| *cells <= MAX_TABLE_CELLS)
+        .ok_or_else(|| ErrorStruct::from(redis_messages::lcs_insufficient_memory()))?;
+    let mut table = vec![0u32; cells];
+    for i in 1..=first.len() {
+        for j in 1..=second.len() {
+            table[i * columns + j] = if first[i - 1] == second[j - 1] {
+                table[(i - 1) * columns + j - 1] + 1
+            } else {
+                table[(i - 1) * columns + j].max(table[i * columns + j - 1])
+            };
+        }
+    }
+    Ok(table)
+}
+
+/// Walks the table from the end of both strings, as Redis does, to build the
+/// subsequence and the matches, from the last to the first.
+fn backtrack(table: &[u32], first: &[u8], second: &[u8]) -> (Vec<u8>, Vec<Match>) {
+    let columns = second.len() + 1;
+    let mut subsequence = Vec::with_capacity(table[table.len() - 1] as usize);
+    let mut matches = Vec::new();
+    let mut current: Option<Match> = None;
+    let (mut i, mut j) = (first.len(), second.len());
+    while i > 0 && j > 0 {
+        let mut emit = false;
+        if first[i - 1] == second[j - 1] {
+            subsequence.push(first[i - 1]);
+            match current.as_mut() {
+                None => {
+                    current = Some(Match {
+                        first: (i - 1, i - 1),
+                        second: (j - 1, j - 1),
+                    })
+                }
+                // The match is contiguous, so it grows backwards
+                Some(found) if found.first.0 == i && found.second.0 == j => {
+                    found.first.0 -= 1;
+                    found.second.0 -= 1;
+                }
+                Some(_) => emit = true,
+            }
+            if i == 1 || j == 1 {
+                emit = true;
+            }
+            i -= 1;
+            j -= 1;
+        } else {
+            if table[(i - 1) * columns + j] > table[i * columns + j - 1] {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+            emit = current.is_some();
+        }
+        if emit {
+            matches.extend(current.take());
+        }
+    }
+    subsequence.reverse();
+    (subsequence, matches)
+}
+
+#[cfg(test)]
+mod test_lcs {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    fn database() -> Arc<Mutex<Database>> {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key1".to_string(), TypeSaved::String("ohmytext".into()));
+        database
+            .lock()
+            .unwrap()
+            .insert("key2".to_string(), TypeSaved::String("mynewtext".into()));
+        database
+    }
+
+    #[test]
+    fn test_01_lcs_and_its_length() {
+        let mut database = database();
+        let result = Lcs.run(vec_strings!["key1", "key2"], &mut database);
+        assert_eq!(result.unwrap(), "$6\r\nmytext\r\n");
+        let result = Lcs.run(vec_strings!["key1", "key2", "LEN"], &mut database);
+        assert_eq!(result.unwrap(), ":6\r\n");
+        let result = Lcs.run(vec_strings!["key1", "missing"], &mut database);
+        assert_eq!(result.unwrap(), "$0\r\n\r\n");
+    }
+
+    #[test]
+    fn test_02_lcs_indexes() {
+        let mut database = database();
+        let result = Lcs.run(vec_strings!["key1", "key2", "IDX"], &mut database);
+        assert_eq!(
+            result.unwrap(),
+            "*4\r\n$7\r\nmatches\r\n*2\r\n*2\r\n*2\r\n:4\r\n:7\r\n*2\r\n:5\r\n:8\r\n\
+             *2\r\n*2\r\n:2\r\n:3\r\n*2\r\n:0\r\n:1\r\n$3\r\nlen\r\n:6\r\n"
+        );
+        let result = Lcs.run(
+            vec_strings!["key1", "key2", "IDX", "MINMATCHLEN", "4", "WITHMATCHLEN"],
+            &mut database,
+        );
+        assert_eq!(
+            result.unwrap(),
+            "*4\r\n$7\r\nmatches\r\n*1\r\n*3\r\n*2\r\n:4\r\n:7\r\n*2\r\n:5\r\n:8\r\n:4\r\n\
+             $3\r\nlen\r\n:6\r\n"
+        );
+    }
+
+    #[test]
+    fn test_03_lcs_errors() {
+        let mut database = database();
+        let result = Lcs.run(vec_strings!["key1", "key2", "LEN", "IDX"], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR If you want both the length and indexes, please just use IDX."
+        );
+        let result = Lcs.run(vec_strings!["key1", "key2", "MINMATCHLEN"], &mut database);
+        assert!(result.is_err());
+        database
+            .lock()
+            .unwrap()
+            .insert("list".to_string(), TypeSaved::List(Default::default()));
+        let result = Lcs.run(vec_strings!["key1", "list"], &mut database);
+        assert!(result.is_err());
+    }
+}
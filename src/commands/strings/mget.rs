@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, Runnable},
+    commands::{strings::text, Runnable},
     database::{Database, TypeSaved},
     messages::redis_messages,
     native_types::{ErrorStruct, RArray, RedisType},
@@ -18,7 +18,6 @@ impl Runnable<Arc<Mutex<Database>>> for Mget {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * The buffer [Vec]<[String]> is empty.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -31,14 +30,13 @@ impl Runnable<Arc<Mutex<Database>>> for Mget {
                 ErrorSeverity::ShutdownServer,
             ))
         })?;
-        check_error_cases(&buffer)?;
 
         let mut values_obtained: Vec<String> = Vec::new();
         buffer
             .iter()
             .for_each(|key| match database.get(&key.to_string()) {
                 Some(value) => match value {
-                    TypeSaved::String(value) => values_obtained.push(value.to_string()),
+                    TypeSaved::String(value) => values_obtained.push(text(value)),
                     _ => values_obtained.push("(nil)".to_string()),
                 },
                 None => {
@@ -49,12 +47,6 @@ impl Runnable<Arc<Mutex<Database>>> for Mget {
     }
 }
 
-fn check_error_cases(buffer: &[String]) -> Result<(), ErrorStruct> {
-    check_empty(buffer, "mget")?;
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod test_get {
     use crate::commands::create_notifier;
@@ -72,11 +64,11 @@ mod test_get {
         database_mock
             .lock()
             .unwrap()
-            .insert("key1".to_string(), TypeSaved::String("value1".to_string()));
+            .insert("key1".to_string(), TypeSaved::String("value1".into()));
         database_mock
             .lock()
             .unwrap()
-            .insert("key2".to_string(), TypeSaved::String("value2".to_string()));
+            .insert("key2".to_string(), TypeSaved::String("value2".into()));
         let result_received = Mget.run(buffer_mock_get, &mut database_mock);
 
         // ->> "*3\r\n $5\r\nvalue\r\n $-1\r\n $5\r\nvalue\r\n"
@@ -100,11 +92,11 @@ mod test_get {
         database_mock
             .lock()
             .unwrap()
-            .insert("key1".to_string(), TypeSaved::String("value1".to_string()));
+            .insert("key1".to_string(), TypeSaved::String("value1".into()));
         database_mock
             .lock()
             .unwrap()
-            .insert("key2".to_string(), TypeSaved::String("value2".to_string()));
+            .insert("key2".to_string(), TypeSaved::String("value2".into()));
 
         let result_received = Mget.run(buffer_mock_get1, &mut database_mock);
         let expected_vec = vec![
@@ -2,27 +2,60 @@ use std::sync::MutexGuard;
 
 use crate::{
     database::{Database, TypeSaved},
+    messages::redis_messages,
     native_types::{ErrorStruct, RBulkString, RInteger, RedisType},
 };
 
 use super::get_as_integer;
 
 pub mod append;
+pub mod bitcount;
+pub mod bitfield;
+pub mod bitfield_ro;
+pub mod bitmap;
+pub mod bitop;
+pub mod bitpos;
+pub mod decr;
 pub mod decrby;
 pub mod getdel;
+pub mod getex;
+pub mod getrange;
 pub mod getset;
+pub mod incr;
 pub mod incrby;
+pub mod incrbyfloat;
+pub mod lcs;
 pub mod mget;
 pub mod mset;
+pub mod setrange;
+pub mod substr;
 
 pub mod get;
+pub mod getbit;
 pub mod set;
+pub mod setbit;
 pub mod strlen;
 
+/// Returns the string value as text, replacing the bytes which are not valid
+/// UTF-8, such as those set by bitmap commands. Replies with the value itself
+/// use [RBulkString::encode_bytes] instead, which keeps those bytes.
+pub fn text(value: &[u8]) -> String {
+    String::from_utf8_lossy(value).into_owned()
+}
+
+/// Applies the operation to the integer stored at the key and the one received,
+/// keeping the expiration time of the key. If the key does not exist, it is
+/// set to 0 before performing the operation.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The value received or the one stored are not integers.
+/// * The operation overflows.
 pub fn execute_value_modification(
     database: &mut MutexGuard<Database>,
     mut buffer: Vec<String>,
-    op: fn(isize, isize) -> isize,
+    op: fn(isize, isize) -> Option<isize>,
 ) -> Result<String, ErrorStruct> {
     let decr = buffer.pop().unwrap(); // extract key and decrement from: Vec<&str> = ["mykey", "10"]
     let key = buffer.pop().unwrap();
@@ -31,15 +64,26 @@ pub fn execute_value_modification(
 
     let current_key_value: isize = string_key_check(database, String::from(&key))?;
 
-    let new_value = op(current_key_value, decr_int);
-    database.insert(key, TypeSaved::String(new_value.to_string()));
+    let new_value = op(current_key_value, decr_int)
+        .ok_or_else(|| ErrorStruct::from(redis_messages::increment_overflow()))?;
+    store_value(database, &key, new_value.to_string().into_bytes());
     Ok(RInteger::encode(new_value)) // as isize
 }
 
+/// Stores the string at the key, keeping its expiration time if it already exists.
+fn store_value(database: &mut Database, key: &str, value: Vec<u8>) {
+    match database.get_mut(key) {
+        Some(stored) => *stored = TypeSaved::String(value),
+        None => {
+            database.insert(key.to_string(), TypeSaved::String(value));
+        }
+    }
+}
+
 pub fn string_key_check(database: &mut Database, key: String) -> Result<isize, ErrorStruct> {
     if let Some(typesaved) = database.get_mut(&key) {
         match typesaved {
-            TypeSaved::String(old_value) => get_as_integer(old_value),
+            TypeSaved::String(old_value) => get_as_integer(&text(old_value)),
             _ => Err(ErrorStruct::new(
                 String::from("ERR"),
                 String::from("key provided is not from strings"),
@@ -48,7 +92,7 @@ pub fn string_key_check(database: &mut Database, key: String) -> Result<isize, E
     } else {
         // key does not exist
         let key_cpy = key.clone();
-        database.insert(key_cpy, TypeSaved::String("0".to_string()));
+        database.insert(key_cpy, TypeSaved::String(b"0".to_vec()));
         get_as_integer(&"0".to_string())
     }
 }
@@ -80,8 +124,11 @@ fn replace_value(
     key: String,
     new_value: String,
 ) -> Result<String, ErrorStruct> {
-    match database.insert(key, TypeSaved::String(new_value)).unwrap() {
-        TypeSaved::String(old_value) => Ok(RBulkString::encode(old_value)),
+    match database
+        .insert(key, TypeSaved::String(new_value.into_bytes()))
+        .unwrap()
+    {
+        TypeSaved::String(old_value) => Ok(RBulkString::encode_bytes(&old_value)),
         _ => Err(ErrorStruct::new(
             String::from("UNKNOWN_ERR"),
             String::from(""),
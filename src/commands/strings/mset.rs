@@ -1,6 +1,6 @@
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{
-    commands::{check_empty, Runnable},
+    commands::Runnable,
     database::{Database, TypeSaved},
     messages::redis_messages,
     native_types::{ErrorStruct, RSimpleString, RedisType},
@@ -18,7 +18,7 @@ impl Runnable<Arc<Mutex<Database>>> for Mset {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * The buffer [Vec]<[String]> is empty or received any key without value.
+    /// * The buffer [Vec]<[String]> received any key without value.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -37,7 +37,7 @@ impl Runnable<Arc<Mutex<Database>>> for Mset {
         keys_and_value.into_iter().for_each(|pair_key_value| {
             database.insert(
                 pair_key_value[0].to_string(),
-                TypeSaved::String(pair_key_value[1].to_string()),
+                TypeSaved::String(pair_key_value[1].clone().into_bytes()),
             );
         });
 
@@ -46,9 +46,7 @@ impl Runnable<Arc<Mutex<Database>>> for Mset {
 }
 
 fn check_error_cases(buffer: &[String]) -> Result<(), ErrorStruct> {
-    check_empty(buffer, "mset")?;
-
-    if buffer.is_empty() || is_odd(buffer) {
+    if is_odd(buffer) {
         // never odd => "key1 value1 key2 value2 ...""
         let error_message = redis_messages::arguments_invalid_to("mset");
         return Err(ErrorStruct::new(
@@ -66,7 +64,7 @@ fn is_odd(buffer: &[String]) -> bool {
 
 #[cfg(test)]
 mod test_mset_function {
-    use crate::commands::create_notifier;
+    use crate::commands::{create_notifier, strings::text};
     use crate::{native_types::RBulkString, vec_strings};
 
     use super::*;
@@ -79,20 +77,20 @@ mod test_mset_function {
         database_mock
             .lock()
             .unwrap()
-            .insert("key1".to_string(), TypeSaved::String("value1".to_string()));
+            .insert("key1".to_string(), TypeSaved::String("value1".into()));
 
         let _ = Mset.run(buffer_mock2, &mut database_mock);
 
         let mut get_received_1 = String::new();
         if let TypeSaved::String(item) = database_mock.lock().unwrap().get("key1").unwrap() {
-            get_received_1 = RBulkString::encode(item.to_string());
+            get_received_1 = RBulkString::encode(text(item));
         }
         let expected = RBulkString::encode("value1_new".to_string());
         assert_eq!(expected, get_received_1);
 
         let mut get_received_2 = String::new();
         if let TypeSaved::String(item) = database_mock.lock().unwrap().get("key2").unwrap() {
-            get_received_2 = RBulkString::encode(item.to_string());
+            get_received_2 = RBulkString::encode(text(item));
         }
 
         let expected = RBulkString::encode("value2".to_string());
@@ -1,7 +1,7 @@
 use std::sync::{Arc, Mutex};
 
 use crate::{
-    commands::{check_empty, Runnable},
+    commands::Runnable,
     database::{Database, TypeSaved},
     messages::redis_messages,
     native_types::{error_severity::ErrorSeverity, ErrorStruct, RSimpleString, RedisType},
@@ -19,7 +19,7 @@ impl Runnable<Arc<Mutex<Database>>> for Set {
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * The buffer [Vec]<[String]> more than two elements is received or empty.
+    /// * The buffer [Vec]<[String]> more than two elements is received.
     /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
     fn run(
         &self,
@@ -37,23 +37,12 @@ impl Runnable<Arc<Mutex<Database>>> for Set {
         let value = buffer[1].to_string();
         let key = buffer[0].to_string();
 
-        database.insert(key, TypeSaved::String(value)); // replace any old value with this key
+        database.insert(key, TypeSaved::String(value.into_bytes())); // replace any old value with this key
         Ok(RSimpleString::encode(redis_messages::ok()))
     }
 }
 
 fn check_error_cases(buffer: &[String]) -> Result<(), ErrorStruct> {
-    check_empty(buffer, "set")?;
-
-    if buffer.len() == 1 {
-        // never "arg1"
-        let error_message = redis_messages::arguments_invalid_to("set");
-        return Err(ErrorStruct::new(
-            error_message.get_prefix(),
-            error_message.get_message(),
-        ));
-    }
-
     // Different error output => checked with src/redis-server!!
     if buffer.len() != 2 {
         // never "arg1 arg2 arg3 ... "
@@ -69,7 +58,7 @@ fn check_error_cases(buffer: &[String]) -> Result<(), ErrorStruct> {
 
 #[cfg(test)]
 mod test_set_function {
-    use crate::commands::create_notifier;
+    use crate::commands::{create_notifier, strings::text};
 
     use crate::{native_types::RBulkString, vec_strings};
 
@@ -95,7 +84,7 @@ mod test_set_function {
         let _ = Set.run(buffer_mock_set, &mut database_mock);
         let mut get_received = String::new();
         if let TypeSaved::String(item) = database_mock.lock().unwrap().get("key").unwrap() {
-            get_received = RBulkString::encode(item.to_string());
+            get_received = RBulkString::encode(text(item));
         }
 
         let expected = RBulkString::encode("value".to_string());
@@ -114,9 +103,9 @@ mod test_set_function {
             .lock()
             .unwrap()
             .get("key2")
-            .unwrap_or(&TypeSaved::String("(nil)".to_string()))
+            .unwrap_or(&TypeSaved::String("(nil)".into()))
         {
-            get_received = RBulkString::encode(item.to_string());
+            get_received = RBulkString::encode(text(item));
         }
 
         let expected = RBulkString::encode("(nil)".to_string());
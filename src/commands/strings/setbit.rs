@@ -0,0 +1,103 @@
+use super::bitmap::{bytes_mut, parse_bit_offset, set_bit};
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::{ErrorStruct, RInteger, RedisType};
+use std::sync::{Arc, Mutex};
+
+pub struct Setbit;
+
+impl Runnable<Arc<Mutex<Database>>> for Setbit {
+    /// Sets or clears the bit at **offset** in the string value stored at **key**.
+    /// The string is grown with zeros to hold the bit, and created if **key** does not exist.
+    ///
+    /// SETBIT key offset value
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the original bit value stored at **offset**.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a non-string value.
+    /// * The offset is not an integer between 0 and 2^32 - 1.
+    /// * The value is not 0 or 1.
+    /// * The buffer [Vec]<[String]> has not three elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = database.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "database",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        if buffer.len() != 3 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "setbit",
+            )));
+        }
+        let offset = parse_bit_offset(&buffer[1])?;
+        let bit = match buffer[2].as_str() {
+            "0" => 0,
+            "1" => 1,
+            _ => return Err(ErrorStruct::from(redis_messages::bit_out_of_range())),
+        };
+        let bytes = bytes_mut(&mut database, &buffer[0], (offset / 8) as usize + 1)?;
+        Ok(RInteger::encode(set_bit(bytes, offset, bit) as isize))
+    }
+}
+
+#[cfg(test)]
+mod test_setbit {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_setbit_grows_the_string_and_returns_the_original_bit() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let result = Setbit.run(vec_strings!["key", "7", "1"], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+        let result = Setbit.run(vec_strings!["key", "7", "0"], &mut database);
+        assert_eq!(result.unwrap(), ":1\r\n");
+        Setbit
+            .run(vec_strings!["key", "8", "1"], &mut database)
+            .unwrap();
+        assert_eq!(
+            database.lock().unwrap().get("key"),
+            Some(&TypeSaved::String(vec![0x00, 0x80]))
+        );
+    }
+
+    #[test]
+    fn test_02_setbit_with_invalid_arguments() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let result = Setbit.run(vec_strings!["key", "-1", "1"], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR bit offset is not an integer or out of range"
+        );
+        let result = Setbit.run(vec_strings!["key", "4294967296", "1"], &mut database);
+        assert!(result.is_err());
+        let result = Setbit.run(vec_strings!["key", "0", "2"], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR bit is not an integer or out of range"
+        );
+        database
+            .lock()
+            .unwrap()
+            .insert("list".to_string(), TypeSaved::List(Default::default()));
+        assert!(Setbit
+            .run(vec_strings!["list", "0", "1"], &mut database)
+            .is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use super::bitfield::lock;
+use super::bitmap::{bytes, bytes_mut};
+use crate::commands::{get_as_integer, Runnable};
+use crate::database::Database;
+use crate::messages::redis_messages;
+use crate::native_types::{ErrorStruct, RInteger, RedisType};
+use std::sync::{Arc, Mutex};
+
+/// Greatest length of a string, 512MB.
+const MAX_STRING_LENGTH: usize = 512 * 1024 * 1024;
+
+pub struct Setrange;
+
+impl Runnable<Arc<Mutex<Database>>> for Setrange {
+    /// Overwrites part of the string stored at **key**, starting at the offset,
+    /// for the entire length of value. If the offset is larger than the length
+    /// of the string, it is padded with zero bytes. A missing key is an empty string.
+    ///
+    /// SETRANGE key offset value
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RInteger]: the length of the string after it was modified.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a non-string value.
+    /// * The offset is negative, or the string would exceed 512MB.
+    /// * The buffer [Vec]<[String]> does not contain three elements.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        let mut database = lock(database)?;
+        if buffer.len() != 3 {
+            return Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                "setrange",
+            )));
+        }
+        let (key, value) = (&buffer[0], buffer[2].as_bytes());
+        let offset = get_as_integer(&buffer[1])?;
+        if offset < 0 {
+            return Err(ErrorStruct::from(redis_messages::offset_out_of_range()));
+        }
+        let offset = offset as usize;
+        if value.is_empty() {
+            // Nothing is written, so a missing key is not created
+            let len = bytes(&mut database, key)?.map_or(0, |bytes| bytes.len());
+            return Ok(RInteger::encode(len as isize));
+        }
+        let end = offset + value.len();
+        if end > MAX_STRING_LENGTH {
+            return Err(ErrorStruct::from(
+                redis_messages::string_exceeds_maximum_size(),
+            ));
+        }
+        let bytes = bytes_mut(&mut database, key, end)?;
+        bytes[offset..end].copy_from_slice(value);
+        Ok(RInteger::encode(bytes.len() as isize))
+    }
+}
+
+#[cfg(test)]
+mod test_setrange {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_setrange_overwrites_part_of_the_string() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database
+            .lock()
+            .unwrap()
+            .insert("key1".to_string(), TypeSaved::String("Hello World".into()));
+        let result = Setrange.run(vec_strings!["key1", "6", "Redis"], &mut database);
+        assert_eq!(result.unwrap(), ":11\r\n");
+        assert_eq!(
+            database.lock().unwrap().get("key1"),
+            Some(&TypeSaved::String("Hello Redis".into()))
+        );
+    }
+
+    #[test]
+    fn test_02_setrange_pads_with_zeros() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let result = Setrange.run(vec_strings!["key2", "6", "Redis"], &mut database);
+        assert_eq!(result.unwrap(), ":11\r\n");
+        assert_eq!(
+            database.lock().unwrap().get("key2"),
+            Some(&TypeSaved::String(b"\0\0\0\0\0\0Redis".to_vec()))
+        );
+    }
+
+    #[test]
+    fn test_03_setrange_empty_values_and_errors() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        let result = Setrange.run(vec_strings!["missing", "10", ""], &mut database);
+        assert_eq!(result.unwrap(), ":0\r\n");
+        assert!(database.lock().unwrap().get("missing").is_none());
+        let result = Setrange.run(vec_strings!["key", "-1", "a"], &mut database);
+        assert_eq!(result.unwrap_err().print_it(), "ERR offset is out of range");
+        let result = Setrange.run(vec_strings!["key", "536870911", "ab"], &mut database);
+        assert_eq!(
+            result.unwrap_err().print_it(),
+            "ERR string exceeds maximum allowed size (proto-max-bulk-len)"
+        );
+    }
+}
@@ -35,7 +35,7 @@ impl Runnable<Arc<Mutex<Database>>> for Strlen {
         })?;
         let key = buffer.pop().unwrap();
         no_more_values(&buffer, "strlen")?;
-        if let Some(typesaved) = database.get_mut(&key) {
+        if let Some(typesaved) = database.get(&key) {
             match typesaved {
                 TypeSaved::String(old_value) => Ok(RInteger::encode(old_value.len() as isize)),
                 _ => err_wrongtype!(),
@@ -59,10 +59,9 @@ pub mod test_strlen {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut data = Arc::new(Mutex::new(Database::new(notifier)));
         // redis> SET mykey somevalue ---> "OK"
-        data.lock().unwrap().insert(
-            "mykey".to_string(),
-            TypeSaved::String("somevalue".to_string()),
-        );
+        data.lock()
+            .unwrap()
+            .insert("mykey".to_string(), TypeSaved::String("somevalue".into()));
         // redis> STRLEN mykey ---> (integer) 9
         let buffer = vec_strings!["mykey"];
         let encoded = Strlen.run(buffer, &mut data);
@@ -0,0 +1,51 @@
+use super::getrange::Getrange;
+use crate::commands::Runnable;
+use crate::database::Database;
+use crate::native_types::ErrorStruct;
+use std::sync::{Arc, Mutex};
+
+pub struct Substr;
+
+impl Runnable<Arc<Mutex<Database>>> for Substr {
+    /// Deprecated alias of GETRANGE.
+    ///
+    /// SUBSTR key start end
+    ///
+    /// # Return value
+    /// [String] _encoded_ in [RBulkString](crate::native_types::RBulkString): the
+    /// substring between the start and the end offsets, both included.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Key holds a non-string value.
+    /// * The offsets are not integers.
+    /// * [Database] received in <[Arc]<[Mutex]>> is poisoned.
+    fn run(
+        &self,
+        buffer: Vec<String>,
+        database: &mut Arc<Mutex<Database>>,
+    ) -> Result<String, ErrorStruct> {
+        Getrange.run(buffer, database)
+    }
+}
+
+#[cfg(test)]
+mod test_substr {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_substr_is_getrange() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Arc::new(Mutex::new(Database::new(notifier)));
+        database.lock().unwrap().insert(
+            "mykey".to_string(),
+            TypeSaved::String("This is a string".into()),
+        );
+        let result = Substr.run(vec_strings!["mykey", "5", "-8"], &mut database);
+        assert_eq!(result.unwrap(), "$4\r\nis a\r\n");
+    }
+}
@@ -99,6 +99,16 @@ impl LogMessage {
         )
     }
 
+    pub fn truncated_dump_loaded(corruption: &str, keys: usize) -> LogMessage {
+        LogMessage::new(
+            1,
+            format!(
+                "Dump file corrupted: {}. Loaded the {} keys before it",
+                corruption, keys
+            ),
+        )
+    }
+
     pub fn log_closed_success() -> LogMessage {
         LogMessage::new(2, "Log center is closed.".to_string())
     }
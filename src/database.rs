@@ -1,21 +1,28 @@
 use crate::commands::server::info_formatter::info_db_formatter;
+use crate::commands::server::info_formatter::info_sections::{title, InfoSections, KEYSPACE};
+use crate::communication::log_messages::LogMessage;
+use crate::dump;
+use crate::logs::server_stats::ServerStats;
 use crate::native_types::error::ErrorStruct;
-use crate::native_types::{RArray, RInteger, RSimpleString, RedisType};
+use crate::rdb::RdbEntry;
 use crate::redis_config;
+use crate::redis_config::DumpFormat;
 use crate::regex::super_regex::SuperRegex;
+use crate::sorted_set::SortedSet;
+use crate::stream::Stream;
+use crate::tcp_protocol::client_atributes::client_fields::ClientFields;
+use crate::tcp_protocol::client_aware::ClientAware;
+use crate::tcp_protocol::client_tracking::ClientTracking;
+use crate::time_expiration::clock::{Clock, SystemClock};
 use crate::time_expiration::expire_info::ExpireInfo;
 use crate::{messages::redis_messages, tcp_protocol::notifier::Notifier};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
-use std::io::Lines;
+use std::fs;
+use std::io::ErrorKind;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    ops::Not,
-};
-use std::{
-    fs::File,
-    io::{BufRead, BufReader, Write},
-};
+use std::thread::{self, JoinHandle};
 
 extern crate rand;
 use rand::seq::IteratorRandom;
@@ -25,13 +32,75 @@ pub struct Database {
     elements: HashMap<String, (ExpireInfo, TypeSaved)>,
     redis_config: Option<Arc<Mutex<RedisConfig>>>,
     notifier: Arc<Mutex<Notifier>>, // https://stackoverflow.com/questions/40384274/rust-mpscsender-cannot-be-shared-between-threads
+    client_tracking: Option<ClientTracking>,
+    server_stats: Option<ServerStats>,
+    current_client: Option<u64>,
+    saves: Arc<SaveCounters>,
+    background_save: Option<JoinHandle<()>>,
+    background_save_scheduled: bool,
+    clock: Arc<dyn Clock>,
+}
+
+/// Changes since the last successful save and the time of that save, shared
+/// with the thread of the background save.
+struct SaveCounters {
+    dirty: AtomicU64,
+    /// Seconds since [UNIX_EPOCH](std::time::UNIX_EPOCH).
+    last_save: AtomicU64,
+}
+
+impl SaveCounters {
+    fn new(clock: &dyn Clock) -> Self {
+        SaveCounters {
+            dirty: AtomicU64::new(0),
+            last_save: AtomicU64::new(clock.unix_secs()),
+        }
+    }
+
+    /// Registers a successful save, which included the first `changes`
+    /// changes. The ones done while saving are kept.
+    fn register_save(&self, changes: u64, clock: &dyn Clock) {
+        let _ = self
+            .dirty
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |dirty| {
+                Some(dirty.saturating_sub(changes))
+            });
+        self.last_save.store(clock.unix_secs(), Ordering::SeqCst);
+    }
+}
+
+/// Copy of the keys of the [Database] which have not expired, taken to be
+/// written to the dump file, with the number of changes it includes.
+pub struct Snapshot {
+    /// Keys with their expire time as unix time in milliseconds.
+    entries: Vec<RdbEntry>,
+    /// Unix time in milliseconds when the snapshot was taken.
+    taken_at: u64,
+    changes: u64,
+}
+
+impl Snapshot {
+    /// Replaces the file at the path with the keys of the snapshot, in the
+    /// given format, without leaving a partial file if the write fails. See
+    /// [Database::take_snapshot].
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Writing the file failed.
+    pub fn write(&self, path: &str, format: DumpFormat) -> Result<(), ErrorStruct> {
+        dump::write_atomically(path, &dump::encode(&self.entries, format, self.taken_at))
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum TypeSaved {
-    String(String),
+    /// Binary-safe string, which can be used as a bitmap.
+    String(Vec<u8>),
     List(VecDeque<String>),
     Set(HashSet<String>),
+    Stream(Stream),
+    SortedSet(SortedSet),
 }
 
 impl Database {
@@ -40,66 +109,143 @@ impl Database {
             elements: HashMap::new(),
             notifier: Arc::new(Mutex::new(notifier)),
             redis_config: None,
+            client_tracking: None,
+            server_stats: None,
+            current_client: None,
+            saves: Arc::new(SaveCounters::new(&SystemClock)),
+            background_save: None,
+            background_save_scheduled: false,
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Database Clock setter. Expirations, idle times and save points are
+    /// measured with it from then on, so it has to be set while the
+    /// database is empty.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.saves = Arc::new(SaveCounters::new(clock.as_ref()));
+        self.clock = clock;
+    }
+
+    /// The [Clock] which gives the current time to the database.
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
     /// Database Redis Config setter
     pub fn set_redis_config(&mut self, redis_config: Arc<Mutex<RedisConfig>>) {
         self.redis_config = Some(redis_config);
     }
 
+    /// Database Client Tracking setter. Once set, the keys read and modified
+    /// are reported to it, so the tracking clients receive the invalidations.
+    pub fn set_client_tracking(&mut self, client_tracking: ClientTracking) {
+        self.client_tracking = Some(client_tracking);
+    }
+
+    /// Database Server Stats setter. Once set, the hits, misses, expirations
+    /// and changes of the keys are counted for INFO.
+    pub fn set_server_stats(&mut self, server_stats: ServerStats) {
+        self.server_stats = Some(server_stats);
+    }
+
+    /// Sets the id of the client whose command is being executed.
+    pub fn set_current_client(&mut self, id: Option<u64>) {
+        self.current_client = id;
+        if let (Some(tracking), Some(id)) = (self.client_tracking.as_ref(), id) {
+            let _ = tracking.start_command(id);
+        }
+    }
+
     /// Creates a new instance of the Database given a specified RedisConfig
     /// This method playes an important role for restoring the Database.
+    /// The dump file can be in the RDB format or in the legacy one, and the
+    /// keys which have already expired, by the received [Clock], are not loaded.
+    /// If there is no dump file yet, the database starts empty.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The dump file exists but can not be read.
+    /// * The dump file is corrupted, telling the offset and the key where. With
+    ///   `dbload-truncated yes`, the keys before the corruption are loaded
+    ///   instead if they are valid, as when the file is truncated.
     pub fn new_from(
         config: Arc<Mutex<RedisConfig>>,
         notifier: Notifier,
+        clock: Arc<dyn Clock>,
     ) -> Result<Self, ErrorStruct> {
         let mut elements = HashMap::new();
-        let file = File::open(
-            config
-                .lock()
-                .map_err(|_| {
-                    ErrorStruct::from(redis_messages::poisoned_lock(
-                        "redis config",
-                        crate::native_types::error_severity::ErrorSeverity::ShutdownServer,
-                    ))
-                })?
-                .db_filename(),
-        )
-        .map_err(|_| {
-            ErrorStruct::from(redis_messages::init_failed(
-                "dbfile name",
-                crate::native_types::error_severity::ErrorSeverity::ShutdownServer,
-            ))
-        })?;
-        let reader = BufReader::new(file);
-        let mut lines = reader.lines();
-        while let Some(line) = lines.next() {
-            match line {
-                Ok(line) => {
-                    let expire_info = get_expire_info(line.clone(), &mut lines)?;
-                    let type_decoded = decode_case(&mut lines)?;
-                    let key_decoded = decode_key(&mut lines)?;
-                    let value_decoded = decode_value(&mut lines, type_decoded)?;
-                    elements.insert(key_decoded, (expire_info, value_decoded));
-                }
-                Err(_) => return Err(ErrorStruct::from(redis_messages::file_read_error())),
+        let (filename, load_truncated) = {
+            let config = config.lock().map_err(|_| {
+                ErrorStruct::from(redis_messages::poisoned_lock(
+                    "redis config",
+                    crate::native_types::error_severity::ErrorSeverity::ShutdownServer,
+                ))
+            })?;
+            (config.db_filename(), config.load_truncated())
+        };
+        let bytes = match fs::read(filename) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(_) => {
+                return Err(ErrorStruct::from(redis_messages::init_failed(
+                    "dbfile name",
+                    crate::native_types::error_severity::ErrorSeverity::ShutdownServer,
+                )))
             }
+        };
+        let dump = dump::read(&bytes);
+        if let Some(corruption) = dump.corruption.as_ref() {
+            if !(load_truncated && corruption.truncated) {
+                return Err(ErrorStruct::from(redis_messages::corrupted_dump(
+                    &corruption.to_string(),
+                )));
+            }
+            notifier.send_log(LogMessage::truncated_dump_loaded(
+                &corruption.to_string(),
+                dump.entries.len(),
+            ))?;
+        }
+        let now = clock.unix_millis();
+        for (key, expire_at, value) in dump.entries {
+            let mut expire_info = ExpireInfo::new(clock.now());
+            match expire_at {
+                Some(expire_at) if expire_at <= now => continue,
+                Some(expire_at) => expire_info.set_timeout_millis(clock.now(), expire_at - now)?,
+                None => {}
+            }
+            elements.insert(key, (expire_info, value));
         }
 
         Ok(Database {
             elements,
             redis_config: Some(config),
             notifier: Arc::new(Mutex::new(notifier)),
+            client_tracking: None,
+            server_stats: None,
+            current_client: None,
+            saves: Arc::new(SaveCounters::new(clock.as_ref())),
+            background_save: None,
+            background_save_scheduled: false,
+            clock,
         })
     }
 
-    /// Returns a vector with a title for the database and its number
-    /// of keys.
-    pub fn info(&self) -> Result<Vec<String>, ErrorStruct> {
+    /// Returns the lines of the *keyspace* section, with the number of keys
+    /// and of keys with a timeout, if that section has been requested.
+    pub fn info(&self, sections: &InfoSections) -> Result<Vec<String>, ErrorStruct> {
+        if !sections.contains(KEYSPACE) {
+            return Ok(Vec::new());
+        }
+        let expires = self
+            .elements
+            .values()
+            .filter(|(expire_info, _)| expire_info.ttl().is_some())
+            .count();
         Ok(vec![
-            info_db_formatter::title(),
-            info_db_formatter::number_of_keys(self.elements.len()),
+            title(KEYSPACE),
+            info_db_formatter::keyspace(self.elements.len(), expires),
         ])
     }
 
@@ -108,18 +254,21 @@ impl Database {
         self.elements.len()
     }
 
-    /// Removes a specified key from the database.
+    /// Removes a specified key from the database. Only a key which existed
+    /// counts as a change.
     pub fn remove(&mut self, key: &str) -> Option<TypeSaved> {
-        if let Some((_, value)) = self.elements.remove(key) {
-            Some(value)
-        } else {
-            None
-        }
+        let (_, value) = self.elements.remove(key)?;
+        self.register_write(key);
+        Some(value)
     }
 
     /// Inserts a key-value pair to the database.
     pub fn insert(&mut self, key: String, value: TypeSaved) -> Option<TypeSaved> {
-        if let Some((_, value)) = self.elements.insert(key, (ExpireInfo::new(), value)) {
+        self.register_write(&key);
+        if let Some((_, value)) = self
+            .elements
+            .insert(key, (ExpireInfo::new(self.clock.now()), value))
+        {
             Some(value)
         } else {
             None
@@ -129,6 +278,11 @@ impl Database {
     /// Database value getter. Important: performs a touch.
     pub fn get(&mut self, key: &str) -> Option<&TypeSaved> {
         let _ = self.private_touch(key, None);
+        self.register_read(key);
+        self.register_access(key);
+        if let Some(stats) = self.server_stats.as_ref() {
+            let _ = stats.register_lookup(self.elements.contains_key(key));
+        }
         if let Some((_, value)) = self.elements.get(key) {
             Some(value)
         } else {
@@ -137,25 +291,94 @@ impl Database {
     }
 
     /// Database value mutable getter. Important: performs a touch.
+    /// Only a key which exists counts as a change.
     pub fn get_mut(&mut self, key: &str) -> Option<&mut TypeSaved> {
         let _ = self.private_touch(key, None);
-        if let Some((_, value)) = self.elements.get_mut(key) {
-            Some(value)
-        } else {
-            None
+        if !self.elements.contains_key(key) {
+            return None;
         }
+        self.register_write(key);
+        self.register_access(key);
+        self.elements.get_mut(key).map(|(_, value)| value)
     }
 
     /// Returns true if the database contains the received key.
     /// Important: performs a touch.
     pub fn contains_key(&mut self, key: &str) -> bool {
         let _ = self.private_touch(key, None);
+        self.register_read(key);
+        self.register_access(key);
         self.elements.contains_key(key)
     }
 
+    /// Returns the access information and the value of a key without
+    /// registering an access, for OBJECT. Expired keys are removed.
+    pub fn object(&mut self, key: &str) -> Option<(&ExpireInfo, &TypeSaved)> {
+        let _ = self.private_touch(key, None);
+        self.elements.get(key).map(|(info, value)| (info, value))
+    }
+
+    /// Returns the access information of a key to change it, for RESTORE.
+    pub fn object_mut(&mut self, key: &str) -> Option<&mut ExpireInfo> {
+        let _ = self.private_touch(key, None);
+        self.elements.get_mut(key).map(|(info, _)| info)
+    }
+
+    /// Renames a key keeping its timeout, overwriting the new key if it exists.
+    /// Returns false if the key does not exist.
+    pub fn rename(&mut self, key: &str, new_key: &str) -> bool {
+        let _ = self.private_touch(key, None);
+        let _ = self.private_touch(new_key, None);
+        if key == new_key {
+            return self.elements.contains_key(key);
+        }
+        match self.elements.remove(key) {
+            Some(entry) => {
+                self.register_write(key);
+                self.register_write(new_key);
+                self.elements.insert(new_key.to_string(), entry);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Empties the database HashMap.
     pub fn clear(&mut self) {
         self.elements.clear();
+        if let Some(tracking) = self.client_tracking.as_ref() {
+            let _ = tracking.invalidate_all(self.current_client);
+        }
+        self.saves.dirty.fetch_add(1, Ordering::SeqCst);
+        if let Some(stats) = self.server_stats.as_ref() {
+            let _ = stats.register_change();
+        }
+    }
+
+    /// Remembers that the current client has read the key, for CLIENT TRACKING.
+    fn register_read(&self, key: &str) {
+        if let (Some(tracking), Some(id)) = (self.client_tracking.as_ref(), self.current_client) {
+            let _ = tracking.remember(id, key);
+        }
+    }
+
+    /// Notifies the clients tracking the key that it has been modified,
+    /// and counts the change since the last save.
+    fn register_write(&self, key: &str) {
+        if let Some(tracking) = self.client_tracking.as_ref() {
+            let _ = tracking.invalidate(&[key.to_string()], self.current_client);
+        }
+        self.saves.dirty.fetch_add(1, Ordering::SeqCst);
+        if let Some(stats) = self.server_stats.as_ref() {
+            let _ = stats.register_change();
+        }
+    }
+
+    /// Registers that a command has accessed the key, for OBJECT IDLETIME and FREQ.
+    fn register_access(&mut self, key: &str) {
+        if let Some((info, _)) = self.elements.get_mut(key) {
+            info.register_access(self.clock.now());
+        }
     }
 
     /// Checks if a key has already expired, in that case, it removes it and returns true.
@@ -167,8 +390,12 @@ impl Database {
         notifier: Option<Arc<Mutex<Notifier>>>,
     ) -> Result<bool, ErrorStruct> {
         if let Some((info, _)) = self.elements.get_mut(key) {
-            if info.is_expired(notifier, key) {
+            if info.is_expired(self.clock.now(), notifier, key) {
                 self.elements.remove(key);
+                self.register_write(key);
+                if let Some(stats) = self.server_stats.as_ref() {
+                    let _ = stats.register_expiration();
+                }
                 Ok(true)
             } else {
                 Ok(false)
@@ -196,11 +423,20 @@ impl Database {
         }
     }
 
+    /// Returns the timeout of a specified key in milliseconds. Important: performs a touch.
+    pub fn ttl_millis(&mut self, key: &str) -> Option<u64> {
+        let _ = self.private_touch(key, None);
+        self.elements
+            .get(key)
+            .and_then(|(info, _)| info.ttl_millis())
+    }
+
     /// Database keys timeout setter. Important: performs a touch.
     pub fn set_ttl(&mut self, key: &str, timeout: u64) -> Result<(), ErrorStruct> {
         let _ = self.private_touch(key, None);
+        self.register_write(key);
         if let Some((info, _)) = self.elements.get_mut(key) {
-            info.set_timeout(timeout)?;
+            info.set_timeout(self.clock.now(), timeout)?;
             Ok(())
         } else {
             let message = redis_messages::key_not_found();
@@ -211,11 +447,23 @@ impl Database {
         }
     }
 
+    /// Database keys timeout setter in milliseconds. Important: performs a touch.
+    pub fn set_ttl_millis(&mut self, key: &str, timeout: u64) -> Result<(), ErrorStruct> {
+        let _ = self.private_touch(key, None);
+        self.register_write(key);
+        if let Some((info, _)) = self.elements.get_mut(key) {
+            info.set_timeout_millis(self.clock.now(), timeout)
+        } else {
+            Err(ErrorStruct::from(redis_messages::key_not_found()))
+        }
+    }
+
     /// Database keys unix timestamp timeout setter. Important: performs a touch.
     pub fn set_ttl_unix_timestamp(&mut self, key: &str, timeout: u64) -> Result<(), ErrorStruct> {
         let _ = self.private_touch(key, None);
+        self.register_write(key);
         if let Some((info, _)) = self.elements.get_mut(key) {
-            info.set_timeout_unix_timestamp(timeout)?;
+            info.set_timeout_unix_timestamp(self.clock.now(), timeout)?;
             Ok(())
         } else {
             let message = redis_messages::key_not_found();
@@ -231,6 +479,7 @@ impl Database {
     /// ExpireInfo persist() for a deeper understanding. Important: performs a touch.
     pub fn persist(&mut self, key: &str) -> Option<u64> {
         let _ = self.private_touch(key, None);
+        self.register_write(key);
         if let Some((info, _)) = self.elements.get_mut(key) {
             info.persist()
         } else {
@@ -248,15 +497,105 @@ impl Database {
     /// including their expiration time and type. See SAVE command.
     /// This method is useful for restoring the database.
     ///
-    /// File format: :{EXPIRE_TIME}:{CASE}+{KEY}+{VALUE}
-    /// Where:
-    /// * EXPIRE_TIME can be any positive value or -1 if its not an expirable key
-    /// encoded as Redis Integer.
-    /// * CASE: 0: String, 1: List, 2: Set encoded as Redis Integer.
-    /// * KEY: Redis Simple String.
-    /// * VALUE: Redis Simple String or Redis Array.
+    /// The file is written in the RDB format of Redis if the [RedisConfig] has
+    /// `dbformat rdb` (see [rdb](crate::rdb)), and in the legacy format otherwise (see
+    /// [dump]). It is replaced only once the new one is completely written.
     pub fn take_snapshot(&mut self) -> Result<(), ErrorStruct> {
-        let mut config = if let Some(config) = self.redis_config.as_ref() {
+        self.wait_background_save();
+        let snapshot = self.snapshot();
+        let (path, format) = self.dump_file()?;
+        snapshot.write(&path, format)?;
+        self.saves
+            .register_save(snapshot.changes, self.clock.as_ref());
+        if let Some(stats) = self.server_stats.as_ref() {
+            stats.register_save(snapshot.changes)?;
+        }
+        Ok(())
+    }
+
+    /// Starts writing a snapshot of the database to the dump file in a
+    /// separate thread, so the clients keep being served meanwhile. See BGSAVE
+    /// command.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Another background save is in progress.
+    /// * There is no dump file available.
+    pub fn background_save(&mut self) -> Result<(), ErrorStruct> {
+        if self.background_save_in_progress() {
+            return Err(ErrorStruct::from(
+                redis_messages::background_save_in_progress(),
+            ));
+        }
+        self.wait_background_save();
+        self.background_save_scheduled = false;
+        let snapshot = self.snapshot();
+        let (path, format) = self.dump_file()?;
+        let saves = Arc::clone(&self.saves);
+        let clock = Arc::clone(&self.clock);
+        let stats = self.server_stats.clone();
+        if let Some(stats) = stats.as_ref() {
+            stats.register_background_save_start()?;
+        }
+        self.background_save = Some(thread::spawn(move || {
+            let saved = snapshot.write(&path, format).is_ok();
+            if saved {
+                saves.register_save(snapshot.changes, clock.as_ref());
+            }
+            if let Some(stats) = stats {
+                let _ = stats.register_background_save_end(saved, snapshot.changes);
+            }
+        }));
+        Ok(())
+    }
+
+    /// Returns true if a background save is writing the dump file.
+    pub fn background_save_in_progress(&self) -> bool {
+        self.background_save
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// Makes the next save point check start a background save, once the one
+    /// in progress finishes. See BGSAVE SCHEDULE.
+    pub fn schedule_background_save(&mut self) {
+        self.background_save_scheduled = true;
+    }
+
+    /// Waits for the background save, if any, to finish.
+    fn wait_background_save(&mut self) {
+        if let Some(handle) = self.background_save.take() {
+            let _ = handle.join();
+        }
+    }
+
+    /// Copies the keys which have not expired, with their time to live.
+    pub fn snapshot(&self) -> Snapshot {
+        let taken_at = self.clock.unix_millis();
+        let now = self.clock.now();
+        let entries = self
+            .elements
+            .iter()
+            .filter_map(|(key, (expire_info, typesaved))| {
+                let mut expire_clone = expire_info.clone();
+                if expire_clone.is_expired(now, Some(self.notifier.clone()), key) {
+                    return None;
+                }
+                let expire_at = expire_clone.ttl_millis().map(|ttl| taken_at + ttl);
+                Some((key.to_string(), expire_at, typesaved.clone()))
+            })
+            .collect();
+        Snapshot {
+            entries,
+            taken_at,
+            changes: self.dirty(),
+        }
+    }
+
+    /// Returns the path of the dump file of the [RedisConfig], and its format.
+    fn dump_file(&self) -> Result<(String, DumpFormat), ErrorStruct> {
+        let config = if let Some(config) = self.redis_config.as_ref() {
             config.lock().map_err(|_| {
                 ErrorStruct::from(redis_messages::poisoned_lock(
                     "redis config",
@@ -268,19 +607,40 @@ impl Database {
                 "no redis config available",
             )));
         };
-        let mut file = config.get_mut_dump_file().unwrap();
-        for (key, (expire_info, typesaved)) in self.elements.iter_mut() {
-            let mut expire_clone = expire_info.clone();
-            if expire_clone
-                .is_expired(Some(self.notifier.clone()), key)
-                .not()
-            {
-                let time = expire_clone.ttl().map(|t| t as isize).unwrap_or(-1);
-                write_integer_to_file(time, &mut file)?;
-                persist_data(key, &mut file, typesaved)?;
-            }
+        Ok((config.db_filename(), config.dump_format()))
+    }
+
+    /// Returns the number of changes since the last successful save.
+    pub fn dirty(&self) -> u64 {
+        self.saves.dirty.load(Ordering::SeqCst)
+    }
+
+    /// Returns the unix time, in seconds, of the last successful save, or of
+    /// the start of the server if there was none.
+    pub fn last_save(&self) -> u64 {
+        self.saves.last_save.load(Ordering::SeqCst)
+    }
+
+    /// Returns true if some of the save points of the [RedisConfig] has been
+    /// reached: at least the given changes were done since the last save, and
+    /// at least the given seconds elapsed, or a background save was scheduled.
+    /// Always false while a background save is in progress.
+    pub fn save_point_reached(&self) -> bool {
+        if self.background_save_in_progress() {
+            return false;
         }
-        Ok(())
+        if self.background_save_scheduled {
+            return true;
+        }
+        let save_points = match self.redis_config.as_ref().map(|config| config.lock()) {
+            Some(Ok(config)) => config.save_points().to_vec(),
+            _ => return false,
+        };
+        let elapsed = self.clock.unix_secs().saturating_sub(self.last_save());
+        let dirty = self.dirty();
+        save_points
+            .iter()
+            .any(|(seconds, changes)| elapsed >= *seconds && dirty >= *changes)
     }
 
     /// Returns all database keys matching the pattern received.
@@ -295,188 +655,46 @@ impl Database {
     }
 }
 
-impl fmt::Display for Database {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Database")
-    }
-}
-
-// Given the lines received moves to the next one, checks if the line is valid
-// and returns a TypeSaved obtained from decoding a value read which can be
-// a Redis Simple String or a Redis Array.
-fn decode_value(
-    lines: &mut Lines<BufReader<File>>,
-    type_decoded: isize,
-) -> Result<TypeSaved, ErrorStruct> {
-    if let Some(line) = lines.next() {
-        match line {
-            Ok(line) => get_matching_typesaved(type_decoded, line, lines),
-            Err(_) => Err(ErrorStruct::from(redis_messages::file_read_error())),
-        }
-    } else {
-        Err(ErrorStruct::from(redis_messages::file_read_error()))
-    }
-}
-
-/// Obtains a specific typesaved according to the type_decoded isize received.
-fn get_matching_typesaved(
-    type_decoded: isize,
-    mut line: String,
-    lines: &mut Lines<BufReader<File>>,
-) -> Result<TypeSaved, ErrorStruct> {
-    match type_decoded {
-        0 => {
-            check_decodable_line(&mut line, '+')?;
-            let value = match RSimpleString::decode(line, lines) {
-                Ok(value) => value,
-                Err(err) => return Err(err),
-            };
-            Ok(TypeSaved::String(value))
-        }
-        1 => {
-            check_decodable_line(&mut line, '*')?;
-            let value = match RArray::decode(line, lines) {
-                Ok(value) => value,
-                Err(err) => return Err(err),
-            };
-            Ok(TypeSaved::List(VecDeque::from(value)))
-        }
-        _ => {
-            check_decodable_line(&mut line, '*')?;
-            let value = match RArray::decode(line, lines) {
-                Ok(value) => value,
-                Err(err) => return Err(err),
-            };
-            Ok(TypeSaved::Set(value.into_iter().collect()))
+impl ClientAware for Arc<Mutex<Database>> {
+    fn set_current_client(&mut self, client: Arc<Mutex<ClientFields>>) {
+        let id = client.lock().ok().map(|fields| fields.id());
+        if let Ok(mut database) = self.lock() {
+            database.set_current_client(id);
         }
     }
 }
 
-/// Given the lines received moves to the next one, checks if the line is valid
-/// and returns a key String.
-fn decode_key(lines: &mut Lines<BufReader<File>>) -> Result<String, ErrorStruct> {
-    if let Some(line) = lines.next() {
-        match line {
-            Ok(mut line) => {
-                check_decodable_line(&mut line, '+')?;
-                RSimpleString::decode(line, lines)
-            }
-            Err(_) => Err(ErrorStruct::from(redis_messages::file_read_error())),
-        }
-    } else {
-        Err(ErrorStruct::from(redis_messages::file_read_error()))
-    }
-}
-
-/// Given the lines received moves to the next one, checks if the line is valid
-/// and returns an isize (0, 1 or 2) identifying the case (String, List or Set).
-fn decode_case(lines: &mut Lines<BufReader<File>>) -> Result<isize, ErrorStruct> {
-    if let Some(line) = lines.next() {
-        match line {
-            Ok(mut line) => {
-                check_decodable_line(&mut line, ':')?;
-                get_case(line, lines)
-            }
-            Err(_) => Err(ErrorStruct::from(redis_messages::file_read_error())),
-        }
-    } else {
-        Err(ErrorStruct::from(redis_messages::file_read_error()))
-    }
-}
-
-/// Obtains an isize from the parameters received and returns it if it matches any
-/// of the 3 possible cases (0: String, 1: List, 2: Set). Any other case, returns error.
-fn get_case(line: String, lines: &mut Lines<BufReader<File>>) -> Result<isize, ErrorStruct> {
-    let value = RInteger::decode(line, lines)?;
-    if value == 0 || value == 1 || value == 2 {
-        return Ok(value);
+impl fmt::Display for Database {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Database")
     }
-    Err(ErrorStruct::from(redis_messages::unexpected_behaviour(
-        "unknown case found at dump",
-    )))
 }
 
-/// Given a string line and its following ones obtains a timeout and returns an instance
-/// of ExpireInfo.
-fn get_expire_info(
-    mut line: String,
-    lines: &mut Lines<BufReader<File>>,
-) -> Result<ExpireInfo, ErrorStruct> {
-    check_decodable_line(&mut line, ':')?;
-    let ttl_decoded = RInteger::decode(line, lines)?;
-    let mut expire_info: ExpireInfo = ExpireInfo::new();
-    if ttl_decoded >= 0 {
-        expire_info.set_timeout(ttl_decoded as u64)?;
-    }
-    Ok(expire_info)
+/// Encodes the bytes as a string of two hexadecimal digits per byte.
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
 }
 
-/// Checks if the first character of the given string line matches the received character.
-fn check_decodable_line(line: &mut String, char: char) -> Result<(), ErrorStruct> {
-    if line.remove(0) != char {
+/// Decodes a string of two hexadecimal digits per byte. Returns error if the
+/// string is not valid.
+pub(crate) fn decode_hex(hex: &str) -> Result<Vec<u8>, ErrorStruct> {
+    if !hex.len().is_multiple_of(2) {
         return Err(ErrorStruct::from(redis_messages::unexpected_behaviour(
-            "unexpected char found at dump",
+            "invalid bytes found at dump",
         )));
     }
-    Ok(())
-}
-
-/// Performs the writing of a String to the given file, while first encoding it as
-/// a Redis Simple String (RSimpleString). Returns error in case writing failed.
-fn write_string_to_file(string: &str, file: &mut File) -> Result<(), ErrorStruct> {
-    file.write_all(RSimpleString::encode(string.to_string()).as_bytes())
-        .map_err(|_| ErrorStruct::from(redis_messages::write_error()))
-}
-
-/// Performs the writing of an isize to the given file, while first encoding it
-/// as a Redis Integer (RInteger). Returns error in case writing failed.
-fn write_integer_to_file(number: isize, file: &mut File) -> Result<(), ErrorStruct> {
-    file.write_all(RInteger::encode(number).as_bytes())
-        .map_err(|_| ErrorStruct::from(redis_messages::write_error()))
-}
-
-/// Performs the writing of a Vec<String> to the given file, while first encoding it
-/// as a Redis Array (RArray). Returns error in case writing failed.
-fn write_array_to_file(vector: Vec<String>, file: &mut File) -> Result<(), ErrorStruct> {
-    file.write_all(RArray::encode(vector).as_bytes())
-        .map_err(|_| ErrorStruct::from(redis_messages::write_error()))
-}
-
-enum TypeCase {
-    String = 0,
-    List = 1,
-    Set = 2,
-}
-
-/// Auxiliar function which performs the writing of a specified pair key-value of the database
-/// to the received file using the established file format: ":{EXPIRE_TIME}:{CASE}+{KEY}+{VALUE}"
-/// Where:
-/// * EXPIRE_TIME can be any positive value or -1 if its not an expirable key
-/// encoded as Redis Integer.
-/// * CASE: 0: String, 1: List, 2: Set encoded as Redis Integer.
-/// * KEY: Redis Simple String.
-/// * VALUE: Redis Simple String or Redis Array.
-fn persist_data(key: &str, file: &mut File, typesaved: &TypeSaved) -> Result<(), ErrorStruct> {
-    match typesaved {
-        TypeSaved::String(value) => {
-            write_integer_to_file(TypeCase::String as isize, file)?; // 0: String Encoding
-            write_string_to_file(key, file)?; // KEY encoded as Redis String
-            write_string_to_file(value, file)?;
-        }
-        TypeSaved::List(values) => {
-            write_integer_to_file(TypeCase::List as isize, file)?; // 1: List Encoding
-            write_string_to_file(key, file)?; // KEY encoded as Redis String
-            let vector: Vec<String> = values.iter().map(|member| member.to_string()).collect();
-            write_array_to_file(vector, file)?;
-        }
-        TypeSaved::Set(values) => {
-            write_integer_to_file(TypeCase::Set as isize, file)?; // 2: Set Encoding
-            write_string_to_file(key, file)?; // KEY encoded as Redis String
-            let vector: Vec<String> = values.iter().map(|member| member.to_string()).collect();
-            write_array_to_file(vector, file)?;
-        }
-    };
-    Ok(())
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| {
+            hex.get(index..index + 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or_else(|| {
+                    ErrorStruct::from(redis_messages::unexpected_behaviour(
+                        "invalid bytes found at dump",
+                    ))
+                })
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -491,21 +709,32 @@ mod test_database {
             strings::{get::Get, set::Set},
             Runnable,
         },
-        native_types::RBulkString,
+        native_types::{RBulkString, RInteger, RedisType},
+        payload::crc64,
+        time_expiration::clock::ManualClock,
         vec_strings,
     };
     use std::fs;
 
+    /// Wraps the keys of a legacy dump with its header and checksum.
+    fn legacy_dump(keys: &[u8]) -> Vec<u8> {
+        let mut bytes = b"#DUMP 2\r\n".to_vec();
+        bytes.extend_from_slice(keys);
+        let checksum = crc64(&bytes);
+        bytes.extend_from_slice(format!("#CRC64 {:016x}\r\n", checksum).as_bytes());
+        bytes
+    }
+
     #[test]
     fn test_01_insert_a_key() {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut database = Database::new(notifier);
-        let value = TypeSaved::String(String::from("hola"));
+        let value = TypeSaved::String("hola".into());
         database.insert("key".to_string(), value);
         let got = database.get("key");
         match got.unwrap() {
             TypeSaved::String(value) => {
-                assert_eq!(value, "hola");
+                assert_eq!(value, b"hola");
             }
             _ => panic!(),
         }
@@ -515,7 +744,7 @@ mod test_database {
     fn test_02_remove_a_key() {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut database = Database::new(notifier);
-        let value = TypeSaved::String(String::from("hola"));
+        let value = TypeSaved::String("hola".into());
         database.insert("key".to_string(), value);
         database.remove("key");
         let got = database.get("key");
@@ -527,7 +756,7 @@ mod test_database {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut database = Database::new(notifier);
         assert!(!database.contains_key("key"));
-        let value = TypeSaved::String(String::from("hola"));
+        let value = TypeSaved::String("hola".into());
         database.insert("key".to_string(), value);
         assert!(database.contains_key("key"));
     }
@@ -536,7 +765,7 @@ mod test_database {
     fn test_04_set_timeout_for_existing_key() {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut database = Database::new(notifier);
-        let value = TypeSaved::String(String::from("hola"));
+        let value = TypeSaved::String("hola".into());
         database.insert("key".to_string(), value);
         database.set_ttl("key", 10).unwrap();
         assert_eq!(database.ttl("key"), Some(9));
@@ -559,7 +788,7 @@ mod test_database {
     fn test_06_set_timeout_for_key_and_let_it_persist() {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut database = Database::new(notifier);
-        let value = TypeSaved::String(String::from("hola"));
+        let value = TypeSaved::String("hola".into());
         database.insert("key".to_string(), value);
         database.set_ttl("key", 10).unwrap();
         assert_eq!(database.persist("key"), Some(9));
@@ -582,16 +811,13 @@ mod test_database {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut original_database = Database::new(notifier);
         original_database.set_redis_config(config);
-        original_database.insert(
-            "key1".to_string(),
-            TypeSaved::String(String::from("value1")),
-        );
+        original_database.insert("key1".to_string(), TypeSaved::String("value1".into()));
 
         original_database.take_snapshot().unwrap();
 
         assert_eq!(
             fs::read("database_01.rdb").unwrap(),
-            b":-1\r\n:0\r\n+key1\r\n+value1\r\n"
+            legacy_dump(b":-1\r\n:0\r\n+key1\r\n+value1\r\n")
         );
     }
 
@@ -611,14 +837,14 @@ mod test_database {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut database = Database::new(notifier);
         database.set_redis_config(config);
-        database.insert("key".to_string(), TypeSaved::String(String::from("value")));
+        database.insert("key".to_string(), TypeSaved::String("value".into()));
         database.set_ttl("key", 5).unwrap();
 
         database.take_snapshot().unwrap();
 
         assert_eq!(
             fs::read("database_08.rdb").unwrap(),
-            b":4\r\n:0\r\n+key\r\n+value\r\n"
+            legacy_dump(b":4\r\n:0\r\n+key\r\n+value\r\n")
         );
     }
 
@@ -644,7 +870,7 @@ mod test_database {
 
         assert_eq!(
             fs::read("database_09.rdb").unwrap(),
-            b":-1\r\n:1\r\n+key\r\n*4\r\n$6\r\nvalue1\r\n$6\r\nvalue2\r\n$6\r\nvalue3\r\n$6\r\nvalue4\r\n"
+            legacy_dump(b":-1\r\n:1\r\n+key\r\n*4\r\n$6\r\nvalue1\r\n$6\r\nvalue2\r\n$6\r\nvalue3\r\n$6\r\nvalue4\r\n")
         );
     }
 
@@ -671,7 +897,7 @@ mod test_database {
 
         assert_eq!(
             fs::read("database_10.rdb").unwrap(),
-            b":-1\r\n:2\r\n+key\r\n*2\r\n$6\r\nvalue1\r\n$6\r\nvalue2\r\n"
+            legacy_dump(b":-1\r\n:2\r\n+key\r\n*2\r\n$6\r\nvalue1\r\n$6\r\nvalue2\r\n")
         );
     }
 
@@ -691,10 +917,7 @@ mod test_database {
         let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
         let mut original_database = Database::new(notifier.clone());
         original_database.set_redis_config(config1.clone());
-        original_database.insert(
-            "key1".to_string(),
-            TypeSaved::String(String::from("value1")),
-        );
+        original_database.insert("key1".to_string(), TypeSaved::String("value1".into()));
         original_database.take_snapshot().unwrap();
 
         let filename2 = "database_11_b.rdb";
@@ -709,7 +932,8 @@ mod test_database {
             .unwrap(),
         ));
 
-        let mut restored_database = Database::new_from(config1, notifier).unwrap();
+        let mut restored_database =
+            Database::new_from(config1, notifier, Arc::new(SystemClock)).unwrap();
         restored_database.set_redis_config(config2);
         restored_database.take_snapshot().unwrap();
 
@@ -741,7 +965,8 @@ mod test_database {
         database.lock().unwrap().take_snapshot().unwrap();
 
         let filename2 = "database_12_b.rdb";
-        let mut restored_database = Database::new_from(config1, notifier).unwrap();
+        let mut restored_database =
+            Database::new_from(config1, notifier, Arc::new(SystemClock)).unwrap();
 
         let config2 = Arc::new(Mutex::new(
             RedisConfig::new(
@@ -797,8 +1022,9 @@ mod test_database {
             .unwrap(),
         ));
 
-        let mut restored_database =
-            Arc::new(Mutex::new(Database::new_from(config1, notifier).unwrap()));
+        let mut restored_database = Arc::new(Mutex::new(
+            Database::new_from(config1, notifier, Arc::new(SystemClock)).unwrap(),
+        ));
         database.lock().unwrap().set_redis_config(config2);
         database.lock().unwrap().take_snapshot().unwrap();
 
@@ -833,7 +1059,7 @@ mod test_database {
         let mut original_database = Database::new(notifier.clone());
         original_database.set_redis_config(config1);
 
-        original_database.insert("key".to_string(), TypeSaved::String(String::from("value")));
+        original_database.insert("key".to_string(), TypeSaved::String("value".into()));
         original_database.set_ttl("key", 2).unwrap();
 
         original_database.take_snapshot().unwrap();
@@ -849,7 +1075,8 @@ mod test_database {
             )
             .unwrap(),
         ));
-        let mut restored_database = Database::new_from(config2.clone(), notifier).unwrap();
+        let mut restored_database =
+            Database::new_from(config2.clone(), notifier, Arc::new(SystemClock)).unwrap();
         restored_database.set_redis_config(config2);
         restored_database.take_snapshot().unwrap();
 
@@ -890,7 +1117,7 @@ mod test_database {
         let _ = original_database.lock().unwrap().take_snapshot();
 
         let mut restored_database = Arc::new(Mutex::new(
-            Database::new_from(config.clone(), notifier).unwrap(),
+            Database::new_from(config.clone(), notifier, Arc::new(SystemClock)).unwrap(),
         ));
 
         // String value check
@@ -933,4 +1160,468 @@ mod test_database {
             RInteger::encode(1)
         );
     }
+
+    #[test]
+    fn test_16_writes_invalidate_the_keys_read_by_tracking_clients() {
+        use crate::tcp_protocol::client_list::ClientList;
+        use crate::tcp_protocol::client_tracking::TrackingOptions;
+        use std::sync::mpsc;
+
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let (log_sender, _) = mpsc::channel();
+        let tracking = ClientTracking::new(Arc::new(Mutex::new(ClientList::new(log_sender))));
+        let mut database = Database::new(notifier);
+        database.set_client_tracking(tracking.clone());
+        tracking.enable(1, TrackingOptions::default()).unwrap();
+
+        database.set_current_client(Some(1));
+        database.get("key");
+        database.set_current_client(Some(2));
+        database.insert("other".to_string(), TypeSaved::String("value".into()));
+        assert!(tracking.options(1).unwrap().is_some());
+
+        // The client is not connected, so its tracking is turned off
+        // when the invalidation of "key" cannot be delivered.
+        database.insert("key".to_string(), TypeSaved::String("value".into()));
+        assert!(tracking.options(1).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_17_reads_writes_and_expirations_are_counted() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let stats = ServerStats::default();
+        let mut database = Database::new(notifier);
+        database.set_server_stats(stats.clone());
+
+        database.insert("key".to_string(), TypeSaved::String("value".into()));
+        database.insert("temp".to_string(), TypeSaved::String("value".into()));
+        database.get("key");
+        database.get("missing");
+        database.set_ttl("temp", 0).unwrap();
+        database.get("temp");
+
+        let snapshot = stats.snapshot().unwrap();
+        assert_eq!(snapshot.keyspace_hits, 1);
+        assert_eq!(snapshot.keyspace_misses, 2);
+        assert_eq!(snapshot.expired_keys, 1);
+        assert!(snapshot.rdb_changes_since_last_save >= 3);
+    }
+
+    #[test]
+    fn test_18_keyspace_info() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Database::new(notifier);
+        database.insert("key".to_string(), TypeSaved::String("value".into()));
+        database.insert("temp".to_string(), TypeSaved::String("value".into()));
+        database.set_ttl("temp", 100).unwrap();
+
+        let info = database.info(&InfoSections::from_buffer(&[])).unwrap();
+        assert_eq!(info, vec!["# Keyspace", "db0:keys=2,expires=1,avg_ttl=0"]);
+        let info = database
+            .info(&InfoSections::from_buffer(&["stats".to_string()]))
+            .unwrap();
+        assert!(info.is_empty());
+    }
+
+    #[test]
+    fn test_19_persist_streams_at_file() {
+        let filename = "database_19.rdb";
+        let config = Arc::new(Mutex::new(
+            RedisConfig::new(
+                String::new(),
+                String::new(),
+                String::from("log.txt"),
+                String::from(filename),
+                0,
+            )
+            .unwrap(),
+        ));
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut original_database = Database::new(notifier.clone());
+        original_database.set_redis_config(config.clone());
+
+        let mut stream = Stream::new();
+        stream.add("1-1", vec_strings!["field", "value"]).unwrap();
+        stream.add("1-2", vec_strings!["a", "1", "b", "2"]).unwrap();
+        original_database.insert("stream".to_string(), TypeSaved::Stream(stream.clone()));
+        original_database.take_snapshot().unwrap();
+
+        let mut restored_database =
+            Database::new_from(config, notifier, Arc::new(SystemClock)).unwrap();
+        assert_eq!(
+            restored_database.get("stream"),
+            Some(&TypeSaved::Stream(stream))
+        );
+    }
+
+    #[test]
+    fn test_20_persist_binary_strings_at_file() {
+        let filename = "database_20.rdb";
+        let config = Arc::new(Mutex::new(
+            RedisConfig::new(
+                String::new(),
+                String::new(),
+                String::from("log.txt"),
+                String::from(filename),
+                0,
+            )
+            .unwrap(),
+        ));
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut original_database = Database::new(notifier.clone());
+        original_database.set_redis_config(config.clone());
+
+        let bitmap = TypeSaved::String(vec![0x80, 0x00, 0xff, b'\r', b'\n']);
+        original_database.insert("bitmap".to_string(), bitmap.clone());
+        original_database.insert("text".to_string(), TypeSaved::String("value".into()));
+        original_database.take_snapshot().unwrap();
+
+        let mut restored_database =
+            Database::new_from(config, notifier, Arc::new(SystemClock)).unwrap();
+        assert_eq!(restored_database.get("bitmap"), Some(&bitmap));
+        assert_eq!(
+            restored_database.get("text"),
+            Some(&TypeSaved::String("value".into()))
+        );
+    }
+
+    #[test]
+    fn test_21_persist_sorted_sets_at_file() {
+        let filename = "database_21.rdb";
+        let config = Arc::new(Mutex::new(
+            RedisConfig::new(
+                String::new(),
+                String::new(),
+                String::from("log.txt"),
+                String::from(filename),
+                0,
+            )
+            .unwrap(),
+        ));
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut original_database = Database::new(notifier.clone());
+        original_database.set_redis_config(config.clone());
+
+        let mut sorted_set = SortedSet::new();
+        sorted_set.insert("Palermo", 3479099956230698.0);
+        sorted_set.insert("Catania", 3479447370796909.0);
+        let sorted_set = TypeSaved::SortedSet(sorted_set);
+        original_database.insert("Sicily".to_string(), sorted_set.clone());
+        original_database.take_snapshot().unwrap();
+
+        let mut restored_database =
+            Database::new_from(config, notifier, Arc::new(SystemClock)).unwrap();
+        assert_eq!(restored_database.get("Sicily"), Some(&sorted_set));
+    }
+
+    #[test]
+    fn test_22_save_points_are_reached_with_enough_changes() {
+        let config = Arc::new(Mutex::new(
+            RedisConfig::new(
+                String::new(),
+                String::new(),
+                String::from("log.txt"),
+                String::from("database_22.rdb"),
+                0,
+            )
+            .unwrap(),
+        ));
+        config
+            .lock()
+            .unwrap()
+            .set_save_points(vec![(3600, 1), (0, 2)]);
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Database::new(notifier);
+        database.set_redis_config(config);
+
+        database.insert("key1".to_string(), TypeSaved::String("value".into()));
+        assert!(!database.save_point_reached());
+        database.insert("key2".to_string(), TypeSaved::String("value".into()));
+        assert!(database.save_point_reached());
+
+        database.take_snapshot().unwrap();
+        assert_eq!(database.dirty(), 0);
+        assert!(!database.save_point_reached());
+        database.schedule_background_save();
+        assert!(database.save_point_reached());
+    }
+
+    #[test]
+    fn test_23_persist_and_restore_in_rdb_format() {
+        let filename = "database_23.rdb";
+        let config = Arc::new(Mutex::new(
+            RedisConfig::new(
+                String::new(),
+                String::new(),
+                String::from("log.txt"),
+                String::from(filename),
+                0,
+            )
+            .unwrap(),
+        ));
+        config.lock().unwrap().set_dump_format(DumpFormat::Rdb);
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut original_database = Database::new(notifier.clone());
+        original_database.set_redis_config(config.clone());
+        let text = TypeSaved::String(b"line\nbreak".to_vec());
+        let list = TypeSaved::List(VecDeque::from(vec_strings!["a", "b"]));
+        original_database.insert("text".to_string(), text.clone());
+        original_database.insert("list".to_string(), list.clone());
+        original_database.set_ttl("list", 100).unwrap();
+        original_database.take_snapshot().unwrap();
+        assert!(fs::read(filename).unwrap().starts_with(b"REDIS0009"));
+
+        let mut restored_database =
+            Database::new_from(config, notifier, Arc::new(SystemClock)).unwrap();
+        assert_eq!(restored_database.get("text"), Some(&text));
+        assert_eq!(restored_database.get("list"), Some(&list));
+        assert!(restored_database.ttl_millis("list").unwrap() > 99_000);
+        assert_eq!(restored_database.ttl_millis("text"), None);
+    }
+
+    #[test]
+    fn test_24_corrupted_files_are_not_loaded_unless_truncated_loading_is_enabled() {
+        let filename = "database_24.rdb";
+        let config = Arc::new(Mutex::new(
+            RedisConfig::new(
+                String::new(),
+                String::new(),
+                String::from("log.txt"),
+                String::from(filename),
+                0,
+            )
+            .unwrap(),
+        ));
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let bytes = legacy_dump(b":-1\r\n:0\r\n+first\r\n+1\r\n:-1\r\n:0\r\n+second\r\n+2\r\n");
+        fs::write(filename, &bytes[..bytes.len() - 28]).unwrap();
+
+        let result = Database::new_from(config.clone(), notifier.clone(), Arc::new(SystemClock));
+        assert_eq!(
+            result.err().unwrap().print_it(),
+            "ERR Bad dump file: Something interrupted file read at offset 30 (key 'second')"
+        );
+
+        config.lock().unwrap().set_load_truncated(true);
+        let mut database = Database::new_from(config, notifier, Arc::new(SystemClock)).unwrap();
+        assert_eq!(
+            database.get("first"),
+            Some(&TypeSaved::String(b"1".to_vec()))
+        );
+        assert_eq!(database.size(), 1);
+    }
+
+    #[test]
+    fn test_25_failed_saves_keep_the_previous_file() {
+        let filename = "database_25.rdb";
+        let config = Arc::new(Mutex::new(
+            RedisConfig::new(
+                String::new(),
+                String::new(),
+                String::from("log.txt"),
+                String::from(filename),
+                0,
+            )
+            .unwrap(),
+        ));
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let mut database = Database::new(notifier);
+        database.set_redis_config(config);
+        database.insert("key".to_string(), TypeSaved::String("value".into()));
+        database.take_snapshot().unwrap();
+        let saved = fs::read(filename).unwrap();
+
+        // The temporary file can not be created where a directory is
+        let temp_path = format!("temp-{}-{}", std::process::id(), filename);
+        fs::create_dir_all(&temp_path).unwrap();
+        database.remove("key");
+        assert!(database.take_snapshot().is_err());
+        assert_eq!(fs::read(filename).unwrap(), saved);
+        let _ = fs::remove_dir(temp_path);
+    }
+
+    #[test]
+    fn test_26_keys_expire_when_the_clock_reaches_their_timeout() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let clock = Arc::new(ManualClock::from_unix_millis(1_000_000));
+        let mut database = Database::new(notifier);
+        database.set_clock(clock.clone());
+        database.insert("key".to_string(), TypeSaved::String("value".into()));
+        database.set_ttl("key", 10).unwrap();
+
+        clock.advance_secs(4);
+        assert_eq!(database.ttl("key"), Some(6));
+        assert_eq!(database.ttl_millis("key"), Some(6000));
+        clock.advance(std::time::Duration::from_millis(5999));
+        assert_eq!(database.ttl_millis("key"), Some(1));
+        clock.advance(std::time::Duration::from_millis(2));
+        assert_eq!(database.get("key"), None);
+        assert_eq!(database.size(), 0);
+    }
+
+    #[test]
+    fn test_27_save_points_wait_for_their_seconds() {
+        let config = Arc::new(Mutex::new(
+            RedisConfig::new(
+                String::new(),
+                String::new(),
+                String::from("log.txt"),
+                String::from("database_27.rdb"),
+                0,
+            )
+            .unwrap(),
+        ));
+        config.lock().unwrap().set_save_points(vec![(60, 1)]);
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let clock = Arc::new(ManualClock::from_unix_millis(1_000_000));
+        let mut database = Database::new(notifier);
+        database.set_clock(clock.clone());
+        database.set_redis_config(config);
+
+        database.insert("key".to_string(), TypeSaved::String("value".into()));
+        clock.advance_secs(59);
+        assert!(!database.save_point_reached());
+        clock.advance_secs(1);
+        assert!(database.save_point_reached());
+        database.take_snapshot().unwrap();
+        assert_eq!(database.last_save(), 1060);
+    }
+
+    #[test]
+    fn test_28_snapshots_keep_the_expire_time_by_the_clock() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let clock = Arc::new(ManualClock::from_unix_millis(1_000_000));
+        let mut database = Database::new(notifier);
+        database.set_clock(clock.clone());
+        database.insert("key".to_string(), TypeSaved::String("value".into()));
+        database.insert("gone".to_string(), TypeSaved::String("value".into()));
+        database.set_ttl_millis("key", 5000).unwrap();
+        database.set_ttl_millis("gone", 1000).unwrap();
+
+        clock.advance_secs(2);
+        let snapshot = database.snapshot();
+
+        assert_eq!(snapshot.taken_at, 1_002_000);
+        assert_eq!(
+            snapshot.entries,
+            vec![(
+                "key".to_string(),
+                Some(1_005_000),
+                TypeSaved::String("value".into())
+            )]
+        );
+    }
+
+    #[test]
+    fn test_29_reads_do_not_invalidate_tracked_keys_and_track_the_reader() {
+        use crate::commands::{
+            lists::lrange::Lrange,
+            sets::{scard::Scard, smembers::Smembers},
+            strings::strlen::Strlen,
+        };
+        use crate::tcp_protocol::client_list::ClientList;
+        use crate::tcp_protocol::client_tracking::TrackingOptions;
+        use std::sync::mpsc;
+
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let (log_sender, _) = mpsc::channel();
+        let tracking = ClientTracking::new(Arc::new(Mutex::new(ClientList::new(log_sender))));
+        let stats = ServerStats::default();
+        let mut database = Database::new(notifier);
+        database.set_client_tracking(tracking.clone());
+        database.set_server_stats(stats.clone());
+        let mut database = Arc::new(Mutex::new(database));
+        RPush.run(vec_strings!["list", "a"], &mut database).unwrap();
+        Sadd.run(vec_strings!["set", "a"], &mut database).unwrap();
+        Set.run(vec_strings!["text", "a"], &mut database).unwrap();
+        tracking.enable(1, TrackingOptions::default()).unwrap();
+        tracking.enable(3, TrackingOptions::default()).unwrap();
+        database.lock().unwrap().set_current_client(Some(1));
+        for key in ["list", "set", "text"] {
+            database.lock().unwrap().get(key);
+        }
+        let changes = stats.snapshot().unwrap().rdb_changes_since_last_save;
+
+        database.lock().unwrap().set_current_client(Some(2));
+        Lrange
+            .run(vec_strings!["list", "0", "-1"], &mut database)
+            .unwrap();
+        Llen.run(vec_strings!["list"], &mut database).unwrap();
+        Smembers.run(vec_strings!["set"], &mut database).unwrap();
+        Sismember
+            .run(vec_strings!["set", "a"], &mut database)
+            .unwrap();
+        Scard.run(vec_strings!["set"], &mut database).unwrap();
+        Strlen.run(vec_strings!["text"], &mut database).unwrap();
+        assert!(tracking.options(1).unwrap().is_some());
+        assert_eq!(
+            stats.snapshot().unwrap().rdb_changes_since_last_save,
+            changes
+        );
+
+        database.lock().unwrap().set_current_client(Some(3));
+        Strlen.run(vec_strings!["text"], &mut database).unwrap();
+        database.lock().unwrap().set_current_client(Some(2));
+        Set.run(vec_strings!["text", "b"], &mut database).unwrap();
+        // Neither client is connected, so their tracking is turned off
+        // when the invalidation of "text" cannot be delivered.
+        assert!(tracking.options(1).unwrap().is_none());
+        assert!(tracking.options(3).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_30_a_missing_dump_file_starts_an_empty_database() {
+        let filename = "database_30.rdb";
+        let _ = fs::remove_file(filename);
+        let config = Arc::new(Mutex::new(
+            RedisConfig::new(
+                String::new(),
+                String::new(),
+                String::from("log.txt"),
+                String::from(filename),
+                0,
+            )
+            .unwrap(),
+        ));
+        assert!(fs::metadata(filename).is_err());
+
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Database::new_from(config, notifier, Arc::new(SystemClock)).unwrap();
+        assert!(database.snapshot().entries.is_empty());
+        assert!(fs::metadata(filename).is_err());
+    }
+
+    #[test]
+    fn test_31_missing_keys_are_not_changed_by_removes_and_mutable_gets() {
+        use crate::tcp_protocol::client_list::ClientList;
+        use crate::tcp_protocol::client_tracking::TrackingOptions;
+        use std::sync::mpsc;
+
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let (log_sender, _) = mpsc::channel();
+        let tracking = ClientTracking::new(Arc::new(Mutex::new(ClientList::new(log_sender))));
+        let stats = ServerStats::default();
+        let mut database = Database::new(notifier);
+        database.set_client_tracking(tracking.clone());
+        database.set_server_stats(stats.clone());
+        database.insert("key".to_string(), TypeSaved::String("value".into()));
+        tracking.enable(1, TrackingOptions::default()).unwrap();
+        database.set_current_client(Some(1));
+        database.get("missing");
+        let changes = stats.snapshot().unwrap().rdb_changes_since_last_save;
+        let dirty = database.dirty();
+
+        database.set_current_client(Some(2));
+        assert!(database.remove("missing").is_none());
+        assert!(database.get_mut("missing").is_none());
+        assert!(tracking.options(1).unwrap().is_some());
+        assert_eq!(
+            stats.snapshot().unwrap().rdb_changes_since_last_save,
+            changes
+        );
+        assert_eq!(database.dirty(), dirty);
+
+        assert!(database.remove("key").is_some());
+        assert_eq!(database.dirty(), dirty + 1);
+    }
 }
@@ -0,0 +1,570 @@
+//! Dump files of the [Database](crate::database::Database): writing its
+//! snapshots without ever leaving a partial file in place, and reading them
+//! back in any of the formats, locating the corruption if there is some.
+//!
+//! A legacy file starts with the header `#DUMP {VERSION}`, followed by the
+//! keys as RESP fragments and the CRC64 of all the previous bytes, as
+//! `#CRC64 {HEX}`. The files of the first version, without header nor
+//! checksum, are still read. See [rdb] for the RDB format.
+
+use crate::database::{decode_hex, encode_hex, TypeSaved};
+use crate::messages::redis_messages;
+use crate::native_types::{ErrorStruct, RArray, RInteger, RSimpleString, RedisType};
+use crate::payload::crc64;
+use crate::rdb::{self, RdbEntry};
+use crate::redis_config::DumpFormat;
+use crate::sorted_set::SortedSet;
+use crate::stream::{now_millis, Stream};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, Lines, Read, Write};
+use std::path::Path;
+use std::process;
+use std::rc::Rc;
+
+/// Version of the legacy files written, the first one with header and checksum.
+pub const LEGACY_VERSION: u16 = 2;
+const LEGACY_HEADER: &str = "#DUMP";
+const LEGACY_CHECKSUM: &str = "#CRC64";
+
+/// Contents of a dump file.
+#[derive(Debug)]
+pub struct Dump {
+    pub format: DumpFormat,
+    /// Version of the RDB file, or of the legacy one: 1 for the files
+    /// without header.
+    pub version: u16,
+    /// Keys with their expire time as unix time in milliseconds. If the file
+    /// is corrupted, the ones found before the corruption.
+    pub entries: Vec<RdbEntry>,
+    pub corruption: Option<Corruption>,
+}
+
+/// Location of the first error found reading a dump file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Corruption {
+    /// Offset of the entry which could not be read, or of the checksum.
+    pub offset: usize,
+    /// Key of the entry which could not be read, if its key was read.
+    pub key: Option<String>,
+    /// Key of the last entry read.
+    pub previous_key: Option<String>,
+    pub detail: String,
+    /// True if the entries before the offset are valid, as when the file is
+    /// truncated. False if its version is not supported or its checksum is
+    /// wrong.
+    pub truncated: bool,
+}
+
+impl Dump {
+    pub(crate) fn new(format: DumpFormat, version: u16) -> Self {
+        Dump {
+            format,
+            version,
+            entries: Vec::new(),
+            corruption: None,
+        }
+    }
+
+    /// Records the corruption found at the offset, and returns the dump.
+    pub(crate) fn corrupted(
+        mut self,
+        offset: usize,
+        key: Option<String>,
+        detail: &str,
+        truncated: bool,
+    ) -> Self {
+        self.corruption = Some(Corruption {
+            offset,
+            key,
+            previous_key: self.entries.last().map(|(key, _, _)| key.to_string()),
+            detail: detail.to_string(),
+            truncated,
+        });
+        self
+    }
+}
+
+impl fmt::Display for Corruption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.detail, self.offset)?;
+        match (self.key.as_ref(), self.previous_key.as_ref()) {
+            (Some(key), _) => write!(f, " (key '{}')", key),
+            (None, Some(previous_key)) => write!(f, " (after key '{}')", previous_key),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Builds a dump file with the keys, in the given format. The expire times
+/// of the legacy format are relative to `now`, in milliseconds.
+pub fn encode(entries: &[RdbEntry], format: DumpFormat, now: u64) -> Vec<u8> {
+    match format {
+        DumpFormat::Rdb => rdb::save(entries),
+        DumpFormat::Legacy => {
+            let mut bytes = format!("{} {}\r\n", LEGACY_HEADER, LEGACY_VERSION).into_bytes();
+            for (key, expire_at, typesaved) in entries {
+                let ttl = expire_at
+                    .map(|expire_at| (expire_at.saturating_sub(now) / 1000) as isize)
+                    .unwrap_or(-1);
+                bytes.extend_from_slice(RInteger::encode(ttl).as_bytes());
+                persist_data(key, &mut bytes, typesaved);
+            }
+            let checksum = crc64(&bytes);
+            bytes.extend_from_slice(checksum_line(checksum).as_bytes());
+            bytes
+        }
+    }
+}
+
+/// Reads a dump file in any of the formats. The keys found before a
+/// corruption, if any, are returned with its location.
+pub fn read(bytes: &[u8]) -> Dump {
+    if bytes.starts_with(b"REDIS") {
+        rdb::read(bytes)
+    } else {
+        read_legacy(bytes)
+    }
+}
+
+/// Replaces the file at the path with the bytes. They are written to a
+/// temporary file of the same directory, flushed to the disk and renamed,
+/// so a failed or interrupted save leaves the previous file intact.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * Writing, flushing or renaming the temporary file failed.
+pub fn write_atomically(path: &str, bytes: &[u8]) -> Result<(), ErrorStruct> {
+    let path = Path::new(path);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!("temp-{}-{}", process::id(), file_name));
+    let written = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(bytes)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&temp_path);
+        return Err(ErrorStruct::from(redis_messages::write_error()));
+    }
+    // The rename is not durable until the directory is flushed too
+    let directory = match path.parent() {
+        Some(directory) if !directory.as_os_str().is_empty() => directory,
+        _ => Path::new("."),
+    };
+    let _ = File::open(directory).and_then(|directory| directory.sync_all());
+    Ok(())
+}
+
+fn checksum_line(checksum: u64) -> String {
+    format!("{} {:016x}\r\n", LEGACY_CHECKSUM, checksum)
+}
+
+/// Reader of a slice which shares the number of bytes consumed, so the
+/// offset of each entry is known while its lines are decoded.
+struct OffsetReader<'a> {
+    bytes: &'a [u8],
+    offset: Rc<Cell<usize>>,
+}
+
+impl Read for OffsetReader<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let mut rest = self.fill_buf()?;
+        let read = rest.read(buffer)?;
+        self.consume(read);
+        Ok(read)
+    }
+}
+
+impl BufRead for OffsetReader<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(&self.bytes[self.offset.get()..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        let offset = (self.offset.get() + amount).min(self.bytes.len());
+        self.offset.set(offset);
+    }
+}
+
+fn read_legacy(bytes: &[u8]) -> Dump {
+    let (version, start) = if bytes.starts_with(LEGACY_HEADER.as_bytes()) {
+        match read_header(bytes) {
+            Some(header) => header,
+            None => {
+                return Dump::new(DumpFormat::Legacy, 0).corrupted(
+                    0,
+                    None,
+                    "Bad dump file: unsupported version",
+                    false,
+                )
+            }
+        }
+    } else {
+        (1, 0)
+    };
+    let mut dump = Dump::new(DumpFormat::Legacy, version);
+    let offset = Rc::new(Cell::new(start));
+    // A last line without its break is not complete, so it is not read
+    let complete = bytes
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |position| position + 1);
+    let mut lines = OffsetReader {
+        bytes: &bytes[..complete],
+        offset: Rc::clone(&offset),
+    }
+    .lines();
+    let now = now_millis();
+    loop {
+        let entry_start = offset.get();
+        let rest = &bytes[entry_start..];
+        if version >= 2 && (rest.is_empty() || rest.starts_with(b"#")) {
+            return read_checksum(dump, bytes, entry_start);
+        }
+        if rest.is_empty() {
+            return dump;
+        }
+        let mut key = None;
+        match read_legacy_entry(&mut lines, now, &mut key) {
+            Ok(entry) => dump.entries.push(entry),
+            Err(error) => {
+                let detail = format!("Bad dump file: {}", error.message());
+                return dump.corrupted(entry_start, key, &detail, true);
+            }
+        }
+    }
+}
+
+/// Returns the version of the legacy header and its length, if the version
+/// is supported.
+fn read_header(bytes: &[u8]) -> Option<(u16, usize)> {
+    let end = bytes.windows(2).position(|window| window == b"\r\n")?;
+    let version = std::str::from_utf8(&bytes[LEGACY_HEADER.len()..end])
+        .ok()?
+        .strip_prefix(' ')?
+        .parse::<u16>()
+        .ok()
+        .filter(|version| (2..=LEGACY_VERSION).contains(version))?;
+    Some((version, end + 2))
+}
+
+/// Checks the checksum at the offset, which must end the file.
+fn read_checksum(dump: Dump, bytes: &[u8], offset: usize) -> Dump {
+    let expected = checksum_line(crc64(&bytes[..offset]));
+    let rest = &bytes[offset..];
+    if rest == expected.as_bytes() {
+        dump
+    } else if rest.starts_with(expected.as_bytes()) {
+        let end = offset + expected.len();
+        dump.corrupted(end, None, "Bad dump file: data after the checksum", true)
+    } else if expected.as_bytes().starts_with(rest) {
+        dump.corrupted(offset, None, "Bad dump file: unexpected end of file", true)
+    } else {
+        dump.corrupted(offset, None, "Bad dump file: wrong checksum", false)
+    }
+}
+
+/// Reads an entry of a legacy file, setting the key once it is read.
+fn read_legacy_entry<B: BufRead>(
+    lines: &mut Lines<B>,
+    now: u64,
+    key: &mut Option<String>,
+) -> Result<RdbEntry, ErrorStruct> {
+    let expire_at = decode_expire_at(lines, now)?;
+    let type_decoded = decode_case(lines)?;
+    let key_decoded = decode_key(lines)?;
+    *key = Some(key_decoded.to_string());
+    let value_decoded = decode_value(lines, type_decoded)?;
+    Ok((key_decoded, expire_at, value_decoded))
+}
+
+/// Returns the next line, or an error if the file ended.
+fn next_line<B: BufRead>(lines: &mut Lines<B>) -> Result<String, ErrorStruct> {
+    match lines.next() {
+        Some(Ok(line)) => Ok(line),
+        _ => Err(ErrorStruct::from(redis_messages::file_read_error())),
+    }
+}
+
+/// Given the lines received moves to the next one, and returns the expire
+/// time of its time to live in seconds, if it is not -1.
+fn decode_expire_at<B: BufRead>(
+    lines: &mut Lines<B>,
+    now: u64,
+) -> Result<Option<u64>, ErrorStruct> {
+    let mut line = next_line(lines)?;
+    check_decodable_line(&mut line, ':')?;
+    let ttl_decoded = RInteger::decode(line, lines)?;
+    Ok(u64::try_from(ttl_decoded).ok().map(|ttl| now + ttl * 1000))
+}
+
+// Given the lines received moves to the next one, checks if the line is valid
+// and returns a TypeSaved obtained from decoding a value read which can be
+// a Redis Simple String or a Redis Array.
+fn decode_value<B: BufRead>(
+    lines: &mut Lines<B>,
+    type_decoded: isize,
+) -> Result<TypeSaved, ErrorStruct> {
+    let line = next_line(lines)?;
+    get_matching_typesaved(type_decoded, line, lines)
+}
+
+/// Obtains a specific typesaved according to the type_decoded isize received.
+fn get_matching_typesaved<B: BufRead>(
+    type_decoded: isize,
+    mut line: String,
+    lines: &mut Lines<B>,
+) -> Result<TypeSaved, ErrorStruct> {
+    match type_decoded {
+        0 => {
+            check_decodable_line(&mut line, '+')?;
+            let value = RSimpleString::decode(line, lines)?;
+            Ok(TypeSaved::String(value.into_bytes()))
+        }
+        1 => {
+            check_decodable_line(&mut line, '*')?;
+            let value = RArray::decode(line, lines)?;
+            Ok(TypeSaved::List(VecDeque::from(value)))
+        }
+        3 => {
+            check_decodable_line(&mut line, '*')?;
+            let value = RArray::decode(line, lines)?;
+            Ok(TypeSaved::Stream(Stream::from_vec(value)?))
+        }
+        4 => {
+            check_decodable_line(&mut line, '+')?;
+            let value = RSimpleString::decode(line, lines)?;
+            Ok(TypeSaved::String(decode_hex(&value)?))
+        }
+        5 => {
+            check_decodable_line(&mut line, '*')?;
+            let value = RArray::decode(line, lines)?;
+            Ok(TypeSaved::SortedSet(SortedSet::from_vec(value)?))
+        }
+        _ => {
+            check_decodable_line(&mut line, '*')?;
+            let value = RArray::decode(line, lines)?;
+            Ok(TypeSaved::Set(value.into_iter().collect()))
+        }
+    }
+}
+
+/// Given the lines received moves to the next one, checks if the line is valid
+/// and returns a key String.
+fn decode_key<B: BufRead>(lines: &mut Lines<B>) -> Result<String, ErrorStruct> {
+    let mut line = next_line(lines)?;
+    check_decodable_line(&mut line, '+')?;
+    RSimpleString::decode(line, lines)
+}
+
+/// Given the lines received moves to the next one, checks if the line is valid
+/// and returns an isize (0 to 5) identifying the case (String, List, Set, Stream, Bytes or SortedSet).
+fn decode_case<B: BufRead>(lines: &mut Lines<B>) -> Result<isize, ErrorStruct> {
+    let mut line = next_line(lines)?;
+    check_decodable_line(&mut line, ':')?;
+    let value = RInteger::decode(line, lines)?;
+    if (0..=5).contains(&value) {
+        return Ok(value);
+    }
+    Err(ErrorStruct::from(redis_messages::unexpected_behaviour(
+        "unknown case found at dump",
+    )))
+}
+
+/// Checks if the first character of the given string line matches the
+/// received character, and removes it.
+fn check_decodable_line(line: &mut String, char: char) -> Result<(), ErrorStruct> {
+    if !line.starts_with(char) {
+        return Err(ErrorStruct::from(redis_messages::unexpected_behaviour(
+            "unexpected char found at dump",
+        )));
+    }
+    line.remove(0);
+    Ok(())
+}
+
+enum TypeCase {
+    String = 0,
+    List = 1,
+    Set = 2,
+    Stream = 3,
+    Bytes = 4,
+    SortedSet = 5,
+}
+
+/// Auxiliar function which performs the writing of a specified pair key-value of the database
+/// using the established file format: ":{EXPIRE_TIME}:{CASE}+{KEY}+{VALUE}", once its
+/// EXPIRE_TIME is written.
+/// Where:
+/// * EXPIRE_TIME can be any positive value or -1 if its not an expirable key
+///   encoded as Redis Integer.
+/// * CASE: 0: String, 1: List, 2: Set, 3: Stream, 4: Bytes, 5: SortedSet encoded as Redis Integer.
+/// * KEY: Redis Simple String.
+/// * VALUE: Redis Simple String or Redis Array. Strings which are not valid UTF-8
+///   or contain line breaks are saved as Bytes, hex encoded in a Redis Simple String.
+fn persist_data(key: &str, bytes: &mut Vec<u8>, typesaved: &TypeSaved) {
+    let (case, value) = match typesaved {
+        TypeSaved::String(value) => match std::str::from_utf8(value) {
+            Ok(text) if !text.contains(&['\r', '\n'][..]) => {
+                (TypeCase::String, RSimpleString::encode(text.to_string()))
+            }
+            _ => (TypeCase::Bytes, RSimpleString::encode(encode_hex(value))),
+        },
+        TypeSaved::List(values) => (
+            TypeCase::List,
+            RArray::encode(values.iter().map(String::from).collect()),
+        ),
+        TypeSaved::Set(values) => (
+            TypeCase::Set,
+            RArray::encode(values.iter().map(String::from).collect()),
+        ),
+        TypeSaved::Stream(stream) => (TypeCase::Stream, RArray::encode(stream.to_vec())),
+        TypeSaved::SortedSet(sorted_set) => {
+            (TypeCase::SortedSet, RArray::encode(sorted_set.to_vec()))
+        }
+    };
+    bytes.extend_from_slice(RInteger::encode(case as isize).as_bytes());
+    bytes.extend_from_slice(RSimpleString::encode(key.to_string()).as_bytes());
+    bytes.extend_from_slice(value.as_bytes());
+}
+
+#[cfg(test)]
+mod test_dump {
+    use super::*;
+    use crate::vec_strings;
+
+    fn entries() -> Vec<RdbEntry> {
+        vec![
+            (
+                "string".to_string(),
+                None,
+                TypeSaved::String(b"value".to_vec()),
+            ),
+            (
+                "bytes".to_string(),
+                None,
+                TypeSaved::String(b"a\r\nb".to_vec()),
+            ),
+            (
+                "list".to_string(),
+                None,
+                TypeSaved::List(vec_strings!["a", "b"].into_iter().collect()),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_01_legacy_files_have_header_and_checksum() {
+        let bytes = encode(&entries()[..1], DumpFormat::Legacy, 0);
+        let body = b"#DUMP 2\r\n:-1\r\n:0\r\n+string\r\n+value\r\n";
+        let mut expected = body.to_vec();
+        expected.extend_from_slice(format!("#CRC64 {:016x}\r\n", crc64(body)).as_bytes());
+        assert_eq!(bytes, expected);
+
+        let dump = read(&bytes);
+        assert_eq!((dump.format, dump.version), (DumpFormat::Legacy, 2));
+        assert_eq!(dump.entries, entries()[..1].to_vec());
+        assert_eq!(dump.corruption, None);
+    }
+
+    #[test]
+    fn test_02_files_without_header_are_read() {
+        let dump = read(b":-1\r\n:0\r\n+key\r\n+value\r\n");
+        assert_eq!(dump.version, 1);
+        assert_eq!(
+            dump.entries,
+            vec![(
+                "key".to_string(),
+                None,
+                TypeSaved::String(b"value".to_vec())
+            )]
+        );
+        assert_eq!(dump.corruption, None);
+    }
+
+    #[test]
+    fn test_03_truncated_files_report_the_entry_not_read() {
+        let bytes = encode(&entries(), DumpFormat::Legacy, 0);
+        let list_start = bytes
+            .windows(6)
+            .position(|window| window == b"+list\r")
+            .unwrap()
+            - 9;
+        let dump = read(&bytes[..list_start + 16]);
+        assert_eq!(dump.entries, entries()[..2].to_vec());
+        let corruption = dump.corruption.unwrap();
+        assert_eq!(corruption.offset, list_start);
+        assert_eq!(corruption.key, Some("list".to_string()));
+        assert!(corruption.truncated);
+        assert_eq!(
+            corruption.to_string(),
+            format!(
+                "Bad dump file: Something interrupted file read at offset {} (key 'list')",
+                list_start
+            )
+        );
+
+        let dump = read(&bytes[..list_start]);
+        let corruption = dump.corruption.unwrap();
+        assert_eq!(corruption.offset, list_start);
+        assert_eq!(
+            corruption.to_string(),
+            format!(
+                "Bad dump file: unexpected end of file at offset {} (after key 'bytes')",
+                list_start
+            )
+        );
+    }
+
+    #[test]
+    fn test_04_every_truncation_is_detected() {
+        for format in [DumpFormat::Legacy, DumpFormat::Rdb] {
+            let bytes = encode(&entries(), format, 0);
+            for len in 1..bytes.len() {
+                let dump = read(&bytes[..len]);
+                let corruption = dump.corruption.unwrap();
+                assert!(corruption.offset <= len);
+                assert!(dump.entries.len() < entries().len() || corruption.truncated);
+            }
+        }
+    }
+
+    #[test]
+    fn test_05_wrong_checksums_are_detected() {
+        let mut bytes = encode(&entries(), DumpFormat::Legacy, 0);
+        let position = bytes.iter().position(|byte| *byte == b'v').unwrap();
+        bytes[position] = b'w';
+        let dump = read(&bytes);
+        let corruption = dump.corruption.unwrap();
+        assert_eq!(corruption.detail, "Bad dump file: wrong checksum");
+        assert_eq!(corruption.offset, bytes.len() - 25);
+        assert!(!corruption.truncated);
+
+        let dump = read(b"#DUMP 9\r\n");
+        assert_eq!(
+            dump.corruption.unwrap().to_string(),
+            "Bad dump file: unsupported version at offset 0"
+        );
+    }
+
+    #[test]
+    fn test_06_write_atomically_replaces_the_file() {
+        let filename = "dump_06.rdb";
+        fs::write(filename, "a previous and longer content").unwrap();
+        write_atomically(filename, b"new").unwrap();
+        assert_eq!(fs::read(filename).unwrap(), b"new");
+        assert!(!Path::new(&format!("temp-{}-{}", process::id(), filename)).exists());
+        let _ = fs::remove_file(filename);
+
+        assert!(write_atomically("missing_directory/dump.rdb", b"new").is_err());
+    }
+}
@@ -0,0 +1,283 @@
+//! Keys of a dump as JSON Lines, one object per key, and as CSV:
+//!
+//! ```text
+//! {"key":"name","type":"string","expire_at":null,"value":"text"}
+//! {"key":"raw","type":"string","expire_at":1700000000000,"value_hex":"00ff"}
+//! {"key":"queue","type":"list","expire_at":null,"value":["a","b"]}
+//! {"key":"tags","type":"set","expire_at":null,"value":["x","y"]}
+//! {"key":"rank","type":"zset","expire_at":null,"value":[["a",1.5],["b","inf"]]}
+//! {"key":"events","type":"stream","expire_at":null,"value":["1-1","1","1-1","2","f","v","0"]}
+//! ```
+//!
+//! The expire time is the unix time in milliseconds. Strings which are not
+//! valid UTF-8 are written in hexadecimal, the infinite scores as strings, and
+//! the streams as in the legacy dumps: the last id, the entries and the
+//! consumer groups, each preceded by their number.
+
+use super::json::Json;
+use crate::database::{decode_hex, encode_hex, TypeSaved};
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use crate::rdb::RdbEntry;
+use crate::sorted_set::SortedSet;
+use crate::stream::Stream;
+
+/// Columns of the CSV export. The value of the lists, sets, sorted sets and
+/// streams is their JSON.
+pub const CSV_HEADER: &str = "key,type,expire_at,value";
+
+/// Name of the type of the value, as the TYPE command.
+pub fn type_name(value: &TypeSaved) -> &'static str {
+    match value {
+        TypeSaved::String(_) => "string",
+        TypeSaved::List(_) => "list",
+        TypeSaved::Set(_) => "set",
+        TypeSaved::SortedSet(_) => "zset",
+        TypeSaved::Stream(_) => "stream",
+    }
+}
+
+/// Returns the line of JSON Lines of the key.
+pub fn to_json(entry: &RdbEntry) -> Json {
+    let (key, expire_at, value) = entry;
+    let mut members = vec![
+        ("key".to_string(), Json::String(key.to_string())),
+        (
+            "type".to_string(),
+            Json::String(type_name(value).to_string()),
+        ),
+        (
+            "expire_at".to_string(),
+            expire_at.map_or(Json::Null, |expire_at| Json::Number(expire_at.to_string())),
+        ),
+    ];
+    members.push(match value {
+        TypeSaved::String(bytes) => match std::str::from_utf8(bytes) {
+            Ok(text) => ("value".to_string(), Json::String(text.to_string())),
+            Err(_) => ("value_hex".to_string(), Json::String(encode_hex(bytes))),
+        },
+        _ => ("value".to_string(), value_to_json(value)),
+    });
+    Json::Object(members)
+}
+
+/// Returns the key of a line of JSON Lines.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * Some member is missing or has a wrong type.
+/// * The stream is not valid.
+pub fn from_json(json: &Json) -> Result<RdbEntry, ErrorStruct> {
+    let key = json
+        .get("key")
+        .and_then(Json::as_str)
+        .ok_or_else(|| invalid("missing key"))?;
+    let expire_at = match json.get("expire_at") {
+        None | Some(Json::Null) => None,
+        Some(expire_at) => Some(
+            expire_at
+                .as_u64()
+                .ok_or_else(|| invalid("invalid expire_at"))?,
+        ),
+    };
+    let kind = json
+        .get("type")
+        .and_then(Json::as_str)
+        .ok_or_else(|| invalid("missing type"))?;
+    let value = json.get("value");
+    let value = match kind {
+        "string" => match (value.and_then(Json::as_str), json.get("value_hex")) {
+            (Some(text), _) => TypeSaved::String(text.as_bytes().to_vec()),
+            (None, Some(hex)) => TypeSaved::String(decode_hex(
+                hex.as_str().ok_or_else(|| invalid("invalid value_hex"))?,
+            )?),
+            (None, None) => return Err(invalid("missing value")),
+        },
+        "list" => TypeSaved::List(strings(value)?.into_iter().collect()),
+        "set" => TypeSaved::Set(strings(value)?.into_iter().collect()),
+        "zset" => {
+            let mut sorted_set = SortedSet::new();
+            for pair in value
+                .and_then(Json::as_array)
+                .ok_or_else(|| invalid("missing value"))?
+            {
+                match pair.as_array() {
+                    Some([Json::String(member), score]) => {
+                        sorted_set.insert(member, score_from_json(score)?)
+                    }
+                    _ => return Err(invalid("invalid member")),
+                };
+            }
+            TypeSaved::SortedSet(sorted_set)
+        }
+        "stream" => TypeSaved::Stream(Stream::from_vec(strings(value)?)?),
+        _ => return Err(invalid("unknown type")),
+    };
+    Ok((key.to_string(), expire_at, value))
+}
+
+/// Returns the line of CSV of the key.
+pub fn to_csv(entry: &RdbEntry) -> String {
+    let (key, expire_at, value) = entry;
+    let value = match value {
+        TypeSaved::String(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        _ => value_to_json(value).to_string(),
+    };
+    [
+        csv_field(key),
+        type_name(&entry.2).to_string(),
+        expire_at
+            .map(|expire_at| expire_at.to_string())
+            .unwrap_or_default(),
+        csv_field(&value),
+    ]
+    .join(",")
+}
+
+fn value_to_json(value: &TypeSaved) -> Json {
+    let strings = |values: Vec<String>| Json::Array(values.into_iter().map(Json::String).collect());
+    match value {
+        TypeSaved::String(bytes) => Json::String(String::from_utf8_lossy(bytes).into_owned()),
+        TypeSaved::List(list) => strings(list.iter().cloned().collect()),
+        TypeSaved::Set(set) => {
+            let mut members: Vec<String> = set.iter().cloned().collect();
+            members.sort();
+            strings(members)
+        }
+        TypeSaved::SortedSet(sorted_set) => Json::Array(
+            sorted_set
+                .iter()
+                .map(|(member, score)| {
+                    Json::Array(vec![Json::String(member.to_string()), score_to_json(score)])
+                })
+                .collect(),
+        ),
+        TypeSaved::Stream(stream) => strings(stream.to_vec()),
+    }
+}
+
+fn score_to_json(score: f64) -> Json {
+    if score.is_infinite() {
+        Json::String(if score > 0.0 { "inf" } else { "-inf" }.to_string())
+    } else {
+        Json::Number(score.to_string())
+    }
+}
+
+fn score_from_json(score: &Json) -> Result<f64, ErrorStruct> {
+    match score {
+        Json::String(text) if text == "inf" => Ok(f64::INFINITY),
+        Json::String(text) if text == "-inf" => Ok(f64::NEG_INFINITY),
+        _ => score.as_f64().ok_or_else(|| invalid("invalid score")),
+    }
+}
+
+fn strings(value: Option<&Json>) -> Result<Vec<String>, ErrorStruct> {
+    value
+        .and_then(Json::as_array)
+        .ok_or_else(|| invalid("missing value"))?
+        .iter()
+        .map(|element| {
+            element
+                .as_str()
+                .map(String::from)
+                .ok_or_else(|| invalid("invalid element"))
+        })
+        .collect()
+}
+
+/// Quotes the field if it has commas, quotes or line breaks.
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn invalid(detail: &str) -> ErrorStruct {
+    ErrorStruct::from(redis_messages::invalid_json(detail))
+}
+
+#[cfg(test)]
+mod test_export {
+    use super::*;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_every_type_is_exported_and_imported_back() {
+        let mut sorted_set = SortedSet::new();
+        sorted_set.insert("a", 1.5);
+        sorted_set.insert("b", f64::INFINITY);
+        let mut stream = Stream::new();
+        stream.add("1-1", vec_strings!["f", "v"]).unwrap();
+        let entries = [
+            (
+                "text".to_string(),
+                Some(1700000000000),
+                TypeSaved::String(b"a \"b\"".to_vec()),
+            ),
+            ("raw".to_string(), None, TypeSaved::String(vec![0, 255])),
+            (
+                "list".to_string(),
+                None,
+                TypeSaved::List(vec_strings!["x", "y"].into_iter().collect()),
+            ),
+            (
+                "set".to_string(),
+                None,
+                TypeSaved::Set(vec_strings!["y", "x"].into_iter().collect()),
+            ),
+            ("zset".to_string(), None, TypeSaved::SortedSet(sorted_set)),
+            ("stream".to_string(), None, TypeSaved::Stream(stream)),
+        ];
+        let lines: Vec<String> = entries
+            .iter()
+            .map(|entry| to_json(entry).to_string())
+            .collect();
+        assert_eq!(
+            lines[..2],
+            [
+                "{\"key\":\"text\",\"type\":\"string\",\"expire_at\":1700000000000,\"value\":\"a \\\"b\\\"\"}",
+                "{\"key\":\"raw\",\"type\":\"string\",\"expire_at\":null,\"value_hex\":\"00ff\"}",
+            ]
+        );
+        assert_eq!(
+            lines[3],
+            "{\"key\":\"set\",\"type\":\"set\",\"expire_at\":null,\"value\":[\"x\",\"y\"]}"
+        );
+        assert_eq!(
+            lines[4],
+            "{\"key\":\"zset\",\"type\":\"zset\",\"expire_at\":null,\"value\":[[\"a\",1.5],[\"b\",\"inf\"]]}"
+        );
+        for (line, entry) in lines.iter().zip(entries.iter()) {
+            assert_eq!(&from_json(&Json::parse(line).unwrap()).unwrap(), entry);
+        }
+    }
+
+    #[test]
+    fn test_02_csv_quotes_the_fields_when_needed() {
+        let entry = (
+            "a,b".to_string(),
+            Some(5),
+            TypeSaved::List(vec_strings!["x\"y"].into_iter().collect()),
+        );
+        assert_eq!(to_csv(&entry), "\"a,b\",list,5,\"[\"\"x\\\"\"y\"\"]\"");
+        let entry = ("k".to_string(), None, TypeSaved::String(b"v".to_vec()));
+        assert_eq!(to_csv(&entry), "k,string,,v");
+    }
+
+    #[test]
+    fn test_03_invalid_lines_are_rejected() {
+        let json = Json::parse("{\"key\":\"k\",\"type\":\"hash\",\"value\":[]}").unwrap();
+        assert_eq!(
+            from_json(&json).unwrap_err().print_it(),
+            "ERR invalid JSON: unknown type"
+        );
+        let json = Json::parse("{\"key\":\"k\",\"type\":\"list\",\"value\":[1]}").unwrap();
+        assert!(from_json(&json).is_err());
+        let json = Json::parse("{\"type\":\"string\",\"value\":\"v\"}").unwrap();
+        assert!(from_json(&json).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+//! The subset of JSON needed to export and import the keys of a dump.
+
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    /// Kept as written, so integers of 64 bits are not rounded.
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    /// Members in the order they were written.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Returns the value of the member of an object.
+    pub fn get(&self, name: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members
+                .iter()
+                .find(|(member, _)| member == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Json::Number(number) => number.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(number) => number.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Parses a JSON text, which must hold a single value.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The text is not valid JSON, telling the offset where.
+    pub fn parse(text: &str) -> Result<Json, ErrorStruct> {
+        let mut parser = Parser {
+            chars: text.char_indices().collect(),
+            position: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.position < parser.chars.len() {
+            return Err(parser.error("unexpected data after the value"));
+        }
+        Ok(value)
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Number(number) => write!(f, "{}", number),
+            Json::String(string) => write_string(f, string),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(members) => {
+                write!(f, "{{")?;
+                for (index, (name, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, name)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, string: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for char in string.chars() {
+        match char {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            char if (char as u32) < 0x20 => write!(f, "\\u{:04x}", char as u32)?,
+            char => write!(f, "{}", char)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+struct Parser {
+    /// Characters with their offset in bytes.
+    chars: Vec<(usize, char)>,
+    position: usize,
+}
+
+impl Parser {
+    fn error(&self, detail: &str) -> ErrorStruct {
+        let offset = self
+            .chars
+            .get(self.position)
+            .map(|(offset, _)| *offset)
+            .unwrap_or_else(|| self.chars.last().map_or(0, |(offset, _)| offset + 1));
+        ErrorStruct::from(redis_messages::invalid_json(&format!(
+            "{} at offset {}",
+            detail, offset
+        )))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).map(|(_, char)| *char)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek();
+        self.position += 1;
+        char
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), ErrorStruct> {
+        for char in expected.chars() {
+            if self.next() != Some(char) {
+                self.position -= 1;
+                return Err(self.error(&format!("expected '{}'", expected)));
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Json, ErrorStruct> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('n') => self.expect("null").map(|_| Json::Null),
+            Some('t') => self.expect("true").map(|_| Json::Bool(true)),
+            Some('f') => self.expect("false").map(|_| Json::Bool(false)),
+            Some('"') => self.string().map(Json::String),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some('-' | '0'..='9') => self.number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, ErrorStruct> {
+        self.position += 1;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Json::Array(values)),
+                _ => {
+                    self.position -= 1;
+                    return Err(self.error("expected ',' or ']'"));
+                }
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, ErrorStruct> {
+        self.position += 1;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a member name"));
+            }
+            let name = self.string()?;
+            self.skip_whitespace();
+            self.expect(":")?;
+            members.push((name, self.value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Json::Object(members)),
+                _ => {
+                    self.position -= 1;
+                    return Err(self.error("expected ',' or '}'"));
+                }
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, ErrorStruct> {
+        let start = self.position;
+        while matches!(self.peek(), Some('-' | '+' | '.' | 'e' | 'E' | '0'..='9')) {
+            self.position += 1;
+        }
+        let number: String = self.chars[start..self.position]
+            .iter()
+            .map(|(_, char)| char)
+            .collect();
+        if number.parse::<f64>().is_err() {
+            self.position = start;
+            return Err(self.error("invalid number"));
+        }
+        Ok(Json::Number(number))
+    }
+
+    fn string(&mut self) -> Result<String, ErrorStruct> {
+        self.position += 1;
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => {
+                    let escaped = match self.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.unicode_escape()?,
+                        _ => {
+                            self.position -= 1;
+                            return Err(self.error("invalid escape"));
+                        }
+                    };
+                    string.push(escaped);
+                }
+                Some(char) if (char as u32) >= 0x20 => string.push(char),
+                _ => {
+                    self.position -= 1;
+                    return Err(self.error("unterminated string"));
+                }
+            }
+        }
+    }
+
+    /// Reads the digits of a `\u` escape, and of the second half of a
+    /// surrogate pair.
+    fn unicode_escape(&mut self) -> Result<char, ErrorStruct> {
+        let first = self.hex_digits()?;
+        let code = if (0xd800..0xdc00).contains(&first) {
+            self.expect("\\u")?;
+            let second = self.hex_digits()?;
+            if !(0xdc00..0xe000).contains(&second) {
+                return Err(self.error("invalid surrogate pair"));
+            }
+            0x10000 + ((first - 0xd800) << 10) + (second - 0xdc00)
+        } else {
+            first
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex_digits(&mut self) -> Result<u32, ErrorStruct> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .next()
+                .and_then(|char| char.to_digit(16))
+                .ok_or_else(|| self.error("invalid unicode escape"))?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod test_json {
+    use super::*;
+
+    #[test]
+    fn test_01_encode_and_parse_every_kind_of_value() {
+        let value = Json::Object(vec![
+            ("null".to_string(), Json::Null),
+            ("bool".to_string(), Json::Bool(true)),
+            (
+                "number".to_string(),
+                Json::Number("18446744073709551615".into()),
+            ),
+            ("text".to_string(), Json::String("a \"b\"\n\u{1}ñ".into())),
+            (
+                "array".to_string(),
+                Json::Array(vec![Json::Number("-1.5e3".into()), Json::Array(vec![])]),
+            ),
+        ]);
+        let text = value.to_string();
+        assert_eq!(
+            text,
+            "{\"null\":null,\"bool\":true,\"number\":18446744073709551615,\
+             \"text\":\"a \\\"b\\\"\\n\\u0001ñ\",\"array\":[-1.5e3,[]]}"
+        );
+        assert_eq!(Json::parse(&text).unwrap(), value);
+        assert_eq!(value.get("number").unwrap().as_u64(), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_02_parse_escapes_and_whitespace() {
+        let value = Json::parse(" [ \"\\u00e9\\ud83d\\ude00\\/\" , { } ] ").unwrap();
+        assert_eq!(
+            value,
+            Json::Array(vec![Json::String("é😀/".to_string()), Json::Object(vec![])])
+        );
+    }
+
+    #[test]
+    fn test_03_invalid_json_tells_the_offset() {
+        assert_eq!(
+            Json::parse("[1, 2").unwrap_err().print_it(),
+            "ERR invalid JSON: expected ',' or ']' at offset 5"
+        );
+        assert_eq!(
+            Json::parse("{\"a\" 1}").unwrap_err().print_it(),
+            "ERR invalid JSON: expected ':' at offset 5"
+        );
+        assert!(Json::parse("\"open").is_err());
+        assert!(Json::parse("[1] 2").is_err());
+        assert!(Json::parse("nul").is_err());
+    }
+}
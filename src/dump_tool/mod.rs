@@ -0,0 +1,303 @@
+//! Offline inspection and repair of the dump files, without starting a
+//! server. See the `dump_tool` binary.
+
+pub mod export;
+pub mod json;
+pub mod stats;
+
+use crate::dump::{self, Dump};
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use crate::redis_config::DumpFormat;
+use crate::stream::now_millis;
+use json::Json;
+use stats::Stats;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+
+/// Number of biggest keys shown by `stats` by default.
+const DEFAULT_TOP: usize = 10;
+
+pub const USAGE: &str = "\
+dump_tool check <dump>                          validates the file
+dump_tool stats <dump> [<top>]                  keys per type, expires, memory and biggest keys
+dump_tool export <dump> jsonl|csv [<output>]    writes the keys, to the standard output by default
+dump_tool import <jsonl> <dump> [legacy|rdb]    builds a dump from JSON Lines
+dump_tool convert <dump> <output> legacy|rdb    writes the keys in the other format
+dump_tool truncate <dump> [<output>]            keeps the keys before the corruption";
+
+/// Runs the command of the arguments, without the name of the binary,
+/// writing its report to `out`.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The command is unknown or has wrong arguments.
+/// * Some file can not be read or written.
+/// * The dump is corrupted. Only `stats` reads the keys before the
+///   corruption, and `truncate` writes them.
+/// * A line of JSON Lines to import is not valid.
+pub fn run<W: Write>(args: &[String], out: &mut W) -> Result<(), ErrorStruct> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["check", path] => check(path, out),
+        ["stats", path] => stats(path, DEFAULT_TOP, out),
+        ["stats", path, top] => {
+            let top = top.parse().map_err(|_| usage_error())?;
+            stats(path, top, out)
+        }
+        ["export", path, kind] => export(path, kind, out),
+        ["export", path, kind, output] => {
+            let mut file = BufWriter::new(File::create(output).map_err(|_| write_error(output))?);
+            export(path, kind, &mut file)?;
+            file.flush().map_err(|_| write_error(output))
+        }
+        ["import", input, output] => import(input, output, DumpFormat::Legacy, out),
+        ["import", input, output, format] => import(input, output, parse_format(format)?, out),
+        ["convert", path, output, format] => convert(path, output, parse_format(format)?, out),
+        ["truncate", path] => truncate(path, path, out),
+        ["truncate", path, output] => truncate(path, output, out),
+        _ => Err(usage_error()),
+    }
+}
+
+fn check<W: Write>(path: &str, out: &mut W) -> Result<(), ErrorStruct> {
+    let dump = read(path)?;
+    print(out, &format!("keys: {}", dump.entries.len()))?;
+    match dump.corruption {
+        Some(corruption) => Err(corrupted(&corruption.to_string())),
+        None => print(out, "OK"),
+    }
+}
+
+fn stats<W: Write>(path: &str, top: usize, out: &mut W) -> Result<(), ErrorStruct> {
+    let dump = read(path)?;
+    let stats = Stats::new(&dump, now_millis(), top);
+    write!(out, "{}", stats).map_err(|_| write_error("standard output"))
+}
+
+fn export<W: Write>(path: &str, kind: &str, out: &mut W) -> Result<(), ErrorStruct> {
+    let dump = read_valid(path)?;
+    match kind {
+        "jsonl" => {
+            for entry in dump.entries.iter() {
+                print(out, &export::to_json(entry).to_string())?;
+            }
+        }
+        "csv" => {
+            print(out, export::CSV_HEADER)?;
+            for entry in dump.entries.iter() {
+                print(out, &export::to_csv(entry))?;
+            }
+        }
+        _ => return Err(usage_error()),
+    }
+    Ok(())
+}
+
+fn import<W: Write>(
+    input: &str,
+    output: &str,
+    format: DumpFormat,
+    out: &mut W,
+) -> Result<(), ErrorStruct> {
+    let text = fs::read_to_string(input).map_err(|_| read_error(input))?;
+    let mut entries = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = Json::parse(line)
+            .and_then(|json| export::from_json(&json))
+            .map_err(|error| {
+                ErrorStruct::from(redis_messages::invalid_line(number + 1, error.message()))
+            })?;
+        entries.push(entry);
+    }
+    dump::write_atomically(output, &dump::encode(&entries, format, now_millis()))?;
+    print(out, &format!("imported {} keys", entries.len()))
+}
+
+fn convert<W: Write>(
+    path: &str,
+    output: &str,
+    format: DumpFormat,
+    out: &mut W,
+) -> Result<(), ErrorStruct> {
+    let dump = read_valid(path)?;
+    dump::write_atomically(output, &dump::encode(&dump.entries, format, now_millis()))?;
+    print(out, &format!("converted {} keys", dump.entries.len()))
+}
+
+fn truncate<W: Write>(path: &str, output: &str, out: &mut W) -> Result<(), ErrorStruct> {
+    let dump = read(path)?;
+    match dump.corruption.as_ref() {
+        None => print(out, "OK: the file is not corrupted"),
+        Some(corruption) if corruption.truncated => {
+            let bytes = dump::encode(&dump.entries, dump.format, now_millis());
+            dump::write_atomically(output, &bytes)?;
+            print(
+                out,
+                &format!(
+                    "kept {} keys, dropped the data from offset {}",
+                    dump.entries.len(),
+                    corruption.offset
+                ),
+            )
+        }
+        Some(corruption) => Err(corrupted(&corruption.to_string())),
+    }
+}
+
+fn read(path: &str) -> Result<Dump, ErrorStruct> {
+    let bytes = fs::read(path).map_err(|_| read_error(path))?;
+    Ok(dump::read(&bytes))
+}
+
+/// Reads the dump, which must not be corrupted.
+fn read_valid(path: &str) -> Result<Dump, ErrorStruct> {
+    let dump = read(path)?;
+    match dump.corruption.as_ref() {
+        Some(corruption) => Err(corrupted(&corruption.to_string())),
+        None => Ok(dump),
+    }
+}
+
+fn parse_format(format: &str) -> Result<DumpFormat, ErrorStruct> {
+    DumpFormat::parse(format).ok_or_else(usage_error)
+}
+
+fn print<W: Write>(out: &mut W, line: &str) -> Result<(), ErrorStruct> {
+    writeln!(out, "{}", line).map_err(|_| write_error("standard output"))
+}
+
+fn corrupted(corruption: &str) -> ErrorStruct {
+    ErrorStruct::from(redis_messages::corrupted_dump(corruption))
+}
+
+fn usage_error() -> ErrorStruct {
+    ErrorStruct::from(redis_messages::wrong_usage(USAGE))
+}
+
+fn read_error(path: &str) -> ErrorStruct {
+    ErrorStruct::from(redis_messages::file_error(path, "can not be read"))
+}
+
+fn write_error(path: &str) -> ErrorStruct {
+    ErrorStruct::from(redis_messages::file_error(path, "can not be written"))
+}
+
+#[cfg(test)]
+mod test_dump_tool {
+    use super::*;
+    use crate::database::TypeSaved;
+    use crate::vec_strings;
+
+    fn run_it(args: Vec<String>) -> Result<String, ErrorStruct> {
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn write_dump(path: &str, format: DumpFormat) {
+        let entries = vec![
+            ("a".to_string(), None, TypeSaved::String(b"1".to_vec())),
+            (
+                "list".to_string(),
+                None,
+                TypeSaved::List(vec_strings!["x", "y"].into_iter().collect()),
+            ),
+        ];
+        fs::write(path, dump::encode(&entries, format, 0)).unwrap();
+    }
+
+    #[test]
+    fn test_01_check_and_truncate_a_corrupted_file() {
+        let path = "dump_tool_01.rdb";
+        write_dump(path, DumpFormat::Rdb);
+        assert_eq!(
+            run_it(vec_strings!["check", path]).unwrap(),
+            "keys: 2\nOK\n"
+        );
+        let bytes = fs::read(path).unwrap();
+        fs::write(path, &bytes[..bytes.len() - 12]).unwrap();
+        assert!(run_it(vec_strings!["check", path])
+            .unwrap_err()
+            .print_it()
+            .starts_with("ERR Bad RDB file: unexpected end of file at offset"));
+        assert!(run_it(vec_strings!["export", path, "jsonl"]).is_err());
+
+        let output = "dump_tool_01_truncated.rdb";
+        let report = run_it(vec_strings!["truncate", path, output]).unwrap();
+        assert!(report.starts_with("kept 1 keys"));
+        assert_eq!(
+            run_it(vec_strings!["check", output]).unwrap(),
+            "keys: 1\nOK\n"
+        );
+        let _ = fs::remove_file(path);
+        let _ = fs::remove_file(output);
+    }
+
+    #[test]
+    fn test_02_export_import_and_convert() {
+        let path = "dump_tool_02.rdb";
+        write_dump(path, DumpFormat::Legacy);
+        let jsonl = run_it(vec_strings!["export", path, "jsonl"]).unwrap();
+        assert_eq!(
+            jsonl,
+            "{\"key\":\"a\",\"type\":\"string\",\"expire_at\":null,\"value\":\"1\"}\n\
+             {\"key\":\"list\",\"type\":\"list\",\"expire_at\":null,\"value\":[\"x\",\"y\"]}\n"
+        );
+        assert_eq!(
+            run_it(vec_strings!["export", path, "csv"]).unwrap(),
+            "key,type,expire_at,value\na,string,,1\nlist,list,,\"[\"\"x\"\",\"\"y\"\"]\"\n"
+        );
+
+        let input = "dump_tool_02.jsonl";
+        let output = "dump_tool_02_imported.rdb";
+        fs::write(input, &jsonl).unwrap();
+        assert_eq!(
+            run_it(vec_strings!["import", input, output, "rdb"]).unwrap(),
+            "imported 2 keys\n"
+        );
+        assert!(fs::read(output).unwrap().starts_with(b"REDIS"));
+        assert_eq!(
+            run_it(vec_strings!["convert", output, path, "legacy"]).unwrap(),
+            "converted 2 keys\n"
+        );
+        assert_eq!(
+            run_it(vec_strings!["export", path, "jsonl"]).unwrap(),
+            jsonl
+        );
+
+        fs::write(
+            input,
+            "{\"key\":\"a\",\"type\":\"string\",\"value\":\"1\"}\n[",
+        )
+        .unwrap();
+        assert_eq!(
+            run_it(vec_strings!["import", input, output])
+                .unwrap_err()
+                .print_it(),
+            "ERR line 2: invalid JSON: expected a value at offset 1"
+        );
+        for file in [path, input, output] {
+            let _ = fs::remove_file(file);
+        }
+    }
+
+    #[test]
+    fn test_03_wrong_arguments_show_the_usage() {
+        assert!(run_it(vec_strings!["inspect", "dump.rdb"])
+            .unwrap_err()
+            .print_it()
+            .contains("dump_tool check <dump>"));
+        assert!(run_it(vec_strings!["convert", "a", "b", "json"]).is_err());
+        assert_eq!(
+            run_it(vec_strings!["check", "missing_dump_tool.rdb"])
+                .unwrap_err()
+                .print_it(),
+            "ERR missing_dump_tool.rdb: can not be read"
+        );
+    }
+}
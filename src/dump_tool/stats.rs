@@ -0,0 +1,206 @@
+//! Statistics of the keys of a dump.
+
+use super::export::type_name;
+use crate::database::TypeSaved;
+use crate::dump::Dump;
+use crate::rdb::RdbEntry;
+use crate::redis_config::DumpFormat;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Bytes of the entry of the dictionary and of the object of each key, as
+/// Redis does in a 64 bits server.
+const KEY_OVERHEAD: usize = 72;
+/// Bytes of the node of each element of a list, set or sorted set.
+const ELEMENT_OVERHEAD: usize = 24;
+/// Bytes of each entry of a stream, besides its fields.
+const STREAM_ENTRY_OVERHEAD: usize = 32;
+
+/// Upper limits of the ranges of the time to live, in seconds.
+const TTL_RANGES: [(u64, &str); 4] = [
+    (60, "< 1 minute"),
+    (3600, "< 1 hour"),
+    (86400, "< 1 day"),
+    (604800, "< 1 week"),
+];
+
+pub struct Stats {
+    format: DumpFormat,
+    version: u16,
+    keys: usize,
+    /// Number of keys of each type, by its name.
+    types: BTreeMap<&'static str, usize>,
+    persistent: usize,
+    expired: usize,
+    /// Number of keys in each range of [TTL_RANGES], and after the last one.
+    ttl_ranges: [usize; TTL_RANGES.len() + 1],
+    memory: usize,
+    /// Biggest keys, with their type, length and estimated size.
+    biggest: Vec<(String, &'static str, usize, usize)>,
+    corruption: Option<String>,
+}
+
+impl Stats {
+    /// Computes the statistics of the keys, with the time to live at `now`,
+    /// in milliseconds, and the `top` biggest keys.
+    pub fn new(dump: &Dump, now: u64, top: usize) -> Self {
+        let mut stats = Stats {
+            format: dump.format,
+            version: dump.version,
+            keys: dump.entries.len(),
+            types: BTreeMap::new(),
+            persistent: 0,
+            expired: 0,
+            ttl_ranges: [0; TTL_RANGES.len() + 1],
+            memory: 0,
+            biggest: Vec::new(),
+            corruption: dump
+                .corruption
+                .as_ref()
+                .map(|corruption| corruption.to_string()),
+        };
+        let mut sizes = Vec::new();
+        for entry in dump.entries.iter() {
+            let (key, expire_at, value) = entry;
+            *stats.types.entry(type_name(value)).or_insert(0) += 1;
+            match expire_at {
+                None => stats.persistent += 1,
+                Some(expire_at) if *expire_at <= now => stats.expired += 1,
+                Some(expire_at) => {
+                    let ttl = (expire_at - now) / 1000;
+                    let range = TTL_RANGES
+                        .iter()
+                        .position(|(limit, _)| ttl < *limit)
+                        .unwrap_or(TTL_RANGES.len());
+                    stats.ttl_ranges[range] += 1;
+                }
+            }
+            let size = estimated_size(entry);
+            stats.memory += size;
+            sizes.push((key.to_string(), type_name(value), length(value), size));
+        }
+        sizes.sort_by(|a, b| b.3.cmp(&a.3).then_with(|| a.0.cmp(&b.0)));
+        sizes.truncate(top);
+        stats.biggest = sizes;
+        stats
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self.format {
+            DumpFormat::Legacy => "legacy",
+            DumpFormat::Rdb => "rdb",
+        };
+        writeln!(f, "format: {} (version {})", format, self.version)?;
+        if let Some(corruption) = self.corruption.as_ref() {
+            writeln!(f, "corruption: {}", corruption)?;
+        }
+        writeln!(f, "keys: {}", self.keys)?;
+        for (name, keys) in self.types.iter() {
+            writeln!(f, "  {}: {}", name, keys)?;
+        }
+        writeln!(f, "expires:")?;
+        writeln!(f, "  none: {}", self.persistent)?;
+        writeln!(f, "  expired: {}", self.expired)?;
+        for ((_, range), keys) in TTL_RANGES.iter().zip(self.ttl_ranges.iter()) {
+            writeln!(f, "  {}: {}", range, keys)?;
+        }
+        writeln!(f, "  >= 1 week: {}", self.ttl_ranges[TTL_RANGES.len()])?;
+        writeln!(f, "memory estimate: {} bytes", self.memory)?;
+        writeln!(f, "biggest keys:")?;
+        for (position, (key, name, length, size)) in self.biggest.iter().enumerate() {
+            let unit = if *name == "string" {
+                "bytes"
+            } else {
+                "elements"
+            };
+            writeln!(
+                f,
+                "  {}. {} ({}, {} {}): {} bytes",
+                position + 1,
+                key,
+                name,
+                length,
+                unit,
+                size
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Bytes of a string, or elements of the other types.
+fn length(value: &TypeSaved) -> usize {
+    match value {
+        TypeSaved::String(bytes) => bytes.len(),
+        TypeSaved::List(list) => list.len(),
+        TypeSaved::Set(set) => set.len(),
+        TypeSaved::SortedSet(sorted_set) => sorted_set.len(),
+        TypeSaved::Stream(stream) => stream.len(),
+    }
+}
+
+/// Rough estimate of the memory used by the key in a Redis server: the
+/// bytes of the key and its elements, with the overhead of their nodes.
+fn estimated_size(entry: &RdbEntry) -> usize {
+    let (key, _, value) = entry;
+    let elements = |lengths: &mut dyn Iterator<Item = usize>| {
+        lengths
+            .map(|length| length + ELEMENT_OVERHEAD)
+            .sum::<usize>()
+    };
+    KEY_OVERHEAD
+        + key.len()
+        + match value {
+            TypeSaved::String(bytes) => bytes.len(),
+            TypeSaved::List(list) => elements(&mut list.iter().map(String::len)),
+            TypeSaved::Set(set) => elements(&mut set.iter().map(String::len)),
+            TypeSaved::SortedSet(sorted_set) => {
+                elements(&mut sorted_set.iter().map(|(member, _)| member.len() + 8))
+            }
+            TypeSaved::Stream(stream) => stream
+                .entries()
+                .values()
+                .map(|fields| STREAM_ENTRY_OVERHEAD + fields.iter().map(String::len).sum::<usize>())
+                .sum(),
+        }
+}
+
+#[cfg(test)]
+mod test_stats {
+    use super::*;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_keys_are_counted_by_type_and_time_to_live() {
+        let now = 1_000_000_000;
+        let entries = vec![
+            ("a".to_string(), None, TypeSaved::String(b"value".to_vec())),
+            (
+                "b".to_string(),
+                Some(now + 30_000),
+                TypeSaved::String(b"1".to_vec()),
+            ),
+            (
+                "list".to_string(),
+                Some(now + 7_200_000),
+                TypeSaved::List(vec_strings!["x", "yy"].into_iter().collect()),
+            ),
+            ("old".to_string(), Some(now - 1), TypeSaved::String(vec![])),
+        ];
+        let mut dump = Dump::new(DumpFormat::Legacy, 2);
+        dump.entries = entries;
+        let stats = Stats::new(&dump, now, 2);
+        assert_eq!(
+            stats.to_string(),
+            "format: legacy (version 2)\n\
+             keys: 4\n  list: 1\n  string: 3\n\
+             expires:\n  none: 1\n  expired: 1\n  < 1 minute: 1\n  < 1 hour: 0\n  \
+             < 1 day: 1\n  < 1 week: 0\n  >= 1 week: 0\n\
+             memory estimate: 354 bytes\n\
+             biggest keys:\n  1. list (list, 2 elements): 127 bytes\n  \
+             2. a (string, 5 bytes): 78 bytes\n"
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use std::{env, io, process};
+
+use redis_rust::dump_tool;
+
+/// ## Commands to Inspect a Dump File, in console:
+///
+/// * *cargo run --bin dump_tool check dump.rdb*
+/// * *cargo run --bin dump_tool stats dump.rdb*
+/// * *cargo run --bin dump_tool export dump.rdb jsonl keys.jsonl*
+/// * *cargo run --bin dump_tool truncate dump.rdb*
+///
+/// See [dump_tool::USAGE] for all of them.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let stdout = io::stdout();
+    if let Err(error) = dump_tool::run(&args, &mut stdout.lock()) {
+        eprintln!("{}", error.print_it());
+        process::exit(1);
+    }
+}
@@ -0,0 +1,175 @@
+//! Geohash encoding of coordinates, compatible with the one Redis uses to store
+//! geospatial indexes as the scores of sorted sets.
+
+/// Bits used for each coordinate, for a hash of 52 bits.
+const STEPS: u32 = 26;
+
+pub const LONGITUDE_MIN: f64 = -180.0;
+pub const LONGITUDE_MAX: f64 = 180.0;
+/// Latitudes are limited to the ones of the Web Mercator projection.
+pub const LATITUDE_MIN: f64 = -85.05112878;
+pub const LATITUDE_MAX: f64 = 85.05112878;
+
+/// Radius of the Earth used to compute distances, the same as Redis.
+const EARTH_RADIUS_IN_METERS: f64 = 6372797.560856;
+
+const BASE32: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// A point of the Earth, in degrees.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Coordinates {
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+impl Coordinates {
+    /// Returns the coordinates, or [None] if they can not be indexed.
+    pub fn new(longitude: f64, latitude: f64) -> Option<Self> {
+        if (LONGITUDE_MIN..=LONGITUDE_MAX).contains(&longitude)
+            && (LATITUDE_MIN..=LATITUDE_MAX).contains(&latitude)
+        {
+            Some(Coordinates {
+                longitude,
+                latitude,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Encodes the coordinates as the 52 bits hash used as score.
+    pub fn encode(&self) -> u64 {
+        encode(
+            self,
+            (LONGITUDE_MIN, LONGITUDE_MAX),
+            (LATITUDE_MIN, LATITUDE_MAX),
+        )
+    }
+
+    /// Decodes the hash used as score, returning the center of its area.
+    pub fn decode(hash: u64) -> Self {
+        let (longitude, latitude) = deinterleave(hash);
+        let center = |cell: u32, (min, max): (f64, f64)| {
+            let cells = (1u64 << STEPS) as f64;
+            let cell_min = min + (cell as f64 / cells) * (max - min);
+            let cell_max = min + ((cell as f64 + 1.0) / cells) * (max - min);
+            ((cell_min + cell_max) / 2.0).clamp(min, max)
+        };
+        Coordinates {
+            longitude: center(longitude, (LONGITUDE_MIN, LONGITUDE_MAX)),
+            latitude: center(latitude, (LATITUDE_MIN, LATITUDE_MAX)),
+        }
+    }
+
+    /// Returns the standard 11 characters geohash of the coordinates, which
+    /// uses the whole range of latitudes.
+    pub fn geohash(&self) -> String {
+        let hash = encode(self, (LONGITUDE_MIN, LONGITUDE_MAX), (-90.0, 90.0));
+        (0..11)
+            .map(|index| {
+                // The 52 bits only fill 10 characters and 2 bits of the last one
+                let digit = if index == 10 {
+                    0
+                } else {
+                    (hash >> (52 - (index + 1) * 5)) & 0x1f
+                };
+                BASE32[digit as usize] as char
+            })
+            .collect()
+    }
+
+    /// Great-circle distance in meters to the other coordinates.
+    pub fn distance(&self, other: &Coordinates) -> f64 {
+        let (latitude1, latitude2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let u = ((latitude2 - latitude1) / 2.0).sin();
+        let v = ((other.longitude - self.longitude).to_radians() / 2.0).sin();
+        let a = u * u + latitude1.cos() * latitude2.cos() * v * v;
+        2.0 * EARTH_RADIUS_IN_METERS * a.sqrt().asin()
+    }
+
+    /// Returns true if the other coordinates are inside the box with the given
+    /// width and height in meters, centered at these coordinates.
+    pub fn box_contains(&self, width: f64, height: f64, other: &Coordinates) -> bool {
+        let latitude_distance =
+            EARTH_RADIUS_IN_METERS * (other.latitude - self.latitude).to_radians().abs();
+        if latitude_distance > height / 2.0 {
+            return false;
+        }
+        let parallel = Coordinates {
+            longitude: self.longitude,
+            latitude: other.latitude,
+        };
+        parallel.distance(other) <= width / 2.0
+    }
+}
+
+/// Interleaves the cells of both coordinates in the given ranges, with the
+/// longitude in the most significant bit of each pair.
+fn encode(coordinates: &Coordinates, longitudes: (f64, f64), latitudes: (f64, f64)) -> u64 {
+    let cell = |value: f64, (min, max): (f64, f64)| {
+        let offset = (value - min) / (max - min);
+        ((offset * (1u64 << STEPS) as f64) as u64).min((1 << STEPS) - 1)
+    };
+    let (longitude, latitude) = (
+        cell(coordinates.longitude, longitudes),
+        cell(coordinates.latitude, latitudes),
+    );
+    (0..STEPS).fold(0, |hash, bit| {
+        hash | (((latitude >> bit) & 1) << (2 * bit)) | (((longitude >> bit) & 1) << (2 * bit + 1))
+    })
+}
+
+/// Splits a hash in the cells of the longitude and the latitude.
+fn deinterleave(hash: u64) -> (u32, u32) {
+    (0..STEPS).fold((0, 0), |(longitude, latitude), bit| {
+        (
+            longitude | (((hash >> (2 * bit + 1)) & 1) as u32) << bit,
+            latitude | (((hash >> (2 * bit)) & 1) as u32) << bit,
+        )
+    })
+}
+
+#[cfg(test)]
+mod test_geohash {
+    use super::*;
+
+    fn palermo() -> Coordinates {
+        Coordinates::new(13.361389, 38.115556).unwrap()
+    }
+
+    fn catania() -> Coordinates {
+        Coordinates::new(15.087269, 37.502669).unwrap()
+    }
+
+    #[test]
+    fn test_01_invalid_coordinates() {
+        assert!(Coordinates::new(181.0, 0.0).is_none());
+        assert!(Coordinates::new(0.0, 86.0).is_none());
+        assert!(Coordinates::new(-180.0, -85.05112878).is_some());
+    }
+
+    #[test]
+    fn test_02_encode_and_decode_as_redis() {
+        assert_eq!(palermo().encode(), 3479099956230698);
+        assert_eq!(catania().encode(), 3479447370796909);
+        let decoded = Coordinates::decode(palermo().encode());
+        assert!((decoded.longitude - 13.361389).abs() < 0.00001);
+        assert!((decoded.latitude - 38.115556).abs() < 0.00001);
+    }
+
+    #[test]
+    fn test_03_standard_geohash() {
+        assert_eq!(palermo().geohash(), "sqc8b49rny0");
+        assert_eq!(catania().geohash(), "sqdtr74hyu0");
+    }
+
+    #[test]
+    fn test_04_distances() {
+        let palermo_indexed = Coordinates::decode(palermo().encode());
+        let catania_indexed = Coordinates::decode(catania().encode());
+        let distance = palermo_indexed.distance(&catania_indexed);
+        assert!((distance - 166274.1516).abs() < 0.0001);
+        assert!(palermo().box_contains(400000.0, 400000.0, &catania()));
+        assert!(!palermo().box_contains(400000.0, 100000.0, &catania()));
+    }
+}
@@ -0,0 +1,400 @@
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+
+/// Bits of the hash used to select the register.
+const P: u32 = 14;
+/// Bits of the hash used to count the leading zeros.
+const Q: u32 = 64 - P;
+const REGISTERS: usize = 1 << P;
+const REGISTER_BITS: usize = 6;
+const REGISTER_MAX: u8 = (1 << REGISTER_BITS) - 1;
+const HEADER_SIZE: usize = 16;
+const DENSE_SIZE: usize = HEADER_SIZE + (REGISTERS * REGISTER_BITS).div_ceil(8);
+/// Greatest size of the sparse encoding before switching to the dense one.
+const SPARSE_MAX_BYTES: usize = 3000;
+/// Greatest register value of the sparse encoding.
+const SPARSE_VALUE_MAX: u8 = 32;
+const MAGIC: &[u8; 4] = b"HYLL";
+const DENSE: u8 = 0;
+const SPARSE: u8 = 1;
+const HASH_SEED: u64 = 0xadc83b19;
+/// Constant for 0.5 / ln(2).
+const ALPHA_INF: f64 = 0.721_347_520_444_481_7;
+
+/// HyperLogLog with the layout of Redis, so it can be stored as a string value:
+///
+/// * A 16 bytes header with "HYLL", the encoding (0: dense, 1: sparse), three
+///   unused bytes and the cached cardinality as a 64 bits little endian integer,
+///   whose most significant bit is set when the cache is not valid.
+/// * 16384 registers of 6 bits, encoded as a packed array (dense) or as runs of
+///   registers with the same value (sparse), which is used while it is small.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+    dense: bool,
+    cached_cardinality: Option<u64>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HyperLogLog {
+    /// Creates an empty HyperLogLog, with the sparse encoding.
+    pub fn new() -> Self {
+        HyperLogLog {
+            registers: vec![0; REGISTERS],
+            dense: false,
+            cached_cardinality: Some(0),
+        }
+    }
+
+    /// Decodes a HyperLogLog stored as a string value.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The bytes have not a HyperLogLog header.
+    /// * The registers are corrupted.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ErrorStruct> {
+        if bytes.len() < HEADER_SIZE
+            || &bytes[..4] != MAGIC
+            || (bytes[4] != DENSE && bytes[4] != SPARSE)
+        {
+            return Err(ErrorStruct::from(redis_messages::not_hyperloglog()));
+        }
+        let mut cache = [0; 8];
+        cache.copy_from_slice(&bytes[8..HEADER_SIZE]);
+        let cached_cardinality = if cache[7] & 0x80 == 0 {
+            Some(u64::from_le_bytes(cache))
+        } else {
+            None
+        };
+        let dense = bytes[4] == DENSE;
+        let registers = if dense {
+            decode_dense(bytes)?
+        } else {
+            decode_sparse(&bytes[HEADER_SIZE..])?
+        };
+        Ok(HyperLogLog {
+            registers,
+            dense,
+            cached_cardinality,
+        })
+    }
+
+    /// Encodes the HyperLogLog to be stored as a string value. The sparse encoding
+    /// is kept while every register fits in it and it is small enough.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let sparse = if self.dense {
+            None
+        } else {
+            encode_sparse(&self.registers)
+                .filter(|sparse| HEADER_SIZE + sparse.len() <= SPARSE_MAX_BYTES)
+        };
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(if sparse.is_some() { SPARSE } else { DENSE });
+        bytes.extend_from_slice(&[0; 3]);
+        match self.cached_cardinality {
+            Some(cardinality) => bytes.extend_from_slice(&cardinality.to_le_bytes()),
+            None => bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0x80]),
+        }
+        match sparse {
+            Some(sparse) => bytes.extend(sparse),
+            None => bytes.extend(encode_dense(&self.registers)),
+        }
+        bytes
+    }
+
+    /// Adds the element to the HyperLogLog.
+    ///
+    /// # Return value
+    /// [bool]: true if some register was updated.
+    pub fn add(&mut self, element: &[u8]) -> bool {
+        let hash = murmur_hash_64a(element, HASH_SEED);
+        let index = (hash & (REGISTERS as u64 - 1)) as usize;
+        // The bit Q makes the count stop at Q + 1
+        let pattern = (hash >> P) | (1 << Q);
+        let count = pattern.trailing_zeros() as u8 + 1;
+        if count > self.registers[index] {
+            self.registers[index] = count;
+            self.cached_cardinality = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Merges the other HyperLogLog into this one, keeping the greatest value of each register.
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        for (register, other) in self.registers.iter_mut().zip(other.registers.iter()) {
+            if *other > *register {
+                *register = *other;
+                self.cached_cardinality = None;
+            }
+        }
+        self.dense |= other.dense;
+    }
+
+    /// Returns the cached cardinality, if it is valid.
+    pub fn cached_cardinality(&self) -> Option<u64> {
+        self.cached_cardinality
+    }
+
+    /// Returns the estimated cardinality, with an standard error of 0.81%, and caches it.
+    pub fn count(&mut self) -> u64 {
+        if let Some(cardinality) = self.cached_cardinality {
+            return cardinality;
+        }
+        let mut histogram = [0u32; 64];
+        for register in self.registers.iter() {
+            histogram[*register as usize] += 1;
+        }
+        let m = REGISTERS as f64;
+        let mut z = m * tau((m - histogram[Q as usize + 1] as f64) / m);
+        for count in histogram[1..=Q as usize].iter().rev() {
+            z += *count as f64;
+            z *= 0.5;
+        }
+        z += m * sigma(histogram[0] as f64 / m);
+        let cardinality = (ALPHA_INF * m * m / z).round() as u64;
+        self.cached_cardinality = Some(cardinality);
+        cardinality
+    }
+}
+
+fn decode_dense(bytes: &[u8]) -> Result<Vec<u8>, ErrorStruct> {
+    if bytes.len() != DENSE_SIZE {
+        return Err(ErrorStruct::from(redis_messages::corrupted_hyperloglog()));
+    }
+    let packed = &bytes[HEADER_SIZE..];
+    Ok((0..REGISTERS)
+        .map(|index| {
+            let (byte, shift) = (index * REGISTER_BITS / 8, index * REGISTER_BITS % 8);
+            let low = packed[byte] as u16 >> shift;
+            let high = packed.get(byte + 1).copied().unwrap_or(0) as u16;
+            ((low | (high << (8 - shift))) as u8) & REGISTER_MAX
+        })
+        .collect())
+}
+
+fn encode_dense(registers: &[u8]) -> Vec<u8> {
+    let mut packed = vec![0u8; DENSE_SIZE - HEADER_SIZE];
+    for (index, register) in registers.iter().enumerate() {
+        let (byte, shift) = (index * REGISTER_BITS / 8, index * REGISTER_BITS % 8);
+        let value = (*register.min(&REGISTER_MAX) as u16) << shift;
+        packed[byte] |= value as u8;
+        if let Some(next) = packed.get_mut(byte + 1) {
+            *next |= (value >> 8) as u8;
+        }
+    }
+    packed
+}
+
+/// Decodes the sparse opcodes:
+///
+/// * ZERO `00xxxxxx`: xxxxxx + 1 registers set to 0.
+/// * XZERO `01xxxxxx yyyyyyyy`: xxxxxxyyyyyyyy + 1 registers set to 0.
+/// * VAL `1vvvvvxx`: xx + 1 registers set to vvvvv + 1.
+fn decode_sparse(bytes: &[u8]) -> Result<Vec<u8>, ErrorStruct> {
+    let corrupted = || ErrorStruct::from(redis_messages::corrupted_hyperloglog());
+    let mut registers = Vec::with_capacity(REGISTERS);
+    let mut index = 0;
+    while index < bytes.len() {
+        let opcode = bytes[index];
+        let (value, len) = match opcode >> 6 {
+            0 => (0, (opcode & 0x3f) as usize + 1),
+            1 => {
+                let low = *bytes.get(index + 1).ok_or_else(corrupted)?;
+                index += 1;
+                (0, ((((opcode & 0x3f) as usize) << 8) | low as usize) + 1)
+            }
+            _ => (((opcode >> 2) & 0x1f) + 1, (opcode & 0x3) as usize + 1),
+        };
+        if registers.len() + len > REGISTERS {
+            return Err(corrupted());
+        }
+        registers.resize(registers.len() + len, value);
+        index += 1;
+    }
+    if registers.len() != REGISTERS {
+        return Err(corrupted());
+    }
+    Ok(registers)
+}
+
+/// Encodes the registers with the sparse opcodes, or returns [None] if some
+/// register is too big for them.
+fn encode_sparse(registers: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut index = 0;
+    while index < registers.len() {
+        let value = registers[index];
+        let run = registers[index..]
+            .iter()
+            .take_while(|register| **register == value)
+            .count();
+        index += run;
+        if value == 0 {
+            if run <= 64 {
+                bytes.push((run - 1) as u8);
+            } else {
+                bytes.push(0x40 | ((run - 1) >> 8) as u8);
+                bytes.push(((run - 1) & 0xff) as u8);
+            }
+        } else if value <= SPARSE_VALUE_MAX {
+            for chunk in 0..run.div_ceil(4) {
+                let len = (run - chunk * 4).min(4);
+                bytes.push(0x80 | ((value - 1) << 2) | (len - 1) as u8);
+            }
+        } else {
+            return None;
+        }
+    }
+    Some(bytes)
+}
+
+/// MurmurHash64A, used by Redis to hash the elements, reading the blocks as little endian.
+fn murmur_hash_64a(key: &[u8], seed: u64) -> u64 {
+    const M: u64 = 0xc6a4_a793_5bd1_e995;
+    const R: u32 = 47;
+    let mut h = seed ^ (key.len() as u64).wrapping_mul(M);
+    let mut chunks = key.chunks_exact(8);
+    for chunk in &mut chunks {
+        let mut buffer = [0; 8];
+        buffer.copy_from_slice(chunk);
+        let mut k = u64::from_le_bytes(buffer).wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+    let rest = chunks.remainder();
+    if !rest.is_empty() {
+        for (index, byte) in rest.iter().enumerate() {
+            h ^= (*byte as u64) << (8 * index);
+        }
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+    h
+}
+
+/// Auxiliary function of the estimation for the registers set to 0.
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+    let (mut y, mut z) = (1.0, x);
+    loop {
+        x *= x;
+        let previous = z;
+        z += x * y;
+        y += y;
+        if previous == z {
+            return z;
+        }
+    }
+}
+
+/// Auxiliary function of the estimation for the registers set to Q + 1.
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+    let (mut y, mut z) = (1.0, 1.0 - x);
+    loop {
+        x = x.sqrt();
+        let previous = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if previous == z {
+            return z / 3.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_hyperloglog {
+    use super::*;
+
+    #[test]
+    fn test_01_empty_hyperloglog_has_the_redis_layout() {
+        let mut hyperloglog = HyperLogLog::new();
+        let bytes = hyperloglog.to_bytes();
+        assert_eq!(
+            bytes,
+            b"HYLL\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x7f\xff".to_vec()
+        );
+        assert_eq!(hyperloglog.count(), 0);
+    }
+
+    #[test]
+    fn test_02_add_updates_registers_and_invalidates_the_cache() {
+        let mut hyperloglog = HyperLogLog::new();
+        assert!(hyperloglog.add(b"a"));
+        assert!(!hyperloglog.add(b"a"));
+        assert_eq!(hyperloglog.cached_cardinality(), None);
+        assert_eq!(hyperloglog.count(), 1);
+        assert_eq!(hyperloglog.cached_cardinality(), Some(1));
+    }
+
+    #[test]
+    fn test_03_estimation_error_is_small() {
+        let mut hyperloglog = HyperLogLog::new();
+        for element in 0..100_000 {
+            hyperloglog.add(element.to_string().as_bytes());
+        }
+        let error = (hyperloglog.count() as f64 - 100_000.0).abs() / 100_000.0;
+        assert!(error < 0.03);
+    }
+
+    #[test]
+    fn test_04_sparse_and_dense_encodings_round_trip() {
+        let mut hyperloglog = HyperLogLog::new();
+        for element in 0..50 {
+            hyperloglog.add(element.to_string().as_bytes());
+        }
+        let sparse = hyperloglog.to_bytes();
+        assert_eq!(sparse[4], SPARSE);
+        assert_eq!(HyperLogLog::from_bytes(&sparse).unwrap(), hyperloglog);
+
+        for element in 0..20_000 {
+            hyperloglog.add(element.to_string().as_bytes());
+        }
+        let dense = hyperloglog.to_bytes();
+        assert_eq!(dense[4], DENSE);
+        assert_eq!(dense.len(), DENSE_SIZE);
+        let mut decoded = HyperLogLog::from_bytes(&dense).unwrap();
+        assert_eq!(decoded.count(), hyperloglog.count());
+    }
+
+    #[test]
+    fn test_05_merge_keeps_the_greatest_registers() {
+        let (mut first, mut second) = (HyperLogLog::new(), HyperLogLog::new());
+        for element in 0..1000 {
+            first.add(format!("first{}", element).as_bytes());
+            second.add(format!("second{}", element).as_bytes());
+        }
+        first.merge(&second);
+        let error = (first.count() as f64 - 2000.0).abs() / 2000.0;
+        assert!(error < 0.03);
+    }
+
+    #[test]
+    fn test_06_invalid_values_are_rejected() {
+        assert!(HyperLogLog::from_bytes(b"value").is_err());
+        assert!(
+            HyperLogLog::from_bytes(b"HYLL\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00")
+                .is_err()
+        );
+        assert!(HyperLogLog::from_bytes(
+            b"HYLL\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x7f"
+        )
+        .is_err());
+    }
+}
@@ -1,15 +1,26 @@
+pub mod benchmark;
+pub mod cli;
+pub mod client;
 pub mod commands;
 pub mod communication;
 pub mod database;
+pub mod dump;
+pub mod dump_tool;
 pub mod file_manager;
+pub mod geohash;
+pub mod hyperloglog;
 pub mod joinable;
 pub mod logs;
 pub mod memory_checker;
 pub mod messages;
 pub mod native_types;
+pub mod payload;
+pub mod rdb;
 pub mod redis_config;
 pub mod regex;
 pub mod server_html;
+pub mod sorted_set;
+pub mod stream;
 pub mod tcp_protocol;
 pub mod time_expiration;
 
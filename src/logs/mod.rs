@@ -1 +1,3 @@
 pub mod log_center;
+pub mod server_stats;
+pub mod slowlog;
@@ -0,0 +1,445 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use crate::logs::slowlog::Slowlog;
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::ErrorStruct;
+use crate::time_expiration::clock::{Clock, SystemClock};
+
+/// Calls and total execution time of a command, for INFO commandstats.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CommandStat {
+    pub calls: u64,
+    /// Total execution time in microseconds.
+    pub usec: u64,
+}
+
+impl CommandStat {
+    /// Average execution time in microseconds.
+    pub fn usec_per_call(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.usec as f64 / self.calls as f64
+        }
+    }
+}
+
+/// Snapshot of the counters of the server, as shown by INFO.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsSnapshot {
+    pub uptime_in_seconds: u64,
+    pub total_commands_processed: u64,
+    pub instantaneous_ops_per_sec: u64,
+    pub keyspace_hits: u64,
+    pub keyspace_misses: u64,
+    pub expired_keys: u64,
+    pub evicted_keys: u64,
+    pub rdb_changes_since_last_save: u64,
+    pub rdb_last_save_time: u64,
+    pub rdb_saves: u64,
+    pub rdb_bgsave_in_progress: bool,
+    pub rdb_last_bgsave_ok: bool,
+    pub commands: BTreeMap<String, CommandStat>,
+}
+
+struct StatsState {
+    started: SystemTime,
+    total_commands_processed: u64,
+    /// Second (since [UNIX_EPOCH](std::time::UNIX_EPOCH)) being counted.
+    current_second: u64,
+    current_second_ops: u64,
+    last_second_ops: u64,
+    keyspace_hits: u64,
+    keyspace_misses: u64,
+    expired_keys: u64,
+    evicted_keys: u64,
+    changes_since_last_save: u64,
+    last_save_time: u64,
+    saves: u64,
+    background_save_in_progress: bool,
+    last_background_save_ok: bool,
+    commands: BTreeMap<String, CommandStat>,
+}
+
+/// Counters of the activity of the server, shared by:
+///
+/// * The [CommandDelegator](crate::tcp_protocol::command_delegator::CommandDelegator),
+///   which registers every command received.
+/// * The [CommandSubDelegator](crate::tcp_protocol::command_subdelegator::CommandSubDelegator)s,
+///   which register the duration of every execution.
+/// * The [Database](crate::database::Database), which registers the hits,
+///   misses, expirations and changes of the keys.
+///
+/// The executions are also forwarded to the [Slowlog], and the uptime,
+/// the operations per second and the time of the last save are measured
+/// with a [Clock].
+#[derive(Clone)]
+pub struct ServerStats {
+    state: Arc<Mutex<StatsState>>,
+    slowlog: Slowlog,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for ServerStats {
+    fn default() -> Self {
+        ServerStats::new(Slowlog::default(), Arc::new(SystemClock))
+    }
+}
+
+impl ServerStats {
+    /// Creates the counters, starting the uptime at the current time of the [Clock].
+    pub fn new(slowlog: Slowlog, clock: Arc<dyn Clock>) -> Self {
+        let now = clock.now();
+        ServerStats {
+            state: Arc::new(Mutex::new(StatsState {
+                started: now,
+                total_commands_processed: 0,
+                current_second: clock.unix_secs(),
+                current_second_ops: 0,
+                last_second_ops: 0,
+                keyspace_hits: 0,
+                keyspace_misses: 0,
+                expired_keys: 0,
+                evicted_keys: 0,
+                changes_since_last_save: 0,
+                last_save_time: clock.unix_secs(),
+                saves: 0,
+                background_save_in_progress: false,
+                last_background_save_ok: true,
+                commands: BTreeMap::new(),
+            })),
+            slowlog,
+            clock,
+        }
+    }
+
+    /// Returns a clone of the [Slowlog] where the executions are forwarded.
+    pub fn slowlog(&self) -> Slowlog {
+        self.slowlog.clone()
+    }
+
+    /// Registers a command received by the server, once even if
+    /// it is executed by several structures.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The counters are poisoned.
+    pub fn register_command(&self, command: &str) -> Result<(), ErrorStruct> {
+        let mut state = self.lock()?;
+        state.total_commands_processed += 1;
+        let now = self.clock.unix_secs();
+        if now != state.current_second {
+            state.last_second_ops = if now == state.current_second + 1 {
+                state.current_second_ops
+            } else {
+                0
+            };
+            state.current_second = now;
+            state.current_second_ops = 0;
+        }
+        state.current_second_ops += 1;
+        state
+            .commands
+            .entry(command.to_lowercase())
+            .or_default()
+            .calls += 1;
+        Ok(())
+    }
+
+    /// Registers the duration of an execution of a command and,
+    /// if it was slow enough, its entry in the [Slowlog].
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The counters or the slowlog are poisoned.
+    pub fn register_execution(
+        &self,
+        command: &[String],
+        duration: Duration,
+        client_addr: String,
+        client_name: String,
+    ) -> Result<(), ErrorStruct> {
+        self.lock()?
+            .commands
+            .entry(command[0].to_lowercase())
+            .or_default()
+            .usec += duration.as_micros() as u64;
+        self.slowlog
+            .record(command, duration, client_addr, client_name)
+    }
+
+    /// Registers a read of a key that exists (a hit) or not (a miss).
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The counters are poisoned.
+    pub fn register_lookup(&self, hit: bool) -> Result<(), ErrorStruct> {
+        let mut state = self.lock()?;
+        if hit {
+            state.keyspace_hits += 1;
+        } else {
+            state.keyspace_misses += 1;
+        }
+        Ok(())
+    }
+
+    /// Registers the removal of an expired key.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The counters are poisoned.
+    pub fn register_expiration(&self) -> Result<(), ErrorStruct> {
+        self.lock()?.expired_keys += 1;
+        Ok(())
+    }
+
+    /// Registers a change in the dataset since the last save.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The counters are poisoned.
+    pub fn register_change(&self) -> Result<(), ErrorStruct> {
+        self.lock()?.changes_since_last_save += 1;
+        Ok(())
+    }
+
+    /// Registers a successful save of the dataset, which included the first
+    /// `changes` changes. The ones done while saving are kept.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The counters are poisoned.
+    pub fn register_save(&self, changes: u64) -> Result<(), ErrorStruct> {
+        let mut state = self.lock()?;
+        state.changes_since_last_save = state.changes_since_last_save.saturating_sub(changes);
+        state.last_save_time = self.clock.unix_secs();
+        state.saves += 1;
+        Ok(())
+    }
+
+    /// Registers the start of a background save.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The counters are poisoned.
+    pub fn register_background_save_start(&self) -> Result<(), ErrorStruct> {
+        self.lock()?.background_save_in_progress = true;
+        Ok(())
+    }
+
+    /// Registers the end of a background save, which included the first
+    /// `changes` changes if it succeeded.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The counters are poisoned.
+    pub fn register_background_save_end(
+        &self,
+        saved: bool,
+        changes: u64,
+    ) -> Result<(), ErrorStruct> {
+        {
+            let mut state = self.lock()?;
+            state.background_save_in_progress = false;
+            state.last_background_save_ok = saved;
+        }
+        if saved {
+            self.register_save(changes)?;
+        }
+        Ok(())
+    }
+
+    /// Clears the counters of commands and keys, as CONFIG RESETSTAT. The
+    /// uptime and the state of the saves are kept.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The counters are poisoned.
+    pub fn reset(&self) -> Result<(), ErrorStruct> {
+        let mut state = self.lock()?;
+        state.total_commands_processed = 0;
+        state.current_second_ops = 0;
+        state.last_second_ops = 0;
+        state.keyspace_hits = 0;
+        state.keyspace_misses = 0;
+        state.expired_keys = 0;
+        state.evicted_keys = 0;
+        state.commands.clear();
+        Ok(())
+    }
+
+    /// Returns the current values of the counters.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The counters are poisoned.
+    pub fn snapshot(&self) -> Result<StatsSnapshot, ErrorStruct> {
+        let state = self.lock()?;
+        let now = self.clock.unix_secs();
+        let instantaneous_ops_per_sec = if now == state.current_second {
+            state.last_second_ops
+        } else if now == state.current_second + 1 {
+            state.current_second_ops
+        } else {
+            0
+        };
+        Ok(StatsSnapshot {
+            uptime_in_seconds: self
+                .clock
+                .now()
+                .duration_since(state.started)
+                .map(|uptime| uptime.as_secs())
+                .unwrap_or(0),
+            total_commands_processed: state.total_commands_processed,
+            instantaneous_ops_per_sec,
+            keyspace_hits: state.keyspace_hits,
+            keyspace_misses: state.keyspace_misses,
+            expired_keys: state.expired_keys,
+            evicted_keys: state.evicted_keys,
+            rdb_changes_since_last_save: state.changes_since_last_save,
+            rdb_last_save_time: state.last_save_time,
+            rdb_saves: state.saves,
+            rdb_bgsave_in_progress: state.background_save_in_progress,
+            rdb_last_bgsave_ok: state.last_background_save_ok,
+            commands: state.commands.clone(),
+        })
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, StatsState>, ErrorStruct> {
+        self.state.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "server stats",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_server_stats {
+    use super::*;
+    use crate::time_expiration::clock::ManualClock;
+    use crate::vec_strings;
+
+    fn register(stats: &ServerStats, command: Vec<String>, micros: u64) {
+        stats.register_command(&command[0]).unwrap();
+        stats
+            .register_execution(
+                &command,
+                Duration::from_micros(micros),
+                "127.0.0.1:8080".to_string(),
+                String::new(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_01_executions_are_counted_by_command() {
+        let stats = ServerStats::default();
+        register(&stats, vec_strings!["GET", "key"], 10);
+        register(&stats, vec_strings!["get", "key"], 20);
+        register(&stats, vec_strings!["set", "key", "value"], 5);
+
+        let snapshot = stats.snapshot().unwrap();
+        assert_eq!(snapshot.total_commands_processed, 3);
+        assert_eq!(snapshot.commands["get"], CommandStat { calls: 2, usec: 30 });
+        assert_eq!(snapshot.commands["get"].usec_per_call(), 15.0);
+        assert_eq!(snapshot.commands["set"].calls, 1);
+    }
+
+    #[test]
+    fn test_02_keyspace_counters() {
+        let stats = ServerStats::default();
+        stats.register_lookup(true).unwrap();
+        stats.register_lookup(false).unwrap();
+        stats.register_lookup(false).unwrap();
+        stats.register_expiration().unwrap();
+
+        let snapshot = stats.snapshot().unwrap();
+        assert_eq!(snapshot.keyspace_hits, 1);
+        assert_eq!(snapshot.keyspace_misses, 2);
+        assert_eq!(snapshot.expired_keys, 1);
+    }
+
+    #[test]
+    fn test_03_save_resets_the_changes() {
+        let stats = ServerStats::default();
+        stats.register_change().unwrap();
+        stats.register_change().unwrap();
+        assert_eq!(stats.snapshot().unwrap().rdb_changes_since_last_save, 2);
+        stats.register_save(2).unwrap();
+        assert_eq!(stats.snapshot().unwrap().rdb_changes_since_last_save, 0);
+    }
+
+    #[test]
+    fn test_04_background_save_keeps_the_changes_done_while_saving() {
+        let stats = ServerStats::default();
+        stats.register_change().unwrap();
+        stats.register_background_save_start().unwrap();
+        stats.register_change().unwrap();
+        assert!(stats.snapshot().unwrap().rdb_bgsave_in_progress);
+
+        stats.register_background_save_end(true, 1).unwrap();
+        let snapshot = stats.snapshot().unwrap();
+        assert!(!snapshot.rdb_bgsave_in_progress);
+        assert!(snapshot.rdb_last_bgsave_ok);
+        assert_eq!(snapshot.rdb_changes_since_last_save, 1);
+        assert_eq!(snapshot.rdb_saves, 1);
+
+        stats.register_background_save_start().unwrap();
+        stats.register_background_save_end(false, 1).unwrap();
+        let snapshot = stats.snapshot().unwrap();
+        assert!(!snapshot.rdb_last_bgsave_ok);
+        assert_eq!(snapshot.rdb_changes_since_last_save, 1);
+    }
+
+    #[test]
+    fn test_05_executions_are_forwarded_to_the_slowlog() {
+        let stats = ServerStats::new(Slowlog::new(0, 10), Arc::new(SystemClock));
+        register(&stats, vec_strings!["get", "key"], 10);
+        assert_eq!(stats.slowlog().len().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_06_reset_clears_the_counters_of_commands_and_keys() {
+        let stats = ServerStats::default();
+        register(&stats, vec_strings!["get", "key"], 10);
+        stats.register_lookup(true).unwrap();
+        stats.register_expiration().unwrap();
+        stats.register_change().unwrap();
+
+        stats.reset().unwrap();
+        let snapshot = stats.snapshot().unwrap();
+        assert_eq!(snapshot.total_commands_processed, 0);
+        assert_eq!(snapshot.keyspace_hits, 0);
+        assert_eq!(snapshot.expired_keys, 0);
+        assert!(snapshot.commands.is_empty());
+        assert_eq!(snapshot.rdb_changes_since_last_save, 1);
+    }
+
+    #[test]
+    fn test_07_uptime_and_last_save_time_are_measured_with_the_clock() {
+        let clock = Arc::new(ManualClock::from_unix_millis(1_000_000));
+        let stats = ServerStats::new(Slowlog::default(), clock.clone());
+        clock.advance_secs(60);
+        stats.register_save(0).unwrap();
+        clock.advance_secs(5);
+
+        let snapshot = stats.snapshot().unwrap();
+        assert_eq!(snapshot.uptime_in_seconds, 65);
+        assert_eq!(snapshot.rdb_last_save_time, 1060);
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::ErrorStruct;
+
+/// Default value of *slowlog-log-slower-than*, in microseconds.
+pub const DEFAULT_LOG_SLOWER_THAN: i64 = 10000;
+/// Default value of *slowlog-max-len*.
+pub const DEFAULT_MAX_LEN: usize = 128;
+
+/// Maximum number of arguments stored in an entry.
+const MAX_ARGS: usize = 32;
+/// Maximum number of bytes stored for each argument.
+const MAX_ARG_LEN: usize = 128;
+
+/// A command whose execution exceeded the *slowlog-log-slower-than* threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlowlogEntry {
+    pub id: u64,
+    /// Unix time in seconds when the command was executed.
+    pub timestamp: u64,
+    /// Duration of the execution in microseconds.
+    pub duration: u64,
+    /// Command and arguments, truncated.
+    pub args: Vec<String>,
+    pub client_addr: String,
+    pub client_name: String,
+}
+
+struct SlowlogState {
+    log_slower_than: i64,
+    max_len: usize,
+    next_id: u64,
+    /// Newest entries first.
+    entries: VecDeque<SlowlogEntry>,
+}
+
+/// Bounded register of the slowest commands executed by the
+/// [CommandSubDelegator](crate::tcp_protocol::command_subdelegator::CommandSubDelegator)s.
+///
+/// * A negative *slowlog-log-slower-than* disables the register.
+/// * Zero registers every command.
+///
+/// When *slowlog-max-len* is reached, the oldest entry is discarded.
+#[derive(Clone)]
+pub struct Slowlog {
+    state: Arc<Mutex<SlowlogState>>,
+}
+
+impl Default for Slowlog {
+    fn default() -> Self {
+        Slowlog::new(DEFAULT_LOG_SLOWER_THAN, DEFAULT_MAX_LEN)
+    }
+}
+
+impl Slowlog {
+    /// Creates an empty register with the given threshold (in microseconds) and length.
+    pub fn new(log_slower_than: i64, max_len: usize) -> Self {
+        Slowlog {
+            state: Arc::new(Mutex::new(SlowlogState {
+                log_slower_than,
+                max_len,
+                next_id: 0,
+                entries: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Registers the execution of the command if it took longer than the threshold.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The slowlog is poisoned.
+    pub fn record(
+        &self,
+        args: &[String],
+        duration: Duration,
+        client_addr: String,
+        client_name: String,
+    ) -> Result<(), ErrorStruct> {
+        let mut state = self.lock()?;
+        let duration = duration.as_micros() as u64;
+        if state.log_slower_than < 0 || duration < state.log_slower_than as u64 {
+            return Ok(());
+        }
+
+        let entry = SlowlogEntry {
+            id: state.next_id,
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|time| time.as_secs())
+                .unwrap_or(0),
+            duration,
+            args: truncate_args(args),
+            client_addr,
+            client_name,
+        };
+        state.next_id += 1;
+        state.entries.push_front(entry);
+        let max_len = state.max_len;
+        state.entries.truncate(max_len);
+        Ok(())
+    }
+
+    /// Returns up to `count` of the newest entries, or all of them if `count` is [None].
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The slowlog is poisoned.
+    pub fn get(&self, count: Option<usize>) -> Result<Vec<SlowlogEntry>, ErrorStruct> {
+        let state = self.lock()?;
+        let count = count.unwrap_or(state.entries.len());
+        Ok(state.entries.iter().take(count).cloned().collect())
+    }
+
+    /// Returns the number of entries in the slowlog.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The slowlog is poisoned.
+    pub fn len(&self) -> Result<usize, ErrorStruct> {
+        Ok(self.lock()?.entries.len())
+    }
+
+    /// Returns true if the slowlog has no entries.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The slowlog is poisoned.
+    pub fn is_empty(&self) -> Result<bool, ErrorStruct> {
+        Ok(self.lock()?.entries.is_empty())
+    }
+
+    /// Removes every entry of the slowlog.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The slowlog is poisoned.
+    pub fn reset(&self) -> Result<(), ErrorStruct> {
+        self.lock()?.entries.clear();
+        Ok(())
+    }
+
+    /// Changes the threshold, in microseconds, of *slowlog-log-slower-than*.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The slowlog is poisoned.
+    pub fn set_log_slower_than(&self, log_slower_than: i64) -> Result<(), ErrorStruct> {
+        self.lock()?.log_slower_than = log_slower_than;
+        Ok(())
+    }
+
+    /// Changes *slowlog-max-len*, discarding the oldest entries that no longer fit.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The slowlog is poisoned.
+    pub fn set_max_len(&self, max_len: usize) -> Result<(), ErrorStruct> {
+        let mut state = self.lock()?;
+        state.max_len = max_len;
+        state.entries.truncate(max_len);
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, SlowlogState>, ErrorStruct> {
+        self.state.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "slowlog",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })
+    }
+}
+
+/// Keeps at most [MAX_ARGS] arguments of at most [MAX_ARG_LEN] bytes,
+/// describing what was left out as Redis does.
+fn truncate_args(args: &[String]) -> Vec<String> {
+    let kept = if args.len() > MAX_ARGS {
+        MAX_ARGS - 1
+    } else {
+        args.len()
+    };
+    let mut truncated: Vec<String> = args[..kept]
+        .iter()
+        .map(|arg| {
+            if arg.len() > MAX_ARG_LEN {
+                let mut end = MAX_ARG_LEN;
+                while !arg.is_char_boundary(end) {
+                    end -= 1;
+                }
+                format!("{}... ({} more bytes)", &arg[..end], arg.len() - end)
+            } else {
+                arg.to_string()
+            }
+        })
+        .collect();
+    if kept < args.len() {
+        truncated.push(format!("... ({} more arguments)", args.len() - kept));
+    }
+    truncated
+}
+
+#[cfg(test)]
+mod test_slowlog {
+    use super::*;
+    use crate::vec_strings;
+
+    fn record(slowlog: &Slowlog, args: Vec<String>, micros: u64) {
+        slowlog
+            .record(
+                &args,
+                Duration::from_micros(micros),
+                "127.0.0.1:8080".to_string(),
+                String::new(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_01_only_slower_commands_are_registered() {
+        let slowlog = Slowlog::new(100, 10);
+        record(&slowlog, vec_strings!["get", "key"], 99);
+        record(&slowlog, vec_strings!["set", "key", "value"], 100);
+        let entries = slowlog.get(None).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].args, vec_strings!["set", "key", "value"]);
+        assert_eq!(entries[0].duration, 100);
+        assert_eq!(entries[0].client_addr, "127.0.0.1:8080");
+    }
+
+    #[test]
+    fn test_02_negative_threshold_disables_the_slowlog() {
+        let slowlog = Slowlog::new(-1, 10);
+        record(&slowlog, vec_strings!["get", "key"], 1000000);
+        assert!(slowlog.is_empty().unwrap());
+    }
+
+    #[test]
+    fn test_03_oldest_entries_are_discarded() {
+        let slowlog = Slowlog::new(0, 2);
+        record(&slowlog, vec_strings!["first"], 1);
+        record(&slowlog, vec_strings!["second"], 1);
+        record(&slowlog, vec_strings!["third"], 1);
+        let entries = slowlog.get(None).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, 2);
+        assert_eq!(entries[0].args, vec_strings!["third"]);
+        assert_eq!(entries[1].args, vec_strings!["second"]);
+    }
+
+    #[test]
+    fn test_04_get_count_and_reset() {
+        let slowlog = Slowlog::new(0, 10);
+        record(&slowlog, vec_strings!["first"], 1);
+        record(&slowlog, vec_strings!["second"], 1);
+        assert_eq!(slowlog.get(Some(1)).unwrap().len(), 1);
+        assert_eq!(slowlog.len().unwrap(), 2);
+        slowlog.reset().unwrap();
+        assert_eq!(slowlog.len().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_05_long_arguments_are_truncated() {
+        let args: Vec<String> = (0..40).map(|i| "a".repeat(i * 10)).collect();
+        let truncated = truncate_args(&args);
+        assert_eq!(truncated.len(), MAX_ARGS);
+        assert_eq!(truncated[MAX_ARGS - 1], "... (9 more arguments)");
+        assert_eq!(truncated[13], "a".repeat(128) + "... (2 more bytes)");
+    }
+
+    #[test]
+    fn test_06_shorter_max_len_discards_entries() {
+        let slowlog = Slowlog::new(0, 10);
+        record(&slowlog, vec_strings!["first"], 1);
+        record(&slowlog, vec_strings!["second"], 1);
+        slowlog.set_max_len(1).unwrap();
+        assert_eq!(slowlog.len().unwrap(), 1);
+    }
+}
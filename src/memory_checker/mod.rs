@@ -1,2 +1,2 @@
-pub mod garbage_collector;
 pub mod periodic_executor;
+pub mod saver;
@@ -10,25 +10,26 @@ use crate::{
 use crate::native_types::ErrorStruct;
 
 use crate::tcp_protocol::Response;
+use crate::time_expiration::clock::Clock;
 
-use std::sync::Arc;
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Mutex,
-};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
-use std::time::Duration;
-use std::{
-    sync::mpsc::{self, Receiver},
-    thread::sleep,
-};
+use std::time::{Duration, SystemTime};
+
+/// Returns the command of a [PeriodicExecutor] and its period in seconds.
+/// It is called again every second, so the executor follows the changes of
+/// the configuration without restarting. A period of 0 disables the command.
+pub type Schedule = Box<dyn Fn() -> Result<(Vec<String>, u64), ErrorStruct> + Send>;
 
 /// This structure sleeps periodically and execute
-/// a given command. When it is needed, the loop
+/// the command of its [Schedule]. When it is needed, the loop
 /// stops and ends the periodic execution.
+/// The period is measured with the received [Clock], and a stop wakes the
+/// loop at once.
 pub struct PeriodicExecutor {
     handle: Option<JoinHandle<Result<(), ErrorStruct>>>,
-    still_working: Arc<AtomicBool>,
+    stop: Option<Sender<()>>,
     notifier: Notifier,
     name: String,
 }
@@ -36,47 +37,51 @@ pub struct PeriodicExecutor {
 impl PeriodicExecutor {
     /// Creates the structure
     pub fn new(
-        command: Vec<String>,
-        period: u64,
+        schedule: Schedule,
         notifier: Notifier,
         name: &str,
+        clock: Arc<dyn Clock>,
     ) -> PeriodicExecutor {
-        let still_working = Arc::new(AtomicBool::new(true));
-        let still_working_clone = Arc::clone(&still_working);
+        let (stop, stopped) = mpsc::channel();
         let c_notifier = notifier.clone();
+        let started = clock.now();
 
         let periodic_executor_handle = std::thread::spawn(move || {
-            PeriodicExecutor::init(command, period, c_notifier, still_working_clone)
+            PeriodicExecutor::init(schedule, c_notifier, stopped, clock, started)
         });
 
         PeriodicExecutor {
             handle: Some(periodic_executor_handle),
-            still_working,
+            stop: Some(stop),
             notifier,
             name: String::from(name),
         }
     }
 
     /// Initialize the loop that periodically send the
-    /// command.
+    /// command. The period is counted from the last execution, or from
+    /// the last check while the command was disabled.
     fn init(
-        command: Vec<String>,
-        period: u64,
+        schedule: Schedule,
         notifier: Notifier,
-        still_working_clone: Arc<AtomicBool>,
+        stopped: Receiver<()>,
+        clock: Arc<dyn Clock>,
+        started: SystemTime,
     ) -> Result<(), ErrorStruct> {
         let (snd_rsp, rcv_rsp): (mpsc::Sender<Response>, mpsc::Receiver<Response>) =
             mpsc::channel();
-        let mut counter = 0;
+        let mut last_execution = started;
 
         loop {
-            sleep(Duration::from_secs(1));
-            counter += 1;
-
-            if !still_working_clone.load(Ordering::Relaxed) {
+            if clock.sleep_unless_stopped(Duration::from_secs(1), &stopped) {
                 return Ok(());
-            } else if counter == period {
-                counter = 0;
+            }
+            let now = clock.now();
+            let (command, period) = schedule()?;
+            if period == 0 {
+                last_execution = now;
+            } else if now >= last_execution + Duration::from_secs(period) {
+                last_execution = now;
                 notifier.send_command_delegator(Some((
                     command.clone(),
                     snd_rsp.clone(),
@@ -85,7 +90,7 @@ impl PeriodicExecutor {
                 PeriodicExecutor::receive_result(&rcv_rsp)?;
                 notifier.notify_successful_shipment(
                     &Arc::new(Mutex::new(ClientFields::default())),
-                    command.clone(),
+                    command,
                 )?;
             }
         }
@@ -111,9 +116,9 @@ impl PeriodicExecutor {
         )
     }
 
-    /// Stops the loop and finishes the job
+    /// Stops the loop and finishes the job, waking it if it sleeps
     fn stop(&mut self) {
-        self.still_working.store(false, Ordering::Relaxed);
+        self.stop.take();
     }
 }
 
@@ -130,17 +135,36 @@ impl Joinable<()> for PeriodicExecutor {
 }
 
 #[cfg(test)]
-
 mod test_periodic_executor {
 
+    use std::sync::atomic::AtomicBool;
     use std::thread;
+    use std::time::Instant;
 
     use super::*;
     use crate::{
         native_types::{RSimpleString, RedisType},
         tcp_protocol::RawCommand,
+        time_expiration::clock::{ManualClock, SystemClock},
     };
 
+    fn every(period: u64) -> Schedule {
+        Box::new(move || Ok((vec!["clean".to_string(), "20".to_string()], period)))
+    }
+
+    fn reply_ok(rcv_test_cmd: &Receiver<Option<RawCommand>>) -> Vec<String> {
+        let (command, sender, _client_fields) = rcv_test_cmd.recv().unwrap().unwrap();
+        sender
+            .send(Ok(RSimpleString::encode("OK".to_string())))
+            .unwrap();
+        let (notification, sender, _client_fields) = rcv_test_cmd.recv().unwrap().unwrap();
+        assert_eq!(notification[0], "notifymonitors");
+        sender
+            .send(Ok(RSimpleString::encode("OK".to_string())))
+            .unwrap();
+        command
+    }
+
     // Para probar los test 1 y 3, hagan fallar el test
     // y verifiquen que se imprima un mensaje indicando que
     // se dropeo el Garbage Collector
@@ -164,8 +188,8 @@ mod test_periodic_executor {
             "test_addr_useless".into(),
         );
 
-        let command = vec!["clean".to_string(), "20".to_string()];
-        let mut collector = PeriodicExecutor::new(command, 10, notifier.clone(), "clean");
+        let mut collector =
+            PeriodicExecutor::new(every(10), notifier.clone(), "clean", Arc::new(SystemClock));
 
         assert_eq!(4, 4);
         drop(notifier);
@@ -194,8 +218,8 @@ mod test_periodic_executor {
             Arc::new(AtomicBool::new(false)),
             "test_addr".into(),
         );
-        let command = vec!["clean".to_string(), "20".to_string()];
-        let mut collector = PeriodicExecutor::new(command, 1, notifier.clone(), "clean");
+        let mut collector =
+            PeriodicExecutor::new(every(1), notifier.clone(), "clean", Arc::new(SystemClock));
         let (command_recv, sender, rcv_client_fields): RawCommand =
             rcv_test_cmd.recv().unwrap().unwrap();
 
@@ -232,8 +256,8 @@ mod test_periodic_executor {
             Arc::new(AtomicBool::new(false)),
             "test_addr".into(),
         );
-        let command = vec!["clean".to_string(), "20".to_string()];
-        let mut collector = PeriodicExecutor::new(command, 1, notifier.clone(), "clean");
+        let mut collector =
+            PeriodicExecutor::new(every(1), notifier.clone(), "clean", Arc::new(SystemClock));
         let (_command, sender, rcv_client_fields) = rcv_test_cmd.recv().unwrap().unwrap();
         sender
             .send(Err(ErrorStruct::new(
@@ -253,4 +277,89 @@ mod test_periodic_executor {
         drop(collector);
         let _ = rcv_notifier.join();
     }
+
+    #[test]
+    fn test_04_command_is_sent_when_the_clock_reaches_the_period() {
+        let (snd_test_cmd, rcv_test_cmd) = mpsc::channel();
+        let (snd_log_test, _rcv_log_test) = mpsc::channel();
+        let notifier = Notifier::new(
+            snd_log_test,
+            snd_test_cmd,
+            Arc::new(AtomicBool::new(false)),
+            "test_addr".into(),
+        );
+        let clock = Arc::new(ManualClock::default());
+        let mut collector =
+            PeriodicExecutor::new(every(10), notifier.clone(), "clean", clock.clone());
+
+        clock.advance_secs(9);
+        assert!(rcv_test_cmd
+            .recv_timeout(Duration::from_millis(100))
+            .is_err());
+        clock.advance_secs(1);
+        assert_eq!(
+            reply_ok(&rcv_test_cmd),
+            vec!["clean".to_string(), "20".to_string()]
+        );
+
+        collector.join().unwrap();
+    }
+
+    #[test]
+    fn test_05_changes_of_the_schedule_are_followed_while_running() {
+        let (snd_test_cmd, rcv_test_cmd) = mpsc::channel();
+        let (snd_log_test, _rcv_log_test) = mpsc::channel();
+        let notifier = Notifier::new(
+            snd_log_test,
+            snd_test_cmd,
+            Arc::new(AtomicBool::new(false)),
+            "test_addr".into(),
+        );
+        let clock = Arc::new(ManualClock::default());
+        let settings = Arc::new(Mutex::new((10, 20)));
+        let schedule: Schedule = {
+            let settings = Arc::clone(&settings);
+            Box::new(move || {
+                let (period, sample) = *settings.lock().unwrap();
+                Ok((vec!["clean".to_string(), sample.to_string()], period))
+            })
+        };
+        let mut collector =
+            PeriodicExecutor::new(schedule, notifier.clone(), "clean", clock.clone());
+
+        clock.advance_secs(5);
+        *settings.lock().unwrap() = (3, 50);
+        assert_eq!(reply_ok(&rcv_test_cmd)[1], "50");
+
+        *settings.lock().unwrap() = (0, 50);
+        clock.advance_secs(100);
+        assert!(rcv_test_cmd
+            .recv_timeout(Duration::from_millis(100))
+            .is_err());
+
+        *settings.lock().unwrap() = (3, 50);
+        clock.advance_secs(3);
+        assert_eq!(reply_ok(&rcv_test_cmd)[1], "50");
+
+        collector.join().unwrap();
+    }
+
+    #[test]
+    fn test_06_join_wakes_the_sleeping_loop() {
+        let (snd_test_cmd, _rcv_test_cmd) = mpsc::channel();
+        let (snd_log_test, _rcv_log_test) = mpsc::channel();
+        let notifier = Notifier::new(
+            snd_log_test,
+            snd_test_cmd,
+            Arc::new(AtomicBool::new(false)),
+            "test_addr".into(),
+        );
+        let mut collector =
+            PeriodicExecutor::new(every(10), notifier, "clean", Arc::new(SystemClock));
+        let start = Instant::now();
+
+        collector.join().unwrap();
+
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
 }
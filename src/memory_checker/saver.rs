@@ -0,0 +1,124 @@
+use crate::{
+    database::Database, joinable::Joinable, messages::redis_messages,
+    native_types::error_severity::ErrorSeverity, redis_config::RedisConfig,
+    tcp_protocol::close_thread, tcp_protocol::notifier::Notifier,
+};
+
+use crate::native_types::ErrorStruct;
+use crate::time_expiration::clock::Clock;
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+/// This structure checks the save points of the [RedisConfig]
+/// every `save-interval` seconds and, when one of them is reached,
+/// starts a background save of the [Database]. The interval is read
+/// again every second, and with 0 the checks are disabled.
+/// The seconds are measured with the received [Clock], and a stop wakes the
+/// loop at once.
+pub struct Saver {
+    handle: Option<JoinHandle<Result<(), ErrorStruct>>>,
+    stop: Option<Sender<()>>,
+    notifier: Notifier,
+}
+
+impl Saver {
+    /// Creates the structure
+    pub fn new(
+        database: Arc<Mutex<Database>>,
+        config: Arc<Mutex<RedisConfig>>,
+        notifier: Notifier,
+        clock: Arc<dyn Clock>,
+    ) -> Saver {
+        let (stop, stopped) = mpsc::channel();
+        let started = clock.now();
+
+        let saver_handle =
+            std::thread::spawn(move || Saver::init(database, config, stopped, clock, started));
+
+        Saver {
+            handle: Some(saver_handle),
+            stop: Some(stop),
+            notifier,
+        }
+    }
+
+    /// Initialize the loop that checks the save points.
+    fn init(
+        database: Arc<Mutex<Database>>,
+        config: Arc<Mutex<RedisConfig>>,
+        stopped: Receiver<()>,
+        clock: Arc<dyn Clock>,
+        started: SystemTime,
+    ) -> Result<(), ErrorStruct> {
+        let mut last_check = started;
+        loop {
+            if clock.sleep_unless_stopped(Duration::from_secs(1), &stopped) {
+                return Ok(());
+            }
+            let now = clock.now();
+            let interval = config
+                .lock()
+                .map_err(|_| {
+                    ErrorStruct::from(redis_messages::poisoned_lock(
+                        "redis config",
+                        ErrorSeverity::ShutdownServer,
+                    ))
+                })?
+                .save_interval();
+            if interval == 0 {
+                last_check = now;
+                continue;
+            } else if now < last_check + Duration::from_secs(interval) {
+                continue;
+            }
+            last_check = now;
+            let mut database = database.lock().map_err(|_| {
+                ErrorStruct::from(redis_messages::poisoned_lock(
+                    "database",
+                    ErrorSeverity::ShutdownServer,
+                ))
+            })?;
+            if database.save_point_reached() {
+                // A failed save is retried at the next check
+                let _ = database.background_save();
+            }
+        }
+    }
+
+    /// Stops the loop and finishes the job, waking it if it sleeps
+    fn stop(&mut self) {
+        self.stop.take();
+    }
+}
+
+impl Joinable<()> for Saver {
+    fn join(&mut self) -> Result<(), ErrorStruct> {
+        self.stop();
+        close_thread(self.handle.take(), "Saver", self.notifier.clone())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_saver {
+    use super::*;
+    use crate::commands::create_notifier;
+    use crate::time_expiration::clock::SystemClock;
+    use std::time::Instant;
+
+    #[test]
+    fn test_01_join_wakes_the_sleeping_loop() {
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier.clone())));
+        let config = Arc::new(Mutex::new(RedisConfig::default()));
+        let mut saver = Saver::new(database, config, notifier, Arc::new(SystemClock));
+        let start = Instant::now();
+
+        saver.join().unwrap();
+
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+}
@@ -73,6 +73,614 @@ pub mod redis_messages {
         }
     }
 
+    pub fn unknown_subcommand(item: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!(
+                "Unknown subcommand or wrong number of arguments for '{}'",
+                item
+            ),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn no_such_client() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "No such client".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn unknown_client_type(client_type: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("Unknown client type '{}'", client_type),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_client_name() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Client names cannot contain spaces, newlines or special characters."
+                .to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn timeout_not_integer() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "timeout is not an integer or out of range".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn redirect_client_not_found() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "The client ID you want redirect to does not exist".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn prefix_requires_bcast() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "PREFIX option requires BCAST mode to be enabled".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn optin_and_optout() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "You can't use both OPTIN and OPTOUT".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn bcast_with_optin_optout() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "OPTIN and OPTOUT are not compatible with BCAST".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn caching_not_allowed() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "CLIENT CACHING can be called only when the client is in tracking mode with OPTIN or OPTOUT mode enabled".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_slowlog_count() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "count should be greater than or equal to -1".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_command_specified() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Invalid command specified".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_number_of_arguments_specified() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Invalid number of arguments specified for command".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn command_has_no_keys() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "The command has no key arguments".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_stream_id() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Invalid stream ID specified as stream command argument".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn stream_id_zero() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "The ID specified in XADD must be greater than 0-0".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn stream_id_too_small() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "The ID specified in XADD is equal or smaller than the target stream top item"
+                .to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn unbalanced_streams(command: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("Unbalanced '{}' list of streams: for each stream key an ID or '$' must be specified.", command),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn bit_offset_out_of_range() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "bit offset is not an integer or out of range".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn bit_out_of_range() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "bit is not an integer or out of range".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn bit_argument() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "The bit argument must be 1 or 0.".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn bitop_not_single_key() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "BITOP NOT must be called with a single source key.".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_bitfield_type() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message:
+                "Invalid bitfield type. Use something like i16 u8. Note that u64 is not supported but i64 is."
+                    .to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_overflow_type() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Invalid OVERFLOW type specified".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn bitfield_ro_only_get() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "BITFIELD_RO only supports the GET subcommand".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn not_hyperloglog() -> MessageRedis {
+        MessageRedis {
+            prefix: "WRONGTYPE".to_string(),
+            message: "Key is not a valid HyperLogLog string value.".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn corrupted_hyperloglog() -> MessageRedis {
+        MessageRedis {
+            prefix: "INVALIDOBJ".to_string(),
+            message: "Corrupted HLL object detected".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn not_valid_float() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "value is not a valid float".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn increment_overflow() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "increment or decrement would overflow".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn nan_or_infinity() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "increment would produce NaN or Infinity".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn offset_out_of_range() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "offset is out of range".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn string_exceeds_maximum_size() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "string exceeds maximum allowed size (proto-max-bulk-len)".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_expire_time(command: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("invalid expire time in '{}' command", command),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn lcs_len_and_idx() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "If you want both the length and indexes, please just use IDX.".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn lcs_insufficient_memory() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Insufficient memory, transient memory for LCS exceeds proto-max-bulk-len"
+                .to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn xx_and_nx_incompatible() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "XX and NX options at the same time are not compatible".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_longitude_latitude(longitude: f64, latitude: f64) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!(
+                "invalid longitude,latitude pair {:.6},{:.6}",
+                longitude, latitude
+            ),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn unsupported_unit() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "unsupported unit provided. please use M, KM, FT, MI".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn zset_member_not_found() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "could not decode requested zset member".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn exactly_one_of(first: &str, second: &str, command: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!(
+                "exactly one of {} or {} can be specified for {}",
+                first, second, command
+            ),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn negative_search_shape() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "radius, height or width cannot be negative".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn count_must_be_positive() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "COUNT must be > 0".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn any_requires_count() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "the ANY argument requires COUNT argument".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn geosearchstore_with_options() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message:
+                "GEOSEARCHSTORE is not compatible with WITHDIST, WITHHASH and WITHCOORD options"
+                    .to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn busy_group() -> MessageRedis {
+        MessageRedis {
+            prefix: "BUSYGROUP".to_string(),
+            message: "Consumer Group name already exists".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn no_such_group(key: &str, group: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "NOGROUP".to_string(),
+            message: format!("No such key '{}' or consumer group '{}'", key, group),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn xgroup_requires_key() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use the MKSTREAM option to create an empty stream automatically.".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn no_such_key() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "no such key".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn db_index_out_of_range() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "DB index is out of range".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn same_source_and_destination() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "source and destination objects are the same".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn nx_and_xx_gt_lt_incompatible() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "NX and XX, GT or LT options at the same time are not compatible".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn gt_and_lt_incompatible() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "GT and LT options at the same time are not compatible".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn unsupported_option(option: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("Unsupported option {}", option),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn background_save_in_progress() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Background save already in progress".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn corrupted_dump(corruption: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: corruption.to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_json(detail: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("invalid JSON: {}", detail),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_line(number: usize, detail: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("line {}: {}", number, detail),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn file_error(path: &str, detail: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("{}: {}", path, detail),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn wrong_usage(usage: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("wrong arguments. Usage:\n{}", usage),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn connection_failed(address: &str, detail: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("Could not connect to Redis at {}: {}", address, detail),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn connection_closed() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Server closed the connection".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn protocol_error(detail: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("Protocol error: {}", detail),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn unexpected_reply(expected: &str, received: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("Unexpected reply: expected {}, got {}", expected, received),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn pool_exhausted(size: usize) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("The {} connections of the pool are in use", size),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_arguments() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Invalid argument(s)".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn failed_commands(errors: usize) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("{} commands replied with an error", errors),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_rdb(detail: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("Bad RDB file: {}", detail),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn dump_payload_invalid() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "DUMP payload version or checksum are wrong".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn busy_key() -> MessageRedis {
+        MessageRedis {
+            prefix: "BUSYKEY".to_string(),
+            message: "Target key name already exists.".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_ttl() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Invalid TTL value, must be >= 0".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_idletime() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Invalid IDLETIME value, must be >= 0".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_freq() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "Invalid FREQ value, must be >= 0 and <= 255".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn migrate_io_error(action: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "IOERR".to_string(),
+            message: format!("error or timeout {} target instance", action),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn target_replied_error(error: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!("Target instance replied with error: {}", error),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
     pub fn wrongtype() -> MessageRedis {
         MessageRedis {
             prefix: "WRONGTYPE".to_string(),
@@ -81,6 +689,44 @@ pub mod redis_messages {
         }
     }
 
+    pub fn value_must_be_positive() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "value is out of range, must be positive".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn invalid_config_argument(parameter: &str, detail: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!(
+                "CONFIG SET failed (possibly related to argument '{}') - {}",
+                parameter, detail
+            ),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn unknown_config_option(name: &str) -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: format!(
+                "Unknown option or number of arguments for CONFIG SET - '{}'",
+                name
+            ),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
+    pub fn no_config_file() -> MessageRedis {
+        MessageRedis {
+            prefix: "ERR".to_string(),
+            message: "The server is running without a config file".to_string(),
+            severity: ErrorSeverity::Comunicate,
+        }
+    }
+
     pub fn negative_number() -> MessageRedis {
         MessageRedis {
             prefix: "NEG".to_string(),
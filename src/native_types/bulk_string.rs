@@ -8,6 +8,37 @@ use super::{
 /// Redis native type: BulkString
 pub struct RBulkString;
 
+/// First character of the replies whose characters each stand for one byte,
+/// so binary values travel as a [String] to the socket.
+const BINARY_REPLY: char = '\u{0}';
+
+impl RBulkString {
+    /// Encodes the raw bytes of a value. If they are not valid UTF-8, the reply
+    /// is marked so [wire_bytes] writes the original bytes in the socket.
+    pub fn encode_bytes(value: &[u8]) -> String {
+        let mut encoded = format!("${}\r\n", value.len());
+        match std::str::from_utf8(value) {
+            Ok(text) => encoded.push_str(text),
+            Err(_) => {
+                encoded.insert(0, BINARY_REPLY);
+                encoded.extend(value.iter().map(|byte| char::from(*byte)));
+            }
+        }
+        encoded.push_str("\r\n");
+        encoded
+    }
+}
+
+/// Returns the bytes to write in the socket for a reply, restoring the binary
+/// values of the replies built by [RBulkString::encode_bytes].
+pub fn wire_bytes(reply: &str) -> Vec<u8> {
+    match reply.strip_prefix(BINARY_REPLY) {
+        // Every character of a marked reply is below 256
+        Some(binary) => binary.chars().map(|c| c as u8).collect(),
+        None => reply.as_bytes().to_vec(),
+    }
+}
+
 impl RedisType<String> for RBulkString {
     fn encode(text: String) -> String {
         if text == "(nil)" {
@@ -37,6 +68,7 @@ mod test_bulk_string {
 
     use super::*;
     use std::io::BufReader;
+
     #[test]
     fn test_04_encoding_of_a_bulk_string() {
         let bulk = String::from("Hello world");
@@ -157,4 +189,14 @@ mod test_bulk_string {
         assert_eq!(bulks.pop().unwrap(), "SET".to_string());
     }
     */
+
+    #[test]
+    fn test_11_bytes_are_written_as_they_were_encoded() {
+        let text = RBulkString::encode_bytes("héllo".as_bytes());
+        let binary = RBulkString::encode_bytes(&[0x80, b'a', 0xff]);
+
+        assert_eq!(text, "$6\r\nhéllo\r\n");
+        assert_eq!(wire_bytes(&text), "$6\r\nhéllo\r\n".as_bytes());
+        assert_eq!(wire_bytes(&binary), b"$3\r\n\x80a\xff\r\n".to_vec());
+    }
 }
@@ -4,7 +4,7 @@ use super::error_severity::ErrorSeverity;
 use super::redis_type::RedisType;
 use crate::messages::MessageRedis;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 /// This structure encapsulate information
 /// about an error that could be thrown from
 /// any execution.
@@ -42,6 +42,10 @@ impl ErrorStruct {
         Some(&self.prefix)
     }
 
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
     pub fn severity(&self) -> Option<&ErrorSeverity> {
         Some(&self.severity)
     }
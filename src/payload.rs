@@ -0,0 +1,172 @@
+use crate::database::{decode_hex, encode_hex, TypeSaved};
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use crate::sorted_set::SortedSet;
+use crate::stream::Stream;
+use std::collections::VecDeque;
+
+/// Version of the payload format, checked when restoring.
+pub const PAYLOAD_VERSION: u16 = 1;
+
+/// Reversed Jones polynomial, used by Redis for its CRC64 checksums.
+const CRC64_POLYNOMIAL: u64 = 0x95ac_9329_ac4b_c9b5;
+
+const STRING: u8 = 0;
+const LIST: u8 = 1;
+const SET: u8 = 2;
+const STREAM: u8 = 3;
+const SORTED_SET: u8 = 4;
+
+/// Serializes a value as used by DUMP and RESTORE: its type, the number of
+/// elements and each element prefixed by its length, followed by the version
+/// and the CRC64 of all the previous bytes, in little endian.
+///
+/// The protocol of this server is not binary-safe, so the payload is returned
+/// as hexadecimal text.
+pub fn serialize(value: &TypeSaved) -> String {
+    let (kind, elements) = match value {
+        TypeSaved::String(bytes) => (STRING, vec![bytes.clone()]),
+        TypeSaved::List(list) => (LIST, as_bytes(list.iter())),
+        TypeSaved::Set(set) => (SET, as_bytes(set.iter())),
+        TypeSaved::Stream(stream) => (STREAM, as_bytes(stream.to_vec().iter())),
+        TypeSaved::SortedSet(sorted_set) => (SORTED_SET, as_bytes(sorted_set.to_vec().iter())),
+    };
+    let mut payload = vec![kind];
+    payload.extend_from_slice(&(elements.len() as u32).to_le_bytes());
+    for element in elements {
+        payload.extend_from_slice(&(element.len() as u32).to_le_bytes());
+        payload.extend_from_slice(&element);
+    }
+    payload.extend_from_slice(&PAYLOAD_VERSION.to_le_bytes());
+    let checksum = crc64(&payload);
+    payload.extend_from_slice(&checksum.to_le_bytes());
+    encode_hex(&payload)
+}
+
+/// Rebuilds a value serialized by [serialize].
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The version or the checksum of the payload are wrong.
+/// * The payload is corrupted.
+pub fn deserialize(payload: &str) -> Result<TypeSaved, ErrorStruct> {
+    let wrong = || ErrorStruct::from(redis_messages::dump_payload_invalid());
+    let payload = decode_hex(payload).map_err(|_| wrong())?;
+    let (body, checksum) = payload
+        .split_at_checked(payload.len().wrapping_sub(8))
+        .ok_or_else(wrong)?;
+    let (data, version) = body
+        .split_at_checked(body.len().wrapping_sub(2))
+        .ok_or_else(wrong)?;
+    if checksum != crc64(body).to_le_bytes() || version != PAYLOAD_VERSION.to_le_bytes() {
+        return Err(wrong());
+    }
+
+    let mut reader = Reader { data, position: 0 };
+    let kind = reader.take(1).ok_or_else(wrong)?[0];
+    let count = reader.length().ok_or_else(wrong)?;
+    let mut elements = Vec::new();
+    for _ in 0..count {
+        let len = reader.length().ok_or_else(wrong)?;
+        elements.push(reader.take(len).ok_or_else(wrong)?.to_vec());
+    }
+    if reader.position != data.len() {
+        return Err(wrong());
+    }
+
+    let texts = elements
+        .iter()
+        .map(|element| String::from_utf8(element.clone()).map_err(|_| wrong()));
+    Ok(match kind {
+        STRING => TypeSaved::String(elements.into_iter().next().ok_or_else(wrong)?),
+        LIST => TypeSaved::List(texts.collect::<Result<VecDeque<_>, _>>()?),
+        SET => TypeSaved::Set(texts.collect::<Result<_, _>>()?),
+        STREAM => TypeSaved::Stream(Stream::from_vec(texts.collect::<Result<_, _>>()?)?),
+        SORTED_SET => TypeSaved::SortedSet(SortedSet::from_vec(texts.collect::<Result<_, _>>()?)?),
+        _ => return Err(wrong()),
+    })
+}
+
+/// Computes the CRC64 of the bytes, with the same parameters as Redis.
+pub fn crc64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |mut crc, byte| {
+        crc ^= *byte as u64;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ CRC64_POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+        }
+        crc
+    })
+}
+
+fn as_bytes<'a>(elements: impl Iterator<Item = &'a String>) -> Vec<Vec<u8>> {
+    elements
+        .map(|element| element.as_bytes().to_vec())
+        .collect()
+}
+
+/// Cursor over the bytes of a payload.
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.position..self.position.checked_add(len)?)?;
+        self.position += len;
+        Some(bytes)
+    }
+
+    fn length(&mut self) -> Option<usize> {
+        let bytes = self.take(4)?;
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    }
+}
+
+#[cfg(test)]
+mod test_payload {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_01_crc64_matches_redis() {
+        assert_eq!(crc64(b"123456789"), 0xe9c6d914c4b8d9ca);
+    }
+
+    #[test]
+    fn test_02_serialize_and_deserialize_every_type() {
+        let mut sorted_set = SortedSet::new();
+        sorted_set.insert("member", 1.5);
+        let values = vec![
+            TypeSaved::String(vec![0, 255, b'\n', b'a']),
+            TypeSaved::List(VecDeque::from(vec!["a".to_string(), "b".to_string()])),
+            TypeSaved::Set(HashSet::from(["a".to_string()])),
+            TypeSaved::Stream(Stream::new()),
+            TypeSaved::SortedSet(sorted_set),
+        ];
+        for value in values {
+            assert_eq!(deserialize(&serialize(&value)).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_03_corrupted_payloads_are_rejected() {
+        let payload = serialize(&TypeSaved::String(b"value".to_vec()));
+        let mut corrupted = payload.clone().into_bytes();
+        corrupted[4] = if corrupted[4] == b'0' { b'1' } else { b'0' };
+        let corrupted = String::from_utf8(corrupted).unwrap();
+        for payload in [corrupted.as_str(), &payload[4..], "", "zz"] {
+            assert_eq!(
+                deserialize(payload).unwrap_err().print_it(),
+                "ERR DUMP payload version or checksum are wrong"
+            );
+        }
+    }
+}
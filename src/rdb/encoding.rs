@@ -0,0 +1,304 @@
+//! Lengths, strings and numbers of the RDB format.
+
+use super::{corrupted, lzf};
+use crate::native_types::ErrorStruct;
+use std::convert::TryFrom;
+
+/// Lengths of 6 bits, in the same byte as the type.
+const LEN_6BIT: u8 = 0;
+/// Lengths of 14 bits, in the type byte and the next one.
+const LEN_14BIT: u8 = 1;
+/// Lengths of 32 and 64 bits, big endian, after the type byte.
+const LEN_32BIT: u8 = 0x80;
+const LEN_64BIT: u8 = 0x81;
+/// Strings encoded in a special way, where the 6 bits of the type byte are
+/// one of the `ENC_*` values.
+const ENCODED: u8 = 3;
+const ENC_INT8: u8 = 0;
+const ENC_INT16: u8 = 1;
+const ENC_INT32: u8 = 2;
+const ENC_LZF: u8 = 3;
+
+/// Strings longer than this are compressed with LZF, as Redis.
+const MIN_COMPRESSED_LEN: usize = 20;
+
+/// Builds the bytes of a RDB file.
+#[derive(Default)]
+pub struct RdbWriter {
+    bytes: Vec<u8>,
+}
+
+impl RdbWriter {
+    pub fn new() -> Self {
+        RdbWriter::default()
+    }
+
+    pub fn byte(&mut self, byte: u8) {
+        self.bytes.push(byte);
+    }
+
+    pub fn raw(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Writes a length in the shortest encoding.
+    pub fn length(&mut self, len: u64) {
+        if len < 1 << 6 {
+            self.byte((LEN_6BIT << 6) | len as u8);
+        } else if len < 1 << 14 {
+            self.byte((LEN_14BIT << 6) | (len >> 8) as u8);
+            self.byte((len & 0xff) as u8);
+        } else if len <= u32::MAX as u64 {
+            self.byte(LEN_32BIT);
+            self.raw(&(len as u32).to_be_bytes());
+        } else {
+            self.byte(LEN_64BIT);
+            self.raw(&len.to_be_bytes());
+        }
+    }
+
+    /// Writes a string as an integer if it is one, compressed if it is long
+    /// and compressible, and as it is otherwise.
+    pub fn string(&mut self, bytes: &[u8]) {
+        if let Some(integer) = as_small_integer(bytes) {
+            if let Ok(integer) = i8::try_from(integer) {
+                self.byte((ENCODED << 6) | ENC_INT8);
+                self.raw(&integer.to_le_bytes());
+            } else if let Ok(integer) = i16::try_from(integer) {
+                self.byte((ENCODED << 6) | ENC_INT16);
+                self.raw(&integer.to_le_bytes());
+            } else {
+                self.byte((ENCODED << 6) | ENC_INT32);
+                self.raw(&integer.to_le_bytes());
+            }
+        } else if let Some(compressed) = (bytes.len() > MIN_COMPRESSED_LEN)
+            .then(|| lzf::compress(bytes, bytes.len() - 4))
+            .flatten()
+        {
+            self.byte((ENCODED << 6) | ENC_LZF);
+            self.length(compressed.len() as u64);
+            self.length(bytes.len() as u64);
+            self.raw(&compressed);
+        } else {
+            self.length(bytes.len() as u64);
+            self.raw(bytes);
+        }
+    }
+
+    /// Writes a unix time in milliseconds, little endian.
+    pub fn millis(&mut self, millis: u64) {
+        self.raw(&millis.to_le_bytes());
+    }
+
+    /// Writes a double, little endian.
+    pub fn double(&mut self, value: f64) {
+        self.raw(&value.to_le_bytes());
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Cursor over the bytes of a RDB file.
+pub struct RdbReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> RdbReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        RdbReader { data, position: 0 }
+    }
+
+    /// Number of bytes already read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], ErrorStruct> {
+        let bytes = self
+            .position
+            .checked_add(len)
+            .and_then(|end| self.data.get(self.position..end))
+            .ok_or_else(|| corrupted("unexpected end of file"))?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    pub fn byte(&mut self) -> Result<u8, ErrorStruct> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Reads a length, or fails if a special encoding is found.
+    pub fn length(&mut self) -> Result<u64, ErrorStruct> {
+        match self.length_or_encoding()? {
+            (len, false) => Ok(len),
+            (_, true) => Err(corrupted("unexpected string encoding")),
+        }
+    }
+
+    /// Reads a length as an usize.
+    pub fn count(&mut self) -> Result<usize, ErrorStruct> {
+        usize::try_from(self.length()?).map_err(|_| corrupted("length too large"))
+    }
+
+    /// Reads a string, in any of its encodings.
+    pub fn string(&mut self) -> Result<Vec<u8>, ErrorStruct> {
+        match self.length_or_encoding()? {
+            (len, false) => Ok(self.take(to_usize(len)?)?.to_vec()),
+            (encoding, true) => match encoding as u8 {
+                ENC_INT8 => Ok((self.byte()? as i8).to_string().into_bytes()),
+                ENC_INT16 => Ok(i16::from_le_bytes(self.array()?).to_string().into_bytes()),
+                ENC_INT32 => Ok(i32::from_le_bytes(self.array()?).to_string().into_bytes()),
+                ENC_LZF => {
+                    let compressed_len = self.count()?;
+                    let len = self.count()?;
+                    let compressed = self.take(compressed_len)?;
+                    lzf::decompress(compressed, len)
+                        .ok_or_else(|| corrupted("invalid LZF compressed string"))
+                }
+                _ => Err(corrupted("unknown string encoding")),
+            },
+        }
+    }
+
+    /// Reads a unix time in milliseconds, little endian.
+    pub fn millis(&mut self) -> Result<u64, ErrorStruct> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a double, little endian.
+    pub fn double(&mut self) -> Result<f64, ErrorStruct> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a double written as text after its length, as in the first
+    /// sorted set encoding. The lengths 253 to 255 are NaN and the infinities.
+    pub fn text_double(&mut self) -> Result<f64, ErrorStruct> {
+        match self.byte()? {
+            253 => Ok(f64::NAN),
+            254 => Ok(f64::INFINITY),
+            255 => Ok(f64::NEG_INFINITY),
+            len => std::str::from_utf8(self.take(len as usize)?)
+                .ok()
+                .and_then(|text| text.parse().ok())
+                .ok_or_else(|| corrupted("invalid double")),
+        }
+    }
+
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], ErrorStruct> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    /// Reads a length, returning true as second value if it is the kind of
+    /// a specially encoded string instead.
+    fn length_or_encoding(&mut self) -> Result<(u64, bool), ErrorStruct> {
+        let first = self.byte()?;
+        match first >> 6 {
+            LEN_6BIT => Ok(((first & 0x3f) as u64, false)),
+            LEN_14BIT => Ok(((((first & 0x3f) as u64) << 8) | self.byte()? as u64, false)),
+            ENCODED => Ok(((first & 0x3f) as u64, true)),
+            _ => match first {
+                LEN_32BIT => Ok((u32::from_be_bytes(self.array()?) as u64, false)),
+                LEN_64BIT => Ok((u64::from_be_bytes(self.array()?), false)),
+                _ => Err(corrupted("unknown length encoding")),
+            },
+        }
+    }
+}
+
+pub fn to_usize(len: u64) -> Result<usize, ErrorStruct> {
+    usize::try_from(len).map_err(|_| corrupted("length too large"))
+}
+
+/// Returns the string as an integer if it fits in 32 bits and is written in
+/// the canonical way, so that it is the same when read.
+fn as_small_integer(bytes: &[u8]) -> Option<i32> {
+    if bytes.len() > 11 {
+        return None;
+    }
+    let integer: i32 = std::str::from_utf8(bytes).ok()?.parse().ok()?;
+    (integer.to_string().as_bytes() == bytes).then_some(integer)
+}
+
+#[cfg(test)]
+mod test_encoding {
+    use super::*;
+
+    #[test]
+    fn test_01_lengths_use_the_shortest_encoding() {
+        let mut writer = RdbWriter::new();
+        for len in [10, 700, 70000, 1 << 40] {
+            writer.length(len);
+        }
+        assert_eq!(
+            writer.bytes(),
+            [
+                &[0x0a][..],
+                &[0x42, 0xbc],
+                &[0x80, 0x00, 0x01, 0x11, 0x70],
+                &[0x81, 0, 0, 1, 0, 0, 0, 0, 0]
+            ]
+            .concat()
+        );
+        let bytes = writer.into_bytes();
+        let mut reader = RdbReader::new(&bytes);
+        for len in [10, 700, 70000, 1 << 40] {
+            assert_eq!(reader.length().unwrap(), len);
+        }
+        assert!(reader.length().is_err());
+    }
+
+    #[test]
+    fn test_02_strings_are_encoded_as_integers_when_possible() {
+        let mut writer = RdbWriter::new();
+        for string in ["-5", "300", "-70000", "007", "4294967296", "text"] {
+            writer.string(string.as_bytes());
+        }
+        assert_eq!(
+            writer.bytes(),
+            [
+                &[0xc0, 0xfb][..],
+                &[0xc1, 0x2c, 0x01],
+                &[0xc2, 0x90, 0xee, 0xfe, 0xff],
+                b"\x03007",
+                b"\x0a4294967296",
+                b"\x04text"
+            ]
+            .concat()
+        );
+        let bytes = writer.into_bytes();
+        let mut reader = RdbReader::new(&bytes);
+        for string in ["-5", "300", "-70000", "007", "4294967296", "text"] {
+            assert_eq!(reader.string().unwrap(), string.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_03_long_strings_are_compressed() {
+        let text = "a".repeat(100);
+        let mut writer = RdbWriter::new();
+        writer.string(text.as_bytes());
+        assert_eq!(writer.bytes()[0], 0xc3);
+        assert!(writer.bytes().len() < 20);
+
+        let bytes = writer.into_bytes();
+        assert_eq!(RdbReader::new(&bytes).string().unwrap(), text.as_bytes());
+    }
+
+    #[test]
+    fn test_04_doubles_as_text() {
+        let bytes = b"\x033.5\xfe\xfd";
+        let mut reader = RdbReader::new(bytes);
+        assert_eq!(reader.text_double().unwrap(), 3.5);
+        assert_eq!(reader.text_double().unwrap(), f64::INFINITY);
+        assert!(reader.text_double().unwrap().is_nan());
+    }
+}
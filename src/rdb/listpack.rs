@@ -0,0 +1,342 @@
+//! Compact containers embedded in RDB strings: listpacks, the ziplists they
+//! replaced and intsets.
+
+use super::corrupted;
+use crate::native_types::ErrorStruct;
+use std::convert::TryFrom;
+
+const LISTPACK_HEADER: usize = 6;
+const ZIPLIST_HEADER: usize = 10;
+const END: u8 = 0xff;
+
+/// Element of a listpack or a ziplist.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Element {
+    Bytes(Vec<u8>),
+    Integer(i64),
+}
+
+impl Element {
+    /// Returns the element as text, replacing the bytes which are not valid
+    /// UTF-8.
+    pub fn to_text(&self) -> String {
+        match self {
+            Element::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+            Element::Integer(integer) => integer.to_string(),
+        }
+    }
+
+    /// Returns the element as an integer, which can be stored as text.
+    pub fn as_integer(&self) -> Result<i64, ErrorStruct> {
+        match self {
+            Element::Integer(integer) => Ok(*integer),
+            Element::Bytes(bytes) => std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|text| text.parse().ok())
+                .ok_or_else(|| corrupted("integer expected")),
+        }
+    }
+
+    /// Returns the element as a double, which can be stored as text.
+    pub fn as_double(&self) -> Result<f64, ErrorStruct> {
+        match self {
+            Element::Integer(integer) => Ok(*integer as f64),
+            Element::Bytes(bytes) => std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|text| text.parse().ok())
+                .ok_or_else(|| corrupted("double expected")),
+        }
+    }
+}
+
+/// Builds a listpack: the total bytes and the number of elements, followed
+/// by each element and the end mark. Each element has its encoding, its
+/// data and the length of both, to be traversed backwards.
+pub fn write_listpack(elements: &[Element]) -> Vec<u8> {
+    let mut listpack = vec![0; LISTPACK_HEADER];
+    for element in elements {
+        let mut entry = Vec::new();
+        match element {
+            Element::Integer(integer) => encode_integer(&mut entry, *integer),
+            Element::Bytes(bytes) => {
+                let len = bytes.len();
+                if len < 1 << 6 {
+                    entry.push(0x80 | len as u8);
+                } else if len < 1 << 12 {
+                    entry.push(0xe0 | (len >> 8) as u8);
+                    entry.push((len & 0xff) as u8);
+                } else {
+                    entry.push(0xf0);
+                    entry.extend_from_slice(&(len as u32).to_le_bytes());
+                }
+                entry.extend_from_slice(bytes);
+            }
+        }
+        let backlen = encode_backlen(entry.len());
+        listpack.extend_from_slice(&entry);
+        listpack.extend_from_slice(&backlen);
+    }
+    listpack.push(END);
+    let total = listpack.len() as u32;
+    listpack[0..4].copy_from_slice(&total.to_le_bytes());
+    let count = elements.len().min(u16::MAX as usize) as u16;
+    listpack[4..6].copy_from_slice(&count.to_le_bytes());
+    listpack
+}
+
+/// Reads the elements of a listpack.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The listpack is corrupted.
+pub fn read_listpack(listpack: &[u8]) -> Result<Vec<Element>, ErrorStruct> {
+    let mut elements = Vec::new();
+    let mut position = LISTPACK_HEADER;
+    loop {
+        let encoding = *listpack
+            .get(position)
+            .ok_or_else(|| corrupted("listpack without end"))?;
+        if encoding == END {
+            return Ok(elements);
+        }
+        let data = &listpack[position + 1..];
+        let (element, len) = if encoding & 0x80 == 0 {
+            (Element::Integer((encoding & 0x7f) as i64), 1)
+        } else if encoding & 0xc0 == 0x80 {
+            let len = (encoding & 0x3f) as usize;
+            (Element::Bytes(slice(data, 0, len)?.to_vec()), 1 + len)
+        } else if encoding & 0xe0 == 0xc0 {
+            let value = ((encoding & 0x1f) as i64) << 8 | slice(data, 0, 1)?[0] as i64;
+            let value = if value >= 1 << 12 {
+                value - (1 << 13)
+            } else {
+                value
+            };
+            (Element::Integer(value), 2)
+        } else if encoding & 0xf0 == 0xe0 {
+            let len = ((encoding & 0x0f) as usize) << 8 | slice(data, 0, 1)?[0] as usize;
+            (Element::Bytes(slice(data, 1, len)?.to_vec()), 2 + len)
+        } else {
+            match encoding {
+                0xf0 => {
+                    let len = u32::from_le_bytes(array(slice(data, 0, 4)?)) as usize;
+                    (Element::Bytes(slice(data, 4, len)?.to_vec()), 5 + len)
+                }
+                0xf1 => (Element::Integer(signed(slice(data, 0, 2)?)), 3),
+                0xf2 => (Element::Integer(signed(slice(data, 0, 3)?)), 4),
+                0xf3 => (Element::Integer(signed(slice(data, 0, 4)?)), 5),
+                0xf4 => (Element::Integer(signed(slice(data, 0, 8)?)), 9),
+                _ => return Err(corrupted("unknown listpack encoding")),
+            }
+        };
+        elements.push(element);
+        position += len + backlen_len(len);
+    }
+}
+
+/// Reads the elements of a ziplist: its total bytes, the offset of the last
+/// element and the number of elements, followed by each element and the
+/// end mark. Each element has the length of the previous one, its encoding
+/// and its data.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The ziplist is corrupted.
+pub fn read_ziplist(ziplist: &[u8]) -> Result<Vec<Element>, ErrorStruct> {
+    let mut elements = Vec::new();
+    let mut position = ZIPLIST_HEADER;
+    loop {
+        let first = *ziplist
+            .get(position)
+            .ok_or_else(|| corrupted("ziplist without end"))?;
+        if first == END {
+            return Ok(elements);
+        }
+        position += if first < 254 { 1 } else { 5 };
+        let encoding = *ziplist
+            .get(position)
+            .ok_or_else(|| corrupted("ziplist without end"))?;
+        let data = &ziplist[position + 1..];
+        let (element, len) = match encoding >> 6 {
+            0 => {
+                let len = (encoding & 0x3f) as usize;
+                (Element::Bytes(slice(data, 0, len)?.to_vec()), 1 + len)
+            }
+            1 => {
+                let len = ((encoding & 0x3f) as usize) << 8 | slice(data, 0, 1)?[0] as usize;
+                (Element::Bytes(slice(data, 1, len)?.to_vec()), 2 + len)
+            }
+            2 => {
+                let len = u32::from_be_bytes(array(slice(data, 0, 4)?)) as usize;
+                (Element::Bytes(slice(data, 4, len)?.to_vec()), 5 + len)
+            }
+            _ => match encoding {
+                0xc0 => (Element::Integer(signed(slice(data, 0, 2)?)), 3),
+                0xd0 => (Element::Integer(signed(slice(data, 0, 4)?)), 5),
+                0xe0 => (Element::Integer(signed(slice(data, 0, 8)?)), 9),
+                0xf0 => (Element::Integer(signed(slice(data, 0, 3)?)), 4),
+                0xfe => (Element::Integer(signed(slice(data, 0, 1)?)), 2),
+                0xf1..=0xfd => (Element::Integer((encoding & 0x0f) as i64 - 1), 1),
+                _ => return Err(corrupted("unknown ziplist encoding")),
+            },
+        };
+        elements.push(element);
+        position += len;
+    }
+}
+
+/// Reads the integers of an intset: the size of each one and the number of
+/// them, followed by the integers in order, all little endian.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The intset is corrupted.
+pub fn read_intset(intset: &[u8]) -> Result<Vec<i64>, ErrorStruct> {
+    let size = u32::from_le_bytes(array(slice(intset, 0, 4)?)) as usize;
+    let count = u32::from_le_bytes(array(slice(intset, 4, 4)?)) as usize;
+    if ![2, 4, 8].contains(&size) {
+        return Err(corrupted("unknown intset encoding"));
+    }
+    (0..count)
+        .map(|index| Ok(signed(slice(intset, 8 + index * size, size)?)))
+        .collect()
+}
+
+fn encode_integer(entry: &mut Vec<u8>, integer: i64) {
+    if (0..128).contains(&integer) {
+        entry.push(integer as u8);
+    } else if (-4096..4096).contains(&integer) {
+        let value = (integer & 0x1fff) as u16;
+        entry.push(0xc0 | (value >> 8) as u8);
+        entry.push((value & 0xff) as u8);
+    } else if let Ok(integer) = i16::try_from(integer) {
+        entry.push(0xf1);
+        entry.extend_from_slice(&integer.to_le_bytes());
+    } else if (-(1 << 23)..1 << 23).contains(&integer) {
+        entry.push(0xf2);
+        entry.extend_from_slice(&integer.to_le_bytes()[..3]);
+    } else if let Ok(integer) = i32::try_from(integer) {
+        entry.push(0xf3);
+        entry.extend_from_slice(&integer.to_le_bytes());
+    } else {
+        entry.push(0xf4);
+        entry.extend_from_slice(&integer.to_le_bytes());
+    }
+}
+
+/// Encodes the length of an entry in 7 bits per byte, the most significant
+/// first, with the highest bit set in all of them but the first.
+fn encode_backlen(len: usize) -> Vec<u8> {
+    let bytes = backlen_len(len);
+    (0..bytes)
+        .map(|index| {
+            let shift = 7 * (bytes - 1 - index);
+            let digit = ((len >> shift) & 0x7f) as u8;
+            if index == 0 {
+                digit
+            } else {
+                digit | 0x80
+            }
+        })
+        .collect()
+}
+
+fn backlen_len(len: usize) -> usize {
+    match len {
+        0..=127 => 1,
+        128..=16382 => 2,
+        16383..=2097150 => 3,
+        2097151..=268435454 => 4,
+        _ => 5,
+    }
+}
+
+fn slice(data: &[u8], start: usize, len: usize) -> Result<&[u8], ErrorStruct> {
+    data.get(start..start + len)
+        .ok_or_else(|| corrupted("unexpected end of container"))
+}
+
+fn array<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut array = [0; N];
+    array.copy_from_slice(&bytes[..N]);
+    array
+}
+
+/// Reads a signed integer of up to 8 bytes, little endian.
+fn signed(bytes: &[u8]) -> i64 {
+    let mut extended = [if bytes[bytes.len() - 1] & 0x80 != 0 {
+        0xff
+    } else {
+        0
+    }; 8];
+    extended[..bytes.len()].copy_from_slice(bytes);
+    i64::from_le_bytes(extended)
+}
+
+#[cfg(test)]
+mod test_listpack {
+    use super::*;
+
+    #[test]
+    fn test_01_write_and_read_listpacks() {
+        let long = vec![b'x'; 5000];
+        let elements = vec![
+            Element::Integer(5),
+            Element::Integer(-100),
+            Element::Integer(20000),
+            Element::Integer(-5_000_000),
+            Element::Integer(1 << 30),
+            Element::Integer(-(1 << 40)),
+            Element::Bytes(b"field".to_vec()),
+            Element::Bytes(vec![b'y'; 200]),
+            Element::Bytes(long),
+        ];
+        let listpack = write_listpack(&elements);
+        assert_eq!(
+            u32::from_le_bytes(array(&listpack[0..4])) as usize,
+            listpack.len()
+        );
+        assert_eq!(read_listpack(&listpack).unwrap(), elements);
+    }
+
+    #[test]
+    fn test_02_listpack_layout() {
+        let listpack = write_listpack(&[Element::Bytes(b"a".to_vec()), Element::Integer(1)]);
+        assert_eq!(
+            listpack,
+            [0x0c, 0, 0, 0, 2, 0, 0x81, b'a', 2, 0x01, 1, 0xff]
+        );
+        assert!(read_listpack(&listpack[..10]).is_err());
+    }
+
+    #[test]
+    fn test_03_read_ziplists() {
+        let ziplist = [
+            0x18, 0, 0, 0, 0x13, 0, 0, 0, 4, 0, // header
+            0, 0x02, b'h', b'i', // "hi"
+            4, 0xf3, // 2
+            2, 0xfe, 0x9c, // -100
+            3, 0xc0, 0x10, 0x27, // 10000
+            0xff,
+        ];
+        assert_eq!(
+            read_ziplist(&ziplist).unwrap(),
+            vec![
+                Element::Bytes(b"hi".to_vec()),
+                Element::Integer(2),
+                Element::Integer(-100),
+                Element::Integer(10000)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_04_read_intsets() {
+        let intset = [2, 0, 0, 0, 2, 0, 0, 0, 0xff, 0xff, 0x05, 0x00];
+        assert_eq!(read_intset(&intset).unwrap(), vec![-1, 5]);
+        assert!(read_intset(&intset[..10]).is_err());
+    }
+}
@@ -0,0 +1,149 @@
+//! LZF compression, used by the RDB format for long strings.
+//!
+//! The compressed data is a sequence of:
+//!
+//! * Literal runs: a byte `000LLLLL` followed by `L + 1` bytes.
+//! * Back references: a byte `LLLOOOOO` (with an extra length byte if `LLL`
+//!   is 7) followed by the low byte of the offset, which copy `L + 2` bytes
+//!   found `O + 1` bytes before.
+
+/// Greatest number of bytes of a literal run.
+const MAX_LITERAL: usize = 32;
+/// Greatest distance of a back reference.
+const MAX_OFFSET: usize = 1 << 13;
+/// Greatest length of a back reference.
+const MAX_REFERENCE: usize = 264;
+const HASH_BITS: u32 = 14;
+
+/// Compresses the bytes. Returns [None] if the result would be longer than
+/// `max_len`, as Redis does to store the string uncompressed.
+pub fn compress(input: &[u8], max_len: usize) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(max_len);
+    let mut table = vec![0usize; 1 << HASH_BITS];
+    let mut literals: Vec<u8> = Vec::with_capacity(MAX_LITERAL);
+    let mut position = 0;
+    while position + 2 < input.len() {
+        let slot = hash(&input[position..position + 3]);
+        let candidate = table[slot];
+        table[slot] = position + 1;
+        if candidate > 0 {
+            let start = candidate - 1;
+            let offset = position - start - 1;
+            if offset < MAX_OFFSET && input[start..start + 3] == input[position..position + 3] {
+                let max = MAX_REFERENCE.min(input.len() - position);
+                let mut len = 3;
+                while len < max && input[start + len] == input[position + len] {
+                    len += 1;
+                }
+                flush_literals(&mut output, &mut literals);
+                let encoded_len = len - 2;
+                if encoded_len < 7 {
+                    output.push(((encoded_len as u8) << 5) | (offset >> 8) as u8);
+                } else {
+                    output.push((7 << 5) | (offset >> 8) as u8);
+                    output.push((encoded_len - 7) as u8);
+                }
+                output.push((offset & 0xff) as u8);
+                position += len;
+                if output.len() > max_len {
+                    return None;
+                }
+                continue;
+            }
+        }
+        push_literal(&mut output, &mut literals, input[position]);
+        position += 1;
+    }
+    for byte in &input[position..] {
+        push_literal(&mut output, &mut literals, *byte);
+    }
+    flush_literals(&mut output, &mut literals);
+    (output.len() <= max_len).then_some(output)
+}
+
+/// Decompresses the bytes, which must expand to exactly `len` bytes.
+/// Returns [None] if the data is corrupted.
+pub fn decompress(input: &[u8], len: usize) -> Option<Vec<u8>> {
+    let mut output: Vec<u8> = Vec::with_capacity(len);
+    let mut position = 0;
+    while position < input.len() {
+        let control = input[position] as usize;
+        position += 1;
+        if control < MAX_LITERAL {
+            let literal = input.get(position..position + control + 1)?;
+            output.extend_from_slice(literal);
+            position += control + 1;
+        } else {
+            let mut reference_len = control >> 5;
+            if reference_len == 7 {
+                reference_len += *input.get(position)? as usize;
+                position += 1;
+            }
+            reference_len += 2;
+            let offset = ((control & 0x1f) << 8) + *input.get(position)? as usize + 1;
+            position += 1;
+            let start = output.len().checked_sub(offset)?;
+            for index in start..start + reference_len {
+                output.push(output[index]);
+            }
+        }
+        if output.len() > len {
+            return None;
+        }
+    }
+    (output.len() == len).then_some(output)
+}
+
+fn hash(bytes: &[u8]) -> usize {
+    let value = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+    (value.wrapping_mul(2654435761) >> (32 - HASH_BITS)) as usize
+}
+
+fn push_literal(output: &mut Vec<u8>, literals: &mut Vec<u8>, byte: u8) {
+    literals.push(byte);
+    if literals.len() == MAX_LITERAL {
+        flush_literals(output, literals);
+    }
+}
+
+fn flush_literals(output: &mut Vec<u8>, literals: &mut Vec<u8>) {
+    if !literals.is_empty() {
+        output.push((literals.len() - 1) as u8);
+        output.append(literals);
+    }
+}
+
+#[cfg(test)]
+mod test_lzf {
+    use super::*;
+
+    #[test]
+    fn test_01_compress_and_decompress() {
+        let text = "abcabcabcabcabcabcabcabcabcabc hello hello hello hello".repeat(20);
+        let compressed = compress(text.as_bytes(), text.len()).unwrap();
+        assert!(compressed.len() < text.len() / 4);
+        assert_eq!(
+            decompress(&compressed, text.len()).unwrap(),
+            text.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_02_incompressible_data_is_rejected() {
+        let data: Vec<u8> = (0..=255).collect();
+        assert_eq!(compress(&data, data.len() - 4), None);
+        let compressed = compress(&data, data.len() * 2).unwrap();
+        assert_eq!(decompress(&compressed, data.len()).unwrap(), data);
+    }
+
+    #[test]
+    fn test_03_decompress_overlapping_references() {
+        // A literal "a", references of 64 and 3 bytes to the previous byte
+        // and a literal "aa"
+        let compressed = [0x00, b'a', 0xe0, 0x37, 0x00, 0x20, 0x00, 0x01, b'a', b'a'];
+        let decompressed = decompress(&compressed, 70).unwrap();
+        assert_eq!(decompressed, vec![b'a'; 70]);
+        assert_eq!(decompress(&compressed, 60), None);
+        assert_eq!(decompress(&[0xe0, 0x37, 0x00], 57), None);
+    }
+}
@@ -0,0 +1,471 @@
+//! Snapshots in the RDB format of Redis, so that the dumps of this server can
+//! be read by Redis and its tools, and the other way around.
+//!
+//! A file is the magic string "REDIS" and the version in four digits,
+//! followed by the auxiliary fields, the database selector, the sizes of the
+//! database and the keys with their optional expire time, then the end mark
+//! and the CRC64 of all the previous bytes.
+
+pub mod encoding;
+pub mod listpack;
+pub mod lzf;
+pub mod stream;
+
+use crate::database::TypeSaved;
+use crate::dump::Dump;
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+use crate::payload::crc64;
+use crate::redis_config::DumpFormat;
+use crate::sorted_set::SortedSet;
+use encoding::{RdbReader, RdbWriter};
+use listpack::{read_intset, read_listpack, read_ziplist, Element};
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+/// Version of the files written, the first one with streams.
+pub const RDB_VERSION: u16 = 9;
+/// Greatest version which can be read, of Redis 7.4.
+pub const MAX_RDB_VERSION: u16 = 12;
+const MAGIC: &[u8] = b"REDIS";
+/// Version since which the files end with a checksum.
+const CHECKSUM_VERSION: u16 = 5;
+
+pub const TYPE_STRING: u8 = 0;
+pub const TYPE_LIST: u8 = 1;
+pub const TYPE_SET: u8 = 2;
+pub const TYPE_ZSET: u8 = 3;
+pub const TYPE_ZSET_2: u8 = 5;
+pub const TYPE_LIST_ZIPLIST: u8 = 10;
+pub const TYPE_SET_INTSET: u8 = 11;
+pub const TYPE_ZSET_ZIPLIST: u8 = 12;
+pub const TYPE_LIST_QUICKLIST: u8 = 14;
+pub const TYPE_STREAM_LISTPACKS: u8 = 15;
+pub const TYPE_ZSET_LISTPACK: u8 = 17;
+pub const TYPE_LIST_QUICKLIST_2: u8 = 18;
+pub const TYPE_STREAM_LISTPACKS_2: u8 = 19;
+pub const TYPE_SET_LISTPACK: u8 = 20;
+pub const TYPE_STREAM_LISTPACKS_3: u8 = 21;
+
+const OPCODE_FUNCTION_2: u8 = 0xf5;
+const OPCODE_FUNCTION_PRE_GA: u8 = 0xf6;
+const OPCODE_MODULE_AUX: u8 = 0xf7;
+const OPCODE_IDLE: u8 = 0xf8;
+const OPCODE_FREQ: u8 = 0xf9;
+const OPCODE_AUX: u8 = 0xfa;
+const OPCODE_RESIZEDB: u8 = 0xfb;
+const OPCODE_EXPIRETIME_MS: u8 = 0xfc;
+const OPCODE_EXPIRETIME: u8 = 0xfd;
+const OPCODE_SELECTDB: u8 = 0xfe;
+const OPCODE_EOF: u8 = 0xff;
+
+/// Nodes of a quicklist which hold a single element instead of a listpack.
+const QUICKLIST_NODE_PLAIN: u64 = 1;
+
+/// A key of a dump, with its expire time as unix time in milliseconds.
+pub type RdbEntry = (String, Option<u64>, TypeSaved);
+
+/// Builds a RDB file with the keys, in the database 0.
+pub fn save(entries: &[RdbEntry]) -> Vec<u8> {
+    let mut writer = RdbWriter::new();
+    writer.raw(MAGIC);
+    writer.raw(format!("{:04}", RDB_VERSION).as_bytes());
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or(0);
+    for (field, value) in [
+        ("redis-ver", env!("CARGO_PKG_VERSION").to_string()),
+        ("redis-bits", (usize::BITS).to_string()),
+        ("ctime", now.to_string()),
+    ] {
+        writer.byte(OPCODE_AUX);
+        writer.string(field.as_bytes());
+        writer.string(value.as_bytes());
+    }
+    writer.byte(OPCODE_SELECTDB);
+    writer.length(0);
+    writer.byte(OPCODE_RESIZEDB);
+    writer.length(entries.len() as u64);
+    writer.length(entries.iter().filter(|entry| entry.1.is_some()).count() as u64);
+    for (key, expire_at, value) in entries {
+        if let Some(expire_at) = expire_at {
+            writer.byte(OPCODE_EXPIRETIME_MS);
+            writer.millis(*expire_at);
+        }
+        write_value(&mut writer, key, value);
+    }
+    writer.byte(OPCODE_EOF);
+    let checksum = crc64(writer.bytes());
+    writer.raw(&checksum.to_le_bytes());
+    writer.into_bytes()
+}
+
+/// Reads the keys of the database 0 of a RDB file. The keys of the other
+/// databases are ignored, and the bytes which are not valid UTF-8 in keys and
+/// elements are replaced.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The file is not a RDB file, or its version is not supported.
+/// * The file is corrupted or its checksum is wrong. The error tells the
+///   offset and the key of the entry which could not be read.
+/// * Some value has a type this server does not support, as hashes, or there
+///   are modules or functions.
+pub fn load(bytes: &[u8]) -> Result<Vec<RdbEntry>, ErrorStruct> {
+    let dump = read(bytes);
+    match dump.corruption {
+        Some(corruption) => Err(ErrorStruct::from(redis_messages::corrupted_dump(
+            &corruption.to_string(),
+        ))),
+        None => Ok(dump.entries),
+    }
+}
+
+/// Reads the keys of the database 0 of a RDB file as [load], up to the first
+/// corruption found, which is returned with them.
+pub fn read(bytes: &[u8]) -> Dump {
+    let mut reader = RdbReader::new(bytes);
+    let version = match read_version(&mut reader) {
+        Ok(version) => version,
+        Err(error) => {
+            return Dump::new(DumpFormat::Rdb, 0).corrupted(0, None, error.message(), false)
+        }
+    };
+    let mut dump = Dump::new(DumpFormat::Rdb, version);
+    let mut database = 0;
+    let mut expire_at = None;
+    let mut entry_start = reader.position();
+    loop {
+        if expire_at.is_none() {
+            entry_start = reader.position();
+        }
+        let read = match reader.byte() {
+            Ok(OPCODE_EOF) => break,
+            Ok(OPCODE_AUX) => reader.string().and_then(|_| reader.string()).map(|_| ()),
+            Ok(OPCODE_SELECTDB) => reader.length().map(|index| database = index),
+            Ok(OPCODE_RESIZEDB) => reader.length().and_then(|_| reader.length()).map(|_| ()),
+            Ok(OPCODE_EXPIRETIME_MS) => reader.millis().map(|millis| expire_at = Some(millis)),
+            Ok(OPCODE_EXPIRETIME) => reader
+                .array()
+                .map(|seconds| expire_at = Some(u32::from_le_bytes(seconds) as u64 * 1000)),
+            Ok(OPCODE_IDLE) => reader.length().map(|_| ()),
+            Ok(OPCODE_FREQ) => reader.byte().map(|_| ()),
+            Ok(OPCODE_FUNCTION_2 | OPCODE_FUNCTION_PRE_GA | OPCODE_MODULE_AUX) => {
+                Err(corrupted("modules and functions are not supported"))
+            }
+            Ok(kind) => match reader.string() {
+                Ok(key) => {
+                    let key = text(&key);
+                    match read_value(&mut reader, kind) {
+                        Ok(value) => {
+                            if database == 0 {
+                                dump.entries.push((key, expire_at, value));
+                            }
+                            expire_at = None;
+                            Ok(())
+                        }
+                        Err(error) => {
+                            return dump.corrupted(entry_start, Some(key), error.message(), true)
+                        }
+                    }
+                }
+                Err(error) => Err(error),
+            },
+            Err(error) => Err(error),
+        };
+        if let Err(error) = read {
+            return dump.corrupted(entry_start, None, error.message(), true);
+        }
+    }
+    if version >= CHECKSUM_VERSION {
+        let end = reader.position();
+        match reader.array() {
+            Ok(checksum) => {
+                let checksum = u64::from_le_bytes(checksum);
+                if checksum != 0 && checksum != crc64(&bytes[..end]) {
+                    let error = corrupted("wrong checksum");
+                    return dump.corrupted(end, None, error.message(), false);
+                }
+            }
+            Err(error) => return dump.corrupted(end, None, error.message(), true),
+        }
+    }
+    dump
+}
+
+/// Reads the signature and the version, if it is supported.
+fn read_version(reader: &mut RdbReader) -> Result<u16, ErrorStruct> {
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(corrupted("wrong signature"));
+    }
+    std::str::from_utf8(reader.take(4)?)
+        .ok()
+        .and_then(|version| version.parse::<u16>().ok())
+        .filter(|version| (1..=MAX_RDB_VERSION).contains(version))
+        .ok_or_else(|| corrupted("unsupported version"))
+}
+
+fn write_value(writer: &mut RdbWriter, key: &str, value: &TypeSaved) {
+    let kind = match value {
+        TypeSaved::String(_) => TYPE_STRING,
+        TypeSaved::List(_) => TYPE_LIST,
+        TypeSaved::Set(_) => TYPE_SET,
+        TypeSaved::SortedSet(_) => TYPE_ZSET_2,
+        TypeSaved::Stream(_) => TYPE_STREAM_LISTPACKS,
+    };
+    writer.byte(kind);
+    writer.string(key.as_bytes());
+    match value {
+        TypeSaved::String(bytes) => writer.string(bytes),
+        TypeSaved::List(list) => {
+            writer.length(list.len() as u64);
+            list.iter()
+                .for_each(|element| writer.string(element.as_bytes()));
+        }
+        TypeSaved::Set(set) => {
+            writer.length(set.len() as u64);
+            set.iter()
+                .for_each(|member| writer.string(member.as_bytes()));
+        }
+        TypeSaved::SortedSet(sorted_set) => {
+            writer.length(sorted_set.len() as u64);
+            for (member, score) in sorted_set.iter() {
+                writer.string(member.as_bytes());
+                writer.double(score);
+            }
+        }
+        TypeSaved::Stream(stream) => stream::write_stream(writer, stream),
+    }
+}
+
+fn read_value(reader: &mut RdbReader, kind: u8) -> Result<TypeSaved, ErrorStruct> {
+    Ok(match kind {
+        TYPE_STRING => TypeSaved::String(reader.string()?),
+        TYPE_LIST => TypeSaved::List(read_strings(reader)?.into_iter().collect()),
+        TYPE_SET => TypeSaved::Set(read_strings(reader)?.into_iter().collect()),
+        TYPE_ZSET | TYPE_ZSET_2 => {
+            let mut sorted_set = SortedSet::new();
+            for _ in 0..reader.length()? {
+                let member = text(&reader.string()?);
+                let score = if kind == TYPE_ZSET {
+                    reader.text_double()?
+                } else {
+                    reader.double()?
+                };
+                sorted_set.insert(&member, score);
+            }
+            TypeSaved::SortedSet(sorted_set)
+        }
+        TYPE_LIST_ZIPLIST => TypeSaved::List(
+            texts(read_ziplist(&reader.string()?)?)
+                .into_iter()
+                .collect(),
+        ),
+        TYPE_LIST_QUICKLIST => {
+            let mut list = VecDeque::new();
+            for _ in 0..reader.length()? {
+                list.extend(texts(read_ziplist(&reader.string()?)?));
+            }
+            TypeSaved::List(list)
+        }
+        TYPE_LIST_QUICKLIST_2 => {
+            let mut list = VecDeque::new();
+            for _ in 0..reader.length()? {
+                let container = reader.length()?;
+                let node = reader.string()?;
+                if container == QUICKLIST_NODE_PLAIN {
+                    list.push_back(text(&node));
+                } else {
+                    list.extend(texts(read_listpack(&node)?));
+                }
+            }
+            TypeSaved::List(list)
+        }
+        TYPE_SET_INTSET => TypeSaved::Set(
+            read_intset(&reader.string()?)?
+                .iter()
+                .map(|integer| integer.to_string())
+                .collect(),
+        ),
+        TYPE_SET_LISTPACK => TypeSaved::Set(
+            texts(read_listpack(&reader.string()?)?)
+                .into_iter()
+                .collect(),
+        ),
+        TYPE_ZSET_ZIPLIST | TYPE_ZSET_LISTPACK => {
+            let container = reader.string()?;
+            let elements = if kind == TYPE_ZSET_ZIPLIST {
+                read_ziplist(&container)?
+            } else {
+                read_listpack(&container)?
+            };
+            let mut sorted_set = SortedSet::new();
+            for pair in elements.chunks(2) {
+                match pair {
+                    [member, score] => {
+                        sorted_set.insert(&member.to_text(), score.as_double()?);
+                    }
+                    _ => return Err(corrupted("sorted set without score")),
+                }
+            }
+            TypeSaved::SortedSet(sorted_set)
+        }
+        TYPE_STREAM_LISTPACKS | TYPE_STREAM_LISTPACKS_2 | TYPE_STREAM_LISTPACKS_3 => {
+            TypeSaved::Stream(stream::read_stream(reader, kind)?)
+        }
+        _ => return Err(corrupted(&format!("unsupported value type {}", kind))),
+    })
+}
+
+fn read_strings(reader: &mut RdbReader) -> Result<Vec<String>, ErrorStruct> {
+    (0..reader.length()?)
+        .map(|_| Ok(text(&reader.string()?)))
+        .collect()
+}
+
+fn text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn texts(elements: Vec<Element>) -> Vec<String> {
+    elements.iter().map(Element::to_text).collect()
+}
+
+fn corrupted(detail: &str) -> ErrorStruct {
+    ErrorStruct::from(redis_messages::invalid_rdb(detail))
+}
+
+#[cfg(test)]
+mod test_rdb {
+    use super::*;
+    use crate::stream::Stream;
+    use crate::vec_strings;
+
+    fn entries() -> Vec<RdbEntry> {
+        let mut sorted_set = SortedSet::new();
+        sorted_set.insert("a", 1.5);
+        sorted_set.insert("b", f64::INFINITY);
+        let mut stream = Stream::new();
+        stream.add("1-1", vec_strings!["field", "value"]).unwrap();
+        vec![
+            (
+                "string".to_string(),
+                None,
+                TypeSaved::String(b"a\r\nb\0".to_vec()),
+            ),
+            (
+                "number".to_string(),
+                Some(4102444800000),
+                TypeSaved::String(b"12345".to_vec()),
+            ),
+            (
+                "long".to_string(),
+                None,
+                TypeSaved::String("long ".repeat(50).into_bytes()),
+            ),
+            (
+                "list".to_string(),
+                None,
+                TypeSaved::List(vec_strings!["a", "1", "a\nb"].into_iter().collect()),
+            ),
+            (
+                "set".to_string(),
+                None,
+                TypeSaved::Set(vec_strings!["x", "y"].into_iter().collect()),
+            ),
+            ("zset".to_string(), None, TypeSaved::SortedSet(sorted_set)),
+            ("stream".to_string(), None, TypeSaved::Stream(stream)),
+        ]
+    }
+
+    #[test]
+    fn test_01_save_and_load_every_type() {
+        let bytes = save(&entries());
+        assert!(bytes.starts_with(b"REDIS0009"));
+        assert_eq!(load(&bytes).unwrap(), entries());
+    }
+
+    #[test]
+    fn test_02_corrupted_files_are_rejected() {
+        let mut bytes = save(&entries());
+        let len = bytes.len();
+        bytes[len - 20] ^= 1;
+        assert_eq!(
+            load(&bytes).unwrap_err().print_it(),
+            format!(
+                "ERR Bad RDB file: wrong checksum at offset {} (after key 'stream')",
+                len - 8
+            )
+        );
+        assert!(load(&bytes[..len - 5]).is_err());
+        assert!(load(b"REDIS0099\xff").is_err());
+        assert!(load(b"NOTREDIS").is_err());
+    }
+
+    #[test]
+    fn test_03_load_the_empty_file_sent_by_redis_to_its_replicas() {
+        let hex = "524544495330303131fa0972656469732d76657205372e322e30fa0a72656469732d62697473\
+                   c040fa056374696d65c26d08bc65fa08757365642d6d656dc2b0c41000fa08616f662d626173\
+                   65c000fff06e3bfec0ff5aa2";
+        let bytes = crate::database::decode_hex(hex).unwrap();
+        assert_eq!(load(&bytes).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_04_load_the_encodings_of_redis_7() {
+        // SET key value, RPUSH list a 1, SADD set 1 2 and ZADD zset 1.5 m in
+        // the database 0, and SET other 1 in the database 1, without checksum
+        let mut bytes = b"REDIS0011\xfa\x09redis-ver\x057.2.4\xfe\x00\xfb\x04\x00".to_vec();
+        bytes.extend_from_slice(b"\x00\x03key\x05value");
+        bytes.extend_from_slice(b"\x12\x04list\x01\x02");
+        let list = listpack::write_listpack(&[Element::Bytes(b"a".to_vec()), Element::Integer(1)]);
+        bytes.push(list.len() as u8);
+        bytes.extend_from_slice(&list);
+        bytes.extend_from_slice(b"\x0b\x03set\x0c\x02\x00\x00\x00\x02\x00\x00\x00\x01\x00\x02\x00");
+        bytes.extend_from_slice(b"\x11\x04zset");
+        let zset = listpack::write_listpack(&[
+            Element::Bytes(b"m".to_vec()),
+            Element::Bytes(b"1.5".to_vec()),
+        ]);
+        bytes.push(zset.len() as u8);
+        bytes.extend_from_slice(&zset);
+        bytes.extend_from_slice(b"\xfe\x01\x00\x05other\xc0\x01\xff");
+        bytes.extend_from_slice(&[0; 8]);
+
+        let entries = load(&bytes).unwrap();
+        let mut sorted_set = SortedSet::new();
+        sorted_set.insert("m", 1.5);
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "key".to_string(),
+                    None,
+                    TypeSaved::String(b"value".to_vec())
+                ),
+                (
+                    "list".to_string(),
+                    None,
+                    TypeSaved::List(vec_strings!["a", "1"].into_iter().collect())
+                ),
+                (
+                    "set".to_string(),
+                    None,
+                    TypeSaved::Set(vec_strings!["1", "2"].into_iter().collect())
+                ),
+                ("zset".to_string(), None, TypeSaved::SortedSet(sorted_set)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_05_unsupported_types_are_rejected() {
+        let bytes = b"REDIS0009\x04\x04hash\x01\x01f\x01v\xff\x00\x00\x00\x00\x00\x00\x00\x00";
+        assert_eq!(
+            load(bytes).unwrap_err().print_it(),
+            "ERR Bad RDB file: unsupported value type 4 at offset 9 (key 'hash')"
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Streams in the RDB format: the entries in listpacks indexed by the id of
+//! their first entry, followed by the consumer groups.
+
+use super::encoding::{RdbReader, RdbWriter};
+use super::listpack::{read_listpack, write_listpack, Element};
+use super::{corrupted, TYPE_STREAM_LISTPACKS_2, TYPE_STREAM_LISTPACKS_3};
+use crate::native_types::ErrorStruct;
+use crate::stream::{ConsumerGroup, PendingEntry, Stream, StreamId};
+use std::collections::BTreeMap;
+
+/// Entries of each listpack, as the default stream-node-max-entries of Redis.
+const NODE_MAX_ENTRIES: usize = 100;
+const FLAG_DELETED: i64 = 1;
+const FLAG_SAME_FIELDS: i64 = 2;
+
+/// Writes the stream as RDB_TYPE_STREAM_LISTPACKS.
+///
+/// Each listpack starts with the master entry: the number of valid and
+/// deleted entries and the fields of its first entry. Each entry has its
+/// flags, its id relative to the master one, its fields (or only the values
+/// if the fields are the master ones) and the number of elements used.
+pub fn write_stream(writer: &mut RdbWriter, stream: &Stream) {
+    let entries: Vec<(&StreamId, &Vec<String>)> = stream.entries().iter().collect();
+    writer.length(entries.len().div_ceil(NODE_MAX_ENTRIES) as u64);
+    for node in entries.chunks(NODE_MAX_ENTRIES) {
+        let (master_id, master_values) = node[0];
+        let master_fields: Vec<&String> = master_values.iter().step_by(2).collect();
+        let mut elements = vec![
+            Element::Integer(node.len() as i64),
+            Element::Integer(0),
+            Element::Integer(master_fields.len() as i64),
+        ];
+        elements.extend(master_fields.iter().map(|field| text(field)));
+        elements.push(Element::Integer(0));
+        for (id, values) in node {
+            let fields: Vec<&String> = values.iter().step_by(2).collect();
+            let same_fields = fields == master_fields;
+            elements.push(Element::Integer(if same_fields {
+                FLAG_SAME_FIELDS
+            } else {
+                0
+            }));
+            elements.push(Element::Integer(id.ms.wrapping_sub(master_id.ms) as i64));
+            elements.push(Element::Integer(id.seq.wrapping_sub(master_id.seq) as i64));
+            let mut count = fields.len() as i64 + 3;
+            if same_fields {
+                elements.extend(values.iter().skip(1).step_by(2).map(|value| text(value)));
+            } else {
+                elements.push(Element::Integer(fields.len() as i64));
+                elements.extend(values.iter().map(|value| text(value)));
+                count += fields.len() as i64 + 1;
+            }
+            elements.push(Element::Integer(count));
+        }
+        writer.string(&raw_id(master_id));
+        writer.string(&write_listpack(&elements));
+    }
+    writer.length(stream.len() as u64);
+    writer.length(stream.last_id().ms);
+    writer.length(stream.last_id().seq);
+
+    writer.length(stream.groups().len() as u64);
+    for (name, group) in stream.groups() {
+        writer.string(name.as_bytes());
+        writer.length(group.last_delivered_id().ms);
+        writer.length(group.last_delivered_id().seq);
+        writer.length(group.pending().len() as u64);
+        for (id, entry) in group.pending() {
+            writer.raw(&raw_id(id));
+            writer.millis(entry.delivery_time);
+            writer.length(entry.delivery_count);
+        }
+        let mut consumers: BTreeMap<&str, (u64, Vec<&StreamId>)> = group
+            .consumers()
+            .iter()
+            .map(|(consumer, seen_time)| (consumer.as_str(), (*seen_time, Vec::new())))
+            .collect();
+        for (id, entry) in group.pending() {
+            consumers
+                .entry(entry.consumer.as_str())
+                .or_insert((entry.delivery_time, Vec::new()))
+                .1
+                .push(id);
+        }
+        writer.length(consumers.len() as u64);
+        for (consumer, (seen_time, ids)) in consumers {
+            writer.string(consumer.as_bytes());
+            writer.millis(seen_time);
+            writer.length(ids.len() as u64);
+            for id in ids {
+                writer.raw(&raw_id(id));
+            }
+        }
+    }
+}
+
+/// Reads a stream written as RDB_TYPE_STREAM_LISTPACKS, or as the later
+/// versions with more metadata, which is not kept.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The stream is corrupted.
+pub fn read_stream(reader: &mut RdbReader, kind: u8) -> Result<Stream, ErrorStruct> {
+    let mut entries = BTreeMap::new();
+    for _ in 0..reader.length()? {
+        let master_id = parse_raw_id(&reader.string()?)?;
+        let elements = read_listpack(&reader.string()?)?;
+        let mut elements = elements.iter();
+        let mut next = || elements.next().ok_or_else(|| corrupted("truncated stream"));
+        let _count = next()?.as_integer()?;
+        let _deleted = next()?.as_integer()?;
+        let master_fields = (0..next()?.as_integer()?)
+            .map(|_| Ok(next()?.to_text()))
+            .collect::<Result<Vec<String>, ErrorStruct>>()?;
+        next()?;
+        while let Ok(flags) = next() {
+            let flags = flags.as_integer()?;
+            let id = StreamId::new(
+                master_id.ms.wrapping_add(next()?.as_integer()? as u64),
+                master_id.seq.wrapping_add(next()?.as_integer()? as u64),
+            );
+            let mut values = Vec::new();
+            if flags & FLAG_SAME_FIELDS != 0 {
+                for field in master_fields.iter() {
+                    values.push(field.to_string());
+                    values.push(next()?.to_text());
+                }
+            } else {
+                for _ in 0..next()?.as_integer()? * 2 {
+                    values.push(next()?.to_text());
+                }
+            }
+            next()?;
+            if flags & FLAG_DELETED == 0 {
+                entries.insert(id, values);
+            }
+        }
+    }
+    let _len = reader.length()?;
+    let last_id = StreamId::new(reader.length()?, reader.length()?);
+    if kind >= TYPE_STREAM_LISTPACKS_2 {
+        // First id, greatest deleted id and number of entries ever added
+        for _ in 0..5 {
+            reader.length()?;
+        }
+    }
+
+    let mut groups = BTreeMap::new();
+    for _ in 0..reader.length()? {
+        let name = String::from_utf8_lossy(&reader.string()?).into_owned();
+        let last_delivered_id = StreamId::new(reader.length()?, reader.length()?);
+        if kind >= TYPE_STREAM_LISTPACKS_2 {
+            // Number of entries read by the group
+            reader.length()?;
+        }
+        let mut pending = BTreeMap::new();
+        for _ in 0..reader.length()? {
+            let id = parse_raw_id(reader.take(16)?)?;
+            let entry = PendingEntry {
+                consumer: String::new(),
+                delivery_time: reader.millis()?,
+                delivery_count: reader.length()?,
+            };
+            pending.insert(id, entry);
+        }
+        let mut consumers = BTreeMap::new();
+        for _ in 0..reader.length()? {
+            let consumer = String::from_utf8_lossy(&reader.string()?).into_owned();
+            let seen_time = reader.millis()?;
+            if kind >= TYPE_STREAM_LISTPACKS_3 {
+                // Time of the last successful interaction
+                reader.millis()?;
+            }
+            for _ in 0..reader.length()? {
+                let id = parse_raw_id(reader.take(16)?)?;
+                pending
+                    .get_mut(&id)
+                    .ok_or_else(|| corrupted("consumer pending entry not found"))?
+                    .consumer = consumer.to_string();
+            }
+            consumers.insert(consumer, seen_time);
+        }
+        if pending.values().any(|entry| entry.consumer.is_empty()) {
+            return Err(corrupted("pending entry without consumer"));
+        }
+        groups.insert(
+            name,
+            ConsumerGroup::from_parts(last_delivered_id, pending, consumers),
+        );
+    }
+    Ok(Stream::from_parts(entries, last_id, groups))
+}
+
+fn text(value: &str) -> Element {
+    Element::Bytes(value.as_bytes().to_vec())
+}
+
+/// Id as 128 bits big endian, as the keys of the listpacks.
+fn raw_id(id: &StreamId) -> [u8; 16] {
+    let mut raw = [0; 16];
+    raw[..8].copy_from_slice(&id.ms.to_be_bytes());
+    raw[8..].copy_from_slice(&id.seq.to_be_bytes());
+    raw
+}
+
+fn parse_raw_id(raw: &[u8]) -> Result<StreamId, ErrorStruct> {
+    if raw.len() != 16 {
+        return Err(corrupted("invalid stream id"));
+    }
+    let mut ms = [0; 8];
+    let mut seq = [0; 8];
+    ms.copy_from_slice(&raw[..8]);
+    seq.copy_from_slice(&raw[8..]);
+    Ok(StreamId::new(
+        u64::from_be_bytes(ms),
+        u64::from_be_bytes(seq),
+    ))
+}
+
+#[cfg(test)]
+mod test_rdb_stream {
+    use super::super::TYPE_STREAM_LISTPACKS;
+    use super::*;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_write_and_read_streams() {
+        let mut stream = Stream::new();
+        for index in 0..150 {
+            stream
+                .add(
+                    &format!("{}-{}", 1000 + index / 3, index),
+                    vec_strings!["a", "1", "b", "2"],
+                )
+                .unwrap();
+        }
+        stream.add("5000-0", vec_strings!["other", "3"]).unwrap();
+        stream.add("5001-0", vec_strings!["a", "4"]).unwrap();
+        stream.delete(&[StreamId::new(1000, 1)]);
+        stream.create_group("group", StreamId::new(0, 0)).unwrap();
+        stream
+            .create_group("empty", StreamId::new(1001, 0))
+            .unwrap();
+        stream.read_group("group", "alice", None, Some(2), false, 10);
+        stream.read_group("group", "bob", None, Some(1), false, 20);
+        stream
+            .group_mut("group")
+            .unwrap()
+            .create_consumer("carol", 30);
+
+        let mut writer = RdbWriter::new();
+        write_stream(&mut writer, &stream);
+        let bytes = writer.into_bytes();
+        let mut reader = RdbReader::new(&bytes);
+        let read = read_stream(&mut reader, TYPE_STREAM_LISTPACKS).unwrap();
+        assert_eq!(read, stream);
+        assert_eq!(reader.position(), bytes.len());
+    }
+
+    #[test]
+    fn test_02_empty_streams_keep_their_last_id() {
+        let mut stream = Stream::new();
+        stream.add("7-1", vec_strings!["a", "1"]).unwrap();
+        stream.delete(&[StreamId::new(7, 1)]);
+
+        let mut writer = RdbWriter::new();
+        write_stream(&mut writer, &stream);
+        let bytes = writer.into_bytes();
+        let read = read_stream(&mut RdbReader::new(&bytes), TYPE_STREAM_LISTPACKS).unwrap();
+        assert_eq!(read.last_id(), StreamId::new(7, 1));
+        assert!(read.is_empty());
+    }
+}
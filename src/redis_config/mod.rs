@@ -0,0 +1,436 @@
+use crate::commands::server::info_formatter::info_server_formatter::*;
+use crate::logs::slowlog::{Slowlog, DEFAULT_LOG_SLOWER_THAN, DEFAULT_MAX_LEN};
+use crate::native_types::ErrorStruct;
+use crate::tcp_protocol::client_list::ClientList;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::LineWriter,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+pub mod parameters;
+
+/// Save points used when the configuration has no `save` line, as Redis:
+/// after an hour if a key changed, after 5 minutes if 100 keys changed and
+/// after a minute if 10000 keys changed.
+pub const DEFAULT_SAVE_POINTS: [(u64, u64); 3] = [(3600, 1), (300, 100), (60, 10000)];
+
+/// Seconds between two runs of the garbage collector, set with `gc-period`.
+pub const DEFAULT_GC_PERIOD: u64 = 10;
+
+/// Keys checked by each run of the garbage collector, set with `gc-sample-size`.
+pub const DEFAULT_GC_SAMPLE_SIZE: u64 = 20;
+
+/// Seconds between two checks of the save points, set with `save-interval`.
+pub const DEFAULT_SAVE_INTERVAL: u64 = 1;
+
+/// Format of the dump file, set with `dbformat rdb|legacy`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DumpFormat {
+    /// Text made of RESP fragments, understood only by this server.
+    #[default]
+    Legacy,
+    /// The binary RDB format of Redis.
+    Rdb,
+}
+
+impl DumpFormat {
+    /// Parses the value of `dbformat`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "legacy" => Some(DumpFormat::Legacy),
+            "rdb" => Some(DumpFormat::Rdb),
+            _ => None,
+        }
+    }
+}
+
+pub struct RedisConfig {
+    ip: String,
+    port: String,
+    log_filename: String,
+    lwriter: LineWriter<File>,
+    dump_filename: String,
+    verbose: usize,
+    timeout_secs: u64,
+    slowlog_log_slower_than: i64,
+    slowlog_max_len: usize,
+    save_points: Vec<(u64, u64)>,
+    dump_format: DumpFormat,
+    load_truncated: bool,
+    gc_period: u64,
+    gc_sample_size: u64,
+    save_interval: u64,
+    config_file: Option<String>,
+    slowlog: Option<Slowlog>,
+    client_list: Option<Arc<Mutex<ClientList>>>,
+}
+
+impl RedisConfig {
+    /// Creates a new instance of RedisConfig.
+    ///
+    /// ## Error
+    ///
+    /// Return Err if opening the file throws an error
+    pub fn new(
+        ip: String,
+        port: String,
+        log_filename: String,
+        dump_filename: String,
+        verbose: usize,
+    ) -> Result<RedisConfig, ErrorStruct> {
+        let lwriter;
+        match OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&log_filename)
+        {
+            Ok(file) => {
+                lwriter = LineWriter::new(file);
+            }
+            Err(err) => {
+                return Err(ErrorStruct::new(
+                    "ERR_CONFIG".into(),
+                    format!("Setting a new config failed. Detail: {}", err),
+                ))
+            }
+        }
+
+        Ok(RedisConfig {
+            ip,
+            port,
+            log_filename,
+            verbose,
+            lwriter,
+            dump_filename,
+            timeout_secs: 0,
+            slowlog_log_slower_than: DEFAULT_LOG_SLOWER_THAN,
+            slowlog_max_len: DEFAULT_MAX_LEN,
+            save_points: DEFAULT_SAVE_POINTS.to_vec(),
+            dump_format: DumpFormat::default(),
+            load_truncated: false,
+            gc_period: DEFAULT_GC_PERIOD,
+            gc_sample_size: DEFAULT_GC_SAMPLE_SIZE,
+            save_interval: DEFAULT_SAVE_INTERVAL,
+            config_file: None,
+            slowlog: None,
+            client_list: None,
+        })
+    }
+
+    /// Pushes RedisConfig information of the *server* section to the specified vector.
+    pub fn info(&self, info_compiler: &mut Vec<String>) {
+        info_compiler.push(ip(&self.ip));
+        info_compiler.push(port(&self.port));
+        info_compiler.push(current_exe_dir());
+        info_compiler.push(logfile_name(&self.log_filename));
+        info_compiler.push(verbose_level(self.verbose));
+        info_compiler.push(client_timeout(self.timeout_secs));
+    }
+
+    /// Parses a vector and returns a RedisConfig instance.
+    pub fn parse_config(argv: Vec<String>) -> Result<RedisConfig, ErrorStruct> {
+        let config = match argv.len().eq(&2) {
+            true => RedisConfig::get_with_new_config(&argv[1])?,
+            false => RedisConfig::default(),
+        };
+        Ok(config)
+    }
+
+    /// Linewriter getter
+    pub fn get_mut_linewriter(&mut self) -> Option<&mut LineWriter<File>> {
+        Some(&mut self.lwriter)
+    }
+
+    /// Modifies RedisConfig log file with the received filename
+    ///
+    /// ## Error
+    ///
+    /// Return Err if opening the file throws an error
+    pub fn change_log_file(&mut self, new_log_filename: String) -> Result<(), ErrorStruct> {
+        match OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&new_log_filename)
+        {
+            Ok(file) => {
+                self.lwriter = LineWriter::new(file);
+                self.log_filename = new_log_filename;
+                Ok(())
+            }
+            Err(err) => Err(ErrorStruct::new(
+                "ERR_CONFIG".into(),
+                format!("Setting a new config failed. Detail: {}", err),
+            )),
+        }
+    }
+
+    /// Db filename updater (setter)
+    ///
+    /// The file is not opened: the saves create it and the start of the
+    /// server loads it only if it exists.
+    pub fn update_db_filename(&mut self, new_dump_filename: String) {
+        self.dump_filename = new_dump_filename;
+    }
+
+    /// Received name path of new file .config to generate a new configuration for server Redis
+    ///
+    /// If the .config file comes incomplete, the default configurations will be taken.
+    /// The path is kept to write the changes back with CONFIG REWRITE.
+    ///
+    /// ## Error
+    ///
+    /// Return Err if opening the file throws an error
+    fn get_with_new_config(path: &str) -> Result<RedisConfig, ErrorStruct> {
+        let content = fs::read_to_string(path).map_err(|err| {
+            ErrorStruct::new(
+                "ERR_CONFIG".into(),
+                format!("Set a new config failure. Detail: {}", err),
+            )
+        })?;
+
+        let mut redis_config = RedisConfig::new(
+            "127.0.0.1".into(),
+            Self::default().port(),
+            String::from("logs.txt"),
+            "dump.rdb".to_string(),
+            0,
+        )?;
+        redis_config.load_parameters(&content);
+        redis_config.config_file = Some(path.to_string());
+        Ok(redis_config)
+    }
+
+    /// Path of the configuration file the server was started with, if any
+    pub fn config_file(&self) -> Option<&str> {
+        self.config_file.as_deref()
+    }
+
+    /// Registers the [Slowlog] of the server, so the changes of its
+    /// parameters are applied to it.
+    pub fn set_slowlog(&mut self, slowlog: Slowlog) {
+        self.slowlog = Some(slowlog);
+    }
+
+    /// Registers the [ClientList] of the server, so the changes of the
+    /// `timeout` are applied to the connected clients. They get the current one.
+    pub fn set_client_list(&mut self, client_list: Arc<Mutex<ClientList>>) {
+        if let Ok(mut clients) = client_list.lock() {
+            clients.set_timeout(self.client_timeout());
+        }
+        self.client_list = Some(client_list);
+    }
+
+    /// IP getter
+    pub fn ip(&self) -> String {
+        self.ip.to_string()
+    }
+
+    /// Port getter
+    pub fn port(&self) -> String {
+        self.port.to_string()
+    }
+
+    /// Timeout getter
+    pub fn timeout(&self) -> u64 {
+        self.timeout_secs
+    }
+
+    /// Time after which an idle client is disconnected, or [None] if the
+    /// `timeout` is 0.
+    pub fn client_timeout(&self) -> Option<Duration> {
+        match self.timeout_secs {
+            0 => None,
+            seconds => Some(Duration::from_secs(seconds)),
+        }
+    }
+
+    /// Address getter
+    pub fn get_addr(&self) -> String {
+        self.ip.to_string() + ":" + &self.port
+    }
+
+    /// IP updater (setter)
+    pub fn update_ip(&mut self, ip: &str) {
+        self.ip = ip.to_string();
+    }
+
+    /// Port name updater (setter)
+    pub fn update_port(&mut self, port: &str) {
+        self.port = port.to_string();
+    }
+
+    /// Db filename getter
+    pub fn db_filename(&self) -> String {
+        self.dump_filename.to_string()
+    }
+
+    /// Log filename getter
+    pub fn log_filename(&self) -> String {
+        String::from(&self.log_filename)
+    }
+
+    /// Verbose getter
+    pub fn verbose(&self) -> &usize {
+        &self.verbose
+    }
+
+    /// Slowlog threshold getter, in microseconds
+    pub fn slowlog_log_slower_than(&self) -> i64 {
+        self.slowlog_log_slower_than
+    }
+
+    /// Slowlog max length getter
+    pub fn slowlog_max_len(&self) -> usize {
+        self.slowlog_max_len
+    }
+
+    /// Save points getter, as pairs of seconds and changes
+    pub fn save_points(&self) -> &[(u64, u64)] {
+        &self.save_points
+    }
+
+    /// Save points setter
+    pub fn set_save_points(&mut self, save_points: Vec<(u64, u64)>) {
+        self.save_points = save_points;
+    }
+
+    /// Dump format getter
+    pub fn dump_format(&self) -> DumpFormat {
+        self.dump_format
+    }
+
+    /// Dump format setter
+    pub fn set_dump_format(&mut self, dump_format: DumpFormat) {
+        self.dump_format = dump_format;
+    }
+
+    /// Getter of `dbload-truncated`: whether a corrupted dump file is loaded up
+    /// to its last valid key instead of refusing to start
+    pub fn load_truncated(&self) -> bool {
+        self.load_truncated
+    }
+
+    /// Setter of `dbload-truncated`
+    pub fn set_load_truncated(&mut self, load_truncated: bool) {
+        self.load_truncated = load_truncated;
+    }
+
+    /// Getter of `gc-period`: seconds between two runs of the garbage
+    /// collector, or 0 if it is disabled
+    pub fn gc_period(&self) -> u64 {
+        self.gc_period
+    }
+
+    /// Setter of `gc-period`
+    pub fn set_gc_period(&mut self, gc_period: u64) {
+        self.gc_period = gc_period;
+    }
+
+    /// Getter of `gc-sample-size`: keys checked by each run of the garbage collector
+    pub fn gc_sample_size(&self) -> u64 {
+        self.gc_sample_size
+    }
+
+    /// Setter of `gc-sample-size`. A run checks at least one key.
+    pub fn set_gc_sample_size(&mut self, gc_sample_size: u64) {
+        self.gc_sample_size = gc_sample_size.max(1);
+    }
+
+    /// Getter of `save-interval`: seconds between two checks of the save
+    /// points, or 0 if the automatic saves are disabled
+    pub fn save_interval(&self) -> u64 {
+        self.save_interval
+    }
+
+    /// Setter of `save-interval`
+    pub fn set_save_interval(&mut self, save_interval: u64) {
+        self.save_interval = save_interval;
+    }
+
+    /// Verbose setter
+    pub fn change_verbose(&mut self, new: usize) {
+        self.verbose = new;
+    }
+}
+
+impl Default for RedisConfig {
+    /// Obtained a default configuration for server Redis
+    ///
+    /// ## Redis Config by Default:
+    ///
+    /// * **IP**: 127.0.0.1
+    ///
+    /// * **PORT**: 6379
+    fn default() -> Self {
+        let ip = "127.0.0.1".into();
+        let port = "6379".into();
+        let log_filename = "logs.txt".to_string();
+        let verbose = 100;
+        RedisConfig::new(ip, port, log_filename, "dump.rdb".to_string(), verbose).unwrap()
+    }
+}
+
+/// Parses pairs of seconds and changes, as in `save 3600 1 300 100`.
+///
+/// Returns [None] if some value is not a number or a pair is incomplete.
+pub fn parse_save_points(args: &[&str]) -> Option<Vec<(u64, u64)>> {
+    if !args.len().is_multiple_of(2) {
+        return None;
+    }
+    args.chunks(2)
+        .map(|pair| Some((pair[0].parse().ok()?, pair[1].parse().ok()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test_redis_config {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_01_parse_save_points() {
+        assert_eq!(
+            parse_save_points(&["3600", "1", "300", "100"]),
+            Some(vec![(3600, 1), (300, 100)])
+        );
+        assert_eq!(parse_save_points(&["3600"]), None);
+        assert_eq!(parse_save_points(&["3600", "many"]), None);
+    }
+
+    #[test]
+    fn test_02_save_lines_replace_the_default_save_points() {
+        let path = "redis_config_02.conf";
+        fs::write(path, "port 7777\nsave 900 1\nsave 60 5 10 100\n").unwrap();
+        let config = RedisConfig::get_with_new_config(path).unwrap();
+        assert_eq!(config.save_points(), &[(900, 1), (60, 5), (10, 100)]);
+
+        fs::write(path, "save 900 1\nsave \"\"\n").unwrap();
+        let config = RedisConfig::get_with_new_config(path).unwrap();
+        assert!(config.save_points().is_empty());
+
+        fs::write(path, "port 7777\n").unwrap();
+        let config = RedisConfig::get_with_new_config(path).unwrap();
+        assert_eq!(config.save_points(), &DEFAULT_SAVE_POINTS);
+        assert_eq!(config.config_file(), Some(path));
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_03_periodic_tasks_are_read_from_the_file() {
+        let path = "redis_config_03.conf";
+        fs::write(path, "gc-period 0\ngc-sample-size 50\nsave-interval 60\n").unwrap();
+        let config = RedisConfig::get_with_new_config(path).unwrap();
+        assert_eq!(config.gc_period(), 0);
+        assert_eq!(config.gc_sample_size(), 50);
+        assert_eq!(config.save_interval(), 60);
+
+        fs::write(path, "gc-period many\ngc-sample-size 0\n").unwrap();
+        let config = RedisConfig::get_with_new_config(path).unwrap();
+        assert_eq!(config.gc_period(), DEFAULT_GC_PERIOD);
+        assert_eq!(config.gc_sample_size(), DEFAULT_GC_SAMPLE_SIZE);
+        assert_eq!(config.save_interval(), DEFAULT_SAVE_INTERVAL);
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,787 @@
+//! Registry of the parameters of the [RedisConfig], read from _redis.conf_,
+//! by CONFIG GET and CONFIG SET, and written back by CONFIG REWRITE.
+//!
+//! Every [Parameter] declares its name, the type and range of its values,
+//! its default, whether CONFIG SET can change it while the server runs and
+//! the hook which applies a validated value.
+//!
+//! `bind` and `port` are only read from the file, as the server listens on
+//! them from the start. A new `timeout` is applied to the connected clients
+//! too: see [ClientList::set_timeout](crate::tcp_protocol::client_list::ClientList::set_timeout).
+//! As the hooks act on the server, CONFIG SET validates every value, the
+//! files included, before it applies any of them.
+
+use super::{parse_save_points, DumpFormat, RedisConfig};
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::ErrorStruct;
+use crate::regex::super_regex::SuperRegex;
+use std::collections::HashSet;
+use std::fs;
+use std::net::Ipv4Addr;
+use std::path::Path;
+
+/// Line added before the parameters which CONFIG REWRITE appends to the file.
+const REWRITE_HEADER: &str = "# Generated by CONFIG REWRITE";
+
+/// Values accepted by a [Parameter].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParameterType {
+    /// An integer between the bounds, both inclusive.
+    Integer { min: i64, max: i64 },
+    /// `yes` or `no`.
+    Boolean,
+    /// Any text.
+    Text,
+    /// The name of a file which can be created or appended to.
+    File,
+    /// One of the values, in any case.
+    Enum(&'static [&'static str]),
+    /// Pairs of seconds and changes, as `3600 1 300 100`, or `""` for none.
+    SavePoints,
+}
+
+/// A value already validated by its [ParameterType].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterValue {
+    Integer(i64),
+    Boolean(bool),
+    Text(String),
+    SavePoints(Vec<(u64, u64)>),
+}
+
+impl ParameterValue {
+    fn integer(&self) -> i64 {
+        match self {
+            ParameterValue::Integer(value) => *value,
+            _ => 0,
+        }
+    }
+
+    fn boolean(&self) -> bool {
+        matches!(self, ParameterValue::Boolean(true))
+    }
+
+    fn text(self) -> String {
+        match self {
+            ParameterValue::Text(value) => value,
+            _ => String::new(),
+        }
+    }
+
+    fn save_points(self) -> Vec<(u64, u64)> {
+        match self {
+            ParameterValue::SavePoints(value) => value,
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl ParameterType {
+    /// Validates a value received for the parameter `name`.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value is not of the type, or it is out of range.
+    pub fn parse(&self, name: &str, value: &str) -> Result<ParameterValue, ErrorStruct> {
+        let invalid =
+            |detail: &str| ErrorStruct::from(redis_messages::invalid_config_argument(name, detail));
+        match self {
+            ParameterType::Integer { min, max } => {
+                let integer = value
+                    .parse::<i64>()
+                    .map_err(|_| invalid("argument couldn't be parsed into an integer"))?;
+                if integer < *min || integer > *max {
+                    return Err(invalid(&format!(
+                        "argument must be between {} and {} inclusive",
+                        min, max
+                    )));
+                }
+                Ok(ParameterValue::Integer(integer))
+            }
+            ParameterType::Boolean => match value.to_lowercase().as_str() {
+                "yes" => Ok(ParameterValue::Boolean(true)),
+                "no" => Ok(ParameterValue::Boolean(false)),
+                _ => Err(invalid("argument must be 'yes' or 'no'")),
+            },
+            ParameterType::Text => Ok(ParameterValue::Text(value.to_string())),
+            ParameterType::File if is_writable(value) => {
+                Ok(ParameterValue::Text(value.to_string()))
+            }
+            ParameterType::File => Err(invalid("argument must be a writable file")),
+            ParameterType::Enum(values) => values
+                .iter()
+                .find(|allowed| allowed.eq_ignore_ascii_case(value))
+                .map(|allowed| ParameterValue::Text(allowed.to_string()))
+                .ok_or_else(|| {
+                    invalid(&format!(
+                        "argument(s) must be one of the following: {}",
+                        values.join(", ")
+                    ))
+                }),
+            ParameterType::SavePoints => {
+                let args: Vec<&str> = value
+                    .split_whitespace()
+                    .filter(|arg| !arg.trim_matches('"').is_empty())
+                    .collect();
+                parse_save_points(&args)
+                    .map(ParameterValue::SavePoints)
+                    .ok_or_else(|| invalid("Invalid save parameters"))
+            }
+        }
+    }
+}
+
+/// A parameter of the [RedisConfig].
+pub struct Parameter {
+    pub name: &'static str,
+    pub kind: ParameterType,
+    pub default: &'static str,
+    /// Whether CONFIG SET can change it. The others are only read from the file.
+    pub mutable: bool,
+    get: fn(&RedisConfig) -> String,
+    apply: fn(&mut RedisConfig, ParameterValue) -> Result<(), ErrorStruct>,
+}
+
+impl Parameter {
+    /// The current value, as CONFIG GET shows it.
+    pub fn get(&self, config: &RedisConfig) -> String {
+        (self.get)(config)
+    }
+
+    /// Validates the value and applies it to the configuration.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The value is not valid for the parameter.
+    /// * The hook failed to apply it, as when a file can not be opened.
+    pub fn set(&self, config: &mut RedisConfig, value: &str) -> Result<(), ErrorStruct> {
+        let value = self.kind.parse(self.name, value)?;
+        (self.apply)(config, value)
+    }
+
+    /// The lines of _redis.conf_ which set the current value.
+    fn lines(&self, config: &RedisConfig) -> Vec<String> {
+        match (self.kind, config.save_points.as_slice()) {
+            (ParameterType::SavePoints, []) => vec![format!("{} \"\"", self.name)],
+            (ParameterType::SavePoints, save_points) => save_points
+                .iter()
+                .map(|(seconds, changes)| format!("{} {} {}", self.name, seconds, changes))
+                .collect(),
+            _ => vec![format!("{} {}", self.name, self.get(config))],
+        }
+    }
+}
+
+/// Every parameter of the configuration, in the order CONFIG GET lists them.
+pub static PARAMETERS: [Parameter; 14] = [
+    Parameter {
+        name: "port",
+        kind: ParameterType::Integer { min: 0, max: 65535 },
+        default: "6379",
+        mutable: false,
+        get: |config| config.port(),
+        apply: |config, value| {
+            config.update_port(&value.integer().to_string());
+            Ok(())
+        },
+    },
+    Parameter {
+        name: "bind",
+        kind: ParameterType::Text,
+        default: "127.0.0.1",
+        mutable: false,
+        get: |config| config.ip(),
+        apply: |config, value| {
+            let ip = value.text();
+            ip.parse::<Ipv4Addr>().map_err(|_| {
+                ErrorStruct::from(redis_messages::invalid_config_argument(
+                    "bind",
+                    "argument must be an IPv4 address",
+                ))
+            })?;
+            config.update_ip(&ip);
+            Ok(())
+        },
+    },
+    Parameter {
+        name: "timeout",
+        kind: ParameterType::Integer {
+            min: 0,
+            max: i64::MAX,
+        },
+        default: "0",
+        mutable: true,
+        get: |config| config.timeout().to_string(),
+        apply: |config, value| {
+            config.timeout_secs = value.integer() as u64;
+            if let Some(client_list) = config.client_list.as_ref() {
+                client_list
+                    .lock()
+                    .map_err(|_| {
+                        ErrorStruct::from(redis_messages::poisoned_lock(
+                            "client list",
+                            ErrorSeverity::ShutdownServer,
+                        ))
+                    })?
+                    .set_timeout(config.client_timeout());
+            }
+            Ok(())
+        },
+    },
+    Parameter {
+        name: "logfile",
+        kind: ParameterType::File,
+        default: "logs.txt",
+        mutable: true,
+        get: |config| config.log_filename(),
+        apply: |config, value| config.change_log_file(value.text()),
+    },
+    Parameter {
+        name: "dbfilename",
+        kind: ParameterType::Text,
+        default: "dump.rdb",
+        mutable: true,
+        get: |config| config.db_filename(),
+        apply: |config, value| {
+            config.update_db_filename(value.text());
+            Ok(())
+        },
+    },
+    Parameter {
+        name: "verbose",
+        kind: ParameterType::Integer {
+            min: 0,
+            max: i64::MAX,
+        },
+        default: "0",
+        mutable: true,
+        get: |config| config.verbose().to_string(),
+        apply: |config, value| {
+            config.change_verbose(value.integer() as usize);
+            Ok(())
+        },
+    },
+    Parameter {
+        name: "slowlog-log-slower-than",
+        kind: ParameterType::Integer {
+            min: -1,
+            max: i64::MAX,
+        },
+        default: "10000",
+        mutable: true,
+        get: |config| config.slowlog_log_slower_than().to_string(),
+        apply: |config, value| {
+            config.slowlog_log_slower_than = value.integer();
+            match config.slowlog.as_ref() {
+                Some(slowlog) => slowlog.set_log_slower_than(value.integer()),
+                None => Ok(()),
+            }
+        },
+    },
+    Parameter {
+        name: "slowlog-max-len",
+        kind: ParameterType::Integer {
+            min: 0,
+            max: i64::MAX,
+        },
+        default: "128",
+        mutable: true,
+        get: |config| config.slowlog_max_len().to_string(),
+        apply: |config, value| {
+            config.slowlog_max_len = value.integer() as usize;
+            match config.slowlog.as_ref() {
+                Some(slowlog) => slowlog.set_max_len(value.integer() as usize),
+                None => Ok(()),
+            }
+        },
+    },
+    Parameter {
+        name: "save",
+        kind: ParameterType::SavePoints,
+        default: "3600 1 300 100 60 10000",
+        mutable: true,
+        get: |config| {
+            config
+                .save_points()
+                .iter()
+                .map(|(seconds, changes)| format!("{} {}", seconds, changes))
+                .collect::<Vec<String>>()
+                .join(" ")
+        },
+        apply: |config, value| {
+            config.set_save_points(value.save_points());
+            Ok(())
+        },
+    },
+    Parameter {
+        name: "dbformat",
+        kind: ParameterType::Enum(&["legacy", "rdb"]),
+        default: "legacy",
+        mutable: true,
+        get: |config| match config.dump_format() {
+            DumpFormat::Legacy => "legacy".to_string(),
+            DumpFormat::Rdb => "rdb".to_string(),
+        },
+        apply: |config, value| {
+            if let Some(dump_format) = DumpFormat::parse(&value.text()) {
+                config.set_dump_format(dump_format);
+            }
+            Ok(())
+        },
+    },
+    Parameter {
+        name: "dbload-truncated",
+        kind: ParameterType::Boolean,
+        default: "no",
+        mutable: true,
+        get: |config| yes_no(config.load_truncated()),
+        apply: |config, value| {
+            config.set_load_truncated(value.boolean());
+            Ok(())
+        },
+    },
+    Parameter {
+        name: "save-interval",
+        kind: ParameterType::Integer {
+            min: 0,
+            max: i64::MAX,
+        },
+        default: "1",
+        mutable: true,
+        get: |config| config.save_interval().to_string(),
+        apply: |config, value| {
+            config.set_save_interval(value.integer() as u64);
+            Ok(())
+        },
+    },
+    Parameter {
+        name: "gc-period",
+        kind: ParameterType::Integer {
+            min: 0,
+            max: i64::MAX,
+        },
+        default: "10",
+        mutable: true,
+        get: |config| config.gc_period().to_string(),
+        apply: |config, value| {
+            config.set_gc_period(value.integer() as u64);
+            Ok(())
+        },
+    },
+    Parameter {
+        name: "gc-sample-size",
+        kind: ParameterType::Integer {
+            min: 1,
+            max: i64::MAX,
+        },
+        default: "20",
+        mutable: true,
+        get: |config| config.gc_sample_size().to_string(),
+        apply: |config, value| {
+            config.set_gc_sample_size(value.integer() as u64);
+            Ok(())
+        },
+    },
+];
+
+/// Looks for a parameter by its name, in any case.
+pub fn parameter(name: &str) -> Option<&'static Parameter> {
+    PARAMETERS
+        .iter()
+        .find(|parameter| parameter.name.eq_ignore_ascii_case(name))
+}
+
+/// Whether the file exists and can be written, or can be created in its
+/// directory. Nothing is created, so an invalid CONFIG SET leaves no trace.
+fn is_writable(path: &str) -> bool {
+    let path = Path::new(path);
+    match fs::metadata(path) {
+        Ok(metadata) => metadata.is_file() && !metadata.permissions().readonly(),
+        Err(_) => match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.is_dir(),
+            _ => !path.as_os_str().is_empty(),
+        },
+    }
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+impl RedisConfig {
+    /// Returns the names and values of the parameters matching any of the
+    /// glob-style patterns, each one once.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * A pattern is not valid.
+    pub fn get_parameters(&self, patterns: &[String]) -> Result<Vec<String>, ErrorStruct> {
+        let mut matchers = Vec::new();
+        for pattern in patterns {
+            matchers.push(SuperRegex::from(&pattern.to_lowercase()).map_err(|_| {
+                ErrorStruct::from(redis_messages::invalid_config_argument(
+                    pattern,
+                    "invalid pattern",
+                ))
+            })?);
+        }
+        let mut found = Vec::new();
+        for matcher in matchers.iter() {
+            for parameter in PARAMETERS.iter() {
+                if matcher.is_match(parameter.name) && !found.contains(&parameter.name) {
+                    found.push(parameter.name);
+                }
+            }
+        }
+        Ok(found
+            .into_iter()
+            .filter_map(parameter)
+            .flat_map(|parameter| vec![parameter.name.to_string(), parameter.get(self)])
+            .collect())
+    }
+
+    /// Sets every parameter to its value. Every pair is validated before
+    /// any hook runs, so an invalid one leaves the server untouched. The
+    /// files are opened first, and if a hook still fails the parameters
+    /// already set go back to their previous values.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * A parameter does not exist, can not be changed or appears twice.
+    /// * A value is not valid for its parameter, or it could not be applied.
+    pub fn set_parameters(&mut self, pairs: &[(String, String)]) -> Result<(), ErrorStruct> {
+        let mut parsed: Vec<(&Parameter, ParameterValue)> = Vec::new();
+        for (name, value) in pairs {
+            let parameter = parameter(name)
+                .ok_or_else(|| ErrorStruct::from(redis_messages::unknown_config_option(name)))?;
+            let invalid = |detail| {
+                ErrorStruct::from(redis_messages::invalid_config_argument(
+                    parameter.name,
+                    detail,
+                ))
+            };
+            if !parameter.mutable {
+                return Err(invalid("can't set immutable config"));
+            }
+            if parsed.iter().any(|(set, _)| set.name == parameter.name) {
+                return Err(invalid("duplicate parameter"));
+            }
+            parsed.push((parameter, parameter.kind.parse(parameter.name, value)?));
+        }
+        parsed.sort_by_key(|(parameter, _)| parameter.kind != ParameterType::File);
+
+        let mut applied: Vec<(&Parameter, String)> = Vec::new();
+        for (parameter, value) in parsed {
+            let previous = parameter.get(self);
+            if let Err(error) = (parameter.apply)(self, value) {
+                for (parameter, previous) in applied.into_iter().rev() {
+                    let _ = parameter.set(self, &previous);
+                }
+                return Err(error);
+            }
+            applied.push((parameter, previous));
+        }
+        Ok(())
+    }
+
+    /// Writes the current values in the file the configuration was read
+    /// from. The lines of the parameters are replaced in place, keeping the
+    /// comments and the other lines, and the parameters missing from the
+    /// file are appended if they are not at their default.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The server was started without a configuration file.
+    /// * The file could not be read or written.
+    pub fn rewrite(&self) -> Result<(), ErrorStruct> {
+        let path = self
+            .config_file
+            .as_ref()
+            .ok_or_else(|| ErrorStruct::from(redis_messages::no_config_file()))?;
+        let file_error = |error: std::io::Error| {
+            ErrorStruct::from(redis_messages::file_error(path, &error.to_string()))
+        };
+        let content = fs::read_to_string(path).map_err(file_error)?;
+
+        let mut written = HashSet::new();
+        let mut lines = Vec::new();
+        for line in content.lines() {
+            let name = match line.trim_start().starts_with('#') {
+                true => None,
+                false => line.split_whitespace().next(),
+            };
+            match name.and_then(parameter) {
+                Some(parameter) => {
+                    if written.insert(parameter.name) {
+                        lines.extend(parameter.lines(self));
+                    }
+                }
+                None => lines.push(line.to_string()),
+            }
+        }
+
+        let missing: Vec<&Parameter> = PARAMETERS
+            .iter()
+            .filter(|parameter| !written.contains(parameter.name))
+            .filter(|parameter| parameter.get(self) != parameter.default)
+            .collect();
+        if !missing.is_empty() && !lines.iter().any(|line| line == REWRITE_HEADER) {
+            lines.push(REWRITE_HEADER.to_string());
+        }
+        for parameter in missing {
+            lines.extend(parameter.lines(self));
+        }
+
+        let temporary = format!("{}.tmp", path);
+        fs::write(&temporary, lines.join("\n") + "\n").map_err(file_error)?;
+        fs::rename(&temporary, path).map_err(file_error)
+    }
+
+    /// Applies the values of the lines of a configuration file. The
+    /// `save` lines add up, and the invalid values are ignored, keeping
+    /// the defaults.
+    pub(super) fn load_parameters(&mut self, content: &str) {
+        let mut save_points: Option<Vec<(u64, u64)>> = None;
+        for line in content.lines() {
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            let mut tokens = line.split_whitespace();
+            let parameter = match tokens.next().and_then(parameter) {
+                Some(parameter) => parameter,
+                None => continue,
+            };
+            let value = tokens.collect::<Vec<&str>>().join(" ");
+            if let Ok(value) = parameter.kind.parse(parameter.name, &value) {
+                match value {
+                    ParameterValue::SavePoints(points) if points.is_empty() => {
+                        save_points = Some(Vec::new());
+                    }
+                    ParameterValue::SavePoints(points) => {
+                        save_points.get_or_insert_with(Vec::new).extend(points);
+                    }
+                    value => {
+                        let _ = (parameter.apply)(self, value);
+                    }
+                }
+            }
+        }
+        if let Some(save_points) = save_points {
+            self.set_save_points(save_points);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_parameters {
+    use super::*;
+    use std::env;
+
+    fn config(name: &str) -> RedisConfig {
+        let path = |extension: &str| {
+            let file = env::temp_dir().join(format!("parameters_{}.{}", name, extension));
+            file.to_string_lossy().into_owned()
+        };
+        RedisConfig::new(
+            "127.0.0.1".to_string(),
+            "6379".to_string(),
+            path("txt"),
+            path("rdb"),
+            0,
+        )
+        .unwrap()
+    }
+
+    fn remove_files(config: &RedisConfig) {
+        let _ = fs::remove_file(config.log_filename());
+        let _ = fs::remove_file(config.db_filename());
+    }
+
+    fn pairs(values: &[(&str, &str)]) -> Vec<(String, String)> {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_01_defaults_are_the_values_of_a_new_config() {
+        let config = config("01");
+        for parameter in PARAMETERS.iter() {
+            if !matches!(parameter.kind, ParameterType::Text | ParameterType::File) {
+                assert_eq!(
+                    parameter.get(&config),
+                    parameter.default,
+                    "{}",
+                    parameter.name
+                );
+            }
+        }
+        remove_files(&config);
+    }
+
+    #[test]
+    fn test_02_get_accepts_patterns_and_several_names() {
+        let config = config("02");
+
+        let slowlog = config.get_parameters(&["slowlog-*".to_string()]).unwrap();
+        let several = config
+            .get_parameters(&["GC-PERIOD".to_string(), "gc-*".to_string()])
+            .unwrap();
+        let none = config.get_parameters(&["missing".to_string()]).unwrap();
+
+        assert_eq!(
+            slowlog,
+            vec!["slowlog-log-slower-than", "10000", "slowlog-max-len", "128"]
+        );
+        assert_eq!(several, vec!["gc-period", "10", "gc-sample-size", "20"]);
+        assert!(none.is_empty());
+        remove_files(&config);
+    }
+
+    #[test]
+    fn test_03_set_changes_every_pair() {
+        let mut config = config("03");
+
+        config
+            .set_parameters(&pairs(&[
+                ("timeout", "30"),
+                ("save", "900 1 60 500"),
+                ("dbformat", "RDB"),
+                ("dbload-truncated", "yes"),
+            ]))
+            .unwrap();
+
+        assert_eq!(config.timeout(), 30);
+        assert_eq!(config.save_points(), &[(900, 1), (60, 500)]);
+        assert_eq!(config.dump_format(), DumpFormat::Rdb);
+        assert!(config.load_truncated());
+        config.set_parameters(&pairs(&[("save", "\"\"")])).unwrap();
+        assert!(config.save_points().is_empty());
+        remove_files(&config);
+    }
+
+    #[test]
+    fn test_04_set_is_atomic() {
+        let mut config = config("04");
+
+        let invalid = config.set_parameters(&pairs(&[("timeout", "30"), ("verbose", "-1")]));
+        let immutable = config.set_parameters(&pairs(&[("timeout", "30"), ("port", "80")]));
+        let unknown = config.set_parameters(&pairs(&[("timeout", "30"), ("missing", "1")]));
+        let duplicate = config.set_parameters(&pairs(&[("timeout", "30"), ("TIMEOUT", "40")]));
+        let failed_hook = config.set_parameters(&pairs(&[
+            ("timeout", "30"),
+            ("logfile", "/missing_directory/logs.txt"),
+        ]));
+
+        assert_eq!(
+            invalid.unwrap_err().print_it(),
+            "ERR CONFIG SET failed (possibly related to argument 'verbose') - argument must be between 0 and 9223372036854775807 inclusive"
+        );
+        assert_eq!(
+            immutable.unwrap_err().print_it(),
+            "ERR CONFIG SET failed (possibly related to argument 'port') - can't set immutable config"
+        );
+        assert_eq!(
+            unknown.unwrap_err().print_it(),
+            "ERR Unknown option or number of arguments for CONFIG SET - 'missing'"
+        );
+        assert!(duplicate.is_err());
+        assert_eq!(
+            failed_hook.unwrap_err().print_it(),
+            "ERR CONFIG SET failed (possibly related to argument 'logfile') - argument must be a writable file"
+        );
+        assert_eq!(config.timeout(), 0);
+        assert_eq!(config.port(), "6379");
+        remove_files(&config);
+    }
+
+    #[test]
+    fn test_05_rewrite_keeps_the_comments_and_appends_the_missing_parameters() {
+        let path = env::temp_dir().join("parameters_05.conf");
+        let path = path.to_string_lossy().into_owned();
+        fs::write(
+            &path,
+            "# Port comment\nport 7777\n\n# Save comment\nsave 900 1\nsave 60 5\ntimeout 10\n",
+        )
+        .unwrap();
+        let mut config = config("05");
+        config.load_parameters(&fs::read_to_string(&path).unwrap());
+        config.config_file = Some(path.clone());
+
+        config
+            .set_parameters(&pairs(&[
+                ("save", "3600 2"),
+                ("timeout", "0"),
+                ("gc-period", "0"),
+            ]))
+            .unwrap();
+        config.rewrite().unwrap();
+        let first = fs::read_to_string(&path).unwrap();
+        config.rewrite().unwrap();
+        let second = fs::read_to_string(&path).unwrap();
+
+        assert_eq!(
+            first,
+            format!(
+                "# Port comment\nport 7777\n\n# Save comment\nsave 3600 2\ntimeout 0\n\
+                 # Generated by CONFIG REWRITE\nlogfile {}\ndbfilename {}\ngc-period 0\n",
+                config.log_filename(),
+                config.db_filename()
+            )
+        );
+        assert_eq!(first, second);
+        let _ = fs::remove_file(&path);
+        remove_files(&config);
+    }
+
+    #[test]
+    fn test_06_rewrite_needs_a_config_file() {
+        let config = config("06");
+        assert_eq!(
+            config.rewrite().unwrap_err().print_it(),
+            "ERR The server is running without a config file"
+        );
+        remove_files(&config);
+    }
+
+    #[test]
+    fn test_07_set_dbfilename_does_not_create_the_file() {
+        let mut config = config("07");
+        let dump_filename = env::temp_dir().join("parameters_07_other.rdb");
+        let dump_filename = dump_filename.to_string_lossy().into_owned();
+        let _ = fs::remove_file(&dump_filename);
+
+        config
+            .set_parameters(&pairs(&[("dbfilename", &dump_filename)]))
+            .unwrap();
+
+        assert_eq!(config.db_filename(), dump_filename);
+        assert!(fs::metadata(&dump_filename).is_err());
+        remove_files(&config);
+    }
+
+    #[test]
+    fn test_08_bind_is_only_read_from_the_file() {
+        let mut config = config("08");
+
+        config.load_parameters("bind 10.0.0.1");
+        assert_eq!(config.get_addr(), "10.0.0.1:6379");
+        config.load_parameters("bind localhost");
+        assert_eq!(config.ip(), "10.0.0.1");
+        assert_eq!(
+            config
+                .set_parameters(&pairs(&[("bind", "0.0.0.0")]))
+                .unwrap_err()
+                .print_it(),
+            "ERR CONFIG SET failed (possibly related to argument 'bind') - can't set immutable config"
+        );
+        remove_files(&config);
+    }
+}
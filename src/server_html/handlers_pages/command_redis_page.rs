@@ -1,7 +1,4 @@
-use std::sync::mpsc;
-
 use crate::{
-    joinable::Joinable,
     native_types::ErrorStruct,
     server_html::{
         available_commands::available_commands, error::http_error::HttpError,
@@ -42,34 +39,13 @@ impl HandlerPage for CommandRedisPage {
 }
 
 fn execute_command(command: String) -> Result<String, HttpError> {
-    let (cmd_sender, cmd_receiver) = mpsc::channel();
-    let (rsp_sender, rsp_receiver) = mpsc::channel();
-
-    let mut client = RedisClient::new(
-        available_commands(),
-        cmd_sender.clone(),
-        rsp_sender,
-        cmd_receiver,
-        "127.0.0.1:6379".to_string(),
-    )
-    .map_err(|_| HttpError::from(status_code::defaults::internal_server_error()))?;
-
-    cmd_sender
-        .send(Some(command))
-        .map_err(|_| HttpError::from(status_code::defaults::internal_server_error()))?;
-    let result = rsp_receiver
-        .recv()
+    let mut client = RedisClient::new(available_commands(), "127.0.0.1", 6379)
         .map_err(|_| HttpError::from(status_code::defaults::internal_server_error()))?;
-    let response = match result {
-        Ok(resp) => resp,
-        Err(err) => map_db_err_to_http_response(err)?,
-    };
 
-    client
-        .join()
-        .map_err(|_| HttpError::from(status_code::defaults::internal_server_error()))?;
-
-    Ok(response)
+    match client.execute(command) {
+        Ok(resp) => Ok(resp),
+        Err(err) => map_db_err_to_http_response(err),
+    }
 }
 // TODO
 /*
@@ -1,16 +1,9 @@
-use std::{
-    collections::HashSet,
-    io::{BufRead, BufReader, Lines, Read, Write},
-    net::{Shutdown, TcpStream},
-    ops::Not,
-    sync::mpsc::{Receiver, Sender},
-    thread::{self, JoinHandle},
-};
+use std::collections::HashSet;
+use std::ops::Not;
 
 use crate::{
-    joinable::Joinable,
-    messages::redis_messages,
-    native_types::{ErrorStruct, RArray, RBulkString, RError, RInteger, RSimpleString, RedisType},
+    client::{Connection, Reply},
+    native_types::ErrorStruct,
 };
 
 /// Manages the website input.
@@ -18,77 +11,58 @@ use crate::{
 /// incorrect, and throw an error in case the command is not available at
 /// the website or does not exist.
 pub struct RedisClient {
-    cmd_sender: Sender<Option<String>>,
-    stream: TcpStream,
-    read_thread: Option<JoinHandle<Result<(), ErrorStruct>>>,
-    write_thread: Option<JoinHandle<Result<(), ErrorStruct>>>,
+    available_commands: HashSet<String>,
+    connection: Connection,
 }
 
 impl RedisClient {
     /// Creates the structure in charge of processing/parsing the website
-    /// input received from the http server.
+    /// input, connected to the server in `host` and `port`.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
     /// * There's an error when connecting to the server.
-    /// * Streams clone can't be performed.
-    /// * An operation processed by function process_web_input fails.
     pub fn new(
         available_commands: HashSet<String>,
-        cmd_sender: Sender<Option<String>>, // Http sender
-        response_http_sender: Sender<Result<String, ErrorStruct>>, // Send OK or ERROR to http server
-        http_receiver: Receiver<Option<String>>, // Http server sends stuff and RedisClient receives it here
-        address: String,
+        host: &str,
+        port: u16,
     ) -> Result<Self, ErrorStruct> {
-        // address: self.ip.to_string() + ":" + &self.port
-        let stream = TcpStream::connect(address).map_err(|_| {
-            ErrorStruct::new(
-                "CONNECTION_FAIL".to_string(),
-                "Redis Client couldn't connect to the server.".to_string(),
-            )
-        })?;
-
-        let mut stream_read_clone = stream.try_clone().map_err(|_| {
-            ErrorStruct::new(
-                "STREAM_CLONE".to_string(),
-                "Stream clone couldn't be performed.".to_string(),
-            )
-        })?;
-
-        let mut stream_write_clone = stream.try_clone().map_err(|_| {
-            ErrorStruct::new(
-                "STREAM_CLONE".to_string(),
-                "Stream clone couldn't be performed.".to_string(),
-            )
-        })?;
-
-        let http_sender_clone = response_http_sender.clone();
-
-        let read_thread = thread::Builder::new()
-            .name("redis_client_read_thread".to_string())
-            .spawn(move || process_db_response(http_sender_clone, &mut stream_read_clone))
-            .unwrap();
-
-        let write_thread = thread::Builder::new()
-            .name("redis_client_write_thread".to_string())
-            .spawn(move || {
-                process_web_input(
-                    &available_commands,
-                    &mut stream_write_clone,
-                    http_receiver,
-                    response_http_sender,
-                )
-            })
-            .unwrap();
-
         Ok(RedisClient {
-            cmd_sender,
-            stream,
-            read_thread: Some(read_thread),
-            write_thread: Some(write_thread),
+            available_commands,
+            connection: Connection::connect(host, port)?,
         })
     }
+
+    /// Sends the command typed in the website to the server and returns its
+    /// reply as text. The errors of the server are returned as text too.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The input has invalid characters or is not an available command.
+    /// * The connection with the server was closed.
+    pub fn execute(&mut self, input: String) -> Result<String, ErrorStruct> {
+        let command = turn_into_vector(&self.available_commands, input)?;
+        let reply = self.connection.request(&command)?;
+        Ok(reply_to_text(reply))
+    }
+}
+
+/// Text shown in the website for a reply, with an element of an array per
+/// line.
+fn reply_to_text(reply: Reply) -> String {
+    match reply {
+        Reply::Status(text) | Reply::Error(text) => text,
+        Reply::Integer(number) => number.to_string(),
+        Reply::Bulk(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Reply::Nil => "(nil)".to_string(),
+        Reply::Array(elements) => elements
+            .into_iter()
+            .map(reply_to_text)
+            .collect::<Vec<String>>()
+            .join("\n"),
+    }
 }
 
 /// Checks if the input received as string relates to an available command.
@@ -161,219 +135,66 @@ pub fn convert_special_characters(input: String) -> Result<String, ErrorStruct>
     Ok(checked_input.into_iter().collect())
 }
 
-/// Iterates receiving string commands from the http server. If the command is
-/// valid, writes it to the stream, if it's not, sends an error to the http server.
-///
-/// # Error
-/// Return an [ErrorStruct] if:
-///
-/// * A closed channel won't let send a message to the http server.
-/// * It is not possible to read a line from a stream.
-fn process_db_response(
-    http_sender: Sender<Result<String, ErrorStruct>>,
-    stream: &mut impl Read,
-) -> Result<(), ErrorStruct> {
-    let stream_reader = BufReader::new(stream);
-    let mut lines = stream_reader.lines();
-    while let Some(received) = lines.next() {
-        match received {
-            Ok(db_response) => {
-                let string_response = get_string_response(db_response, &mut lines);
-                http_sender.send(string_response).map_err(|_| {
-                    ErrorStruct::new(
-                        "CLOSED_CHANNEL".to_string(),
-                        "Message couldn't be delivered to http server.".to_string(),
-                    )
-                })?;
-            }
-            Err(_) => {
-                return Err(ErrorStruct::new(
-                    "STREAM_READ".to_string(),
-                    "Error while reading line from stream.".to_string(),
-                ));
-            }
-        }
-    }
-    Ok(())
-}
-
-/// Decodes the response receveid according to its first character using RedisType decode method
-/// and returns a string representation of it. In case of error, returns an ErrorStruct.
-fn get_string_response(
-    mut db_response: String,
-    mut lines: &mut Lines<BufReader<&mut impl Read>>,
-) -> Result<String, ErrorStruct> {
-    let string_response;
-    if db_response.is_empty() {
-        return Err(ErrorStruct::new(
-            "STREAM_READ".to_string(),
-            "Given response is empty.".to_string(),
-        ));
-    }
-    match db_response.remove(0) {
-        '*' => {
-            let array_response = RArray::decode(db_response, &mut lines);
-            string_response = array_response.map(|a| a.join("\n"));
-        }
-        '+' => {
-            string_response = RSimpleString::decode(db_response, &mut lines);
-        }
-        '-' => {
-            let error_response = RError::decode(db_response, &mut lines);
-            string_response = error_response.map(|a| a.print_it());
-        }
-        ':' => {
-            let integer_response = RInteger::decode(db_response, &mut lines);
-            string_response = integer_response.map(|a| a.to_string());
-        }
-        '$' => {
-            string_response = RBulkString::decode(db_response, &mut lines);
-        }
-        _ => {
-            return Err(ErrorStruct::new(
-                "STREAM_READ".to_string(),
-                "Error while reading line from stream.".to_string(),
-            ));
-        }
-    }
-    let _ = lines.next();
-    string_response
-}
-
-/// Iterates receiving string commands from the http server. If the command is
-/// valid, writes it to the stream, if it's not, sends an error to the http server.
-///
-/// # Error
-/// Return an [ErrorStruct] if:
-///
-/// * A closed channel won't let send a message to the http server.
-/// * It is not possible to write a line to a stream.
-fn process_web_input(
-    available_commands: &HashSet<String>,
-    stream: &mut impl Write,
-    http_receiver: Receiver<Option<String>>,
-    http_sender: Sender<Result<String, ErrorStruct>>,
-) -> Result<(), ErrorStruct> {
-    for wrapped_cmd in http_receiver.iter() {
-        if let Some(cmd) = wrapped_cmd {
-            match turn_into_vector(available_commands, cmd) {
-                Ok(cmd) => {
-                    let encoded_cmd_vector = RArray::encode(cmd);
-                    stream
-                        .write_all(encoded_cmd_vector.as_bytes())
-                        .map_err(|_| {
-                            ErrorStruct::new(
-                                "STREAM_WRITE".to_string(),
-                                "Stream write couldn't be performed successfully.".to_string(),
-                            )
-                        })?;
-                }
-                Err(err) => {
-                    // in case of error http server must be warned!
-                    http_sender.send(Err(err)).map_err(|_| {
-                        ErrorStruct::new(
-                            "CLOSED_CHANNEL".to_string(),
-                            "Message couldn't be delivered to http server.".to_string(),
-                        )
-                    })?;
-                }
-            }
-        } else {
-            break;
-        }
-    }
-    Ok(())
-}
-
-impl Joinable<()> for RedisClient {
-    fn join(&mut self) -> Result<(), ErrorStruct> {
-        let _ = self.stream.shutdown(Shutdown::Both);
-        let _ = self.cmd_sender.send(None);
-        let _ = close_thread(self.read_thread.take(), "redis client read thread");
-        let _ = close_thread(self.write_thread.take(), "redis client write thread");
-        Ok(())
-    }
-}
-
-pub fn close_thread(
-    thread: Option<JoinHandle<Result<(), ErrorStruct>>>,
-    name: &str,
-) -> Result<(), ErrorStruct> {
-    if let Some(handle) = thread {
-        handle
-            .join()
-            .map_err(|_| ErrorStruct::from(redis_messages::thread_panic(name)))?
-    } else {
-        Ok(())
-    }
-}
-
 #[cfg(test)]
 mod test_redis_client {
 
     use super::*;
+    use crate::redis_config::RedisConfig;
     use crate::server_html::available_commands::available_commands;
     use crate::ServerRedis;
-    use std::{
-        sync::mpsc,
-        thread::{sleep, spawn, JoinHandle},
-        time::Duration,
-    };
+    use std::{env, fs};
 
     #[test]
-    #[ignore]
-    fn long_test_01() -> Result<(), ErrorStruct> {
-        let available_commands_set = available_commands();
-        let _server_thread: JoinHandle<Result<(), ErrorStruct>> = spawn(move || {
-            ServerRedis::start(vec![])?;
-            Ok(())
-        });
-        let millisecond = Duration::from_millis(10);
-        let mut retries = 0;
-        loop {
-            let (response_http_mock, processed_response_mock) = mpsc::channel();
-            let (cmd_sender_mock, http_receiver_mock) = mpsc::channel();
-            match RedisClient::new(
-                available_commands_set.clone(),
-                cmd_sender_mock.clone(),
-                response_http_mock,
-                http_receiver_mock,
-                "127.0.0.1:6379".to_string(),
-            ) {
-                Err(err) => {
-                    if let Some(prefix) = err.prefix() {
-                        if prefix == "CONNECTION_FAIL" {
-                            sleep(millisecond);
-                            retries += 1;
-                            if retries > 100000 {
-                                return Err(ErrorStruct::new(
-                                    "ERR_CLIENT".to_string(),
-                                    "Tried to connect too many times".to_string(),
-                                ));
-                            }
-                        }
-                    } else {
-                        return Err(ErrorStruct::new(
-                            "ERR_CLIENT".to_string(),
-                            "Could not connect".to_string(),
-                        ));
-                    }
-                }
-                Ok(_redis_client) => {
-                    let _ = cmd_sender_mock.send(Some("set key value1".to_string()));
-                    let _ = cmd_sender_mock.send(Some("set key value2".to_string()));
-                    let _ = cmd_sender_mock.send(Some("get key".to_string()));
-
-                    assert_eq!(processed_response_mock.recv().unwrap().unwrap(), "OK");
-                    assert_eq!(processed_response_mock.recv().unwrap().unwrap(), "OK");
-                    assert_eq!(processed_response_mock.recv().unwrap().unwrap(), "value2");
-                    break;
-                }
-            }
-        }
+    fn test_01_commands_are_run_in_the_server() -> Result<(), ErrorStruct> {
+        let path = |extension: &str| {
+            let file = format!("redis_client_{}.{}", std::process::id(), extension);
+            env::temp_dir().join(file).to_string_lossy().into_owned()
+        };
+        let _ = fs::remove_file(path("rdb"));
+        let config = RedisConfig::new(
+            "127.0.0.1".to_string(),
+            "0".to_string(),
+            path("txt"),
+            path("rdb"),
+            0,
+        )?;
+        let server = ServerRedis::builder(config).start()?;
+        let mut redis_client = RedisClient::new(available_commands(), "127.0.0.1", server.port())?;
+
+        assert_eq!(redis_client.execute("set key value1".to_string())?, "OK");
+        assert_eq!(redis_client.execute("set key value2".to_string())?, "OK");
+        assert_eq!(redis_client.execute("get key".to_string())?, "value2");
+        assert_eq!(
+            redis_client
+                .execute("flushall".to_string())
+                .unwrap_err()
+                .prefix(),
+            Some("COMMAND")
+        );
+        server.shutdown()?;
+        let _ = fs::remove_file(path("txt"));
+        let _ = fs::remove_file(path("rdb"));
         Ok(())
     }
 
+    #[test]
+    fn test_replies_as_text() {
+        assert_eq!(reply_to_text(Reply::Status("OK".to_string())), "OK");
+        assert_eq!(reply_to_text(Reply::Integer(3)), "3");
+        assert_eq!(reply_to_text(Reply::Nil), "(nil)");
+        assert_eq!(
+            reply_to_text(Reply::Error("ERR wrong number".to_string())),
+            "ERR wrong number"
+        );
+        assert_eq!(
+            reply_to_text(Reply::Array(vec![
+                Reply::Bulk(b"a".to_vec()),
+                Reply::Bulk(b"b".to_vec())
+            ])),
+            "a\nb"
+        );
+    }
+
     #[test]
     fn test_special_characters() {
         assert_eq!(
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+
+/// Score of a member of a sorted set, totally ordered so it can be used as a key.
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Score(f64);
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Set of unique members ordered by their score, and by the member itself
+/// when the scores are equal.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SortedSet {
+    scores: HashMap<String, f64>,
+    ordered: BTreeSet<(Score, String)>,
+}
+
+impl SortedSet {
+    pub fn new() -> Self {
+        SortedSet::default()
+    }
+
+    /// Number of members of the sorted set.
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// Returns true if the sorted set has no members.
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    /// Score of the member, or [None] if it does not belong to the sorted set.
+    pub fn score(&self, member: &str) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    /// Adds the member with the score, or updates its score if it already belongs
+    /// to the sorted set.
+    ///
+    /// # Return value
+    /// [Option]<[f64]>: the previous score of the member, or [None] if it was added.
+    pub fn insert(&mut self, member: &str, score: f64) -> Option<f64> {
+        let previous = self.scores.insert(member.to_string(), score);
+        if let Some(previous) = previous {
+            self.ordered.remove(&(Score(previous), member.to_string()));
+        }
+        self.ordered.insert((Score(score), member.to_string()));
+        previous
+    }
+
+    /// Removes the member, returning true if it belonged to the sorted set.
+    pub fn remove(&mut self, member: &str) -> bool {
+        match self.scores.remove(member) {
+            Some(score) => self.ordered.remove(&(Score(score), member.to_string())),
+            None => false,
+        }
+    }
+
+    /// Iterates the members and their scores from the lowest to the greatest score.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&str, f64)> {
+        self.ordered
+            .iter()
+            .map(|(score, member)| (member.as_str(), score.0))
+    }
+
+    /// Encodes the sorted set as a list of strings: each member followed by its score.
+    pub fn to_vec(&self) -> Vec<String> {
+        self.iter()
+            .flat_map(|(member, score)| vec![member.to_string(), score.to_string()])
+            .collect()
+    }
+
+    /// Decodes a sorted set encoded with [to_vec](SortedSet::to_vec).
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The list does not contain a sorted set.
+    pub fn from_vec(vector: Vec<String>) -> Result<Self, ErrorStruct> {
+        let corrupted =
+            || ErrorStruct::from(redis_messages::unexpected_behaviour("corrupted sorted set"));
+        let mut sorted_set = SortedSet::new();
+        let mut values = vector.into_iter();
+        while let Some(member) = values.next() {
+            let score = values
+                .next()
+                .and_then(|score| score.parse::<f64>().ok())
+                .filter(|score| !score.is_nan())
+                .ok_or_else(corrupted)?;
+            sorted_set.insert(&member, score);
+        }
+        Ok(sorted_set)
+    }
+}
+
+#[cfg(test)]
+mod test_sorted_set {
+    use super::*;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_members_are_ordered_by_score_and_member() {
+        let mut sorted_set = SortedSet::new();
+        assert_eq!(sorted_set.insert("c", 1.0), None);
+        assert_eq!(sorted_set.insert("b", 2.0), None);
+        assert_eq!(sorted_set.insert("a", 1.0), None);
+        let members: Vec<&str> = sorted_set.iter().map(|(member, _)| member).collect();
+        assert_eq!(members, vec!["a", "c", "b"]);
+    }
+
+    #[test]
+    fn test_02_updating_and_removing_members() {
+        let mut sorted_set = SortedSet::new();
+        sorted_set.insert("a", 1.0);
+        sorted_set.insert("b", 2.0);
+        assert_eq!(sorted_set.insert("a", 3.0), Some(1.0));
+        assert_eq!(sorted_set.len(), 2);
+        assert_eq!(sorted_set.iter().last(), Some(("a", 3.0)));
+        assert!(sorted_set.remove("a"));
+        assert!(!sorted_set.remove("a"));
+        assert_eq!(sorted_set.score("a"), None);
+        assert_eq!(sorted_set.score("b"), Some(2.0));
+    }
+
+    #[test]
+    fn test_03_encode_and_decode() {
+        let mut sorted_set = SortedSet::new();
+        sorted_set.insert("a", 0.1);
+        sorted_set.insert("b", 3471579339700058.0);
+        let decoded = SortedSet::from_vec(sorted_set.to_vec()).unwrap();
+        assert_eq!(decoded, sorted_set);
+        assert!(SortedSet::from_vec(vec_strings!["a"]).is_err());
+        assert!(SortedSet::from_vec(vec_strings!["a", "b"]).is_err());
+    }
+}
@@ -0,0 +1,819 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Bound;
+use std::time::SystemTime;
+
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+
+/// Identifier of a stream entry: the unix time in milliseconds when it was
+/// added and a sequence number for the entries added in the same millisecond.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct StreamId {
+    pub ms: u64,
+    pub seq: u64,
+}
+
+impl StreamId {
+    pub fn new(ms: u64, seq: u64) -> Self {
+        StreamId { ms, seq }
+    }
+
+    /// Parses an id as *ms-seq*. When the sequence is missing, `default_seq` is used.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The id is not valid.
+    pub fn parse(id: &str, default_seq: u64) -> Result<Self, ErrorStruct> {
+        let invalid = || ErrorStruct::from(redis_messages::invalid_stream_id());
+        match id.split_once('-') {
+            Some((ms, seq)) => Ok(StreamId::new(
+                ms.parse().map_err(|_| invalid())?,
+                seq.parse().map_err(|_| invalid())?,
+            )),
+            None => Ok(StreamId::new(
+                id.parse().map_err(|_| invalid())?,
+                default_seq,
+            )),
+        }
+    }
+
+    /// Returns the next possible id, or [None] for the greatest one.
+    pub fn next(&self) -> Option<Self> {
+        if self.seq < u64::MAX {
+            Some(StreamId::new(self.ms, self.seq + 1))
+        } else if self.ms < u64::MAX {
+            Some(StreamId::new(self.ms + 1, 0))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the previous possible id, or [None] for 0-0.
+    pub fn previous(&self) -> Option<Self> {
+        if self.seq > 0 {
+            Some(StreamId::new(self.ms, self.seq - 1))
+        } else if self.ms > 0 {
+            Some(StreamId::new(self.ms - 1, u64::MAX))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+/// An entry of a stream: its id and its fields and values, one after the other.
+pub type StreamEntry = (StreamId, Vec<String>);
+
+/// An entry of the history of a consumer: its id and its fields and values,
+/// or [None] if it has been deleted from the stream.
+pub type HistoryEntry = (StreamId, Option<Vec<String>>);
+
+/// Entry delivered to a consumer of a group, and not acknowledged yet.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PendingEntry {
+    pub consumer: String,
+    /// Unix time in milliseconds of the last delivery.
+    pub delivery_time: u64,
+    pub delivery_count: u64,
+}
+
+/// Options of XCLAIM.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ClaimOptions {
+    /// Milliseconds since the last delivery to set.
+    pub idle: Option<u64>,
+    /// Unix time in milliseconds of the last delivery to set.
+    pub time: Option<u64>,
+    /// Delivery count to set.
+    pub retry_count: Option<u64>,
+    /// Claims the entries that are not pending too.
+    pub force: bool,
+    /// Does not increment the delivery count.
+    pub just_id: bool,
+}
+
+/// Consumer group of a stream: the last id delivered to its consumers and
+/// the entries delivered but not acknowledged yet.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ConsumerGroup {
+    last_delivered_id: StreamId,
+    /// Pending entries of every consumer, ordered by id.
+    pending: BTreeMap<StreamId, PendingEntry>,
+    /// Consumers and the unix time in milliseconds when they were last seen.
+    consumers: BTreeMap<String, u64>,
+}
+
+impl ConsumerGroup {
+    pub fn new(last_delivered_id: StreamId) -> Self {
+        ConsumerGroup {
+            last_delivered_id,
+            ..ConsumerGroup::default()
+        }
+    }
+
+    /// Builds a group from its parts, as read from a dump.
+    pub fn from_parts(
+        last_delivered_id: StreamId,
+        pending: BTreeMap<StreamId, PendingEntry>,
+        consumers: BTreeMap<String, u64>,
+    ) -> Self {
+        ConsumerGroup {
+            last_delivered_id,
+            pending,
+            consumers,
+        }
+    }
+
+    /// Id of the last entry delivered to the consumers of the group.
+    pub fn last_delivered_id(&self) -> StreamId {
+        self.last_delivered_id
+    }
+
+    pub fn set_last_delivered_id(&mut self, id: StreamId) {
+        self.last_delivered_id = id;
+    }
+
+    /// Pending entries of every consumer, ordered by id.
+    pub fn pending(&self) -> &BTreeMap<StreamId, PendingEntry> {
+        &self.pending
+    }
+
+    /// Pending entries with an id between both bounds.
+    pub fn pending_range(
+        &self,
+        start: Bound<StreamId>,
+        end: Bound<StreamId>,
+    ) -> Vec<(StreamId, PendingEntry)> {
+        if is_empty_range(&start, &end) {
+            return Vec::new();
+        }
+        self.pending
+            .range((start, end))
+            .map(|(id, entry)| (*id, entry.clone()))
+            .collect()
+    }
+
+    /// Consumers and the unix time in milliseconds when they were last seen.
+    pub fn consumers(&self) -> &BTreeMap<String, u64> {
+        &self.consumers
+    }
+
+    /// Number of pending entries of the consumer.
+    pub fn consumer_pending(&self, consumer: &str) -> usize {
+        self.pending
+            .values()
+            .filter(|entry| entry.consumer == consumer)
+            .count()
+    }
+
+    /// Creates the consumer, returning false if it already existed.
+    pub fn create_consumer(&mut self, consumer: &str, now: u64) -> bool {
+        if self.consumers.contains_key(consumer) {
+            return false;
+        }
+        self.consumers.insert(consumer.to_string(), now);
+        true
+    }
+
+    /// Deletes the consumer and its pending entries.
+    ///
+    /// # Return value
+    /// [usize]: the number of pending entries the consumer had.
+    pub fn delete_consumer(&mut self, consumer: &str) -> usize {
+        let pending = self.consumer_pending(consumer);
+        self.pending.retain(|_, entry| entry.consumer != consumer);
+        self.consumers.remove(consumer);
+        pending
+    }
+
+    /// Acknowledges the entries, removing them from the pending entries.
+    ///
+    /// # Return value
+    /// [usize]: the number of entries acknowledged.
+    pub fn ack(&mut self, ids: &[StreamId]) -> usize {
+        ids.iter()
+            .filter(|id| self.pending.remove(id).is_some())
+            .count()
+    }
+
+    /// Registers that the consumer has been seen now, creating it if needed.
+    fn seen(&mut self, consumer: &str, now: u64) {
+        self.consumers.insert(consumer.to_string(), now);
+    }
+
+    /// Delivers the entry to the consumer, as pending.
+    fn deliver(&mut self, id: StreamId, consumer: &str, now: u64) {
+        let entry = self.pending.entry(id).or_insert(PendingEntry {
+            consumer: consumer.to_string(),
+            delivery_time: now,
+            delivery_count: 0,
+        });
+        entry.consumer = consumer.to_string();
+        entry.delivery_time = now;
+        entry.delivery_count += 1;
+    }
+}
+
+/// Append-only log of entries, ordered by [StreamId], read by consumer groups.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Stream {
+    entries: BTreeMap<StreamId, Vec<String>>,
+    last_id: StreamId,
+    groups: BTreeMap<String, ConsumerGroup>,
+}
+
+impl Stream {
+    pub fn new() -> Self {
+        Stream::default()
+    }
+
+    /// Builds a stream from its parts, as read from a dump.
+    pub fn from_parts(
+        entries: BTreeMap<StreamId, Vec<String>>,
+        last_id: StreamId,
+        groups: BTreeMap<String, ConsumerGroup>,
+    ) -> Self {
+        Stream {
+            entries,
+            last_id,
+            groups,
+        }
+    }
+
+    /// Entries of the stream, ordered by id.
+    pub fn entries(&self) -> &BTreeMap<StreamId, Vec<String>> {
+        &self.entries
+    }
+
+    /// Number of entries of the stream.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the stream has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Id of the last entry ever added, even if it was deleted later.
+    pub fn last_id(&self) -> StreamId {
+        self.last_id
+    }
+
+    /// Adds an entry with the given fields and values. The id can be:
+    ///
+    /// * `*`: generated from the current time.
+    /// * `ms-*`: the sequence number is generated.
+    /// * `ms-seq` or `ms`: an explicit id.
+    ///
+    /// # Return value
+    /// [StreamId]: the id of the new entry.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The id is not valid.
+    /// * The id is 0-0, or not greater than the last id of the stream.
+    pub fn add(&mut self, id: &str, fields: Vec<String>) -> Result<StreamId, ErrorStruct> {
+        let id = self.next_id(id)?;
+        if id == StreamId::default() {
+            return Err(ErrorStruct::from(redis_messages::stream_id_zero()));
+        }
+        if id <= self.last_id {
+            return Err(ErrorStruct::from(redis_messages::stream_id_too_small()));
+        }
+        self.entries.insert(id, fields);
+        self.last_id = id;
+        Ok(id)
+    }
+
+    fn next_id(&self, id: &str) -> Result<StreamId, ErrorStruct> {
+        if id == "*" {
+            let now = now_millis();
+            if now > self.last_id.ms {
+                Ok(StreamId::new(now, 0))
+            } else {
+                self.last_id
+                    .next()
+                    .ok_or_else(|| ErrorStruct::from(redis_messages::stream_id_too_small()))
+            }
+        } else if let Some(ms) = id.strip_suffix("-*") {
+            let ms = ms
+                .parse::<u64>()
+                .map_err(|_| ErrorStruct::from(redis_messages::invalid_stream_id()))?;
+            if ms == self.last_id.ms {
+                self.last_id
+                    .next()
+                    .filter(|next| next.ms == ms)
+                    .ok_or_else(|| ErrorStruct::from(redis_messages::stream_id_too_small()))
+            } else {
+                Ok(StreamId::new(ms, if ms == 0 { 1 } else { 0 }))
+            }
+        } else {
+            StreamId::parse(id, 0)
+        }
+    }
+
+    /// Returns the entries between `start` and `end`, in order, or in reverse
+    /// order if `rev` is true, up to `count` entries.
+    pub fn range(
+        &self,
+        start: Bound<StreamId>,
+        end: Bound<StreamId>,
+        count: Option<usize>,
+        rev: bool,
+    ) -> Vec<StreamEntry> {
+        if is_empty_range(&start, &end) {
+            return Vec::new();
+        }
+        let range = self.entries.range((start, end));
+        let count = count.unwrap_or(usize::MAX);
+        let cloned = |(id, fields): (&StreamId, &Vec<String>)| (*id, fields.clone());
+        if rev {
+            range.rev().take(count).map(cloned).collect()
+        } else {
+            range.take(count).map(cloned).collect()
+        }
+    }
+
+    /// Returns the entries with an id greater than the given one, up to `count` entries.
+    pub fn after(&self, id: StreamId, count: Option<usize>) -> Vec<StreamEntry> {
+        self.range(Bound::Excluded(id), Bound::Unbounded, count, false)
+    }
+
+    /// Removes the entries with the given ids.
+    ///
+    /// # Return value
+    /// [usize]: the number of entries removed.
+    pub fn delete(&mut self, ids: &[StreamId]) -> usize {
+        ids.iter()
+            .filter(|id| self.entries.remove(id).is_some())
+            .count()
+    }
+
+    /// Removes the oldest entries until there are at most `max_len`.
+    ///
+    /// # Return value
+    /// [usize]: the number of entries removed.
+    pub fn trim_max_len(&mut self, max_len: usize) -> usize {
+        let mut removed = 0;
+        while self.entries.len() > max_len {
+            if let Some(first) = self.entries.keys().next().copied() {
+                self.entries.remove(&first);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Removes the entries with an id lower than `min_id`.
+    ///
+    /// # Return value
+    /// [usize]: the number of entries removed.
+    pub fn trim_min_id(&mut self, min_id: StreamId) -> usize {
+        let kept = self.entries.split_off(&min_id);
+        let removed = self.entries.len();
+        self.entries = kept;
+        removed
+    }
+
+    /// First entry of the stream.
+    pub fn first_entry(&self) -> Option<StreamEntry> {
+        self.entries
+            .iter()
+            .next()
+            .map(|(id, fields)| (*id, fields.clone()))
+    }
+
+    /// Last entry of the stream.
+    pub fn last_entry(&self) -> Option<StreamEntry> {
+        self.entries
+            .iter()
+            .next_back()
+            .map(|(id, fields)| (*id, fields.clone()))
+    }
+
+    /// Consumer groups of the stream, by name.
+    pub fn groups(&self) -> &BTreeMap<String, ConsumerGroup> {
+        &self.groups
+    }
+
+    pub fn group(&self, name: &str) -> Option<&ConsumerGroup> {
+        self.groups.get(name)
+    }
+
+    pub fn group_mut(&mut self, name: &str) -> Option<&mut ConsumerGroup> {
+        self.groups.get_mut(name)
+    }
+
+    /// Creates a consumer group that starts reading after the given id.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The group already exists.
+    pub fn create_group(&mut self, name: &str, id: StreamId) -> Result<(), ErrorStruct> {
+        if self.groups.contains_key(name) {
+            return Err(ErrorStruct::from(redis_messages::busy_group()));
+        }
+        self.groups.insert(name.to_string(), ConsumerGroup::new(id));
+        Ok(())
+    }
+
+    /// Destroys the consumer group, returning false if it did not exist.
+    pub fn destroy_group(&mut self, name: &str) -> bool {
+        self.groups.remove(name).is_some()
+    }
+
+    /// Returns true if the group has entries not delivered yet.
+    pub fn has_undelivered(&self, group: &str) -> bool {
+        self.groups.get(group).is_some_and(|group| {
+            self.entries
+                .range((Bound::Excluded(group.last_delivered_id), Bound::Unbounded))
+                .next()
+                .is_some()
+        })
+    }
+
+    /// Reads the stream as a consumer of a group, up to `count` entries:
+    ///
+    /// * With no `start`, the entries never delivered to the group, which
+    ///   become pending for the consumer unless `no_ack` is true.
+    /// * With a `start`, the pending entries of the consumer with a greater
+    ///   id, whose delivery count is incremented.
+    ///
+    /// # Return value
+    /// [Vec]<[HistoryEntry]>: the entries read, or [None] if the group does not exist.
+    pub fn read_group(
+        &mut self,
+        group: &str,
+        consumer: &str,
+        start: Option<StreamId>,
+        count: Option<usize>,
+        no_ack: bool,
+        now: u64,
+    ) -> Option<Vec<HistoryEntry>> {
+        let entries = &self.entries;
+        let group = self.groups.get_mut(group)?;
+        group.seen(consumer, now);
+        let count = count.unwrap_or(usize::MAX);
+        match start {
+            None => {
+                let read: Vec<HistoryEntry> = entries
+                    .range((Bound::Excluded(group.last_delivered_id), Bound::Unbounded))
+                    .take(count)
+                    .map(|(id, fields)| (*id, Some(fields.clone())))
+                    .collect();
+                for (id, _) in read.iter() {
+                    group.last_delivered_id = *id;
+                    if !no_ack {
+                        group.deliver(*id, consumer, now);
+                    }
+                }
+                Some(read)
+            }
+            Some(start) => {
+                let ids: Vec<StreamId> = group
+                    .pending
+                    .range((Bound::Excluded(start), Bound::Unbounded))
+                    .filter(|(_, entry)| entry.consumer == consumer)
+                    .map(|(id, _)| *id)
+                    .take(count)
+                    .collect();
+                Some(
+                    ids.into_iter()
+                        .map(|id| {
+                            group.deliver(id, consumer, now);
+                            (id, entries.get(&id).cloned())
+                        })
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    /// Changes the owner of the pending entries that have been idle for at least
+    /// `min_idle` milliseconds to the consumer. The pending entries deleted from
+    /// the stream are removed.
+    ///
+    /// # Return value
+    /// [Vec]<[StreamEntry]>: the entries claimed, or [None] if the group does not exist.
+    pub fn claim(
+        &mut self,
+        group: &str,
+        consumer: &str,
+        min_idle: u64,
+        ids: &[StreamId],
+        options: &ClaimOptions,
+        now: u64,
+    ) -> Option<Vec<StreamEntry>> {
+        let entries = &self.entries;
+        let group = self.groups.get_mut(group)?;
+        group.seen(consumer, now);
+        let mut claimed = Vec::new();
+        for id in ids {
+            let fields = match entries.get(id) {
+                Some(fields) => fields,
+                None => {
+                    group.pending.remove(id);
+                    continue;
+                }
+            };
+            if !group.pending.contains_key(id) {
+                if !options.force {
+                    continue;
+                }
+                group.pending.insert(
+                    *id,
+                    PendingEntry {
+                        consumer: consumer.to_string(),
+                        delivery_time: now,
+                        delivery_count: 0,
+                    },
+                );
+            } else if idle_time(&group.pending[id], now) < min_idle {
+                continue;
+            }
+            if let Some(entry) = group.pending.get_mut(id) {
+                entry.consumer = consumer.to_string();
+                entry.delivery_time = match (options.idle, options.time) {
+                    (Some(idle), _) => now.saturating_sub(idle),
+                    (None, Some(time)) => time,
+                    (None, None) => now,
+                };
+                if !options.just_id {
+                    entry.delivery_count += 1;
+                }
+                if let Some(retry_count) = options.retry_count {
+                    entry.delivery_count = retry_count;
+                }
+            }
+            claimed.push((*id, fields.clone()));
+        }
+        Some(claimed)
+    }
+
+    /// Returns up to `count` ids of pending entries, equal or greater than `start`,
+    /// that have been idle for at least `min_idle` milliseconds, for XAUTOCLAIM.
+    ///
+    /// # Return value
+    /// The id to start the next scan, or 0-0 if every pending entry has been
+    /// scanned, and the ids found. [None] if the group does not exist.
+    pub fn idle_pending(
+        &self,
+        group: &str,
+        min_idle: u64,
+        start: StreamId,
+        count: usize,
+        now: u64,
+    ) -> Option<(StreamId, Vec<StreamId>)> {
+        let mut idle = self
+            .group(group)?
+            .pending
+            .range(start..)
+            .filter(|(_, entry)| idle_time(entry, now) >= min_idle)
+            .map(|(id, _)| *id);
+        let ids: Vec<StreamId> = idle.by_ref().take(count).collect();
+        Some((idle.next().unwrap_or_default(), ids))
+    }
+
+    /// Encodes the stream as a list of strings to be persisted:
+    ///
+    /// * The last id and the number of entries, followed by the id, the number
+    ///   of fields and values and the fields and values of each entry.
+    /// * The number of groups, followed by the name, the last delivered id, the
+    ///   number of consumers, the name and seen time of each consumer, the number
+    ///   of pending entries and the id, consumer, delivery time and delivery count
+    ///   of each pending entry of each group.
+    pub fn to_vec(&self) -> Vec<String> {
+        let mut vector = vec![self.last_id.to_string(), self.entries.len().to_string()];
+        for (id, fields) in self.entries.iter() {
+            vector.push(id.to_string());
+            vector.push(fields.len().to_string());
+            vector.extend(fields.iter().cloned());
+        }
+        vector.push(self.groups.len().to_string());
+        for (name, group) in self.groups.iter() {
+            vector.push(name.to_string());
+            vector.push(group.last_delivered_id.to_string());
+            vector.push(group.consumers.len().to_string());
+            for (consumer, seen_time) in group.consumers.iter() {
+                vector.push(consumer.to_string());
+                vector.push(seen_time.to_string());
+            }
+            vector.push(group.pending.len().to_string());
+            for (id, entry) in group.pending.iter() {
+                vector.push(id.to_string());
+                vector.push(entry.consumer.to_string());
+                vector.push(entry.delivery_time.to_string());
+                vector.push(entry.delivery_count.to_string());
+            }
+        }
+        vector
+    }
+
+    /// Decodes a stream encoded with [to_vec](Stream::to_vec).
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The list does not contain a stream.
+    pub fn from_vec(vector: Vec<String>) -> Result<Self, ErrorStruct> {
+        let mut values = vector.into_iter();
+        let mut stream = Stream::new();
+        stream.last_id = next_id(&mut values)?;
+        for _ in 0..next_number(&mut values)? {
+            let id = next_id(&mut values)?;
+            let len = next_number(&mut values)? as usize;
+            let fields: Vec<String> = values.by_ref().take(len).collect();
+            if fields.len() != len {
+                return Err(corrupted());
+            }
+            stream.entries.insert(id, fields);
+        }
+        for _ in 0..next_number(&mut values)? {
+            let name = values.next().ok_or_else(corrupted)?;
+            let mut group = ConsumerGroup::new(next_id(&mut values)?);
+            for _ in 0..next_number(&mut values)? {
+                let consumer = values.next().ok_or_else(corrupted)?;
+                group.consumers.insert(consumer, next_number(&mut values)?);
+            }
+            for _ in 0..next_number(&mut values)? {
+                let id = next_id(&mut values)?;
+                let entry = PendingEntry {
+                    consumer: values.next().ok_or_else(corrupted)?,
+                    delivery_time: next_number(&mut values)?,
+                    delivery_count: next_number(&mut values)?,
+                };
+                group.pending.insert(id, entry);
+            }
+            stream.groups.insert(name, group);
+        }
+        Ok(stream)
+    }
+}
+
+/// Milliseconds since the last delivery of the pending entry.
+pub fn idle_time(entry: &PendingEntry, now: u64) -> u64 {
+    now.saturating_sub(entry.delivery_time)
+}
+
+/// Unix time in milliseconds.
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|time| time.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn corrupted() -> ErrorStruct {
+    ErrorStruct::from(redis_messages::unexpected_behaviour("corrupted stream"))
+}
+
+fn next_id(values: &mut impl Iterator<Item = String>) -> Result<StreamId, ErrorStruct> {
+    StreamId::parse(&values.next().ok_or_else(corrupted)?, 0)
+}
+
+fn next_number(values: &mut impl Iterator<Item = String>) -> Result<u64, ErrorStruct> {
+    values
+        .next()
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(corrupted)
+}
+
+/// Returns true if no id can be between both bounds.
+fn is_empty_range(start: &Bound<StreamId>, end: &Bound<StreamId>) -> bool {
+    match (start, end) {
+        (Bound::Included(start), Bound::Included(end)) => start > end,
+        (Bound::Included(start), Bound::Excluded(end))
+        | (Bound::Excluded(start), Bound::Included(end)) => start >= end,
+        (Bound::Excluded(start), Bound::Excluded(end)) => {
+            start.next().is_none_or(|next| next >= *end)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test_stream {
+    use super::*;
+    use crate::vec_strings;
+
+    #[test]
+    fn test_01_ids_are_generated_in_order() {
+        let mut stream = Stream::new();
+        let first = stream.add("*", vec_strings!["a", "1"]).unwrap();
+        let second = stream.add("*", vec_strings!["b", "2"]).unwrap();
+        assert!(second > first);
+        assert_eq!(stream.len(), 2);
+        assert_eq!(stream.last_id(), second);
+    }
+
+    #[test]
+    fn test_02_explicit_ids_must_grow() {
+        let mut stream = Stream::new();
+        assert_eq!(
+            stream.add("5-1", vec_strings!["a", "1"]).unwrap(),
+            StreamId::new(5, 1)
+        );
+        assert_eq!(
+            stream.add("5-*", vec_strings!["a", "1"]).unwrap(),
+            StreamId::new(5, 2)
+        );
+        assert!(stream.add("5-2", vec_strings!["a", "1"]).is_err());
+        assert!(stream.add("4", vec_strings!["a", "1"]).is_err());
+        assert!(Stream::new().add("0-0", vec_strings!["a", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_03_range_and_reverse_range() {
+        let mut stream = Stream::new();
+        for seq in 1..=5 {
+            stream
+                .add(&format!("1-{}", seq), vec_strings!["n", seq])
+                .unwrap();
+        }
+        let range = stream.range(
+            Bound::Included(StreamId::new(1, 2)),
+            Bound::Unbounded,
+            Some(2),
+            false,
+        );
+        assert_eq!(range[0].0, StreamId::new(1, 2));
+        assert_eq!(range[1].0, StreamId::new(1, 3));
+
+        let range = stream.range(Bound::Unbounded, Bound::Unbounded, Some(1), true);
+        assert_eq!(range[0], (StreamId::new(1, 5), vec_strings!["n", "5"]));
+    }
+
+    #[test]
+    fn test_04_delete_and_trim() {
+        let mut stream = Stream::new();
+        for seq in 1..=5 {
+            stream
+                .add(&format!("1-{}", seq), vec_strings!["n", seq])
+                .unwrap();
+        }
+        assert_eq!(
+            stream.delete(&[StreamId::new(1, 1), StreamId::new(9, 9)]),
+            1
+        );
+        assert_eq!(stream.trim_max_len(3), 1);
+        assert_eq!(stream.trim_min_id(StreamId::new(1, 4)), 1);
+        assert_eq!(stream.len(), 2);
+        assert_eq!(stream.last_id(), StreamId::new(1, 5));
+    }
+
+    #[test]
+    fn test_05_encode_and_decode() {
+        let mut stream = Stream::new();
+        stream.add("1-1", vec_strings!["a", "1", "b", "2"]).unwrap();
+        stream.add("2-0", vec_strings!["c", "3"]).unwrap();
+        stream.delete(&[StreamId::new(2, 0)]);
+        let decoded = Stream::from_vec(stream.to_vec()).unwrap();
+        assert_eq!(decoded, stream);
+        assert_eq!(decoded.last_id(), StreamId::new(2, 0));
+    }
+
+    #[test]
+    fn test_06_read_group_delivers_new_entries_and_keeps_them_pending() {
+        let mut stream = Stream::new();
+        stream.add("1-1", vec_strings!["a", "1"]).unwrap();
+        stream.add("1-2", vec_strings!["b", "2"]).unwrap();
+        stream.create_group("group", StreamId::default()).unwrap();
+        assert!(stream.create_group("group", StreamId::default()).is_err());
+
+        let read = stream.read_group("group", "alice", None, Some(1), false, 10);
+        assert_eq!(read.unwrap().len(), 1);
+        assert!(stream.has_undelivered("group"));
+        let history =
+            stream.read_group("group", "alice", Some(StreamId::default()), None, false, 20);
+        assert_eq!(history.unwrap()[0].0, StreamId::new(1, 1));
+
+        let group = stream.group_mut("group").unwrap();
+        assert_eq!(group.last_delivered_id(), StreamId::new(1, 1));
+        assert_eq!(group.consumer_pending("alice"), 1);
+        assert_eq!(group.ack(&[StreamId::new(1, 1)]), 1);
+        assert_eq!(group.consumer_pending("alice"), 0);
+    }
+
+    #[test]
+    fn test_07_encode_and_decode_groups() {
+        let mut stream = Stream::new();
+        stream.add("1-1", vec_strings!["a", "1"]).unwrap();
+        stream.add("1-2", vec_strings!["b", "2"]).unwrap();
+        stream.create_group("group", StreamId::default()).unwrap();
+        stream.create_group("empty", StreamId::new(1, 2)).unwrap();
+        stream.read_group("group", "alice", None, None, false, 10);
+        let decoded = Stream::from_vec(stream.to_vec()).unwrap();
+        assert_eq!(decoded, stream);
+        assert_eq!(decoded.group("group").unwrap().pending().len(), 2);
+    }
+}
@@ -8,13 +8,22 @@ use crate::native_types::ErrorStruct;
 use crate::tcp_protocol::client_atributes::status::Status;
 use crate::tcp_protocol::runnables_map::RunnablesMap;
 use crate::tcp_protocol::RawCommandTwo;
+use crate::time_expiration::clock::{Clock, SystemClock};
 use std::collections::HashSet;
 use std::net::Ipv4Addr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::SystemTime;
 
 use std::net::SocketAddrV4;
 
+/// Next id to be assigned to a new client. Ids are never reused.
+static NEXT_CLIENT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Commands that a client can execute whatever its status is.
+const ALLOWED_IN_ANY_STATUS: [&str; 2] = ["quit", "reset"];
+
 /// Contains the atributes of one client.
 /// Its behaviour depends on the client status.
 pub struct ClientFields {
@@ -22,21 +31,145 @@ pub struct ClientFields {
     status: Status,
     subscriptions: HashSet<String>,
     pub address: SocketAddrV4,
+    id: u64,
+    name: Option<String>,
+    created: SystemTime,
+    last_interaction: SystemTime,
+    last_command: Option<String>,
+    commands_processed: u64,
+    no_evict: bool,
+    clock: Arc<dyn Clock>,
 }
 
 impl ClientFields {
-    /// Return a new instance of the Client Fields
+    /// Return a new instance of the Client Fields, whose age and idle
+    /// time are measured with the given [Clock].
     ///
     /// # Return value
     /// [ClientFields]
     ///
-    pub fn new(address: SocketAddrV4) -> ClientFields {
+    pub fn new(address: SocketAddrV4, clock: Arc<dyn Clock>) -> ClientFields {
+        let now = clock.now();
         ClientFields {
             map: Some(RunnablesMap::<Arc<Mutex<ClientFields>>>::executor()),
             status: Status::Executor,
             subscriptions: HashSet::new(),
             address,
+            id: NEXT_CLIENT_ID.fetch_add(1, Ordering::SeqCst),
+            name: None,
+            created: now,
+            last_interaction: now,
+            last_command: None,
+            commands_processed: 0,
+            no_evict: false,
+            clock,
+        }
+    }
+
+    /// Returns the unique id of the client.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the name of the client, if one was set with CLIENT SETNAME.
+    pub fn name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    /// Sets the name of the client. An empty name removes the current one.
+    pub fn set_name(&mut self, name: String) {
+        if name.is_empty() {
+            self.name = None;
+        } else {
+            self.name = Some(name);
+        }
+    }
+
+    /// Turns on or off the no-evict mode of the client.
+    pub fn set_no_evict(&mut self, no_evict: bool) {
+        self.no_evict = no_evict;
+    }
+
+    /// Registers that the client has just sent the given command,
+    /// updating its idle time and its counters.
+    pub fn register_command(&mut self, command: &str) {
+        self.last_interaction = self.clock.now();
+        self.last_command = Some(command.to_lowercase());
+        self.commands_processed += 1;
+    }
+
+    /// Returns the number of commands sent by the client.
+    pub fn commands_processed(&self) -> u64 {
+        self.commands_processed
+    }
+
+    /// Returns the number of seconds since the client connected.
+    pub fn age(&self) -> u64 {
+        self.seconds_since(self.created)
+    }
+
+    /// Returns the number of seconds since the last command of the client.
+    pub fn idle(&self) -> u64 {
+        self.seconds_since(self.last_interaction)
+    }
+
+    fn seconds_since(&self, time: SystemTime) -> u64 {
+        self.clock
+            .now()
+            .duration_since(time)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// Returns the user the client is authenticated as.
+    pub fn user(&self) -> String {
+        String::from("default")
+    }
+
+    /// Returns the type of the client, as used by CLIENT LIST and CLIENT KILL:
+    /// *pubsub* for subscribers and *normal* for any other one.
+    pub fn client_type(&self) -> String {
+        match self.status {
+            Status::Subscriber => String::from("pubsub"),
+            _ => String::from("normal"),
+        }
+    }
+
+    /// Returns the flags of the client in the CLIENT LIST format.
+    ///
+    /// * *N*: no specific flag set.
+    /// * *P*: the client is a subscriber.
+    /// * *O*: the client is a monitor.
+    /// * *e*: the client is excluded from the eviction.
+    pub fn flags(&self) -> String {
+        let mut flags = match self.status {
+            Status::Subscriber => String::from("P"),
+            Status::Monitor => String::from("O"),
+            _ => String::new(),
+        };
+        if self.no_evict {
+            flags.push('e');
+        }
+        if flags.is_empty() {
+            flags.push('N');
         }
+        flags
+    }
+
+    /// Returns a line describing the client in the format of CLIENT LIST and CLIENT INFO.
+    pub fn get_info(&self) -> String {
+        format!(
+            "id={} addr={} name={} age={} idle={} flags={} db=0 sub={} psub=0 cmd={} user={}",
+            self.id,
+            self.get_addr(),
+            self.name.as_ref().unwrap_or(&String::new()),
+            self.age(),
+            self.idle(),
+            self.flags(),
+            self.subscriptions.len(),
+            self.last_command.as_ref().unwrap_or(&String::from("NULL")),
+            self.user()
+        )
     }
 
     /// Returns the address of the client.
@@ -87,6 +220,7 @@ impl ClientFields {
     }
 
     /// Check if the client could execute a given command.
+    /// QUIT and RESET are allowed in every status.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
@@ -94,13 +228,17 @@ impl ClientFields {
     /// * Client is in monitor or dead status.
     ///
     pub fn is_allowed_to(&self, command: &str) -> Result<(), ErrorStruct> {
+        let command = command.to_lowercase();
+        if ALLOWED_IN_ANY_STATUS.contains(&command.as_str()) {
+            return Ok(());
+        }
         match self.status {
             Status::Executor => Ok(()),
             Status::Subscriber => self
                 .map
                 .as_ref()
                 .ok_or_else(|| ErrorStruct::from(broken_state()))?
-                .contains_key(command)
+                .contains_key(&command)
                 .then(|| ())
                 .ok_or_else(|| ErrorStruct::from(not_valid_pubsub())),
             _ => Err(ErrorStruct::from(not_valid_monitor())),
@@ -142,7 +280,7 @@ impl ClientFields {
             self.map
                 .as_ref()
                 .ok_or_else(|| ErrorStruct::from(broken_state()))?
-                .get(command.get(0).unwrap()),
+                .get(&command[0].to_lowercase()),
         )
         .ok_or_else(|| ErrorStruct::from(not_valid_pubsub()))
     }
@@ -152,7 +290,7 @@ impl ClientFields {
             self.map
                 .as_ref()
                 .ok_or_else(|| ErrorStruct::from(broken_state()))?
-                .get(command.get(0).unwrap()),
+                .get(&command[0].to_lowercase()),
         )
         .ok_or_else(|| ErrorStruct::from(not_valid_executor()))
     }
@@ -244,6 +382,20 @@ impl ClientFields {
         self.subscriptions.len() as isize
     }
 
+    /// Leaves the client as it was when it connected, as requested by RESET:
+    /// it goes back to [Status::Executor], and its subscriptions, name and
+    /// no-evict mode are removed.
+    ///
+    /// # Return value
+    /// [Vec]<[String]>: the channels the client was subscribed to.
+    pub fn reset(&mut self) -> Vec<String> {
+        let channels = self.subscriptions.drain().collect();
+        self.replace_status(Status::Executor);
+        self.name = None;
+        self.no_evict = false;
+        channels
+    }
+
     /// Return the details of the client atributes.
     ///
     /// # Return value
@@ -266,7 +418,10 @@ impl ClientFields {
 
 impl Default for ClientFields {
     fn default() -> ClientFields {
-        ClientFields::new(SocketAddrV4::new(Ipv4Addr::new(1, 0, 0, 1), 8080))
+        ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(1, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        )
     }
 }
 
@@ -274,17 +429,24 @@ impl Default for ClientFields {
 mod test_client_status {
 
     use super::*;
+    use crate::time_expiration::clock::ManualClock;
     use std::net::Ipv4Addr;
 
     #[test]
     fn test_01_initial_state() {
-        let status = ClientFields::new(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080));
+        let status = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        );
         assert_eq!(status.status(), Some(&Status::Executor));
     }
 
     #[test]
     fn test_02_add_subscriptions() {
-        let mut status = ClientFields::new(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080));
+        let mut status = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        );
         let added = status.add_subscriptions(vec!["telefe".to_string(), "trece".to_string()]);
         assert_eq!(added.unwrap(), 2);
         assert_eq!(status.status(), Some(&Status::Subscriber));
@@ -292,7 +454,10 @@ mod test_client_status {
 
     #[test]
     fn test_03_remove_not_all_subscriptions() {
-        let mut status = ClientFields::new(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080));
+        let mut status = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        );
         let added = status.add_subscriptions(vec![
             "telefe".to_string(),
             "trece".to_string(),
@@ -309,7 +474,10 @@ mod test_client_status {
 
     #[test]
     fn test_04_remove_all_subscriptions() {
-        let mut status = ClientFields::new(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080));
+        let mut status = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        );
         let added = status.add_subscriptions(vec![
             "telefe".to_string(),
             "trece".to_string(),
@@ -329,7 +497,10 @@ mod test_client_status {
 
     #[test]
     fn test_05_remove_all_subscriptions_by_default_empty_vec() {
-        let mut status = ClientFields::new(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080));
+        let mut status = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        );
         let added = status.add_subscriptions(vec![
             "telefe".to_string(),
             "trece".to_string(),
@@ -342,4 +513,110 @@ mod test_client_status {
         assert_eq!(removed.unwrap(), 0);
         assert_eq!(status.status(), Some(&Status::Executor));
     }
+
+    #[test]
+    fn test_06_clients_have_different_ids() {
+        let first = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        );
+        let second = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8081),
+            Arc::new(SystemClock),
+        );
+        assert!(first.id() < second.id());
+    }
+
+    #[test]
+    fn test_07_info_of_a_new_client() {
+        let status = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        );
+        let info = status.get_info();
+        assert!(info.starts_with(&format!("id={} addr=127.0.0.1:8080 name= ", status.id())));
+        assert!(info.contains(" flags=N db=0 sub=0 psub=0 cmd=NULL user=default"));
+    }
+
+    #[test]
+    fn test_08_info_after_setname_and_commands() {
+        let mut status = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        );
+        status.set_name("martina".to_string());
+        status.register_command("GET");
+        status.register_command("SET");
+        status
+            .add_subscriptions(vec!["telefe".to_string()])
+            .unwrap();
+        status.set_no_evict(true);
+
+        let info = status.get_info();
+        assert!(info.contains(" name=martina "));
+        assert!(info.contains(" flags=Pe "));
+        assert!(info.contains(" sub=1 "));
+        assert!(info.contains(" cmd=set "));
+        assert_eq!(status.commands_processed(), 2);
+        assert_eq!(status.client_type(), "pubsub");
+    }
+
+    #[test]
+    fn test_09_empty_name_removes_the_name() {
+        let mut status = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            Arc::new(SystemClock),
+        );
+        status.set_name("martina".to_string());
+        status.set_name(String::new());
+        assert_eq!(status.name(), None);
+    }
+
+    #[test]
+    fn test_10_reset_goes_back_to_executor() {
+        let mut status = ClientFields::default();
+        status.set_name("martina".to_string());
+        status
+            .add_subscriptions(vec!["telefe".to_string(), "trece".to_string()])
+            .unwrap();
+        let mut channels = status.reset();
+        channels.sort();
+        assert_eq!(channels, vec!["telefe".to_string(), "trece".to_string()]);
+        assert_eq!(status.status(), Some(&Status::Executor));
+        assert!(!status.is_subscripted_to("telefe"));
+        assert_eq!(status.name(), None);
+    }
+
+    #[test]
+    fn test_11_quit_and_reset_are_allowed_in_every_status() {
+        let mut status = ClientFields::default();
+        status.replace_status(Status::Monitor);
+        assert!(status.is_allowed_to("get").is_err());
+        assert!(status.is_allowed_to("QUIT").is_ok());
+        assert!(status.is_allowed_to("reset").is_ok());
+
+        let mut status = ClientFields::default();
+        status
+            .add_subscriptions(vec!["telefe".to_string()])
+            .unwrap();
+        assert!(status.is_allowed_to("get").is_err());
+        assert!(status.is_allowed_to("PING").is_ok());
+        assert!(status.is_allowed_to("Reset").is_ok());
+    }
+
+    #[test]
+    fn test_12_age_and_idle_are_measured_with_the_clock() {
+        let clock = Arc::new(ManualClock::default());
+        let mut status = ClientFields::new(
+            SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080),
+            clock.clone(),
+        );
+        clock.advance_secs(30);
+        status.register_command("PING");
+        clock.advance_secs(12);
+
+        assert_eq!(status.age(), 42);
+        assert_eq!(status.idle(), 12);
+        assert!(status.get_info().contains(" age=42 idle=12 "));
+    }
 }
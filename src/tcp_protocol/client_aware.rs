@@ -0,0 +1,13 @@
+use std::sync::{Arc, Mutex};
+
+use crate::tcp_protocol::client_atributes::client_fields::ClientFields;
+
+/// This trait is implemented by the structures on which a
+/// [CommandSubDelegator](crate::tcp_protocol::command_subdelegator::CommandSubDelegator)
+/// executes commands. Before running each command, the sub delegator
+/// tells the structure which client has sent it.
+pub trait ClientAware {
+    /// Receives the fields of the client whose command is about to run.
+    /// By default, the client is ignored.
+    fn set_current_client(&mut self, _client: Arc<Mutex<ClientFields>>) {}
+}
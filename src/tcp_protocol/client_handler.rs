@@ -1,20 +1,32 @@
 use crate::tcp_protocol::close_thread;
 use crate::{communication::log_messages::LogMessage, native_types::RError};
 use std::{
-    io::{BufRead, BufReader, Lines, Write},
+    io::{BufRead, BufReader, ErrorKind, Lines, Write},
     net::{Shutdown, SocketAddr, SocketAddrV4, TcpStream},
-    sync::{mpsc, Arc, Mutex},
+    sync::{mpsc, mpsc::RecvTimeoutError, Arc, Mutex},
     thread,
+    time::Duration,
 };
 use std::{sync::mpsc::Sender, thread::JoinHandle};
 
 use crate::joinable::Joinable;
 use crate::messages::redis_messages;
 use crate::native_types::error_severity::ErrorSeverity;
-use crate::native_types::{redis_type::encode_netcat_input, ErrorStruct, RArray, RedisType};
+use crate::native_types::{
+    bulk_string::wire_bytes, redis_type::encode_netcat_input, ErrorStruct, RArray, RedisType,
+};
 use crate::tcp_protocol::client_atributes::status::Status;
+use crate::time_expiration::clock::Clock;
+
+use super::{
+    client_atributes::client_fields::ClientFields, client_pause::ClientPause, notifier::Notifier,
+    Response,
+};
 
-use super::{client_atributes::client_fields::ClientFields, notifier::Notifier, Response};
+/// How often a client waiting for the response of a blocking command checks its socket.
+const PEER_CHECK_PERIOD: Duration = Duration::from_millis(100);
+/// How long the socket is peeked when checking if the client closed it.
+const PEER_CHECK_WAIT: Duration = Duration::from_millis(1);
 
 /// Structure in charge of processing what is received in the socket [TcpStream] of the client connected to the server,
 /// with the help of [Notifier] the different tasks requested by the client will
@@ -31,7 +43,9 @@ pub struct ClientHandler {
 impl ClientHandler {
     /// Creates the structure in charge of processing what is received in
     /// the socket [TcpStream] of the client connected to the server.
-    /// You also need the [Notifier] to communicate with the main structures.
+    /// You also need the [Notifier] to communicate with the main structures,
+    /// and the [ClientPause] of the server to know when to suspend the commands.
+    /// The age and idle time of the client are measured with the [Clock].
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
@@ -41,6 +55,8 @@ impl ClientHandler {
     pub fn new(
         stream_received: TcpStream,
         notifier: Notifier,
+        client_pause: ClientPause,
+        clock: Arc<dyn Clock>,
     ) -> Result<ClientHandler, ErrorStruct> {
         let c_notifier = notifier.clone();
         let in_stream = stream_received
@@ -50,7 +66,7 @@ impl ClientHandler {
             .try_clone()
             .map_err(|_| ErrorStruct::from(redis_messages::clone_socket()))?;
         let address = get_peer(&stream_received)?;
-        let fields = ClientFields::new(address);
+        let fields = ClientFields::new(address, clock);
         let shared_fields = Arc::new(Mutex::new(fields));
         let c_shared_fields = Arc::clone(&shared_fields);
 
@@ -60,7 +76,13 @@ impl ClientHandler {
         ) = mpsc::channel();
         let response_snd_clone = response_snd.clone();
         let in_thread = thread::spawn(move || {
-            read_socket(in_stream, c_shared_fields, c_notifier, response_snd_clone)
+            read_socket(
+                in_stream,
+                c_shared_fields,
+                c_notifier,
+                client_pause,
+                response_snd_clone,
+            )
         });
 
         let out_thread = thread::spawn(move || write_socket(out_stream, response_recv));
@@ -109,6 +131,47 @@ impl ClientHandler {
         send_response(response, &self.response_snd)
     }
 
+    /// Returns the id of the client, or [None] if its fields are poisoned.
+    pub fn get_id(&self) -> Option<u64> {
+        self.fields.lock().ok().map(|fields| fields.id())
+    }
+
+    /// Changes the time without receiving commands after which the client is
+    /// disconnected, [None] to never disconnect it. A read already waiting keeps
+    /// the previous time, so it applies from the next command of the client.
+    pub fn set_timeout(&self, timeout: Option<Duration>) {
+        let _ = self.stream.set_read_timeout(timeout);
+    }
+
+    /// Returns true if the fields of the client satisfy the given filter.
+    pub fn matches<F>(&self, filter: F) -> bool
+    where
+        F: Fn(&ClientFields) -> bool,
+    {
+        match self.fields.lock() {
+            Ok(fields_guard) => filter(&fields_guard),
+            Err(_) => false,
+        }
+    }
+
+    /// Get a [String] with the information of the client in the CLIENT LIST format.
+    pub fn get_info(&self) -> String {
+        match self.fields.lock() {
+            Ok(fields_guard) => fields_guard.get_info(),
+            Err(_) => String::from("(nil)"),
+        }
+    }
+
+    /// Closes the connection with the client, as requested by CLIENT KILL.
+    /// The client is marked with [Status::Dead] so it is removed from the
+    /// [ClientList](crate::tcp_protocol::client_list::ClientList).
+    pub fn kill(&self) {
+        let _ = self.stream.shutdown(Shutdown::Both);
+        if let Ok(mut fields_guard) = self.fields.lock() {
+            fields_guard.replace_status(Status::Dead);
+        }
+    }
+
     /// Get a [String] with the detailed information of a client.
     pub fn get_detail(&self) -> String {
         match self.fields.lock() {
@@ -123,6 +186,7 @@ impl ClientHandler {
 /// The string will be sent to the client through the [TcpStream] socket.
 ///
 /// When [None] is received, it is an indication to stop writing to the socket and close the client.
+/// The responses received before are written first, so a QUIT reply reaches the client.
 ///
 /// # Error
 /// Return an [ErrorStruct] if:
@@ -133,14 +197,15 @@ fn write_socket(
     response_recv: mpsc::Receiver<Option<String>>,
 ) -> Result<(), ErrorStruct> {
     for packed_response in response_recv.iter() {
-        if let Some(response) = packed_response {
+        if let Some(mut response) = packed_response {
+            // A single write, so the line break does not wait for the
+            // acknowledgement of the response
+            response.push('\n');
             stream
-                .write_all(response.as_bytes())
-                .map_err(|_| ErrorStruct::from(redis_messages::closed_socket()))?;
-            stream
-                .write("\n".as_bytes())
+                .write_all(&wire_bytes(&response))
                 .map_err(|_| ErrorStruct::from(redis_messages::closed_socket()))?;
         } else {
+            let _ = stream.shutdown(Shutdown::Both);
             return Ok(());
         }
     }
@@ -149,7 +214,8 @@ fn write_socket(
 }
 
 /// Function in charge of delegating the function of reading the 'socket' [TcpStream].
-/// In case the client has been disconnected, its [Status] will be replaced by [Status::Dead]
+/// In case the client has been disconnected or has quit, its [Status] will be replaced by [Status::Dead],
+/// the socket will be closed after writing the pending responses
 /// and the [LogCenter](crate::logs::log_center::LogCenter) will be notified of the disconnection.
 ///
 /// # Error
@@ -162,6 +228,7 @@ fn read_socket(
     stream: TcpStream,
     c_shared_fields: Arc<Mutex<ClientFields>>,
     notifier: Notifier,
+    client_pause: ClientPause,
     response_snd: mpsc::Sender<Option<String>>,
 ) -> Result<(), ErrorStruct> {
     let buf_reader_stream = BufReader::new(
@@ -172,9 +239,11 @@ fn read_socket(
 
     let status_while = listen_while_client(
         buf_reader_stream.lines(),
+        &stream,
         &c_shared_fields,
         &notifier,
-        response_snd,
+        &client_pause,
+        response_snd.clone(),
     )
     .map_err(|error| {
         if error.severity().eq(&Some(&ErrorSeverity::ShutdownServer)) {
@@ -192,6 +261,7 @@ fn read_socket(
             ))
         })?
         .replace_status(Status::Dead);
+    let _ = response_snd.send(None);
     notifier.send_log(LogMessage::client_off(
         get_peer(&stream)
             .map(|x| x.to_string())
@@ -214,8 +284,10 @@ fn read_socket(
 /// * An error that justifies causing a forced shutdown of the server or closing a client.
 fn listen_while_client(
     mut lines: Lines<BufReader<TcpStream>>,
+    stream: &TcpStream,
     c_shared_fields: &Arc<Mutex<ClientFields>>,
     notifier: &Notifier,
+    client_pause: &ClientPause,
     response_snd: mpsc::Sender<Option<String>>,
 ) -> Result<(), ErrorStruct> {
     let mut response_critical;
@@ -226,13 +298,21 @@ fn listen_while_client(
                     response_critical = process_command_redis(
                         input,
                         &mut lines,
+                        stream,
                         c_shared_fields,
                         notifier,
+                        client_pause,
                         &response_snd,
                     );
                 } else {
-                    response_critical =
-                        process_other(input, c_shared_fields, notifier, &response_snd);
+                    response_critical = process_other(
+                        input,
+                        stream,
+                        c_shared_fields,
+                        notifier,
+                        client_pause,
+                        &response_snd,
+                    );
                 }
             }
             Err(err) => match err.kind() {
@@ -261,24 +341,51 @@ fn listen_while_client(
                 }
             }
         }
+
+        if client_is_dead(c_shared_fields)? {
+            break;
+        }
     }
     Ok(())
 }
 
+/// Returns true if the client has been marked with [Status::Dead] by a
+/// command such as QUIT, so no more commands are read.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * [ClientFields] is poisoned.
+fn client_is_dead(c_shared_fields: &Arc<Mutex<ClientFields>>) -> Result<bool, ErrorStruct> {
+    Ok(c_shared_fields
+        .lock()
+        .map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "client_status",
+                ErrorSeverity::CloseClient,
+            ))
+        })?
+        .is_dead())
+}
+
 /// Function in charge of delegating the processing of a command received correctly with the **redis protocol**.
 fn process_command_redis(
     mut input: String,
     mut lines_buffer_reader: &mut Lines<BufReader<TcpStream>>,
+    stream: &TcpStream,
     client_status: &Arc<Mutex<ClientFields>>,
     notifier: &Notifier,
+    client_pause: &ClientPause,
     response_sender: &Sender<Option<String>>,
 ) -> Result<(), ErrorStruct> {
     input.remove(0);
     process_command_general(
         input,
         &mut lines_buffer_reader,
+        stream,
         client_status,
         notifier,
+        client_pause,
         response_sender,
     )
 }
@@ -292,8 +399,10 @@ fn process_command_redis(
 /// * If what is received in the socket does not comply with the redis protocol.
 fn process_other(
     input: String,
+    stream: &TcpStream,
     client_status: &Arc<Mutex<ClientFields>>,
     notifier: &Notifier,
+    client_pause: &ClientPause,
     response_sender: &Sender<Option<String>>,
 ) -> Result<(), ErrorStruct> {
     let mut input_encoded = encode_netcat_input(input)?;
@@ -306,14 +415,17 @@ fn process_other(
     process_command_general(
         first_lecture,
         &mut lines,
+        stream,
         client_status,
         notifier,
+        client_pause,
         response_sender,
     )
 }
 
 /// Function in charge of decoding what is received in the socket and then delegating it as <[Vec]<[String]>> in case the [Status] of the client allows it.
 /// All that received command is always received in [RArray] format, so it is decoded as a redis array.
+/// While a CLIENT PAUSE suspends the command, the thread of the client waits before delegating it.
 ///
 /// # Error
 /// Return an [ErrorStruct] if:
@@ -323,26 +435,38 @@ fn process_other(
 fn process_command_general<G>(
     first_lecture: String,
     lines_buffer_reader: &mut Lines<G>,
+    stream: &TcpStream,
     client_status: &Arc<Mutex<ClientFields>>,
     notifier: &Notifier,
+    client_pause: &ClientPause,
     response_sender: &Sender<Option<String>>,
 ) -> Result<(), ErrorStruct>
 where
     G: BufRead,
 {
     let command_vec = RArray::decode(first_lecture, lines_buffer_reader)?;
-    let result = client_status
-        .lock()
-        .map_err(|_| {
+    let result = {
+        let mut fields = client_status.lock().map_err(|_| {
             ErrorStruct::from(redis_messages::poisoned_lock(
                 "client_status",
                 ErrorSeverity::CloseClient,
             ))
-        })?
-        .is_allowed_to(&command_vec[0]);
+        })?;
+        fields.register_command(&command_vec[0]);
+        fields.is_allowed_to(&command_vec[0])
+    };
 
     match result {
-        Ok(()) => delegate_command(command_vec, client_status, notifier, response_sender),
+        Ok(()) => {
+            client_pause.wait_if_paused(&command_vec[0])?;
+            delegate_command(
+                command_vec,
+                stream,
+                client_status,
+                notifier,
+                response_sender,
+            )
+        }
         Err(error) => send_response(RError::encode(error), response_sender),
     }
 }
@@ -351,6 +475,9 @@ where
 ///
 /// Depending on the type of response, the client will be communicated or the client will be closed.
 /// They could even cause a forced shutdown of the server.
+/// While a blocking command waits for its response, the socket is checked every
+/// [PEER_CHECK_PERIOD]; if the client closed it, the client is marked as [Status::Dead]
+/// so the command subdelegator can discard the blocked command.
 ///
 /// # Error
 /// Return an [ErrorStruct] if:
@@ -359,6 +486,7 @@ where
 /// * [ClientFields] is poisoned.
 fn delegate_command(
     command_received: Vec<String>,
+    stream: &TcpStream,
     client_fields: &Arc<Mutex<ClientFields>>,
     notifier: &Notifier,
     response_sender: &Sender<Option<String>>,
@@ -366,7 +494,26 @@ fn delegate_command(
     let command_received_initial = command_received.clone();
     let (sender, receiver): (mpsc::Sender<Response>, mpsc::Receiver<Response>) = mpsc::channel();
     notifier.send_command_delegator(Some((command_received, sender, Arc::clone(client_fields))))?;
-    for response in receiver.iter() {
+    loop {
+        let response = match receiver.recv_timeout(PEER_CHECK_PERIOD) {
+            Ok(response) => response,
+            Err(RecvTimeoutError::Timeout) => {
+                if peer_closed(stream) {
+                    client_fields
+                        .lock()
+                        .map_err(|_| {
+                            ErrorStruct::from(redis_messages::poisoned_lock(
+                                "client_status",
+                                ErrorSeverity::CloseClient,
+                            ))
+                        })?
+                        .replace_status(Status::Dead);
+                    return Ok(());
+                }
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
         match response {
             Ok(good_string) => {
                 send_response(good_string, response_sender)?;
@@ -392,6 +539,24 @@ fn delegate_command(
     Ok(())
 }
 
+/// Returns true if the client closed its end of the [TcpStream] (or the connection failed).
+/// The check peeks at the socket, so data sent by the client is not consumed.
+fn peer_closed(stream: &TcpStream) -> bool {
+    let read_timeout = stream.read_timeout().ok().flatten();
+    if stream.set_read_timeout(Some(PEER_CHECK_WAIT)).is_err() {
+        return false;
+    }
+    let closed = match stream.peek(&mut [0; 1]) {
+        Ok(read) => read == 0,
+        Err(error) => !matches!(
+            error.kind(),
+            ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted
+        ),
+    };
+    let _ = stream.set_read_timeout(read_timeout);
+    closed
+}
+
 /// Gets the address of a [TcpStream].
 ///
 /// # Error
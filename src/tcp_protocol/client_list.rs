@@ -6,10 +6,13 @@ use crate::{
 use crate::{joinable::Joinable, native_types::ErrorStruct};
 use std::collections::HashMap;
 use std::sync::mpsc::{SendError, Sender};
-use std::time::SystemTime;
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::communication::log_messages::LogMessage;
+use crate::tcp_protocol::client_atributes::client_fields::ClientFields;
 use crate::tcp_protocol::client_handler::ClientHandler;
+use crate::time_expiration::clock::{Clock, SystemClock};
 
 /// This structure contain a list with all the
 /// client handlers that are active in the server.
@@ -17,6 +20,8 @@ pub struct ClientList {
     list: Vec<Option<ClientHandler>>,
     channel_register: HashMap<String, usize>,
     log_channel: Sender<Option<LogMessage>>,
+    clock: Arc<dyn Clock>,
+    timeout: Option<Duration>,
 }
 
 impl Joinable<()> for ClientList {
@@ -45,15 +50,43 @@ impl ClientList {
             list: Vec::new(),
             channel_register: HashMap::new(),
             log_channel,
+            clock: Arc::new(SystemClock),
+            timeout: None,
         }
     }
 
+    /// Changes the time without receiving commands after which the clients
+    /// are disconnected, [None] to never disconnect them. See the `timeout`
+    /// parameter.
+    ///
+    /// The connected clients already idle for longer are closed at once, and
+    /// the others get the new time.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+        if let Some(timeout) = timeout {
+            self.kill_clients(|fields| fields.idle() >= timeout.as_secs());
+        }
+        for client in self.list.iter().flatten() {
+            client.set_timeout(timeout);
+        }
+    }
+
+    /// Client List Clock setter, which gives the time of the notifications
+    /// to the monitors and measures the idle time of the clients.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Returns the [Clock] of the list, for the new clients.
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        Arc::clone(&self.clock)
+    }
+
     /// Push info about the client list in a given compiler.
     pub fn info(&mut self, info_compiler: &mut Vec<String>) {
         self.drop_clients_dead();
         info_compiler.push(clients_connected(self.list.len()));
         info_compiler.push(active_channels(self.channel_register.len()));
-        info_compiler.push(String::new())
     }
 
     /// Drops all the clients with a dead status.
@@ -69,6 +102,11 @@ impl ClientList {
         self.list.retain(|client| client.is_some());
     }
 
+    /// Time without receiving commands after which the clients are disconnected.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
     /// Insert a new client handler.
     pub fn insert(&mut self, new_client: ClientHandler) {
         self.drop_clients_dead();
@@ -76,6 +114,75 @@ impl ClientList {
         self.print_detail_clients().unwrap();
     }
 
+    /// Returns the CLIENT LIST line of each connected client whose
+    /// fields satisfy the given filter.
+    pub fn clients_info<F>(&mut self, filter: F) -> Vec<String>
+    where
+        F: Fn(&ClientFields) -> bool,
+    {
+        self.drop_clients_dead();
+        self.list
+            .iter()
+            .flatten()
+            .filter(|client| client.matches(&filter))
+            .map(|client| client.get_info())
+            .collect()
+    }
+
+    /// Closes the connection of each client whose fields satisfy the given filter.
+    ///
+    /// # Return value
+    /// [usize]: The number of clients that have been killed.
+    ///
+    pub fn kill_clients<F>(&mut self, filter: F) -> usize
+    where
+        F: Fn(&ClientFields) -> bool,
+    {
+        self.drop_clients_dead();
+        let mut killed = 0;
+        for client in self.list.iter().flatten() {
+            if client.matches(&filter) {
+                client.kill();
+                killed += 1;
+            }
+        }
+        self.drop_clients_dead();
+        killed
+    }
+
+    /// Writes the message to the client with the given id. If a channel is
+    /// received, the message is only written if the client is subscribed to it.
+    ///
+    /// # Return value
+    /// [bool]: false if there is no connected client with the given id.
+    ///
+    pub fn write_to_client(&mut self, id: u64, message: String, channel: Option<&str>) -> bool {
+        self.drop_clients_dead();
+        match self
+            .list
+            .iter()
+            .flatten()
+            .find(|client| client.get_id() == Some(id))
+        {
+            Some(client) => {
+                if channel.is_none_or(|channel| client.is_subscripted_to(channel)) {
+                    let _ = client.write_stream(message);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns true if there is a connected client with the given id.
+    pub fn contains_client(&mut self, id: u64) -> bool {
+        self.drop_clients_dead();
+        self.list
+            .iter()
+            .flatten()
+            .any(|client| client.get_id() == Some(id))
+    }
+
     /// Send the clients details to the Log Center.
     ///
     /// # Error
@@ -101,17 +208,12 @@ impl ClientList {
     /// Notify the monitors about a succesfull command execution
     ///
     pub fn notify_monitors(&mut self, addr: String, notification: Vec<String>) {
+        let time = self.clock.unix_secs();
         self.list
             .iter_mut()
             .map(|x| x.as_ref().unwrap())
             .filter(|x| x.is_monitor_notificable())
             .for_each(|client| {
-                let time = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-                    Ok(n) => n.as_secs(),
-                    Err(_) => {
-                        panic!("SystemTime before UNIX EPOCH! Are we travelling to the past?")
-                    }
-                };
                 let message_to_notify = format!("At {}: [{}] {:?}\r\n", time, &addr, notification);
                 let _ = client.write_stream(RBulkString::encode(message_to_notify));
             });
@@ -198,7 +300,6 @@ impl ClientList {
 }
 
 #[cfg(test)]
-
 mod test_client_list {
     use super::*;
     use std::sync::mpsc;
@@ -0,0 +1,247 @@
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::ErrorStruct;
+use crate::tcp_protocol::command_table::CommandTable;
+
+/// Commands without the `write` flag which are also suspended by a
+/// CLIENT PAUSE in [PauseMode::Write], because they are propagated as
+/// Redis does with its *may-replicate* commands.
+const MAY_REPLICATE_COMMANDS: [&str; 1] = ["publish"];
+
+/// Kind of commands suspended by a CLIENT PAUSE.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PauseMode {
+    /// Only the commands that can modify the dataset are suspended.
+    Write,
+    /// Every command sent by the clients is suspended.
+    All,
+}
+
+/// Pause in progress: the mode and the instant when it finishes.
+struct Pause {
+    mode: PauseMode,
+    until: SystemTime,
+}
+
+/// Shared state of CLIENT PAUSE and CLIENT UNPAUSE.
+///
+/// Each [ClientHandler](crate::tcp_protocol::client_handler::ClientHandler) asks
+/// the structure before delegating a command, and waits in its own thread while
+/// the command is suspended. The CLIENT command is never suspended, so a paused
+/// server can always be resumed with CLIENT UNPAUSE.
+///
+/// The commands suspended in [PauseMode::Write] are the ones with the `write`
+/// flag in the [CommandTable].
+#[derive(Clone)]
+pub struct ClientPause {
+    state: Arc<(Mutex<Option<Pause>>, Condvar)>,
+    commands: Arc<CommandTable>,
+}
+
+impl Default for ClientPause {
+    fn default() -> Self {
+        ClientPause::new(Arc::new(CommandTable::default()))
+    }
+}
+
+impl ClientPause {
+    /// Creates the structure without any pause in progress, reading the
+    /// flags of the commands from the given [CommandTable].
+    pub fn new(commands: Arc<CommandTable>) -> Self {
+        ClientPause {
+            state: Arc::new((Mutex::new(None), Condvar::new())),
+            commands,
+        }
+    }
+
+    /// Suspends the commands of the clients for the given duration.
+    /// If a pause is already in progress, the longer one and the most
+    /// restrictive mode are kept.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The lock of the pause state is poisoned.
+    pub fn pause(&self, mode: PauseMode, timeout: Duration) -> Result<(), ErrorStruct> {
+        let until = SystemTime::now() + timeout;
+        let mut pause = self.lock()?;
+        let (mode, until) = match pause.take() {
+            Some(old) if old.until > SystemTime::now() => (
+                if old.mode == PauseMode::All {
+                    PauseMode::All
+                } else {
+                    mode
+                },
+                if old.until > until { old.until } else { until },
+            ),
+            _ => (mode, until),
+        };
+        *pause = Some(Pause { mode, until });
+        Ok(())
+    }
+
+    /// Resumes the commands suspended by a previous pause.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The lock of the pause state is poisoned.
+    pub fn unpause(&self) -> Result<(), ErrorStruct> {
+        self.lock()?.take();
+        self.state.1.notify_all();
+        Ok(())
+    }
+
+    /// Returns true if the given command would be suspended right now.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The lock of the pause state is poisoned.
+    pub fn is_paused(&self, command: &str) -> Result<bool, ErrorStruct> {
+        let pause = self.lock()?;
+        Ok(remaining(&pause, &self.commands, command).is_some())
+    }
+
+    /// Blocks the current thread while the given command is suspended.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The lock of the pause state is poisoned.
+    pub fn wait_if_paused(&self, command: &str) -> Result<(), ErrorStruct> {
+        let mut pause = self.lock()?;
+        while let Some(left) = remaining(&pause, &self.commands, command) {
+            pause = self
+                .state
+                .1
+                .wait_timeout(pause, left)
+                .map_err(|_| poisoned())?
+                .0;
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Option<Pause>>, ErrorStruct> {
+        self.state.0.lock().map_err(|_| poisoned())
+    }
+}
+
+/// Returns how long the given command still has to wait, if it is suspended.
+fn remaining(pause: &Option<Pause>, commands: &CommandTable, command: &str) -> Option<Duration> {
+    let command = command.to_lowercase();
+    if command == "client" {
+        return None;
+    }
+    pause.as_ref().and_then(|pause| {
+        let suspended = match pause.mode {
+            PauseMode::All => true,
+            PauseMode::Write => {
+                MAY_REPLICATE_COMMANDS.contains(&command.as_str())
+                    || commands.get(&command).is_some_and(|spec| spec.is_write())
+            }
+        };
+        if suspended {
+            pause.until.duration_since(SystemTime::now()).ok()
+        } else {
+            None
+        }
+    })
+}
+
+fn poisoned() -> ErrorStruct {
+    ErrorStruct::from(redis_messages::poisoned_lock(
+        "client pause",
+        ErrorSeverity::ShutdownServer,
+    ))
+}
+
+#[cfg(test)]
+mod test_client_pause {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_01_nothing_is_paused_by_default() {
+        let pause = ClientPause::default();
+        assert!(!pause.is_paused("set").unwrap());
+        assert!(!pause.is_paused("get").unwrap());
+    }
+
+    #[test]
+    fn test_02_write_pause_only_suspends_write_commands() {
+        let pause = ClientPause::default();
+        pause
+            .pause(PauseMode::Write, Duration::from_secs(10))
+            .unwrap();
+        assert!(pause.is_paused("set").unwrap());
+        assert!(pause.is_paused("LPUSH").unwrap());
+        assert!(!pause.is_paused("get").unwrap());
+        assert!(!pause.is_paused("client").unwrap());
+    }
+
+    #[test]
+    fn test_07_write_pause_follows_the_write_flag_of_the_command_table() {
+        let pause = ClientPause::default();
+        pause
+            .pause(PauseMode::Write, Duration::from_secs(10))
+            .unwrap();
+        for command in [
+            "xadd", "setbit", "incr", "pfadd", "geoadd", "unlink", "publish",
+        ] {
+            assert!(pause.is_paused(command).unwrap(), "{}", command);
+        }
+        for command in ["xrange", "getbit", "pfcount", "geopos", "unknown"] {
+            assert!(!pause.is_paused(command).unwrap(), "{}", command);
+        }
+    }
+
+    #[test]
+    fn test_03_all_pause_suspends_every_command_but_client() {
+        let pause = ClientPause::default();
+        pause
+            .pause(PauseMode::All, Duration::from_secs(10))
+            .unwrap();
+        assert!(pause.is_paused("set").unwrap());
+        assert!(pause.is_paused("get").unwrap());
+        assert!(!pause.is_paused("client").unwrap());
+    }
+
+    #[test]
+    fn test_04_unpause_resumes_the_commands() {
+        let pause = ClientPause::default();
+        pause
+            .pause(PauseMode::All, Duration::from_secs(10))
+            .unwrap();
+        pause.unpause().unwrap();
+        assert!(!pause.is_paused("set").unwrap());
+    }
+
+    #[test]
+    fn test_05_wait_if_paused_blocks_until_the_timeout() {
+        let pause = ClientPause::default();
+        pause
+            .pause(PauseMode::Write, Duration::from_millis(200))
+            .unwrap();
+        let start = Instant::now();
+        pause.wait_if_paused("set").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert!(!pause.is_paused("set").unwrap());
+    }
+
+    #[test]
+    fn test_06_a_shorter_pause_does_not_reduce_the_current_one() {
+        let pause = ClientPause::default();
+        pause
+            .pause(PauseMode::All, Duration::from_secs(10))
+            .unwrap();
+        pause
+            .pause(PauseMode::Write, Duration::from_millis(1))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(pause.is_paused("get").unwrap());
+    }
+}
@@ -0,0 +1,492 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::messages::redis_messages;
+use crate::native_types::error_severity::ErrorSeverity;
+use crate::native_types::{ErrorStruct, RArray, RBulkString, RedisType};
+use crate::tcp_protocol::client_list::ClientList;
+
+/// Channel where the invalidations are published for the clients
+/// that use REDIRECT.
+pub const INVALIDATE_CHANNEL: &str = "__redis__:invalidate";
+
+/// Options received by CLIENT TRACKING ON.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrackingOptions {
+    /// Id of the client that receives the invalidations.
+    pub redirect: Option<u64>,
+    /// Every write of a key with any of the prefixes is notified,
+    /// without remembering the keys read by the client.
+    pub bcast: bool,
+    /// Prefixes of the keys notified in BCAST mode. Empty means every key.
+    pub prefixes: Vec<String>,
+    /// Keys are only remembered after CLIENT CACHING yes.
+    pub optin: bool,
+    /// Keys are not remembered after CLIENT CACHING no.
+    pub optout: bool,
+    /// Writes done by the client itself are not notified to it.
+    pub noloop: bool,
+}
+
+/// A client with the tracking turned on.
+struct TrackedClient {
+    options: TrackingOptions,
+    /// Value set by CLIENT CACHING, for the next command of the client.
+    next_caching: Option<bool>,
+    /// Value of CLIENT CACHING for the command being executed.
+    caching: Option<bool>,
+}
+
+impl TrackedClient {
+    /// Returns true if the keys read by the client must be remembered.
+    fn remembers_keys(&self) -> bool {
+        if self.options.bcast {
+            false
+        } else if self.options.optin {
+            self.caching == Some(true)
+        } else if self.options.optout {
+            self.caching != Some(false)
+        } else {
+            true
+        }
+    }
+
+    /// Returns true if the write of the key must be notified to the client.
+    fn is_interested_in(&self, key: &str) -> bool {
+        self.options.bcast
+            && (self.options.prefixes.is_empty()
+                || self
+                    .options
+                    .prefixes
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix.as_str())))
+    }
+}
+
+#[derive(Default)]
+struct TrackingState {
+    clients: HashMap<u64, TrackedClient>,
+    /// Keys read by the clients in default mode, and the ids of those clients.
+    keys: HashMap<String, HashSet<u64>>,
+}
+
+/// Server side of the client-side caching.
+///
+/// The structure is shared by the [ServerRedisAttributes](crate::tcp_protocol::server_redis_attributes::ServerRedisAttributes),
+/// where CLIENT TRACKING turns the tracking on and off, and the
+/// [Database](crate::database::Database), which remembers the keys read by the
+/// clients and sends the invalidations when those keys are modified.
+///
+/// Invalidations are sent as RESP3 push messages to the client itself, or as
+/// messages of the [INVALIDATE_CHANNEL] to the client of the REDIRECT option.
+#[derive(Clone)]
+pub struct ClientTracking {
+    state: Arc<Mutex<TrackingState>>,
+    clients: Arc<Mutex<ClientList>>,
+}
+
+impl ClientTracking {
+    /// Creates the structure without any client tracking keys.
+    pub fn new(clients: Arc<Mutex<ClientList>>) -> Self {
+        ClientTracking {
+            state: Arc::new(Mutex::new(TrackingState::default())),
+            clients,
+        }
+    }
+
+    /// Turns on the tracking of the given client, replacing its previous options.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The client of the REDIRECT option is not connected.
+    /// * Any structure is poisoned.
+    pub fn enable(&self, id: u64, options: TrackingOptions) -> Result<(), ErrorStruct> {
+        if let Some(redirect) = options.redirect {
+            if redirect != id && !self.lock_clients()?.contains_client(redirect) {
+                return Err(ErrorStruct::from(
+                    redis_messages::redirect_client_not_found(),
+                ));
+            }
+        }
+        self.lock()?.clients.insert(
+            id,
+            TrackedClient {
+                options,
+                next_caching: None,
+                caching: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Turns off the tracking of the given client and forgets the keys it has read.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The tracking state is poisoned.
+    pub fn disable(&self, id: u64) -> Result<(), ErrorStruct> {
+        let mut state = self.lock()?;
+        forget_client(&mut state, id);
+        Ok(())
+    }
+
+    /// Returns the options of the client, or [None] if its tracking is off.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The tracking state is poisoned.
+    pub fn options(&self, id: u64) -> Result<Option<TrackingOptions>, ErrorStruct> {
+        Ok(self
+            .lock()?
+            .clients
+            .get(&id)
+            .map(|client| client.options.clone()))
+    }
+
+    /// Stores the value of CLIENT CACHING for the next command of the client.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The client is not tracking in OPTIN or OPTOUT mode.
+    /// * The tracking state is poisoned.
+    pub fn set_caching(&self, id: u64, caching: bool) -> Result<(), ErrorStruct> {
+        match self.lock()?.clients.get_mut(&id) {
+            Some(client) if client.options.optin || client.options.optout => {
+                client.next_caching = Some(caching);
+                Ok(())
+            }
+            _ => Err(ErrorStruct::from(redis_messages::caching_not_allowed())),
+        }
+    }
+
+    /// Marks the beginning of a command of the client in the [Database](crate::database::Database),
+    /// consuming the value of the last CLIENT CACHING.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The tracking state is poisoned.
+    pub fn start_command(&self, id: u64) -> Result<(), ErrorStruct> {
+        if let Some(client) = self.lock()?.clients.get_mut(&id) {
+            client.caching = client.next_caching.take();
+        }
+        Ok(())
+    }
+
+    /// Remembers that the client has read the key, if its tracking requires it.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The tracking state is poisoned.
+    pub fn remember(&self, id: u64, key: &str) -> Result<(), ErrorStruct> {
+        let mut state = self.lock()?;
+        if state
+            .clients
+            .get(&id)
+            .is_some_and(|client| client.remembers_keys())
+        {
+            state.keys.entry(key.to_string()).or_default().insert(id);
+        }
+        Ok(())
+    }
+
+    /// Notifies the clients interested in the modified keys. The keys are
+    /// forgotten, so a client is notified once until it reads them again.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Any structure is poisoned.
+    pub fn invalidate(&self, keys: &[String], writer: Option<u64>) -> Result<(), ErrorStruct> {
+        let mut pending: HashMap<u64, Vec<String>> = HashMap::new();
+        {
+            let mut state = self.lock()?;
+            for key in keys {
+                let mut ids = state.keys.remove(key).unwrap_or_default();
+                ids.extend(
+                    state
+                        .clients
+                        .iter()
+                        .filter(|(_, client)| client.is_interested_in(key))
+                        .map(|(id, _)| *id),
+                );
+                for id in ids {
+                    pending.entry(id).or_default().push(key.to_string());
+                }
+            }
+        }
+        self.send_invalidations(
+            pending
+                .into_iter()
+                .map(|(id, keys)| (id, Some(keys)))
+                .collect(),
+            writer,
+        )
+    }
+
+    /// Notifies every tracking client that all the keys have been modified,
+    /// as after a FLUSHDB.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * Any structure is poisoned.
+    pub fn invalidate_all(&self, writer: Option<u64>) -> Result<(), ErrorStruct> {
+        let ids: Vec<u64> = {
+            let mut state = self.lock()?;
+            state.keys.clear();
+            state.clients.keys().copied().collect()
+        };
+        self.send_invalidations(ids.into_iter().map(|id| (id, None)).collect(), writer)
+    }
+
+    /// Writes the invalidation messages to the clients. The tracking of
+    /// the clients that are no longer connected is turned off.
+    fn send_invalidations(
+        &self,
+        pending: Vec<(u64, Option<Vec<String>>)>,
+        writer: Option<u64>,
+    ) -> Result<(), ErrorStruct> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut deliveries = Vec::new();
+        {
+            let state = self.lock()?;
+            for (id, keys) in pending {
+                if let Some(client) = state.clients.get(&id) {
+                    if client.options.noloop && writer == Some(id) {
+                        continue;
+                    }
+                    deliveries.push((id, client.options.redirect, keys));
+                }
+            }
+        }
+
+        let mut disconnected = Vec::new();
+        {
+            let mut clients = self.lock_clients()?;
+            for (id, redirect, keys) in deliveries {
+                let delivered = match redirect {
+                    Some(redirect) => clients.write_to_client(
+                        redirect,
+                        encode_invalidate_message(keys),
+                        Some(INVALIDATE_CHANNEL),
+                    ),
+                    None => clients.write_to_client(id, encode_invalidate_push(keys), None),
+                };
+                if !delivered && redirect.is_none() {
+                    disconnected.push(id);
+                }
+            }
+        }
+
+        if !disconnected.is_empty() {
+            let mut state = self.lock()?;
+            for id in disconnected {
+                forget_client(&mut state, id);
+            }
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, TrackingState>, ErrorStruct> {
+        self.state.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "client tracking",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })
+    }
+
+    fn lock_clients(&self) -> Result<MutexGuard<'_, ClientList>, ErrorStruct> {
+        self.clients.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "client list",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })
+    }
+}
+
+/// Removes the client and every key it has read from the tracking state.
+fn forget_client(state: &mut TrackingState, id: u64) {
+    state.clients.remove(&id);
+    state.keys.retain(|_, ids| {
+        ids.remove(&id);
+        !ids.is_empty()
+    });
+}
+
+/// Encodes the keys invalidated, or a null if all of them were.
+fn encode_invalidated_keys(keys: Option<Vec<String>>) -> String {
+    match keys {
+        Some(keys) => RArray::encode(keys),
+        None => String::from("*-1\r\n"),
+    }
+}
+
+/// Encodes the invalidation as a RESP3 push message.
+fn encode_invalidate_push(keys: Option<Vec<String>>) -> String {
+    format!(
+        ">2\r\n{}{}",
+        RBulkString::encode(String::from("invalidate")),
+        encode_invalidated_keys(keys)
+    )
+}
+
+/// Encodes the invalidation as a message of the [INVALIDATE_CHANNEL], for RESP2 clients.
+fn encode_invalidate_message(keys: Option<Vec<String>>) -> String {
+    format!(
+        "*3\r\n{}{}{}",
+        RBulkString::encode(String::from("message")),
+        RBulkString::encode(String::from(INVALIDATE_CHANNEL)),
+        encode_invalidated_keys(keys)
+    )
+}
+
+#[cfg(test)]
+mod test_client_tracking {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn create_tracking() -> ClientTracking {
+        let (sender, _) = mpsc::channel();
+        ClientTracking::new(Arc::new(Mutex::new(ClientList::new(sender))))
+    }
+
+    fn tracked_keys(tracking: &ClientTracking, key: &str) -> Vec<u64> {
+        let mut ids: Vec<u64> = tracking
+            .lock()
+            .unwrap()
+            .keys
+            .get(key)
+            .map(|ids| ids.iter().copied().collect())
+            .unwrap_or_default();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_01_keys_read_are_only_remembered_with_tracking_on() {
+        let tracking = create_tracking();
+        tracking.remember(1, "key").unwrap();
+        assert!(tracked_keys(&tracking, "key").is_empty());
+
+        tracking.enable(1, TrackingOptions::default()).unwrap();
+        tracking.remember(1, "key").unwrap();
+        assert_eq!(tracked_keys(&tracking, "key"), vec![1]);
+    }
+
+    #[test]
+    fn test_02_invalidate_forgets_the_keys_of_disconnected_clients() {
+        let tracking = create_tracking();
+        tracking.enable(1, TrackingOptions::default()).unwrap();
+        tracking.remember(1, "key").unwrap();
+        tracking.remember(1, "other").unwrap();
+
+        tracking.invalidate(&["key".to_string()], None).unwrap();
+        assert!(tracked_keys(&tracking, "key").is_empty());
+        assert!(tracked_keys(&tracking, "other").is_empty());
+        assert_eq!(tracking.options(1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_03_optin_only_remembers_after_caching_yes() {
+        let tracking = create_tracking();
+        let options = TrackingOptions {
+            optin: true,
+            ..TrackingOptions::default()
+        };
+        tracking.enable(1, options).unwrap();
+
+        tracking.start_command(1).unwrap();
+        tracking.remember(1, "key").unwrap();
+        assert!(tracked_keys(&tracking, "key").is_empty());
+
+        tracking.set_caching(1, true).unwrap();
+        tracking.start_command(1).unwrap();
+        tracking.remember(1, "key").unwrap();
+        assert_eq!(tracked_keys(&tracking, "key"), vec![1]);
+
+        tracking.start_command(1).unwrap();
+        tracking.remember(1, "other").unwrap();
+        assert!(tracked_keys(&tracking, "other").is_empty());
+    }
+
+    #[test]
+    fn test_04_optout_does_not_remember_after_caching_no() {
+        let tracking = create_tracking();
+        let options = TrackingOptions {
+            optout: true,
+            ..TrackingOptions::default()
+        };
+        tracking.enable(1, options).unwrap();
+
+        tracking.set_caching(1, false).unwrap();
+        tracking.start_command(1).unwrap();
+        tracking.remember(1, "key").unwrap();
+        assert!(tracked_keys(&tracking, "key").is_empty());
+
+        tracking.start_command(1).unwrap();
+        tracking.remember(1, "key").unwrap();
+        assert_eq!(tracked_keys(&tracking, "key"), vec![1]);
+    }
+
+    #[test]
+    fn test_05_caching_requires_optin_or_optout() {
+        let tracking = create_tracking();
+        tracking.enable(1, TrackingOptions::default()).unwrap();
+        assert!(tracking.set_caching(1, true).is_err());
+        assert!(tracking.set_caching(2, true).is_err());
+    }
+
+    #[test]
+    fn test_06_bcast_clients_do_not_remember_keys() {
+        let tracking = create_tracking();
+        let options = TrackingOptions {
+            bcast: true,
+            prefixes: vec!["user:".to_string()],
+            ..TrackingOptions::default()
+        };
+        tracking.enable(1, options).unwrap();
+        tracking.remember(1, "user:1").unwrap();
+        assert!(tracked_keys(&tracking, "user:1").is_empty());
+
+        let client = &tracking.lock().unwrap().clients[&1];
+        assert!(client.is_interested_in("user:1"));
+        assert!(!client.is_interested_in("item:1"));
+    }
+
+    #[test]
+    fn test_07_redirect_to_unknown_client() {
+        let tracking = create_tracking();
+        let options = TrackingOptions {
+            redirect: Some(99),
+            ..TrackingOptions::default()
+        };
+        let error = tracking.enable(1, options).unwrap_err();
+        assert_eq!(
+            error.print_it(),
+            "ERR The client ID you want redirect to does not exist"
+        );
+    }
+
+    #[test]
+    fn test_08_encoded_invalidations() {
+        assert_eq!(
+            encode_invalidate_push(Some(vec!["key".to_string()])),
+            ">2\r\n$10\r\ninvalidate\r\n*1\r\n$3\r\nkey\r\n"
+        );
+        assert_eq!(
+            encode_invalidate_message(None),
+            "*3\r\n$7\r\nmessage\r\n$20\r\n__redis__:invalidate\r\n*-1\r\n"
+        );
+    }
+}
@@ -6,10 +6,11 @@ use std::sync::Mutex;
 use std::thread;
 use std::thread::JoinHandle;
 
-use super::{commands_map::CommandsMap, notifier::Notifier};
+use super::{command_table::CommandTable, commands_map::CommandsMap, notifier::Notifier};
 use super::{RawCommand, Response};
 
 use crate::joinable::Joinable;
+use crate::logs::server_stats::ServerStats;
 use crate::messages::redis_messages;
 use crate::messages::redis_messages::command_not_found;
 use crate::native_types::error_severity::ErrorSeverity;
@@ -50,11 +51,21 @@ impl CommandDelegator {
         command_delegator_recv: Receiver<Option<RawCommand>>,
         commands_map: CommandsMap,
         notifier: Notifier,
+        stats: ServerStats,
+        command_table: Arc<CommandTable>,
     ) -> Result<Self, ErrorStruct> {
         let builder = thread::Builder::new().name("Command Delegator".into());
         let c_notifier = notifier.clone();
         let handler = builder
-            .spawn(move || CommandDelegator::init(command_delegator_recv, commands_map, c_notifier))
+            .spawn(move || {
+                CommandDelegator::init(
+                    command_delegator_recv,
+                    commands_map,
+                    c_notifier,
+                    stats,
+                    command_table,
+                )
+            })
             .map_err(|_| {
                 ErrorStruct::from(redis_messages::init_failed(
                     "Fail init Command Delegator",
@@ -69,7 +80,8 @@ impl CommandDelegator {
     }
 
     /// Initializes the reception of raw commands that come from
-    /// client handlers.
+    /// client handlers. The arity of every known command is validated
+    /// with the [CommandTable] before it is registered in the [ServerStats].
     ///
 
     /// # Error
@@ -82,6 +94,8 @@ impl CommandDelegator {
         command_delegator_recv: Receiver<Option<RawCommand>>,
         mut commands_map: CommandsMap,
         notifier: Notifier,
+        stats: ServerStats,
+        command_table: Arc<CommandTable>,
     ) -> Result<(), ErrorStruct> {
         let mut result = Ok(());
         for packed_raw_command in command_delegator_recv.iter() {
@@ -90,12 +104,17 @@ impl CommandDelegator {
                 let command_type = raw_command.0.get(0).unwrap_or(&default).to_lowercase();
                 let err_critical;
                 if let Some(command_dest) = commands_map.get(&command_type) {
-                    err_critical = is_critical(delegate_jobs(raw_command, command_dest))
+                    if let Err(error) = command_table.check_arity(&raw_command.0) {
+                        err_critical = is_critical(reject_command(raw_command, error));
+                    } else {
+                        if command_type != "notifymonitors" {
+                            stats.register_command(&command_type)?;
+                        }
+                        err_critical = is_critical(delegate_jobs(raw_command, command_dest))
+                    }
                 } else {
-                    let error = command_not_found(command_type.to_string(), raw_command.0);
-                    err_critical = is_critical(raw_command.1.send(Err(error)).map_err(|_| {
-                        ErrorStruct::from(redis_messages::closed_sender(ErrorSeverity::Comunicate))
-                    }));
+                    let error = command_not_found(command_type.to_string(), raw_command.0.clone());
+                    err_critical = is_critical(reject_command(raw_command, error));
                 }
 
                 if let Err(err) = err_critical {
@@ -115,6 +134,14 @@ impl CommandDelegator {
     }
 }
 
+/// Answers the client with the error that prevents the command from being delegated.
+fn reject_command(raw_command: RawCommand, error: ErrorStruct) -> Result<(), ErrorStruct> {
+    raw_command
+        .1
+        .send(Err(error))
+        .map_err(|_| ErrorStruct::from(redis_messages::closed_sender(ErrorSeverity::Comunicate)))
+}
+
 /// Determines if an error requiers some shutdown action.
 /// If the error is critical, then it is returned.
 /// Else, Ok() is returned
@@ -247,6 +274,7 @@ pub mod test_command_delegator {
 
     use crate::commands::create_notifier;
     use crate::communication::log_messages::LogMessage;
+    use crate::logs::server_stats::ServerStats;
     use crate::tcp_protocol::command_subdelegator::CommandSubDelegator;
     use crate::tcp_protocol::BoxedCommand;
     use crate::vec_strings;
@@ -306,12 +334,19 @@ pub mod test_command_delegator {
             runnables_map,
             Arc::clone(&database),
             notifier.clone(),
+            ServerStats::default(),
             "database",
         )
         .unwrap();
 
-        let mut command_delegator =
-            CommandDelegator::start(rcv_test_cmd, commands_map, notifier.clone()).unwrap();
+        let mut command_delegator = CommandDelegator::start(
+            rcv_test_cmd,
+            commands_map,
+            notifier.clone(),
+            ServerStats::default(),
+            Arc::new(CommandTable::default()),
+        )
+        .unwrap();
 
         // ACT
 
@@ -420,12 +455,19 @@ pub mod test_command_delegator {
             runnables_map,
             database,
             notifier.clone(),
+            ServerStats::default(),
             "database",
         )
         .unwrap();
 
-        let mut command_delegator =
-            CommandDelegator::start(rcv_test_cmd, commands_map, notifier.clone()).unwrap();
+        let mut command_delegator = CommandDelegator::start(
+            rcv_test_cmd,
+            commands_map,
+            notifier.clone(),
+            ServerStats::default(),
+            Arc::new(CommandTable::default()),
+        )
+        .unwrap();
 
         // ACT
 
@@ -448,4 +490,77 @@ pub mod test_command_delegator {
         let _ = command_delegator.join();
         let _ = database_command_delegator.join();
     }
+
+    #[test]
+    fn test_03_commands_with_wrong_arity_are_rejected() {
+        // ARRANGE
+
+        let mut map: HashMap<String, Arc<BoxedCommand<Arc<Mutex<Database>>>>> = HashMap::new();
+        map.insert(String::from("lpush"), Arc::new(Box::new(LPush)));
+        let runnables_map = RunnablesMap::new(map);
+
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier)));
+
+        let (snd_cmd_dat, rcv_cmd_dat) = mpsc::channel();
+        let mut channel_map: HashMap<String, Vec<Option<Sender<Option<RawCommand>>>>> =
+            HashMap::new();
+        channel_map.insert(String::from("lpush"), vec![Some(snd_cmd_dat.clone())]);
+        let commands_map = CommandsMap::new(channel_map);
+
+        let (snd_test_cmd, rcv_test_cmd) = mpsc::channel();
+        let (snd_log_test, _b): (Sender<Option<LogMessage>>, Receiver<Option<LogMessage>>) =
+            mpsc::channel();
+        let notifier = Notifier::new(
+            snd_log_test,
+            snd_test_cmd.clone(),
+            Arc::new(AtomicBool::new(false)),
+            "test_addr".into(),
+        );
+
+        let mut database_command_delegator = CommandSubDelegator::start::<Arc<Mutex<Database>>>(
+            snd_cmd_dat.clone(),
+            rcv_cmd_dat,
+            runnables_map,
+            database,
+            notifier.clone(),
+            ServerStats::default(),
+            "database",
+        )
+        .unwrap();
+
+        let stats = ServerStats::default();
+        let mut command_delegator = CommandDelegator::start(
+            rcv_test_cmd,
+            commands_map,
+            notifier.clone(),
+            stats.clone(),
+            Arc::new(CommandTable::default()),
+        )
+        .unwrap();
+
+        // ACT
+
+        let (snd_dat_test, rcv_dat_test): (Sender<Response>, Receiver<Response>) = mpsc::channel();
+        snd_test_cmd
+            .send(Some((
+                vec_strings!["LPUSH", "key"],
+                snd_dat_test,
+                Arc::new(Mutex::new(ClientFields::default())),
+            )))
+            .unwrap();
+
+        // ASSERT
+
+        let response = rcv_dat_test.recv().unwrap();
+        assert_eq!(
+            response.unwrap_err().print_it(),
+            "ERR wrong number of arguments for 'lpush' command"
+        );
+        assert_eq!(stats.snapshot().unwrap().total_commands_processed, 0);
+
+        drop(notifier);
+        let _ = command_delegator.join();
+        let _ = database_command_delegator.join();
+    }
 }
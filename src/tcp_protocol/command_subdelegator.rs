@@ -1,21 +1,29 @@
 use crate::communication::log_messages::LogMessage;
 use crate::joinable::Joinable;
+use crate::logs::server_stats::ServerStats;
 use crate::messages::redis_messages;
 use crate::tcp_protocol::close_thread;
 use crate::tcp_protocol::BoxedCommand;
 use std::sync::mpsc::Receiver;
+use std::sync::mpsc::RecvTimeoutError;
 use std::sync::mpsc::Sender;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::native_types::ErrorStruct;
 use crate::tcp_protocol::runnables_map::RunnablesMap;
 
+use super::client_atributes::client_fields::ClientFields;
+use super::client_aware::ClientAware;
 use super::notifier::Notifier;
 use super::{RawCommand, Response};
 
+/// How often the blocked commands are checked to discard the ones whose client is gone.
+const PRUNE_PERIOD: Duration = Duration::from_millis(100);
+
 /// Interprets raw commands and gives runnables to execute
 /// in a predetermined structure.
 pub struct CommandSubDelegator {
@@ -48,15 +56,18 @@ impl CommandSubDelegator {
         runnables_map: RunnablesMap<T>,
         data: T,
         notifier: Notifier,
+        stats: ServerStats,
         name: &str,
     ) -> Result<Self, ErrorStruct>
     where
-        T: Send + Sync,
+        T: Send + Sync + ClientAware,
     {
         let builder = thread::Builder::new().name("Command Sub-Delegator".to_string());
         let c_notifier = notifier.clone();
         let command_sub_delegator_handler = builder
-            .spawn(move || CommandSubDelegator::init(rcv_cmd, runnables_map, data, c_notifier))
+            .spawn(move || {
+                CommandSubDelegator::init(rcv_cmd, runnables_map, data, c_notifier, stats)
+            })
             .map_err(|_| {
                 ErrorStruct::from(redis_messages::init_failed(
                     "Command Subdelegator",
@@ -73,6 +84,11 @@ impl CommandSubDelegator {
     }
 
     /// Initializes the reception of raw commands.
+    /// Each execution is timed and registered in the [ServerStats].
+    ///
+    /// Blocking commands that must wait for data are kept aside and tried
+    /// again after every command received, so a write can answer them, or
+    /// when they time out.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
@@ -84,47 +100,236 @@ impl CommandSubDelegator {
         runnables_map: RunnablesMap<T>,
         mut data: T,
         notifier: Notifier,
+        stats: ServerStats,
     ) -> Result<(), ErrorStruct>
     where
-        T: Send + Sync,
+        T: Send + Sync + ClientAware,
     {
-        for packed_raw_command in rcv_cmd.iter() {
-            if let Some((mut command_input_user, sender_to_client, _)) = packed_raw_command {
-                let command_type = command_input_user.remove(0).to_lowercase();
-                if let Some(runnable_command) = runnables_map.get(&command_type) {
-                    let err_critical = is_critical(run_command(
-                        runnable_command,
-                        command_input_user,
-                        sender_to_client,
-                        &mut data,
-                    ));
-                    if let Err(err) = err_critical {
-                        if err.severity().eq(&Some(&ErrorSeverity::ShutdownServer)) {
-                            notifier.force_shutdown_server(err.print_it());
-                            return Err(err);
-                        }
-                    }
-                } else {
-                    let error = redis_messages::command_not_found(command_type, command_input_user);
-                    if sender_to_client.send(Err(error)).is_err() {
-                        notifier.send_log(LogMessage::channel_client_off())?;
-                    }
-                }
-            } else {
-                break;
+        let mut blocked: Vec<BlockedCommand<T>> = Vec::new();
+        while let Some(packed_raw_command) = receive(&rcv_cmd, &blocked) {
+            if let Some(raw_command) = packed_raw_command {
+                delegate(
+                    raw_command,
+                    &runnables_map,
+                    &mut data,
+                    &notifier,
+                    &stats,
+                    &mut blocked,
+                )?;
+            }
+            blocked = serve_blocked(blocked, &mut data, &notifier, &stats)?;
+        }
+        Ok(())
+    }
+}
+
+/// A command waiting for data, as told by [blocking_time](crate::commands::Runnable::blocking_time).
+struct BlockedCommand<T> {
+    runnable_command: Arc<BoxedCommand<T>>,
+    command_type: String,
+    command_input_user: Vec<String>,
+    sender_to_client: Sender<Response>,
+    client_fields: Arc<Mutex<ClientFields>>,
+    /// [None] if it waits forever.
+    deadline: Option<Instant>,
+}
+
+/// Waits for the next raw command, or until the first blocked command times out.
+/// While there are blocked commands, it waits at most [PRUNE_PERIOD] so the
+/// ones whose client is gone are discarded even if nothing else happens.
+///
+/// # Return value
+/// * [None]: the sub delegator must stop.
+/// * [Some]([None]): the blocked commands must be checked again.
+/// * [Some]([Some]<[RawCommand]>): the command received.
+fn receive<T>(
+    rcv_cmd: &Receiver<Option<RawCommand>>,
+    blocked: &[BlockedCommand<T>],
+) -> Option<Option<RawCommand>> {
+    let first_deadline = blocked.iter().filter_map(|command| command.deadline).min();
+    let wait = match first_deadline {
+        Some(deadline) => Some(deadline.saturating_duration_since(Instant::now())),
+        None if !blocked.is_empty() => Some(PRUNE_PERIOD),
+        None => None,
+    };
+    let received = match wait {
+        Some(wait) => match rcv_cmd.recv_timeout(wait.min(PRUNE_PERIOD)) {
+            Err(RecvTimeoutError::Timeout) => return Some(None),
+            received => received.ok(),
+        },
+        None => rcv_cmd.recv().ok(),
+    };
+    received.flatten().map(Some)
+}
+
+/// Runs the raw command with its runnable, or keeps it aside if it must wait for data.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The error received demands to close the server.
+fn delegate<T>(
+    raw_command: RawCommand,
+    runnables_map: &RunnablesMap<T>,
+    data: &mut T,
+    notifier: &Notifier,
+    stats: &ServerStats,
+    blocked: &mut Vec<BlockedCommand<T>>,
+) -> Result<(), ErrorStruct>
+where
+    T: 'static + ClientAware,
+{
+    let (mut command_input_user, sender_to_client, client_fields) = raw_command;
+    let command_type = command_input_user.remove(0).to_lowercase();
+    if let Some(runnable_command) = runnables_map.get(&command_type) {
+        data.set_current_client(Arc::clone(&client_fields));
+        match runnable_command.blocking_time(&mut command_input_user, data) {
+            Ok(Some(timeout)) => {
+                blocked.push(BlockedCommand {
+                    runnable_command,
+                    command_type,
+                    command_input_user,
+                    sender_to_client,
+                    client_fields,
+                    deadline: deadline_after(timeout),
+                });
+                Ok(())
+            }
+            Ok(None) => shutdown_if_critical(
+                run_command(
+                    runnable_command,
+                    command_type,
+                    command_input_user,
+                    sender_to_client,
+                    data,
+                    stats,
+                    &client_fields,
+                ),
+                notifier,
+            ),
+            Err(err) => {
+                let _ = sender_to_client.send(Err(err.clone()));
+                shutdown_if_critical(Err(err), notifier)
             }
         }
+    } else {
+        let error = redis_messages::command_not_found(command_type, command_input_user);
+        if sender_to_client.send(Err(error)).is_err() {
+            notifier.send_log(LogMessage::channel_client_off())?;
+        }
         Ok(())
     }
 }
 
+/// Runs the blocked commands that have data to answer or have timed out,
+/// and returns the ones that keep waiting, in the same order.
+/// The commands whose client is dead are dropped without running them.
+///
+/// # Error
+/// Return an [ErrorStruct] if:
+///
+/// * The error received demands to close the server.
+fn serve_blocked<T>(
+    blocked: Vec<BlockedCommand<T>>,
+    data: &mut T,
+    notifier: &Notifier,
+    stats: &ServerStats,
+) -> Result<Vec<BlockedCommand<T>>, ErrorStruct>
+where
+    T: 'static + ClientAware,
+{
+    let mut still_blocked = Vec::new();
+    for mut command in blocked {
+        if client_is_gone(&command) {
+            continue;
+        }
+        let timed_out = command
+            .deadline
+            .is_some_and(|deadline| deadline <= Instant::now());
+        if !timed_out {
+            data.set_current_client(Arc::clone(&command.client_fields));
+            match command
+                .runnable_command
+                .blocking_time(&mut command.command_input_user, data)
+            {
+                Ok(Some(_)) => {
+                    still_blocked.push(command);
+                    continue;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    let _ = command.sender_to_client.send(Err(err.clone()));
+                    shutdown_if_critical(Err(err), notifier)?;
+                    continue;
+                }
+            }
+        }
+        data.set_current_client(Arc::clone(&command.client_fields));
+        shutdown_if_critical(
+            run_command(
+                command.runnable_command,
+                command.command_type,
+                command.command_input_user,
+                command.sender_to_client,
+                data,
+                stats,
+                &command.client_fields,
+            ),
+            notifier,
+        )?;
+    }
+    Ok(still_blocked)
+}
+
+/// Returns true if the client of the blocked command is dead or its fields are poisoned.
+/// A client handler marks its client as dead before it stops waiting for the response,
+/// also when the socket is closed while the command is blocked.
+fn client_is_gone<T>(command: &BlockedCommand<T>) -> bool {
+    command
+        .client_fields
+        .lock()
+        .map(|fields| fields.is_dead())
+        .unwrap_or(true)
+}
+
+/// Instant when a blocking command times out, or [None] if the timeout is zero.
+fn deadline_after(timeout: Duration) -> Option<Instant> {
+    if timeout.is_zero() {
+        None
+    } else {
+        Some(Instant::now() + timeout)
+    }
+}
+
+/// Forces the shutdown of the server if the error received is critical.
+fn shutdown_if_critical(
+    result: Result<(), ErrorStruct>,
+    notifier: &Notifier,
+) -> Result<(), ErrorStruct> {
+    if let Err(err) = is_critical(result) {
+        if err.severity().eq(&Some(&ErrorSeverity::ShutdownServer)) {
+            notifier.force_shutdown_server(err.print_it());
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
 fn run_command<T: 'static>(
     runnable_command: Arc<BoxedCommand<T>>,
+    command_type: String,
     command_input_user: Vec<String>,
     sender_to_client: Sender<Response>,
     data: &mut T,
+    stats: &ServerStats,
+    client_fields: &Arc<Mutex<ClientFields>>,
 ) -> Result<(), ErrorStruct> {
+    let mut command = vec![command_type];
+    command.extend_from_slice(&command_input_user);
+
+    let start = Instant::now();
     let result = runnable_command.run(command_input_user, data);
+    register_execution(stats, &command, start.elapsed(), client_fields)?;
 
     sender_to_client
         .send(result.clone())
@@ -133,6 +338,34 @@ fn run_command<T: 'static>(
     result.map(|_| ())
 }
 
+/// Registers the execution in the [ServerStats], with the address and name of the client.
+/// The notifications to the monitors are internal, so they are not registered.
+fn register_execution(
+    stats: &ServerStats,
+    command: &[String],
+    duration: Duration,
+    client_fields: &Arc<Mutex<ClientFields>>,
+) -> Result<(), ErrorStruct> {
+    if command[0] == "notifymonitors" {
+        return Ok(());
+    }
+    let (addr, name) = client_fields
+        .lock()
+        .map(|fields| {
+            (
+                fields.get_addr(),
+                fields.name().cloned().unwrap_or_default(),
+            )
+        })
+        .map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "client fields",
+                ErrorSeverity::CloseClient,
+            ))
+        })?;
+    stats.register_execution(command, duration, addr, name)
+}
+
 fn is_critical(potential_error: Result<(), ErrorStruct>) -> Result<(), ErrorStruct> {
     match potential_error {
         Ok(()) => Ok(()),
@@ -156,6 +389,7 @@ pub mod test_database_command_delegator {
     use crate::commands::create_notifier;
     use crate::commands::lists::rpop::RPop;
     use crate::commands::lists::rpush::RPush;
+    use crate::commands::streams::{xadd::Xadd, xread::Xread};
     use crate::commands::strings::get::Get;
     use crate::commands::strings::set::Set;
     use crate::commands::strings::strlen::Strlen;
@@ -163,6 +397,7 @@ pub mod test_database_command_delegator {
     use crate::native_types::RError;
     use crate::native_types::RedisType;
     use crate::tcp_protocol::client_atributes::client_fields::ClientFields;
+    use crate::tcp_protocol::client_atributes::status::Status;
     use crate::tcp_protocol::BoxedCommand;
     use crate::{commands::lists::llen::Llen, tcp_protocol::Response};
     use std::sync::atomic::AtomicBool;
@@ -177,6 +412,8 @@ pub mod test_database_command_delegator {
     };
 
     use super::*;
+    use crate::logs::slowlog::Slowlog;
+    use crate::time_expiration::clock::SystemClock;
 
     #[test]
 
@@ -199,6 +436,7 @@ pub mod test_database_command_delegator {
             runnables_map,
             database,
             notifier.clone(),
+            ServerStats::default(),
             "database",
         );
 
@@ -258,6 +496,7 @@ pub mod test_database_command_delegator {
             runnables_map,
             database,
             notifier.clone(),
+            ServerStats::default(),
             "database",
         );
 
@@ -321,6 +560,7 @@ pub mod test_database_command_delegator {
             runnables_map,
             database,
             notifier.clone(),
+            ServerStats::default(),
             "database",
         );
         let (tx2, rx2): (Sender<Response>, Receiver<Response>) = mpsc::channel();
@@ -370,4 +610,138 @@ pub mod test_database_command_delegator {
         drop(notifier);
         drop(tx1);
     }
+
+    #[test]
+    fn test_04_executions_are_registered_in_the_slowlog() {
+        let mut map: HashMap<String, Arc<BoxedCommand<Arc<Mutex<Database>>>>> = HashMap::new();
+        map.insert(String::from("set"), Arc::new(Box::new(Set)));
+        let runnables_map = RunnablesMap::new(map);
+
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier.clone())));
+        let stats = ServerStats::new(Slowlog::new(0, 10), Arc::new(SystemClock));
+
+        let (tx1, rx1) = mpsc::channel();
+        let _database_command_delegator_recv = CommandSubDelegator::start::<Arc<Mutex<Database>>>(
+            tx1.clone(),
+            rx1,
+            runnables_map,
+            database,
+            notifier.clone(),
+            stats.clone(),
+            "database",
+        );
+
+        let mut client = ClientFields::default();
+        client.set_name("martina".to_string());
+        let (tx2, rx2): (Sender<Response>, Receiver<Response>) = mpsc::channel();
+        tx1.send(Some((
+            vec_strings!["set", "key", "value"],
+            tx2,
+            Arc::new(Mutex::new(client)),
+        )))
+        .unwrap();
+        rx2.recv().unwrap().unwrap();
+
+        let entries = stats.slowlog().get(None).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].args, vec_strings!["set", "key", "value"]);
+        assert_eq!(entries[0].client_addr, "1.0.0.1:8080");
+        assert_eq!(entries[0].client_name, "martina");
+        drop(notifier);
+        drop(tx1);
+    }
+
+    fn start_streams() -> (Sender<Option<RawCommand>>, Notifier) {
+        let mut map: HashMap<String, Arc<BoxedCommand<Arc<Mutex<Database>>>>> = HashMap::new();
+        map.insert(String::from("xadd"), Arc::new(Box::new(Xadd)));
+        map.insert(String::from("xread"), Arc::new(Box::new(Xread)));
+        let runnables_map = RunnablesMap::new(map);
+
+        let (notifier, _log_rcv, _cmd_rcv) = create_notifier();
+        let database = Arc::new(Mutex::new(Database::new(notifier.clone())));
+
+        let (tx1, rx1) = mpsc::channel();
+        let _database_command_delegator_recv = CommandSubDelegator::start::<Arc<Mutex<Database>>>(
+            tx1.clone(),
+            rx1,
+            runnables_map,
+            database,
+            notifier.clone(),
+            ServerStats::default(),
+            "database",
+        );
+        (tx1, notifier)
+    }
+
+    fn send(tx: &Sender<Option<RawCommand>>, command: Vec<String>) -> Receiver<Response> {
+        let (tx_response, rx_response): (Sender<Response>, Receiver<Response>) = mpsc::channel();
+        tx.send(Some((
+            command,
+            tx_response,
+            Arc::new(Mutex::new(ClientFields::default())),
+        )))
+        .unwrap();
+        rx_response
+    }
+
+    #[test]
+    fn test_05_blocked_xread_is_answered_by_xadd() {
+        let (tx1, notifier) = start_streams();
+        send(&tx1, vec_strings!["xadd", "key", "1-1", "a", "1"])
+            .recv()
+            .unwrap()
+            .unwrap();
+
+        let blocked = send(
+            &tx1,
+            vec_strings!["xread", "BLOCK", "0", "STREAMS", "key", "$"],
+        );
+        assert!(blocked.recv_timeout(Duration::from_millis(50)).is_err());
+
+        send(&tx1, vec_strings!["xadd", "key", "1-2", "b", "2"])
+            .recv()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            blocked.recv().unwrap().unwrap(),
+            "*1\r\n*2\r\n$3\r\nkey\r\n*1\r\n*2\r\n$3\r\n1-2\r\n*2\r\n$1\r\nb\r\n$1\r\n2\r\n"
+        );
+        drop(notifier);
+        drop(tx1);
+    }
+
+    #[test]
+    fn test_06_blocked_xread_times_out() {
+        let (tx1, notifier) = start_streams();
+        let blocked = send(
+            &tx1,
+            vec_strings!["xread", "BLOCK", "20", "STREAMS", "key", "$"],
+        );
+        assert_eq!(blocked.recv().unwrap().unwrap(), "*-1\r\n");
+        drop(notifier);
+        drop(tx1);
+    }
+
+    #[test]
+    fn test_07_blocked_xread_of_a_dead_client_is_discarded() {
+        let (tx1, notifier) = start_streams();
+        let (tx_response, blocked) = mpsc::channel();
+        let fields = Arc::new(Mutex::new(ClientFields::default()));
+        tx1.send(Some((
+            vec_strings!["xread", "BLOCK", "0", "STREAMS", "key", "$"],
+            tx_response,
+            Arc::clone(&fields),
+        )))
+        .unwrap();
+        assert!(blocked.recv_timeout(Duration::from_millis(50)).is_err());
+
+        fields.lock().unwrap().replace_status(Status::Dead);
+        assert_eq!(
+            blocked.recv_timeout(Duration::from_secs(1)),
+            Err(RecvTimeoutError::Disconnected)
+        );
+        drop(notifier);
+        drop(tx1);
+    }
 }
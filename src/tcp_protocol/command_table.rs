@@ -0,0 +1,1282 @@
+use std::collections::HashMap;
+
+use crate::messages::redis_messages;
+use crate::native_types::ErrorStruct;
+
+/// Metadata of a command, as shown by COMMAND INFO and COMMAND DOCS.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    /// Number of arguments, including the name of the command. A negative
+    /// arity means *at least* that many arguments.
+    pub arity: isize,
+    pub flags: &'static [&'static str],
+    /// Position of the first key, or 0 if the command has no keys.
+    pub first_key: isize,
+    /// Position of the last key. A negative position counts from the end.
+    pub last_key: isize,
+    /// Distance between two consecutive keys.
+    pub step: isize,
+    pub acl_categories: &'static [&'static str],
+    pub group: &'static str,
+    pub summary: &'static str,
+}
+
+impl CommandSpec {
+    /// Indicates if the number of arguments of the buffer, including the
+    /// name of the command, satisfies the arity.
+    pub fn accepts(&self, buffer_len: usize) -> bool {
+        let buffer_len = buffer_len as isize;
+        if self.arity >= 0 {
+            buffer_len == self.arity
+        } else {
+            buffer_len >= -self.arity
+        }
+    }
+
+    /// Indicates if the command can modify the dataset.
+    pub fn is_write(&self) -> bool {
+        self.flags.contains(&"write")
+    }
+
+    /// Returns the keys found in the buffer, including the name of the command.
+    pub fn keys(&self, buffer: &[String]) -> Vec<String> {
+        if self.first_key <= 0 || self.step <= 0 {
+            return Vec::new();
+        }
+        let last_key = if self.last_key < 0 {
+            buffer.len() as isize + self.last_key
+        } else {
+            self.last_key.min(buffer.len() as isize - 1)
+        };
+        (self.first_key..=last_key)
+            .step_by(self.step as usize)
+            .filter_map(|position| buffer.get(position as usize).cloned())
+            .collect()
+    }
+}
+
+/// Associate a command's name with its [CommandSpec]. Shared by the
+/// [CommandDelegator](crate::tcp_protocol::command_delegator::CommandDelegator),
+/// which validates the arity of every command received, and the COMMAND command.
+pub struct CommandTable {
+    elements: HashMap<String, CommandSpec>,
+}
+
+impl Default for CommandTable {
+    /// Creates an instance with every command of the server.
+    fn default() -> Self {
+        let commands = vec![
+            // bitmap
+            command(
+                "bitcount",
+                -2,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@bitmap", "@slow"],
+                "bitmap",
+                "Counts the number of set bits in a string.",
+            ),
+            command(
+                "bitfield",
+                -2,
+                &["write", "denyoom"],
+                (1, 1, 1),
+                &["@write", "@bitmap", "@slow"],
+                "bitmap",
+                "Performs arbitrary bitfield integer operations on strings.",
+            ),
+            command(
+                "bitfield_ro",
+                -2,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@read", "@bitmap", "@fast"],
+                "bitmap",
+                "Performs arbitrary read-only bitfield integer operations on strings.",
+            ),
+            command(
+                "bitop",
+                -4,
+                &["write", "denyoom"],
+                (2, -1, 1),
+                &["@write", "@bitmap", "@slow"],
+                "bitmap",
+                "Performs bitwise operations on multiple strings, and stores the result.",
+            ),
+            command(
+                "bitpos",
+                -3,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@bitmap", "@slow"],
+                "bitmap",
+                "Finds the first set or clear bit in a string.",
+            ),
+            command(
+                "getbit",
+                3,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@read", "@bitmap", "@fast"],
+                "bitmap",
+                "Returns a bit value by offset.",
+            ),
+            command(
+                "setbit",
+                4,
+                &["write", "denyoom"],
+                (1, 1, 1),
+                &["@write", "@bitmap", "@slow"],
+                "bitmap",
+                "Sets or clears the bit at offset of the string value.",
+            ),
+            // generic
+            command(
+                "clean",
+                2,
+                &["write"],
+                (0, 0, 0),
+                &["@keyspace", "@write", "@slow"],
+                "generic",
+                "Expires a sample of the keys with a timeout.",
+            ),
+            command(
+                "copy",
+                -3,
+                &["write", "denyoom"],
+                (1, 2, 1),
+                &["@keyspace", "@write", "@slow"],
+                "generic",
+                "Copies the value of a key to a new key.",
+            ),
+            command(
+                "del",
+                -2,
+                &["write"],
+                (1, -1, 1),
+                &["@keyspace", "@write", "@slow"],
+                "generic",
+                "Deletes one or more keys.",
+            ),
+            command(
+                "dump",
+                2,
+                &["readonly"],
+                (1, 1, 1),
+                &["@keyspace", "@read", "@slow"],
+                "generic",
+                "Returns a serialized representation of the value stored at a key.",
+            ),
+            command(
+                "exists",
+                -2,
+                &["readonly", "fast"],
+                (1, -1, 1),
+                &["@keyspace", "@read", "@fast"],
+                "generic",
+                "Determines whether one or more keys exist.",
+            ),
+            command(
+                "expire",
+                -3,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@keyspace", "@write", "@fast"],
+                "generic",
+                "Sets the expiration time of a key in seconds.",
+            ),
+            command(
+                "expireat",
+                -3,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@keyspace", "@write", "@fast"],
+                "generic",
+                "Sets the expiration time of a key to a Unix timestamp.",
+            ),
+            command(
+                "expiretime",
+                2,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@keyspace", "@read", "@fast"],
+                "generic",
+                "Returns the expiration time of a key as a Unix timestamp.",
+            ),
+            command(
+                "keys",
+                2,
+                &["readonly"],
+                (0, 0, 0),
+                &["@keyspace", "@read", "@slow", "@dangerous"],
+                "generic",
+                "Returns all key names that match a pattern.",
+            ),
+            command(
+                "migrate",
+                -6,
+                &["write"],
+                (3, 3, 1),
+                &["@keyspace", "@write", "@slow", "@dangerous"],
+                "generic",
+                "Atomically transfers a key from one Redis instance to another.",
+            ),
+            command(
+                "object",
+                -2,
+                &["readonly"],
+                (2, 2, 1),
+                &["@keyspace", "@read", "@slow"],
+                "generic",
+                "Returns information about the internals of a value.",
+            ),
+            command(
+                "persist",
+                2,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@keyspace", "@write", "@fast"],
+                "generic",
+                "Removes the expiration time of a key.",
+            ),
+            command(
+                "pexpiretime",
+                2,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@keyspace", "@read", "@fast"],
+                "generic",
+                "Returns the expiration time of a key as a Unix milliseconds timestamp.",
+            ),
+            command(
+                "randomkey",
+                1,
+                &["readonly"],
+                (0, 0, 0),
+                &["@keyspace", "@read", "@slow"],
+                "generic",
+                "Returns a random key name from the database.",
+            ),
+            command(
+                "rename",
+                3,
+                &["write"],
+                (1, 2, 1),
+                &["@keyspace", "@write", "@slow"],
+                "generic",
+                "Renames a key and overwrites the destination.",
+            ),
+            command(
+                "renamenx",
+                3,
+                &["write", "fast"],
+                (1, 2, 1),
+                &["@keyspace", "@write", "@fast"],
+                "generic",
+                "Renames a key only when the target key name doesn't exist.",
+            ),
+            command(
+                "restore",
+                -4,
+                &["write", "denyoom"],
+                (1, 1, 1),
+                &["@keyspace", "@write", "@slow", "@dangerous"],
+                "generic",
+                "Creates a key from the serialized representation of a value.",
+            ),
+            command(
+                "sort",
+                -2,
+                &["write", "denyoom"],
+                (1, 1, 1),
+                &[
+                    "@write",
+                    "@set",
+                    "@sortedset",
+                    "@list",
+                    "@slow",
+                    "@dangerous",
+                ],
+                "generic",
+                "Sorts the elements in a list or a set.",
+            ),
+            command(
+                "touch",
+                -2,
+                &["readonly", "fast"],
+                (1, -1, 1),
+                &["@keyspace", "@read", "@fast"],
+                "generic",
+                "Updates the last access time of the keys that exist.",
+            ),
+            command(
+                "ttl",
+                2,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@keyspace", "@read", "@fast"],
+                "generic",
+                "Returns the expiration time in seconds of a key.",
+            ),
+            command(
+                "type",
+                2,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@keyspace", "@read", "@fast"],
+                "generic",
+                "Determines the type of value stored at a key.",
+            ),
+            command(
+                "unlink",
+                -2,
+                &["write", "fast"],
+                (1, -1, 1),
+                &["@keyspace", "@write", "@fast"],
+                "generic",
+                "Asynchronously deletes one or more keys.",
+            ),
+            // geo
+            command(
+                "geoadd",
+                -5,
+                &["write", "denyoom"],
+                (1, 1, 1),
+                &["@write", "@geo", "@slow"],
+                "geo",
+                "Adds one or more members to a geospatial index. The key is created if it doesn't exist.",
+            ),
+            command(
+                "geodist",
+                -4,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@geo", "@slow"],
+                "geo",
+                "Returns the distance between two members of a geospatial index.",
+            ),
+            command(
+                "geohash",
+                -2,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@geo", "@slow"],
+                "geo",
+                "Returns members from a geospatial index as geohash strings.",
+            ),
+            command(
+                "geopos",
+                -2,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@geo", "@slow"],
+                "geo",
+                "Returns the longitude and latitude of members from a geospatial index.",
+            ),
+            command(
+                "geosearch",
+                -7,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@geo", "@slow"],
+                "geo",
+                "Queries a geospatial index for members inside an area of a box or a circle.",
+            ),
+            command(
+                "geosearchstore",
+                -8,
+                &["write", "denyoom"],
+                (1, 2, 1),
+                &["@write", "@geo", "@slow"],
+                "geo",
+                "Queries a geospatial index for members inside an area of a box or a circle, optionally stores the result.",
+            ),
+            // hyperloglog
+            command(
+                "pfadd",
+                -2,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@hyperloglog", "@fast"],
+                "hyperloglog",
+                "Adds elements to a HyperLogLog key. Creates the key if it doesn't exist.",
+            ),
+            command(
+                "pfcount",
+                -2,
+                &["readonly"],
+                (1, -1, 1),
+                &["@read", "@hyperloglog", "@slow"],
+                "hyperloglog",
+                "Returns the approximated cardinality of the set(s) observed by the HyperLogLog key(s).",
+            ),
+            command(
+                "pfmerge",
+                -2,
+                &["write", "denyoom"],
+                (1, -1, 1),
+                &["@write", "@hyperloglog", "@slow"],
+                "hyperloglog",
+                "Merges one or more HyperLogLog values into a single key.",
+            ),
+            // list
+            command(
+                "lindex",
+                3,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@list", "@slow"],
+                "list",
+                "Returns an element from a list by its index.",
+            ),
+            command(
+                "llen",
+                2,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@read", "@list", "@fast"],
+                "list",
+                "Returns the length of a list.",
+            ),
+            command(
+                "lpop",
+                -2,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@write", "@list", "@fast"],
+                "list",
+                "Returns the first elements in a list after removing it.",
+            ),
+            command(
+                "lpush",
+                -3,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@list", "@fast"],
+                "list",
+                "Prepends one or more elements to a list.",
+            ),
+            command(
+                "lpushx",
+                -3,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@list", "@fast"],
+                "list",
+                "Prepends one or more elements to a list only when the list exists.",
+            ),
+            command(
+                "lrange",
+                4,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@list", "@slow"],
+                "list",
+                "Returns a range of elements from a list.",
+            ),
+            command(
+                "lrem",
+                4,
+                &["write"],
+                (1, 1, 1),
+                &["@write", "@list", "@slow"],
+                "list",
+                "Removes elements from a list.",
+            ),
+            command(
+                "lset",
+                4,
+                &["write", "denyoom"],
+                (1, 1, 1),
+                &["@write", "@list", "@slow"],
+                "list",
+                "Sets the value of an element in a list by its index.",
+            ),
+            command(
+                "rpop",
+                -2,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@write", "@list", "@fast"],
+                "list",
+                "Returns and removes the last elements of a list.",
+            ),
+            command(
+                "rpush",
+                -3,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@list", "@fast"],
+                "list",
+                "Appends one or more elements to a list.",
+            ),
+            command(
+                "rpushx",
+                -3,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@list", "@fast"],
+                "list",
+                "Appends an element to a list only when the list exists.",
+            ),
+            // set
+            command(
+                "sadd",
+                -3,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@set", "@fast"],
+                "set",
+                "Adds one or more members to a set.",
+            ),
+            command(
+                "scard",
+                2,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@read", "@set", "@fast"],
+                "set",
+                "Returns the number of members in a set.",
+            ),
+            command(
+                "sismember",
+                3,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@read", "@set", "@fast"],
+                "set",
+                "Determines whether a member belongs to a set.",
+            ),
+            command(
+                "smembers",
+                2,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@set", "@slow"],
+                "set",
+                "Returns all members of a set.",
+            ),
+            command(
+                "spop",
+                -2,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@write", "@set", "@fast"],
+                "set",
+                "Removes and returns one or more random members from a set.",
+            ),
+            command(
+                "srem",
+                -3,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@write", "@set", "@fast"],
+                "set",
+                "Removes one or more members from a set.",
+            ),
+            // stream
+            command(
+                "xack",
+                -4,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@write", "@stream", "@fast"],
+                "stream",
+                "Acknowledges the pending entries of a consumer group.",
+            ),
+            command(
+                "xadd",
+                -5,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@stream", "@fast"],
+                "stream",
+                "Appends a new entry to a stream.",
+            ),
+            command(
+                "xautoclaim",
+                -6,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@write", "@stream", "@fast"],
+                "stream",
+                "Changes the owner of the idle pending entries of a consumer group.",
+            ),
+            command(
+                "xclaim",
+                -6,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@write", "@stream", "@fast"],
+                "stream",
+                "Changes the owner of pending entries of a consumer group.",
+            ),
+            command(
+                "xdel",
+                -3,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@write", "@stream", "@fast"],
+                "stream",
+                "Deletes entries from a stream.",
+            ),
+            command(
+                "xgroup",
+                -2,
+                &["write"],
+                (2, 2, 1),
+                &["@write", "@stream", "@slow"],
+                "stream",
+                "Creates, destroys and manages consumer groups.",
+            ),
+            command(
+                "xinfo",
+                -2,
+                &["readonly"],
+                (2, 2, 1),
+                &["@read", "@stream", "@slow"],
+                "stream",
+                "Returns information about a stream and its consumer groups.",
+            ),
+            command(
+                "xlen",
+                2,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@read", "@stream", "@fast"],
+                "stream",
+                "Returns the number of entries in a stream.",
+            ),
+            command(
+                "xpending",
+                -3,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@stream", "@slow"],
+                "stream",
+                "Returns the pending entries of a consumer group.",
+            ),
+            command(
+                "xrange",
+                -4,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@stream", "@slow"],
+                "stream",
+                "Returns the entries of a stream within a range of IDs.",
+            ),
+            command(
+                "xread",
+                -4,
+                &["readonly", "blocking", "movablekeys"],
+                (0, 0, 0),
+                &["@read", "@stream", "@slow", "@blocking"],
+                "stream",
+                "Returns entries from streams, blocking if needed.",
+            ),
+            command(
+                "xreadgroup",
+                -7,
+                &["write", "blocking", "movablekeys"],
+                (0, 0, 0),
+                &["@write", "@stream", "@slow", "@blocking"],
+                "stream",
+                "Returns entries from streams for a consumer group, blocking if needed.",
+            ),
+            command(
+                "xrevrange",
+                -4,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@stream", "@slow"],
+                "stream",
+                "Returns the entries of a stream in reverse order.",
+            ),
+            command(
+                "xtrim",
+                -4,
+                &["write"],
+                (1, 1, 1),
+                &["@write", "@stream", "@slow"],
+                "stream",
+                "Deletes entries from the beginning of a stream.",
+            ),
+            // string
+            command(
+                "append",
+                3,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@string", "@fast"],
+                "string",
+                "Appends a string to the value of a key.",
+            ),
+            command(
+                "decr",
+                2,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@string", "@fast"],
+                "string",
+                "Decrements the integer value of a key by one.",
+            ),
+            command(
+                "decrby",
+                3,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@string", "@fast"],
+                "string",
+                "Decrements a number from the integer value of a key.",
+            ),
+            command(
+                "get",
+                2,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@read", "@string", "@fast"],
+                "string",
+                "Returns the string value of a key.",
+            ),
+            command(
+                "getdel",
+                2,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@write", "@string", "@fast"],
+                "string",
+                "Returns the string value of a key after deleting the key.",
+            ),
+            command(
+                "getex",
+                -2,
+                &["write", "fast"],
+                (1, 1, 1),
+                &["@write", "@string", "@fast"],
+                "string",
+                "Returns the string value of a key after setting its expiration time.",
+            ),
+            command(
+                "getrange",
+                4,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@string", "@slow"],
+                "string",
+                "Returns a substring of the string stored at a key.",
+            ),
+            command(
+                "getset",
+                3,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@string", "@fast"],
+                "string",
+                "Returns the previous string value of a key after setting it to a new value.",
+            ),
+            command(
+                "incr",
+                2,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@string", "@fast"],
+                "string",
+                "Increments the integer value of a key by one.",
+            ),
+            command(
+                "incrby",
+                3,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@string", "@fast"],
+                "string",
+                "Increments the integer value of a key by a number.",
+            ),
+            command(
+                "incrbyfloat",
+                3,
+                &["write", "denyoom", "fast"],
+                (1, 1, 1),
+                &["@write", "@string", "@fast"],
+                "string",
+                "Increments the floating point value of a key by a number.",
+            ),
+            command(
+                "lcs",
+                -3,
+                &["readonly"],
+                (1, 2, 1),
+                &["@read", "@string", "@slow"],
+                "string",
+                "Finds the longest common substring.",
+            ),
+            command(
+                "mget",
+                -2,
+                &["readonly", "fast"],
+                (1, -1, 1),
+                &["@read", "@string", "@fast"],
+                "string",
+                "Atomically returns the string values of one or more keys.",
+            ),
+            command(
+                "mset",
+                -3,
+                &["write", "denyoom"],
+                (1, -1, 2),
+                &["@write", "@string", "@slow"],
+                "string",
+                "Atomically creates or modifies the string values of one or more keys.",
+            ),
+            command(
+                "set",
+                -3,
+                &["write", "denyoom"],
+                (1, 1, 1),
+                &["@write", "@string", "@slow"],
+                "string",
+                "Sets the string value of a key.",
+            ),
+            command(
+                "setrange",
+                4,
+                &["write", "denyoom"],
+                (1, 1, 1),
+                &["@write", "@string", "@slow"],
+                "string",
+                "Overwrites a part of a string value with another by an offset.",
+            ),
+            command(
+                "strlen",
+                2,
+                &["readonly", "fast"],
+                (1, 1, 1),
+                &["@read", "@string", "@fast"],
+                "string",
+                "Returns the length of a string value.",
+            ),
+            command(
+                "substr",
+                4,
+                &["readonly"],
+                (1, 1, 1),
+                &["@read", "@string", "@slow"],
+                "string",
+                "Returns a substring from a string value.",
+            ),
+            // pubsub
+            command(
+                "publish",
+                3,
+                &["pubsub", "loading", "stale", "fast"],
+                (0, 0, 0),
+                &["@pubsub", "@fast"],
+                "pubsub",
+                "Posts a message to a channel.",
+            ),
+            command(
+                "pubsub",
+                -2,
+                &["pubsub", "loading", "stale"],
+                (0, 0, 0),
+                &["@pubsub", "@slow"],
+                "pubsub",
+                "A container for Pub/Sub commands.",
+            ),
+            command(
+                "subscribe",
+                -2,
+                &["pubsub", "noscript", "loading", "stale"],
+                (0, 0, 0),
+                &["@pubsub", "@slow"],
+                "pubsub",
+                "Listens for messages published to channels.",
+            ),
+            command(
+                "unsubscribe",
+                -1,
+                &["pubsub", "noscript", "loading", "stale"],
+                (0, 0, 0),
+                &["@pubsub", "@slow"],
+                "pubsub",
+                "Stops listening to messages posted to channels.",
+            ),
+            // connection
+            command(
+                "client",
+                -2,
+                &["admin", "noscript", "loading", "stale"],
+                (0, 0, 0),
+                &["@slow", "@connection"],
+                "connection",
+                "A container for client connection commands.",
+            ),
+            command(
+                "echo",
+                2,
+                &["loading", "stale", "fast"],
+                (0, 0, 0),
+                &["@fast", "@connection"],
+                "connection",
+                "Returns the given string.",
+            ),
+            command(
+                "ping",
+                -1,
+                &["loading", "stale", "fast"],
+                (0, 0, 0),
+                &["@fast", "@connection"],
+                "connection",
+                "Returns the server's liveliness response.",
+            ),
+            command(
+                "quit",
+                -1,
+                &["noscript", "loading", "stale", "fast"],
+                (0, 0, 0),
+                &["@fast", "@connection"],
+                "connection",
+                "Closes the connection.",
+            ),
+            command(
+                "reset",
+                1,
+                &["noscript", "loading", "stale", "fast"],
+                (0, 0, 0),
+                &["@fast", "@connection"],
+                "connection",
+                "Resets the connection.",
+            ),
+            // server
+            command(
+                "bgsave",
+                -1,
+                &["admin", "noscript"],
+                (0, 0, 0),
+                &["@admin", "@slow", "@dangerous"],
+                "server",
+                "Asynchronously saves the database to disk.",
+            ),
+            command(
+                "command",
+                -1,
+                &["loading", "stale"],
+                (0, 0, 0),
+                &["@slow", "@connection"],
+                "server",
+                "Returns detailed information about all commands.",
+            ),
+            command(
+                "config",
+                -2,
+                &["admin", "noscript", "loading", "stale"],
+                (0, 0, 0),
+                &["@admin", "@slow", "@dangerous"],
+                "server",
+                "A container for server configuration commands.",
+            ),
+            command(
+                "dbsize",
+                1,
+                &["readonly", "fast"],
+                (0, 0, 0),
+                &["@keyspace", "@read", "@fast"],
+                "server",
+                "Returns the number of keys in the database.",
+            ),
+            command(
+                "flushdb",
+                -1,
+                &["write"],
+                (0, 0, 0),
+                &["@keyspace", "@write", "@slow", "@dangerous"],
+                "server",
+                "Removes all keys from the current database.",
+            ),
+            command(
+                "info",
+                -1,
+                &["loading", "stale"],
+                (0, 0, 0),
+                &["@slow", "@dangerous"],
+                "server",
+                "Returns information and statistics about the server.",
+            ),
+            command(
+                "time",
+                1,
+                &["loading", "stale", "fast"],
+                (0, 0, 0),
+                &["@fast"],
+                "server",
+                "Returns the server time.",
+            ),
+            command(
+                "lastsave",
+                1,
+                &["loading", "stale", "fast"],
+                (0, 0, 0),
+                &["@admin", "@fast", "@dangerous"],
+                "server",
+                "Returns the Unix timestamp of the last successful save to disk.",
+            ),
+            command(
+                "monitor",
+                1,
+                &["admin", "noscript", "loading", "stale"],
+                (0, 0, 0),
+                &["@admin", "@slow", "@dangerous"],
+                "server",
+                "Listens for all requests received by the server in real-time.",
+            ),
+            command(
+                "notifymonitors",
+                -3,
+                &["admin", "noscript", "loading", "stale"],
+                (0, 0, 0),
+                &["@admin", "@slow", "@dangerous"],
+                "server",
+                "Sends a command executed by a client to the clients in MONITOR mode.",
+            ),
+            command(
+                "save",
+                1,
+                &["admin", "noscript"],
+                (0, 0, 0),
+                &["@admin", "@slow", "@dangerous"],
+                "server",
+                "Synchronously saves the database to disk.",
+            ),
+            command(
+                "shutdown",
+                -1,
+                &["admin", "noscript", "loading", "stale"],
+                (0, 0, 0),
+                &["@admin", "@slow", "@dangerous"],
+                "server",
+                "Synchronously saves the database to disk and shuts down the server.",
+            ),
+            command(
+                "slowlog",
+                -2,
+                &["admin", "loading", "stale"],
+                (0, 0, 0),
+                &["@admin", "@slow", "@dangerous"],
+                "server",
+                "A container for slow log commands.",
+            ),
+        ];
+
+        let mut elements = HashMap::new();
+        for info in commands {
+            elements.insert(info.name.to_string(), info);
+        }
+        CommandTable { elements }
+    }
+}
+
+impl CommandTable {
+    /// Returns the metadata of the given command's name, in lowercase.
+    pub fn get(&self, name: &str) -> Option<&CommandSpec> {
+        self.elements.get(name)
+    }
+
+    /// Returns the number of commands in the table.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns true if the table has no commands.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the metadata of every command, sorted by name.
+    pub fn all(&self) -> Vec<&CommandSpec> {
+        let mut commands: Vec<&CommandSpec> = self.elements.values().collect();
+        commands.sort_by_key(|info| info.name);
+        commands
+    }
+
+    /// Checks the number of arguments of a command received, including its
+    /// name. Runnables rely on this check, so every command they handle must
+    /// have metadata.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The command has no metadata.
+    /// * The command does not satisfy its arity.
+    pub fn check_arity(&self, buffer: &[String]) -> Result<(), ErrorStruct> {
+        let name = buffer
+            .first()
+            .map(|name| name.to_lowercase())
+            .unwrap_or_default();
+        let info = self.elements.get(&name).ok_or_else(|| {
+            redis_messages::command_not_found(
+                name.clone(),
+                buffer.iter().skip(1).cloned().collect(),
+            )
+        })?;
+        if info.accepts(buffer.len()) {
+            Ok(())
+        } else {
+            Err(ErrorStruct::from(redis_messages::wrong_number_args_for(
+                &name,
+            )))
+        }
+    }
+
+    /// Returns the keys of a full command, including its name.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The command is unknown.
+    /// * The command does not satisfy its arity.
+    /// * The command has no keys.
+    pub fn get_keys(&self, buffer: &[String]) -> Result<Vec<String>, ErrorStruct> {
+        let name = buffer
+            .first()
+            .map(|name| name.to_lowercase())
+            .unwrap_or_default();
+        let info = self
+            .elements
+            .get(&name)
+            .ok_or_else(|| ErrorStruct::from(redis_messages::invalid_command_specified()))?;
+        if !info.accepts(buffer.len()) {
+            return Err(ErrorStruct::from(
+                redis_messages::invalid_number_of_arguments_specified(),
+            ));
+        }
+        let keys = info.keys(buffer);
+        if keys.is_empty() {
+            Err(ErrorStruct::from(redis_messages::command_has_no_keys()))
+        } else {
+            Ok(keys)
+        }
+    }
+}
+
+fn command(
+    name: &'static str,
+    arity: isize,
+    flags: &'static [&'static str],
+    (first_key, last_key, step): (isize, isize, isize),
+    acl_categories: &'static [&'static str],
+    group: &'static str,
+    summary: &'static str,
+) -> CommandSpec {
+    CommandSpec {
+        name,
+        arity,
+        flags,
+        first_key,
+        last_key,
+        step,
+        acl_categories,
+        group,
+        summary,
+    }
+}
+
+#[cfg(test)]
+mod test_command_table {
+    use super::*;
+    use crate::vec_strings;
+
+    fn args(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn test_01_fixed_and_variable_arity() {
+        let table = CommandTable::default();
+        assert!(table.check_arity(&args(&["get", "key"])).is_ok());
+        assert!(table.check_arity(&args(&["GET"])).is_err());
+        assert!(table.check_arity(&args(&["get", "key", "other"])).is_err());
+        assert!(table.check_arity(&args(&["del", "a", "b", "c"])).is_ok());
+        assert!(table.check_arity(&args(&["del"])).is_err());
+    }
+
+    #[test]
+    fn test_02_every_delegated_command_has_metadata() {
+        use crate::tcp_protocol::commands_map::CommandsMap;
+        use std::sync::mpsc;
+        let table = CommandTable::default();
+        let (sender, _receiver) = mpsc::channel();
+        let commands = CommandsMap::default(sender.clone(), sender);
+
+        for name in commands.names() {
+            assert!(table.get(name).is_some(), "{} has no metadata", name);
+        }
+        assert!(table
+            .check_arity(&args(&["notifymonitors", "get", "key", "addr"]))
+            .is_ok());
+        assert!(table.check_arity(&args(&["unknown"])).is_err());
+        assert!(table.check_arity(&[]).is_err());
+    }
+
+    #[test]
+    fn test_03_get_keys() {
+        let table = CommandTable::default();
+        assert_eq!(
+            table
+                .get_keys(&args(&["mset", "a", "1", "b", "2"]))
+                .unwrap(),
+            vec_strings!["a", "b"]
+        );
+        assert_eq!(
+            table.get_keys(&args(&["del", "a", "b"])).unwrap(),
+            vec_strings!["a", "b"]
+        );
+        assert_eq!(
+            table.get_keys(&args(&["set", "a", "1"])).unwrap(),
+            vec_strings!["a"]
+        );
+    }
+
+    #[test]
+    fn test_04_get_keys_errors() {
+        let table = CommandTable::default();
+        assert!(table.get_keys(&args(&["unknown", "a"])).is_err());
+        assert!(table.get_keys(&args(&["get"])).is_err());
+        assert!(table.get_keys(&args(&["publish", "a", "b"])).is_err());
+    }
+
+    #[test]
+    fn test_05_all_is_sorted() {
+        let table = CommandTable::default();
+        let all = table.all();
+        assert_eq!(all.len(), table.len());
+        assert_eq!(all[0].name, "append");
+    }
+
+    #[test]
+    fn test_06_runnables_receive_commands_with_a_valid_arity() {
+        let table = CommandTable::default();
+        assert!(table.check_arity(&args(&["time", "now"])).is_err());
+        assert!(table.check_arity(&args(&["lpop"])).is_err());
+        assert!(table.check_arity(&args(&["rpop"])).is_err());
+        assert!(table.check_arity(&args(&["lset"])).is_err());
+        assert!(table.check_arity(&args(&["lset", "key"])).is_err());
+        assert!(table.check_arity(&args(&["lset", "key", "2"])).is_err());
+        assert!(table
+            .check_arity(&args(&["lset", "key", "2", "a", "b"]))
+            .is_err());
+        assert!(table
+            .check_arity(&args(&["scard", "key1", "key2", "key3"]))
+            .is_err());
+        assert!(table
+            .check_arity(&args(&["sismember", "arg1", "arg2", "arg3"]))
+            .is_err());
+        assert!(table
+            .check_arity(&args(&["smembers", "arg1", "arg2"]))
+            .is_err());
+        assert_eq!(
+            table.check_arity(&args(&["lset"])).unwrap_err().print_it(),
+            "ERR wrong number of arguments for 'lset' command"
+        );
+    }
+}
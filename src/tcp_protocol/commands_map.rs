@@ -33,6 +33,11 @@ impl CommandsMap {
         self.channel_map.get(string)
     }
 
+    /// Returns the names of every command in the map.
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.channel_map.keys()
+    }
+
     /// Creates an fully complete instance of the commands map.
     pub fn default(
         snd_cmd_dat: Sender<Option<RawCommand>>,
@@ -47,16 +52,34 @@ impl CommandsMap {
                 "clean".to_string(),
                 "copy".to_string(),
                 "del".to_string(),
+                "dump".to_string(),
                 "exists".to_string(),
                 "expire".to_string(),
                 "expireat".to_string(),
+                "expiretime".to_string(),
                 "keys".to_string(),
+                "migrate".to_string(),
+                "object".to_string(),
                 "persist".to_string(),
                 "rename".to_string(),
+                "renamenx".to_string(),
+                "pexpiretime".to_string(),
+                "randomkey".to_string(),
+                "restore".to_string(),
                 "sort".to_string(),
                 "touch".to_string(),
                 "ttl".to_string(),
                 "type".to_string(),
+                "unlink".to_string(),
+                "geoadd".to_string(),
+                "geodist".to_string(),
+                "geohash".to_string(),
+                "geopos".to_string(),
+                "geosearch".to_string(),
+                "geosearchstore".to_string(),
+                "pfadd".to_string(),
+                "pfcount".to_string(),
+                "pfmerge".to_string(),
                 "lindex".to_string(),
                 "llen".to_string(),
                 "lpop".to_string(),
@@ -72,22 +95,54 @@ impl CommandsMap {
                 "scard".to_string(),
                 "sismember".to_string(),
                 "smembers".to_string(),
+                "spop".to_string(),
                 "srem".to_string(),
+                "xack".to_string(),
+                "xadd".to_string(),
+                "xautoclaim".to_string(),
+                "xclaim".to_string(),
+                "xdel".to_string(),
+                "xgroup".to_string(),
+                "xinfo".to_string(),
+                "xlen".to_string(),
+                "xpending".to_string(),
+                "xrange".to_string(),
+                "xread".to_string(),
+                "xreadgroup".to_string(),
+                "xrevrange".to_string(),
+                "xtrim".to_string(),
                 "append".to_string(),
+                "bitcount".to_string(),
+                "bitfield".to_string(),
+                "bitfield_ro".to_string(),
+                "bitop".to_string(),
+                "bitpos".to_string(),
+                "decr".to_string(),
                 "decrby".to_string(),
                 "get".to_string(),
+                "getbit".to_string(),
                 "getdel".to_string(),
+                "getex".to_string(),
+                "getrange".to_string(),
                 "getset".to_string(),
+                "incr".to_string(),
                 "incrby".to_string(),
+                "incrbyfloat".to_string(),
+                "lcs".to_string(),
                 "mget".to_string(),
                 "mset".to_string(),
                 "set".to_string(),
+                "setbit".to_string(),
+                "setrange".to_string(),
                 "strlen".to_string(),
+                "substr".to_string(),
                 "dbsize".to_string(),
                 "flushdb".to_string(),
                 "save".to_string(),
+                "bgsave".to_string(),
+                "lastsave".to_string(),
             ],
-            snd_cmd_dat.clone(),
+            snd_cmd_dat,
         );
 
         // asociacion de comandos con server atributes
@@ -100,6 +155,12 @@ impl CommandsMap {
                 "config".to_string(),
                 "notifymonitors".to_string(),
                 "shutdown".to_string(),
+                "client".to_string(),
+                "slowlog".to_string(),
+                "command".to_string(),
+                "quit".to_string(),
+                "reset".to_string(),
+                "info".to_string(),
             ],
             snd_cmd_server.clone(),
         );
@@ -110,13 +171,12 @@ impl CommandsMap {
         );
         channel_map.insert(
             String::from("unsubscribe"),
-            vec![None, Some(snd_cmd_server.clone())],
-        );
-        channel_map.insert(
-            String::from("info"),
-            vec![Some(snd_cmd_server), Some(snd_cmd_dat)],
+            vec![None, Some(snd_cmd_server)],
         );
         channel_map.insert(String::from("monitor"), vec![None]);
+        channel_map.insert(String::from("ping"), vec![None]);
+        channel_map.insert(String::from("echo"), vec![None]);
+        channel_map.insert(String::from("time"), vec![None]);
 
         CommandsMap { channel_map }
     }
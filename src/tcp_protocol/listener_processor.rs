@@ -86,20 +86,30 @@ fn start_incoming(
 
         match stream {
             Ok(client) => {
-                server_redis.set_timeout(&client)?;
+                // Pipelined replies are small writes; waiting to batch them
+                // delays each one until the client acknowledges the previous
+                let _ = client.set_nodelay(true);
                 notifier.send_log(LogMessage::new_conection(&client))?;
-                if let Ok(new_client) = ClientHandler::new(client, notifier.clone()) {
-                    if let Ok(mut client_list) = server_redis.get_client_list().lock() {
+                // The timeout is set before the client is read, with the list
+                // locked so a CONFIG SET of the timeout does not miss it
+                if let Ok(mut client_list) = server_redis.get_client_list().lock() {
+                    let _ = client.set_read_timeout(client_list.timeout());
+                    if let Ok(new_client) = ClientHandler::new(
+                        client,
+                        notifier.clone(),
+                        server_redis.get_client_pause(),
+                        client_list.clock(),
+                    ) {
                         client_list.insert(new_client);
-                    } else {
-                        let _ = notifier.send_log(LogMessage::from_errorstruct(
-                            // I'm not interested ... I retired with the forced Shutdown!
-                            ErrorStruct::from(redis_messages::poisoned_lock(
-                                "Client List",
-                                ErrorSeverity::ShutdownServer,
-                            )),
-                        ))?;
                     }
+                } else {
+                    let _ = notifier.send_log(LogMessage::from_errorstruct(
+                        // I'm not interested ... I retired with the forced Shutdown!
+                        ErrorStruct::from(redis_messages::poisoned_lock(
+                            "Client List",
+                            ErrorSeverity::ShutdownServer,
+                        )),
+                    ))?;
                 }
             }
             Err(e) => {
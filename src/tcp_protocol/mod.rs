@@ -11,10 +11,14 @@ use std::thread::JoinHandle;
 use self::notifier::Notifier;
 
 pub mod client_atributes;
+pub mod client_aware;
 pub mod client_handler;
 pub mod client_list;
+pub mod client_pause;
+pub mod client_tracking;
 pub mod command_delegator;
 pub mod command_subdelegator;
+pub mod command_table;
 pub mod commands_map;
 pub mod listener_processor;
 pub mod notifier;
@@ -1,9 +1,19 @@
 use crate::{
     commands::{
+        connection::{
+            client::Client, echo::Echo, ping::Ping, quit::Quit, reset::Reset, time::Time,
+        },
+        geo::{
+            geoadd::GeoAdd, geodist::GeoDist, geohash::GeoHash, geopos::GeoPos,
+            geosearch::GeoSearch, geosearchstore::GeoSearchStore,
+        },
+        hyperloglog::{pfadd::Pfadd, pfcount::Pfcount, pfmerge::Pfmerge},
         keys::{
-            _type::Type, clean::Clean, copy::Copy, del::Del, exists::Exists, expire::Expire,
-            expireat::ExpireAt, key_command::Keys, persist::Persist, rename::Rename, sort::Sort,
-            touch::Touch, ttl::Ttl,
+            _type::Type, clean::Clean, copy::Copy, del::Del, dump::Dump, exists::Exists,
+            expire::Expire, expireat::ExpireAt, expiretime::ExpireTime, key_command::Keys,
+            migrate::Migrate, object::Object, persist::Persist, pexpiretime::PexpireTime,
+            randomkey::RandomKey, rename::Rename, renamenx::RenameNx, restore::Restore, sort::Sort,
+            touch::Touch, ttl::Ttl, unlink::Unlink,
         },
         lists::{
             lindex::LIndex, llen::Llen, lpop::LPop, lpush::LPush, lpushx::LPushx, lrange::Lrange,
@@ -15,13 +25,25 @@ use crate::{
             unsubscribe_cl::UnsubscribeCl,
         },
         server::{
-            config::Config, dbsize::Dbsize, flushdb::FlushDb, info_db::InfoDb, info_sv::InfoSv,
-            monitor::Monitor, notify_monitors::NotifyMonitors, save::Save, shutdown::Shutdown,
+            bgsave::BgSave, command::Command, config::Config, dbsize::Dbsize, flushdb::FlushDb,
+            info_sv::InfoSv, lastsave::LastSave, monitor::Monitor, notify_monitors::NotifyMonitors,
+            save::Save, shutdown::Shutdown, slowlog::Slowlog,
+        },
+        sets::{
+            sadd::Sadd, scard::Scard, sismember::Sismember, smembers::Smembers, spop::Spop,
+            srem::Srem,
+        },
+        streams::{
+            xack::Xack, xadd::Xadd, xautoclaim::Xautoclaim, xclaim::Xclaim, xdel::Xdel,
+            xgroup::Xgroup, xinfo::Xinfo, xlen::Xlen, xpending::Xpending, xrange::Xrange,
+            xread::Xread, xreadgroup::Xreadgroup, xrevrange::Xrevrange, xtrim::Xtrim,
         },
-        sets::{sadd::Sadd, scard::Scard, sismember::Sismember, smembers::Smembers, srem::Srem},
         strings::{
-            append::Append, decrby::Decrby, get::Get, getdel::Getdel, getset::Getset,
-            incrby::Incrby, mget::Mget, mset::Mset, set::Set, strlen::Strlen,
+            append::Append, bitcount::Bitcount, bitfield::Bitfield, bitfield_ro::BitfieldRo,
+            bitop::Bitop, bitpos::Bitpos, decr::Decr, decrby::Decrby, get::Get, getbit::Getbit,
+            getdel::Getdel, getex::Getex, getrange::Getrange, getset::Getset, incr::Incr,
+            incrby::Incrby, incrbyfloat::Incrbyfloat, lcs::Lcs, mget::Mget, mset::Mset, set::Set,
+            setbit::Setbit, setrange::Setrange, strlen::Strlen, substr::Substr,
         },
     },
     database::Database,
@@ -76,15 +98,100 @@ impl<T> RunnablesMap<T> {
     pub fn database() -> RunnablesMap<Arc<Mutex<Database>>> {
         let mut map: HashMap<String, Arc<BoxedCommand<Arc<Mutex<Database>>>>> = HashMap::new();
         map = get_runnables!(
-            map, Type, Clean, Copy, Del, Exists, Expire, ExpireAt, Keys, Persist, Rename, Sort,
-            Touch, Ttl, LIndex, Llen, LPop, LPush, LPushx, Lrange, Lrem, Lset, RPop, RPush, RPushx,
-            Dbsize, FlushDb, Sadd, Scard, Sismember, Smembers, Srem, Append, Decrby, Get, Getdel,
-            Getset, Incrby, Mget, Mset, Set, Strlen, Save
-        );
-        map.insert(
-            "info".to_string().to_lowercase(),
-            Arc::new(Box::new(InfoDb)),
+            map,
+            Type,
+            Clean,
+            Copy,
+            Del,
+            Dump,
+            Exists,
+            Expire,
+            ExpireAt,
+            ExpireTime,
+            Keys,
+            Migrate,
+            Object,
+            Persist,
+            PexpireTime,
+            RandomKey,
+            Rename,
+            RenameNx,
+            Restore,
+            Sort,
+            Touch,
+            Ttl,
+            Unlink,
+            LIndex,
+            Llen,
+            LPop,
+            LPush,
+            LPushx,
+            Lrange,
+            Lrem,
+            Lset,
+            RPop,
+            RPush,
+            RPushx,
+            Dbsize,
+            FlushDb,
+            Sadd,
+            Scard,
+            Sismember,
+            Smembers,
+            Spop,
+            Srem,
+            Append,
+            Decr,
+            Decrby,
+            Get,
+            Getdel,
+            Getex,
+            Getrange,
+            Getset,
+            Incr,
+            Incrby,
+            Incrbyfloat,
+            Lcs,
+            Mget,
+            Mset,
+            Set,
+            Setrange,
+            Strlen,
+            Substr,
+            Bitcount,
+            Bitfield,
+            Bitop,
+            Bitpos,
+            Getbit,
+            Setbit,
+            GeoAdd,
+            GeoDist,
+            GeoHash,
+            GeoPos,
+            GeoSearch,
+            GeoSearchStore,
+            Pfadd,
+            Pfcount,
+            Pfmerge,
+            Save,
+            BgSave,
+            LastSave,
+            Xack,
+            Xadd,
+            Xautoclaim,
+            Xclaim,
+            Xdel,
+            Xgroup,
+            Xinfo,
+            Xlen,
+            Xpending,
+            Xrange,
+            Xread,
+            Xreadgroup,
+            Xrevrange,
+            Xtrim
         );
+        map.insert("bitfield_ro".to_string(), Arc::new(Box::new(BitfieldRo)));
         RunnablesMap { elements: map }
     }
 
@@ -92,7 +199,19 @@ impl<T> RunnablesMap<T> {
     pub fn server() -> RunnablesMap<ServerRedisAttributes> {
         let mut map: HashMap<String, Arc<BoxedCommand<ServerRedisAttributes>>> = HashMap::new();
 
-        map = get_runnables!(map, Publish, Pubsub, Config, NotifyMonitors, Shutdown);
+        map = get_runnables!(
+            map,
+            Publish,
+            Pubsub,
+            Config,
+            NotifyMonitors,
+            Shutdown,
+            Client,
+            Slowlog,
+            Command,
+            Quit,
+            Reset
+        );
         map.insert(
             "subscribe".to_string().to_lowercase(),
             Arc::new(Box::new(SubscribeCl)),
@@ -117,6 +236,9 @@ impl<T> RunnablesMap<T> {
             String::from("unsubscribe"),
             Arc::new(Box::new(UnsubscribeCf)),
         );
+        map.insert(String::from("ping"), Arc::new(Box::new(Ping)));
+        map.insert(String::from("echo"), Arc::new(Box::new(Echo)));
+        map.insert(String::from("time"), Arc::new(Box::new(Time)));
         RunnablesMap { elements: map }
     }
 
@@ -128,6 +250,7 @@ impl<T> RunnablesMap<T> {
             String::from("unsubscribe"),
             Arc::new(Box::new(UnsubscribeCf)),
         );
+        map.insert(String::from("ping"), Arc::new(Box::new(Ping)));
         RunnablesMap { elements: map }
     }
 }
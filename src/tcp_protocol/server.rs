@@ -1,4 +1,6 @@
+use std::net::{SocketAddr, TcpStream};
 use std::sync::{atomic::AtomicBool, mpsc::channel, Arc, Mutex};
+use std::thread::{self, JoinHandle};
 
 use crate::database::Database;
 use crate::native_types::error_severity::ErrorSeverity;
@@ -14,7 +16,14 @@ use crate::{
         runnables_map::RunnablesMap,
     },
 };
-use crate::{memory_checker::periodic_executor::PeriodicExecutor, messages::redis_messages};
+use crate::{
+    memory_checker::{
+        periodic_executor::{PeriodicExecutor, Schedule},
+        saver::Saver,
+    },
+    messages::redis_messages,
+    time_expiration::clock::{Clock, SystemClock},
+};
 
 use super::{
     client_list::ClientList, command_subdelegator::CommandSubDelegator, commands_map::CommandsMap,
@@ -28,6 +37,7 @@ impl ServerRedis {
     /// # Start of Server Redis
     /// Starts the server, letting the program flow listen for new clients connecting to it.
     /// Because of this, it is recommended to start the server in a separate thread from the rest of the main program.
+    /// To run it without blocking, use [ServerRedis::builder] instead.
     ///
     /// The received vector may be empty: in this case, the server starts with the default setting of [RedisConfig].
     /// If it is not empty, at index 1 it receives the path where the _redis.conf_ file is located to start the server with a different configuration.
@@ -41,9 +51,69 @@ impl ServerRedis {
     /// * Incorrect reading of the data persistence file.     
     /// * Thread initialization failure.
     pub fn start(argv: Vec<String>) -> Result<(), ErrorStruct> {
-        // ################## 1° Initialization structures: BASIC ELEMENTS ##################
         let config = RedisConfig::parse_config(argv)?;
+        ServerRedis::builder(config).start()?.wait()
+    }
+
+    /// Returns a [ServerBuilder] to run the server in the background of the
+    /// current process, as `ServerRedis::builder(config).port(0).start()?`.
+    pub fn builder(config: RedisConfig) -> ServerBuilder {
+        ServerBuilder {
+            config,
+            clock: Arc::new(SystemClock),
+        }
+    }
+}
+
+/// Settings of a server which runs in its own threads. Built with
+/// [ServerRedis::builder].
+pub struct ServerBuilder {
+    config: RedisConfig,
+    clock: Arc<dyn Clock>,
+}
+
+impl ServerBuilder {
+    /// Replaces the port of the configuration. With 0 the operating system
+    /// chooses a free one, which is given by [ServerHandle::address].
+    pub fn port(mut self, port: u16) -> Self {
+        self.config.update_port(&port.to_string());
+        self
+    }
+
+    /// Replaces the [SystemClock] which measures expirations, the garbage
+    /// collector period and the save points, as with a
+    /// [ManualClock](crate::time_expiration::clock::ManualClock) in tests.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Binds the address, starts every structure of the server and listens
+    /// for clients in a new thread.
+    ///
+    /// # Return value
+    /// [ServerHandle] of the running server.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The server cannot be started on the address of the [RedisConfig].
+    /// * Poisoned structures.
+    /// * Incorrect reading of the data persistence file.
+    /// * Thread initialization failure.
+    pub fn start(self) -> Result<ServerHandle, ErrorStruct> {
+        // ################## 1° Initialization structures: BASIC ELEMENTS ##################
+        let mut config = self.config;
+        let clock = self.clock;
         let listener = ListenerProcessor::new_tcp_listener(&config)?;
+        let address = listener.local_addr().map_err(|_| {
+            ErrorStruct::from(redis_messages::init_failed(
+                "Fail in local address of listener",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        // The port 0 is replaced by the chosen one, so SHUTDOWN can reach the listener
+        config.update_port(&address.port().to_string());
 
         // ################## 2° Initialization structures: CHANNELS and COMMANDS MAP ##################
         let (command_delegator_sender, command_delegator_recv) = channel();
@@ -57,17 +127,19 @@ impl ServerRedis {
         let status_listener = Arc::new(AtomicBool::new(false));
 
         // ################## 4° Initialization structures: CLIENT LIST AND MORE PACHMUTEX ##################
-        let clients = ClientList::new(sender_log.clone());
+        let mut clients = ClientList::new(sender_log.clone());
+        clients.set_clock(Arc::clone(&clock));
         let shared_clients = Arc::new(Mutex::new(clients));
         let drop_shared_clients = Arc::clone(&shared_clients);
 
         // ################## 5° Initialization structures: SERVER REDIS ATRIBUTES AND RUNNABLES MAP ##################
         // and
         // ################## 6° Initialization structures: Notifier ##################
-        let server_redis = ServerRedisAttributes::new(
+        let mut server_redis = ServerRedisAttributes::new(
             Arc::clone(&config),
             status_listener.clone(),
             shared_clients,
+            Arc::clone(&clock),
         );
 
         let notifier = Notifier::new(
@@ -76,67 +148,191 @@ impl ServerRedis {
             status_listener,
             server_redis.get_addr()?,
         );
-        let database = Database::new_from(Arc::clone(&config), notifier.clone())?;
+        let mut database =
+            Database::new_from(Arc::clone(&config), notifier.clone(), Arc::clone(&clock))?;
+        database.set_client_tracking(server_redis.get_client_tracking());
+        database.set_server_stats(server_redis.get_stats());
 
         let c_database = Arc::new(Mutex::new(database));
+        server_redis.set_database(Arc::clone(&c_database));
         let runnables_database = RunnablesMap::<Arc<Mutex<Database>>>::database();
         let runnables_server = RunnablesMap::<ServerRedisAttributes>::server();
 
         // ################## 7° Initialization structures: STRUCTS WITH THREADS ##################
-        let mut log_center = LogCenter::new(
+        let log_center = LogCenter::new(
             sender_log,
             receiver,
             Arc::clone(&config),
             FileManager::new(),
         )?;
 
-        let mut command_delegator =
-            CommandDelegator::start(command_delegator_recv, commands_map, notifier.clone())?;
-        let mut command_sub_delegator_databse = CommandSubDelegator::start::<Arc<Mutex<Database>>>(
+        let command_delegator = CommandDelegator::start(
+            command_delegator_recv,
+            commands_map,
+            notifier.clone(),
+            server_redis.get_stats(),
+            server_redis.get_command_table(),
+        )?;
+        let command_sub_delegator_databse = CommandSubDelegator::start::<Arc<Mutex<Database>>>(
             snd_cmd_dat,
             rcv_cmd_dat,
             runnables_database,
             Arc::clone(&c_database),
             notifier.clone(),
+            server_redis.get_stats(),
             "database",
         )?;
-        let mut command_sub_delegator_server_atributes =
+        let command_sub_delegator_server_atributes =
             CommandSubDelegator::start::<ServerRedisAttributes>(
                 snd_cmd_sv,
                 rcv_cmd_sv,
                 runnables_server,
                 server_redis.clone(),
                 notifier.clone(),
+                server_redis.get_stats(),
                 "server atributes",
             )?;
 
-        let clean = vec!["clean".to_string(), "20".to_string()];
-        let mut garbage_collector =
-            PeriodicExecutor::new(clean, 10, notifier.clone(), "garbage collector");
-
-        let save = vec!["save".to_string()];
-        let mut saver = PeriodicExecutor::new(save, 60, notifier.clone(), "saver");
-
-        /*let quit_notifier = Mutex::new(notifier.clone());
-        let quit: JoinHandle<Result<(), ErrorStruct>> = thread::spawn(move ||{
-            for line in stdin().lock().lines() {
-                match line {
-                    Ok(line) => {
-                        if line.contains("q") || line.contains("quit") || line.contains("exit") || line.contains("shutdown")  {
-                            quit_notifier.lock().map_err(|_| {
-                                ErrorStruct::from(redis_messages::closed_sender(ErrorSeverity::ShutdownServer))
-                            })?.force_shutdown_server("Shutdown by stdin console of server".to_string()); break;
-                        }
-                    },
-                    Err(e) => panic!("{}", e),
-                }
-            }
-            Ok(())
-        });*/
+        let garbage_collector = PeriodicExecutor::new(
+            garbage_collection(Arc::clone(&config)),
+            notifier.clone(),
+            "garbage collector",
+            Arc::clone(&clock),
+        );
+
+        let saver = Saver::new(
+            Arc::clone(&c_database),
+            Arc::clone(&config),
+            notifier.clone(),
+            clock,
+        );
+
+        let threads = ServerThreads {
+            database: Arc::clone(&c_database),
+            saver,
+            command_delegator,
+            garbage_collector,
+            command_sub_delegator_databse,
+            command_sub_delegator_server_atributes,
+            clients: drop_shared_clients,
+            log_center,
+        };
+
         // ################## ListenerProcessor ##################
+        let attributes = server_redis.clone();
+        let builder = thread::Builder::new().name("Listener".into());
+        let listener = builder
+            .spawn(move || {
+                ListenerProcessor::incoming(listener, attributes, notifier);
+                threads.finish()
+            })
+            .map_err(|_| {
+                ErrorStruct::from(redis_messages::init_failed(
+                    "Listener",
+                    ErrorSeverity::ShutdownServer,
+                ))
+            })?;
+
+        Ok(ServerHandle {
+            address,
+            database: c_database,
+            server_redis,
+            listener: Some(listener),
+        })
+    }
+}
+
+/// Server running in the background of the current process. It is shut
+/// down when dropped, if it was not before.
+pub struct ServerHandle {
+    address: SocketAddr,
+    database: Arc<Mutex<Database>>,
+    server_redis: ServerRedisAttributes,
+    listener: Option<JoinHandle<Result<(), ErrorStruct>>>,
+}
+
+impl ServerHandle {
+    /// Address where the server listens for clients.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn port(&self) -> u16 {
+        self.address.port()
+    }
+
+    /// The [Database] used by the server, to inspect it or change it without
+    /// a client.
+    pub fn database(&self) -> Arc<Mutex<Database>> {
+        Arc::clone(&self.database)
+    }
+
+    /// Blocks until the server is shut down by a SHUTDOWN command.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The server could not take the last snapshot or close its threads.
+    pub fn wait(mut self) -> Result<(), ErrorStruct> {
+        self.join()
+    }
+
+    /// Stops listening for clients, takes a snapshot of the database and
+    /// waits for every thread of the server to finish, as SHUTDOWN does.
+    ///
+    /// # Error
+    /// Return an [ErrorStruct] if:
+    ///
+    /// * The server could not take the last snapshot or close its threads.
+    pub fn shutdown(mut self) -> Result<(), ErrorStruct> {
+        self.stop();
+        self.join()
+    }
+
+    /// Wakes the listener, which checks the status before accepting the
+    /// next client. It may have stopped already by a SHUTDOWN command.
+    fn stop(&self) {
+        if !self.server_redis.status_listener() {
+            self.server_redis.store(true);
+            let _ = TcpStream::connect(self.address);
+        }
+    }
+
+    fn join(&mut self) -> Result<(), ErrorStruct> {
+        match self.listener.take() {
+            Some(listener) => listener
+                .join()
+                .map_err(|_| ErrorStruct::from(redis_messages::thread_panic("Listener")))?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for ServerHandle {
+    fn drop(&mut self) {
+        if self.listener.is_some() {
+            self.stop();
+            let _ = self.join();
+        }
+    }
+}
 
-        ListenerProcessor::incoming(listener, server_redis, notifier);
-        c_database
+/// Structures with threads, closed in order once the listener stops.
+struct ServerThreads {
+    database: Arc<Mutex<Database>>,
+    saver: Saver,
+    command_delegator: CommandDelegator,
+    garbage_collector: PeriodicExecutor,
+    command_sub_delegator_databse: CommandSubDelegator,
+    command_sub_delegator_server_atributes: CommandSubDelegator,
+    clients: Arc<Mutex<ClientList>>,
+    log_center: LogCenter,
+}
+
+impl ServerThreads {
+    fn finish(mut self) -> Result<(), ErrorStruct> {
+        self.saver.join()?;
+        self.database
             .lock()
             .map_err(|_| {
                 ErrorStruct::from(redis_messages::poisoned_lock(
@@ -147,12 +343,11 @@ impl ServerRedis {
             .take_snapshot()?;
 
         // ################## FINISH SERVER ##################
-        command_delegator.join()?;
-        garbage_collector.join()?;
-        saver.join()?;
-        command_sub_delegator_databse.join()?;
-        command_sub_delegator_server_atributes.join()?;
-        drop_shared_clients
+        self.command_delegator.join()?;
+        self.garbage_collector.join()?;
+        self.command_sub_delegator_databse.join()?;
+        self.command_sub_delegator_server_atributes.join()?;
+        self.clients
             .lock()
             .map_err(|_| {
                 ErrorStruct::from(redis_messages::poisoned_lock(
@@ -161,8 +356,217 @@ impl ServerRedis {
                 ))
             })?
             .join()?;
-        log_center.join()?;
+        self.log_center.join()?;
 
         Ok(())
     }
 }
+
+/// The CLEAN command of the garbage collector, with the sample size and the
+/// period of the `gc-*` parameters of the configuration.
+fn garbage_collection(config: Arc<Mutex<RedisConfig>>) -> Schedule {
+    Box::new(move || {
+        let config = config.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "redis config",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })?;
+        Ok((
+            vec!["clean".to_string(), config.gc_sample_size().to_string()],
+            config.gc_period(),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod test_server {
+    use super::*;
+    use crate::client::{cmd, Connection};
+    use crate::database::TypeSaved;
+    use crate::time_expiration::clock::ManualClock;
+    use std::env;
+    use std::fs;
+
+    fn config(name: &str) -> RedisConfig {
+        let path = |extension: &str| {
+            let file = format!("server_{}_{}.{}", name, std::process::id(), extension);
+            env::temp_dir().join(file).to_string_lossy().into_owned()
+        };
+        let _ = fs::remove_file(path("rdb"));
+        RedisConfig::new(
+            "127.0.0.1".to_string(),
+            "0".to_string(),
+            path("txt"),
+            path("rdb"),
+            0,
+        )
+        .unwrap()
+    }
+
+    fn remove_files(config: (String, String)) {
+        let _ = fs::remove_file(config.0);
+        let _ = fs::remove_file(config.1);
+    }
+
+    #[test]
+    fn test_01_server_listens_in_an_ephemeral_port_and_shares_its_database() {
+        let config = config("handle");
+        let files = (config.log_filename(), config.db_filename());
+        let server = ServerRedis::builder(config).start().unwrap();
+        let mut connection = Connection::connect("127.0.0.1", server.port()).unwrap();
+
+        connection.set("key", "value").unwrap();
+        server
+            .database()
+            .lock()
+            .unwrap()
+            .insert("other".to_string(), TypeSaved::String("inserted".into()));
+        let inserted: String = connection.get("other").unwrap();
+
+        assert_ne!(server.port(), 0);
+        assert_eq!(inserted, "inserted");
+        match server.database().lock().unwrap().get("key") {
+            Some(TypeSaved::String(value)) => assert_eq!(value, b"value"),
+            _ => panic!("key not stored"),
+        }
+        let address = server.address();
+        server.shutdown().unwrap();
+        assert!(Connection::connect("127.0.0.1", address.port()).is_err());
+        remove_files(files);
+    }
+
+    #[test]
+    fn test_02_servers_in_port_zero_do_not_clash() {
+        let (first, second) = (config("first"), config("second"));
+        let files = [
+            (first.log_filename(), first.db_filename()),
+            (second.log_filename(), second.db_filename()),
+        ];
+        let first = ServerRedis::builder(first).start().unwrap();
+        let second = ServerRedis::builder(second).start().unwrap();
+
+        assert_ne!(first.port(), second.port());
+        first.shutdown().unwrap();
+        second.shutdown().unwrap();
+        for pair in files.iter().cloned() {
+            remove_files(pair);
+        }
+    }
+
+    #[test]
+    fn test_03_shutdown_command_finishes_the_server() {
+        let config = config("command");
+        let files = (config.log_filename(), config.db_filename());
+        let server = ServerRedis::builder(config).start().unwrap();
+        let mut connection = Connection::connect("127.0.0.1", server.port()).unwrap();
+
+        cmd("SHUTDOWN").execute(&mut connection).unwrap();
+
+        assert!(server.wait().is_ok());
+        remove_files(files);
+    }
+
+    #[test]
+    fn test_04_keys_expire_by_the_clock_of_the_server() {
+        let config = config("clock");
+        let files = (config.log_filename(), config.db_filename());
+        let clock = Arc::new(ManualClock::default());
+        let server = ServerRedis::builder(config)
+            .clock(clock.clone())
+            .start()
+            .unwrap();
+        let mut connection = Connection::connect("127.0.0.1", server.port()).unwrap();
+
+        connection.set("key", "value").unwrap();
+        connection.expire("key", 100).unwrap();
+        clock.advance_secs(40);
+        assert_eq!(connection.ttl("key").unwrap(), 60);
+        clock.advance_secs(61);
+        let value: Option<String> = connection.get("key").unwrap();
+
+        assert_eq!(value, None);
+        server.shutdown().unwrap();
+        remove_files(files);
+    }
+
+    #[test]
+    fn test_05_a_new_timeout_closes_the_idle_clients_already_connected() {
+        let config = config("timeout");
+        let files = (config.log_filename(), config.db_filename());
+        let clock = Arc::new(ManualClock::default());
+        let server = ServerRedis::builder(config)
+            .clock(clock.clone())
+            .start()
+            .unwrap();
+        // The ping waits until the server has registered the client
+        let connect = || {
+            let mut connection = Connection::connect("127.0.0.1", server.port()).unwrap();
+            connection.ping().unwrap();
+            connection
+        };
+        let set_timeout = |admin: &mut Connection| {
+            cmd("CONFIG")
+                .arg(["SET", "timeout", "10"])
+                .execute(admin)
+                .unwrap();
+        };
+        let mut admin = connect();
+        let mut idle = connect();
+        clock.advance_secs(10);
+        let mut active = connect();
+
+        set_timeout(&mut admin);
+        let mut late = connect();
+
+        assert!(idle.ping().is_err());
+        assert_eq!(active.ping().unwrap(), "PONG");
+        assert_eq!(late.ping().unwrap(), "PONG");
+        clock.advance_secs(10);
+        set_timeout(&mut admin);
+        assert!(active.ping().is_err());
+        assert!(late.ping().is_err());
+        assert_eq!(admin.ping().unwrap(), "PONG");
+        server.shutdown().unwrap();
+        remove_files(files);
+    }
+
+    #[test]
+    fn test_06_a_failed_config_set_does_not_close_the_idle_clients() {
+        let config = config("failed_timeout");
+        let files = (config.log_filename(), config.db_filename());
+        let clock = Arc::new(ManualClock::default());
+        let server = ServerRedis::builder(config)
+            .clock(clock.clone())
+            .start()
+            .unwrap();
+        let mut admin = Connection::connect("127.0.0.1", server.port()).unwrap();
+        let mut idle = Connection::connect("127.0.0.1", server.port()).unwrap();
+        idle.ping().unwrap();
+        clock.advance_secs(10);
+
+        let unknown = cmd("CONFIG")
+            .arg(["SET", "timeout", "1", "bogus", "x"])
+            .execute(&mut admin);
+        let missing_directory = cmd("CONFIG")
+            .arg([
+                "SET",
+                "timeout",
+                "1",
+                "logfile",
+                "/missing_directory/logs.txt",
+            ])
+            .execute(&mut admin);
+        let timeout: Vec<String> = cmd("CONFIG")
+            .arg(["GET", "timeout"])
+            .query(&mut admin)
+            .unwrap();
+
+        assert!(unknown.is_err());
+        assert!(missing_directory.is_err());
+        assert_eq!(timeout, vec!["timeout".to_string(), "0".to_string()]);
+        assert_eq!(idle.ping().unwrap(), "PONG");
+        server.shutdown().unwrap();
+        remove_files(files);
+    }
+}
@@ -1,19 +1,40 @@
-use std::net::TcpStream;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::sync::{Arc, Mutex, MutexGuard};
 
+use crate::commands::server::info_formatter::info_sections::*;
+use crate::commands::server::info_formatter::{info_server_formatter, info_stats_formatter};
+use crate::database::Database;
+use crate::logs::server_stats::ServerStats;
+use crate::logs::slowlog::Slowlog;
 use crate::messages::redis_messages;
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::native_types::ErrorStruct;
 use crate::redis_config::RedisConfig;
+use crate::tcp_protocol::client_atributes::client_fields::ClientFields;
+use crate::tcp_protocol::client_aware::ClientAware;
 use crate::tcp_protocol::client_list::ClientList;
+use crate::tcp_protocol::client_pause::ClientPause;
+use crate::tcp_protocol::client_tracking::ClientTracking;
+use crate::tcp_protocol::command_table::CommandTable;
+use crate::time_expiration::clock::Clock;
 
 #[derive(Clone)]
 pub struct ServerRedisAttributes {
     config: Arc<Mutex<RedisConfig>>,
     status_listener: Arc<AtomicBool>,
     shared_clients: Arc<Mutex<ClientList>>,
+    client_pause: ClientPause,
+    client_tracking: ClientTracking,
+    stats: ServerStats,
+    command_table: Arc<CommandTable>,
+    database: Option<Arc<Mutex<Database>>>,
+    current_client: Option<Arc<Mutex<ClientFields>>>,
+}
+
+impl ClientAware for ServerRedisAttributes {
+    fn set_current_client(&mut self, client: Arc<Mutex<ClientFields>>) {
+        self.current_client = Some(client);
+    }
 }
 
 impl ServerRedisAttributes {
@@ -21,191 +42,174 @@ impl ServerRedisAttributes {
         config: Arc<Mutex<RedisConfig>>,
         status_listener: Arc<AtomicBool>,
         shared_clients: Arc<Mutex<ClientList>>,
+        clock: Arc<dyn Clock>,
     ) -> Self {
+        let client_tracking = ClientTracking::new(Arc::clone(&shared_clients));
+        let slowlog = config
+            .lock()
+            .map(|mut config| {
+                let slowlog =
+                    Slowlog::new(config.slowlog_log_slower_than(), config.slowlog_max_len());
+                config.set_slowlog(slowlog.clone());
+                config.set_client_list(Arc::clone(&shared_clients));
+                slowlog
+            })
+            .unwrap_or_default();
+        let command_table = Arc::new(CommandTable::default());
         ServerRedisAttributes {
             config,
             status_listener,
             shared_clients,
+            client_pause: ClientPause::new(Arc::clone(&command_table)),
+            client_tracking,
+            stats: ServerStats::new(slowlog, clock),
+            command_table,
+            database: None,
+            current_client: None,
         }
     }
 
-    /// Obtains in a [Vec]<[String]> detailed information about the status of the server with its clients, database and pubsub system.
+    /// Obtains in a [Vec]<[String]> detailed information about the status of the server with its clients, memory, persistence, statistics and keyspace, only for the requested sections.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
     /// * any structure to which the information is consulted is poisoned.
-    pub fn info(&mut self) -> Result<Vec<String>, ErrorStruct> {
+    pub fn info(&mut self, sections: &InfoSections) -> Result<Vec<String>, ErrorStruct> {
         let mut info = Vec::new();
+        let snapshot = self.stats.snapshot()?;
 
-        self.config
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "redis config",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .info(&mut info);
+        if sections.contains(SERVER) {
+            info.push(title(SERVER));
+            info.push(info_server_formatter::version());
+            info.push(info_server_formatter::process_id());
+            self.config
+                .lock()
+                .map_err(|_| {
+                    ErrorStruct::from(redis_messages::poisoned_lock(
+                        "redis config",
+                        ErrorSeverity::ShutdownServer,
+                    ))
+                })?
+                .info(&mut info);
+            info.extend(info_stats_formatter::uptime(&snapshot));
+            info.push(String::new());
+        }
 
-        self.shared_clients
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "redis config",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .info(&mut info);
+        if sections.contains(CLIENTS) {
+            info.push(title(CLIENTS));
+            self.shared_clients
+                .lock()
+                .map_err(|_| {
+                    ErrorStruct::from(redis_messages::poisoned_lock(
+                        "client list",
+                        ErrorSeverity::ShutdownServer,
+                    ))
+                })?
+                .info(&mut info);
+            info.push(String::new());
+        }
+
+        let mut push_section = |section: &str, lines: Vec<String>| {
+            if sections.contains(section) {
+                info.push(title(section));
+                info.extend(lines);
+                info.push(String::new());
+            }
+        };
+        push_section(MEMORY, info_stats_formatter::memory(used_memory()));
+        push_section(PERSISTENCE, info_stats_formatter::persistence(&snapshot));
+        push_section(STATS, info_stats_formatter::stats(&snapshot));
+        push_section(REPLICATION, info_stats_formatter::replication());
+        push_section(
+            COMMANDSTATS,
+            snapshot
+                .commands
+                .iter()
+                .map(|(name, stat)| info_stats_formatter::command_stat(name, stat))
+                .collect(),
+        );
+
+        if let Some(database) = &self.database {
+            info.extend(
+                database
+                    .lock()
+                    .map_err(|_| {
+                        ErrorStruct::from(redis_messages::poisoned_lock(
+                            "database",
+                            ErrorSeverity::ShutdownServer,
+                        ))
+                    })?
+                    .info(sections)?,
+            );
+        }
 
         Ok(info)
     }
 
+    /// Shares the [Database] with the server, so INFO can report its keyspace.
+    pub fn set_database(&mut self, database: Arc<Mutex<Database>>) {
+        self.database = Some(database);
+    }
+
     /// Returns a clone of [Arc]<[Mutex]<[ClientList](crate::tcp_protocol::client_list::ClientList)>> to be shared.
     pub fn get_client_list(&self) -> Arc<Mutex<ClientList>> {
         Arc::clone(&self.shared_clients)
     }
 
-    /// Changes the state of the client with a [bool].
-    ///
-    /// * If [true]: stop listening to new clients with [TcpListener](std::net::TcpListener).
-    /// * If [false]: keep listening to clients with [TcpListener](std::net::TcpListener).
-    pub fn store(&self, val: bool) {
-        self.status_listener.store(val, Ordering::SeqCst);
+    /// Returns a clone of the [ClientPause] shared by all the clients of the server.
+    pub fn get_client_pause(&self) -> ClientPause {
+        self.client_pause.clone()
     }
 
-    /// Change verbose level to display more or less debug information.
-    ///
-    /// # Error
-    /// Return an [ErrorStruct] if:
-    ///
-    /// * the structure that stores the name is poisoned.
-    pub fn change_verbose(&self, new: usize) -> Result<(), ErrorStruct> {
-        self.config
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "Server Redis Atributes",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .change_verbose(new);
-        Ok(())
+    /// Returns a clone of the [ClientTracking] shared with the [Database](crate::database::Database).
+    pub fn get_client_tracking(&self) -> ClientTracking {
+        self.client_tracking.clone()
     }
 
-    /// Change the name of the log file used to store debug information.
-    //
-    /// # Error
-    /// Return an [ErrorStruct] if:
-    ///
-    /// * the structure that stores the name is poisoned.
-    pub fn change_logfilename(&self, new_file_name: String) -> Result<(), ErrorStruct> {
-        self.config
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "Server Redis Atributes",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .change_log_file(new_file_name)?;
-        Ok(())
+    /// Returns a clone of the [ServerStats] shared with the command delegators and the [Database](crate::database::Database).
+    pub fn get_stats(&self) -> ServerStats {
+        self.stats.clone()
     }
 
-    /// Change the name of the dump file used to store db information.
-    //
-    /// # Error
-    /// Return an [ErrorStruct] if:
-    ///
-    /// * the structure that stores the name is poisoned.
-    pub fn change_dump_filename(&self, new_file_name: String) -> Result<(), ErrorStruct> {
-        self.config
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "Server Redis Atributes",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .change_dump_file(new_file_name)?;
-        Ok(())
+    /// Returns the [CommandTable] shared with the command delegator.
+    pub fn get_command_table(&self) -> Arc<CommandTable> {
+        Arc::clone(&self.command_table)
     }
 
-    /// Changes the time it takes to disconnect a client that is not interacting with the server.
-    ///
-    /// # Error
-    /// Return an [ErrorStruct] if:
-    ///
-    /// * the structure that stores the timeout is poisoned.    
-    pub fn set_timeout(&self, client: &TcpStream) -> Result<(), ErrorStruct> {
-        let time = self
-            .config
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "Server Redis Atributes",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .timeout();
-        if time.gt(&0) {
-            client
-                .set_read_timeout(Some(Duration::new(time, 0)))
-                .map_err(|_| {
-                    ErrorStruct::from(redis_messages::init_failed(
-                        "Failed timeout",
-                        ErrorSeverity::ShutdownServer,
-                    ))
-                })?;
-        }
-        Ok(())
+    /// Returns a clone of the [Slowlog] where the command sub delegators register the slow executions.
+    pub fn get_slowlog(&self) -> Slowlog {
+        self.stats.slowlog()
     }
 
-    /// Gets a [String] with the address to connect as a client to the server.
+    /// Returns the fields of the client whose command is being executed.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * the structure that stores the address is poisoned.
-    pub fn get_addr(&self) -> Result<String, ErrorStruct> {
-        Ok(self
-            .config
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "Server Redis Atributes",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .get_addr())
+    /// * No command is being executed on behalf of a client.
+    pub fn get_current_client(&self) -> Result<Arc<Mutex<ClientFields>>, ErrorStruct> {
+        self.current_client
+            .as_ref()
+            .map(Arc::clone)
+            .ok_or_else(|| ErrorStruct::from(redis_messages::normal_error()))
     }
 
-    /// Gets a [String] with the port to connect as a client to the server.
-    ///
-    /// # Error
-    /// Return an [ErrorStruct] if:
+    /// Changes the state of the client with a [bool].
     ///
-    /// * the structure that stores the port is poisoned.
-    pub fn get_port(&self) -> Result<String, ErrorStruct> {
-        Ok(self
-            .config
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "Server Redis Atributes",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .port())
+    /// * If [true]: stop listening to new clients with [TcpListener](std::net::TcpListener).
+    /// * If [false]: keep listening to clients with [TcpListener](std::net::TcpListener).
+    pub fn store(&self, val: bool) {
+        self.status_listener.store(val, Ordering::SeqCst);
     }
 
-    /// Gets a [String] with the verbosity level to display debug information.
+    /// Gets a [String] with the address to connect as a client to the server.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * the structure that stores the verbose level is poisoned.
-    pub fn get_verbose(&self) -> Result<String, ErrorStruct> {
+    /// * the structure that stores the address is poisoned.
+    pub fn get_addr(&self) -> Result<String, ErrorStruct> {
         Ok(self
             .config
             .lock()
@@ -215,66 +219,41 @@ impl ServerRedisAttributes {
                     ErrorSeverity::ShutdownServer,
                 ))
             })?
-            .verbose()
-            .to_string())
+            .get_addr())
     }
 
-    /// Gets a [String] with timeout allowed for clients.
+    /// Gets the names and values of the parameters of the configuration
+    /// matching any of the glob-style patterns.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * the structure that stores the timeout is poisoned.
-    pub fn get_timeout(&self) -> Result<String, ErrorStruct> {
-        Ok(self
-            .config
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "Server Redis Atributes",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .timeout()
-            .to_string())
+    /// * A pattern is not valid.
+    /// * the structure that stores the configuration is poisoned.
+    pub fn get_config_parameters(&self, patterns: &[String]) -> Result<Vec<String>, ErrorStruct> {
+        self.lock_config()?.get_parameters(patterns)
     }
 
-    /// Gets a [String] with the name of the file in charge of saving the debug information.
+    /// Changes every parameter of the configuration to its value, or none of them if one fails.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * the structure that stores the name is poisoned.
-    pub fn get_logfile_name(&self) -> Result<String, ErrorStruct> {
-        Ok(self
-            .config
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "Server Redis Atributes",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .log_filename())
+    /// * A parameter does not exist, can not be changed or its value is not valid.
+    /// * the structure that stores the configuration is poisoned.
+    pub fn set_config_parameters(&self, pairs: &[(String, String)]) -> Result<(), ErrorStruct> {
+        self.lock_config()?.set_parameters(pairs)
     }
 
-    /// Gets a [String] with the name of the file in charge of the persistence of the [Database](crate::database::Database).
+    /// Writes the current configuration in the file the server was started with.
     ///
     /// # Error
     /// Return an [ErrorStruct] if:
     ///
-    /// * the structure that stores the name is poisoned.
-    pub fn get_dbfile_name(&self) -> Result<String, ErrorStruct> {
-        Ok(self
-            .config
-            .lock()
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::poisoned_lock(
-                    "Server Redis Atributes",
-                    ErrorSeverity::ShutdownServer,
-                ))
-            })?
-            .db_filename())
+    /// * The server was started without a configuration file, or it could not be written.
+    /// * the structure that stores the configuration is poisoned.
+    pub fn rewrite_config(&self) -> Result<(), ErrorStruct> {
+        self.lock_config()?.rewrite()
     }
 
     /// Returns the current state of the listener processor with [bool].
@@ -285,4 +264,28 @@ impl ServerRedisAttributes {
         self.status_listener
             .load(std::sync::atomic::Ordering::SeqCst)
     }
+
+    fn lock_config(&self) -> Result<MutexGuard<'_, RedisConfig>, ErrorStruct> {
+        self.config.lock().map_err(|_| {
+            ErrorStruct::from(redis_messages::poisoned_lock(
+                "Server Redis Atributes",
+                ErrorSeverity::ShutdownServer,
+            ))
+        })
+    }
+}
+
+/// Resident memory of the process in bytes, read from */proc/self/statm*.
+/// Returns 0 where that file is not available.
+fn used_memory() -> u64 {
+    std::fs::read_to_string("/proc/self/statm")
+        .ok()
+        .and_then(|statm| {
+            statm
+                .split_whitespace()
+                .nth(1)
+                .and_then(|pages| pages.parse::<u64>().ok())
+        })
+        .map(|pages| pages * 4096)
+        .unwrap_or(0)
 }
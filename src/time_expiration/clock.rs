@@ -1,135 +1,187 @@
+use std::fmt;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, TryRecvError};
+use std::sync::{Condvar, Mutex};
 use std::thread;
-use std::time::SystemTime;
-use std::time::Duration;
-
-use std::sync::Arc;
-use std::sync::Mutex;
-
-/// CronoCounter es un cronometro/contador.
-/// La idea es que simule un cronometro, pero la actualizacion se tiene que hacer
-/// manualmente. Work es un booleano medio agarrado de los pelos,
-/// que se utiliza en Clock
-
-#[derive(Clone)]
-struct CronoCounter {
-
-    now: SystemTime,
-    work: bool,
-}
-
-impl CronoCounter {
-
-    pub fn new() -> CronoCounter{
-
-        CronoCounter {
-            now: SystemTime::now(),
-            work: true,
-        }
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Longest real pause of a sleep in a [ManualClock], so the loops sleeping on
+/// it can notice they were stopped although the clock is not advanced.
+const MANUAL_SLEEP_POLL: Duration = Duration::from_millis(10);
+
+/// Source of the current time for expiration, TTL reporting, garbage
+/// collection and scheduled saves. The server uses a [SystemClock], and the
+/// tests a [ManualClock] to move the time forward without sleeping.
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+
+    /// Blocks the thread until the duration passes. It may return before,
+    /// so loops compare [now](Clock::now) against their deadline.
+    fn sleep(&self, duration: Duration);
+
+    /// Sleeps as [sleep](Clock::sleep), but returns as soon as a stop is
+    /// received or its sender is dropped.
+    ///
+    /// # Return value
+    /// True if the loop sleeping must stop.
+    fn sleep_unless_stopped(&self, duration: Duration, stop: &Receiver<()>) -> bool {
+        self.sleep(duration);
+        !matches!(stop.try_recv(), Err(TryRecvError::Empty))
+    }
 
+    /// Milliseconds since [UNIX_EPOCH].
+    fn unix_millis(&self) -> u64 {
+        self.now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_millis() as u64)
+            .unwrap_or(0)
     }
 
-    pub fn update(&mut self) -> bool{
+    /// Seconds since [UNIX_EPOCH].
+    fn unix_secs(&self) -> u64 {
+        self.unix_millis() / 1000
+    }
+}
 
-        self.now = self.now.checked_add(Duration::new(1, 0)).unwrap();
-        self.work
+/// The time of the operating system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
 
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
     }
 
-    pub fn get(&self) -> SystemTime {
-        self.now.clone()
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
     }
 
-    pub fn stop(&mut self) {
-        self.work = false;
+    fn sleep_unless_stopped(&self, duration: Duration, stop: &Receiver<()>) -> bool {
+        !matches!(stop.recv_timeout(duration), Err(RecvTimeoutError::Timeout))
     }
-
 }
 
-/// Clock es el cronometro en si.
-/// Posee un timer: un proceso paralelo que loopea indefinidamente
-/// (bueno en realidad si esta definido cuando detenerse).
-/// El loop duerme un segundo, y actualiza el cronocounter.
-/// Si en algun momento le digo stop() al cronocounter, timer
-/// va a detenerse y cerrara su proceso (como esta implementado
-/// en Drop).
-
-pub struct Clock {
-    crono: Arc<Mutex<CronoCounter>>,
-    timer: Option<thread::JoinHandle<()>>,
+/// Clock which only moves when it is advanced.
+pub struct ManualClock {
+    now: Mutex<SystemTime>,
+    advanced: Condvar,
 }
 
-impl Clock {
-
-    pub fn new() -> Clock {
-
-        let cronocounter = Arc::new(Mutex::new(CronoCounter {
-            now: SystemTime::now(),
-            work: true,
-        }));
-
-        let cronocounter_clone = Arc::clone(&cronocounter);
-        let timer = thread::spawn(move || {
-            
-            loop {
-                if !cronocounter_clone.lock().unwrap().update() {
-                    println!("Shutting down clock");
-                    break;
-                }
-                thread::sleep(Duration::new(1, 0));
-            }
-
-        });
-
-        Clock {
-            crono: cronocounter,
-            timer: Some(timer),
+impl ManualClock {
+    /// Creates a clock stopped at the given time.
+    pub fn new(start: SystemTime) -> ManualClock {
+        ManualClock {
+            now: Mutex::new(start),
+            advanced: Condvar::new(),
         }
-
     }
 
-    fn update(&mut self){
-
-        self.crono.lock().unwrap().update();
-
+    /// Creates a clock stopped at the given milliseconds since [UNIX_EPOCH].
+    pub fn from_unix_millis(millis: u64) -> ManualClock {
+        ManualClock::new(UNIX_EPOCH + Duration::from_millis(millis))
     }
 
-    pub fn get_now(&self) -> SystemTime {
-
-        self.crono.lock().unwrap().get()
+    /// Moves the clock forward, waking the threads sleeping on it.
+    pub fn advance(&self, duration: Duration) {
+        if let Ok(mut now) = self.now.lock() {
+            *now += duration;
+        }
+        self.advanced.notify_all();
+    }
 
+    /// Moves the clock forward the given seconds.
+    pub fn advance_secs(&self, seconds: u64) {
+        self.advance(Duration::from_secs(seconds));
     }
+}
 
+impl Default for ManualClock {
+    /// Starts at the current time of the system.
+    fn default() -> Self {
+        ManualClock::new(SystemTime::now())
+    }
 }
 
-impl Drop for Clock {
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        self.now.lock().map(|now| *now).unwrap_or(UNIX_EPOCH)
+    }
 
-    fn drop(&mut self) {
-        self.crono.lock().unwrap().stop();
-        if let Some(thread) = self.timer.take() {
-            thread.join().unwrap();
+    /// Returns once the clock is advanced past the duration, or after a short
+    /// real pause.
+    fn sleep(&self, duration: Duration) {
+        if let Ok(now) = self.now.lock() {
+            let deadline = *now + duration;
+            let _ = self
+                .advanced
+                .wait_timeout_while(now, MANUAL_SLEEP_POLL, |now| *now < deadline);
         }
-        println!("Clock was shutted down");
     }
+}
 
+impl fmt::Debug for ManualClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ManualClock({:?})", self.now())
+    }
 }
 
 #[cfg(test)]
 mod test_clock {
-
     use super::*;
+    use std::sync::{mpsc, Arc};
+    use std::time::Instant;
+
+    #[test]
+    fn test_01_manual_clock_only_moves_when_advanced() {
+        let clock = ManualClock::from_unix_millis(1_500);
+
+        assert_eq!(clock.unix_millis(), 1_500);
+        clock.advance_secs(2);
+        assert_eq!(clock.unix_millis(), 3_500);
+        assert_eq!(clock.unix_secs(), 3);
+    }
 
     #[test]
-    fn test_01_waiting_five_seconds() {
+    fn test_02_manual_sleep_does_not_wait_the_duration() {
+        let clock = ManualClock::default();
+        let start = Instant::now();
 
-        let mut clock = Clock::new();
-        let time1 = clock.get_now();
-        thread::sleep(Duration::new(5, 0));
-        let time2 = clock.get_now();
-        let diff = time2.duration_since(time1).unwrap();
-        assert!(4.99 < diff.as_secs_f32());
-        assert!(diff.as_secs_f32() < 5.01);
+        clock.sleep(Duration::from_secs(60));
 
+        assert!(start.elapsed() < Duration::from_secs(1));
     }
 
-}
+    #[test]
+    fn test_03_manual_sleep_wakes_when_advanced() {
+        let clock = Arc::new(ManualClock::from_unix_millis(0));
+        let sleeping = {
+            let clock = Arc::clone(&clock);
+            thread::spawn(move || {
+                while clock.unix_secs() < 5 {
+                    clock.sleep(Duration::from_secs(5));
+                }
+            })
+        };
+
+        clock.advance_secs(5);
 
+        sleeping.join().unwrap();
+        assert_eq!(clock.unix_secs(), 5);
+    }
+
+    #[test]
+    fn test_04_sleeps_end_when_stopped() {
+        let (stop, stopped) = mpsc::channel();
+        let system_clock = SystemClock;
+        let manual_clock = ManualClock::default();
+        let start = Instant::now();
+
+        assert!(!manual_clock.sleep_unless_stopped(Duration::from_secs(60), &stopped));
+        stop.send(()).unwrap();
+        assert!(manual_clock.sleep_unless_stopped(Duration::from_secs(60), &stopped));
+        drop(stop);
+        assert!(system_clock.sleep_unless_stopped(Duration::from_secs(60), &stopped));
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
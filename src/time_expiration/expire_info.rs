@@ -2,44 +2,62 @@ use crate::messages::redis_messages;
 use crate::native_types::error::ErrorStruct;
 use crate::native_types::error_severity::ErrorSeverity;
 use crate::{communication::log_messages::LogMessage, tcp_protocol::notifier::Notifier};
+use rand::Rng;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
+/// Initial value of the access frequency counter, as Redis, so that new keys
+/// are not the first ones considered rarely used.
+const LFU_INIT_VAL: u8 = 5;
+/// How fast the access frequency counter grows: the higher, the more accesses
+/// are needed to increment it.
+const LFU_LOG_FACTOR: f64 = 10.0;
+/// Seconds without accesses needed to decrement the access frequency counter.
+const LFU_DECAY_TIME: u64 = 60;
+
 #[derive(Clone)]
 /// This structure contains information about the
 /// time to live of a key. It has the instant of
 /// the last access to the key, and the value of
 /// the timeout of the key (if there is one).
+/// It also keeps the instant the key was last read
+/// or written by a command and a logarithmic counter
+/// of its access frequency, for OBJECT.
+/// The current time is received from the [Clock](crate::time_expiration::clock::Clock)
+/// of the [Database](crate::database::Database).
 pub struct ExpireInfo {
     last_touch: SystemTime,
     timeout: Option<Duration>,
-}
-
-impl Default for ExpireInfo {
-    fn default() -> Self {
-        Self::new()
-    }
+    last_access: SystemTime,
+    frequency: u8,
 }
 
 impl ExpireInfo {
     /// Creates the structure
-    pub fn new() -> ExpireInfo {
+    pub fn new(now: SystemTime) -> ExpireInfo {
         ExpireInfo {
-            last_touch: SystemTime::now(),
+            last_touch: now,
             timeout: None,
+            last_access: now,
+            frequency: LFU_INIT_VAL,
         }
     }
 
     /// Evaluates if the timeout has ended.
     #[allow(clippy::branches_sharing_code)]
-    pub fn is_expired(&mut self, notifier: Option<Arc<Mutex<Notifier>>>, key_name: &str) -> bool {
+    pub fn is_expired(
+        &mut self,
+        now: SystemTime,
+        notifier: Option<Arc<Mutex<Notifier>>>,
+        key_name: &str,
+    ) -> bool {
         if self.timeout.is_some() {
-            let _ = self.update(notifier, key_name);
+            let _ = self.update(now, notifier, key_name);
             !matches!(self.timeout, Some(_))
         } else {
-            let _ = self.update(notifier, key_name);
+            let _ = self.update(now, notifier, key_name);
             false
         }
     }
@@ -48,11 +66,12 @@ impl ExpireInfo {
     /// the remaining time to live.
     pub fn update(
         &mut self,
+        now: SystemTime,
         wrapped_notifier: Option<Arc<Mutex<Notifier>>>,
         key_name: &str,
     ) -> Result<(), ErrorStruct> {
         let previous_touch = self.last_touch;
-        self.last_touch = SystemTime::now();
+        self.last_touch = now;
         if let Some(ttl) = self.timeout {
             let difference = duration_since(&self.last_touch, previous_touch)?;
             self.timeout = ttl.checked_sub(difference);
@@ -78,17 +97,79 @@ impl ExpireInfo {
         self.timeout.map(|ttl| ttl.as_secs())
     }
 
+    /// Returns the timeout as milliseconds.
+    pub fn ttl_millis(&self) -> Option<u64> {
+        self.timeout.map(|ttl| ttl.as_millis() as u64)
+    }
+
+    /// Registers an access to the key by a command, updating its
+    /// access frequency counter the way Redis LFU policy does.
+    pub fn register_access(&mut self, now: SystemTime) {
+        let frequency = self.frequency(now);
+        let base = frequency.saturating_sub(LFU_INIT_VAL) as f64;
+        let probability = 1.0 / (base * LFU_LOG_FACTOR + 1.0);
+        self.frequency = if frequency < u8::MAX && rand::thread_rng().gen::<f64>() < probability {
+            frequency + 1
+        } else {
+            frequency
+        };
+        self.last_access = now;
+    }
+
+    /// Returns the seconds since the key was last accessed by a command.
+    pub fn idle_time(&self, now: SystemTime) -> u64 {
+        now.duration_since(self.last_access)
+            .map(|idle| idle.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// Returns the access frequency counter, decremented by the
+    /// periods of time the key has not been accessed.
+    pub fn frequency(&self, now: SystemTime) -> u8 {
+        let periods = self.idle_time(now) / LFU_DECAY_TIME;
+        self.frequency
+            .saturating_sub(periods.min(u8::MAX as u64) as u8)
+    }
+
+    /// Sets the seconds since the key was last accessed, for RESTORE.
+    pub fn set_idle_time(&mut self, now: SystemTime, seconds: u64) {
+        self.last_access = now
+            .checked_sub(Duration::from_secs(seconds))
+            .unwrap_or(UNIX_EPOCH);
+    }
+
+    /// Sets the access frequency counter, for RESTORE.
+    pub fn set_frequency(&mut self, now: SystemTime, frequency: u8) {
+        self.last_access = now;
+        self.frequency = frequency;
+    }
+
     /// Sets a new timeout for the structure from seconds.
-    pub fn set_timeout(&mut self, duration: u64) -> Result<(), ErrorStruct> {
-        self.last_touch = SystemTime::now();
+    pub fn set_timeout(&mut self, now: SystemTime, duration: u64) -> Result<(), ErrorStruct> {
+        self.last_touch = now;
         self.timeout = Some(Duration::new(duration, 0));
         Ok(())
     }
 
+    /// Sets a new timeout for the structure from milliseconds.
+    pub fn set_timeout_millis(
+        &mut self,
+        now: SystemTime,
+        duration: u64,
+    ) -> Result<(), ErrorStruct> {
+        self.last_touch = now;
+        self.timeout = Some(Duration::from_millis(duration));
+        Ok(())
+    }
+
     /// Sets a new timeout for the structure from a time coded
     /// in Unix timestamp.
-    pub fn set_timeout_unix_timestamp(&mut self, duration: u64) -> Result<(), ErrorStruct> {
-        self.last_touch = SystemTime::now();
+    pub fn set_timeout_unix_timestamp(
+        &mut self,
+        now: SystemTime,
+        duration: u64,
+    ) -> Result<(), ErrorStruct> {
+        self.last_touch = now;
 
         duration_since(&self.last_touch, UNIX_EPOCH).map(|duration_since_epoch| {
             self.timeout = Some(Duration::new(duration, 0) - duration_since_epoch);
@@ -110,45 +191,70 @@ fn duration_since(time: &SystemTime, previous_time: SystemTime) -> Result<Durati
 mod test_expire_info {
 
     use super::*;
-    use std::thread::sleep;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
 
     #[test]
-    #[ignore = "Long test"]
-    fn long_test_01_new_expireinfo_does_not_have_timeout() -> Result<(), ErrorStruct> {
-        let mut info = ExpireInfo::new();
-        assert!(!info.is_expired(None, "key"));
+    fn test_01_new_expireinfo_does_not_have_timeout() -> Result<(), ErrorStruct> {
+        let mut info = ExpireInfo::new(at(1000));
+        assert!(!info.is_expired(at(5000), None, "key"));
         assert_eq!(info.ttl(), None);
         drop(info);
         Ok(())
     }
 
     #[test]
-    #[ignore = "Long test"]
-    fn long_test_02_setting_ten_seconds_of_timeout() -> Result<(), ErrorStruct> {
-        let mut info = ExpireInfo::new();
-        info.set_timeout(10)
+    fn test_02_setting_ten_seconds_of_timeout() -> Result<(), ErrorStruct> {
+        let mut info = ExpireInfo::new(at(1000));
+        info.set_timeout(at(1000), 10)
             .map_err(|_| ErrorStruct::new("ERR_TEST".to_string(), "FAIL TEST".to_string()))?;
         assert_eq!(info.ttl(), Some(10));
-        sleep(Duration::new(5, 0));
-        assert!(!info.is_expired(None, "key"));
+        assert!(!info.is_expired(at(1005) + Duration::from_millis(500), None, "key"));
         assert_eq!(info.ttl(), Some(4));
-        sleep(Duration::new(6, 0));
-        assert!(info.is_expired(None, "key"));
+        assert!(info.is_expired(at(1011), None, "key"));
         assert_eq!(info.ttl(), None);
         Ok(())
     }
 
     #[test]
-    #[ignore = "Long test"]
-    fn long_test_03_setting_five_seconds_of_timeout_with_unix_timestamp() {
-        let mut info = ExpireInfo::new();
-        match SystemTime::now().duration_since(UNIX_EPOCH - Duration::new(5, 0)) {
-            Ok(dura) => info.set_timeout_unix_timestamp(dura.as_secs()).unwrap(),
-            Err(_) => {}
-        }
-        assert_eq!(info.ttl(), Some(4));
-        sleep(Duration::new(2, 0));
-        assert!(info.update(None, "key").is_ok());
+    fn test_03_setting_five_seconds_of_timeout_with_unix_timestamp() {
+        let mut info = ExpireInfo::new(at(1000));
+        info.set_timeout_unix_timestamp(at(1000), 1005).unwrap();
+        assert_eq!(info.ttl(), Some(5));
+        assert!(info.update(at(1003), None, "key").is_ok());
         assert_eq!(info.ttl(), Some(2));
     }
+
+    #[test]
+    fn test_04_new_expireinfo_has_the_initial_access_frequency() {
+        let mut info = ExpireInfo::new(at(1000));
+        assert_eq!(info.frequency(at(1000)), LFU_INIT_VAL);
+        assert_eq!(info.idle_time(at(1000)), 0);
+        info.register_access(at(1000));
+        assert!(info.frequency(at(1000)) >= LFU_INIT_VAL);
+    }
+
+    #[test]
+    fn test_05_access_frequency_grows_logarithmically() {
+        let mut info = ExpireInfo::new(at(1000));
+        for _ in 0..1000 {
+            info.register_access(at(1000));
+        }
+        assert!(info.frequency(at(1000)) > LFU_INIT_VAL);
+        assert!(info.frequency(at(1000)) < 40);
+    }
+
+    #[test]
+    fn test_06_access_frequency_decays_while_idle() {
+        let mut info = ExpireInfo::new(at(1000));
+        info.set_frequency(at(1000), 10);
+
+        assert_eq!(
+            info.idle_time(at(1000 + 3 * LFU_DECAY_TIME)),
+            3 * LFU_DECAY_TIME
+        );
+        assert_eq!(info.frequency(at(1000 + 3 * LFU_DECAY_TIME)), 7);
+    }
 }
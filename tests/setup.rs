@@ -1,85 +1,66 @@
 use std::{
-    thread::{sleep, spawn, JoinHandle},
-    time::Duration,
+    env, fs, process,
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
 use redis_rust::{
-    messages::redis_messages, native_types::ErrorStruct, redis_config::RedisConfig,
-    tcp_protocol::server::ServerRedis,
+    client::Connection,
+    native_types::ErrorStruct,
+    redis_config::RedisConfig,
+    tcp_protocol::server::{ServerHandle, ServerRedis},
 };
 
+/// Número de servidores levantados por este proceso, para que cada uno use sus propios archivos.
+static SERVERS: AtomicUsize = AtomicUsize::new(0);
+
 /// Provee un servidor Redis (desarrollado por Rust-eze Team ©) para los tests de integración.
 ///
-/// Además incluye el cliente del crate de Redis que se necesita para ejecutar los comandos del crate de Redis.
+/// Además incluye la dirección del servidor para conectar los clientes del módulo `client` de la biblioteca.
 pub struct ServerTest {
-    client: redis::Client,
-    server_thread: Option<JoinHandle<Result<(), ErrorStruct>>>,
+    host: String,
+    port: u16,
+    files: Vec<String>,
+    server: Option<ServerHandle>,
 }
 
 impl ServerTest {
-    /// Levanta un servidor Redis (desarrollado por Rust-eze Team ©) para los tests de integración en un thread aparte.
-    /// Conecta un cliente del crate de Redis que se necesita para ejecutar los comandos del crate de Redis.
+    /// Levanta un servidor Redis (desarrollado por Rust-eze Team ©) para los tests de integración en threads aparte.
+    /// Cada servidor escucha en un puerto libre elegido por el sistema operativo, con sus propios archivos
+    /// de logs y de persistencia, así los tests pueden correr en paralelo.
     /// Siempre se inicia el server con la database vacía, limpia.
     pub fn start() -> Result<Self, ErrorStruct> {
-        let server_thread: JoinHandle<Result<(), ErrorStruct>> = spawn(move || {
-            ServerRedis::start(vec![])?;
-            Ok(())
-        });
+        let name = format!(
+            "redis_rust_test_{}_{}",
+            process::id(),
+            SERVERS.fetch_add(1, Ordering::SeqCst)
+        );
+        let path = |extension: &str| {
+            let file = env::temp_dir().join(format!("{}.{}", name, extension));
+            file.to_string_lossy().into_owned()
+        };
+        let files = vec![path("txt"), path("rdb")];
+        let _ = fs::remove_file(&files[1]);
 
-        let client_redis = redis::Client::open(
-            "redis://".to_owned()
-                + &RedisConfig::default().ip()
-                + ":"
-                + &RedisConfig::default().port()
-                + "/",
-        )
-        .map_err(|_| {
-            ErrorStruct::new(
-                "ERR_CLIENT".to_string(),
-                "Failed conection of client.".to_string(),
-            )
-        })?;
+        let config = RedisConfig::new(
+            "127.0.0.1".to_string(),
+            "0".to_string(),
+            files[0].clone(),
+            files[1].clone(),
+            0,
+        )?;
+        let server = ServerRedis::builder(config).start()?;
 
-        let mut connection;
-
-        let millisecond = Duration::from_millis(10);
-        let mut retries = 0;
-        loop {
-            match client_redis.get_connection() {
-                Err(err) => {
-                    if err.is_connection_refusal() {
-                        sleep(millisecond);
-                        retries += 1;
-                        if retries > 100000 {
-                            return Err(ErrorStruct::new(
-                                "ERR_CLIENT".to_string(),
-                                format!("Tried to connect too many times, last error: {}", err),
-                            ));
-                        }
-                    } else {
-                        return Err(ErrorStruct::new(
-                            "ERR_CLIENT".to_string(),
-                            format!("Could not connect: {}", err),
-                        ));
-                    }
-                }
-                Ok(x) => {
-                    connection = x;
-                    break;
-                }
-            }
-        }
-
-        redis::cmd("flushdb").execute(&mut connection);
         Ok(Self {
-            client: client_redis,
-            server_thread: Some(server_thread),
+            host: server.address().ip().to_string(),
+            port: server.port(),
+            files,
+            server: Some(server),
         })
     }
 
-    /// Retorna la conexión del cliente con el crate de Redis para ejecutar los comandos del crate de Redis.
-    pub fn get_connection_client(&self) -> Result<redis::Connection, ErrorStruct> {
-        self.client.get_connection().map_err(|_| {
+    /// Retorna una nueva conexión de cliente con el servidor para ejecutar los comandos.
+    pub fn get_connection_client(&self) -> Result<Connection, ErrorStruct> {
+        Connection::connect(&self.host, self.port).map_err(|_| {
             ErrorStruct::new(
                 "ERR_CLIENT".to_string(),
                 "Failed conection of client.".to_string(),
@@ -87,19 +68,14 @@ impl ServerTest {
         })
     }
 
-    /// Apaga el servidor con el comando "shutdown", previamente realiza una limpieza de la database.
-    /// Libera la memoria del thread usado para el servidor de los tests de integración.
+    /// Apaga el servidor y espera a que terminen todos sus threads.
+    /// Borra los archivos de logs y de persistencia que usó.
     pub fn shutdown(&mut self) -> Result<(), ErrorStruct> {
-        redis::cmd("flushdb").execute(&mut self.client);
-        redis::cmd("shutdown")
-            .query(&mut self.client)
-            .map_err(|_| {
-                ErrorStruct::from(redis_messages::thread_panic("server for test integration"))
-            })?;
-        if let Some(handle) = self.server_thread.take() {
-            handle.join().map(|_| ()).map_err(|_| {
-                ErrorStruct::from(redis_messages::thread_panic("server for test integration"))
-            })?;
+        if let Some(server) = self.server.take() {
+            server.shutdown()?;
+        }
+        for file in self.files.iter() {
+            let _ = fs::remove_file(file);
         }
         Ok(())
     }